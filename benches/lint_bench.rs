@@ -420,6 +420,49 @@ fn bench_rule_md049_md050(c: &mut Criterion) {
     });
 }
 
+/// Heading- and link-heavy document (~5k lines) for the rules that share
+/// heading IDs and reference definitions (MD042, MD045, MD051-MD053)
+fn bench_link_rules(c: &mut Criterion) {
+    let mut content = String::new();
+    content.push_str("# Title\n\n");
+    for i in 0..1000 {
+        content.push_str(&format!("## Section {}\n\n", i));
+        content.push_str(&format!(
+            "See [section](#section-{}), [docs][ref-{}], and ![logo][img-{}].\n\n",
+            (i + 1) % 1000,
+            i,
+            i
+        ));
+    }
+    for i in 0..1000 {
+        content.push_str(&format!("[ref-{}]: https://example.com/{}\n", i, i));
+        content.push_str(&format!("[img-{}]: logo-{}.png\n", i, i));
+    }
+
+    let mut rules = HashMap::new();
+    for rule in ["MD042", "MD045", "MD051", "MD052", "MD053"] {
+        rules.insert(rule.to_string(), RuleConfig::Enabled(true));
+    }
+    let config = Config {
+        default: Some(false),
+        rules,
+        ..Default::default()
+    };
+
+    c.bench_function("lint_link_rules_5k_lines", |b| {
+        b.iter(|| {
+            let options = LintOptions {
+                strings: vec![("bench.md".to_string(), content.clone())]
+                    .into_iter()
+                    .collect(),
+                config: Some(config.clone()),
+                ..Default::default()
+            };
+            black_box(lint_sync(&options).unwrap())
+        })
+    });
+}
+
 fn bench_inline_config(c: &mut Criterion) {
     let mut content_with_directives = String::new();
     content_with_directives.push_str("# Title\n\n");
@@ -479,5 +522,6 @@ criterion_group!(
     bench_rule_md013,
     bench_rule_md049_md050,
     bench_inline_config,
+    bench_link_rules,
 );
 criterion_main!(benches);
@@ -31,11 +31,17 @@ export function createClient(
 
   const config = vscode.workspace.getConfiguration("mkdlint");
   const preset = config.get<string | null>("preset") ?? null;
+  const fixOnSave = config.get<boolean>("fixOnSave") ?? false;
+  const scanConcurrency = config.get<number | null>("scanConcurrency") ?? null;
 
   const clientOptions: LanguageClientOptions = {
     documentSelector: [
       { scheme: "file", language: "markdown" },
       { scheme: "untitled", language: "markdown" },
+      {
+        scheme: "file",
+        pattern: "**/.markdownlint{.json,.jsonc,.yaml,.yml,.toml,rc}",
+      },
     ],
     synchronize: {
       fileEvents: vscode.workspace.createFileSystemWatcher(
@@ -45,6 +51,8 @@ export function createClient(
     outputChannelName: "mkdlint",
     initializationOptions: {
       ...(preset ? { preset } : {}),
+      fixOnSave,
+      ...(scanConcurrency ? { scanConcurrency } : {}),
     },
   };
 
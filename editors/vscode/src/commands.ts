@@ -25,6 +25,40 @@ export function registerCommands(
     })
   );
 
+  context.subscriptions.push(
+    vscode.commands.registerCommand("mkdlint.showFixPreview", async () => {
+      const editor = vscode.window.activeTextEditor;
+      if (!editor || editor.document.languageId !== "markdown") {
+        vscode.window.showWarningMessage("mkdlint: No active markdown file.");
+        return;
+      }
+      if (!client.isRunning()) {
+        vscode.window.showWarningMessage(
+          "mkdlint: Language server is not running."
+        );
+        return;
+      }
+      const preview = await client.sendRequest<{
+        diff: string;
+        message: string;
+      } | null>("workspace/executeCommand", {
+        command: "mkdlint.previewFixes",
+        arguments: [editor.document.uri.toString()],
+      });
+      if (!preview || preview.diff === "") {
+        vscode.window.showInformationMessage(
+          `mkdlint: ${preview?.message ?? "No fixable issues"}.`
+        );
+        return;
+      }
+      const doc = await vscode.workspace.openTextDocument({
+        content: preview.diff,
+        language: "diff",
+      });
+      await vscode.window.showTextDocument(doc, { preview: true });
+    })
+  );
+
   context.subscriptions.push(
     vscode.commands.registerCommand("mkdlint.restart", async () => {
       if (client.isRunning()) {
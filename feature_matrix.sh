@@ -0,0 +1,50 @@
+#!/bin/bash
+# Check every rule group feature on its own and compare binary sizes.
+#
+# Each `rules-*` feature must build alone, without the default features,
+# and the library must leave the other groups' rules out. The size table
+# lists the release `mkdlint` binary built with each group against the
+# default `rules-all` build.
+#
+# Usage: ./feature_matrix.sh [--no-size]
+
+set -e
+
+RULE_GROUPS=(
+    rules-headings
+    rules-whitespace
+    rules-lists
+    rules-links
+    rules-tables
+    rules-code
+    rules-style
+    rules-kramdown
+)
+
+echo "🔍 Checking the library without any rule group..."
+cargo check --no-default-features
+
+for group in "${RULE_GROUPS[@]}"; do
+    echo "🔍 Checking $group..."
+    cargo check --no-default-features --features "$group"
+done
+
+echo "🧪 Testing a build without rules-kramdown..."
+cargo test --lib --no-default-features --features rules-whitespace rules::tests
+
+if [ "$1" = "--no-size" ]; then
+    exit 0
+fi
+
+size_of() {
+    cargo build --release --quiet --no-default-features --features "cli,$1"
+    wc -c < target/release/mkdlint
+}
+
+echo ""
+echo "📦 Release binary size by rule group"
+printf "%-18s %12s\n" "features" "bytes"
+printf "%-18s %12s\n" "rules-all" "$(size_of rules-all)"
+for group in "${RULE_GROUPS[@]}"; do
+    printf "%-18s %12s\n" "$group" "$(size_of "$group")"
+done
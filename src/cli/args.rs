@@ -10,6 +10,17 @@ pub(crate) enum OutputFormat {
     Sarif,
     /// GitHub Actions workflow command annotations (::error file=...)
     Github,
+    /// Newline-delimited JSON events, streamed as each file finishes
+    Ndjson,
+}
+
+/// Lowest severity reported by `--severity`
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum MinSeverity {
+    /// Errors only; warnings are hidden and do not fail the run
+    Error,
+    #[default]
+    Warning,
 }
 
 #[derive(Parser, Debug)]
@@ -32,6 +43,22 @@ pub(crate) struct Args {
     #[arg(short = 'o', long, default_value = "text", global = true)]
     pub(crate) output_format: OutputFormat,
 
+    /// One line per error for text output, e.g. '{file}:{line}:{column} {rule} {message}'
+    /// (placeholders: {file} {line} {column} {rule} {alias} {severity}
+    /// {message} {detail}; '{{' and '}}' are literal braces)
+    #[arg(long, value_name = "TEMPLATE", global = true)]
+    pub(crate) output_template: Option<mkdlint::formatters::OutputTemplate>,
+
+    /// With --output-format json, add a "summary" object with totals and
+    /// per-rule and per-file counts
+    #[arg(long, global = true)]
+    pub(crate) statistics: bool,
+
+    /// Directory that SARIF and GitHub output paths are relative to
+    /// (default: the current directory)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub(crate) root: Option<String>,
+
     /// Glob patterns for files to ignore (repeatable)
     #[arg(long, action = clap::ArgAction::Append, global = true)]
     pub(crate) ignore: Vec<String>,
@@ -40,10 +67,53 @@ pub(crate) struct Args {
     #[arg(long, global = true)]
     pub(crate) no_color: bool,
 
+    /// Don't layer the config over the global user config
+    /// (`$XDG_CONFIG_HOME/mkdlint/config.{json,yaml,toml}`)
+    #[arg(long, global = true)]
+    pub(crate) no_global_config: bool,
+
     /// Disable inline configuration comments
     #[arg(long, global = true)]
     pub(crate) no_inline_config: bool,
 
+    /// Report inline disable directives that suppressed no errors; with
+    /// `--fix`, remove them
+    #[arg(long, global = true)]
+    pub(crate) report_unused_disable: bool,
+
+    /// Front matter delimiter regex, or `auto` for YAML, TOML, and JSON (repeatable)
+    #[arg(long, action = clap::ArgAction::Append, global = true, value_name = "PATTERN")]
+    pub(crate) front_matter: Vec<String>,
+
+    /// Lowest severity to report; `error` hides warnings from the output
+    /// and the exit code
+    #[arg(
+        long,
+        global = true,
+        value_name = "LEVEL",
+        default_value = "warning",
+        conflicts_with_all = ["fix", "fix_dry_run"]
+    )]
+    pub(crate) severity: MinSeverity,
+
+    /// Only show violations that --fix can correct (the exit code still
+    /// counts all of them)
+    #[arg(long, global = true, conflicts_with_all = ["fix", "fix_dry_run"])]
+    pub(crate) only_fixable: bool,
+
+    /// Only show violations that --fix cannot correct (the exit code still
+    /// counts all of them)
+    #[arg(
+        long,
+        global = true,
+        conflicts_with_all = ["fix", "fix_dry_run", "only_fixable"]
+    )]
+    pub(crate) only_unfixable: bool,
+
+    /// Exit non-zero only when a rule exceeds its `budgets` entry in the config
+    #[arg(long, global = true)]
+    pub(crate) enforce_budgets: bool,
+
     /// Automatically fix violations where possible
     #[arg(short, long, global = true)]
     pub(crate) fix: bool,
@@ -52,6 +122,17 @@ pub(crate) struct Args {
     #[arg(long, global = true)]
     pub(crate) fix_dry_run: bool,
 
+    /// With --fix, write the fixes as one unified diff to FILE instead of
+    /// modifying sources (apply with `patch -p1 < FILE` or `git apply FILE`)
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        requires = "fix",
+        conflicts_with_all = ["stdin", "watch"]
+    )]
+    pub(crate) patch_output: Option<String>,
+
     /// List all available rules
     #[arg(long, global = true)]
     pub(crate) list_rules: bool,
@@ -60,10 +141,20 @@ pub(crate) struct Args {
     #[arg(long, global = true)]
     pub(crate) list_presets: bool,
 
+    /// Print the files that would be linted, without linting them; with
+    /// --verbose, also show excluded files and why
+    #[arg(long)]
+    pub(crate) list_files: bool,
+
     /// Show detailed documentation for a specific rule (e.g., --explain MD013)
     #[arg(long, global = true, value_name = "RULE")]
     pub(crate) explain: Option<String>,
 
+    /// Show each rule's resolved state and where it was set (config file,
+    /// `extends` parent, preset, command-line flag, or built-in default)
+    #[arg(long, global = true)]
+    pub(crate) explain_config: bool,
+
     /// Read input from stdin (use '-' as filename)
     #[arg(long, global = true)]
     pub(crate) stdin: bool,
@@ -76,6 +167,11 @@ pub(crate) struct Args {
     #[arg(long, action = clap::ArgAction::Append, global = true)]
     pub(crate) disable: Vec<String>,
 
+    /// Enable every rule, including opt-in ones such as KMD*, at error
+    /// severity; `--disable` still turns rules off
+    #[arg(long, global = true)]
+    pub(crate) strict: bool,
+
     /// Verbose output with detailed information
     #[arg(short, long, global = true)]
     pub(crate) verbose: bool,
@@ -100,9 +196,107 @@ pub(crate) struct Args {
     #[arg(long, global = true)]
     pub(crate) generate_schema: bool,
 
-    /// Filename to use for stdin content in error output (requires --stdin)
+    /// Filename to use for stdin content in error output (requires --stdin);
+    /// config discovery and --ignore treat the content as that file
     #[arg(long, global = true)]
     pub(crate) stdin_filename: Option<String>,
+
+    /// With --stdin, split the input at marker lines (see --stdin-separator)
+    /// and lint each part as its own document, named by the marker or
+    /// `stdin:N`; line numbers are relative to each document
+    #[arg(
+        long,
+        global = true,
+        requires = "stdin",
+        conflicts_with_all = ["fix", "fix_dry_run", "lines"]
+    )]
+    pub(crate) stdin_multi: bool,
+
+    /// Regex for the --stdin-multi marker lines; its first capture group,
+    /// when it matches, names the document that follows
+    #[arg(
+        long,
+        global = true,
+        value_name = "REGEX",
+        requires = "stdin_multi",
+        default_value = super::stdin_multi::DEFAULT_SEPARATOR
+    )]
+    pub(crate) stdin_separator: String,
+
+    /// Follow directory symlinks when expanding directories (cycles are skipped)
+    #[arg(long, global = true)]
+    pub(crate) follow_symlinks: bool,
+
+    /// Lint only files changed relative to a git ref (default: HEAD)
+    #[arg(
+        long,
+        global = true,
+        value_name = "REF",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "HEAD"
+    )]
+    pub(crate) changed_only: Option<String>,
+
+    /// Lint only files added, modified, or renamed relative to a git ref
+    /// (default paths: the current directory)
+    #[arg(
+        long,
+        global = true,
+        value_name = "REF",
+        conflicts_with = "changed_only"
+    )]
+    pub(crate) changed: Option<String>,
+
+    /// Report only errors on lines START through END of the single input,
+    /// e.g. '40:80'; the whole file is still linted for context
+    #[arg(
+        long,
+        global = true,
+        value_name = "START:END",
+        conflicts_with_all = ["fix", "fix_dry_run", "watch"]
+    )]
+    pub(crate) lines: Option<LineRange>,
+
+    /// Number of files to lint in parallel (default: one per CPU; 1 = sequential)
+    #[arg(short, long, global = true, value_name = "N")]
+    pub(crate) jobs: Option<std::num::NonZeroUsize>,
+}
+
+/// An inclusive `START:END` range of 1-based lines, for `--lines`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LineRange {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl LineRange {
+    /// The lines as an end-exclusive range, for `LintOptions::line_ranges`
+    pub(crate) fn to_range(&self) -> std::ops::Range<usize> {
+        self.start..self.end + 1
+    }
+}
+
+impl std::str::FromStr for LineRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected START:END with 1 <= START <= END, got '{}'", s);
+        let (start, end) = s.split_once(':').ok_or_else(invalid)?;
+        let start: usize = start.trim().parse().map_err(|_| invalid())?;
+        let end: usize = end.trim().parse().map_err(|_| invalid())?;
+        if start == 0 || end < start {
+            return Err(invalid());
+        }
+        Ok(Self { start, end })
+    }
+}
+
+impl Args {
+    /// Git ref to compare against for `--changed REF` / `--changed-only[=REF]`
+    pub(crate) fn changed_base(&self) -> Option<&str> {
+        self.changed.as_deref().or(self.changed_only.as_deref())
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -121,4 +315,30 @@ pub(crate) enum Command {
         #[arg(long, short)]
         interactive: bool,
     },
+
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
+
+    /// Work with configuration files
+    #[command(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub(crate) enum ConfigCommand {
+    /// Convert a config file to another format, with rules keyed by ID
+    Migrate {
+        /// Config file to read: JSON, JSONC, YAML, TOML, package.json or
+        /// a markdownlint-cli2 options file
+        #[arg(long)]
+        from: String,
+
+        /// File to write; its extension (json, yaml, yml or toml) picks
+        /// the format
+        #[arg(long)]
+        to: String,
+    },
 }
@@ -0,0 +1,71 @@
+//! `completions` subcommand — print a shell completion script
+
+use super::args::Args;
+use clap::CommandFactory;
+use clap::builder::PossibleValuesParser;
+use clap_complete::Shell;
+
+/// The completion script for `shell`.
+///
+/// Shells that support value completion also complete preset names for
+/// `--preset` and rule IDs for `--enable`, `--disable`, and `--explain`.
+/// The lists are attached only to the command used for generation, so
+/// parsing still accepts aliases and reports unknown names the usual way.
+pub(crate) fn completion_script(shell: Shell) -> String {
+    let rule_ids: Vec<&'static str> = mkdlint::rules::get_rules()
+        .iter()
+        .map(|rule| rule.names()[0])
+        .collect();
+    let presets = mkdlint::config::presets::preset_names();
+
+    let mut command = Args::command()
+        .mut_arg("preset", |arg| {
+            arg.value_parser(PossibleValuesParser::new(presets.iter().copied()))
+        })
+        .mut_arg("enable", |arg| {
+            arg.value_parser(PossibleValuesParser::new(rule_ids.iter().copied()))
+        })
+        .mut_arg("disable", |arg| {
+            arg.value_parser(PossibleValuesParser::new(rule_ids.iter().copied()))
+        })
+        .mut_arg("explain", |arg| {
+            arg.value_parser(PossibleValuesParser::new(rule_ids.iter().copied()))
+        });
+    let name = command.get_name().to_string();
+    let mut out = Vec::new();
+    clap_complete::generate(shell, &mut command, name, &mut out);
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_bash_completions_include_long_flags_and_values() {
+        let bash = completion_script(Shell::Bash);
+        for flag in [
+            "--config",
+            "--output-format",
+            "--fix",
+            "--enable",
+            "--preset",
+        ] {
+            assert!(bash.contains(flag), "missing {flag}");
+        }
+        assert!(bash.contains("MD013"));
+        assert!(bash.contains("kramdown"));
+        assert!(bash.contains("sarif"));
+    }
+
+    #[test]
+    fn test_completions_generate_for_every_shell() {
+        for shell in Shell::value_variants() {
+            assert!(
+                !completion_script(*shell).is_empty(),
+                "{shell} produced no script"
+            );
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Detect a repository's existing Markdown conventions
+//!
+//! Used by the init wizard to prefill its answers. Each document is parsed
+//! and its heading, list marker, emphasis and code block styles are tallied,
+//! along with the longest prose line.
+
+use mkdlint::parser::{Token, parse};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Directories never scanned: dependencies and tool caches
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor"];
+
+/// How often each style of one kind was seen
+#[derive(Debug, Default)]
+pub(crate) struct Tally(BTreeMap<&'static str, usize>);
+
+impl Tally {
+    fn add(&mut self, style: &'static str) {
+        *self.0.entry(style).or_default() += 1;
+    }
+
+    /// The most common style, or `None` if nothing was seen or the top
+    /// styles are tied
+    pub(crate) fn dominant(&self) -> Option<&'static str> {
+        let max = self.0.values().copied().max()?;
+        let mut top = self.0.iter().filter(|&(_, &count)| count == max);
+        let (style, _) = top.next()?;
+        top.next().is_none().then_some(*style)
+    }
+}
+
+/// Conventions observed across the scanned files
+#[derive(Debug, Default)]
+pub(crate) struct Conventions {
+    pub(crate) files_scanned: usize,
+    /// MD003 style of level 1 and 2 headings: `atx` or `setext`
+    pub(crate) heading_style: Tally,
+    /// MD004 style of unordered lists: `dash`, `asterisk` or `plus`
+    pub(crate) list_marker: Tally,
+    /// MD049 style: `asterisk` or `underscore`
+    pub(crate) emphasis_style: Tally,
+    /// MD050 style: `asterisk` or `underscore`
+    pub(crate) strong_style: Tally,
+    /// MD046 style: `fenced` or `indented`
+    pub(crate) code_style: Tally,
+    /// MD048 style: `backtick` or `tilde`
+    pub(crate) code_fence_style: Tally,
+    /// Longest line in characters, outside code blocks and tables
+    pub(crate) max_line_length: usize,
+}
+
+impl Conventions {
+    /// Tally the conventions of one document
+    pub(crate) fn add_document(&mut self, content: &str) {
+        let tokens = parse(content);
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        self.files_scanned += 1;
+
+        for token in &tokens {
+            match token.token_type.as_str() {
+                "heading" if token.heading_level().is_some_and(|level| level <= 2) => {
+                    let setext = token.is_setext() == Some(true);
+                    self.heading_style
+                        .add(if setext { "setext" } else { "atx" });
+                }
+                "list" if token.is_ordered_list() == Some(false) => {
+                    match token.metadata.get("bullet_char").map(String::as_str) {
+                        Some("-") => self.list_marker.add("dash"),
+                        Some("*") => self.list_marker.add("asterisk"),
+                        Some("+") => self.list_marker.add("plus"),
+                        _ => {}
+                    }
+                }
+                "emphasis" => {
+                    if let Some(style) = delimiter_style(token, &lines) {
+                        self.emphasis_style.add(style);
+                    }
+                }
+                "strong" => {
+                    if let Some(style) = delimiter_style(token, &lines) {
+                        self.strong_style.add(style);
+                    }
+                }
+                "codeBlock" => match token.metadata.get("fence_char").map(String::as_str) {
+                    _ if token.fence_info().is_none() => self.code_style.add("indented"),
+                    Some("~") => {
+                        self.code_style.add("fenced");
+                        self.code_fence_style.add("tilde");
+                    }
+                    _ => {
+                        self.code_style.add("fenced");
+                        self.code_fence_style.add("backtick");
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        let code_lines = mkdlint::helpers::code_block_lines(&lines, &tokens);
+        self.max_line_length = lines
+            .iter()
+            .zip(code_lines)
+            .filter(|(line, in_code)| !in_code && !line.trim_start().starts_with('|'))
+            .map(|(line, _)| line.trim_end().chars().count())
+            .chain([self.max_line_length])
+            .max()
+            .unwrap_or_default();
+    }
+}
+
+/// `asterisk` or `underscore`, from the delimiter at the token's start
+fn delimiter_style(token: &Token, lines: &[&str]) -> Option<&'static str> {
+    let line = lines.get(token.start_line.checked_sub(1)?)?;
+    match line.as_bytes().get(token.start_column.checked_sub(1)?)? {
+        b'*' => Some("asterisk"),
+        b'_' => Some("underscore"),
+        _ => None,
+    }
+}
+
+/// Markdown files under `dir`, in name order, at most `limit` of them.
+///
+/// Hidden directories and dependency directories such as `node_modules`
+/// are skipped.
+pub(crate) fn markdown_files(dir: &Path, limit: usize) -> Vec<std::path::PathBuf> {
+    walkdir::WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || !(name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref()))
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && super::files::is_markdown_path(entry.path()))
+        .take(limit)
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+/// Scan up to `limit` Markdown files under `dir`; unreadable files are
+/// skipped
+pub(crate) fn detect_conventions(dir: &Path, limit: usize) -> Conventions {
+    let mut conventions = Conventions::default();
+    for path in markdown_files(dir, limit) {
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            conventions.add_document(&content);
+        }
+    }
+    conventions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(documents: &[&str]) -> Conventions {
+        let mut conventions = Conventions::default();
+        for document in documents {
+            conventions.add_document(document);
+        }
+        conventions
+    }
+
+    #[test]
+    fn test_detect_styles() {
+        let conventions = detect(&[
+            "Title\n=====\n\n* one\n* two\n\nSome _emphasis_ and __strong__ text.\n\n~~~sh\nmake\n~~~\n",
+            "Other\n-----\n\n### Deep\n\n* item\n\n- dash\n\n_more_ *mixed*\n",
+        ]);
+        assert_eq!(conventions.files_scanned, 2);
+        assert_eq!(conventions.heading_style.dominant(), Some("setext"));
+        assert_eq!(conventions.list_marker.dominant(), Some("asterisk"));
+        assert_eq!(conventions.emphasis_style.dominant(), Some("underscore"));
+        assert_eq!(conventions.strong_style.dominant(), Some("underscore"));
+        assert_eq!(conventions.code_style.dominant(), Some("fenced"));
+        assert_eq!(conventions.code_fence_style.dominant(), Some("tilde"));
+    }
+
+    #[test]
+    fn test_detect_ties_and_absence() {
+        let conventions = detect(&["# One\n\n    code\n\n```\nfenced\n```\n"]);
+        assert_eq!(conventions.heading_style.dominant(), Some("atx"));
+        assert_eq!(conventions.code_style.dominant(), None);
+        assert_eq!(conventions.code_fence_style.dominant(), Some("backtick"));
+        assert_eq!(conventions.list_marker.dominant(), None);
+        assert_eq!(conventions.emphasis_style.dominant(), None);
+    }
+
+    #[test]
+    fn test_detect_max_line_length_skips_code_and_tables() {
+        let long = "x".repeat(200);
+        let conventions = detect(&[
+            &format!("# Title\n\nShort prose line.\n\n```\n{long}\n```\n\n| {long} |\n|---|\n"),
+            "# Other\n\nA somewhat longer prose line, with café.\n",
+        ]);
+        assert_eq!(conventions.max_line_length, 40);
+    }
+
+    #[test]
+    fn test_markdown_files_skips_hidden_and_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        for path in [
+            "README.md",
+            "docs/b.markdown",
+            "docs/a.md",
+            "docs/notes.txt",
+            ".github/PULL_REQUEST_TEMPLATE.md",
+            "node_modules/pkg/README.md",
+        ] {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "# Title\n").unwrap();
+        }
+
+        let names = |limit| -> Vec<String> {
+            markdown_files(dir.path(), limit)
+                .iter()
+                .map(|p| {
+                    let relative = p.strip_prefix(dir.path()).unwrap();
+                    relative.to_string_lossy().replace('\\', "/")
+                })
+                .collect()
+        };
+        assert_eq!(names(10), ["README.md", "docs/a.md", "docs/b.markdown"]);
+        assert_eq!(names(2), ["README.md", "docs/a.md"]);
+        assert_eq!(detect_conventions(dir.path(), 10).files_scanned, 3);
+    }
+}
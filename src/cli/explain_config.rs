@@ -0,0 +1,64 @@
+//! `--explain-config` handler — show where each rule's setting came from
+
+use super::args::Args;
+use super::lint::{apply_config_flags, apply_strict, load_config_file, load_global_config};
+use colored::Colorize;
+use mkdlint::config::ConfigSource;
+
+/// Print every rule's resolved state and the config layer that decided it:
+/// the config file, an `extends` parent, a preset, the global user config,
+/// a command-line flag, or the built-in default
+pub(crate) fn explain_config(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    // Discovery starts where a lint run would: at the first path given
+    let start = args
+        .stdin_filename
+        .as_ref()
+        .or(args.files.first())
+        .map(std::path::Path::new)
+        .map(|path| {
+            if path.is_dir() {
+                path
+            } else {
+                path.parent().unwrap_or(path)
+            }
+        })
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(std::path::Path::new("."));
+
+    let (mut config, source) = load_config_file(args, start)?;
+    let flag_keys = apply_config_flags(args, &mut config);
+    let (mut resolved, mut trace) = match load_global_config(args)? {
+        Some((path, global)) => config.resolve_over_traced(
+            source,
+            &global,
+            ConfigSource::File(path.display().to_string()),
+        )?,
+        None => config.resolve_extends_traced(source)?,
+    };
+    let strict_keys = apply_strict(args, &mut resolved);
+    if args.strict {
+        trace.default = Some(ConfigSource::CommandLine);
+    }
+    for key in flag_keys.into_iter().chain(strict_keys) {
+        trace.rules.insert(key, ConfigSource::CommandLine);
+    }
+
+    if let (Some(default), Some(source)) = (resolved.default, &trace.default) {
+        println!(
+            "{:<7} {:<8} {}",
+            "default",
+            default,
+            source.to_string().dimmed()
+        );
+    }
+    for rule in resolved.effective_rules() {
+        let state = if rule.enabled {
+            format!("{:<8}", "enabled").green()
+        } else {
+            format!("{:<8}", "disabled").red()
+        };
+        let source = trace.rule_source(rule.name).to_string();
+        println!("{:<7} {} {}", rule.name, state, source.dimmed());
+    }
+    Ok(())
+}
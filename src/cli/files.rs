@@ -1,49 +1,383 @@
 //! File expansion and ignore-pattern filtering
 
-/// Expand directories to .md/.markdown files recursively
-pub(crate) fn expand_paths(paths: &[String]) -> Vec<String> {
-    use walkdir::WalkDir;
-
-    let mut expanded = Vec::new();
-    for path in paths {
-        let p = std::path::Path::new(path);
-        if p.is_dir() {
-            for entry in WalkDir::new(p).into_iter().filter_map(|e| e.ok()) {
-                let ep = entry.path();
-                if ep.is_file()
-                    && let Some(ext) = ep.extension().and_then(|e| e.to_str())
-                    && (ext == "md" || ext == "markdown")
-                {
-                    expanded.push(ep.to_string_lossy().to_string());
+use mkdlint::config::SkipGenerated;
+use mkdlint::helpers::normalize_path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Extensions of the files directory walks pick up as Markdown
+pub(crate) const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown"];
+
+/// Whether `path` has one of the [`MARKDOWN_EXTENSIONS`]
+pub(crate) fn is_markdown_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| MARKDOWN_EXTENSIONS.contains(&ext))
+}
+
+/// Why a path was left out of the set of files to lint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Exclusion {
+    /// Found in a directory walk without one of the [`MARKDOWN_EXTENSIONS`]
+    NotMarkdown,
+    /// The same file as an earlier path (a symlink or overlapping argument)
+    Duplicate { of: String },
+    /// Matched an `--ignore` pattern
+    Ignored { pattern: String },
+    /// Not changed relative to the `--changed`/`--changed-only` base
+    Unchanged { base: String },
+    /// Carries a `skip_generated` marker
+    Generated,
+}
+
+impl std::fmt::Display for Exclusion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Exclusion::NotMarkdown => write!(f, "not a markdown extension"),
+            Exclusion::Duplicate { of } => write!(f, "same file as {}", of),
+            Exclusion::Ignored { pattern } => write!(f, "ignored by --ignore '{}'", pattern),
+            Exclusion::Unchanged { base } => write!(f, "unchanged since {}", base),
+            Exclusion::Generated => write!(f, "generated: has a skip_generated marker"),
+        }
+    }
+}
+
+/// A candidate path and, if it won't be linted, why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FileDecision {
+    pub(crate) path: String,
+    pub(crate) excluded: Option<Exclusion>,
+}
+
+/// Every path considered for linting, sorted, with the reason for each
+/// exclusion. Each filter only looks at paths still included, so a path
+/// records the first reason it was dropped.
+#[derive(Debug, Default)]
+pub(crate) struct FileSet {
+    decisions: Vec<FileDecision>,
+}
+
+impl FileSet {
+    /// `paths` as given, all included
+    pub(crate) fn from_paths(paths: Vec<String>) -> Self {
+        let decisions = paths
+            .into_iter()
+            .map(|path| FileDecision {
+                path,
+                excluded: None,
+            })
+            .collect();
+        Self { decisions }
+    }
+
+    /// Expand directories to .md/.markdown files recursively
+    ///
+    /// Files reachable through several paths (symlinks, overlapping
+    /// arguments) are linted once, under the first path seen. Directory
+    /// symlinks are only traversed with `follow_symlinks`, and symlink
+    /// cycles are never followed.
+    pub(crate) fn expand(paths: &[String], follow_symlinks: bool) -> Self {
+        use std::collections::HashMap;
+        use walkdir::WalkDir;
+
+        let mut seen: HashMap<String, String> = HashMap::new();
+        let mut decisions = Vec::new();
+        let mut add = |display: String, markdown: bool| {
+            if !markdown {
+                decisions.push(FileDecision {
+                    path: display,
+                    excluded: Some(Exclusion::NotMarkdown),
+                });
+                return;
+            }
+            // Files that cannot be canonicalized (e.g. missing) dedupe by name.
+            // Windows canonical paths lose their `\\?\` prefix so both kinds
+            // of key compare alike.
+            let key = std::fs::canonicalize(&display)
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or_else(|_| display.clone());
+            let excluded = match seen.get(&normalize_path(&key)) {
+                Some(first) => Some(Exclusion::Duplicate { of: first.clone() }),
+                None => {
+                    seen.insert(normalize_path(&key), display.clone());
+                    None
+                }
+            };
+            decisions.push(FileDecision {
+                path: display,
+                excluded,
+            });
+        };
+
+        for path in paths {
+            let p = Path::new(path);
+            if p.is_dir() {
+                // WalkDir reports symlink loops as errors, which are skipped
+                let walker = WalkDir::new(p)
+                    .follow_links(follow_symlinks)
+                    .sort_by_file_name();
+                for entry in walker.into_iter().filter_map(|e| e.ok()) {
+                    let ep = entry.path();
+                    if ep.is_file() {
+                        add(ep.to_string_lossy().to_string(), is_markdown_path(ep));
+                    }
                 }
+            } else {
+                add(path.clone(), true);
+            }
+        }
+        decisions.sort_by(|a, b| a.path.cmp(&b.path));
+        Self { decisions }
+    }
+
+    /// All decisions, in path order
+    pub(crate) fn decisions(&self) -> &[FileDecision] {
+        &self.decisions
+    }
+
+    /// Paths that will be linted, in path order
+    pub(crate) fn included(&self) -> Vec<String> {
+        self.decisions
+            .iter()
+            .filter(|d| d.excluded.is_none())
+            .map(|d| d.path.clone())
+            .collect()
+    }
+
+    /// Exclude included paths for which `reason` returns one
+    fn exclude(&mut self, mut reason: impl FnMut(&str) -> Option<Exclusion>) {
+        for decision in &mut self.decisions {
+            if decision.excluded.is_none() {
+                decision.excluded = reason(&decision.path);
             }
-        } else {
-            expanded.push(path.clone());
         }
     }
-    expanded.sort();
-    expanded
-}
 
-/// Filter files by ignore glob patterns
-pub(crate) fn filter_ignored(
-    files: Vec<String>,
-    ignore_patterns: &[String],
-) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-    if ignore_patterns.is_empty() {
-        return Ok(files);
+    /// Exclude paths matching an ignore glob pattern
+    ///
+    /// Patterns are matched against each path with forward slashes, so
+    /// `docs/**` also ignores `docs\guide.md` on Windows.
+    pub(crate) fn exclude_ignored(
+        &mut self,
+        ignore_patterns: &[String],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if ignore_patterns.is_empty() {
+            return Ok(());
+        }
+
+        use globset::{Glob, GlobSetBuilder};
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in ignore_patterns {
+            builder.add(Glob::new(pattern)?);
+        }
+        let ignore_set = builder.build()?;
+
+        self.exclude(|path| {
+            let first = *ignore_set.matches(normalize_path(path)).first()?;
+            Some(Exclusion::Ignored {
+                pattern: ignore_patterns[first].clone(),
+            })
+        });
+        Ok(())
     }
 
-    use globset::{Glob, GlobSetBuilder};
+    /// Keep only files changed relative to the git ref `base`
+    ///
+    /// Changed files are the Markdown files `git diff --name-only
+    /// --diff-filter=ACMR <base>` reports anywhere in the repository (added,
+    /// copied, modified, or renamed between `base` and the working tree;
+    /// deletions are skipped). The diff runs from the repository root, so the
+    /// selection doesn't depend on the working directory. Paths are compared
+    /// canonically, so relative and absolute arguments both match.
+    /// Fails if git is unavailable, the working directory is not inside a git
+    /// repository, or `base` does not name a commit.
+    pub(crate) fn exclude_unchanged(&mut self, base: &str) -> Result<(), String> {
+        let changed = changed_files(base)?;
+        self.exclude(|path| {
+            let changed = std::fs::canonicalize(path).is_ok_and(|path| changed.contains(&path));
+            (!changed).then(|| Exclusion::Unchanged {
+                base: base.to_string(),
+            })
+        });
+        Ok(())
+    }
 
-    let mut builder = GlobSetBuilder::new();
-    for pattern in ignore_patterns {
-        builder.add(Glob::new(pattern)?);
+    /// Exclude files carrying one of `skip`'s generated-file markers;
+    /// unreadable files stay included
+    pub(crate) fn exclude_generated(&mut self, skip: &SkipGenerated) {
+        self.exclude(|path| {
+            let content = std::fs::read_to_string(path).ok()?;
+            skip.matches(&content).then_some(Exclusion::Generated)
+        });
     }
-    let ignore_set = builder.build()?;
+}
+
+/// Canonical paths of the Markdown files changed relative to `base`; see
+/// [`FileSet::exclude_unchanged`]
+fn changed_files(base: &str) -> Result<HashSet<PathBuf>, String> {
+    use std::process::Command;
 
-    Ok(files
-        .into_iter()
-        .filter(|f| !ignore_set.is_match(f))
+    let git = |dir: Option<&Path>, args: &[&str]| -> Result<String, String> {
+        let mut command = Command::new("git");
+        if let Some(dir) = dir {
+            command.current_dir(dir);
+        }
+        let output = command
+            .args(args)
+            .output()
+            .map_err(|e| format!("selecting changed files requires git: {}", e))?;
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }
+    };
+
+    let root = git(None, &["rev-parse", "--show-toplevel"])
+        .map_err(|_| "cannot select changed files: not inside a git repository".to_string())?;
+    let root = PathBuf::from(root.trim());
+    git(
+        Some(&root),
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{}^{{commit}}", base),
+        ],
+    )
+    .map_err(|_| format!("cannot select changed files: unknown git ref '{}'", base))?;
+
+    // `:(top)` anchors each pattern at the repository root; without `glob`
+    // magic, `*` also matches `/`, so files in every directory are listed.
+    let pathspecs: Vec<String> = MARKDOWN_EXTENSIONS
+        .iter()
+        .map(|ext| format!(":(top)*.{}", ext))
+        .collect();
+    let mut args = vec!["diff", "--name-only", "--diff-filter=ACMR", base, "--"];
+    args.extend(pathspecs.iter().map(String::as_str));
+    let changed = git(Some(&root), &args).map_err(|e| {
+        format!(
+            "cannot select changed files: git diff against '{}' failed: {}",
+            base, e
+        )
+    })?;
+
+    Ok(changed
+        .lines()
+        .filter_map(|name| std::fs::canonicalize(root.join(name)).ok())
         .collect())
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    /// docs/v2/guide.md, plus docs/latest -> docs/v2, docs/v2/alias.md ->
+    /// guide.md, and a docs/v2/loop -> docs cycle
+    fn symlinked_tree() -> tempfile::TempDir {
+        let temp = tempfile::tempdir().unwrap();
+        let docs = temp.path().join("docs");
+        let v2 = docs.join("v2");
+        std::fs::create_dir_all(&v2).unwrap();
+        std::fs::write(v2.join("guide.md"), "# Guide\n").unwrap();
+        symlink(v2.join("guide.md"), v2.join("alias.md")).unwrap();
+        symlink(&v2, docs.join("latest")).unwrap();
+        symlink(&docs, v2.join("loop")).unwrap();
+        temp
+    }
+
+    fn names(files: &[String], root: &std::path::Path) -> Vec<String> {
+        files
+            .iter()
+            .map(|f| {
+                std::path::Path::new(f)
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_paths_dedupes_file_symlink() {
+        let temp = symlinked_tree();
+        let docs = temp.path().join("docs").to_string_lossy().to_string();
+        let files = FileSet::expand(&[docs], false).included();
+        // alias.md sorts first in the walk, so it is the path kept
+        assert_eq!(names(&files, temp.path()), ["docs/v2/alias.md"]);
+    }
+
+    #[test]
+    fn test_expand_paths_follow_symlinks_survives_cycle() {
+        let temp = symlinked_tree();
+        let docs = temp.path().join("docs").to_string_lossy().to_string();
+        let files = FileSet::expand(&[docs], true).included();
+        // docs/latest is walked before docs/v2; the cycle is not followed
+        assert_eq!(names(&files, temp.path()), ["docs/latest/alias.md"]);
+    }
+
+    #[test]
+    fn test_expand_paths_overlapping_arguments() {
+        let temp = symlinked_tree();
+        let v2 = temp.path().join("docs/v2");
+        let guide = v2.join("guide.md").to_string_lossy().to_string();
+        let files = FileSet::expand(&[guide, v2.to_string_lossy().to_string()], false).included();
+        assert_eq!(names(&files, temp.path()), ["docs/v2/guide.md"]);
+    }
+
+    #[test]
+    fn test_file_set_records_exclusions() {
+        let temp = symlinked_tree();
+        let v2 = temp.path().join("docs/v2");
+        std::fs::write(v2.join("notes.txt"), "notes\n").unwrap();
+        std::fs::write(v2.join("api.md"), "<!-- @generated -->\n# API\n").unwrap();
+        std::fs::write(v2.join("draft.md"), "# Draft\n").unwrap();
+
+        let mut files = FileSet::expand(&[v2.to_string_lossy().to_string()], false);
+        files
+            .exclude_ignored(&["**/*.txt".to_string(), "**/draft.md".to_string()])
+            .unwrap();
+        files.exclude_generated(&SkipGenerated::default());
+
+        let reasons: Vec<(String, Option<String>)> = files
+            .decisions()
+            .iter()
+            .map(|d| {
+                let path = std::path::Path::new(&d.path);
+                let name = path
+                    .strip_prefix(&v2)
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string();
+                (name, d.excluded.as_ref().map(ToString::to_string))
+            })
+            .collect();
+        let alias = v2.join("alias.md").to_string_lossy().to_string();
+        assert_eq!(
+            reasons,
+            [
+                ("alias.md".to_string(), None),
+                (
+                    "api.md".to_string(),
+                    Some("generated: has a skip_generated marker".to_string())
+                ),
+                (
+                    "draft.md".to_string(),
+                    Some("ignored by --ignore '**/draft.md'".to_string())
+                ),
+                (
+                    "guide.md".to_string(),
+                    Some(format!("same file as {alias}"))
+                ),
+                (
+                    "notes.txt".to_string(),
+                    Some("not a markdown extension".to_string())
+                ),
+            ]
+        );
+        assert_eq!(files.included(), [alias]);
+    }
+}
@@ -0,0 +1,104 @@
+//! Convergent `--fix` passes and the per-rule summary printed after them
+
+use mkdlint::lint::converge_fixes;
+use mkdlint::types::{FixOutcome, MarkdownlintError};
+use mkdlint::{LintOptions, lint_sync};
+use std::collections::{BTreeMap, HashMap};
+
+/// Fix `content` of `file_path` until it stops changing.
+///
+/// Each pass lints with `options`' config, inline config and front matter
+/// settings, reusing `cached_headings` for cross-file MD051 checks.
+pub(crate) fn converge(
+    file_path: &str,
+    content: &str,
+    options: &LintOptions,
+    cached_headings: Option<&HashMap<String, Vec<String>>>,
+) -> Result<FixOutcome, MarkdownlintError> {
+    converge_fixes(content, |current| {
+        let pass_options = LintOptions {
+            files: vec![],
+            strings: [(file_path.to_string(), current.to_string())].into(),
+            config: options.config.clone(),
+            no_inline_config: options.no_inline_config,
+            report_unused_disables: options.report_unused_disables,
+            front_matter: options.front_matter.clone(),
+            front_matter_patterns: options.front_matter_patterns.clone(),
+            cached_workspace_headings: cached_headings.cloned(),
+            ..Default::default()
+        };
+        let mut results = lint_sync(&pass_options)?;
+        Ok(results.results.remove(file_path).unwrap_or_default())
+    })
+}
+
+/// Fixes one rule applied across a run
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct RuleFixes {
+    fixes: usize,
+    files: usize,
+}
+
+/// Fixes applied per rule across a `--fix` or `--fix-dry-run` run
+#[derive(Debug, Default)]
+pub(crate) struct FixSummary {
+    rules: BTreeMap<String, RuleFixes>,
+}
+
+impl FixSummary {
+    /// Count one file's fixes
+    pub(crate) fn add(&mut self, outcome: &FixOutcome) {
+        for (rule, fixes) in outcome.fixes_by_rule() {
+            let entry = self.rules.entry(rule).or_default();
+            entry.fixes += fixes;
+            entry.files += 1;
+        }
+    }
+
+    /// One line per rule, e.g. `MD009 fixed 3 occurrence(s) across 2 file(s)`,
+    /// where `verb` is `fixed` or `would fix`
+    pub(crate) fn lines(&self, verb: &str) -> Vec<String> {
+        self.rules
+            .iter()
+            .map(|(rule, counts)| {
+                format!(
+                    "{} {} {} occurrence(s) across {} file(s)",
+                    rule, verb, counts.fixes, counts.files
+                )
+            })
+            .collect()
+    }
+
+    /// Print the breakdown on stdout, indented under the run's summary line
+    pub(crate) fn print(&self, verb: &str) {
+        for line in self.lines(verb) {
+            println!("  {}", line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fix_summary_aggregates_files() {
+        let outcome = |passes: &[&[(&str, usize)]]| FixOutcome {
+            content: String::new(),
+            passes: passes
+                .iter()
+                .map(|pass| pass.iter().map(|(r, n)| (r.to_string(), *n)).collect())
+                .collect(),
+        };
+        let mut summary = FixSummary::default();
+        summary.add(&outcome(&[&[("MD009", 2), ("MD047", 1)], &[("MD009", 1)]]));
+        summary.add(&outcome(&[&[("MD009", 4)]]));
+        assert_eq!(
+            summary.lines("fixed"),
+            [
+                "MD009 fixed 7 occurrence(s) across 2 file(s)",
+                "MD047 fixed 1 occurrence(s) across 1 file(s)",
+            ]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! `mkdlint init` subcommand — initialize a new configuration file
 
 use super::wizard::init_config_interactive;
+use mkdlint::Config;
 
 /// Initialize a new configuration file
 pub(crate) fn init_config(
@@ -28,60 +29,24 @@ pub(crate) fn init_config(
     }
 
     // Create a useful default configuration with examples
-    let content = match format {
-        "json" => r#"{
-  "default": true,
-  "MD013": {
-    "line_length": 120,
-    "code_blocks": false,
-    "tables": false
-  },
-  "MD033": {
-    "allowed_elements": ["br", "img", "details", "summary"]
-  },
-  "MD040": {
-    "default_language": "text"
-  }
-}"#
-        .to_string(),
-        "yaml" | "yml" => r#"# Markdownlint configuration
-default: true
-
-# Line length
-MD013:
-  line_length: 120
-  code_blocks: false
-  tables: false
-
-# Inline HTML
-MD033:
-  allowed_elements:
-    - br
-    - img
-    - details
-    - summary
-
-# Fenced code language
-MD040:
-  default_language: text
-"#
-        .to_string(),
-        "toml" => r#"# Markdownlint configuration
-default = true
-
-[MD013]
-line_length = 120
-code_blocks = false
-tables = false
-
-[MD033]
-allowed_elements = ["br", "img", "details", "summary"]
-
-[MD040]
-default_language = "text"
-"#
-        .to_string(),
-        _ => {
+    let config: Config = serde_json::from_value(serde_json::json!({
+        "default": true,
+        "MD013": {
+            "line_length": 120,
+            "code_blocks": false,
+            "tables": false
+        },
+        "MD033": {
+            "allowed_elements": ["br", "img", "details", "summary"]
+        },
+        "MD040": {
+            "default_language": "text"
+        }
+    }))?;
+    let content = match config.to_format_string(format) {
+        Some(content) if format == "json" => content?,
+        Some(content) => format!("# Markdownlint configuration\n{}", content?),
+        None => {
             eprintln!(
                 "{} Unsupported format '{}'. Use json, yaml, or toml.",
                 "Error:".red().bold(),
@@ -1,59 +1,248 @@
 //! Core linting logic — lint files once (used by watch mode and normal mode)
 
-use super::args::{Args, OutputFormat};
-use super::files::{expand_paths, filter_ignored};
-use mkdlint::{LintOptions, apply_fixes, formatters, lint_sync};
+use super::args::{Args, MinSeverity, OutputFormat};
+use super::files::FileSet;
+use mkdlint::config::ConfigSource;
+use mkdlint::formatters::NdjsonEvent;
+use mkdlint::types::Severity;
+use mkdlint::{LintOptions, LintResults, apply_fixes, formatters, lint_sync, lint_sync_streaming};
+use std::io::Write;
 
-/// Lint files once (used by watch mode and normal mode)
-pub(crate) fn lint_files_once(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
-    use colored::Colorize;
+/// Print one NDJSON event and flush so consumers see it immediately
+pub(crate) fn emit_ndjson(event: &NdjsonEvent) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{}", event.to_line());
+    let _ = stdout.flush();
+}
 
-    // Expand directories and filter ignored files
-    let files = expand_paths(&args.files);
-    let files = filter_ignored(files, &args.ignore)?;
+/// With `--verbose`, list files skipped as generated on stderr
+pub(crate) fn report_skipped_generated(args: &Args, results: &LintResults) {
+    if args.verbose {
+        for path in &results.skipped_generated {
+            eprintln!("skipped (generated): {}", path);
+        }
+    }
+}
 
-    if files.is_empty() {
-        if !args.quiet {
-            println!("No files to lint.");
+/// Warn on stderr about files skipped because they look binary
+pub(crate) fn report_skipped_binary(args: &Args, results: &LintResults) {
+    if !args.quiet {
+        for path in &results.skipped_binary {
+            eprintln!("warning: {}: skipped: binary file", path);
         }
-        return Ok(());
     }
+}
 
-    // Build configuration
-    let mut config = if let Some(ref config_path) = args.config {
-        mkdlint::Config::from_file(config_path)?
+/// `results` without the violations below `--severity`; rules that
+/// panicked are always kept
+pub(crate) fn at_severity(args: &Args, results: LintResults) -> LintResults {
+    match args.severity {
+        MinSeverity::Warning => results,
+        MinSeverity::Error => {
+            results.filtered(|e| e.severity == Severity::Error || e.is_internal_error())
+        }
+    }
+}
+
+/// `results` narrowed to what `--only-fixable` or `--only-unfixable` shows
+pub(crate) fn shown_results(args: &Args, results: &LintResults) -> LintResults {
+    if args.only_fixable {
+        results.filtered(|e| e.fix_info.is_some())
+    } else if args.only_unfixable {
+        results.filtered(|e| e.fix_info.is_none())
     } else {
-        mkdlint::Config::default()
+        results.clone()
+    }
+}
+
+/// `results` keyed by the paths SARIF and GitHub output report: forward
+/// slashes, relative to `--root` (default: the current directory) for files
+/// under it
+pub(crate) fn with_report_paths(args: &Args, results: &LintResults) -> LintResults {
+    use std::path::{Path, PathBuf};
+
+    let Ok(cwd) = std::env::current_dir() else {
+        return results.clone();
     };
+    let canonical = |path: PathBuf| std::fs::canonicalize(&path).unwrap_or(path);
+    let cwd = canonical(cwd);
+    let root = canonical(cwd.join(args.root.as_deref().unwrap_or(".")));
+    let root = root.to_string_lossy();
+
+    let mut reported = results.clone();
+    reported.results = results
+        .results
+        .iter()
+        .map(|(name, errors)| {
+            // Drop `.` components so `./docs/a.md` lines up with the root
+            let absolute: PathBuf = cwd.join(Path::new(name)).components().collect();
+            let name = mkdlint::helpers::relative_report_path(&absolute.to_string_lossy(), &root);
+            (name, errors.clone())
+        })
+        .collect();
+    reported
+}
+
+/// Lint with `--output-format ndjson`, streaming events as each file completes
+pub(crate) fn lint_ndjson_stream(
+    options: &LintOptions,
+) -> Result<LintResults, Box<dyn std::error::Error>> {
+    emit_ndjson(&NdjsonEvent::start());
+    let results = lint_sync_streaming(options, |path, errors| {
+        for event in formatters::ndjson_file_events(path, errors) {
+            emit_ndjson(&event);
+        }
+    })?;
+    emit_ndjson(&formatters::ndjson_summary(&results));
+    Ok(results)
+}
 
-    // Apply --enable and --disable flags
+/// Resolve the configuration for a run: `--config`, or else the config file
+/// discovered by walking up from the directory of `path` (the first file, or
+/// the `--stdin-filename` for stdin), then `--enable`, `--disable`, and
+/// `--preset` on top
+pub(crate) fn build_config(
+    args: &Args,
+    path: Option<&str>,
+) -> Result<mkdlint::Config, Box<dyn std::error::Error>> {
+    let start = path
+        .and_then(|p| std::path::Path::new(p).parent())
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(std::path::Path::new("."));
+    let (mut config, _) = load_config_file(args, start)?;
+    if let Some((_, global)) = load_global_config(args)? {
+        config = config.resolve_over(&global)?;
+    }
+    apply_config_flags(args, &mut config);
+    // apply_preset is called inside resolve_extends() via load_config(),
+    // but since we bypass load_config here, call it explicitly.
+    config.apply_preset();
+    apply_strict(args, &mut config);
+    Ok(config)
+}
+
+/// The config file for a run and where it came from: `--config`, or else
+/// the file discovered by walking up from `start`, or an empty config
+pub(crate) fn load_config_file(
+    args: &Args,
+    start: &std::path::Path,
+) -> Result<(mkdlint::Config, ConfigSource), Box<dyn std::error::Error>> {
+    if let Some(ref config_path) = args.config {
+        let config = mkdlint::Config::from_file(config_path)?;
+        return Ok((config, ConfigSource::File(config_path.clone())));
+    }
+    Ok(match mkdlint::Config::discover_with_path(start) {
+        Some((path, config)) => (config, ConfigSource::File(path.display().to_string())),
+        None => (mkdlint::Config::default(), ConfigSource::Default),
+    })
+}
+
+/// The global user config and its path, unless `--no-global-config`
+pub(crate) fn load_global_config(
+    args: &Args,
+) -> Result<Option<(std::path::PathBuf, mkdlint::Config)>, Box<dyn std::error::Error>> {
+    if args.no_global_config {
+        return Ok(None);
+    }
+    Ok(mkdlint::Config::global()?)
+}
+
+/// Apply `--enable`, `--disable`, and `--preset` to `config` (a
+/// `--preset` overrides one set in the config file), returning the rule
+/// keys the flags set
+pub(crate) fn apply_config_flags(args: &Args, config: &mut mkdlint::Config) -> Vec<String> {
     use mkdlint::RuleConfig;
-    for rule in &args.enable {
+
+    let mut keys = Vec::new();
+    let flags = args.enable.iter().map(|rule| (rule, true));
+    for (rule, enabled) in flags.chain(args.disable.iter().map(|rule| (rule, false))) {
+        let key = rule.to_uppercase();
         config
             .rules
-            .insert(rule.to_uppercase(), RuleConfig::Enabled(true));
+            .insert(key.clone(), RuleConfig::Enabled(enabled));
+        keys.push(key);
     }
-    for rule in &args.disable {
+    if let Some(ref preset_name) = args.preset {
+        config.preset = Some(preset_name.clone());
+    }
+    keys
+}
+
+/// Apply `--strict` to a config whose preset is already resolved: every
+/// rule not named by `--disable` is enabled at error severity, keeping its
+/// options and overriding any severity the config or preset gave it, and
+/// every rule it names is disabled. Returns the rule keys it set.
+pub(crate) fn apply_strict(args: &Args, config: &mut mkdlint::Config) -> Vec<String> {
+    use mkdlint::RuleConfig;
+
+    if !args.strict {
+        return Vec::new();
+    }
+    config.default = Some(true);
+
+    let mut keys = Vec::new();
+    for rule in mkdlint::rules::get_rules() {
+        let disabled = rule
+            .names()
+            .iter()
+            .any(|name| args.disable.iter().any(|d| d.eq_ignore_ascii_case(name)));
+        let id = rule.names()[0];
+        keys.push(id.to_string());
+        if disabled {
+            // `--disable` may have named the rule by alias
+            config
+                .rules
+                .insert(id.to_string(), RuleConfig::Enabled(false));
+            continue;
+        }
+        let mut options = match config.rules.remove(id) {
+            Some(RuleConfig::Options(options)) => options,
+            _ => Default::default(),
+        };
+        options.remove("enabled");
+        options.insert("severity".to_string(), serde_json::json!("error"));
         config
             .rules
-            .insert(rule.to_uppercase(), RuleConfig::Enabled(false));
+            .insert(id.to_string(), RuleConfig::Options(options));
     }
+    keys
+}
 
-    // Apply --preset flag
-    if let Some(ref preset_name) = args.preset {
-        config.preset = Some(preset_name.clone());
+/// Lint files once (used by watch mode and normal mode)
+pub(crate) fn lint_files_once(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    // Expand directories and filter ignored files
+    let mut files = FileSet::expand(&args.files, args.follow_symlinks);
+    files.exclude_ignored(&args.ignore)?;
+    if let Some(base) = args.changed_base() {
+        files.exclude_unchanged(base)?;
     }
-    config.apply_preset();
+    let files = files.included();
+
+    if files.is_empty() {
+        if !args.quiet {
+            println!("No files to lint.");
+        }
+        return Ok(());
+    }
+
+    // Build configuration
+    let config = build_config(args, files.first().map(String::as_str))?;
 
     let options = LintOptions {
         files: files.clone(),
         strings: std::collections::HashMap::new(),
         config: Some(config),
         no_inline_config: args.no_inline_config,
+        report_unused_disables: args.report_unused_disable,
+        front_matter_patterns: args.front_matter.clone(),
         ..Default::default()
     };
 
-    let results = lint_sync(&options)?;
+    let results = at_severity(args, lint_sync(&options)?);
+    report_skipped_generated(args, &results);
+    report_skipped_binary(args, &results);
 
     // Pre-build workspace heading index once for convergence passes (fix/dry-run)
     let cached_headings = if files.len() > 1 && (args.fix || args.fix_dry_run) {
@@ -69,7 +258,7 @@ pub(crate) fn lint_files_once(args: &Args) -> Result<(), Box<dyn std::error::Err
     // Handle --fix-dry-run: show what would change without writing
     if args.fix_dry_run {
         let mut would_fix_count = 0;
-        for file_path in &files {
+        for file_path in files.iter().filter(|f| !results.is_skipped_binary(f)) {
             let content = std::fs::read_to_string(file_path)?;
             let mut current = content.clone();
 
@@ -81,6 +270,8 @@ pub(crate) fn lint_files_once(args: &Args) -> Result<(), Box<dyn std::error::Err
                     strings: [(file_path.clone(), current.clone())].into(),
                     config: options.config.clone(),
                     no_inline_config: args.no_inline_config,
+                    report_unused_disables: args.report_unused_disable,
+                    front_matter_patterns: args.front_matter.clone(),
                     cached_workspace_headings: cached_headings.clone(),
                     ..Default::default()
                 };
@@ -138,10 +329,12 @@ pub(crate) fn lint_files_once(args: &Args) -> Result<(), Box<dyn std::error::Err
         return Ok(());
     }
 
+    let shown = shown_results(args, &results);
+
     // Handle auto-fix
     if args.fix {
         let mut fixed_count = 0;
-        for file_path in &files {
+        for file_path in files.iter().filter(|f| !results.is_skipped_binary(f)) {
             let content = std::fs::read_to_string(file_path)?;
             let mut current = content.clone();
 
@@ -153,6 +346,8 @@ pub(crate) fn lint_files_once(args: &Args) -> Result<(), Box<dyn std::error::Err
                     strings: [(file_path.clone(), current.clone())].into(),
                     config: options.config.clone(),
                     no_inline_config: args.no_inline_config,
+                    report_unused_disables: args.report_unused_disable,
+                    front_matter_patterns: args.front_matter.clone(),
                     cached_workspace_headings: cached_headings.clone(),
                     ..Default::default()
                 };
@@ -187,32 +382,41 @@ pub(crate) fn lint_files_once(args: &Args) -> Result<(), Box<dyn std::error::Err
                 println!("{}", "No fixable issues found.".dimmed());
             }
         }
-    } else if results.is_empty() {
+    } else if shown.is_empty() {
         if !args.quiet {
             println!("{} No errors found!", "✓".green().bold());
         }
     } else {
         // Display errors
         if args.quiet {
-            for (file, errors) in &results.results {
+            for (file, errors) in &shown.results {
                 if !errors.is_empty() {
                     println!("{}", file);
                 }
             }
         } else {
             let output = match args.output_format {
-                OutputFormat::Text => {
-                    let mut sources = std::collections::HashMap::new();
-                    for file in &files {
-                        if let Ok(content) = std::fs::read_to_string(file) {
-                            sources.insert(file.clone(), content);
+                OutputFormat::Text => match &args.output_template {
+                    Some(template) => {
+                        format!("{}\n", formatters::format_text_template(&shown, template))
+                    }
+                    None => {
+                        let mut sources = std::collections::HashMap::new();
+                        for file in &files {
+                            if let Ok(content) = std::fs::read_to_string(file) {
+                                sources.insert(file.clone(), content);
+                            }
                         }
+                        formatters::format_text_with_context(&shown, &sources)
                     }
-                    formatters::format_text_with_context(&results, &sources)
+                },
+                OutputFormat::Json if args.statistics => {
+                    formatters::format_json_with_summary(&shown)
                 }
-                OutputFormat::Json => formatters::format_json(&results),
-                OutputFormat::Sarif => formatters::format_sarif(&results),
-                OutputFormat::Github => formatters::format_github(&results),
+                OutputFormat::Json => formatters::format_json(&shown),
+                OutputFormat::Sarif => formatters::format_sarif(&with_report_paths(args, &shown)),
+                OutputFormat::Github => formatters::format_github(&with_report_paths(args, &shown)),
+                OutputFormat::Ndjson => formatters::format_ndjson(&shown),
             };
             print!("{}", output);
         }
@@ -225,3 +429,49 @@ pub(crate) fn lint_files_once(args: &Args) -> Result<(), Box<dyn std::error::Err
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn test_strict_enables_every_rule_at_error_severity() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.json");
+        std::fs::write(
+            &config_path,
+            r#"{"preset": "github", "MD013": {"line_length": 100}, "MD041": "warning"}"#,
+        )
+        .unwrap();
+        let args = Args::parse_from([
+            "mkdlint",
+            "--no-global-config",
+            "--strict",
+            "--disable",
+            "no-inline-html",
+            "--config",
+            config_path.to_str().unwrap(),
+        ]);
+        let config = build_config(&args, None).unwrap();
+
+        // KMD002 is off unless enabled, and fires under --strict
+        let errors = mkdlint::lint_string("# Title\n\nA note[^1].\n", &config).unwrap();
+        let kmd002: Vec<&mkdlint::LintError> = errors
+            .iter()
+            .filter(|e| e.rule_names[0] == "KMD002")
+            .collect();
+        assert_eq!(kmd002.len(), 1);
+        assert_eq!(kmd002[0].severity, Severity::Error);
+
+        // The preset's MD013 opt-out is overridden but its options are kept
+        assert_eq!(config.get_rule_severity("MD013"), Some(Severity::Error));
+        assert_eq!(
+            serde_json::to_value(config.get_rule_config("MD013")).unwrap(),
+            serde_json::json!({"line_length": 100, "severity": "error"})
+        );
+        assert_eq!(config.get_rule_severity("MD041"), Some(Severity::Error));
+        // --disable still carves out exceptions, by ID or alias
+        assert!(!config.is_rule_enabled("MD033"));
+    }
+}
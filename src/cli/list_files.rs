@@ -0,0 +1,53 @@
+//! `--list-files` — print the resolved file set without linting
+
+use super::args::Args;
+use super::files::FileSet;
+use super::lint::build_config;
+
+/// Print each file that would be linted on stdout. With `--verbose`, also
+/// list excluded files and the reason on stderr.
+///
+/// Runs the same resolution as a lint run: directory expansion and the
+/// extension filter, symlink dedupe, `--ignore`, `--changed`, and the
+/// config's `skip_generated` markers.
+pub(crate) fn list_files(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let mut files = if args.stdin {
+        FileSet::from_paths(vec![
+            args.stdin_filename
+                .clone()
+                .unwrap_or_else(|| "-".to_string()),
+        ])
+    } else {
+        FileSet::expand(&args.files, args.follow_symlinks)
+    };
+    files.exclude_ignored(&args.ignore)?;
+    if !args.stdin
+        && let Some(base) = args.changed_base()
+    {
+        files.exclude_unchanged(base).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        });
+    }
+
+    let config_path = if args.stdin {
+        args.stdin_filename.clone()
+    } else {
+        files.included().into_iter().next()
+    };
+    let config = build_config(args, config_path.as_deref())?;
+    if !args.stdin
+        && let Some(skip) = &config.skip_generated
+    {
+        files.exclude_generated(skip);
+    }
+
+    for decision in files.decisions() {
+        match &decision.excluded {
+            None => println!("{}", decision.path),
+            Some(reason) if args.verbose => eprintln!("excluded: {} ({})", decision.path, reason),
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
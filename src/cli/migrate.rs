@@ -0,0 +1,89 @@
+//! `mkdlint config migrate` — convert a config file to another format
+
+use mkdlint::Config;
+use std::path::Path;
+
+/// Read the config at `from` and write it to `to` in the format of `to`'s
+/// extension, with rule aliases replaced by rule IDs
+pub(crate) fn migrate_config(from: &str, to: &str) -> Result<(), Box<dyn std::error::Error>> {
+    use colored::Colorize;
+
+    if Path::new(to).exists() {
+        eprintln!(
+            "{} Configuration file '{}' already exists.",
+            "Error:".red().bold(),
+            to
+        );
+        eprintln!("Remove it first or choose a different path with --to");
+        std::process::exit(1);
+    }
+    let extension = Path::new(to)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+
+    let mut config = Config::from_file(from).map_err(|e| format!("cannot read {}: {}", from, e))?;
+    let renamed = config.normalize_rule_keys();
+    let Some(content) = config.to_format_string(extension) else {
+        eprintln!(
+            "{} Unsupported format for '{}'. Use a .json, .yaml, .yml or .toml file.",
+            "Error:".red().bold(),
+            to
+        );
+        std::process::exit(1);
+    };
+    let content = note_renamed_keys(&content?, extension, &renamed);
+    std::fs::write(to, content)?;
+
+    println!("{} Migrated {} to {}", "✓".green().bold(), from, to.cyan());
+    for (alias, id) in &renamed {
+        println!("  {} → {}", alias, id);
+    }
+    Ok(())
+}
+
+/// `content` with a comment above each rule entry that was keyed by an
+/// alias in the source file, naming that key. JSON has no comments, so it
+/// is returned unchanged.
+fn note_renamed_keys(content: &str, extension: &str, renamed: &[(String, &str)]) -> String {
+    if extension == "json" || renamed.is_empty() {
+        return content.to_string();
+    }
+    let mut out = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let entry = renamed.iter().find(|(_, id)| {
+            line.strip_prefix(id)
+                .is_some_and(|rest| rest.starts_with(':') || rest.starts_with(" ="))
+                || line.trim_end() == format!("[{}]", id)
+        });
+        if let Some((alias, _)) = entry {
+            out.push_str(&format!("# Was '{}'\n", alias));
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_renamed_keys() {
+        let renamed = [
+            ("line-length".to_string(), "MD013"),
+            ("no-bare-urls".to_string(), "MD034"),
+        ];
+        let yaml = "default: true\nMD034: false\nMD013:\n  line_length: 100\n";
+        assert_eq!(
+            note_renamed_keys(yaml, "yaml", &renamed),
+            "default: true\n# Was 'no-bare-urls'\nMD034: false\n# Was 'line-length'\nMD013:\n  line_length: 100\n"
+        );
+        let toml = "MD034 = false\n\n[MD013]\nline_length = 100\n";
+        assert_eq!(
+            note_renamed_keys(toml, "toml", &renamed),
+            "# Was 'no-bare-urls'\nMD034 = false\n\n# Was 'line-length'\n[MD013]\nline_length = 100\n"
+        );
+        assert_eq!(note_renamed_keys("{}\n", "json", &renamed), "{}\n");
+    }
+}
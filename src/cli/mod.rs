@@ -1,36 +1,62 @@
 //! CLI entry point — module declarations and the `run()` dispatcher
 
 mod args;
+mod completions;
+mod detect;
 mod explain;
+mod explain_config;
 mod files;
+mod fix;
 mod init;
 mod lint;
+mod list_files;
+mod migrate;
 mod rules;
 mod schema;
+mod stdin_multi;
 mod watch;
 mod wizard;
 
-use args::{Args, Command, OutputFormat};
+use args::{Args, Command, ConfigCommand, OutputFormat};
 use clap::Parser;
-use files::{expand_paths, filter_ignored};
-use mkdlint::{LintOptions, apply_fixes, formatters, lint_sync};
+use files::FileSet;
+use mkdlint::{LintOptions, formatters, lint_sync};
 
 /// Main CLI entry point — parse args and dispatch to the appropriate handler
 pub fn run() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+
+    // `--changed REF` alone checks the whole working directory
+    if args.files.is_empty() && args.changed.is_some() && !args.stdin {
+        args.files.push(".".to_string());
+    }
 
     if args.no_color {
         colored::control::set_override(false);
     }
 
-    // Handle init subcommand
-    if let Some(Command::Init {
-        output,
-        format,
-        interactive,
-    }) = args.command
-    {
-        return init::init_config(&output, &format, interactive);
+    // Size the global rayon pool before anything lints
+    if let Some(jobs) = args.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs.get())
+            .build_global()?;
+    }
+
+    // Handle subcommands
+    match args.command {
+        Some(Command::Init {
+            ref output,
+            ref format,
+            interactive,
+        }) => return init::init_config(output, format, interactive),
+        Some(Command::Completions { shell }) => {
+            print!("{}", completions::completion_script(shell));
+            return Ok(());
+        }
+        Some(Command::Config(ConfigCommand::Migrate { ref from, ref to })) => {
+            return migrate::migrate_config(from, to);
+        }
+        None => {}
     }
 
     // Handle --generate-schema flag
@@ -56,12 +82,22 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         return explain::explain_rule(rule_name);
     }
 
+    // Handle --explain-config flag
+    if args.explain_config {
+        return explain_config::explain_config(&args);
+    }
+
     // Validate files are provided
     if args.files.is_empty() && !args.stdin {
         eprintln!("error: FILES argument required (or use --stdin)");
         std::process::exit(1);
     }
 
+    // Handle --list-files flag
+    if args.list_files {
+        return list_files::list_files(&args);
+    }
+
     // Watch mode requires files, not stdin
     if args.watch && args.stdin {
         eprintln!("error: --watch cannot be used with --stdin");
@@ -75,14 +111,32 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
 
     // Handle stdin input
     let (files, stdin_content) = if args.stdin {
-        (
-            vec!["-".to_string()],
-            Some(std::io::read_to_string(std::io::stdin())?),
-        )
+        let content = std::io::read_to_string(std::io::stdin())?;
+        // --ignore applies to the stdin path as if the file were on disk
+        if let Some(name) = &args.stdin_filename
+            && {
+                let mut named = FileSet::from_paths(vec![name.clone()]);
+                named.exclude_ignored(&args.ignore)?;
+                named.included().is_empty()
+            }
+        {
+            if !args.quiet {
+                println!("No files to lint.");
+            }
+            return Ok(());
+        }
+        (vec!["-".to_string()], Some(content))
     } else {
         // Expand directories and filter ignored files
-        let files = expand_paths(&args.files);
-        let files = filter_ignored(files, &args.ignore)?;
+        let mut files = FileSet::expand(&args.files, args.follow_symlinks);
+        files.exclude_ignored(&args.ignore)?;
+        if let Some(base) = args.changed_base() {
+            files.exclude_unchanged(base).unwrap_or_else(|e| {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            });
+        }
+        let files = files.included();
 
         if files.is_empty() {
             if !args.quiet {
@@ -93,41 +147,55 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         (files, None)
     };
 
-    // Build configuration with enable/disable rules
-    let mut config = if let Some(ref config_path) = args.config {
-        mkdlint::Config::from_file(config_path)?
+    // Build configuration; stdin content is placed by --stdin-filename
+    let config_path = if args.stdin {
+        args.stdin_filename.as_deref()
     } else {
-        mkdlint::Config::default()
+        files.first().map(String::as_str)
+    };
+    let config = lint::build_config(&args, config_path)?;
+
+    let stdin_key = args
+        .stdin_filename
+        .clone()
+        .unwrap_or_else(|| "-".to_string());
+
+    // --lines narrows the report for exactly one input
+    let line_ranges = match &args.lines {
+        Some(_) if files.len() != 1 => {
+            eprintln!(
+                "error: --lines needs exactly one input file, got {}",
+                files.len()
+            );
+            std::process::exit(1);
+        }
+        Some(lines) => {
+            let name = if args.stdin { &stdin_key } else { &files[0] };
+            Some([(name.clone(), vec![lines.to_range()])].into())
+        }
+        None => None,
     };
-
-    // Apply --enable and --disable flags
-    use mkdlint::RuleConfig;
-    for rule in &args.enable {
-        config
-            .rules
-            .insert(rule.to_uppercase(), RuleConfig::Enabled(true));
-    }
-    for rule in &args.disable {
-        config
-            .rules
-            .insert(rule.to_uppercase(), RuleConfig::Enabled(false));
-    }
-
-    // Apply --preset flag (overrides config-file preset if both are set)
-    if let Some(ref preset_name) = args.preset {
-        config.preset = Some(preset_name.clone());
-    }
-    // apply_preset is called inside resolve_extends() via load_config(),
-    // but since we bypass load_config here, call it explicitly.
-    config.apply_preset();
 
     let mut strings = std::collections::HashMap::new();
+    // --stdin-multi: where each document starts in the input
+    let mut line_offsets = std::collections::BTreeMap::new();
     if let Some(content) = stdin_content {
-        let stdin_key = args
-            .stdin_filename
-            .clone()
-            .unwrap_or_else(|| "-".to_string());
-        strings.insert(stdin_key, content);
+        if args.stdin_multi {
+            let separator = regex::Regex::new(&args.stdin_separator).unwrap_or_else(|e| {
+                eprintln!("error: invalid --stdin-separator: {}", e);
+                std::process::exit(1);
+            });
+            let documents = stdin_multi::split(&content, &separator).unwrap_or_else(|e| {
+                eprintln!("error: --stdin-multi: {}", e);
+                std::process::exit(1);
+            });
+            for document in documents {
+                line_offsets.insert(document.name.clone(), document.line_offset);
+                strings.insert(document.name, document.content);
+            }
+        } else {
+            strings.insert(stdin_key.clone(), content);
+        }
     }
 
     let options = LintOptions {
@@ -135,17 +203,43 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         strings,
         config: Some(config),
         no_inline_config: args.no_inline_config,
+        report_unused_disables: args.report_unused_disable,
+        front_matter_patterns: args.front_matter.clone(),
+        line_ranges,
         ..Default::default()
     };
 
-    let results = lint_sync(&options)?;
+    let ndjson = matches!(args.output_format, OutputFormat::Ndjson);
+
+    let filtered =
+        args.severity != args::MinSeverity::Warning || args.only_fixable || args.only_unfixable;
+
+    // NDJSON without fixing streams events as each file finishes; filtered
+    // runs are reported once linting is done
+    if ndjson && !args.fix && !args.fix_dry_run && !args.quiet && !filtered {
+        let results = lint::lint_ndjson_stream(&options)?;
+        if let Some(code) = exit_code(&args, &options, &results) {
+            std::process::exit(code);
+        }
+        return Ok(());
+    }
+
+    let mut results = lint_sync(&options)?;
+    results.line_offsets = line_offsets;
+    let results = lint::at_severity(&args, results);
+    lint::report_skipped_generated(&args, &results);
+    lint::report_skipped_binary(&args, &results);
 
     // Handle --fix-dry-run: show what would change without writing
     if args.fix_dry_run {
         use colored::Colorize;
         let mut would_fix_count = 0;
+        let mut summary = fix::FixSummary::default();
+        // With --output-format json, collect proposals instead of printing text
+        let json = matches!(args.output_format, OutputFormat::Json);
+        let mut proposals = Vec::new();
         let file_list: Vec<String> = if args.stdin {
-            vec!["-".to_string()]
+            vec![stdin_key.clone()]
         } else {
             files.clone()
         };
@@ -155,11 +249,8 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             let inputs: Vec<(String, String)> = file_list
                 .iter()
                 .filter_map(|f| {
-                    if f == "-" {
-                        options
-                            .strings
-                            .get("-")
-                            .map(|c| ("-".to_string(), c.clone()))
+                    if args.stdin {
+                        options.strings.get(f).map(|c| (f.clone(), c.clone()))
                     } else {
                         std::fs::read_to_string(f).ok().map(|c| (f.clone(), c))
                     }
@@ -170,45 +261,35 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             None
         };
 
-        for file_path in &file_list {
-            let content = if file_path == "-" {
+        for file_path in file_list.iter().filter(|f| !results.is_skipped_binary(f)) {
+            let content = if args.stdin {
                 options
                     .strings
-                    .get("-")
-                    .expect("stdin content must be present when reading from '-'")
+                    .get(file_path)
+                    .expect("stdin content must be present under its stdin key")
                     .clone()
             } else {
                 std::fs::read_to_string(file_path)?
             };
 
-            let mut current = content.clone();
-
-            // Multi-pass fix convergence for dry-run preview
-            for _pass in 0..10 {
-                // DEFAULT_FIX_PASSES = 10
-                let pass_options = LintOptions {
-                    files: vec![],
-                    strings: [(file_path.clone(), current.clone())].into(),
-                    config: options.config.clone(),
-                    no_inline_config: options.no_inline_config,
-                    front_matter: options.front_matter.clone(),
-                    cached_workspace_headings: cached_headings.clone(),
-                    ..Default::default()
-                };
-
-                let pass_results = lint_sync(&pass_options)?;
-                let pass_errors = pass_results.get(file_path).unwrap_or(&[]);
-
-                let next = apply_fixes(&current, pass_errors);
-                if next == current {
-                    break; // Converged
-                }
-                current = next;
-            }
+            let outcome = fix::converge(file_path, &content, &options, cached_headings.as_ref())?;
+            let current = &outcome.content;
 
-            if current != content {
+            if *current != content {
                 would_fix_count += 1;
-                if !args.quiet {
+                summary.add(&outcome);
+                if json {
+                    let original_errors = results.get(file_path).unwrap_or(&[]);
+                    proposals.push(formatters::FixProposal {
+                        applied: outcome.fixes_by_rule(),
+                        ..formatters::FixProposal::new(
+                            file_path,
+                            &content,
+                            original_errors,
+                            current,
+                        )
+                    });
+                } else if !args.quiet {
                     println!("{} {}", "Would fix:".yellow().bold(), file_path);
                     // Show errors from original lint
                     let original_errors = results.get(file_path).unwrap_or(&[]);
@@ -227,7 +308,11 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
-        if !args.quiet {
+        if json {
+            if !args.quiet {
+                println!("{}", formatters::format_fix_proposals_json(&proposals));
+            }
+        } else if !args.quiet {
             if would_fix_count > 0 {
                 println!(
                     "\n{} {} file(s) would be fixed (run with {} to apply).",
@@ -235,6 +320,7 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                     would_fix_count.to_string().yellow(),
                     "--fix".bold()
                 );
+                summary.print("would fix");
             } else {
                 println!("{}", "No fixable issues found.".dimmed());
             }
@@ -247,8 +333,14 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
 
     if args.fix {
         let mut fixed_count = 0;
+        let mut summary = fix::FixSummary::default();
+        // With --patch-output, fixes are collected here instead of written
+        let mut patch = String::new();
+        if ndjson && !args.stdin {
+            lint::emit_ndjson(&formatters::NdjsonEvent::start());
+        }
         let file_list = if args.stdin {
-            vec!["-".to_string()]
+            vec![stdin_key.clone()]
         } else {
             files.clone()
         };
@@ -258,11 +350,8 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             let inputs: Vec<(String, String)> = file_list
                 .iter()
                 .filter_map(|f| {
-                    if f == "-" {
-                        options
-                            .strings
-                            .get("-")
-                            .map(|c| ("-".to_string(), c.clone()))
+                    if args.stdin {
+                        options.strings.get(f).map(|c| (f.clone(), c.clone()))
                     } else {
                         std::fs::read_to_string(f).ok().map(|c| (f.clone(), c))
                     }
@@ -284,119 +373,195 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
                 continue;
             }
 
-            let content = if file_path == "-" {
+            let content = if args.stdin {
                 options
                     .strings
-                    .get("-")
-                    .expect("stdin content must be present when reading from '-'")
+                    .get(file_path)
+                    .expect("stdin content must be present under its stdin key")
                     .clone()
             } else {
                 std::fs::read_to_string(file_path)?
             };
 
             // Multi-pass fix convergence: re-lint and re-fix until stable
-            let mut current = content.clone();
-            for _pass in 0..10 {
-                // DEFAULT_FIX_PASSES = 10
-                // Re-lint the current content
-                let pass_options = LintOptions {
-                    files: vec![],
-                    strings: [(file_path.clone(), current.clone())].into(),
-                    config: options.config.clone(),
-                    no_inline_config: options.no_inline_config,
-                    front_matter: options.front_matter.clone(),
-                    cached_workspace_headings: cached_headings.clone(),
-                    ..Default::default()
-                };
-
-                let pass_results = lint_sync(&pass_options)?;
-                let pass_errors = pass_results.get(file_path).unwrap_or(&[]);
-
-                // Apply fixes
-                let next = apply_fixes(&current, pass_errors);
-                if next == current {
-                    break; // Converged
-                }
-                current = next;
-            }
+            let outcome = fix::converge(file_path, &content, &options, cached_headings.as_ref())?;
+            let current = outcome.content.as_str();
 
             if current != content {
-                if file_path == "-" {
+                summary.add(&outcome);
+                if args.stdin {
                     // Output to stdout
                     print!("{}", current);
                 } else {
-                    std::fs::write(file_path, &current)?;
+                    if args.patch_output.is_some() {
+                        patch.push_str(&formatters::unified_diff(file_path, &content, current));
+                    } else {
+                        std::fs::write(file_path, current)?;
+                    }
                     fixed_count += 1;
-                    if args.verbose || !args.quiet {
+                    if ndjson {
+                        let fixes = outcome.fixes_by_rule();
+                        lint::emit_ndjson(&formatters::NdjsonEvent::Fixed {
+                            path: file_path.clone(),
+                            rules: fixes.keys().cloned().collect(),
+                            fixes,
+                        });
+                    } else if args.verbose || !args.quiet {
                         println!("Fixed: {}", file_path);
                     }
                 }
             }
         }
 
-        if !args.quiet && !args.stdin {
-            if fixed_count > 0 {
+        if let Some(patch_path) = &args.patch_output {
+            std::fs::write(patch_path, &patch)?;
+        }
+
+        if ndjson && !args.stdin {
+            lint::emit_ndjson(&formatters::ndjson_summary(&results));
+        } else if !args.quiet && !args.stdin {
+            if let Some(patch_path) = &args.patch_output {
+                println!("{} file(s) fixed in {}.", fixed_count, patch_path);
+            } else if fixed_count > 0 {
                 println!("{} file(s) fixed.", fixed_count);
             } else {
                 println!("No fixable issues found.");
             }
+            summary.print("fixed");
         }
     } else if results.is_empty() {
         if !args.quiet {
             println!("No errors found!");
         }
     } else {
+        let shown = lint::shown_results(&args, &results);
         // Handle different output modes
         if args.quiet {
             // Quiet mode: just list files with errors
-            for (file, errors) in &results.results {
+            for (file, errors) in &shown.results {
                 if !errors.is_empty() {
                     println!("{}", file);
                 }
             }
         } else {
             let output = match args.output_format {
-                OutputFormat::Text => {
-                    // Read source files for context display
-                    let mut sources = std::collections::HashMap::new();
-                    if args.stdin {
-                        let stdin_key = args
-                            .stdin_filename
-                            .clone()
-                            .unwrap_or_else(|| "-".to_string());
-                        if let Some(content) = options.strings.get(&stdin_key) {
-                            sources.insert(stdin_key, content.clone());
-                        }
-                    } else {
-                        for file_path in &files {
-                            if let Ok(content) = std::fs::read_to_string(file_path) {
-                                sources.insert(file_path.clone(), content);
+                OutputFormat::Text => match &args.output_template {
+                    Some(template) => formatters::format_text_template(&shown, template),
+                    None => {
+                        // Read source files for context display
+                        let mut sources = std::collections::HashMap::new();
+                        if args.stdin {
+                            sources.extend(options.strings.clone());
+                        } else {
+                            for file_path in &files {
+                                if let Ok(content) = std::fs::read_to_string(file_path) {
+                                    sources.insert(file_path.clone(), content);
+                                }
                             }
                         }
-                    }
 
-                    let formatted = formatters::format_text_with_context(&results, &sources);
-
-                    // Add summary if verbose
-                    if args.verbose {
-                        let total_errors: usize = results.results.values().map(|e| e.len()).sum();
-                        let total_files = results.results.len();
-                        format!(
-                            "{}\n\nSummary: {} error(s) in {} file(s)",
-                            formatted, total_errors, total_files
-                        )
-                    } else {
-                        formatted
+                        let formatted = formatters::format_text_with_context(&shown, &sources);
+
+                        // Add summary if verbose
+                        if args.verbose {
+                            let total_errors: usize = shown.results.values().map(|e| e.len()).sum();
+                            let total_files = shown.results.len();
+                            format!(
+                                "{}\n\nSummary: {} error(s) in {} file(s)",
+                                formatted, total_errors, total_files
+                            )
+                        } else {
+                            formatted
+                        }
                     }
+                },
+                OutputFormat::Json if args.statistics => {
+                    formatters::format_json_with_summary(&shown)
+                }
+                OutputFormat::Json => formatters::format_json(&shown),
+                OutputFormat::Sarif => {
+                    formatters::format_sarif(&lint::with_report_paths(&args, &shown))
                 }
-                OutputFormat::Json => formatters::format_json(&results),
-                OutputFormat::Sarif => formatters::format_sarif(&results),
-                OutputFormat::Github => formatters::format_github(&results),
+                OutputFormat::Github => {
+                    formatters::format_github(&lint::with_report_paths(&args, &shown))
+                }
+                OutputFormat::Ndjson => formatters::format_ndjson(&shown),
             };
-            println!("{}", output);
+            // Empty when filters hide every violation
+            if !output.is_empty() {
+                println!("{}", output);
+            }
+        }
+        if let Some(code) = exit_code(&args, &options, &results) {
+            std::process::exit(code);
         }
-        std::process::exit(1);
     }
 
     Ok(())
 }
+
+/// Exit code when the only failures are rules that panicked
+const EXIT_INTERNAL_ERROR: i32 = 2;
+
+/// Non-zero exit code for a lint run, if it failed
+///
+/// Violations exit 1, except MKD006 warnings: directive hygiene is reported
+/// without failing the run unless MKD006 is configured as an error or
+/// `--report-unused-disable` asks for it. With
+/// `--enforce-budgets` only rules over their configured budget count, and
+/// each one is reported on stderr. Rules that
+/// panicked are reported on stderr and exit [`EXIT_INTERNAL_ERROR`] unless
+/// something else already failed the run.
+fn exit_code(args: &Args, options: &LintOptions, results: &mkdlint::LintResults) -> Option<i32> {
+    let mut internal_errors = 0;
+    for (file, error) in results.internal_errors() {
+        internal_errors += 1;
+        eprintln!(
+            "{}: {}",
+            file,
+            error.error_detail.as_deref().unwrap_or_default()
+        );
+    }
+
+    let failed = if args.enforce_budgets {
+        budgets_exceeded(options, results)
+    } else {
+        results.results.values().flatten().any(|e| {
+            !e.is_internal_error()
+                && !e.fix_only
+                && (args.report_unused_disable || !is_directive_warning(e))
+        })
+    };
+
+    if failed {
+        Some(1)
+    } else if internal_errors > 0 {
+        Some(EXIT_INTERNAL_ERROR)
+    } else {
+        None
+    }
+}
+
+/// Whether `error` is an MKD006 finding at warning severity
+fn is_directive_warning(error: &mkdlint::LintError) -> bool {
+    error.severity == mkdlint::types::Severity::Warning
+        && error.rule_names.first() == Some(&"MKD006")
+}
+
+/// Whether any rule exceeds its budget, reporting each one on stderr
+fn budgets_exceeded(options: &LintOptions, results: &mkdlint::LintResults) -> bool {
+    let Some(config) = options.config.as_ref() else {
+        return false;
+    };
+    if config.budgets.is_empty() {
+        eprintln!("warning: --enforce-budgets given but the config sets no budgets");
+    }
+    let overruns = config.over_budget(results);
+    for overrun in &overruns {
+        eprintln!(
+            "{}: {} violation(s) exceed budget of {}",
+            overrun.rule, overrun.count, overrun.budget
+        );
+    }
+    !overruns.is_empty()
+}
@@ -16,8 +16,13 @@ pub(crate) fn generate_config_schema() -> String {
         let description = rule.description();
         let tags: Vec<&str> = rule.tags().to_vec();
         let is_fixable = tags.contains(&"fixable");
+        let options: serde_json::Map<String, serde_json::Value> =
+            mkdlint::rules::rule_option_docs(id)
+                .iter()
+                .map(|option| (option.name.to_string(), option_schema(option)))
+                .collect();
 
-        // Each rule can be true/false, "warning"/"error", or an object with options
+        // Each rule can be true/false, "warning"/"error"/"off", or an object with options
         let prop = serde_json::json!({
             "description": format!(
                 "{description}{}",
@@ -27,12 +32,13 @@ pub(crate) fn generate_config_schema() -> String {
                 { "type": "boolean", "description": "Enable or disable the rule" },
                 {
                     "type": "string",
-                    "enum": ["error", "warning"],
-                    "description": "Set severity level"
+                    "enum": ["error", "warning", "off"],
+                    "description": "Set severity level, or disable the rule with \"off\""
                 },
                 {
                     "type": "object",
                     "description": "Rule-specific options",
+                    "properties": options,
                     "additionalProperties": true
                 }
             ]
@@ -63,6 +69,63 @@ pub(crate) fn generate_config_schema() -> String {
             "enum": ["kramdown", "github"]
         }),
     );
+    properties.insert(
+        "budgets".to_string(),
+        serde_json::json!({
+            "description": "Most violations allowed per rule, enforced with --enforce-budgets",
+            "type": "object",
+            "additionalProperties": { "type": "integer", "minimum": 0 }
+        }),
+    );
+    properties.insert(
+        "skip_generated".to_string(),
+        serde_json::json!({
+            "description": "Skip files with a generated-file marker near the top",
+            "type": "object",
+            "properties": {
+                "markers": {
+                    "description": "Text that marks a file as generated",
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "default": ["AUTO-GENERATED", "@generated"]
+                },
+                "search_lines": {
+                    "description": "How many lines from the top of the file to search",
+                    "type": "integer",
+                    "minimum": 0,
+                    "default": 5
+                }
+            },
+            "additionalProperties": false
+        }),
+    );
+    properties.insert(
+        "foreign_directives".to_string(),
+        serde_json::json!({
+            "description": "Other tools' comment directives (e.g. 'prettier-ignore') and the action each maps to",
+            "type": "object",
+            "additionalProperties": {
+                "type": "string",
+                "enum": ["ignore", "disable-next-line", "disable", "enable"]
+            }
+        }),
+    );
+    properties.insert(
+        "no_fix".to_string(),
+        serde_json::json!({
+            "description": "Rules (IDs or aliases) whose violations are reported but never auto-fixed",
+            "type": "array",
+            "items": { "type": "string" }
+        }),
+    );
+    properties.insert(
+        "math".to_string(),
+        serde_json::json!({
+            "description": "Recognize $...$ and $$...$$ as math, which emphasis and whitespace rules then skip",
+            "type": "boolean",
+            "default": true
+        }),
+    );
     for (k, v) in rule_props {
         properties.insert(k, v);
     }
@@ -77,7 +140,7 @@ pub(crate) fn generate_config_schema() -> String {
             "description": "Rule ID or alias (true/false/severity/options)",
             "oneOf": [
                 { "type": "boolean" },
-                { "type": "string", "enum": ["error", "warning"] },
+                { "type": "string", "enum": ["error", "warning", "off"] },
                 { "type": "object", "additionalProperties": true }
             ]
         }
@@ -86,3 +149,81 @@ pub(crate) fn generate_config_schema() -> String {
     serde_json::to_string_pretty(&final_schema)
         .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}"))
 }
+
+/// Schema for a rule option, from its row in the rule doc's option table:
+/// the type, the description and, when it is a JSON value, the default
+fn option_schema(option: &mkdlint::rules::RuleOptionDoc) -> serde_json::Value {
+    use serde_json::json;
+
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+    let mut schema = match option.kind {
+        "boolean" => json!({ "type": "boolean" }),
+        "integer" => json!({ "type": "integer", "minimum": 0 }),
+        "integer array" => json!({
+            "type": "array",
+            "items": { "type": "integer", "minimum": 0 }
+        }),
+        "string" => json!({ "type": "string" }),
+        "string array" | "string[]" | "regex array" => strings,
+        "string or `false`" => json!({ "oneOf": [{ "type": "string" }, { "const": false }] }),
+        "object" => json!({ "type": "object" }),
+        _ => json!({}),
+    };
+    schema["description"] = option.description.into();
+    let default = option
+        .default
+        .strip_prefix('`')
+        .and_then(|d| d.strip_suffix('`'));
+    if let Some(default) = default.and_then(|d| serde_json::from_str::<serde_json::Value>(d).ok()) {
+        schema["default"] = default;
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_documented_option_has_a_type() {
+        for rule in mkdlint::rules::get_rules().iter() {
+            for option in mkdlint::rules::rule_option_docs(rule.names()[0]) {
+                let schema = option_schema(&option);
+                assert!(
+                    schema.get("type").is_some() || schema.get("oneOf").is_some(),
+                    "{} option {} has unknown type {:?}",
+                    rule.names()[0],
+                    option.name,
+                    option.kind
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_option_schema_from_doc_row() {
+        let schema: serde_json::Value = serde_json::from_str(&generate_config_schema()).unwrap();
+        let options = |rule: &str| schema["properties"][rule]["oneOf"][2]["properties"].clone();
+
+        let newlines = &options("MD047")["newlines"];
+        assert_eq!(newlines["type"], "integer");
+        assert_eq!(newlines["minimum"], 0);
+        assert!(newlines.get("default").is_none());
+        assert!(
+            newlines["description"]
+                .as_str()
+                .unwrap()
+                .starts_with("Exact number of newlines")
+        );
+        assert_eq!(options("MD013")["line_length"]["default"], 80);
+        assert_eq!(options("MD003")["style"]["default"], "consistent");
+        assert_eq!(
+            options("MD033")["allowed_elements"]["items"]["type"],
+            "string"
+        );
+        assert_eq!(
+            options("MD025")["front_matter_title"]["oneOf"][1]["const"],
+            false
+        );
+    }
+}
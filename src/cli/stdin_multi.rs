@@ -0,0 +1,100 @@
+//! `--stdin-multi` — split concatenated stdin into separately linted documents
+
+use regex::Regex;
+
+/// Marker lines that start a new document: a form feed on its own line, or
+/// `<!-- mkdlint:file NAME -->`, which also names the document
+pub(crate) const DEFAULT_SEPARATOR: &str = r"^\s*(?:\x0c|<!--\s*mkdlint:file\s+(\S+?)\s*-->)\s*$";
+
+/// One document cut from the input
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Document {
+    /// The marker's first capture group, or `stdin:N` for the Nth document
+    pub(crate) name: String,
+    pub(crate) content: String,
+    /// Input lines before the document's first line
+    pub(crate) line_offset: usize,
+}
+
+/// Split `input` at lines matching `separator`; marker lines belong to no
+/// document.
+///
+/// Text before the first marker is a document only if it is not blank, and
+/// so is an unnamed document after the last marker (a trailing form feed
+/// adds nothing). A name used twice is an error.
+pub(crate) fn split(input: &str, separator: &Regex) -> Result<Vec<Document>, String> {
+    // (name from the marker, content, line offset) of the documents so far
+    let mut parts: Vec<(Option<String>, String, usize)> = vec![(None, String::new(), 0)];
+    for (index, line) in input.split_inclusive('\n').enumerate() {
+        let text = line.trim_end_matches(['\n', '\r']);
+        if let Some(captures) = separator.captures(text) {
+            let name = captures.get(1).map(|m| m.as_str().to_string());
+            parts.push((name, String::new(), index + 1));
+        } else if let Some((_, content, _)) = parts.last_mut() {
+            content.push_str(line);
+        }
+    }
+
+    let mut documents: Vec<Document> = Vec::new();
+    for (name, content, line_offset) in parts {
+        if name.is_none() && content.trim().is_empty() {
+            continue;
+        }
+        let name = name.unwrap_or_else(|| format!("stdin:{}", documents.len() + 1));
+        if documents.iter().any(|d| d.name == name) {
+            return Err(format!("document name '{}' is used more than once", name));
+        }
+        documents.push(Document {
+            name,
+            content,
+            line_offset,
+        });
+    }
+    Ok(documents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names_and_offsets(input: &str) -> Vec<(String, usize)> {
+        split(input, &Regex::new(DEFAULT_SEPARATOR).unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.name, d.line_offset))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_on_default_markers() {
+        let input = "# One\n\x0c\n# Two\n\n<!-- mkdlint:file docs/three.md -->\n# Three\n\x0c\n";
+        let documents = split(input, &Regex::new(DEFAULT_SEPARATOR).unwrap()).unwrap();
+        assert_eq!(documents.len(), 3);
+        assert_eq!(documents[0].content, "# One\n");
+        assert_eq!(documents[1].content, "# Two\n\n");
+        assert_eq!(
+            names_and_offsets(input),
+            [
+                ("stdin:1".to_string(), 0),
+                ("stdin:2".to_string(), 2),
+                ("docs/three.md".to_string(), 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_skips_blank_leading_text() {
+        assert_eq!(
+            names_and_offsets("\n<!-- mkdlint:file a.md -->\n# A\n\x0c\r\n# B\n"),
+            [("a.md".to_string(), 2), ("stdin:2".to_string(), 4)]
+        );
+        assert_eq!(names_and_offsets("# Only\n"), [("stdin:1".to_string(), 0)]);
+    }
+
+    #[test]
+    fn test_split_rejects_duplicate_names() {
+        let separator = Regex::new(DEFAULT_SEPARATOR).unwrap();
+        let input = "<!-- mkdlint:file a.md -->\n# A\n<!-- mkdlint:file a.md -->\n# B\n";
+        assert!(split(input, &separator).unwrap_err().contains("'a.md'"));
+    }
+}
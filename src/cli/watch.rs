@@ -59,12 +59,10 @@ pub(crate) fn run_watch_mode(args: &Args) -> Result<(), Box<dyn std::error::Erro
                 Ok(events) => {
                     // Filter for markdown file changes
                     let has_markdown_changes = events.iter().any(|event| {
-                        event.paths.iter().any(|path| {
-                            path.extension()
-                                .and_then(|ext| ext.to_str())
-                                .map(|ext| ext == "md" || ext == "markdown")
-                                .unwrap_or(false)
-                        })
+                        event
+                            .paths
+                            .iter()
+                            .any(|path| super::files::is_markdown_path(path))
                     });
 
                     if has_markdown_changes {
@@ -1,5 +1,11 @@
 //! Interactive configuration wizard
 
+use super::detect::{Conventions, detect_conventions};
+use mkdlint::config::{Config, RuleConfig};
+
+/// Most Markdown files the wizard scans for existing conventions
+const SCAN_LIMIT: usize = 200;
+
 /// Config options collected from wizard
 pub(crate) struct ConfigOptions<'a> {
     pub(crate) line_length: usize,
@@ -10,239 +16,94 @@ pub(crate) struct ConfigOptions<'a> {
     pub(crate) allow_html: bool,
     pub(crate) allowed_elements: Vec<&'a str>,
     pub(crate) code_style: &'a str,
+    pub(crate) code_fence_style: &'a str,
+    /// Newlines files must end with (MD047); 1 keeps the default
+    pub(crate) final_newlines: usize,
     pub(crate) disabled_rules: Vec<&'a str>,
 }
 
-/// Generate configuration content based on wizard answers
-pub(crate) fn generate_config(format: &str, options: &ConfigOptions) -> String {
-    match format {
-        "json" => generate_json_config(options),
-        "yaml" => generate_yaml_config(options),
-        "toml" => generate_toml_config(options),
-        _ => String::new(),
-    }
-}
-
-pub(crate) fn generate_json_config(options: &ConfigOptions) -> String {
-    let schema_url =
-        "https://raw.githubusercontent.com/192d-Wing/mkdlint/main/schema/mkdlint-schema.json";
-    let mut config = format!("{{\n  \"$schema\": \"{schema_url}\",\n  \"default\": true");
+/// Published JSON schema for config files
+const SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/192d-Wing/mkdlint/main/schema/mkdlint-schema.json";
 
-    // Disabled rules
-    for rule in &options.disabled_rules {
-        config.push_str(&format!(",\n  \"{}\": false", rule));
-    }
-
-    // Line length
-    if options.line_length > 0 {
-        config.push_str(&format!(
-            ",\n  \"MD013\": {{\n    \"line_length\": {},\n    \"code_blocks\": false,\n    \"tables\": false\n  }}",
-            options.line_length
-        ));
-    }
+/// The configuration the wizard's answers describe
+pub(crate) fn build_config(options: &ConfigOptions) -> Config {
+    use serde_json::json;
 
-    // Heading style (MD003)
-    if options.heading_style != "consistent" {
-        config.push_str(&format!(
-            ",\n  \"MD003\": {{\n    \"style\": \"{}\"\n  }}",
-            options.heading_style
-        ));
-    }
-
-    // List marker style (MD004)
-    if options.list_marker != "consistent" {
-        config.push_str(&format!(
-            ",\n  \"MD004\": {{\n    \"style\": \"{}\"\n  }}",
-            options.list_marker
-        ));
-    }
-
-    // Inline HTML (MD033)
-    if options.allow_html && !options.allowed_elements.is_empty() {
-        let elements_json = options
-            .allowed_elements
-            .iter()
-            .map(|e| format!("\"{}\"", e))
-            .collect::<Vec<_>>()
-            .join(", ");
-        config.push_str(&format!(
-            ",\n  \"MD033\": {{\n    \"allowed_elements\": [{}]\n  }}",
-            elements_json
-        ));
-    }
-
-    // Code block style (MD046)
-    if options.code_style != "consistent" {
-        config.push_str(&format!(
-            ",\n  \"MD046\": {{\n    \"style\": \"{}\"\n  }}",
-            options.code_style
-        ));
-    }
-
-    // Emphasis style (MD049)
-    if options.emphasis_style != "consistent" {
-        config.push_str(&format!(
-            ",\n  \"MD049\": {{\n    \"style\": \"{}\"\n  }}",
-            options.emphasis_style
-        ));
-    }
-
-    // Strong emphasis style (MD050)
-    if options.strong_style != "consistent" {
-        config.push_str(&format!(
-            ",\n  \"MD050\": {{\n    \"style\": \"{}\"\n  }}",
-            options.strong_style
-        ));
-    }
-
-    config.push_str("\n}\n");
-    config
-}
-
-pub(crate) fn generate_yaml_config(options: &ConfigOptions) -> String {
-    let mut config = String::from(
-        "# mkdlint configuration\n# Schema: https://raw.githubusercontent.com/192d-Wing/mkdlint/main/schema/mkdlint-schema.json\ndefault: true\n",
-    );
+    let mut config = Config {
+        default: Some(true),
+        ..Default::default()
+    };
+    // A rule the user disabled keeps that setting over any style answer
+    let mut set = |rule: &str, value: serde_json::Value| {
+        let value = serde_json::from_value(value).expect("rule settings are valid");
+        config.rules.entry(rule.to_string()).or_insert(value);
+    };
 
-    // Disabled rules
     for rule in &options.disabled_rules {
-        config.push_str(&format!("\n{}: false", rule));
+        set(rule, json!(false));
     }
-
-    // Line length
     if options.line_length > 0 {
-        config.push_str(&format!(
-            "\n\n# Line length\nMD013:\n  line_length: {}\n  code_blocks: false\n  tables: false",
-            options.line_length
-        ));
-    }
-
-    // Heading style (MD003)
-    if options.heading_style != "consistent" {
-        config.push_str(&format!(
-            "\n\n# Heading style\nMD003:\n  style: {}",
-            options.heading_style
-        ));
-    }
-
-    // List marker style (MD004)
-    if options.list_marker != "consistent" {
-        config.push_str(&format!(
-            "\n\n# List marker\nMD004:\n  style: {}",
-            options.list_marker
-        ));
+        set(
+            "MD013",
+            json!({"line_length": options.line_length, "code_blocks": false, "tables": false}),
+        );
     }
-
-    // Inline HTML (MD033)
-    if options.allow_html && !options.allowed_elements.is_empty() {
-        config.push_str("\n\n# Inline HTML\nMD033:\n  allowed_elements:");
-        for element in &options.allowed_elements {
-            config.push_str(&format!("\n    - {}", element));
+    let styles = [
+        ("MD003", options.heading_style),
+        ("MD004", options.list_marker),
+        ("MD046", options.code_style),
+        ("MD048", options.code_fence_style),
+        ("MD049", options.emphasis_style),
+        ("MD050", options.strong_style),
+    ];
+    for (rule, style) in styles {
+        if style != "consistent" {
+            set(rule, json!({ "style": style }));
         }
     }
-
-    // Code block style (MD046)
-    if options.code_style != "consistent" {
-        config.push_str(&format!(
-            "\n\n# Code block style\nMD046:\n  style: {}",
-            options.code_style
-        ));
-    }
-
-    // Emphasis style (MD049)
-    if options.emphasis_style != "consistent" {
-        config.push_str(&format!(
-            "\n\n# Emphasis style\nMD049:\n  style: {}",
-            options.emphasis_style
-        ));
+    if options.allow_html && !options.allowed_elements.is_empty() {
+        set(
+            "MD033",
+            json!({ "allowed_elements": options.allowed_elements }),
+        );
     }
-
-    // Strong emphasis style (MD050)
-    if options.strong_style != "consistent" {
-        config.push_str(&format!(
-            "\n\n# Strong emphasis style\nMD050:\n  style: {}",
-            options.strong_style
-        ));
+    if options.final_newlines != 1 {
+        set("MD047", json!({ "newlines": options.final_newlines }));
     }
-
-    config.push('\n');
     config
 }
 
-pub(crate) fn generate_toml_config(options: &ConfigOptions) -> String {
-    let mut config = String::from(
-        "# mkdlint configuration\n# Schema: https://raw.githubusercontent.com/192d-Wing/mkdlint/main/schema/mkdlint-schema.json\ndefault = true\n",
-    );
-
-    // Disabled rules
-    for rule in &options.disabled_rules {
-        config.push_str(&format!("\n{} = false", rule));
-    }
-
-    // Line length
-    if options.line_length > 0 {
-        config.push_str(&format!(
-            "\n\n# Line length\n[MD013]\nline_length = {}\ncode_blocks = false\ntables = false",
-            options.line_length
-        ));
-    }
-
-    // Heading style (MD003)
-    if options.heading_style != "consistent" {
-        config.push_str(&format!(
-            "\n\n# Heading style\n[MD003]\nstyle = \"{}\"",
-            options.heading_style
-        ));
-    }
-
-    // List marker style (MD004)
-    if options.list_marker != "consistent" {
-        config.push_str(&format!(
-            "\n\n# List marker\n[MD004]\nstyle = \"{}\"",
-            options.list_marker
-        ));
-    }
-
-    // Inline HTML (MD033)
-    if options.allow_html && !options.allowed_elements.is_empty() {
-        let elements_toml = options
-            .allowed_elements
-            .iter()
-            .map(|e| format!("\"{}\"", e))
-            .collect::<Vec<_>>()
-            .join(", ");
-        config.push_str(&format!(
-            "\n\n# Inline HTML\n[MD033]\nallowed_elements = [{}]",
-            elements_toml
-        ));
-    }
-
-    // Code block style (MD046)
-    if options.code_style != "consistent" {
-        config.push_str(&format!(
-            "\n\n# Code block style\n[MD046]\nstyle = \"{}\"",
-            options.code_style
-        ));
-    }
-
-    // Emphasis style (MD049)
-    if options.emphasis_style != "consistent" {
-        config.push_str(&format!(
-            "\n\n# Emphasis style\n[MD049]\nstyle = \"{}\"",
-            options.emphasis_style
-        ));
-    }
-
-    // Strong emphasis style (MD050)
-    if options.strong_style != "consistent" {
-        config.push_str(&format!(
-            "\n\n# Strong emphasis style\n[MD050]\nstyle = \"{}\"",
-            options.strong_style
-        ));
+/// Generate configuration content based on wizard answers
+///
+/// JSON files point `$schema` at the published schema; YAML and TOML
+/// files name it in a comment.
+pub(crate) fn generate_config(
+    format: &str,
+    options: &ConfigOptions,
+) -> mkdlint::types::Result<String> {
+    let mut config = build_config(options);
+    if format == "json" {
+        config.rules.insert(
+            "$schema".to_string(),
+            RuleConfig::Severity(SCHEMA_URL.to_string()),
+        );
+        return config.to_json_string();
     }
+    let body = config
+        .to_format_string(format)
+        .unwrap_or_else(|| Ok(String::new()))?;
+    Ok(format!(
+        "# mkdlint configuration\n# Schema: {}\n{}",
+        SCHEMA_URL, body
+    ))
+}
 
-    config.push('\n');
-    config
+/// Index of the `detected` style in `values`, or the first entry
+fn default_index(values: &[&str], detected: Option<&str>) -> usize {
+    detected
+        .and_then(|style| values.iter().position(|value| *value == style))
+        .unwrap_or(0)
 }
 
 /// Interactive configuration wizard
@@ -258,6 +119,27 @@ pub(crate) fn init_config_interactive(
     println!("This wizard will help you create a custom configuration file.");
     println!();
 
+    // Step 0: Prefill the answers from the repository's existing files
+    let detected = if Confirm::new()
+        .with_prompt("Detect current conventions from existing files?")
+        .default(true)
+        .interact()?
+    {
+        let scan_dir = std::path::Path::new(output_path)
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(std::path::Path::new("."));
+        let conventions = detect_conventions(scan_dir, SCAN_LIMIT);
+        println!(
+            "Scanned {} Markdown file(s); detected styles are offered as the defaults.",
+            conventions.files_scanned
+        );
+        println!();
+        conventions
+    } else {
+        Conventions::default()
+    };
+
     // Question 1: Format preference (already set via --format, but allow override)
     let formats = vec!["JSON", "YAML", "TOML"];
     let default_format_idx = match format {
@@ -277,9 +159,15 @@ pub(crate) fn init_config_interactive(
     };
 
     // Question 2: Line length
+    // Long enough that no existing prose line breaks the limit
+    let default_line_length = if detected.max_line_length > 0 {
+        detected.max_line_length.max(80)
+    } else {
+        120
+    };
     let line_length: usize = Input::new()
         .with_prompt("Maximum line length (0 to disable)")
-        .default(120)
+        .default(default_line_length)
         .interact()?;
 
     // Question 3: Heading style
@@ -288,16 +176,16 @@ pub(crate) fn init_config_interactive(
         "Setext (Underlined)",
         "Consistent (auto-detect)",
     ];
+    let heading_values = ["atx", "setext", "consistent"];
     let heading_style_idx = Select::new()
         .with_prompt("Preferred heading style?")
         .items(&heading_styles)
-        .default(0)
+        .default(default_index(
+            &heading_values,
+            detected.heading_style.dominant(),
+        ))
         .interact()?;
-    let heading_style = match heading_style_idx {
-        1 => "setext",
-        2 => "consistent",
-        _ => "atx",
-    };
+    let heading_style = heading_values[heading_style_idx];
 
     // Question 4: List marker style
     let list_markers = vec![
@@ -306,43 +194,41 @@ pub(crate) fn init_config_interactive(
         "Plus (+)",
         "Consistent (auto-detect)",
     ];
+    let list_marker_values = ["dash", "asterisk", "plus", "consistent"];
     let list_marker_idx = Select::new()
         .with_prompt("Preferred unordered list marker?")
         .items(&list_markers)
-        .default(0)
+        .default(default_index(
+            &list_marker_values,
+            detected.list_marker.dominant(),
+        ))
         .interact()?;
-    let list_marker = match list_marker_idx {
-        1 => "asterisk",
-        2 => "plus",
-        3 => "consistent",
-        _ => "dash",
-    };
+    let list_marker = list_marker_values[list_marker_idx];
 
     // Question 5: Emphasis style
     let emphasis_styles = vec!["Asterisk (*text*)", "Underscore (_text_)", "Consistent"];
+    let emphasis_values = ["asterisk", "underscore", "consistent"];
     let emphasis_style_idx = Select::new()
         .with_prompt("Preferred emphasis style?")
         .items(&emphasis_styles)
-        .default(0)
+        .default(default_index(
+            &emphasis_values,
+            detected.emphasis_style.dominant(),
+        ))
         .interact()?;
-    let emphasis_style = match emphasis_style_idx {
-        1 => "underscore",
-        2 => "consistent",
-        _ => "asterisk",
-    };
+    let emphasis_style = emphasis_values[emphasis_style_idx];
 
     // Question 6: Strong emphasis style
     let strong_styles = vec!["Asterisk (**text**)", "Underscore (__text__)", "Consistent"];
     let strong_style_idx = Select::new()
         .with_prompt("Preferred strong emphasis style?")
         .items(&strong_styles)
-        .default(0)
+        .default(default_index(
+            &emphasis_values,
+            detected.strong_style.dominant(),
+        ))
         .interact()?;
-    let strong_style = match strong_style_idx {
-        1 => "underscore",
-        2 => "consistent",
-        _ => "asterisk",
-    };
+    let strong_style = emphasis_values[strong_style_idx];
 
     // Question 7: Inline HTML
     let allow_html = Confirm::new()
@@ -370,18 +256,42 @@ pub(crate) fn init_config_interactive(
 
     // Question 8: Code block style
     let code_styles = vec!["Fenced (```)", "Indented (4 spaces)", "Consistent"];
+    let code_values = ["fenced", "indented", "consistent"];
     let code_style_idx = Select::new()
         .with_prompt("Preferred code block style?")
         .items(&code_styles)
+        .default(default_index(&code_values, detected.code_style.dominant()))
+        .interact()?;
+    let code_style = code_values[code_style_idx];
+
+    // Question 9: Code fence style
+    let code_fence_styles = vec!["Backtick (```)", "Tilde (~~~)", "Consistent"];
+    let code_fence_values = ["backtick", "tilde", "consistent"];
+    let code_fence_style_idx = Select::new()
+        .with_prompt("Preferred code fence style?")
+        .items(&code_fence_styles)
+        .default(default_index(
+            &code_fence_values,
+            detected.code_fence_style.dominant(),
+        ))
+        .interact()?;
+    let code_fence_style = code_fence_values[code_fence_style_idx];
+
+    // Question 10: Newlines at end of file
+    let final_newline_choices = vec![
+        "One (standard)",
+        "None (no final newline)",
+        "Two (one blank line)",
+    ];
+    let final_newline_values = [1, 0, 2];
+    let final_newlines_idx = Select::new()
+        .with_prompt("How many newlines should files end with?")
+        .items(&final_newline_choices)
         .default(0)
         .interact()?;
-    let code_style = match code_style_idx {
-        1 => "indented",
-        2 => "consistent",
-        _ => "fenced",
-    };
+    let final_newlines = final_newline_values[final_newlines_idx];
 
-    // Question 9: Rules to disable
+    // Question 11: Rules to disable
     let common_rules = vec![
         "MD013 (Line length)",
         "MD033 (Inline HTML)",
@@ -418,11 +328,13 @@ pub(crate) fn init_config_interactive(
         allow_html,
         allowed_elements,
         code_style,
+        code_fence_style,
+        final_newlines,
         disabled_rules,
     };
 
     // Generate configuration based on answers
-    let content = generate_config(selected_format, &options);
+    let content = generate_config(selected_format, &options)?;
 
     // Update output path extension if format changed
     let output_path = if selected_format != format {
@@ -1,12 +1,13 @@
 //! Configuration parsing and management
 
 pub mod presets;
+mod write;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::types::Result;
+use crate::types::{MarkdownlintError, Result};
 
 /// Configuration for markdownlint
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -23,11 +24,247 @@ pub struct Config {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub preset: Option<String>,
 
+    /// Per-rule violation budgets, enforced by the CLI's `--enforce-budgets`
+    ///
+    /// Maps a rule ID or alias to the most violations the run may report
+    /// for it, e.g. `{"MD013": 5, "MD045": 0}`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub budgets: HashMap<String, usize>,
+
+    /// Skip files that carry a generated-file marker near the top
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_generated: Option<SkipGenerated>,
+
+    /// Other tools' comment directives and what they mean to mkdlint
+    ///
+    /// Maps the text of a comment such as `prettier-ignore` to an action.
+    /// Entries extend [`DEFAULT_FOREIGN_DIRECTIVES`], which are all
+    /// [`ForeignDirective::Ignore`].
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub foreign_directives: HashMap<String, ForeignDirective>,
+
+    /// Rules whose violations are reported without a fix (IDs or aliases)
+    ///
+    /// Their `fix_info` is dropped when linting, so `--fix`, `fix_content`
+    /// and editor quick fixes leave those violations alone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub no_fix: Vec<String>,
+
+    /// Recognize `$...$` and `$$...$$` as math (default `true`)
+    ///
+    /// Math is then literal text to the emphasis and whitespace rules, like
+    /// a code span. Set to `false` for documents that use `$` as a plain
+    /// character.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub math: Option<bool>,
+
     /// Rule-specific configuration
     #[serde(flatten)]
     pub rules: HashMap<String, RuleConfig>,
 }
 
+/// Markers that identify generated files, which are neither linted nor fixed
+///
+/// ```
+/// let skip: mkdlint::config::SkipGenerated = serde_json::from_str("{}").unwrap();
+/// assert!(skip.matches("<!-- AUTO-GENERATED, do not edit -->\n# API\n"));
+/// assert!(!skip.matches("# API\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkipGenerated {
+    /// Text that marks a file as generated when found on one of its first lines
+    #[serde(default = "SkipGenerated::default_markers")]
+    pub markers: Vec<String>,
+
+    /// How many lines from the top of the file to search
+    #[serde(default = "SkipGenerated::default_search_lines")]
+    pub search_lines: usize,
+}
+
+impl SkipGenerated {
+    fn default_markers() -> Vec<String> {
+        vec!["AUTO-GENERATED".to_string(), "@generated".to_string()]
+    }
+
+    fn default_search_lines() -> usize {
+        5
+    }
+
+    /// Whether any marker appears within the first `search_lines` lines
+    pub fn matches(&self, content: &str) -> bool {
+        content.lines().take(self.search_lines).any(|line| {
+            self.markers
+                .iter()
+                .any(|marker| line.contains(marker.as_str()))
+        })
+    }
+}
+
+impl Default for SkipGenerated {
+    fn default() -> Self {
+        Self {
+            markers: Self::default_markers(),
+            search_lines: Self::default_search_lines(),
+        }
+    }
+}
+
+/// Comment directives of other tools that are recognized without
+/// configuration. Their lines are never linted as prose.
+pub const DEFAULT_FOREIGN_DIRECTIVES: [&str; 13] = [
+    "prettier-ignore",
+    "prettier-ignore-start",
+    "prettier-ignore-end",
+    "eslint-disable",
+    "eslint-disable-next-line",
+    "eslint-enable",
+    "textlint-disable",
+    "textlint-enable",
+    "cspell:disable",
+    "cspell:disable-next-line",
+    "cspell:enable",
+    "vale off",
+    "vale on",
+];
+
+/// What another tool's comment directive, such as
+/// `<!-- prettier-ignore -->`, means to mkdlint
+///
+/// Whatever the action, the directive's own line is never reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ForeignDirective {
+    /// Skip the directive's line only
+    Ignore,
+    /// Also skip the next line, like `markdownlint-disable-next-line`
+    DisableNextLine,
+    /// Disable all rules until an `enable` directive
+    Disable,
+    /// Re-enable all rules
+    Enable,
+}
+
+/// A registered rule's resolved state, as reported by [`Config::effective_rules`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveRule {
+    /// Rule ID (e.g. `"MD013"`)
+    pub name: &'static str,
+    /// Whether the rule runs
+    pub enabled: bool,
+    /// Configured severity override; None keeps the rule's own severity
+    pub severity: Option<crate::types::Severity>,
+    /// Configured options (empty when the rule has none)
+    pub options: HashMap<String, serde_json::Value>,
+}
+
+/// Where a resolved setting came from, as traced by
+/// [`Config::resolve_extends_traced`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// A config file: the one being resolved, or a parent named by `extends`
+    File(String),
+    /// A named preset
+    Preset(String),
+    /// A command-line flag such as `--enable` or `--disable`
+    CommandLine,
+    /// Not configured anywhere; the built-in default applies
+    Default,
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::File(path) => write!(f, "{}", path),
+            ConfigSource::Preset(name) => write!(f, "preset {}", name),
+            ConfigSource::CommandLine => write!(f, "command line"),
+            ConfigSource::Default => write!(f, "built-in default"),
+        }
+    }
+}
+
+/// The source of each setting in a resolved config
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigTrace {
+    /// Source of each rule entry, keyed as in [`Config::rules`]
+    pub rules: HashMap<String, ConfigSource>,
+    /// Source of the `default` setting, if any layer set it
+    pub default: Option<ConfigSource>,
+}
+
+impl ConfigTrace {
+    /// Where a rule's state comes from: its own entry if configured,
+    /// otherwise `default`, otherwise the built-in default
+    pub fn rule_source(&self, rule_name: &str) -> ConfigSource {
+        self.rules
+            .get(rule_name)
+            .or(self.default.as_ref())
+            .cloned()
+            .unwrap_or(ConfigSource::Default)
+    }
+
+    /// Attribute every setting in `config` to `source`, as merging it on
+    /// top does
+    fn record(&mut self, config: &Config, source: &ConfigSource) {
+        for key in config.rules.keys() {
+            self.rules.insert(key.clone(), source.clone());
+        }
+        if config.default.is_some() {
+            self.default = Some(source.clone());
+        }
+    }
+}
+
+/// A rule that reported more violations than its budget allows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BudgetOverrun {
+    /// Rule ID or alias, as written in `budgets`
+    pub rule: String,
+    /// Violations reported across all files
+    pub count: usize,
+    /// Configured budget
+    pub budget: usize,
+}
+
+/// A problem found by [`Config::validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Top-level key the problem is under, e.g. `"MD013"` or `"preset"`
+    pub key: String,
+    /// Entry within that key's object, for option-level problems
+    pub option: Option<String>,
+    /// What is wrong
+    pub message: String,
+}
+
+/// Severity string that disables a rule
+const SEVERITY_OFF: &str = "off";
+
+/// Options every rule accepts besides its own
+pub(crate) const COMMON_RULE_OPTIONS: [&str; 5] =
+    ["enabled", "severity", "files", "include", "exclude"];
+
+/// The regexes in a rule's options, as `(option, pattern)`, in the form
+/// the rules compile them with [`RuleParams::user_regex`]: each of
+/// `allowed_preamble_patterns`, and `front_matter_title` matched without
+/// regard to case.
+///
+/// [`RuleParams::user_regex`]: crate::types::RuleParams::user_regex
+pub(crate) fn option_regexes(opts: &HashMap<String, serde_json::Value>) -> Vec<(&str, String)> {
+    let mut regexes = Vec::new();
+    if let Some(serde_json::Value::String(pattern)) = opts.get("front_matter_title")
+        && !pattern.is_empty()
+    {
+        regexes.push(("front_matter_title", format!("(?i){}", pattern)));
+    }
+    let preamble = opts
+        .get("allowed_preamble_patterns")
+        .and_then(|v| v.as_array());
+    for pattern in preamble.into_iter().flatten().filter_map(|v| v.as_str()) {
+        regexes.push(("allowed_preamble_patterns", pattern.to_string()));
+    }
+    regexes
+}
+
 /// Configuration for an individual rule
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -35,7 +272,7 @@ pub enum RuleConfig {
     /// Simple boolean (enabled/disabled)
     Enabled(bool),
 
-    /// String severity ("error" or "warning")
+    /// String severity ("error" or "warning"), or "off" to disable the rule
     Severity(String),
 
     /// Detailed configuration with options
@@ -69,13 +306,73 @@ impl Config {
         Ok(config)
     }
 
+    /// Load configuration from the `key` entry of a `package.json`-style file,
+    /// ignoring the rest of the file
+    ///
+    /// Returns `Ok(None)` when the file has no such top-level key.
+    pub fn from_package_json(path: impl AsRef<Path>, key: &str) -> Result<Option<Self>> {
+        let content = std::fs::read_to_string(path)?;
+        let mut manifest: serde_json::Value = serde_json::from_str(&content)?;
+        match manifest.get_mut(key).map(serde_json::Value::take) {
+            Some(value) => Ok(Some(serde_json::from_value(value)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Load configuration from a JSON file that may contain `//` and
+    /// `/* */` comments (`.jsonc`)
+    pub fn from_jsonc_file(path: impl AsRef<Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let config = serde_json::from_str(&strip_json_comments(&content))?;
+        Ok(config)
+    }
+
+    /// Load the `config` entry of a markdownlint-cli2 options file
+    /// (`.markdownlint-cli2.jsonc` or `.markdownlint-cli2.yaml`), ignoring
+    /// its other options
+    ///
+    /// A file without a `config` entry gives the default configuration.
+    pub fn from_cli2_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let mut options: serde_json::Value = match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml") | Some("yml") => serde_yaml_ng::from_str(&content)?,
+            _ => serde_json::from_str(&strip_json_comments(&content))?,
+        };
+        match options.get_mut("config").map(serde_json::Value::take) {
+            Some(value) => Ok(serde_json::from_value(value)?),
+            None => Ok(Self::default()),
+        }
+    }
+
     /// Load configuration from a file (auto-detect format)
+    ///
+    /// A `package.json` is read through its [`PACKAGE_JSON_KEY`](Self::PACKAGE_JSON_KEY)
+    /// entry, and is an error without one. Files named
+    /// `.markdownlint-cli2.*` are read with [`from_cli2_file`](Self::from_cli2_file).
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
         let path = path.as_ref();
+        if path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with(".markdownlint-cli2."))
+        {
+            return Self::from_cli2_file(path);
+        }
+        if path.file_name().is_some_and(|n| n == "package.json") {
+            return Self::from_package_json(path, Self::PACKAGE_JSON_KEY)?.ok_or_else(|| {
+                MarkdownlintError::InvalidConfig(format!(
+                    "{} has no `{}` key",
+                    path.display(),
+                    Self::PACKAGE_JSON_KEY
+                ))
+            });
+        }
         let ext = path.extension().and_then(|e| e.to_str());
 
         match ext {
             Some("json") => Self::from_json_file(path),
+            Some("jsonc") => Self::from_jsonc_file(path),
             Some("yaml") | Some("yml") => Self::from_yaml_file(path),
             Some("toml") => Self::from_toml_file(path),
             _ => {
@@ -87,17 +384,29 @@ impl Config {
         }
     }
 
+    /// Top-level `package.json` key holding the configuration
+    pub const PACKAGE_JSON_KEY: &'static str = "markdownlint";
+
     /// Config file names to search for during auto-discovery
-    const DISCOVERY_NAMES: [&'static str; 5] = [
+    const DISCOVERY_NAMES: [&'static str; 6] = [
         ".markdownlint.json",
         ".markdownlint.yaml",
         ".markdownlint.yml",
         ".markdownlint.toml",
         ".markdownlintrc",
+        "package.json",
     ];
 
     /// Walk up from `start_dir` looking for a config file
+    ///
+    /// Files that fail to load are skipped, including a `package.json`
+    /// without a `markdownlint` key.
     pub fn discover(start_dir: impl AsRef<Path>) -> Option<Self> {
+        Self::discover_with_path(start_dir).map(|(_, config)| config)
+    }
+
+    /// [`discover`](Self::discover), also returning the path of the file found
+    pub fn discover_with_path(start_dir: impl AsRef<Path>) -> Option<(PathBuf, Self)> {
         let mut dir = start_dir.as_ref().to_path_buf();
         loop {
             for name in &Self::DISCOVERY_NAMES {
@@ -105,7 +414,7 @@ impl Config {
                 if candidate.is_file()
                     && let Ok(config) = Self::from_file(&candidate)
                 {
-                    return Some(config);
+                    return Some((candidate, config));
                 }
             }
             if !dir.pop() {
@@ -115,6 +424,43 @@ impl Config {
         None
     }
 
+    /// Global user config file names, tried in order in
+    /// [`global_dir`](Self::global_dir)
+    const GLOBAL_NAMES: [&'static str; 4] =
+        ["config.json", "config.yaml", "config.yml", "config.toml"];
+
+    /// Directory of the global user config: `$XDG_CONFIG_HOME/mkdlint` when
+    /// that is set to an absolute path, otherwise `mkdlint` in the
+    /// platform's config directory (`~/.config` on Linux,
+    /// `~/Library/Application Support` on macOS, `%APPDATA%` on Windows)
+    pub fn global_dir() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(dirs::config_dir)
+            .map(|dir| dir.join("mkdlint"))
+    }
+
+    /// The global user config and its path, if there is one: the first of
+    /// `config.json`, `config.yaml`, `config.yml` and `config.toml` in
+    /// [`global_dir`](Self::global_dir).
+    ///
+    /// Unlike discovery, a global config that fails to load is an error
+    /// rather than skipped. Layer project configs over it with
+    /// [`resolve_over`](Self::resolve_over).
+    pub fn global() -> Result<Option<(PathBuf, Self)>> {
+        let Some(dir) = Self::global_dir() else {
+            return Ok(None);
+        };
+        for name in &Self::GLOBAL_NAMES {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                return Ok(Some((candidate.clone(), Self::from_file(&candidate)?)));
+            }
+        }
+        Ok(None)
+    }
+
     /// Apply the named preset (if any) as a base, then re-apply explicit rules on top.
     ///
     /// Preset rules are overridden by any explicit rule config in `self`.
@@ -131,20 +477,92 @@ impl Config {
     }
 
     /// Resolve the `extends` chain: load the parent config and merge self on top.
-    /// Also applies any named preset after the chain is resolved.
+    ///
+    /// Each config's named preset applies between its parent and its own
+    /// settings, so a preset never overrides a rule the config sets itself.
     pub fn resolve_extends(&self) -> Result<Self> {
-        if let Some(ref extends_path) = self.extends {
-            let parent = Config::from_file(extends_path)?;
-            let mut resolved = parent.resolve_extends()?;
-            resolved.merge(self.clone());
-            resolved.extends = None;
-            resolved.apply_preset();
-            Ok(resolved)
-        } else {
-            let mut resolved = self.clone();
-            resolved.apply_preset();
-            Ok(resolved)
+        self.resolve_traced(&ConfigSource::Default, &mut ConfigTrace::default())
+    }
+
+    /// [`resolve_extends`](Self::resolve_extends), also reporting where each
+    /// setting of the result came from: `source` for this config's own
+    /// settings, the file path for settings from an `extends` parent, or the
+    /// preset.
+    ///
+    /// ```
+    /// use mkdlint::config::{Config, ConfigSource};
+    /// let config: Config = serde_json::from_str(r#"{"preset": "github", "MD001": false}"#).unwrap();
+    /// let (_, trace) = config
+    ///     .resolve_extends_traced(ConfigSource::File(".markdownlint.json".into()))
+    ///     .unwrap();
+    /// assert_eq!(trace.rule_source("MD001"), ConfigSource::File(".markdownlint.json".into()));
+    /// assert_eq!(trace.rule_source("MD013"), ConfigSource::Preset("github".into()));
+    /// assert_eq!(trace.rule_source("MD009"), ConfigSource::Default);
+    /// ```
+    pub fn resolve_extends_traced(&self, source: ConfigSource) -> Result<(Self, ConfigTrace)> {
+        let mut trace = ConfigTrace::default();
+        let resolved = self.resolve_traced(&source, &mut trace)?;
+        Ok((resolved, trace))
+    }
+
+    /// [`resolve_extends`](Self::resolve_extends) on top of `base`, such as
+    /// the [global user config](Self::global): `base` is resolved on its
+    /// own, then this config's settings, its `extends` parents and its
+    /// preset all override it.
+    ///
+    /// ```
+    /// use mkdlint::Config;
+    /// let global: Config = serde_json::from_str(r#"{"MD013": false, "MD033": false}"#).unwrap();
+    /// let project: Config = serde_json::from_str(r#"{"MD033": true}"#).unwrap();
+    /// let resolved = project.resolve_over(&global).unwrap();
+    /// assert!(!resolved.is_rule_enabled("MD013"));
+    /// assert!(resolved.is_rule_enabled("MD033"));
+    /// ```
+    pub fn resolve_over(&self, base: &Config) -> Result<Self> {
+        let source = ConfigSource::Default;
+        self.resolve_over_traced(source.clone(), base, source)
+            .map(|(resolved, _)| resolved)
+    }
+
+    /// [`resolve_over`](Self::resolve_over), also reporting where each
+    /// setting came from, as [`resolve_extends_traced`](Self::resolve_extends_traced)
+    /// does; `base_source` is the source of `base`'s own settings
+    pub fn resolve_over_traced(
+        &self,
+        source: ConfigSource,
+        base: &Config,
+        base_source: ConfigSource,
+    ) -> Result<(Self, ConfigTrace)> {
+        let mut trace = ConfigTrace::default();
+        let mut resolved = base.resolve_traced(&base_source, &mut trace)?;
+        let own = self.resolve_traced(&source, &mut trace)?;
+        let preset = own.preset.clone().or(resolved.preset.take());
+        resolved.merge(own);
+        resolved.preset = preset;
+        Ok((resolved, trace))
+    }
+
+    fn resolve_traced(&self, source: &ConfigSource, trace: &mut ConfigTrace) -> Result<Self> {
+        let mut resolved = match self.extends {
+            Some(ref extends_path) => {
+                let parent = Config::from_file(extends_path)?;
+                let parent_source = ConfigSource::File(extends_path.clone());
+                parent.resolve_traced(&parent_source, trace)?
+            }
+            None => Config::default(),
+        };
+        // A config's preset sits between its parent and its own settings
+        if let Some(ref name) = self.preset
+            && let Some(preset) = presets::resolve_preset(name)
+        {
+            trace.record(&preset, &ConfigSource::Preset(name.clone()));
+            resolved.merge(preset);
+            resolved.preset = Some(name.clone());
         }
+        trace.record(self, source);
+        resolved.merge(self.clone());
+        resolved.extends = None;
+        Ok(resolved)
     }
 
     /// Merge another configuration into this one
@@ -152,49 +570,406 @@ impl Config {
         if other.default.is_some() {
             self.default = other.default;
         }
+        self.budgets.extend(other.budgets);
+        if other.skip_generated.is_some() {
+            self.skip_generated = other.skip_generated;
+        }
+        self.foreign_directives.extend(other.foreign_directives);
+        if other.math.is_some() {
+            self.math = other.math;
+        }
+        for rule in other.no_fix {
+            if !self.no_fix.contains(&rule) {
+                self.no_fix.push(rule);
+            }
+        }
         self.rules.extend(other.rules);
     }
 
+    /// Key rule entries, `budgets` and `no_fix` by rule ID instead of alias
+    /// (or differently cased ID), returning the `(old key, ID)` pairs renamed
+    /// in the rule entries, sorted by old key.
+    ///
+    /// Where a rule has entries under both, the one under its ID is kept.
+    /// Keys that name no rule are left alone.
+    ///
+    /// ```
+    /// let mut config: mkdlint::Config =
+    ///     serde_json::from_str(r#"{"line-length": {"line_length": 100}, "no_fix": ["md009"]}"#).unwrap();
+    /// assert_eq!(config.normalize_rule_keys(), [("line-length".to_string(), "MD013")]);
+    /// assert!(config.rules.contains_key("MD013"));
+    /// assert_eq!(config.no_fix, ["MD009"]);
+    /// ```
+    pub fn normalize_rule_keys(&mut self) -> Vec<(String, &'static str)> {
+        let rule_id = |key: &str| crate::rules::find_rule(key).map(|rule| rule.names()[0]);
+
+        let mut keys: Vec<String> = self.rules.keys().cloned().collect();
+        keys.sort();
+        let mut renamed = Vec::new();
+        for key in keys {
+            if let Some(id) = rule_id(&key)
+                && key != id
+                && let Some(entry) = self.rules.remove(&key)
+            {
+                self.rules.entry(id.to_string()).or_insert(entry);
+                renamed.push((key, id));
+            }
+        }
+
+        let mut budgets: Vec<(String, usize)> = self.budgets.drain().collect();
+        // IDs last, so they win over aliases
+        budgets.sort_by_key(|(key, _)| rule_id(key) == Some(key.as_str()));
+        for (key, budget) in budgets {
+            let id = rule_id(&key).map_or(key, str::to_string);
+            self.budgets.insert(id, budget);
+        }
+
+        let mut no_fix = Vec::new();
+        for key in self.no_fix.drain(..) {
+            let id = rule_id(&key).map_or(key, str::to_string);
+            if !no_fix.contains(&id) {
+                no_fix.push(id);
+            }
+        }
+        self.no_fix = no_fix;
+
+        renamed
+    }
+
+    /// Whether `no_fix` lists the rule with these names, case-insensitively
+    ///
+    /// ```
+    /// let config: mkdlint::Config = serde_json::from_str(r#"{"no_fix": ["heading-style"]}"#).unwrap();
+    /// assert!(config.is_fix_disabled(&["MD003", "heading-style"]));
+    /// assert!(!config.is_fix_disabled(&["MD009", "no-trailing-spaces"]));
+    /// ```
+    pub fn is_fix_disabled(&self, rule_names: &[&str]) -> bool {
+        self.no_fix
+            .iter()
+            .any(|rule| rule_names.iter().any(|n| n.eq_ignore_ascii_case(rule)))
+    }
+
+    /// Whether `$` math is recognized, see [`Config::math`]
+    pub fn math_enabled(&self) -> bool {
+        self.math.unwrap_or(true)
+    }
+
+    /// The action for a comment whose trimmed text is `inner`, if it is
+    /// a foreign directive.
+    ///
+    /// A key matches the whole text or a prefix followed by whitespace, so
+    /// `eslint-disable` also covers `eslint-disable no-console`. Configured
+    /// keys take precedence over the defaults, and the longest match wins.
+    ///
+    /// ```
+    /// use mkdlint::config::{Config, ForeignDirective};
+    /// let config = Config::default();
+    /// assert_eq!(config.foreign_directive("prettier-ignore"), Some(ForeignDirective::Ignore));
+    /// assert_eq!(config.foreign_directive("prettier-ignored"), None);
+    /// ```
+    pub fn foreign_directive(&self, inner: &str) -> Option<ForeignDirective> {
+        let matches = |key: &str| {
+            inner
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        };
+        self.foreign_directives
+            .iter()
+            .filter(|(key, _)| matches(key))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, action)| *action)
+            .or_else(|| {
+                DEFAULT_FOREIGN_DIRECTIVES
+                    .iter()
+                    .any(|key| matches(key))
+                    .then_some(ForeignDirective::Ignore)
+            })
+    }
+
+    /// Rules whose violations in `results` exceed their `budgets` entry,
+    /// sorted by rule.
+    ///
+    /// Budget keys match any of a rule's names, case-insensitively; fix-only
+    /// entries are not counted.
+    pub fn over_budget(&self, results: &crate::types::LintResults) -> Vec<BudgetOverrun> {
+        let mut overruns: Vec<BudgetOverrun> = self
+            .budgets
+            .iter()
+            .filter_map(|(rule, &budget)| {
+                let count = results
+                    .results
+                    .values()
+                    .flatten()
+                    .filter(|e| {
+                        !e.fix_only
+                            && !e.is_internal_error()
+                            && e.rule_names.iter().any(|n| n.eq_ignore_ascii_case(rule))
+                    })
+                    .count();
+                (count > budget).then(|| BudgetOverrun {
+                    rule: rule.clone(),
+                    count,
+                    budget,
+                })
+            })
+            .collect();
+        overruns.sort_by(|a, b| a.rule.cmp(&b.rule));
+        overruns
+    }
+
+    /// Problems that would make parts of this config silently ineffective:
+    /// unknown rules, presets, and options, rule aliases used as keys, and
+    /// invalid severities.
+    ///
+    /// A rule's known options are those in the configuration table of its
+    /// documentation (see [`crate::rules::rule_options`]). Keys starting with
+    /// `$`, such as `$schema`, are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// let config: mkdlint::Config =
+    ///     serde_json::from_str(r#"{"MD013": {"line_lenght": 100}, "MD999": false}"#).unwrap();
+    /// let issues = config.validate();
+    /// assert_eq!(issues[0].message, "Unknown option 'line_lenght' for MD013 (expected one of: line_length, code_blocks, ignore_html_comments, tab_size, enabled, severity, files, include, exclude)");
+    /// assert_eq!(issues[1].message, "Unknown rule 'MD999'");
+    /// ```
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let issue = |key: &str, option: Option<&str>, message: String| ConfigIssue {
+            key: key.to_string(),
+            option: option.map(str::to_string),
+            message,
+        };
+        let mut issues = Vec::new();
+
+        if let Some(preset) = &self.preset
+            && presets::resolve_preset(preset).is_none()
+        {
+            issues.push(issue(
+                "preset",
+                None,
+                format!(
+                    "Unknown preset '{}' (expected one of: {})",
+                    preset,
+                    presets::preset_names().join(", ")
+                ),
+            ));
+        }
+
+        let mut keys: Vec<&String> = self.rules.keys().filter(|k| !k.starts_with('$')).collect();
+        keys.sort();
+        for key in keys {
+            let Some(rule) = crate::rules::find_rule(key) else {
+                let message = match crate::rules::missing_rule_feature(key) {
+                    Some(feature) => format!(
+                        "Rule '{}' is not in this build of mkdlint (cargo feature `{}`)",
+                        key, feature
+                    ),
+                    None => format!("Unknown rule '{}'", key),
+                };
+                issues.push(issue(key, None, message));
+                continue;
+            };
+            let id = rule.names()[0];
+            // Rules look up their settings by ID only
+            if key != id {
+                issues.push(issue(
+                    key,
+                    None,
+                    format!(
+                        "'{}' has no effect: use the rule ID '{}' as the key",
+                        key, id
+                    ),
+                ));
+            }
+
+            let invalid_severity = |value: &str| {
+                !matches!(
+                    value.to_lowercase().as_str(),
+                    "error" | "warning" | "warn" | SEVERITY_OFF
+                )
+            };
+            match &self.rules[key] {
+                RuleConfig::Enabled(_) => {}
+                RuleConfig::Severity(severity) => {
+                    if invalid_severity(severity) {
+                        issues.push(issue(
+                            key,
+                            None,
+                            format!(
+                                "Invalid severity '{}' for {} (expected \"error\", \"warning\" or \"off\")",
+                                severity, id
+                            ),
+                        ));
+                    }
+                }
+                RuleConfig::Options(opts) => {
+                    let mut known = crate::rules::rule_options(id);
+                    known.extend(COMMON_RULE_OPTIONS);
+                    let mut options: Vec<&String> = opts.keys().collect();
+                    options.sort();
+                    for option in options {
+                        if !known.contains(&option.as_str()) {
+                            issues.push(issue(
+                                key,
+                                Some(option),
+                                format!(
+                                    "Unknown option '{}' for {} (expected one of: {})",
+                                    option,
+                                    id,
+                                    known.join(", ")
+                                ),
+                            ));
+                        } else if option == "severity"
+                            && let Some(severity) = opts[option].as_str()
+                            && invalid_severity(severity)
+                        {
+                            issues.push(issue(
+                                key,
+                                Some(option),
+                                format!(
+                                    "Invalid severity '{}' for {} (expected \"error\", \"warning\" or \"off\")",
+                                    severity, id
+                                ),
+                            ));
+                        }
+                    }
+                    let patterns = crate::helpers::PatternCache::new();
+                    for (option, pattern) in option_regexes(opts) {
+                        if let Err(MarkdownlintError::InvalidConfig(detail)) =
+                            patterns.get(&pattern)
+                        {
+                            issues.push(issue(
+                                key,
+                                Some(option),
+                                format!("Invalid {} `{}`: {}", id, option, detail),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut budgets: Vec<&String> = self.budgets.keys().collect();
+        budgets.sort();
+        for rule in budgets {
+            if crate::rules::find_rule(rule).is_none() {
+                issues.push(issue(
+                    "budgets",
+                    Some(rule),
+                    format!("Unknown rule '{}' in budgets", rule),
+                ));
+            }
+        }
+
+        for rule in &self.no_fix {
+            if crate::rules::find_rule(rule).is_none() {
+                issues.push(issue(
+                    "no_fix",
+                    Some(rule),
+                    format!("Unknown rule '{}' in no_fix", rule),
+                ));
+            }
+        }
+
+        issues
+    }
+
     /// Get effective configuration for a rule
     pub fn get_rule_config(&self, rule_name: &str) -> Option<&RuleConfig> {
         self.rules.get(rule_name)
     }
 
     /// Check if a rule is enabled
+    ///
+    /// An `"enabled"` key in the rule's options decides on its own, whatever
+    /// else is set; otherwise `"off"` as the rule's severity (`"MD013":
+    /// "off"` or `"MD013": {"severity": "off"}`) disables it.
     pub fn is_rule_enabled(&self, rule_name: &str) -> bool {
         match self.get_rule_config(rule_name) {
             Some(RuleConfig::Enabled(enabled)) => *enabled,
-            Some(RuleConfig::Severity(_)) => true,
-            Some(RuleConfig::Options(opts)) => opts
-                .get("enabled")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(true),
+            Some(RuleConfig::Options(opts)) if opts.contains_key("enabled") => {
+                opts["enabled"].as_bool().unwrap_or(true)
+            }
+            Some(_) => !self
+                .configured_severity(rule_name)
+                .is_some_and(|s| s.eq_ignore_ascii_case(SEVERITY_OFF)),
             None => self.default.unwrap_or(true),
         }
     }
 
+    /// Whether `rule` runs under this config: its own entry when configured,
+    /// otherwise `default`, otherwise the rule's built-in default (extension
+    /// rules such as KMD* are off).
+    pub(crate) fn rule_enabled(&self, rule: &dyn crate::types::Rule) -> bool {
+        let id = rule.names()[0];
+        if self.get_rule_config(id).is_some() {
+            self.is_rule_enabled(id)
+        } else {
+            self.default.unwrap_or_else(|| rule.is_enabled_by_default())
+        }
+    }
+
+    /// The resolved state of every registered rule, in registry order.
+    ///
+    /// Uses the same logic as linting, so call it on a config whose
+    /// `extends` and `preset` are already resolved (as
+    /// [`Config::resolve_extends`] returns it).
+    ///
+    /// ```
+    /// let config: mkdlint::Config =
+    ///     serde_json::from_str(r#"{"MD013": {"line_length": 120, "severity": "warning"}}"#)
+    ///         .unwrap();
+    /// let md013 = config
+    ///     .effective_rules()
+    ///     .into_iter()
+    ///     .find(|rule| rule.name == "MD013")
+    ///     .unwrap();
+    /// assert!(md013.enabled);
+    /// assert_eq!(md013.severity, Some(mkdlint::types::Severity::Warning));
+    /// assert_eq!(md013.options["line_length"], 120);
+    /// ```
+    pub fn effective_rules(&self) -> Vec<EffectiveRule> {
+        crate::rules::get_rules()
+            .iter()
+            .map(|rule| {
+                let name = rule.names()[0];
+                EffectiveRule {
+                    name,
+                    enabled: self.rule_enabled(rule.as_ref()),
+                    severity: self.get_rule_severity(name),
+                    options: match self.get_rule_config(name) {
+                        Some(RuleConfig::Options(opts)) => opts.clone(),
+                        _ => HashMap::new(),
+                    },
+                }
+            })
+            .collect()
+    }
+
     /// Get the configured severity for a rule, if set.
     ///
-    /// Returns None if no explicit severity is configured (rule uses its default).
+    /// Returns None if no explicit severity is configured (rule uses its
+    /// default), or if the rule's own entry disables it.
     /// Supports both `"MD001": "warning"` and `"MD001": {"severity": "warning"}` formats.
     pub fn get_rule_severity(&self, rule_name: &str) -> Option<crate::types::Severity> {
-        match self.get_rule_config(rule_name) {
-            Some(RuleConfig::Severity(s)) => match s.to_lowercase().as_str() {
-                "warning" | "warn" => Some(crate::types::Severity::Warning),
-                "error" => Some(crate::types::Severity::Error),
-                _ => None,
-            },
-            Some(RuleConfig::Options(opts)) => opts
-                .get("severity")
-                .and_then(|v| v.as_str())
-                .and_then(|s| match s.to_lowercase().as_str() {
-                    "warning" | "warn" => Some(crate::types::Severity::Warning),
-                    "error" => Some(crate::types::Severity::Error),
-                    _ => None,
-                }),
+        if !self.is_rule_enabled(rule_name) {
+            return None;
+        }
+        match self.configured_severity(rule_name)?.to_lowercase().as_str() {
+            "warning" | "warn" => Some(crate::types::Severity::Warning),
+            "error" => Some(crate::types::Severity::Error),
             _ => None,
         }
     }
+
+    /// The severity string in a rule's entry, in either format
+    fn configured_severity(&self, rule_name: &str) -> Option<&str> {
+        match self.get_rule_config(rule_name)? {
+            RuleConfig::Severity(s) => Some(s),
+            RuleConfig::Options(opts) => opts.get("severity")?.as_str(),
+            RuleConfig::Enabled(_) => None,
+        }
+    }
 }
 
 /// Configuration parser trait for custom formats
@@ -203,6 +978,45 @@ pub trait ConfigParser {
     fn parse(&self, content: &str) -> Result<Config>;
 }
 
+/// `content` with `//` and `/* */` comments outside strings removed, line
+/// breaks kept so JSON error line numbers stay right
+fn strip_json_comments(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => while chars.next_if(|&c| c != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    out.push(if c == '\n' { '\n' } else { ' ' });
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -243,6 +1057,55 @@ mod tests {
         assert!(!config.is_rule_enabled("MD001"));
     }
 
+    #[test]
+    fn test_discover_package_json() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"name": "docs", "version": "1.0.0", "markdownlint": {"MD013": false}}"#,
+        )
+        .unwrap();
+
+        let config = Config::discover(dir.path()).unwrap();
+        assert!(!config.is_rule_enabled("MD013"));
+        assert!(config.is_rule_enabled("MD001"));
+    }
+
+    #[test]
+    fn test_discover_skips_package_json_without_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("pkg");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(sub.join("package.json"), r#"{"name": "pkg"}"#).unwrap();
+        std::fs::write(dir.path().join(".markdownlint.json"), r#"{"MD001": false}"#).unwrap();
+
+        let config = Config::discover(&sub).unwrap();
+        assert!(!config.is_rule_enabled("MD001"));
+        assert!(Config::from_file(sub.join("package.json")).is_err());
+    }
+
+    #[test]
+    fn test_from_file_reads_cli2_options_and_jsonc() {
+        let dir = tempfile::tempdir().unwrap();
+        let cli2 = dir.path().join(".markdownlint-cli2.jsonc");
+        std::fs::write(
+            &cli2,
+            "{\n  // Globs are not ours\n  \"globs\": [\"**/*.md\"],\n  \"config\": { /* wide */ \"MD013\": false, \"MD033\": \"http://x//y\" }\n}\n",
+        )
+        .unwrap();
+        let config = Config::from_file(&cli2).unwrap();
+        assert!(!config.is_rule_enabled("MD013"));
+        assert!(matches!(&config.rules["MD033"], RuleConfig::Severity(s) if s == "http://x//y"));
+
+        let yaml = dir.path().join(".markdownlint-cli2.yaml");
+        std::fs::write(&yaml, "ignores:\n  - vendor\n").unwrap();
+        assert!(Config::from_file(&yaml).unwrap().rules.is_empty());
+
+        let jsonc = dir.path().join(".markdownlint.jsonc");
+        std::fs::write(&jsonc, "// Team config\n{\"MD001\": false}\n").unwrap();
+        assert!(!Config::from_file(&jsonc).unwrap().is_rule_enabled("MD001"));
+    }
+
     #[test]
     fn test_discover_none_when_missing() {
         let dir = tempfile::tempdir().unwrap();
@@ -278,6 +1141,82 @@ mod tests {
         assert!(resolved.extends.is_none());
     }
 
+    #[test]
+    fn test_resolve_extends_traced_local_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().join("base.json");
+        std::fs::write(
+            &base_path,
+            r#"{"default": true, "MD013": false, "MD033": false}"#,
+        )
+        .unwrap();
+        let base_path = base_path.to_str().unwrap().to_string();
+
+        let child_json = format!(
+            r#"{{"extends": "{}", "MD013": true}}"#,
+            base_path.replace('\\', "\\\\")
+        );
+        let child: Config = serde_json::from_str(&child_json).unwrap();
+        let local = ConfigSource::File("child.json".to_string());
+        let (resolved, trace) = child.resolve_extends_traced(local.clone()).unwrap();
+
+        assert!(resolved.is_rule_enabled("MD013"));
+        assert_eq!(trace.rule_source("MD013"), local);
+        assert_eq!(
+            trace.rule_source("MD033"),
+            ConfigSource::File(base_path.clone())
+        );
+        // Rules without an entry follow `default`, set by the parent
+        assert_eq!(trace.rule_source("MD001"), ConfigSource::File(base_path));
+    }
+
+    #[test]
+    fn test_resolve_extends_traced_preset() {
+        let config: Config =
+            serde_json::from_str(r#"{"preset": "github", "MD013": true}"#).unwrap();
+        let (resolved, trace) = config
+            .resolve_extends_traced(ConfigSource::CommandLine)
+            .unwrap();
+        assert_eq!(trace.rule_source("MD013"), ConfigSource::CommandLine);
+        assert_eq!(
+            trace.rule_source("MD034"),
+            ConfigSource::Preset("github".to_string())
+        );
+        assert!(!resolved.is_rule_enabled("MD034"));
+        assert_eq!(trace.rule_source("MD001"), ConfigSource::Default);
+    }
+
+    #[test]
+    fn test_resolve_over_global_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let global_path = dir.path().join("config.yaml");
+        std::fs::write(&global_path, "MD013: false\nMD033: false\n").unwrap();
+        let global = Config::from_file(&global_path).unwrap();
+        let global_source = ConfigSource::File(global_path.display().to_string());
+
+        // Project settings, its preset included, win over the global config
+        let project: Config =
+            serde_json::from_str(r#"{"preset": "github", "MD013": true}"#).unwrap();
+        let local = ConfigSource::File(".markdownlint.json".to_string());
+        let (resolved, trace) = project
+            .resolve_over_traced(local.clone(), &global, global_source.clone())
+            .unwrap();
+
+        assert!(resolved.is_rule_enabled("MD013"));
+        assert_eq!(trace.rule_source("MD013"), local);
+        assert!(!resolved.is_rule_enabled("MD033"));
+        assert_eq!(trace.rule_source("MD033"), global_source);
+        assert_eq!(resolved.preset.as_deref(), Some("github"));
+        assert_eq!(
+            trace.rule_source("MD034"),
+            ConfigSource::Preset("github".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(project.resolve_over(&global).unwrap()).unwrap(),
+            serde_json::to_value(&resolved).unwrap()
+        );
+    }
+
     #[test]
     fn test_resolve_extends_no_extends() {
         let config = Config::new();
@@ -316,6 +1255,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_severity_off_disables_rule() {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "MD001": "off",
+                "MD009": "OFF",
+                "MD013": {"severity": "off", "line_length": 10},
+                "MD003": {"enabled": false, "severity": "warning", "style": "atx"},
+                "MD024": {"enabled": true, "severity": "off"}
+            }"#,
+        )
+        .unwrap();
+        for rule in ["MD001", "MD003", "MD009", "MD013"] {
+            assert!(!config.is_rule_enabled(rule), "{rule}");
+            // Disabled rules never carry a severity override
+            assert_eq!(config.get_rule_severity(rule), None, "{rule}");
+        }
+        // `enabled` is authoritative over other options
+        assert!(config.is_rule_enabled("MD024"));
+        assert_eq!(config.get_rule_severity("MD024"), None);
+        assert!(config.validate().is_empty());
+
+        let config: Config =
+            serde_json::from_str(r#"{"MD013": {"enabled": false, "line_length": 10}}"#).unwrap();
+        assert!(!config.is_rule_enabled("MD013"));
+        let errors = crate::lint_string(&format!("{}\n", "x".repeat(20)), &config).unwrap();
+        assert!(errors.iter().all(|e| e.rule_names[0] != "MD013"));
+    }
+
     #[test]
     fn test_get_rule_severity_warn_alias() {
         let json = r#"{"MD001": "warn"}"#;
@@ -325,4 +1293,126 @@ mod tests {
             Some(crate::types::Severity::Warning)
         );
     }
+
+    #[test]
+    fn test_effective_rules_default_false() {
+        let config: Config = serde_json::from_str(r#"{"default": false, "MD009": true}"#).unwrap();
+        let rules = config.effective_rules();
+        assert_eq!(rules.len(), crate::rules::get_rules().len());
+
+        let enabled: Vec<&str> = rules.iter().filter(|r| r.enabled).map(|r| r.name).collect();
+        assert_eq!(enabled, vec!["MD009"]);
+    }
+
+    #[test]
+    fn test_effective_rules_rule_defaults() {
+        let config: Config = serde_json::from_str(r#"{"MD013": false}"#).unwrap();
+        let rules = config.effective_rules();
+        let state = |name: &str| rules.iter().find(|r| r.name == name).unwrap().enabled;
+        assert!(!state("MD013"));
+        assert!(state("MD001"));
+        // Extension rules stay off unless enabled
+        assert!(!state("KMD001"));
+    }
+
+    #[test]
+    fn test_over_budget() {
+        let json = r#"{"budgets": {"MD013": 1, "no-trailing-spaces": 5}}"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+
+        let error = |names: &'static [&'static str]| crate::types::LintError {
+            line_number: 1,
+            rule_names: names,
+            ..Default::default()
+        };
+        let mut results = crate::types::LintResults::new();
+        results.add(
+            "a.md".to_string(),
+            vec![
+                error(&["MD013", "line-length"]),
+                error(&["MD009", "no-trailing-spaces"]),
+            ],
+        );
+        results.add(
+            "b.md".to_string(),
+            vec![
+                error(&["MD013", "line-length"]),
+                error(&["MD009", "no-trailing-spaces"]),
+            ],
+        );
+
+        // MD013 is over its budget; MD009 (budgeted by alias) is within
+        assert_eq!(
+            config.over_budget(&results),
+            vec![BudgetOverrun {
+                rule: "MD013".to_string(),
+                count: 2,
+                budget: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let json = r#"{
+            "$schema": "https://example.com/schema.json",
+            "preset": "gfm",
+            "MD013": {"line_length": 100, "severity": "loud", "files": ["docs/**"]},
+            "line-length": false,
+            "MD009": {"br_spaces": 2, "strict": true},
+            "MD033": "warn",
+            "MD999": true,
+            "budgets": {"MD045": 0, "nope": 1},
+            "no_fix": ["heading-style", "MD0"]
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let issues: Vec<(String, Option<String>)> = config
+            .validate()
+            .into_iter()
+            .map(|issue| (issue.key, issue.option))
+            .collect();
+        let issue = |key: &str, option: Option<&str>| (key.to_string(), option.map(str::to_string));
+        assert_eq!(
+            issues,
+            [
+                issue("preset", None),
+                issue("MD009", Some("strict")),
+                issue("MD013", Some("severity")),
+                issue("MD999", None),
+                issue("line-length", None),
+                issue("budgets", Some("nope")),
+                issue("no_fix", Some("MD0")),
+            ]
+        );
+
+        assert!(Config::default().validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_invalid_regex_options() {
+        let json = r#"{
+            "MD025": {"front_matter_title": "title("},
+            "MD041": {"allowed_preamble_patterns": ["^<p", "[badge"], "front_matter_title": false}
+        }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let issues = config.validate();
+        let found: Vec<(&str, Option<&str>)> = issues
+            .iter()
+            .map(|issue| (issue.key.as_str(), issue.option.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("MD025", Some("front_matter_title")),
+                ("MD041", Some("allowed_preamble_patterns")),
+            ]
+        );
+        assert!(
+            issues[1]
+                .message
+                .starts_with("Invalid MD041 `allowed_preamble_patterns`: invalid pattern '[badge'"),
+            "{}",
+            issues[1].message
+        );
+    }
 }
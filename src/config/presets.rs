@@ -42,6 +42,11 @@ fn github_preset() -> Config {
         default: None,
         extends: None,
         preset: None,
+        budgets: HashMap::new(),
+        skip_generated: None,
+        foreign_directives: HashMap::new(),
+        math: None,
+        no_fix: Vec::new(),
         rules,
     }
 }
@@ -76,6 +81,11 @@ fn kramdown_preset() -> Config {
         default: None,
         extends: None,
         preset: None,
+        budgets: HashMap::new(),
+        skip_generated: None,
+        foreign_directives: HashMap::new(),
+        math: None,
+        no_fix: Vec::new(),
         rules,
     }
 }
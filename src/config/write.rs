@@ -0,0 +1,243 @@
+//! Writing a [`Config`] back out as JSON, YAML or TOML, in a stable order
+
+use super::{Config, RuleConfig};
+use crate::types::{MarkdownlintError, Result};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::BTreeMap;
+
+impl Config {
+    /// The config as pretty-printed JSON, with a trailing newline.
+    ///
+    /// Keys come in a fixed order: `$`-prefixed keys such as `$schema`;
+    /// `default`, `extends`, `preset`, `math` and `no_fix`; `budgets`,
+    /// `skip_generated` and `foreign_directives`; then the rules, sorted by
+    /// key, each with its options sorted too.
+    ///
+    /// # Examples
+    /// ```
+    /// let config: mkdlint::Config =
+    ///     serde_json::from_str(r#"{"MD013": {"line_length": 100}, "default": true, "MD001": false}"#)?;
+    /// assert_eq!(
+    ///     config.to_json_string()?,
+    ///     "{\n  \"default\": true,\n  \"MD001\": false,\n  \"MD013\": {\n    \"line_length\": 100\n  }\n}\n"
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&Ordered::new(self))? + "\n")
+    }
+
+    /// The config as YAML, in the order of [`to_json_string`](Self::to_json_string)
+    pub fn to_yaml_string(&self) -> Result<String> {
+        Ok(serde_yaml_ng::to_string(&Ordered::new(self))?)
+    }
+
+    /// The config as TOML, in the order of [`to_json_string`](Self::to_json_string)
+    /// except that values come before tables, as TOML requires: rules set to a
+    /// plain `true`, `false` or severity come before `budgets`, and rules with
+    /// options after `foreign_directives`.
+    pub fn to_toml_string(&self) -> Result<String> {
+        let ordered = Ordered {
+            tables_last: true,
+            ..Ordered::new(self)
+        };
+        toml::to_string(&ordered).map_err(|e| MarkdownlintError::InvalidConfig(e.to_string()))
+    }
+
+    /// Serialize in the format named by a file extension (`json`, `yaml`,
+    /// `yml` or `toml`), or `None` for any other extension
+    pub fn to_format_string(&self, extension: &str) -> Option<Result<String>> {
+        match extension {
+            "json" => Some(self.to_json_string()),
+            "yaml" | "yml" => Some(self.to_yaml_string()),
+            "toml" => Some(self.to_toml_string()),
+            _ => None,
+        }
+    }
+}
+
+/// A [`Config`] serialized with its keys in a stable order
+struct Ordered<'a> {
+    config: &'a Config,
+    /// Write rules with options after every other key, for TOML
+    tables_last: bool,
+}
+
+impl<'a> Ordered<'a> {
+    fn new(config: &'a Config) -> Self {
+        Self {
+            config,
+            tables_last: false,
+        }
+    }
+}
+
+/// A rule's entry with its options sorted
+enum OrderedRule<'a> {
+    Plain(&'a RuleConfig),
+    Options(BTreeMap<&'a String, &'a serde_json::Value>),
+}
+
+impl Serialize for OrderedRule<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            OrderedRule::Plain(rule) => rule.serialize(serializer),
+            OrderedRule::Options(options) => options.serialize(serializer),
+        }
+    }
+}
+
+impl Serialize for Ordered<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let config = self.config;
+        let mut rules: Vec<(&String, OrderedRule)> = config
+            .rules
+            .iter()
+            .map(|(key, rule)| {
+                let rule = match rule {
+                    RuleConfig::Options(options) => OrderedRule::Options(options.iter().collect()),
+                    plain => OrderedRule::Plain(plain),
+                };
+                (key, rule)
+            })
+            .collect();
+        rules.sort_by_key(|(key, _)| *key);
+        let (special, rules): (Vec<_>, Vec<_>) =
+            rules.into_iter().partition(|(key, _)| key.starts_with('$'));
+
+        let mut map = serializer.serialize_map(None)?;
+        for (key, value) in &special {
+            map.serialize_entry(key, value)?;
+        }
+        if let Some(default) = config.default {
+            map.serialize_entry("default", &default)?;
+        }
+        if let Some(extends) = &config.extends {
+            map.serialize_entry("extends", extends)?;
+        }
+        if let Some(preset) = &config.preset {
+            map.serialize_entry("preset", preset)?;
+        }
+        if let Some(math) = config.math {
+            map.serialize_entry("math", &math)?;
+        }
+        if !config.no_fix.is_empty() {
+            map.serialize_entry("no_fix", &config.no_fix)?;
+        }
+        // TOML needs plain values before tables, so only options tables
+        // follow the other settings there
+        let (plain, rules): (Vec<_>, Vec<_>) = if self.tables_last {
+            rules
+                .into_iter()
+                .partition(|(_, rule)| matches!(rule, OrderedRule::Plain(_)))
+        } else {
+            (Vec::new(), rules)
+        };
+        for (key, value) in &plain {
+            map.serialize_entry(key, value)?;
+        }
+        if !config.budgets.is_empty() {
+            let budgets: BTreeMap<_, _> = config.budgets.iter().collect();
+            map.serialize_entry("budgets", &budgets)?;
+        }
+        if let Some(skip_generated) = &config.skip_generated {
+            map.serialize_entry("skip_generated", skip_generated)?;
+        }
+        if !config.foreign_directives.is_empty() {
+            let directives: BTreeMap<_, _> = config.foreign_directives.iter().collect();
+            map.serialize_entry("foreign_directives", &directives)?;
+        }
+        for (key, value) in &rules {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"{
+        "$schema": "https://example.com/schema.json",
+        "default": true,
+        "preset": "kramdown",
+        "math": false,
+        "no_fix": ["MD013"],
+        "budgets": {"MD045": 0, "MD013": 5},
+        "skip_generated": {"markers": ["@generated"], "search_lines": 3},
+        "foreign_directives": {"vale off": "disable"},
+        "MD013": {"line_length": 100, "code_blocks": false, "severity": "warning"},
+        "MD033": {"allowed_elements": ["br", "img"]},
+        "MD001": false,
+        "MD041": "warning"
+    }"#;
+
+    #[test]
+    fn test_round_trip_all_formats() {
+        let config: Config = serde_json::from_str(SAMPLE).unwrap();
+        let expected = serde_json::to_value(&config).unwrap();
+
+        let json = config.to_json_string().unwrap();
+        let from_json: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_value(&from_json).unwrap(), expected);
+
+        let yaml = config.to_yaml_string().unwrap();
+        let from_yaml: Config = serde_yaml_ng::from_str(&yaml).unwrap();
+        assert_eq!(serde_json::to_value(&from_yaml).unwrap(), expected);
+
+        let toml = config.to_toml_string().unwrap();
+        let from_toml: Config = toml::from_str(&toml).unwrap();
+        assert_eq!(serde_json::to_value(&from_toml).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_output_order_is_stable() {
+        let config: Config = serde_json::from_str(SAMPLE).unwrap();
+        let yaml = config.to_yaml_string().unwrap();
+        let keys: Vec<&str> = yaml
+            .lines()
+            .filter(|line| !line.starts_with([' ', '-']))
+            .filter_map(|line| line.split(':').next())
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "$schema",
+                "default",
+                "preset",
+                "math",
+                "no_fix",
+                "budgets",
+                "skip_generated",
+                "foreign_directives",
+                "MD001",
+                "MD013",
+                "MD033",
+                "MD041",
+            ]
+        );
+        let json = config.to_json_string().unwrap();
+        let rule_keys: Vec<&str> = json
+            .lines()
+            .filter_map(|line| line.strip_prefix("  \"MD"))
+            .map(|line| &line[..3])
+            .collect();
+        assert_eq!(rule_keys, ["001", "013", "033", "041"]);
+        // Serializing again gives the same text, whatever the HashMap order
+        let again: Config = serde_yaml_ng::from_str(&yaml).unwrap();
+        assert_eq!(again.to_yaml_string().unwrap(), yaml);
+        // TOML keeps plain rules ahead of the tables
+        let toml = config.to_toml_string().unwrap();
+        assert!(
+            toml.contains("MD001 = false\nMD041 = \"warning\"\n\n[budgets]"),
+            "{}",
+            toml
+        );
+        assert!(
+            toml.contains(
+                "[MD013]\ncode_blocks = false\nline_length = 100\nseverity = \"warning\"\n"
+            )
+        );
+    }
+}
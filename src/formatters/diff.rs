@@ -0,0 +1,131 @@
+//! Unified diffs and fix proposals for previewing `--fix`
+
+use crate::types::LintError;
+use serde::{Deserialize, Serialize};
+use similar::TextDiff;
+use std::collections::BTreeMap;
+
+/// Unified diff (3 lines of context) turning `original` into `fixed`.
+///
+/// Headers are `a/<path>` and `b/<path>`; identical inputs give an empty string.
+///
+/// # Examples
+/// ```
+/// let diff = mkdlint::formatters::unified_diff("doc.md", "# Title  \n", "# Title\n");
+/// assert!(diff.starts_with("--- a/doc.md\n+++ b/doc.md\n"));
+/// assert!(diff.contains("-# Title  \n+# Title\n"));
+/// ```
+pub fn unified_diff(path: &str, original: &str, fixed: &str) -> String {
+    if original == fixed {
+        return String::new();
+    }
+    TextDiff::from_lines(original, fixed)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", path), &format!("b/{}", path))
+        .to_string()
+}
+
+/// A fixable violation that a fix proposal would resolve
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposedFix {
+    /// Primary rule ID (e.g. `MD009`)
+    pub rule: String,
+    /// 1-based line number in the original file
+    pub line: usize,
+    /// Rule description
+    pub message: String,
+}
+
+/// The changes `--fix` would make to one file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixProposal {
+    /// File path (or stdin name)
+    pub file: String,
+    /// Fixable violations found in the original content
+    pub fixes: Vec<ProposedFix>,
+    /// Unified diff from the original to the fixed content
+    pub diff: String,
+    /// Fixes applied while converging, by rule ID; empty unless the caller
+    /// fills it in from a [`FixOutcome`](crate::types::FixOutcome)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub applied: BTreeMap<String, usize>,
+}
+
+impl FixProposal {
+    /// Build a proposal from a file's original content, its lint errors, and
+    /// the content after fixing. `fix_only` and unfixable errors are skipped.
+    pub fn new(file: &str, original: &str, errors: &[LintError], fixed: &str) -> Self {
+        let fixes = errors
+            .iter()
+            .filter(|e| e.fix_info.is_some() && !e.fix_only)
+            .map(|e| ProposedFix {
+                rule: e.rule_names.first().copied().unwrap_or("").to_string(),
+                line: e.line_number,
+                message: e.rule_description.to_string(),
+            })
+            .collect();
+        Self {
+            file: file.to_string(),
+            fixes,
+            diff: unified_diff(file, original, fixed),
+            applied: BTreeMap::new(),
+        }
+    }
+}
+
+/// Format fix proposals as a pretty-printed JSON array
+pub fn format_fix_proposals_json(proposals: &[FixProposal]) -> String {
+    serde_json::to_string_pretty(proposals)
+        .unwrap_or_else(|e| format!("{{\"error\": \"Failed to serialize proposals: {}\"}}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FixInfo;
+
+    #[test]
+    fn test_unified_diff_identical() {
+        assert_eq!(unified_diff("a.md", "same\n", "same\n"), "");
+    }
+
+    #[test]
+    fn test_fix_proposal_json() {
+        let errors = vec![
+            LintError {
+                line_number: 1,
+                rule_names: &["MD009", "no-trailing-spaces"],
+                rule_description: "Trailing spaces",
+                fix_info: Some(FixInfo {
+                    line_number: None,
+                    edit_column: Some(8),
+                    delete_count: Some(2),
+                    insert_text: None,
+                }),
+                ..Default::default()
+            },
+            LintError {
+                line_number: 2,
+                rule_names: &["MD013"],
+                rule_description: "Line length",
+                ..Default::default()
+            },
+        ];
+        let proposal = FixProposal::new("a.md", "# Title  \n", &errors, "# Title\n");
+        assert_eq!(
+            proposal.fixes,
+            vec![ProposedFix {
+                rule: "MD009".to_string(),
+                line: 1,
+                message: "Trailing spaces".to_string(),
+            }]
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&format_fix_proposals_json(&[proposal])).unwrap();
+        assert_eq!(json[0]["file"], "a.md");
+        assert_eq!(json[0]["fixes"][0]["rule"], "MD009");
+        assert!(json[0]["diff"].as_str().unwrap().contains("+# Title\n"));
+    }
+}
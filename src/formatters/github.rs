@@ -15,7 +15,9 @@ use crate::types::{LintResults, Severity};
 /// ::error file=foo.md,line=5,col=1,endLine=5,endColumn=20,title=MD009::Trailing spaces [Expected: 0; Actual: 3]
 /// ```
 ///
-/// `fix_only` errors (internal auto-fix helpers) are silently skipped.
+/// File paths are written with forward slashes so GitHub can map them to
+/// repository files. `fix_only` errors (internal auto-fix helpers) are
+/// silently skipped.
 pub fn format_github(results: &LintResults) -> String {
     let mut lines: Vec<String> = Vec::new();
     let mut files: Vec<_> = results.results.keys().collect();
@@ -23,6 +25,7 @@ pub fn format_github(results: &LintResults) -> String {
 
     for file in &files {
         if let Some(errors) = results.results.get(*file) {
+            let file = crate::helpers::normalize_path(file);
             for error in errors {
                 if error.fix_only {
                     continue;
@@ -113,6 +116,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_github_forward_slash_paths() {
+        let mut results = LintResults::new();
+        results.add(
+            r"docs\guide.md".to_string(),
+            vec![make_error(Severity::Error, false)],
+        );
+        let output = format_github(&results);
+        assert!(output.contains("file=docs/guide.md,"), "Got: {output}");
+    }
+
     #[test]
     fn test_format_github_skips_fix_only() {
         let mut results = LintResults::new();
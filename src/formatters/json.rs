@@ -1,6 +1,7 @@
 //! JSON output formatter
 
-use crate::types::LintResults;
+use crate::types::{LintResults, LintStats};
+use serde::Serialize;
 
 /// Format lint results as JSON
 pub fn format_json(results: &LintResults) -> String {
@@ -8,6 +9,24 @@ pub fn format_json(results: &LintResults) -> String {
         .unwrap_or_else(|e| format!("{{\"error\": \"Failed to serialize results: {}\"}}", e))
 }
 
+/// Format lint results as JSON with a `"summary"` key holding
+/// [`LintResults::stats`]
+pub fn format_json_with_summary(results: &LintResults) -> String {
+    #[derive(Serialize)]
+    struct WithSummary<'a> {
+        #[serde(flatten)]
+        results: &'a LintResults,
+        summary: LintStats,
+    }
+
+    let output = WithSummary {
+        results,
+        summary: results.stats(),
+    };
+    serde_json::to_string_pretty(&output)
+        .unwrap_or_else(|e| format!("{{\"error\": \"Failed to serialize results: {}\"}}", e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,4 +61,26 @@ mod tests {
         assert_eq!(errors[0]["line_number"], 5);
         assert_eq!(errors[0]["rule_names"][0], "MD009");
     }
+
+    #[test]
+    fn test_format_json_with_summary() {
+        let mut results = LintResults::new();
+        results.add(
+            "test.md".to_string(),
+            vec![LintError {
+                line_number: 5,
+                rule_names: &["MD009"],
+                severity: Severity::Error,
+                ..Default::default()
+            }],
+        );
+        results.rules_run = vec!["MD009".to_string()];
+        let parsed: serde_json::Value =
+            serde_json::from_str(&format_json_with_summary(&results)).unwrap();
+        assert_eq!(parsed["results"]["test.md"][0]["line_number"], 5);
+        assert_eq!(parsed["summary"]["errors"], 1);
+        assert_eq!(parsed["summary"]["by_rule"]["MD009"], 1);
+        assert_eq!(parsed["summary"]["by_file"]["test.md"], 1);
+        assert_eq!(parsed["summary"]["rules_run"][0], "MD009");
+    }
 }
@@ -1,11 +1,20 @@
 //! Output formatters for lint results
 
+mod diff;
 mod github;
 mod json;
+mod ndjson;
 mod sarif;
 mod text;
 
+pub use diff::{FixProposal, ProposedFix, format_fix_proposals_json, unified_diff};
 pub use github::format_github;
-pub use json::format_json;
+pub use json::{format_json, format_json_with_summary};
+pub use ndjson::{
+    NDJSON_SCHEMA_VERSION, NdjsonEvent, format_ndjson, ndjson_file_events, ndjson_summary,
+};
 pub use sarif::format_sarif;
-pub use text::{format_text, format_text_with_context};
+pub use text::{
+    OutputTemplate, TEMPLATE_PLACEHOLDERS, format_text, format_text_template,
+    format_text_with_context,
+};
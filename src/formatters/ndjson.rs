@@ -0,0 +1,279 @@
+//! Newline-delimited JSON event stream formatter
+//!
+//! Emits one JSON object per line so wrapping tools can consume results
+//! incrementally. Every event carries a `type` tag:
+//!
+//! | `type`       | Fields                                                                 |
+//! |--------------|------------------------------------------------------------------------|
+//! | `start`      | `version` (schema version), `tool_version`                             |
+//! | `file_start` | `path`                                                                 |
+//! | `violation`  | `path`, `line`, `column`, `rule`, `alias`, `severity`, `message`, `detail`, `fixable` |
+//! | `file_end`   | `path`, `errors`, `warnings`                                           |
+//! | `fixed`      | `path`, `rules` (rule IDs whose fixes were applied)                    |
+//! | `summary`    | `files`, `errors`, `warnings`                                          |
+//!
+//! The `start` event is always first. Fields are only ever added within a
+//! schema version; removals or renames bump [`NDJSON_SCHEMA_VERSION`].
+
+use crate::types::{LintError, LintResults, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Version of the NDJSON event schema, reported in the `start` event.
+pub const NDJSON_SCHEMA_VERSION: u32 = 1;
+
+/// A single event in the NDJSON output stream.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NdjsonEvent {
+    /// First event of every stream
+    Start {
+        /// Event schema version ([`NDJSON_SCHEMA_VERSION`])
+        version: u32,
+        /// mkdlint version that produced the stream
+        tool_version: String,
+    },
+    /// A file is about to be reported
+    FileStart {
+        /// File path (or stdin name)
+        path: String,
+    },
+    /// A single lint violation
+    Violation {
+        /// File path (or stdin name)
+        path: String,
+        /// 1-based line number
+        line: usize,
+        /// 1-based column, when known
+        column: Option<usize>,
+        /// Primary rule ID (e.g. `MD009`)
+        rule: String,
+        /// Rule alias (e.g. `no-trailing-spaces`), when the rule has one
+        alias: Option<String>,
+        /// `error` or `warning`
+        severity: String,
+        /// Rule description
+        message: String,
+        /// Additional detail about this violation
+        detail: Option<String>,
+        /// Whether an automatic fix is available
+        fixable: bool,
+    },
+    /// All violations for a file have been reported
+    FileEnd {
+        /// File path (or stdin name)
+        path: String,
+        /// Number of error-severity violations
+        errors: usize,
+        /// Number of warning-severity violations
+        warnings: usize,
+    },
+    /// Fixes were applied to a file
+    Fixed {
+        /// File path (or stdin name)
+        path: String,
+        /// Rule IDs whose fixes were applied
+        rules: Vec<String>,
+        /// Fixes applied per rule ID, over all fix passes
+        #[serde(default)]
+        fixes: BTreeMap<String, usize>,
+    },
+    /// Last event of every stream
+    Summary {
+        /// Number of files linted
+        files: usize,
+        /// Total error-severity violations
+        errors: usize,
+        /// Total warning-severity violations
+        warnings: usize,
+    },
+}
+
+impl NdjsonEvent {
+    /// The `start` event for the current schema and library version.
+    pub fn start() -> Self {
+        NdjsonEvent::Start {
+            version: NDJSON_SCHEMA_VERSION,
+            tool_version: crate::VERSION.to_string(),
+        }
+    }
+
+    /// Serialize the event as a single JSON line (without trailing newline).
+    pub fn to_line(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|e| format!("{{\"type\":\"error\",\"message\":\"{}\"}}", e))
+    }
+}
+
+/// Build the `file_start`, `violation`, and `file_end` events for one file.
+///
+/// `fix_only` errors (internal auto-fix helpers) are skipped.
+pub fn ndjson_file_events(path: &str, errors: &[LintError]) -> Vec<NdjsonEvent> {
+    let mut events = vec![NdjsonEvent::FileStart {
+        path: path.to_string(),
+    }];
+    let mut error_count = 0;
+    let mut warning_count = 0;
+
+    for error in errors.iter().filter(|e| !e.fix_only) {
+        match error.severity {
+            Severity::Error => error_count += 1,
+            Severity::Warning => warning_count += 1,
+        }
+        events.push(NdjsonEvent::Violation {
+            path: path.to_string(),
+            line: error.line_number,
+            column: error.error_range.map(|(col, _)| col),
+            rule: error.rule_names.first().copied().unwrap_or("").to_string(),
+            alias: error.rule_names.get(1).map(|a| a.to_string()),
+            severity: error.severity.to_string(),
+            message: error.rule_description.to_string(),
+            detail: error.error_detail.clone(),
+            fixable: error.fix_info.is_some(),
+        });
+    }
+
+    events.push(NdjsonEvent::FileEnd {
+        path: path.to_string(),
+        errors: error_count,
+        warnings: warning_count,
+    });
+    events
+}
+
+/// Build the closing `summary` event for a set of results.
+pub fn ndjson_summary(results: &LintResults) -> NdjsonEvent {
+    NdjsonEvent::Summary {
+        files: results.results.len(),
+        errors: results.error_count(),
+        warnings: results.warning_count(),
+    }
+}
+
+/// Format complete lint results as an NDJSON event stream.
+///
+/// Files are emitted in sorted order. For incremental output, build events
+/// with [`ndjson_file_events`] from a [`crate::lint_sync_streaming`] callback.
+pub fn format_ndjson(results: &LintResults) -> String {
+    let mut files: Vec<_> = results.results.keys().collect();
+    files.sort();
+
+    let mut lines = vec![NdjsonEvent::start().to_line()];
+    for file in files {
+        for event in ndjson_file_events(file, &results.results[file]) {
+            lines.push(event.to_line());
+        }
+    }
+    lines.push(ndjson_summary(results).to_line());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FixInfo;
+
+    fn parse_stream(output: &str) -> Vec<NdjsonEvent> {
+        output
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("each line is one event"))
+            .collect()
+    }
+
+    #[test]
+    fn test_format_ndjson_empty() {
+        let events = parse_stream(&format_ndjson(&LintResults::new()));
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], NdjsonEvent::start());
+        assert_eq!(
+            events[1],
+            NdjsonEvent::Summary {
+                files: 0,
+                errors: 0,
+                warnings: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_format_ndjson_with_errors() {
+        let mut results = LintResults::new();
+        results.add(
+            "test.md".to_string(),
+            vec![
+                LintError {
+                    line_number: 3,
+                    rule_names: &["MD009", "no-trailing-spaces"],
+                    rule_description: "Trailing spaces",
+                    error_detail: Some("Expected: 0; Actual: 3".to_string()),
+                    error_range: Some((5, 3)),
+                    fix_info: Some(FixInfo {
+                        line_number: None,
+                        edit_column: Some(5),
+                        delete_count: Some(3),
+                        insert_text: None,
+                    }),
+                    severity: Severity::Warning,
+                    ..Default::default()
+                },
+                LintError {
+                    line_number: 1,
+                    rule_names: &["MD041"],
+                    rule_description: "First line heading",
+                    fix_only: true,
+                    ..Default::default()
+                },
+            ],
+        );
+
+        let events = parse_stream(&format_ndjson(&results));
+        assert_eq!(events.len(), 5);
+        assert_eq!(
+            events[1],
+            NdjsonEvent::FileStart {
+                path: "test.md".to_string()
+            }
+        );
+        assert_eq!(
+            events[2],
+            NdjsonEvent::Violation {
+                path: "test.md".to_string(),
+                line: 3,
+                column: Some(5),
+                rule: "MD009".to_string(),
+                alias: Some("no-trailing-spaces".to_string()),
+                severity: "warning".to_string(),
+                message: "Trailing spaces".to_string(),
+                detail: Some("Expected: 0; Actual: 3".to_string()),
+                fixable: true,
+            }
+        );
+        assert_eq!(
+            events[3],
+            NdjsonEvent::FileEnd {
+                path: "test.md".to_string(),
+                errors: 0,
+                warnings: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_ndjson_event_tags() {
+        let line = NdjsonEvent::Fixed {
+            path: "a.md".to_string(),
+            rules: vec!["MD009".to_string()],
+            fixes: [("MD009".to_string(), 2)].into(),
+        }
+        .to_line();
+        assert_eq!(
+            line,
+            r#"{"type":"fixed","path":"a.md","rules":["MD009"],"fixes":{"MD009":2}}"#
+        );
+
+        let start: serde_json::Value =
+            serde_json::from_str(&NdjsonEvent::start().to_line()).unwrap();
+        assert_eq!(start["type"], "start");
+        assert_eq!(start["version"], NDJSON_SCHEMA_VERSION);
+    }
+}
@@ -6,18 +6,23 @@ use crate::types::{LintResults, Severity};
 ///
 /// Absolute paths become `file:///...` URIs; relative paths are kept as-is
 /// (SARIF allows relative URIs resolved against `originalUriBaseIds`).
+/// Either way separators become forward slashes and a Windows verbatim
+/// prefix (`\\?\`) is dropped.
 fn path_to_uri(path: &str) -> String {
-    // Check using std::path first (handles Windows drive letters), and also
-    // treat any path that starts with '/' as absolute (Unix convention).
-    let p = std::path::Path::new(path);
-    let is_abs = p.is_absolute() || path.starts_with('/');
-    if is_abs {
-        // Encode as file URI — percent-encode spaces and other special chars.
-        // Normalise backslashes to forward slashes for Windows paths.
-        let encoded = path.replace('\\', "/").replace(' ', "%20");
+    let path = crate::helpers::normalize_path(path);
+    let bytes = path.as_bytes();
+    let drive = bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && &bytes[1..3] == b":/";
+    // Percent-encode spaces; UNC paths (`//server/share`) already carry the
+    // authority slashes, drive paths (`C:/...`) need the root one
+    let encoded = path.replace(' ', "%20");
+    if path.starts_with("//") {
+        format!("file:{encoded}")
+    } else if path.starts_with('/') {
         format!("file://{encoded}")
+    } else if drive {
+        format!("file:///{encoded}")
     } else {
-        path.to_string()
+        path
     }
 }
 
@@ -319,6 +324,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_path_to_uri_windows_paths() {
+        assert_eq!(path_to_uri(r"docs\a b.md"), "docs/a b.md");
+        assert_eq!(
+            path_to_uri(r"\\?\C:\repo\a b.md"),
+            "file:///C:/repo/a%20b.md"
+        );
+        assert_eq!(
+            path_to_uri(r"\\?\UNC\server\share\a.md"),
+            "file://server/share/a.md"
+        );
+        assert_eq!(path_to_uri("/home/a.md"), "file:///home/a.md");
+    }
+
     #[test]
     fn test_format_sarif_empty() {
         let results = LintResults::new();
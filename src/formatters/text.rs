@@ -1,6 +1,6 @@
 //! Plain text output formatter
 
-use crate::types::{LintResults, Severity};
+use crate::types::{LintError, LintResults, MarkdownlintError, Result, Severity};
 use colored::Colorize;
 use std::collections::HashMap;
 
@@ -55,6 +55,13 @@ pub fn format_text_with_context(
                     ));
                 }
 
+                if let Some(absolute) = results.absolute_line(file, error.line_number) {
+                    line.push_str(&format!(
+                        " {}",
+                        format!("[input line {}]", absolute).dimmed()
+                    ));
+                }
+
                 output.push(line);
 
                 // Show suggestion if available
@@ -77,40 +84,42 @@ pub fn format_text_with_context(
                     ));
                 }
 
-                // Show source line and underline if we have both source and error_range
-                if let (Some(lines), Some((col_start, col_len))) =
-                    (&source_lines, error.error_range)
+                // Show source line and underline if we have both source and error_range;
+                // the source comes from `sources` or the error's captured line text
+                let line_idx = error.line_number.saturating_sub(1);
+                let source_line = source_lines
+                    .as_ref()
+                    .and_then(|lines| lines.get(line_idx).copied())
+                    .or(error.line_text.as_deref());
+                if let Some((col_start, col_len)) = error.error_range
+                    && let Some(src) = source_line
                 {
-                    let line_idx = error.line_number.saturating_sub(1);
-                    if line_idx < lines.len() {
-                        let src = lines[line_idx];
-                        let line_num_width = error.line_number.to_string().len();
-                        let gutter = format!("{:>width$} |", "", width = line_num_width);
-                        let numbered = format!(
-                            "{:>width$} |  {}",
-                            error.line_number,
-                            src,
-                            width = line_num_width
-                        );
-                        output.push(format!("  {}", gutter.dimmed()));
-                        output.push(format!("  {}", numbered.dimmed()));
-
-                        // Build underline: spaces up to col_start, then carets for col_len
-                        let prefix_len = col_start.saturating_sub(1);
-                        let caret_len = col_len.max(1);
-                        let underline = format!(
-                            "{:>width$} |  {}{}",
-                            "",
-                            " ".repeat(prefix_len),
-                            "^".repeat(caret_len),
-                            width = line_num_width,
-                        );
-                        let colored_underline = match error.severity {
-                            Severity::Error => underline.red().to_string(),
-                            Severity::Warning => underline.yellow().to_string(),
-                        };
-                        output.push(format!("  {}", colored_underline));
-                    }
+                    let line_num_width = error.line_number.to_string().len();
+                    let gutter = format!("{:>width$} |", "", width = line_num_width);
+                    let numbered = format!(
+                        "{:>width$} |  {}",
+                        error.line_number,
+                        src,
+                        width = line_num_width
+                    );
+                    output.push(format!("  {}", gutter.dimmed()));
+                    output.push(format!("  {}", numbered.dimmed()));
+
+                    // Build underline: spaces up to col_start, then carets for col_len
+                    let prefix_len = col_start.saturating_sub(1);
+                    let caret_len = col_len.max(1);
+                    let underline = format!(
+                        "{:>width$} |  {}{}",
+                        "",
+                        " ".repeat(prefix_len),
+                        "^".repeat(caret_len),
+                        width = line_num_width,
+                    );
+                    let colored_underline = match error.severity {
+                        Severity::Error => underline.red().to_string(),
+                        Severity::Warning => underline.yellow().to_string(),
+                    };
+                    output.push(format!("  {}", colored_underline));
                 }
             }
         }
@@ -133,10 +142,186 @@ pub fn format_text_with_context(
     output.join("\n")
 }
 
+/// Placeholders accepted by [`OutputTemplate`], in documentation order
+pub const TEMPLATE_PLACEHOLDERS: [&str; 8] = [
+    "file", "line", "column", "rule", "alias", "severity", "message", "detail",
+];
+
+/// A one-line-per-error output format such as `{file}:{line}:{column} {rule} {message}`.
+///
+/// Placeholders are `{file}`, `{line}`, `{column}` (1 when the error has no
+/// range), `{rule}` (e.g. `MD009`), `{alias}` (e.g. `no-trailing-spaces`,
+/// or the rule ID if there is none), `{severity}` (`error` or `warning`),
+/// `{message}` (the rule description) and `{detail}` (empty if none).
+/// `{{` and `}}` stand for literal braces.
+///
+/// # Examples
+/// ```
+/// use mkdlint::formatters::OutputTemplate;
+///
+/// assert!(OutputTemplate::parse("{file}:{line}: {message}").is_ok());
+/// assert!(OutputTemplate::parse("{file}:{lnum}").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Literal(String),
+    Placeholder(Field),
+}
+
+/// An error field a placeholder stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    File,
+    Line,
+    Column,
+    Rule,
+    Alias,
+    Severity,
+    Message,
+    Detail,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "file" => Self::File,
+            "line" => Self::Line,
+            "column" => Self::Column,
+            "rule" => Self::Rule,
+            "alias" => Self::Alias,
+            "severity" => Self::Severity,
+            "message" => Self::Message,
+            "detail" => Self::Detail,
+            _ => return None,
+        })
+    }
+}
+
+impl OutputTemplate {
+    /// Parse a template, rejecting unknown placeholders and unmatched braces
+    pub fn parse(template: &str) -> Result<Self> {
+        let invalid = |message: String| {
+            MarkdownlintError::InvalidConfig(format!("output template: {}", message))
+        };
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(invalid(format!("unclosed '{{{}'", name))),
+                        }
+                    }
+                    let field = Field::from_name(&name).ok_or_else(|| {
+                        invalid(format!(
+                            "unknown placeholder '{{{}}}' (expected one of {})",
+                            name,
+                            TEMPLATE_PLACEHOLDERS
+                                .map(|p| format!("{{{}}}", p))
+                                .join(", ")
+                        ))
+                    })?;
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(TemplatePart::Placeholder(field));
+                }
+                '}' => {
+                    return Err(invalid(
+                        "unmatched '}' (use '}}' for a literal brace)".into(),
+                    ));
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Render the template for one error in `file`
+    pub fn render(&self, file: &str, error: &LintError) -> String {
+        let mut line = String::new();
+        for part in &self.parts {
+            match part {
+                TemplatePart::Literal(text) => line.push_str(text),
+                TemplatePart::Placeholder(field) => match field {
+                    Field::File => line.push_str(file),
+                    Field::Line => line.push_str(&error.line_number.to_string()),
+                    Field::Column => {
+                        let column = error.error_range.map_or(1, |(start, _)| start);
+                        line.push_str(&column.to_string());
+                    }
+                    Field::Rule => line.push_str(error.rule_names.first().copied().unwrap_or("")),
+                    Field::Alias => line.push_str(
+                        error
+                            .rule_names
+                            .get(1)
+                            .or(error.rule_names.first())
+                            .copied()
+                            .unwrap_or(""),
+                    ),
+                    Field::Severity => line.push_str(match error.severity {
+                        Severity::Error => "error",
+                        Severity::Warning => "warning",
+                    }),
+                    Field::Message => line.push_str(error.rule_description),
+                    Field::Detail => line.push_str(error.error_detail.as_deref().unwrap_or("")),
+                },
+            }
+        }
+        line
+    }
+}
+
+impl std::str::FromStr for OutputTemplate {
+    type Err = MarkdownlintError;
+
+    fn from_str(template: &str) -> Result<Self> {
+        Self::parse(template)
+    }
+}
+
+/// Format lint results one line per error using `template`, without color,
+/// context, or a summary line
+pub fn format_text_template(results: &LintResults, template: &OutputTemplate) -> String {
+    let mut files: Vec<_> = results.results.keys().collect();
+    files.sort();
+
+    let mut output = Vec::new();
+    for file in files {
+        for error in &results.results[file] {
+            if !error.fix_only {
+                output.push(template.render(file, error));
+            }
+        }
+    }
+    output.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::LintError;
 
     #[test]
     fn test_format_text_empty() {
@@ -164,6 +349,25 @@ mod tests {
         assert!(output.contains("MD001"));
     }
 
+    #[test]
+    fn test_format_text_notes_input_line() {
+        colored::control::set_override(false);
+        let mut results = LintResults::new();
+        results.add(
+            "stdin:2".to_string(),
+            vec![LintError {
+                line_number: 3,
+                rule_names: &["MD025", "single-title"],
+                rule_description: "Multiple top-level headings in the same document",
+                ..Default::default()
+            }],
+        );
+        results.line_offsets.insert("stdin:2".to_string(), 10);
+        let output = format_text(&results);
+        assert!(output.contains("stdin:2: 3: MD025/single-title"));
+        assert!(output.contains("[input line 13]"));
+    }
+
     #[test]
     fn test_format_text_summary() {
         colored::control::set_override(false);
@@ -243,6 +447,32 @@ mod tests {
         assert!(!output.contains("^^^"), "No context without sources");
     }
 
+    #[test]
+    fn test_format_text_context_from_line_text() {
+        colored::control::set_override(false);
+        let mut results = LintResults::new();
+        results.add(
+            "test.md".to_string(),
+            vec![LintError {
+                line_number: 3,
+                rule_names: &["MD009"],
+                rule_description: "Trailing spaces",
+                error_range: Some((10, 3)),
+                line_text: Some("Some text   ".to_string()),
+                related: None,
+                ..Default::default()
+            }],
+        );
+
+        // No sources map: the captured line text is used instead
+        let output = format_text(&results);
+        assert!(
+            output.contains("3 |  Some text   "),
+            "Should show line text"
+        );
+        assert!(output.contains("^^^"), "Should show underline carets");
+    }
+
     #[test]
     fn test_format_text_no_context_without_error_range() {
         colored::control::set_override(false);
@@ -267,4 +497,62 @@ mod tests {
         // Has the error line but no underline (no error_range)
         assert!(!output.contains("^^^"), "No carets without error_range");
     }
+
+    fn template_results() -> LintResults {
+        let mut results = LintResults::new();
+        results.add(
+            "docs/a.md".to_string(),
+            vec![
+                LintError {
+                    line_number: 3,
+                    rule_names: &["MD009", "no-trailing-spaces"],
+                    rule_description: "Trailing spaces",
+                    error_detail: Some("Expected: 0; Actual: 2".to_string()),
+                    error_range: Some((7, 2)),
+                    severity: Severity::Warning,
+                    ..Default::default()
+                },
+                LintError {
+                    line_number: 4,
+                    rule_names: &["MD047"],
+                    rule_description: "Files should end with a single newline character",
+                    fix_only: true,
+                    ..Default::default()
+                },
+            ],
+        );
+        results
+    }
+
+    #[test]
+    fn test_format_text_template_vim() {
+        // Vim: set errorformat=%f:%l:%c:\ %t%*[^:]:\ %m
+        let template = OutputTemplate::parse(
+            "{file}:{line}:{column}: {severity}: {rule}/{alias} {message} [{detail}]",
+        )
+        .unwrap();
+        assert_eq!(
+            format_text_template(&template_results(), &template),
+            "docs/a.md:3:7: warning: MD009/no-trailing-spaces Trailing spaces [Expected: 0; Actual: 2]"
+        );
+    }
+
+    #[test]
+    fn test_format_text_template_literal_braces() {
+        let template = OutputTemplate::parse("{{{rule}}} {{line}} }}{line}{{").unwrap();
+        assert_eq!(
+            format_text_template(&template_results(), &template),
+            "{MD009} {line} }3{"
+        );
+    }
+
+    #[test]
+    fn test_output_template_rejects_bad_placeholders() {
+        let err = OutputTemplate::parse("{file}:{lnum}")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown placeholder '{lnum}'"), "{err}");
+        assert!(OutputTemplate::parse("{file").is_err());
+        assert!(OutputTemplate::parse("file}").is_err());
+    }
 }
@@ -0,0 +1,150 @@
+//! Per-file analysis shared between rules
+
+use super::{ListGeometry, ListLineInfo, ParsedHeading, ReferenceDefinition, ReferenceLink};
+use crate::parser::Token;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// Document structure that several rules need, computed at most once per
+/// file.
+///
+/// `lint_content` creates one cache per file and hands it to every rule
+/// through [`RuleParams`](crate::types::RuleParams); each field is computed
+/// by the first rule that asks for it. Rules should go through the
+/// `RuleParams` accessors, which fall back to computing directly when no
+/// cache is present (e.g. in unit tests).
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::AnalysisCache;
+///
+/// let lines = ["# Title\n", "\n", "## Title\n"];
+/// let cache = AnalysisCache::new(&lines, &[]);
+/// assert_eq!(cache.heading_ids(), ["title", "title-1"]);
+/// ```
+#[derive(Debug)]
+pub struct AnalysisCache<'a> {
+    lines: &'a [&'a str],
+    tokens: &'a [Token],
+    code_block_lines: OnceLock<Vec<bool>>,
+    literal_spans: OnceLock<Vec<Vec<Range<usize>>>>,
+    headings: OnceLock<Vec<ParsedHeading>>,
+    heading_ids: OnceLock<Vec<String>>,
+    reference_definitions: OnceLock<Vec<ReferenceDefinition>>,
+    reference_links: OnceLock<Vec<ReferenceLink>>,
+    list_geometry: OnceLock<Vec<ListGeometry>>,
+    list_context: OnceLock<Vec<Option<ListLineInfo>>>,
+}
+
+impl<'a> AnalysisCache<'a> {
+    /// Create an empty cache over a file's lines and parser tokens
+    pub fn new(lines: &'a [&'a str], tokens: &'a [Token]) -> Self {
+        Self {
+            lines,
+            tokens,
+            code_block_lines: OnceLock::new(),
+            literal_spans: OnceLock::new(),
+            headings: OnceLock::new(),
+            heading_ids: OnceLock::new(),
+            reference_definitions: OnceLock::new(),
+            reference_links: OnceLock::new(),
+            list_geometry: OnceLock::new(),
+            list_context: OnceLock::new(),
+        }
+    }
+
+    /// See [`code_block_lines`](super::code_block_lines)
+    pub fn code_block_lines(&self) -> &[bool] {
+        self.code_block_lines
+            .get_or_init(|| super::code_block_lines(self.lines, self.tokens))
+    }
+
+    /// See [`literal_spans`](super::literal_spans)
+    pub fn literal_spans(&self) -> &[Vec<Range<usize>>] {
+        self.literal_spans
+            .get_or_init(|| super::literal_spans(self.lines, self.tokens))
+    }
+
+    /// See [`parse_headings`](super::parse_headings)
+    pub fn headings(&self) -> &[ParsedHeading] {
+        self.headings
+            .get_or_init(|| super::parse_headings(self.lines))
+    }
+
+    /// Anchor IDs of the headings, in order, with `-1`, `-2`, ... suffixes
+    /// on duplicates (see [`collect_heading_ids`](super::collect_heading_ids))
+    pub fn heading_ids(&self) -> &[String] {
+        self.heading_ids
+            .get_or_init(|| super::anchor_ids(self.headings()))
+    }
+
+    /// See [`reference_definitions`](super::reference_definitions)
+    pub fn reference_definitions(&self) -> &[ReferenceDefinition] {
+        self.reference_definitions
+            .get_or_init(|| super::reference_definitions(self.lines, self.code_block_lines()))
+    }
+
+    /// See [`reference_links`](super::reference_links)
+    pub fn reference_links(&self) -> &[ReferenceLink] {
+        self.reference_links
+            .get_or_init(|| super::reference_links(self.lines, self.code_block_lines()))
+    }
+
+    /// See [`list_geometry`](super::list_geometry)
+    pub fn list_geometry(&self) -> &[ListGeometry] {
+        self.list_geometry
+            .get_or_init(|| super::list_geometry(self.tokens, self.lines))
+    }
+
+    /// See [`list_context`](super::list_context)
+    pub fn list_context(&self) -> &[Option<ListLineInfo>] {
+        self.list_context
+            .get_or_init(|| super::list_context(self.lines, self.tokens))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analysis_matches_direct_helpers() {
+        let content = "# A\n\n```\n[x]: skipped\n```\n\nSee [a][x] and [y].\n\n[x]: a.md\n## A\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        let cache = AnalysisCache::new(&lines, &tokens);
+        let mask = super::super::code_block_lines(&lines, &tokens);
+
+        assert_eq!(cache.code_block_lines(), mask);
+        assert_eq!(
+            cache.literal_spans(),
+            super::super::literal_spans(&lines, &tokens)
+        );
+        assert_eq!(
+            cache.heading_ids(),
+            super::super::collect_heading_ids(&lines)
+        );
+        assert_eq!(
+            cache.reference_definitions(),
+            super::super::reference_definitions(&lines, &mask)
+        );
+        assert_eq!(
+            cache.reference_links(),
+            super::super::reference_links(&lines, &mask)
+        );
+        assert_eq!(cache.reference_definitions().len(), 1);
+
+        let content = "- a\n  1. b\n\n  c\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        let cache = AnalysisCache::new(&lines, &tokens);
+        assert_eq!(
+            cache.list_geometry(),
+            super::super::list_geometry(&tokens, &lines)
+        );
+        assert_eq!(
+            cache.list_context(),
+            super::super::list_context(&lines, &tokens)
+        );
+    }
+}
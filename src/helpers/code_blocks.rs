@@ -0,0 +1,271 @@
+//! Per-line code block detection shared by rules
+
+use crate::parser::Token;
+use std::ops::Range;
+
+/// Tracks fenced code blocks line by line, without a full parse.
+///
+/// A fence opens with 3+ backticks or tildes (indented at most 3 spaces) and
+/// closes with a fence of the same character at least as long, so a `~~~`
+/// line inside a backtick block does not end it.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::CodeFenceTracker;
+///
+/// let mut tracker = CodeFenceTracker::new();
+/// let lines = ["text", "````md", "```", "````", "text"];
+/// let flags: Vec<bool> = lines.iter().map(|l| tracker.update(l)).collect();
+/// assert_eq!(flags, [false, true, true, true, false]);
+/// ```
+#[derive(Debug, Default)]
+pub struct CodeFenceTracker {
+    /// Character and length of the open fence
+    open: Option<(char, usize)>,
+}
+
+impl CodeFenceTracker {
+    /// Create a tracker positioned outside any code block
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the tracker is currently inside a fenced code block
+    pub fn in_code_block(&self) -> bool {
+        self.open.is_some()
+    }
+
+    /// Feed the next line; returns whether it belongs to a fenced code block,
+    /// counting the opening and closing fence lines.
+    pub fn update(&mut self, line: &str) -> bool {
+        let fence = parse_fence(line);
+        match (self.open, fence) {
+            (None, Some((ch, len, _))) => {
+                self.open = Some((ch, len));
+                true
+            }
+            (None, None) => false,
+            (Some((open_ch, open_len)), Some((ch, len, bare)))
+                if ch == open_ch && len >= open_len && bare =>
+            {
+                self.open = None;
+                true
+            }
+            (Some(_), _) => true,
+        }
+    }
+}
+
+/// A code fence line split into its parts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FenceLine<'a> {
+    /// Number of leading spaces (at most 3)
+    pub indent: usize,
+    /// Fence character, `` ` `` or `~`
+    pub marker: char,
+    /// Length of the fence run
+    pub length: usize,
+    /// Everything after the fence run, line ending excluded
+    pub info: &'a str,
+}
+
+/// Split a line into its code fence parts, or `None` if it isn't a fence
+///
+/// # Examples
+/// ```
+/// let fence = mkdlint::helpers::fence_line("  ~~~~ rust \n").unwrap();
+/// assert_eq!((fence.indent, fence.marker, fence.length), (2, '~', 4));
+/// assert_eq!(fence.info, " rust ");
+/// assert!(mkdlint::helpers::fence_line("``` a`b\n").is_none());
+/// ```
+pub fn fence_line(line: &str) -> Option<FenceLine<'_>> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let marker = rest.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = rest.chars().take_while(|c| *c == marker).count();
+    if length < 3 {
+        return None;
+    }
+    let info = &rest[length..];
+    // Backtick fence info strings may not contain backticks
+    if marker == '`' && info.contains('`') {
+        return None;
+    }
+    Some(FenceLine {
+        indent,
+        marker,
+        length,
+        info,
+    })
+}
+
+/// Parse a fence line into (char, length, has nothing after the fence)
+fn parse_fence(line: &str) -> Option<(char, usize, bool)> {
+    fence_line(line).map(|f| (f.marker, f.length, f.info.trim().is_empty()))
+}
+
+/// Indentation width of a line, counting a tab as 4 columns
+fn indent_width(line: &str) -> usize {
+    let mut width = 0;
+    for c in line.chars() {
+        match c {
+            ' ' => width += 1,
+            '\t' => width += 4 - width % 4,
+            _ => break,
+        }
+    }
+    width
+}
+
+/// Per-line flags marking lines inside code blocks (index `line_number - 1`).
+///
+/// Fenced and indented code blocks are both covered, fence lines included.
+/// When `tokens` is non-empty the flags come from the parser's `codeBlock`
+/// tokens; otherwise fences are tracked with [`CodeFenceTracker`] and
+/// indented blocks are recognized heuristically (indented 4+ columns after
+/// a blank line, outside list items).
+///
+/// # Examples
+/// ```
+/// let lines = ["Text\n", "\n", "    code\n", "\n", "```\n", "more\n", "```\n"];
+/// let flags = mkdlint::helpers::code_block_lines(&lines, &[]);
+/// assert_eq!(flags, [false, false, true, false, true, true, true]);
+/// ```
+pub fn code_block_lines(lines: &[&str], tokens: &[Token]) -> Vec<bool> {
+    let mut flags = vec![false; lines.len()];
+
+    if !tokens.is_empty() {
+        for token in tokens.iter().filter(|t| t.is_type("codeBlock")) {
+            let start = token.start_line.max(1);
+            let mut end = token.end_line.min(lines.len());
+            // Indented blocks' source positions include trailing blank lines
+            if token.fence_info().is_none() {
+                while end > start && lines[end - 1].trim().is_empty() {
+                    end -= 1;
+                }
+            }
+            for flag in flags.iter_mut().take(end).skip(start - 1) {
+                *flag = true;
+            }
+        }
+        return flags;
+    }
+
+    let mut fences = CodeFenceTracker::new();
+    let mut prev_blank = true;
+    let mut prev_indented_code = false;
+    let mut in_list = false;
+
+    for (flag, line) in flags.iter_mut().zip(lines) {
+        if fences.update(line) {
+            *flag = true;
+            prev_blank = false;
+            prev_indented_code = false;
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            prev_blank = true;
+            continue;
+        }
+
+        let indent = indent_width(line);
+        if indent >= 4 && !in_list && (prev_blank || prev_indented_code) {
+            *flag = true;
+            prev_indented_code = true;
+        } else {
+            prev_indented_code = false;
+            if indent == 0 {
+                in_list = super::list_marker_len(trimmed).is_some();
+            } else if indent < 4 && super::list_marker_len(trimmed).is_some() {
+                in_list = true;
+            }
+        }
+        prev_blank = false;
+    }
+
+    flags
+}
+
+/// Byte ranges of each line (index `line_number - 1`) covered by inline
+/// text that is taken literally: code spans and, when the parser recognized
+/// it, `$` math (inline or display), delimiters included.
+///
+/// Line-based rules skip matches inside these ranges, so `` `a * b` `` and
+/// `$a * b$` are never read as emphasis. Ranges come from the parser's
+/// `code` and `math` tokens; without tokens every line is empty.
+///
+/// # Examples
+/// ```
+/// let content = "Use `a * b` or $a * b$ here\n";
+/// let lines: Vec<&str> = content.split_inclusive('\n').collect();
+/// let spans = mkdlint::helpers::literal_spans(&lines, &mkdlint::parser::parse(content));
+/// assert_eq!(spans[0], [4..11, 15..22]);
+/// ```
+pub fn literal_spans(lines: &[&str], tokens: &[Token]) -> Vec<Vec<Range<usize>>> {
+    let mut spans = vec![Vec::new(); lines.len()];
+    for token in tokens.iter().filter(|t| t.is_any_type(&["code", "math"])) {
+        for line_number in token.start_line.max(1)..=token.end_line.min(lines.len()) {
+            let line = lines[line_number - 1].trim_end_matches(['\n', '\r']);
+            let start = if line_number == token.start_line {
+                token.start_column.saturating_sub(1)
+            } else {
+                0
+            };
+            let end = if line_number == token.end_line {
+                token.end_column.min(line.len())
+            } else {
+                line.len()
+            };
+            if start < end {
+                spans[line_number - 1].push(start..end);
+            }
+        }
+    }
+    for line_spans in &mut spans {
+        line_spans.sort_by_key(|span| span.start);
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tilde_fence_does_not_close_backticks() {
+        let lines = ["```\n", "~~~\n", "code\n", "```\n", "after\n"];
+        assert_eq!(
+            code_block_lines(&lines, &[]),
+            [true, true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn test_indented_code_inside_list_is_not_code() {
+        let lines = ["- item\n", "\n", "    continuation\n"];
+        assert_eq!(code_block_lines(&lines, &[]), [false, false, false]);
+    }
+
+    #[test]
+    fn test_indented_code_needs_blank_line() {
+        let lines = ["Paragraph\n", "    lazy continuation\n"];
+        assert_eq!(code_block_lines(&lines, &[]), [false, false]);
+    }
+
+    #[test]
+    fn test_tokens_and_fallback_agree() {
+        let content = "# Title\n\n    indented\n    code\n\nText\n\n~~~sh\nls\n~~~\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        assert_eq!(
+            code_block_lines(&lines, &tokens),
+            code_block_lines(&lines, &[])
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Emphasis and strong spans located by the parser
+
+use crate::parser::Token;
+
+/// An emphasis (`*a*`) or strong (`**a**`) span from the parser
+///
+/// Working from parser tokens rather than raw text means underscores inside
+/// words (`snake_case`), math (`$x_i$`), and code spans are never mistaken
+/// for emphasis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmphasisSpan {
+    /// Line of the opening marker (1-based)
+    pub start_line: usize,
+    /// Byte column of the opening marker (1-based)
+    pub start_column: usize,
+    /// Line of the closing marker (1-based)
+    pub end_line: usize,
+    /// Byte column of the last closing marker byte (1-based)
+    pub end_column: usize,
+    /// Marker character, `b'*'` or `b'_'`
+    pub marker: u8,
+    /// Whether a letter or digit touches the span on either side, as in
+    /// `foo*bar*baz`; underscores cannot emphasize there
+    pub intraword: bool,
+}
+
+impl EmphasisSpan {
+    /// `"asterisk"` or `"underscore"`, as used by the style options
+    pub fn style(&self) -> &'static str {
+        if self.marker == b'*' {
+            "asterisk"
+        } else {
+            "underscore"
+        }
+    }
+}
+
+/// Spans of `token_type` (`"emphasis"` or `"strong"`) in document order
+///
+/// Tokens whose source position doesn't start and end on a marker
+/// character are skipped.
+pub fn emphasis_spans(lines: &[&str], tokens: &[Token], token_type: &str) -> Vec<EmphasisSpan> {
+    let byte_at = |line: usize, column: usize| {
+        column
+            .checked_sub(1)
+            .and_then(|i| lines.get(line.checked_sub(1)?)?.as_bytes().get(i).copied())
+    };
+
+    tokens
+        .iter()
+        .filter(|t| t.token_type == token_type)
+        .filter_map(|t| {
+            let marker = byte_at(t.start_line, t.start_column)?;
+            if !matches!(marker, b'*' | b'_') || byte_at(t.end_line, t.end_column)? != marker {
+                return None;
+            }
+            let is_word = |b: Option<u8>| b.is_some_and(|b| b.is_ascii_alphanumeric());
+            let intraword = is_word(byte_at(t.start_line, t.start_column - 1))
+                || is_word(byte_at(t.end_line, t.end_column + 1));
+            Some(EmphasisSpan {
+                start_line: t.start_line,
+                start_column: t.start_column,
+                end_line: t.end_line,
+                end_column: t.end_column,
+                marker,
+                intraword,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(content: &str, token_type: &str) -> Vec<EmphasisSpan> {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        emphasis_spans(&lines, &crate::parser::parse(content), token_type)
+    }
+
+    #[test]
+    fn test_emphasis_spans_skip_words_and_math() {
+        let found = spans("my_var_name and $x_i$ and _real_\n", "emphasis");
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].start_column, found[0].end_column), (27, 32));
+        assert_eq!(found[0].style(), "underscore");
+        assert!(!found[0].intraword);
+    }
+
+    #[test]
+    fn test_emphasis_spans_nested_and_multiline() {
+        let found = spans("***x*** and **a\nb**\n", "strong");
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].start_column, found[0].end_column), (2, 6));
+        assert_eq!((found[1].start_line, found[1].end_line), (1, 2));
+        assert_eq!(found[1].end_column, 3);
+    }
+
+    #[test]
+    fn test_emphasis_spans_intraword() {
+        let found = spans("foo*bar*baz\n", "emphasis");
+        assert_eq!(found.len(), 1);
+        assert!(found[0].intraword);
+    }
+}
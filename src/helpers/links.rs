@@ -0,0 +1,247 @@
+//! Link destination and reference definition parsing shared by rules
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// A reference definition line: `[label]: destination "title"`
+static DEFINITION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^ {0,3}\[([^\]]+)\]:(.*)$").expect("valid regex"));
+
+/// Full reference links: `[text][label]`
+static FULL_REF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\[([^\]]+)\]").expect("valid regex"));
+
+/// Collapsed reference links: `[label][]`
+static COLLAPSED_REF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\[\]").expect("valid regex"));
+
+/// Shortcut reference links: `[label]` (not followed by `[`, `(` or `:`)
+static SHORTCUT_REF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\](?:[^(\[:]|$)").expect("valid regex"));
+
+/// The destination of a link, without angle brackets or title.
+///
+/// `raw` is the text between an inline link's parentheses or after a
+/// reference definition's colon. `<>`, whitespace-only and missing
+/// destinations all come back empty.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::link_destination;
+///
+/// assert_eq!(link_destination(" https://example.com \"Title\""), "https://example.com");
+/// assert_eq!(link_destination("<my page.md>"), "my page.md");
+/// assert_eq!(link_destination("< >"), "");
+/// assert_eq!(link_destination("  "), "");
+/// ```
+pub fn link_destination(raw: &str) -> &str {
+    let raw = raw.trim();
+    match raw.strip_prefix('<') {
+        Some(rest) => rest.find('>').map_or(rest, |end| &rest[..end]).trim(),
+        None => raw.split_whitespace().next().unwrap_or(""),
+    }
+}
+
+/// A link reference definition's position and contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceDefinition {
+    /// 1-based line of the `[label]:` part
+    pub line_number: usize,
+    /// Number of lines it spans (2 when the destination is on the next line)
+    pub line_count: usize,
+    /// Label as written, without brackets
+    pub label: String,
+    /// Destination as returned by [`link_destination`]
+    pub destination: String,
+}
+
+/// Reference definitions in a document, in order.
+///
+/// A destination may start on the line after the label, as CommonMark
+/// allows. Lines flagged in `code_block_mask` are skipped; definitions in
+/// block quotes are not recognized.
+///
+/// # Examples
+/// ```
+/// let lines = ["Text\n", "[Docs]:\n", "  https://example.com\n"];
+/// let defs = mkdlint::helpers::reference_definitions(&lines, &[]);
+/// assert_eq!(defs.len(), 1);
+/// assert_eq!((defs[0].line_number, defs[0].line_count), (2, 2));
+/// assert_eq!(defs[0].label, "Docs");
+/// ```
+pub fn reference_definitions(lines: &[&str], code_block_mask: &[bool]) -> Vec<ReferenceDefinition> {
+    let mut definitions = Vec::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        if code_block_mask.get(idx).copied().unwrap_or(false) {
+            continue;
+        }
+        let Some(cap) = DEFINITION_RE.captures(line.trim_end_matches(['\n', '\r'])) else {
+            continue;
+        };
+
+        let mut rest = cap.get(2).map_or("", |m| m.as_str());
+        let mut line_count = 1;
+        if rest.trim().is_empty()
+            && let Some(next) = lines.get(idx + 1)
+            && !next.trim().is_empty()
+            && !DEFINITION_RE.is_match(next)
+        {
+            rest = next;
+            line_count = 2;
+        }
+
+        definitions.push(ReferenceDefinition {
+            line_number: idx + 1,
+            line_count,
+            label: cap[1].to_string(),
+            destination: link_destination(rest).to_string(),
+        });
+    }
+
+    definitions
+}
+
+/// The form of a reference link or image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// `[text][label]`
+    Full,
+    /// `[label][]`
+    Collapsed,
+    /// `[label]`
+    Shortcut,
+}
+
+/// A reference link or image occurrence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceLink {
+    /// 1-based line number
+    pub line_number: usize,
+    /// Which of the three reference forms this is
+    pub kind: ReferenceKind,
+    /// Label as written, without brackets
+    pub label: String,
+    /// Source text of the link (`[text][label]`, `[label][]` or `[label]`)
+    pub text: String,
+}
+
+/// Reference links and images in a document, in order.
+///
+/// On each line, full references come first, then collapsed, then shortcut
+/// ones. Shortcut matches are permissive: the label of `[text][label]` is
+/// also reported as a shortcut, so they suit "is this label used" checks
+/// rather than "is this label defined" ones. Lines flagged in
+/// `code_block_mask` are skipped.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::{ReferenceKind, reference_links};
+///
+/// let lines = ["See [the docs][docs] and [api][].\n"];
+/// let links = reference_links(&lines, &[]);
+/// assert_eq!(links[0].kind, ReferenceKind::Full);
+/// assert_eq!(links[0].label, "docs");
+/// assert_eq!(links[1].kind, ReferenceKind::Collapsed);
+/// assert_eq!(links[1].text, "[api][]");
+/// ```
+pub fn reference_links(lines: &[&str], code_block_mask: &[bool]) -> Vec<ReferenceLink> {
+    let mut links = Vec::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        if code_block_mask.get(idx).copied().unwrap_or(false) {
+            continue;
+        }
+        let mut push = |kind, label: &str, text: &str| {
+            links.push(ReferenceLink {
+                line_number: idx + 1,
+                kind,
+                label: label.to_string(),
+                text: text.to_string(),
+            });
+        };
+        for cap in FULL_REF_RE.captures_iter(line) {
+            push(ReferenceKind::Full, &cap[2], &cap[0]);
+        }
+        for cap in COLLAPSED_REF_RE.captures_iter(line) {
+            push(ReferenceKind::Collapsed, &cap[1], &cap[0]);
+        }
+        for cap in SHORTCUT_REF_RE.captures_iter(line) {
+            push(ReferenceKind::Shortcut, &cap[1], &format!("[{}]", &cap[1]));
+        }
+    }
+
+    links
+}
+
+/// Reference definitions in a document, keyed by normalized label.
+///
+/// Labels are lowercased with inner whitespace collapsed; values are link
+/// destinations as returned by [`link_destination`]. See
+/// [`reference_definitions`] for what counts as a definition; the first
+/// definition of a label wins.
+///
+/// # Examples
+/// ```
+/// let lines = ["[Docs]:\n", "  https://example.com\n", "[empty]: <>\n"];
+/// let defs = mkdlint::helpers::link_reference_definitions(&lines, &[]);
+/// assert_eq!(defs["docs"], "https://example.com");
+/// assert_eq!(defs["empty"], "");
+/// ```
+pub fn link_reference_definitions(
+    lines: &[&str],
+    code_block_mask: &[bool],
+) -> HashMap<String, String> {
+    destinations_by_label(&reference_definitions(lines, code_block_mask))
+}
+
+/// Destinations of `definitions` keyed by normalized label, as in
+/// [`link_reference_definitions`]; the first definition of a label wins.
+pub fn destinations_by_label(definitions: &[ReferenceDefinition]) -> HashMap<String, String> {
+    let mut destinations = HashMap::new();
+    for definition in definitions {
+        destinations
+            .entry(normalize_label(&definition.label))
+            .or_insert_with(|| definition.destination.clone());
+    }
+    destinations
+}
+
+/// Normalize a reference label for matching (case-insensitive, collapsed
+/// whitespace)
+pub fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition_destination_on_next_line() {
+        let lines = ["[a]:\n", "\n", "[b]:\n", "<b.md> \"Title\"\n"];
+        let defs = link_reference_definitions(&lines, &[]);
+        assert_eq!(defs["a"], "");
+        assert_eq!(defs["b"], "b.md");
+    }
+
+    #[test]
+    fn test_definitions_skip_code_blocks_and_keep_first() {
+        let lines = [
+            "```\n",
+            "[a]: skipped\n",
+            "```\n",
+            "[A]: first\n",
+            "[a]: second\n",
+        ];
+        let mask = crate::helpers::code_block_lines(&lines, &[]);
+        let defs = link_reference_definitions(&lines, &mask);
+        assert_eq!(defs.len(), 1);
+        assert_eq!(defs["a"], "first");
+    }
+}
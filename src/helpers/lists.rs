@@ -0,0 +1,512 @@
+//! List item geometry shared by the list rules
+
+use super::{CodeFenceTracker, blockquote_prefix_len, leading_whitespace_len, list_marker_len};
+use crate::parser::Token;
+use crate::types::FixInfo;
+use std::collections::BTreeMap;
+
+/// Where a list item's marker and content sit on the item's first line.
+///
+/// Offsets are 0-based bytes into the line, so they include any blockquote
+/// markers in front of the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListItemGeometry {
+    /// 1-based line of the marker
+    pub line_number: usize,
+    /// 1-based last line of the item, including nested blocks
+    pub end_line: usize,
+    /// Offset of the marker
+    pub marker_start: usize,
+    /// Length of the marker (`-` is 1, `10.` is 3)
+    pub marker_len: usize,
+    /// The bullet (`-`, `*`, `+`), or the delimiter after an ordered
+    /// item's number (`.`, `)`)
+    pub marker: char,
+    /// Offset of the item's text, or `None` if nothing follows the marker
+    /// on its line. Past four spaces the text is an indented code block and
+    /// the content starts one space after the marker, as in CommonMark.
+    pub content_start: Option<usize>,
+}
+
+impl ListItemGeometry {
+    /// Offset just past the marker
+    pub fn marker_end(&self) -> usize {
+        self.marker_start + self.marker_len
+    }
+
+    /// Spaces between the marker and the item's text, if it has any
+    pub fn spacing(&self) -> Option<usize> {
+        self.content_start.map(|start| start - self.marker_end())
+    }
+
+    /// Offset where the item's content starts: its text, or one column
+    /// past the marker when the first line has none. Continuation lines
+    /// and nested lists line up here.
+    pub fn content_column(&self) -> usize {
+        self.content_start.unwrap_or(self.marker_end() + 1)
+    }
+}
+
+/// One list (a nested list is a separate entry) and its direct items
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListGeometry {
+    /// Whether the list is ordered (`1.`, `1)`)
+    pub ordered: bool,
+    /// Whether every item fits on one line, with nothing between items
+    pub single_line: bool,
+    /// Number of lists this one is nested in (0 for a top-level list)
+    pub depth: usize,
+    /// Items in document order
+    pub items: Vec<ListItemGeometry>,
+}
+
+/// The geometry of every list in the document, outer lists first.
+///
+/// Items whose line doesn't show a marker where the parser put it are left
+/// out.
+///
+/// # Examples
+/// ```
+/// let content = "1.  One\n    - Nested\n";
+/// let lines: Vec<&str> = content.split_inclusive('\n').collect();
+/// let lists = mkdlint::helpers::list_geometry(&mkdlint::parser::parse(content), &lines);
+/// assert_eq!(lists[0].items[0].content_start, Some(4));
+/// assert_eq!(lists[1].items[0].marker_start, 4);
+/// ```
+pub fn list_geometry(tokens: &[Token], lines: &[&str]) -> Vec<ListGeometry> {
+    tokens
+        .iter()
+        .filter(|token| token.is_type("list"))
+        .map(|list| {
+            let items: Vec<ListItemGeometry> = list
+                .children(tokens)
+                .filter(|child| child.is_type("listItem"))
+                .filter_map(|item| item_geometry(item, lines))
+                .collect();
+            ListGeometry {
+                ordered: list.is_ordered_list() == Some(true),
+                single_line: list.line_span() == items.len(),
+                depth: list.ancestors(tokens).filter(|t| t.is_type("list")).count(),
+                items,
+            }
+        })
+        .collect()
+}
+
+fn item_geometry(item: &Token, lines: &[&str]) -> Option<ListItemGeometry> {
+    let line = lines.get(item.start_line.checked_sub(1)?)?;
+    let marker_start = item.start_column.checked_sub(1)?;
+    let rest = line.get(marker_start..)?;
+    let prefix_len = list_marker_len(rest)?;
+    marker_geometry(
+        line,
+        marker_start,
+        prefix_len,
+        item.start_line,
+        item.end_line,
+    )
+}
+
+/// Geometry of the item whose marker starts at `marker_start`, where
+/// `prefix_len` is the marker plus the whitespace after it
+fn marker_geometry(
+    line: &str,
+    marker_start: usize,
+    prefix_len: usize,
+    line_number: usize,
+    end_line: usize,
+) -> Option<ListItemGeometry> {
+    let rest = &line[marker_start..];
+    let marker_len = if rest.starts_with(['-', '*', '+']) {
+        1
+    } else {
+        rest.bytes().take_while(u8::is_ascii_digit).count() + 1
+    };
+    let marker = rest[marker_len - 1..].chars().next()?;
+    let spaces = prefix_len - marker_len;
+    let has_text = !rest[prefix_len..].trim_end_matches(['\n', '\r']).is_empty();
+    let marker_end = marker_start + marker_len;
+    Some(ListItemGeometry {
+        line_number,
+        end_line: end_line.max(line_number),
+        marker_start,
+        marker_len,
+        marker,
+        content_start: has_text.then(|| marker_end + if spaces > 4 { 1 } else { spaces }),
+    })
+}
+
+/// The list item a line belongs to, from [`list_context`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListLineInfo {
+    /// The innermost item containing the line
+    pub item: ListItemGeometry,
+    /// Whether the item's list is ordered
+    pub ordered: bool,
+    /// Number of lists the item's list is nested in (0 for a top-level list)
+    pub depth: usize,
+    /// The item the item's list is nested in, if any; nested lists line up
+    /// with its [`content_column`](ListItemGeometry::content_column)
+    pub parent: Option<ListItemGeometry>,
+}
+
+impl ListLineInfo {
+    /// Whether `line_number` (1-based) is the line with the item's marker
+    pub fn is_marker_line(&self, line_number: usize) -> bool {
+        self.item.line_number == line_number
+    }
+}
+
+/// For each line, the innermost list item containing it (index
+/// `line_number - 1`), or `None` outside lists.
+///
+/// Uses the `list`/`listItem` tokens when there are any, else a line scan
+/// that follows CommonMark's indentation rules for the common cases: lazy
+/// continuation lines, blank lines between blocks of an item, and fenced
+/// code blocks inside items. Lines of code blocks inside an item belong to
+/// it; combine with [`code_block_lines`](super::code_block_lines) to skip
+/// them.
+///
+/// # Examples
+/// ```
+/// let content = "- One\n  1. Nested\n     more\n";
+/// let lines: Vec<&str> = content.split_inclusive('\n').collect();
+/// let context = mkdlint::helpers::list_context(&lines, &mkdlint::parser::parse(content));
+/// let info = context[2].unwrap();
+/// assert!(info.ordered && !info.is_marker_line(3));
+/// assert_eq!((info.depth, info.item.marker, info.item.content_column()), (1, '.', 5));
+/// ```
+pub fn list_context(lines: &[&str], tokens: &[Token]) -> Vec<Option<ListLineInfo>> {
+    if tokens.is_empty() {
+        return scan_list_context(lines);
+    }
+    let mut context = vec![None; lines.len()];
+    // Outer lists come first, so nested items overwrite their parents' lines
+    for list in list_geometry(tokens, lines) {
+        // The parent item's lines are filled in by now
+        let parent = list
+            .items
+            .first()
+            .filter(|_| list.depth > 0)
+            .and_then(|first| context.get(first.line_number - 1).copied().flatten())
+            .map(|parent: ListLineInfo| parent.item);
+        for item in list.items {
+            let info = ListLineInfo {
+                item,
+                ordered: list.ordered,
+                depth: list.depth,
+                parent,
+            };
+            let end = item.end_line.min(lines.len());
+            for slot in context.iter_mut().take(end).skip(item.line_number - 1) {
+                *slot = Some(info);
+            }
+        }
+    }
+    context
+}
+
+/// [`list_context`] without tokens
+fn scan_list_context(lines: &[&str]) -> Vec<Option<ListLineInfo>> {
+    let mut fences = CodeFenceTracker::new();
+    // Every item seen, and for each line the index of its item
+    let mut items: Vec<ListLineInfo> = Vec::new();
+    // For each item, the index of its parent item
+    let mut parents: Vec<Option<usize>> = Vec::new();
+    let mut line_items: Vec<Option<usize>> = vec![None; lines.len()];
+    // Open items, outermost first
+    let mut open: Vec<usize> = Vec::new();
+    let mut blanks: Vec<usize> = Vec::new();
+    // Whether the previous line was paragraph text, which a lazy line
+    // continues
+    let mut prev_text = false;
+
+    for (idx, line) in lines.iter().enumerate() {
+        let text = line.trim_end_matches(['\n', '\r']);
+        let opens_fence = !fences.in_code_block();
+        let in_code = fences.update(line);
+        if text.trim().is_empty() {
+            blanks.push(idx);
+            prev_text = false;
+            continue;
+        }
+        let quote = blockquote_prefix_len(text);
+        let indent = quote + leading_whitespace_len(&text[quote..]);
+        let close_to = |open: &mut Vec<usize>, column: usize| {
+            while open
+                .last()
+                .is_some_and(|&i| items[i].item.content_column() > column)
+            {
+                open.pop();
+            }
+        };
+
+        let marker = (!in_code)
+            .then(|| list_marker_len(&text[indent..]))
+            .flatten()
+            .filter(|_| !is_thematic_break(&text[indent..]));
+        if in_code {
+            // Lines inside a fence stay with the item the fence opened in
+            if opens_fence {
+                close_to(&mut open, indent);
+            }
+            prev_text = false;
+        } else if let Some(prefix_len) = marker {
+            close_to(&mut open, indent);
+            let Some(item) = marker_geometry(text, indent, prefix_len, idx + 1, idx + 1) else {
+                continue;
+            };
+            items.push(ListLineInfo {
+                item,
+                ordered: item.marker == '.' || item.marker == ')',
+                depth: open.len(),
+                parent: None,
+            });
+            parents.push(open.last().copied());
+            open.push(items.len() - 1);
+            prev_text = item.content_start.is_some();
+        } else {
+            let lazy = prev_text && is_lazy_continuation(&text[indent..]);
+            if !lazy {
+                close_to(&mut open, indent);
+            }
+            prev_text = !text[indent..].starts_with(['#', '>', '`', '~', '|', '<']);
+        }
+
+        line_items[idx] = open.last().copied();
+        // Blank lines before this one belong to the innermost open item
+        // that started before them
+        for blank in blanks.drain(..) {
+            line_items[blank] = open
+                .iter()
+                .rev()
+                .copied()
+                .find(|&i| items[i].item.line_number <= blank);
+        }
+        for &i in &open {
+            items[i].item.end_line = idx + 1;
+        }
+    }
+
+    // Parents are filled in last, once their end lines are known
+    for (i, parent) in parents.into_iter().enumerate() {
+        items[i].parent = parent.map(|p| items[p].item);
+    }
+    line_items
+        .into_iter()
+        .map(|item| item.map(|i| items[i]))
+        .collect()
+}
+
+/// Whether `text` (indentation removed) is a thematic break such as `* * *`
+fn is_thematic_break(text: &str) -> bool {
+    let text = text.trim_end();
+    let Some(first) = text.chars().next() else {
+        return false;
+    };
+    matches!(first, '-' | '*' | '_')
+        && text.chars().filter(|&c| c == first).count() >= 3
+        && text.chars().all(|c| c == first || c == ' ' || c == '\t')
+}
+
+/// Whether `text` (indentation removed) can continue the previous line's
+/// paragraph without being indented: it starts no other block
+fn is_lazy_continuation(text: &str) -> bool {
+    !text.starts_with(['#', '>', '<', '|', '`', '~'])
+        && list_marker_len(text).is_none()
+        // Setext underlines and thematic breaks
+        && !text.chars().all(|c| matches!(c, '-' | '=' | '*' | '_' | ' ' | '\t'))
+}
+
+/// Fixes that move whole lines sideways: each entry of `shifts` maps a
+/// 1-based line number to the columns to add (positive) or remove
+/// (negative) in front of its text, after any blockquote markers.
+///
+/// Used to carry an item's continuation lines and nested lists along when
+/// a fix moves the item's text, so they stay aligned with it. Blank lines
+/// are skipped, and a line never loses more indentation than it has.
+pub fn shift_lines_fixes(lines: &[&str], shifts: &BTreeMap<usize, isize>) -> Vec<FixInfo> {
+    shifts
+        .iter()
+        .filter(|&(_, &shift)| shift != 0)
+        .filter_map(|(&line_number, &shift)| {
+            let line = lines.get(line_number.checked_sub(1)?)?;
+            if line.trim().is_empty() {
+                return None;
+            }
+            let quote = blockquote_prefix_len(line);
+            let (delete, insert) = if shift > 0 {
+                (0, shift.unsigned_abs())
+            } else {
+                let indent = leading_whitespace_len(&line[quote..]);
+                (shift.unsigned_abs().min(indent), 0)
+            };
+            (delete > 0 || insert > 0).then(|| FixInfo {
+                line_number: Some(line_number),
+                edit_column: Some(quote + 1),
+                delete_count: Some(delete as i32),
+                insert_text: (insert > 0).then(|| " ".repeat(insert)),
+            })
+        })
+        .collect()
+}
+
+/// Add `shift` to every line after the item's first, for
+/// [`shift_lines_fixes`]
+pub fn shift_item_body(shifts: &mut BTreeMap<usize, isize>, item: &ListItemGeometry, shift: isize) {
+    for line_number in item.line_number + 1..=item.end_line {
+        *shifts.entry(line_number).or_default() += shift;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn geometry(content: &str) -> Vec<ListGeometry> {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        list_geometry(&crate::parser::parse(content), &lines)
+    }
+
+    #[test]
+    fn test_list_geometry_nested() {
+        let lists = geometry("1.  One\n    1. Nested\n    10.   Ten\n2. Two\n");
+        assert_eq!(lists.len(), 2);
+
+        let outer = &lists[0];
+        assert!(outer.ordered);
+        assert!(!outer.single_line);
+        assert_eq!(outer.items[0].spacing(), Some(2));
+        assert_eq!(outer.items[0].end_line, 3);
+        assert_eq!(outer.items[1].content_start, Some(3));
+
+        let inner = &lists[1];
+        assert!(inner.single_line);
+        assert_eq!(inner.items[0].marker_start, 4);
+        assert_eq!(inner.items[0].marker_len, 2);
+        assert_eq!(inner.items[1].marker_end(), 7);
+        assert_eq!(inner.items[1].spacing(), Some(3));
+    }
+
+    #[test]
+    fn test_list_geometry_empty_item_and_code_content() {
+        let lists = geometry("-\n  text\n-      code\n");
+        assert_eq!(lists[0].items[0].content_start, None);
+        assert_eq!(lists[0].items[1].spacing(), Some(1));
+    }
+
+    /// Marker line, depth, marker and content column of a line's item
+    type Summary = Option<(usize, usize, char, usize)>;
+
+    /// Each line's [`Summary`], from the tokens and from the line scan
+    fn context(content: &str) -> [Vec<Summary>; 2] {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let summary = |context: Vec<Option<ListLineInfo>>| {
+            context
+                .into_iter()
+                .map(|info| {
+                    info.map(|i| {
+                        (
+                            i.item.line_number,
+                            i.depth,
+                            i.item.marker,
+                            i.item.content_column(),
+                        )
+                    })
+                })
+                .collect()
+        };
+        [
+            summary(list_context(&lines, &crate::parser::parse(content))),
+            summary(list_context(&lines, &[])),
+        ]
+    }
+
+    #[test]
+    fn test_list_context_nested_ordered_and_unordered() {
+        let content = "- One\n  1. Nested\n     more\n  2) Other\n     * Deep\n- Two\n\nAfter\n";
+        let expected = [
+            Some((1, 0, '-', 2)),
+            Some((2, 1, '.', 5)),
+            Some((2, 1, '.', 5)),
+            Some((4, 1, ')', 5)),
+            Some((5, 2, '*', 7)),
+            Some((6, 0, '-', 2)),
+            None,
+            None,
+        ];
+        for context in context(content) {
+            assert_eq!(context, expected);
+        }
+    }
+
+    #[test]
+    fn test_list_context_parents() {
+        let content = "1. One\n   - x\n     - y\n   - z\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        for context in [
+            list_context(&lines, &crate::parser::parse(content)),
+            list_context(&lines, &[]),
+        ] {
+            let parents: Vec<Option<(usize, usize)>> = context
+                .iter()
+                .map(|info| {
+                    let parent = info.unwrap().parent?;
+                    Some((parent.line_number, parent.content_column()))
+                })
+                .collect();
+            assert_eq!(parents, [None, Some((1, 3)), Some((2, 5)), Some((1, 3))]);
+            assert_eq!(context[1].unwrap().parent.unwrap().end_line, 4);
+        }
+    }
+
+    #[test]
+    fn test_list_context_continuation_paragraphs() {
+        // A lazy line, then a second paragraph after a blank line
+        let content = "1. First\nlazy\n\n   Second\n2. Next\n\nOutside\n";
+        let item = |line| Some((line, 0, '.', 3));
+        let expected = [item(1), item(1), item(1), item(1), item(5), None, None];
+        for context in context(content) {
+            assert_eq!(context, expected);
+        }
+
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let info = list_context(&lines, &[])[1].unwrap();
+        assert!(info.ordered && !info.is_marker_line(2));
+        assert_eq!(info.item.end_line, 4);
+    }
+
+    #[test]
+    fn test_list_context_code_blocks_in_items() {
+        let content = "- Item\n\n  ```\n  - not an item\n\n  ```\n```\n- code\n```\n";
+        let item = Some((1, 0, '-', 2));
+        let expected = [item, item, item, item, item, item, None, None, None];
+        for context in context(content) {
+            assert_eq!(context, expected);
+        }
+    }
+
+    #[test]
+    fn test_list_context_scan_skips_thematic_breaks_and_reads_blockquotes() {
+        let lines = ["* * *\n", "> - Quoted\n", ">   text\n"];
+        let context = list_context(&lines, &[]);
+        assert_eq!(context[0], None);
+        let info = context[2].unwrap();
+        assert_eq!(info.item.line_number, 2);
+        assert_eq!(info.item.content_column(), 4);
+    }
+
+    #[test]
+    fn test_shift_lines_fixes() {
+        let lines = ["- a\n", "    b\n", "\n", ">   c\n", "d\n"];
+        let shifts = BTreeMap::from([(2, -2), (3, -2), (4, 1), (5, -1)]);
+        let fixes = shift_lines_fixes(&lines, &shifts);
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(fixes[0].line_number, Some(2));
+        assert_eq!(fixes[0].edit_column, Some(1));
+        assert_eq!(fixes[0].delete_count, Some(2));
+        assert_eq!(fixes[1].line_number, Some(4));
+        assert_eq!(fixes[1].edit_column, Some(3));
+        assert_eq!(fixes[1].insert_text.as_deref(), Some(" "));
+    }
+}
@@ -1,5 +1,31 @@
 //! Helper utilities
 
+mod analysis;
+mod code_blocks;
+mod emphasis;
+mod links;
+mod lists;
+mod paths;
+mod patterns;
+mod plaintext;
+
+pub use analysis::AnalysisCache;
+pub use code_blocks::{CodeFenceTracker, FenceLine, code_block_lines, fence_line, literal_spans};
+pub use emphasis::{EmphasisSpan, emphasis_spans};
+pub use links::{
+    ReferenceDefinition, ReferenceKind, ReferenceLink, destinations_by_label, link_destination,
+    link_reference_definitions, normalize_label, reference_definitions, reference_links,
+};
+pub use lists::{
+    ListGeometry, ListItemGeometry, ListLineInfo, list_context, list_geometry, shift_item_body,
+    shift_lines_fixes,
+};
+pub use paths::{normalize_path, relative_report_path};
+pub use patterns::{MAX_PATTERN_LEN, PatternCache};
+pub use plaintext::extract_plaintext;
+
+use crate::types::FixInfo;
+
 /// Check if a string is a valid URL
 pub fn is_url(s: &str) -> bool {
     s.starts_with("http://") || s.starts_with("https://")
@@ -10,6 +36,261 @@ pub fn is_empty_string(s: &str) -> bool {
     s.is_empty()
 }
 
+/// The UTF-8 byte order mark (`U+FEFF`, encoded as 3 bytes).
+pub const BOM: &str = "\u{FEFF}";
+
+/// Split a leading UTF-8 byte order mark off `content`.
+///
+/// Returns whether a BOM was present and the content without it.
+///
+/// # Examples
+/// ```
+/// assert_eq!(mkdlint::helpers::strip_bom("\u{FEFF}# Title"), (true, "# Title"));
+/// assert_eq!(mkdlint::helpers::strip_bom("# Title"), (false, "# Title"));
+/// ```
+pub fn strip_bom(content: &str) -> (bool, &str) {
+    match content.strip_prefix(BOM) {
+        Some(rest) => (true, rest),
+        None => (false, content),
+    }
+}
+
+/// Whether `c` is whitespace other than a line ending.
+fn is_inline_whitespace(c: char) -> bool {
+    c.is_whitespace() && c != '\n' && c != '\r'
+}
+
+/// Byte length of the whitespace run at the start of `s`, stopping at line endings.
+///
+/// Any Unicode whitespace counts, so tabs and ideographic spaces (`U+3000`)
+/// are included.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::leading_whitespace_len;
+/// assert_eq!(leading_whitespace_len(" \tText"), 2);
+/// assert_eq!(leading_whitespace_len("\u{3000}Text"), 3);
+/// assert_eq!(leading_whitespace_len("  \n"), 2);
+/// ```
+pub fn leading_whitespace_len(s: &str) -> usize {
+    s.len() - s.trim_start_matches(is_inline_whitespace).len()
+}
+
+/// Byte length of the whitespace run at the end of `s`, stopping at line endings.
+///
+/// The counterpart of [`leading_whitespace_len`].
+pub fn trailing_whitespace_len(s: &str) -> usize {
+    s.len() - s.trim_end_matches(is_inline_whitespace).len()
+}
+
+/// Fix replacing the whitespace run at 1-based byte `column` with one space.
+///
+/// A run that already starts with a space keeps it and drops the rest.
+pub fn normalize_whitespace_fix(whitespace: &str, column: usize) -> FixInfo {
+    if whitespace.starts_with(' ') {
+        FixInfo {
+            line_number: None,
+            edit_column: Some(column + 1),
+            delete_count: Some((whitespace.len() - 1) as i32),
+            insert_text: None,
+        }
+    } else {
+        FixInfo {
+            line_number: None,
+            edit_column: Some(column),
+            delete_count: Some(whitespace.len() as i32),
+            insert_text: Some(" ".to_string()),
+        }
+    }
+}
+
+/// A whitespace run as the `Actual` of an error detail: its length when it
+/// is all ASCII spaces, else the characters it is made of.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::describe_whitespace;
+/// assert_eq!(describe_whitespace("   "), "3");
+/// assert_eq!(describe_whitespace("\t"), "tab");
+/// assert_eq!(describe_whitespace(" \t\t"), "space, 2 tabs");
+/// assert_eq!(describe_whitespace("\u{3000}"), "ideographic space");
+/// ```
+pub fn describe_whitespace(whitespace: &str) -> String {
+    if whitespace.chars().all(|c| c == ' ') {
+        return whitespace.len().to_string();
+    }
+    let name = |c: char| match c {
+        ' ' => "space".to_string(),
+        '\t' => "tab".to_string(),
+        '\u{a0}' => "no-break space".to_string(),
+        '\u{3000}' => "ideographic space".to_string(),
+        c => format!("U+{:04X} character", c as u32),
+    };
+    let mut runs: Vec<(char, usize)> = Vec::new();
+    for c in whitespace.chars() {
+        match runs.last_mut() {
+            Some((last, count)) if *last == c => *count += 1,
+            _ => runs.push((c, 1)),
+        }
+    }
+    runs.into_iter()
+        .map(|(c, count)| match count {
+            1 => name(c),
+            _ => format!("{} {}s", count, name(c)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The opening `#` run of an ATX heading line and the whitespace after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtxOpening<'a> {
+    /// Byte length of the indentation before the first `#`
+    pub indent: usize,
+    /// Number of `#` characters (1-6)
+    pub level: usize,
+    /// Whitespace between the `#` run and the text; tabs and other Unicode
+    /// whitespace count, line endings don't
+    pub whitespace: &'a str,
+    /// Everything after that whitespace, including the line ending
+    pub rest: &'a str,
+}
+
+/// Split a line that starts with 1-6 `#` characters into its ATX parts.
+///
+/// Shared by the heading spacing rules so `#\tTitle` and `#  Title` are
+/// measured the same way.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::atx_opening;
+/// let opening = atx_opening("##\tTitle\n").unwrap();
+/// assert_eq!((opening.level, opening.whitespace, opening.rest), (2, "\t", "Title\n"));
+/// assert!(atx_opening("####### Seven\n").is_none());
+/// ```
+pub fn atx_opening(line: &str) -> Option<AtxOpening<'_>> {
+    let trimmed = line.trim_start();
+    let level = trimmed.bytes().take_while(|&b| b == b'#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let after_hash = &trimmed[level..];
+    let (whitespace, rest) = after_hash.split_at(leading_whitespace_len(after_hash));
+    Some(AtxOpening {
+        indent: line.len() - trimmed.len(),
+        level,
+        whitespace,
+        rest,
+    })
+}
+
+/// ATX heading text (after the opening hashes) without its closing
+/// sequence, or None if it has none.
+///
+/// As in CommonMark, the closing sequence is a trailing run of `#` preceded
+/// by whitespace, or making up all of the text: `C#` and an escaped `\##`
+/// end in text, not a closing sequence. Like [`leading_whitespace_len`], any
+/// Unicode whitespace counts.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::strip_closing_sequence;
+/// assert_eq!(strip_closing_sequence("Title ##"), Some("Title"));
+/// assert_eq!(strip_closing_sequence("Foo \\# #"), Some("Foo \\#"));
+/// assert_eq!(strip_closing_sequence("C#"), None);
+/// ```
+pub fn strip_closing_sequence(text: &str) -> Option<&str> {
+    let text = text.trim_end();
+    let without = text.trim_end_matches('#');
+    if without.len() == text.len() {
+        None
+    } else if without.is_empty() {
+        Some("")
+    } else if without.ends_with(char::is_whitespace) {
+        Some(without.trim_end())
+    } else {
+        None
+    }
+}
+
+/// Byte length of the blockquote markers (`>`, each with one optional
+/// following space) at the start of `line`, including nested quotes.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::blockquote_prefix_len;
+/// assert_eq!(blockquote_prefix_len("> > | a |"), 4);
+/// assert_eq!(blockquote_prefix_len("| a |"), 0);
+/// ```
+pub fn blockquote_prefix_len(line: &str) -> usize {
+    let bytes = line.as_bytes();
+    let mut pos = 0;
+    loop {
+        let indent = bytes[pos..].iter().take_while(|&&b| b == b' ').count();
+        if indent > 3 || bytes.get(pos + indent) != Some(&b'>') {
+            return pos;
+        }
+        pos += indent + 1;
+        if bytes.get(pos) == Some(&b' ') {
+            pos += 1;
+        }
+    }
+}
+
+/// Byte length of a list marker (`-`, `*`, `+`, `1.`, `1)`) at the start of
+/// `s` plus the whitespace after it, or `None` if `s` does not start one.
+pub fn list_marker_len(s: &str) -> Option<usize> {
+    let marker = if s.starts_with(['-', '*', '+']) {
+        1
+    } else {
+        let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+        if !(1..=9).contains(&digits) || !s[digits..].starts_with(['.', ')']) {
+            return None;
+        }
+        digits + 1
+    };
+    let rest = s[marker..].trim_end_matches(['\n', '\r']);
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    Some(marker + leading_whitespace_len(rest))
+}
+
+/// Byte length of the container prefix of `line`: blockquote markers, then
+/// indentation and a list marker if one follows.
+///
+/// What remains is the line's content as a block inside those containers,
+/// e.g. a table row inside a quoted list item.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::container_prefix_len;
+/// let line = "> - | a | b |";
+/// assert_eq!(&line[container_prefix_len(line)..], "| a | b |");
+/// ```
+pub fn container_prefix_len(line: &str) -> usize {
+    let quote = blockquote_prefix_len(line);
+    let rest = &line[quote..];
+    let indent = rest.len() - rest.trim_start_matches(' ').len();
+    match list_marker_len(&rest[indent..]) {
+        Some(marker) => quote + indent + marker,
+        None => quote,
+    }
+}
+
+/// Content of a candidate table row, after its container prefix.
+///
+/// Only a table's first row can share its line with a list marker; later
+/// rows are continuation lines, so when `continues_table` is set only
+/// blockquote markers are stripped and a `- | -` delimiter row stays intact.
+pub fn table_row_content(line: &str, continues_table: bool) -> &str {
+    if continues_table {
+        &line[blockquote_prefix_len(line)..]
+    } else {
+        &line[container_prefix_len(line)..]
+    }
+}
+
 /// Detect line ending style
 pub fn detect_line_ending(content: &str) -> &str {
     if content.contains("\r\n") {
@@ -145,10 +426,15 @@ pub fn parse_heading_line(trimmed: &str) -> Option<(usize, &str)> {
 /// This is used by MD051 for fragment validation and by the linting pipeline
 /// for building the workspace heading index.
 pub fn collect_heading_ids(lines: &[&str]) -> Vec<String> {
+    anchor_ids(&parse_headings(lines))
+}
+
+/// Anchor IDs for parsed headings, suffixing duplicates
+fn anchor_ids(headings: &[ParsedHeading]) -> Vec<String> {
     let mut ids = Vec::new();
     let mut id_counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
 
-    for heading in parse_headings(lines) {
+    for heading in headings {
         let base_id = heading_to_anchor_id(&heading.text);
         let count = id_counts.entry(base_id.clone()).or_insert(0);
         let final_id = if *count == 0 {
@@ -181,6 +467,17 @@ mod tests {
         assert!(!is_url("not a url"));
     }
 
+    #[test]
+    fn test_strip_closing_sequence() {
+        assert_eq!(strip_closing_sequence("Title ##"), Some("Title"));
+        assert_eq!(strip_closing_sequence("Title\t#  "), Some("Title"));
+        assert_eq!(strip_closing_sequence("Foo \\# #"), Some("Foo \\#"));
+        assert_eq!(strip_closing_sequence("C#"), None);
+        assert_eq!(strip_closing_sequence("Foo \\##"), None);
+        assert_eq!(strip_closing_sequence("Run `x #`"), None);
+        assert_eq!(strip_closing_sequence("見出し\u{3000}#"), Some("見出し"));
+    }
+
     #[test]
     fn test_detect_line_ending() {
         assert_eq!(detect_line_ending("line1\nline2"), "\n");
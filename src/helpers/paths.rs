@@ -0,0 +1,111 @@
+//! Path strings for glob matching and reports, independent of platform
+
+/// Prefix `canonicalize` puts on Windows UNC paths (`\\?\UNC\server\share`)
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+/// Prefix `canonicalize` puts on other Windows paths (`\\?\C:\docs`)
+const VERBATIM_PREFIX: &str = r"\\?\";
+
+/// `path` with forward slashes and without a Windows verbatim prefix.
+///
+/// Pure string manipulation, so it behaves the same on every platform.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::normalize_path;
+/// assert_eq!(normalize_path(r"docs\guide.md"), "docs/guide.md");
+/// assert_eq!(normalize_path(r"\\?\C:\repo\README.md"), "C:/repo/README.md");
+/// assert_eq!(normalize_path(r"\\?\UNC\server\share\a.md"), "//server/share/a.md");
+/// ```
+pub fn normalize_path(path: &str) -> String {
+    let path = match path.strip_prefix(VERBATIM_UNC_PREFIX) {
+        Some(rest) => format!(r"\\{}", rest),
+        None => path
+            .strip_prefix(VERBATIM_PREFIX)
+            .unwrap_or(path)
+            .to_string(),
+    };
+    path.replace('\\', "/")
+}
+
+/// `path` as reported to CI tools: normalized like [`normalize_path`],
+/// relative to `root` when it lies under it, and without a leading `./`.
+///
+/// Paths outside `root` stay absolute. Windows drive paths compare without
+/// regard to case.
+///
+/// # Examples
+/// ```
+/// use mkdlint::helpers::relative_report_path;
+/// assert_eq!(relative_report_path(r"C:\repo\docs\a.md", "c:/repo"), "docs/a.md");
+/// assert_eq!(relative_report_path("/elsewhere/a.md", "/repo"), "/elsewhere/a.md");
+/// ```
+pub fn relative_report_path(path: &str, root: &str) -> String {
+    let path = normalize_path(path);
+    let root = normalize_path(root);
+    let root = root.trim_end_matches('/');
+
+    let relative = (!root.is_empty())
+        .then(|| path.get(..root.len()).zip(path.get(root.len()..)))
+        .flatten()
+        .filter(|(prefix, _)| {
+            *prefix == root || (has_drive_letter(root) && prefix.eq_ignore_ascii_case(root))
+        })
+        .and_then(|(_, rest)| rest.strip_prefix('/'))
+        .unwrap_or(&path);
+    relative.strip_prefix("./").unwrap_or(relative).to_string()
+}
+
+/// Whether `path` starts with a Windows drive such as `C:`
+fn has_drive_letter(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("docs/a.md"), "docs/a.md");
+        assert_eq!(normalize_path(r".\docs\a.md"), "./docs/a.md");
+        assert_eq!(normalize_path(r"\\?\D:\a.md"), "D:/a.md");
+        assert_eq!(
+            normalize_path(r"\\server\share\a.md"),
+            "//server/share/a.md"
+        );
+        assert_eq!(
+            normalize_path(r"\\?\UNC\server\share\a.md"),
+            "//server/share/a.md"
+        );
+    }
+
+    #[test]
+    fn test_relative_report_path() {
+        assert_eq!(
+            relative_report_path("/repo/docs/a.md", "/repo"),
+            "docs/a.md"
+        );
+        assert_eq!(
+            relative_report_path("/repo/docs/a.md", "/repo/"),
+            "docs/a.md"
+        );
+        assert_eq!(relative_report_path("./docs/a.md", "/repo"), "docs/a.md");
+        assert_eq!(
+            relative_report_path(r"\\?\C:\Repo\docs\a.md", r"c:\repo"),
+            "docs/a.md"
+        );
+        assert_eq!(
+            relative_report_path(r"\\?\UNC\srv\share\repo\a.md", r"\\srv\share\repo"),
+            "a.md"
+        );
+        // A sibling that merely shares the prefix is not under the root
+        assert_eq!(
+            relative_report_path("/repository/a.md", "/repo"),
+            "/repository/a.md"
+        );
+        // Case only folds for drive paths
+        assert_eq!(relative_report_path("/Repo/a.md", "/repo"), "/Repo/a.md");
+    }
+}
@@ -0,0 +1,102 @@
+//! Compile-once cache for user-supplied regular expressions
+
+use crate::types::{MarkdownlintError, Result};
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Longest user-supplied pattern accepted, in bytes
+pub const MAX_PATTERN_LEN: usize = 1024;
+
+/// Upper bound on the compiled size of a user-supplied pattern, in bytes
+const COMPILED_SIZE_LIMIT: usize = 1 << 20;
+
+/// Cache of compiled user-supplied regular expressions, keyed by pattern.
+///
+/// Config values such as the front matter pattern are compiled once per lint
+/// run and shared across files and threads. The `regex` engine already runs
+/// in linear time; the cache additionally rejects oversized patterns and
+/// reports invalid ones as configuration errors.
+#[derive(Debug, Default)]
+pub struct PatternCache {
+    patterns: Mutex<HashMap<String, Regex>>,
+    compilations: AtomicUsize,
+}
+
+impl PatternCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the compiled regex for `pattern`, compiling it on first use.
+    ///
+    /// Returns [`MarkdownlintError::InvalidConfig`] if the pattern is longer
+    /// than [`MAX_PATTERN_LEN`], too large once compiled, or not valid syntax.
+    pub fn get(&self, pattern: &str) -> Result<Regex> {
+        let mut patterns = self
+            .patterns
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(regex) = patterns.get(pattern) {
+            return Ok(regex.clone());
+        }
+
+        if pattern.len() > MAX_PATTERN_LEN {
+            return Err(MarkdownlintError::InvalidConfig(format!(
+                "pattern is {} bytes long (maximum {})",
+                pattern.len(),
+                MAX_PATTERN_LEN
+            )));
+        }
+        let regex = RegexBuilder::new(pattern)
+            .size_limit(COMPILED_SIZE_LIMIT)
+            .build()
+            .map_err(|e| {
+                MarkdownlintError::InvalidConfig(format!("invalid pattern '{}': {}", pattern, e))
+            })?;
+        self.compilations.fetch_add(1, Ordering::Relaxed);
+        patterns.insert(pattern.to_string(), regex.clone());
+        Ok(regex)
+    }
+
+    /// Number of patterns compiled so far (cache misses)
+    pub fn compilations(&self) -> usize {
+        self.compilations.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_compiled_once() {
+        let cache = PatternCache::new();
+        for _ in 0..3 {
+            assert!(cache.get("^---$").unwrap().is_match("---"));
+        }
+        assert_eq!(cache.compilations(), 1);
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_config_error() {
+        let err = PatternCache::new().get("[").unwrap_err();
+        assert!(matches!(err, MarkdownlintError::InvalidConfig(_)));
+        assert!(err.to_string().contains("invalid pattern '['"));
+    }
+
+    #[test]
+    fn test_pattern_too_long() {
+        let pattern = "a".repeat(MAX_PATTERN_LEN + 1);
+        let err = PatternCache::new().get(&pattern).unwrap_err();
+        assert!(matches!(err, MarkdownlintError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_compiled_size_limit() {
+        let err = PatternCache::new().get(r"\w{1000}\w{1000}").unwrap_err();
+        assert!(matches!(err, MarkdownlintError::InvalidConfig(_)));
+    }
+}
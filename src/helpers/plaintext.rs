@@ -0,0 +1,115 @@
+//! Prose text of a document with the Markdown syntax removed
+
+use crate::parser::Token;
+
+/// Inline containers whose text is part of the surrounding block's prose
+const INLINE_TYPES: &[&str] = &[
+    "emphasis",
+    "strong",
+    "strikethrough",
+    "superscript",
+    "link",
+    "image",
+];
+
+/// The prose of `content`, one entry per source line that has any, as
+/// `(line_number, text)` with 1-based line numbers.
+///
+/// Emphasis markers, link and image syntax, and escapes are removed (links
+/// keep their text, images their alt text); code spans, code blocks, math,
+/// HTML and autolinked URLs are left out entirely. Text from different
+/// blocks on one line, like table cells, is separated by a space. Front
+/// matter is not recognized, so strip it first.
+///
+/// Meant for feeding prose to a spell checker while keeping line numbers
+/// that point back into the file.
+///
+/// # Examples
+/// ```
+/// let text = mkdlint::helpers::extract_plaintext("# Title\n\nSee *the* [guide](docs/guide.md) `now`.\n");
+/// assert_eq!(
+///     text,
+///     [(1, "Title".to_string()), (3, "See the guide .".to_string())]
+/// );
+/// ```
+pub fn extract_plaintext(content: &str) -> Vec<(usize, String)> {
+    let tokens = crate::parser::parse(content);
+    // (line number, text, block the text so far came from)
+    let mut lines: Vec<(usize, String, Option<usize>)> = Vec::new();
+
+    for (idx, token) in tokens.iter().enumerate() {
+        if !token.is_type("text") || token.ancestors(&tokens).any(is_autolink) {
+            continue;
+        }
+        let block = enclosing_block(&tokens, idx);
+        match lines.last_mut() {
+            Some((line, text, last_block)) if *line == token.start_line => {
+                if *last_block != block {
+                    text.push(' ');
+                    *last_block = block;
+                }
+                text.push_str(&token.text);
+            }
+            _ => lines.push((token.start_line, token.text.clone(), block)),
+        }
+    }
+
+    lines
+        .into_iter()
+        .filter_map(|(line, text, _)| {
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!text.is_empty()).then_some((line, text))
+        })
+        .collect()
+}
+
+/// Whether `token` is a link written as a bare URL or `<url>`
+fn is_autolink(token: &Token) -> bool {
+    token.is_type("link")
+        && token.link_url().is_some_and(|url| {
+            url == token.text || url.strip_prefix("mailto:") == Some(token.text.as_str())
+        })
+}
+
+/// Index of the nearest ancestor of `tokens[idx]` that is not an inline
+/// container
+fn enclosing_block(tokens: &[Token], idx: usize) -> Option<usize> {
+    let mut parent = tokens[idx].parent;
+    while let Some(p) = parent
+        && tokens[p].is_any_type(INLINE_TYPES)
+    {
+        parent = tokens[p].parent;
+    }
+    parent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_plaintext_skips_code_and_keeps_line_numbers() {
+        let content = "Some **bold** and ~~old~~ text,\nwrapped `code` here.\n\n```rust\nlet x = 1;\n```\n\n- An ![alt text](a.png) item\n\n<div>html</div>\n\nVisit https://example.com or <me@example.com>.\n";
+        assert_eq!(
+            extract_plaintext(content),
+            [
+                (1, "Some bold and old text,".to_string()),
+                (2, "wrapped here.".to_string()),
+                (8, "An alt text item".to_string()),
+                (12, "Visit or .".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_plaintext_separates_table_cells() {
+        let content = "| Name | Notes |\n| --- | --- |\n| *one* | first\\|only |\n";
+        assert_eq!(
+            extract_plaintext(content),
+            [
+                (1, "Name Notes".to_string()),
+                (3, "one first|only".to_string()),
+            ]
+        );
+    }
+}
@@ -5,7 +5,7 @@
 //!
 //! ## Features
 //!
-//! - **64 built-in rules** enforcing Markdown best practices
+//! - **71 built-in rules** enforcing Markdown best practices
 //! - **Automatic fixing** for many rule violations
 //! - **Custom rules** support via the Rule trait
 //! - **Configuration** via JSON, YAML, or TOML files
@@ -70,6 +70,15 @@
 //! This line can be as long as you want.
 //! <!-- markdownlint-enable MD013 -->
 //! ```
+//!
+//! ## API Stability
+//!
+//! The items in [`prelude`] are the supported API and follow semantic
+//! versioning: they are only removed or changed incompatibly in a major
+//! release. The remaining public modules ([`helpers`], [`parser`],
+//! [`rules`], [`types`], ...) stay reachable for custom rule authors and
+//! tooling, but may change in minor releases. Items hidden from these docs
+//! are internal and carry no stability guarantee.
 
 #![warn(missing_docs)]
 #![warn(clippy::all)]
@@ -79,6 +88,7 @@ pub mod formatters;
 pub mod helpers;
 pub mod lint;
 pub mod parser;
+pub mod prelude;
 pub mod rules;
 pub mod types;
 
@@ -87,7 +97,9 @@ pub mod lsp;
 
 // Re-export main types and functions
 pub use config::{Config, ConfigParser, RuleConfig};
-pub use lint::{apply_fixes, build_workspace_headings, lint_sync};
+pub use lint::{
+    apply_fixes, build_workspace_headings, fix_content, lint_string, lint_sync, lint_sync_streaming,
+};
 pub use types::{LintError, LintOptions, LintResults, Rule, RuleParams};
 
 #[cfg(feature = "async")]
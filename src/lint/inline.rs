@@ -0,0 +1,574 @@
+//! Inline configuration directives (`<!-- markdownlint-disable ... -->`)
+//!
+//! Exposed so tools can ask which rules are disabled where without running
+//! a full lint:
+//!
+//! ```
+//! use mkdlint::lint::inline::InlineConfig;
+//!
+//! let content = "\
+//! Intro line
+//! <!-- markdownlint-disable MD013 -->
+//! A very long line that would normally be reported
+//! <!-- markdownlint-enable MD013 -->
+//! Another line
+//! ";
+//! let inline = InlineConfig::from_content(content);
+//! assert!(!inline.is_rule_disabled(1, "MD013"));
+//! assert!(inline.is_rule_disabled(3, "MD013"));
+//! assert!(inline.is_rule_disabled(3, "line-length"));
+//! assert!(!inline.is_rule_disabled(3, "MD009"));
+//! assert!(!inline.is_rule_disabled(5, "MD013"));
+//! ```
+
+use crate::config::{Config, ForeignDirective};
+use std::collections::{HashMap, HashSet};
+
+/// Parsed inline configuration state.
+///
+/// Uses a snapshot-based approach: instead of cloning rule ID strings into
+/// per-line HashSets (O(lines × rules) allocations), we store directive
+/// events and evaluate `is_disabled()` lazily by scanning events.
+///
+/// Supports the following HTML comment directives:
+/// - `<!-- markdownlint-disable MD001 MD002 -->` — disable specific rules
+/// - `<!-- markdownlint-disable -->` — disable all rules
+/// - `<!-- markdownlint-enable MD001 -->` — re-enable specific rules
+/// - `<!-- markdownlint-enable -->` — re-enable all rules
+/// - `<!-- markdownlint-disable-next-line MD001 -->` — disable for next line only
+/// - `<!-- markdownlint-disable-file MD001 -->` — disable for entire file
+/// - `<!-- markdownlint-enable-file MD001 -->` — re-enable for rest of file
+///
+/// Comments inside fenced or indented code blocks are example text, not
+/// directives, and are skipped.
+///
+/// Rule lists may be separated by spaces or commas and name rules by ID or
+/// alias in any case; built-in rule names are stored as canonical IDs.
+///
+/// Other tools' directives, such as `<!-- prettier-ignore -->`, are matched
+/// against the config's foreign directives (see
+/// [`Config::foreign_directive`]). Their lines are never reported, and
+/// `disable-next-line` directives skip over them.
+#[derive(Debug, Default)]
+pub struct InlineConfig {
+    /// Whether any directives were found (fast path for skipping filter).
+    has_directives: bool,
+    /// Sorted directive events (line_number, event). Always sorted by line_number.
+    events: Vec<(usize, DirectiveEvent)>,
+    /// Directive rule names matching no built-in rule, with their line number.
+    unknown_rules: Vec<(usize, String)>,
+    /// Lines holding another tool's directive, in ascending order.
+    foreign_lines: Vec<usize>,
+}
+
+/// A single inline directive event, stored once during parse.
+#[derive(Debug)]
+enum DirectiveEvent {
+    Disable(Vec<String>),
+    Enable(Vec<String>),
+    DisableNextLine(Vec<String>),
+    DisableFile(Vec<String>),
+    EnableFile(Vec<String>),
+}
+
+impl InlineConfig {
+    /// Parse inline directives from a whole document.
+    pub fn from_content(content: &str) -> Self {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        Self::from_lines(&lines)
+    }
+
+    /// Parse inline directives from document lines, recognizing only the
+    /// default foreign directives.
+    pub fn from_lines(lines: &[&str]) -> Self {
+        Self::from_lines_with_config(lines, &Config::default())
+    }
+
+    /// Parse inline directives from document lines, recognizing the
+    /// foreign directives configured in `config`.
+    pub fn from_lines_with_config(lines: &[&str], config: &Config) -> Self {
+        let mut has_directives = false;
+        let mut events = Vec::new();
+        let mut unknown_rules = Vec::new();
+        let mut foreign_lines = Vec::new();
+
+        // Directives shown as examples in code blocks are not directives
+        let code_lines = crate::helpers::code_block_lines(lines, &[]);
+
+        for (idx, line) in lines.iter().enumerate() {
+            if code_lines[idx] {
+                continue;
+            }
+            let line_number = idx + 1;
+            let trimmed = line.trim();
+
+            if let Some(directive) = Self::parse_directive(trimmed) {
+                has_directives = true;
+                let event = match directive {
+                    Directive::Disable(rules) => DirectiveEvent::Disable(rules),
+                    Directive::Enable(rules) => DirectiveEvent::Enable(rules),
+                    Directive::DisableNextLine(rules) => DirectiveEvent::DisableNextLine(rules),
+                    Directive::DisableFile(rules) => DirectiveEvent::DisableFile(rules),
+                    Directive::EnableFile(rules) => DirectiveEvent::EnableFile(rules),
+                };
+                unknown_rules.extend(
+                    event
+                        .rules()
+                        .iter()
+                        .filter(|r| crate::rules::find_rule(r).is_none())
+                        .map(|r| (line_number, r.clone())),
+                );
+                events.push((line_number, event));
+            } else if let Some(action) = Self::parse_foreign_directive(trimmed, config) {
+                has_directives = true;
+                foreign_lines.push(line_number);
+                let event = match action {
+                    ForeignDirective::Ignore => None,
+                    ForeignDirective::DisableNextLine => {
+                        Some(DirectiveEvent::DisableNextLine(Vec::new()))
+                    }
+                    ForeignDirective::Disable => Some(DirectiveEvent::Disable(Vec::new())),
+                    ForeignDirective::Enable => Some(DirectiveEvent::Enable(Vec::new())),
+                };
+                events.extend(event.map(|event| (line_number, event)));
+            }
+        }
+
+        InlineConfig {
+            has_directives,
+            events,
+            unknown_rules,
+            foreign_lines,
+        }
+    }
+
+    /// Whether the document contained any directives.
+    pub fn has_directives(&self) -> bool {
+        self.has_directives
+    }
+
+    /// Rule names in directives that match no built-in rule, as
+    /// `(line_number, NAME)` pairs in document order.
+    ///
+    /// Names are uppercased. Custom rule names are included, since they are
+    /// not known at parse time.
+    pub fn unknown_rules(&self) -> &[(usize, String)] {
+        &self.unknown_rules
+    }
+
+    /// Check if `rule` is disabled on the 1-based `line`.
+    ///
+    /// `rule` may be a rule ID or alias of a built-in rule (matched
+    /// case-insensitively); directives naming any of its names apply.
+    /// Unknown names are matched as given, e.g. for custom rules.
+    pub fn is_rule_disabled(&self, line: usize, rule: &str) -> bool {
+        match crate::rules::find_rule(rule) {
+            Some(r) => self.is_disabled(line, r.names()),
+            None => self.is_disabled(line, &[rule]),
+        }
+    }
+
+    /// Check if a rule, given all of its names, is disabled at a given line.
+    pub(crate) fn is_disabled(&self, line_number: usize, rule_names: &[&str]) -> bool {
+        self.disabled_by(line_number, rule_names).is_some()
+    }
+
+    /// The directive disabling a rule, given all of its names, at a given
+    /// line: its line number and the rule name it matched on (empty when it
+    /// disables all rules). A line holding another tool's directive is
+    /// disabled by itself.
+    ///
+    /// Replays directive events up to `line_number` to compute the disabled
+    /// state. This avoids the O(lines × rules) String cloning of the
+    /// previous per-line HashSet approach.
+    pub(crate) fn disabled_by(
+        &self,
+        line_number: usize,
+        rule_names: &[&str],
+    ) -> Option<(usize, &str)> {
+        // Directive rule lists hold canonical IDs (uppercased for custom rules)
+        if self.foreign_lines.binary_search(&line_number).is_ok() {
+            return Some((line_number, ""));
+        }
+        let rule_names: Vec<String> = rule_names.iter().map(|n| n.to_uppercase()).collect();
+        let state = self.state_before(line_number);
+        // File-level disables, then sticky disable/enable
+        for disabled in [&state.file, &state.active] {
+            let found = std::iter::once("")
+                .chain(rule_names.iter().map(String::as_str))
+                .find_map(|name| disabled.get_key_value(name));
+            if let Some((name, line)) = found {
+                return Some((*line, *name));
+            }
+        }
+
+        // Check disable-next-line: applies to the first non-directive line
+        // after the directive. We need to find if line_number is the target.
+        let (dnl_line, rules) = state.next_line?;
+        if self.find_next_non_directive_line(dnl_line) != Some(line_number) {
+            return None;
+        }
+        if rules.is_empty() {
+            return Some((dnl_line, ""));
+        }
+        rules
+            .iter()
+            .find(|rule| rule_names.contains(rule))
+            .map(|rule| (dnl_line, rule.as_str()))
+    }
+
+    /// Disabled rules after the events before `line_number`, each with the
+    /// line of the directive that disabled it ("" for all rules)
+    fn state_before(&self, line_number: usize) -> DisabledState<'_> {
+        let mut state = DisabledState::default();
+        for (event_line, event) in &self.events {
+            if *event_line >= line_number {
+                break;
+            }
+            state.apply(*event_line, event);
+        }
+        state
+    }
+
+    /// `enable` and `enable-file` directives that re-enable nothing, in
+    /// document order: the directive's line and the rule name that was not
+    /// disabled there (`None` for a directive naming no rules, when no rule
+    /// was disabled). Other tools' directives are not included.
+    pub fn unmatched_enables(&self) -> Vec<(usize, Option<String>)> {
+        let mut unmatched = Vec::new();
+        let mut state = DisabledState::default();
+        for (line, event) in &self.events {
+            let disabled = match event {
+                DirectiveEvent::Enable(_) => Some(&state.active),
+                DirectiveEvent::EnableFile(_) => Some(&state.file),
+                _ => None,
+            };
+            if let Some(disabled) = disabled
+                && self.foreign_lines.binary_search(line).is_err()
+            {
+                if event.rules().is_empty() {
+                    if disabled.is_empty() {
+                        unmatched.push((*line, None));
+                    }
+                } else {
+                    unmatched.extend(
+                        event
+                            .rules()
+                            .iter()
+                            .filter(|rule| !disabled.contains_key(rule.as_str()))
+                            .map(|rule| (*line, Some(rule.clone()))),
+                    );
+                }
+            }
+            state.apply(*line, event);
+        }
+        unmatched
+    }
+
+    /// `disable`, `disable-next-line` and `disable-file` directives, as
+    /// `(line_number, rule names)` in document order (no names = all
+    /// rules). Other tools' directives are not included.
+    pub fn disables(&self) -> impl Iterator<Item = (usize, &[String])> {
+        self.events
+            .iter()
+            .filter(|(line, _)| self.foreign_lines.binary_search(line).is_err())
+            .filter(|(_, event)| {
+                matches!(
+                    event,
+                    DirectiveEvent::Disable(_)
+                        | DirectiveEvent::DisableNextLine(_)
+                        | DirectiveEvent::DisableFile(_)
+                )
+            })
+            .map(|(line, event)| (*line, event.rules()))
+    }
+
+    /// Find the first non-directive line after `after_line`.
+    fn find_next_non_directive_line(&self, after_line: usize) -> Option<usize> {
+        // Collect all directive line numbers
+        let directive_lines: HashSet<usize> = self.events.iter().map(|(l, _)| *l).collect();
+        let mut line = after_line + 1;
+        // Skip consecutive directive lines, ours or other tools'
+        while directive_lines.contains(&line) || self.foreign_lines.binary_search(&line).is_ok() {
+            line += 1;
+        }
+        Some(line)
+    }
+
+    /// Parse a single directive from a trimmed line.
+    fn parse_directive(line: &str) -> Option<Directive> {
+        // Must be an HTML comment: <!-- markdownlint-xxx ... -->
+        let inner = line.strip_prefix("<!--")?.strip_suffix("-->")?.trim();
+
+        if let Some(rest) = inner.strip_prefix("markdownlint-disable-next-line") {
+            let rules = Self::parse_rule_list(rest);
+            Some(Directive::DisableNextLine(rules))
+        } else if let Some(rest) = inner.strip_prefix("markdownlint-disable-file") {
+            let rules = Self::parse_rule_list(rest);
+            Some(Directive::DisableFile(rules))
+        } else if let Some(rest) = inner.strip_prefix("markdownlint-enable-file") {
+            let rules = Self::parse_rule_list(rest);
+            Some(Directive::EnableFile(rules))
+        } else if let Some(rest) = inner.strip_prefix("markdownlint-disable") {
+            let rules = Self::parse_rule_list(rest);
+            Some(Directive::Disable(rules))
+        } else if let Some(rest) = inner.strip_prefix("markdownlint-enable") {
+            let rules = Self::parse_rule_list(rest);
+            Some(Directive::Enable(rules))
+        } else {
+            None
+        }
+    }
+
+    /// Match a trimmed line against the config's foreign directives.
+    fn parse_foreign_directive(line: &str, config: &Config) -> Option<ForeignDirective> {
+        let inner = line.strip_prefix("<!--")?.strip_suffix("-->")?.trim();
+        config.foreign_directive(inner)
+    }
+
+    /// Parse a space- or comma-separated list of rule names from directive
+    /// content, mapping built-in IDs and aliases to their canonical ID.
+    fn parse_rule_list(s: &str) -> Vec<String> {
+        Self::rule_list_names(s).map(Self::canonical_name).collect()
+    }
+
+    /// Rule names of a directive's rule list, as written
+    fn rule_list_names(s: &str) -> impl Iterator<Item = &str> {
+        s.split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|r| !r.is_empty())
+    }
+
+    /// A directive rule name as stored: the ID of a built-in rule, or the
+    /// name uppercased
+    fn canonical_name(name: &str) -> String {
+        match crate::rules::find_rule(name) {
+            Some(rule) => rule.names()[0].to_string(),
+            None => name.to_uppercase(),
+        }
+    }
+
+    /// The directive on `line` rewritten without the rules in `remove`
+    /// (stored names, as in [`disables`](Self::disables)), keeping its
+    /// indentation and separator style; `None` if `line` holds no directive.
+    /// The line ending is not included.
+    pub(crate) fn without_rules(line: &str, remove: &[&str]) -> Option<String> {
+        let text = line.trim_end_matches(['\n', '\r']);
+        let indent = &text[..text.len() - text.trim_start().len()];
+        let inner = text
+            .trim()
+            .strip_prefix("<!--")?
+            .strip_suffix("-->")?
+            .trim();
+        let (keyword, rules) = inner.split_once(char::is_whitespace)?;
+        let separator = if rules.contains(',') { ", " } else { " " };
+        let kept: Vec<&str> = Self::rule_list_names(rules)
+            .filter(|name| !remove.contains(&Self::canonical_name(name).as_str()))
+            .collect();
+        Some(format!(
+            "{}<!-- {} {} -->",
+            indent,
+            keyword,
+            kept.join(separator)
+        ))
+    }
+}
+
+/// Rules disabled at some point of a document, each with the line of the
+/// directive that disabled it; the empty name stands for all rules
+#[derive(Default)]
+struct DisabledState<'a> {
+    active: HashMap<&'a str, usize>,
+    file: HashMap<&'a str, usize>,
+    /// The last `disable-next-line` directive and its rules
+    next_line: Option<(usize, &'a [String])>,
+}
+
+impl<'a> DisabledState<'a> {
+    fn apply(&mut self, line: usize, event: &'a DirectiveEvent) {
+        match event {
+            DirectiveEvent::Disable(rules) => disable(&mut self.active, line, rules),
+            DirectiveEvent::Enable(rules) => enable(&mut self.active, rules),
+            DirectiveEvent::DisableNextLine(rules) => self.next_line = Some((line, rules)),
+            DirectiveEvent::DisableFile(rules) => disable(&mut self.file, line, rules),
+            DirectiveEvent::EnableFile(rules) => enable(&mut self.file, rules),
+        }
+    }
+}
+
+fn disable<'a>(disabled: &mut HashMap<&'a str, usize>, line: usize, rules: &'a [String]) {
+    if rules.is_empty() {
+        disabled.insert("", line);
+    }
+    for rule in rules {
+        disabled.insert(rule, line);
+    }
+}
+
+fn enable(disabled: &mut HashMap<&str, usize>, rules: &[String]) {
+    if rules.is_empty() {
+        disabled.clear();
+    }
+    for rule in rules {
+        disabled.remove(rule.as_str());
+    }
+}
+
+impl DirectiveEvent {
+    /// Rule names the directive applies to (empty = all rules)
+    fn rules(&self) -> &[String] {
+        match self {
+            DirectiveEvent::Disable(rules)
+            | DirectiveEvent::Enable(rules)
+            | DirectiveEvent::DisableNextLine(rules)
+            | DirectiveEvent::DisableFile(rules)
+            | DirectiveEvent::EnableFile(rules) => rules,
+        }
+    }
+}
+
+enum Directive {
+    Disable(Vec<String>),
+    Enable(Vec<String>),
+    DisableNextLine(Vec<String>),
+    DisableFile(Vec<String>),
+    EnableFile(Vec<String>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_directives() {
+        let inline = InlineConfig::from_content("# Title\n\nText\n");
+        assert!(!inline.has_directives());
+        assert!(!inline.is_rule_disabled(3, "MD013"));
+    }
+
+    #[test]
+    fn test_disable_next_line() {
+        let inline =
+            InlineConfig::from_content("<!-- markdownlint-disable-next-line MD009 -->\na  \nb  \n");
+        assert!(inline.is_rule_disabled(2, "MD009"));
+        assert!(!inline.is_rule_disabled(3, "MD009"));
+    }
+
+    #[test]
+    fn test_alias_in_directive() {
+        let inline =
+            InlineConfig::from_content("<!-- markdownlint-disable no-trailing-spaces -->\na  \n");
+        assert!(inline.is_rule_disabled(2, "MD009"));
+        assert!(inline.is_rule_disabled(2, "no-trailing-spaces"));
+    }
+
+    #[test]
+    fn test_custom_rule_name() {
+        let inline =
+            InlineConfig::from_content("<!-- markdownlint-disable-file custom001 -->\ntext\n");
+        assert!(inline.is_rule_disabled(2, "CUSTOM001"));
+        assert!(!inline.is_rule_disabled(2, "MD013"));
+    }
+
+    #[test]
+    fn test_alias_enable_matches_id_disable() {
+        let inline = InlineConfig::from_content(
+            "<!-- markdownlint-disable MD009 -->\na  \n<!-- markdownlint-enable No-Trailing-Spaces -->\nb  \n",
+        );
+        assert!(inline.is_rule_disabled(2, "MD009"));
+        assert!(!inline.is_rule_disabled(4, "MD009"));
+    }
+
+    #[test]
+    fn test_comma_separated_rule_list() {
+        let inline = InlineConfig::from_content(
+            "<!-- markdownlint-disable md009,MD010, line-length -->\ntext\n",
+        );
+        assert!(inline.is_rule_disabled(2, "MD009"));
+        assert!(inline.is_rule_disabled(2, "MD010"));
+        assert!(inline.is_rule_disabled(2, "MD013"));
+        assert!(!inline.is_rule_disabled(2, "MD012"));
+        assert!(inline.unknown_rules().is_empty());
+    }
+
+    #[test]
+    fn test_without_rules() {
+        assert_eq!(
+            InlineConfig::without_rules(
+                "  <!-- markdownlint-disable md009 line-length MD033 -->\n",
+                &["MD009", "MD013"]
+            )
+            .as_deref(),
+            Some("  <!-- markdownlint-disable MD033 -->")
+        );
+        assert_eq!(
+            InlineConfig::without_rules(
+                "<!-- markdownlint-disable-next-line MD009, MD010 -->",
+                &["MD010"]
+            )
+            .as_deref(),
+            Some("<!-- markdownlint-disable-next-line MD009 -->")
+        );
+        assert_eq!(InlineConfig::without_rules("text", &["MD009"]), None);
+    }
+
+    #[test]
+    fn test_unknown_rule_names() {
+        let inline = InlineConfig::from_content(
+            "# Title\n<!-- markdownlint-disable MD009 no-such-rule -->\ntext\n",
+        );
+        assert_eq!(inline.unknown_rules(), [(2, "NO-SUCH-RULE".to_string())]);
+        assert!(inline.is_rule_disabled(3, "MD009"));
+    }
+
+    #[test]
+    fn test_directives_in_code_blocks_are_ignored() {
+        let inline = InlineConfig::from_content(
+            "# Usage\n\n```markdown\n<!-- markdownlint-disable MD013 -->\n```\n\n    <!-- markdownlint-disable-file -->\n\nlong line\n",
+        );
+        assert!(!inline.has_directives());
+        assert!(!inline.is_rule_disabled(9, "MD013"));
+    }
+
+    #[test]
+    fn test_foreign_directive_line_is_ignored() {
+        let inline = InlineConfig::from_content("<!-- prettier-ignore -->\n| a |\n");
+        assert!(inline.has_directives());
+        assert!(inline.is_rule_disabled(1, "MD041"));
+        assert!(inline.is_rule_disabled(1, "MD033"));
+        assert!(!inline.is_rule_disabled(2, "MD058"));
+        assert!(inline.unknown_rules().is_empty());
+    }
+
+    #[test]
+    fn test_disable_next_line_skips_foreign_directive() {
+        let inline = InlineConfig::from_content(
+            "<!-- markdownlint-disable-next-line MD009 -->\n<!-- eslint-disable -->\na  \nb  \n",
+        );
+        assert!(inline.is_rule_disabled(3, "MD009"));
+        assert!(!inline.is_rule_disabled(4, "MD009"));
+    }
+
+    #[test]
+    fn test_configured_foreign_directives() {
+        let config: Config = serde_json::from_str(
+            r#"{"foreign_directives": {"prettier-ignore": "disable-next-line", "vale off": "disable", "vale on": "enable", "my-tool": "ignore"}}"#,
+        )
+        .unwrap();
+        let lines = [
+            "<!-- prettier-ignore -->\n",
+            "a  \n",
+            "b  \n",
+            "<!-- vale off -->\n",
+            "c  \n",
+            "<!-- vale on -->\n",
+            "d  \n",
+            "<!-- my-tool skip -->\n",
+        ];
+        let inline = InlineConfig::from_lines_with_config(&lines, &config);
+        assert!(inline.is_rule_disabled(2, "MD009"));
+        assert!(!inline.is_rule_disabled(3, "MD009"));
+        assert!(inline.is_rule_disabled(5, "MD009"));
+        assert!(!inline.is_rule_disabled(7, "MD009"));
+        assert!(inline.is_rule_disabled(8, "MD009"));
+    }
+}
@@ -1,16 +1,36 @@
 //! Core linting functionality
 
+pub mod inline;
+
 use crate::config::Config;
+use crate::helpers::PatternCache;
 use crate::parser;
 use crate::types::{
-    BoxedRule, LintError, LintOptions, LintResults, MarkdownlintError, ParserType, Result,
+    BoxedRule, FixOutcome, LintError, LintOptions, LintResults, MarkdownlintError, ParserType,
+    Result,
 };
+use inline::InlineConfig;
 use rayon::prelude::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 /// Default maximum number of fix passes for convergence
 pub const DEFAULT_FIX_PASSES: usize = 10;
 
+/// Front matter pattern that selects the built-in delimiters
+const AUTO_FRONT_MATTER: &str = "auto";
+
+/// (opening, closing) delimiters recognized by `"auto"`: YAML, TOML, and JSON
+const AUTO_FRONT_MATTER_DELIMITERS: [(&str, &str); 3] = [
+    (r"^---\s*$", r"^---\s*$"),
+    (r"^\+\+\+\s*$", r"^\+\+\+\s*$"),
+    (r"^\{\s*$", r"^\}\s*$"),
+];
+
 /// Pre-computed rule state for a given configuration.
 ///
 /// Built once per lint invocation and shared across all files,
@@ -18,32 +38,93 @@ pub const DEFAULT_FIX_PASSES: usize = 10;
 struct PreparedRules<'a> {
     enabled: Vec<&'a dyn crate::types::Rule>,
     needs_parser: bool,
-    front_matter_pattern: Option<String>,
+    /// Front matter (opening, closing) delimiters, tried in order
+    front_matter: Vec<(regex::Regex, regex::Regex)>,
+    /// User-supplied patterns, compiled once for the whole run
+    patterns: PatternCache,
+    /// Per-rule time budget (`LintOptions::rule_timeout_ms`)
+    rule_timeout: Option<Duration>,
+    /// Rules (by index into `enabled`) that exceeded the budget this run
+    timed_out: Vec<AtomicBool>,
+    /// Fill in `LintError::line_text` (`LintOptions::capture_line_text`)
+    capture_line_text: bool,
+    /// Per-rule `files` and `include` globs (by index into `enabled`); None
+    /// runs everywhere
+    file_globs: Vec<Option<globset::GlobSet>>,
+    /// Per-rule `exclude` globs (by index into `enabled`)
+    exclude_globs: Vec<Option<globset::GlobSet>>,
+    /// Line ranges to report, by input name (`LintOptions::line_ranges`)
+    line_ranges: HashMap<String, Vec<Range<usize>>>,
+    /// Fenced code block languages to lint exclusively
+    /// (`LintOptions::code_block_languages`)
+    code_block_languages: Vec<String>,
+    /// Paths rules see instead of the input name (`LintOptions::virtual_paths`)
+    virtual_paths: HashMap<String, String>,
+    /// Uppercased names of all custom rules, enabled or not, so directives
+    /// naming them are not reported as unknown
+    custom_names: HashSet<String>,
+    /// Report disable directives that suppressed nothing, with fixes
+    /// (`LintOptions::report_unused_disables`)
+    report_unused_disables: bool,
+}
+
+impl PreparedRules<'_> {
+    /// Restrict reported errors to `line_ranges`, if given
+    fn with_line_ranges(
+        mut self,
+        line_ranges: Option<&HashMap<String, Vec<Range<usize>>>>,
+    ) -> Self {
+        self.line_ranges = line_ranges.cloned().unwrap_or_default();
+        self
+    }
+
+    /// Lint only fenced code blocks in `languages`, if any are given
+    fn with_code_block_languages(mut self, languages: &[String]) -> Self {
+        self.code_block_languages = languages.to_vec();
+        self
+    }
+
+    /// Show rules string inputs under their virtual paths
+    fn with_virtual_paths(mut self, virtual_paths: &HashMap<String, PathBuf>) -> Self {
+        self.virtual_paths = virtual_paths
+            .iter()
+            .map(|(name, path)| (name.clone(), path.to_string_lossy().into_owned()))
+            .collect();
+        self
+    }
+
+    /// Report unused disable directives as fixable MKD006 errors
+    fn with_unused_disables(mut self, report: bool) -> Self {
+        self.report_unused_disables = report;
+        self
+    }
+
+    /// IDs of the enabled rules, for [`LintResults::rules_run`]
+    fn rule_ids(&self) -> Vec<String> {
+        self.enabled
+            .iter()
+            .map(|rule| rule.names()[0].to_string())
+            .collect()
+    }
 }
 
 /// Build the enabled-rules list and parser flag from the config.
 ///
 /// Accepts both static rules (from the global registry) and custom rules.
 /// The lifetime `'a` is tied to custom_rules when present, otherwise it's `'static`.
+/// Fails if a user-supplied pattern (e.g. `front_matter`, or a regex in an
+/// enabled rule's options) is invalid.
 fn prepare_rules<'a>(
     config: &Config,
     custom_rules: &'a [BoxedRule],
-    front_matter_pattern: Option<String>,
-) -> PreparedRules<'a> {
+    front_matter_patterns: &[&str],
+    rule_timeout_ms: Option<u64>,
+    capture_line_text: bool,
+) -> Result<PreparedRules<'a>> {
     use crate::rules;
     use crate::types::Rule;
 
-    // Helper to check if a rule is enabled based on config
-    let rule_is_enabled = |rule: &dyn Rule| {
-        let explicitly_configured = config.get_rule_config(rule.names()[0]).is_some();
-        if explicitly_configured {
-            config.is_rule_enabled(rule.names()[0])
-        } else {
-            config
-                .default
-                .unwrap_or_else(|| rule.is_enabled_by_default())
-        }
-    };
+    let rule_is_enabled = |rule: &dyn Rule| config.rule_enabled(rule);
 
     // Combine static rules (coerced to 'a) and custom rules
     let static_enabled: Vec<&'a dyn Rule> = rules::get_rules()
@@ -64,11 +145,115 @@ fn prepare_rules<'a>(
         .iter()
         .any(|rule| rule.parser_type() == ParserType::Micromark);
 
-    PreparedRules {
+    let patterns = PatternCache::new();
+    let mut front_matter = Vec::new();
+    for &pattern in front_matter_patterns {
+        if pattern == AUTO_FRONT_MATTER {
+            for (open, close) in AUTO_FRONT_MATTER_DELIMITERS {
+                front_matter.push((patterns.get(open)?, patterns.get(close)?));
+            }
+        } else {
+            let regex = patterns.get(pattern)?;
+            front_matter.push((regex.clone(), regex));
+        }
+    }
+
+    // Rules compile their regex options from the shared cache; an invalid
+    // one fails the run here rather than being ignored by the rule
+    for rule in &enabled {
+        let id = rule.names()[0];
+        let Some(crate::config::RuleConfig::Options(opts)) = config.get_rule_config(id) else {
+            continue;
+        };
+        for (option, pattern) in crate::config::option_regexes(opts) {
+            patterns.get(&pattern).map_err(|e| match e {
+                MarkdownlintError::InvalidConfig(detail) => {
+                    MarkdownlintError::InvalidConfig(format!("{} `{}`: {}", id, option, detail))
+                }
+                e => e,
+            })?;
+        }
+    }
+
+    let timed_out = enabled.iter().map(|_| AtomicBool::new(false)).collect();
+    let file_globs = enabled
+        .iter()
+        .map(|rule| rule_file_globs(config, rule.names()[0], &["files", "include"]))
+        .collect::<Result<_>>()?;
+    let exclude_globs = enabled
+        .iter()
+        .map(|rule| rule_file_globs(config, rule.names()[0], &["exclude"]))
+        .collect::<Result<_>>()?;
+
+    Ok(PreparedRules {
         enabled,
         needs_parser,
-        front_matter_pattern,
+        front_matter,
+        patterns,
+        rule_timeout: rule_timeout_ms.map(Duration::from_millis),
+        timed_out,
+        capture_line_text,
+        file_globs,
+        exclude_globs,
+        line_ranges: HashMap::new(),
+        code_block_languages: Vec::new(),
+        virtual_paths: HashMap::new(),
+        custom_names: custom_rules
+            .iter()
+            .flat_map(|rule| rule.names())
+            .map(|name| name.to_uppercase())
+            .collect(),
+        report_unused_disables: false,
+    })
+}
+
+/// Compile the glob lists under `keys` in a rule's options into one set,
+/// or None when the rule has none of them
+fn rule_file_globs(
+    config: &Config,
+    rule_name: &str,
+    keys: &[&str],
+) -> Result<Option<globset::GlobSet>> {
+    let Some(crate::config::RuleConfig::Options(opts)) = config.get_rule_config(rule_name) else {
+        return Ok(None);
+    };
+    let mut builder = globset::GlobSetBuilder::new();
+    let mut found = false;
+    for key in keys {
+        let Some(patterns) = opts.get(*key) else {
+            continue;
+        };
+        found = true;
+        let invalid = |detail: String| {
+            MarkdownlintError::InvalidConfig(format!("{} `{}`: {}", rule_name, key, detail))
+        };
+        let patterns = patterns
+            .as_array()
+            .ok_or_else(|| invalid("expected an array of glob patterns".to_string()))?;
+        for pattern in patterns {
+            let pattern = pattern
+                .as_str()
+                .ok_or_else(|| invalid("expected an array of glob patterns".to_string()))?;
+            builder.add(globset::Glob::new(pattern).map_err(|e| invalid(e.to_string()))?);
+        }
     }
+    if !found {
+        return Ok(None);
+    }
+    builder.build().map(Some).map_err(|e| {
+        MarkdownlintError::InvalidConfig(format!("{} `{}`: {}", rule_name, keys.join("`/`"), e))
+    })
+}
+
+/// Whether a `files` glob set selects this file, matching either the path
+/// (without a leading `./`) or the bare file name
+pub(crate) fn file_globs_match(globs: &globset::GlobSet, name: &str) -> bool {
+    let path = name.strip_prefix("./").unwrap_or(name);
+    let file_name = std::path::Path::new(path)
+        .file_name()
+        .and_then(|f| f.to_str())
+        .unwrap_or(path);
+    globs.is_match(path) || globs.is_match(file_name)
 }
 
 /// Build a workspace heading index from input files.
@@ -88,6 +273,68 @@ pub fn build_workspace_headings(inputs: &[(String, String)]) -> HashMap<String,
     index
 }
 
+/// Bytes at the start of a file searched for a NUL by [`looks_binary`]
+const BINARY_SNIFF_LEN: usize = 8192;
+
+/// Whether file content is binary rather than text: it has a NUL byte in
+/// its first [`BINARY_SNIFF_LEN`] bytes. The extension plays no part, so
+/// Markdown kept in a `.txt` file is still linted.
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_LEN)].contains(&0)
+}
+
+/// One file's content, or `None` if it looks binary
+fn read_file(file_path: &str, bytes: std::io::Result<Vec<u8>>) -> Result<Option<String>> {
+    let not_found = || MarkdownlintError::FileNotFound(file_path.to_string());
+    let bytes = bytes.map_err(|_| not_found())?;
+    if looks_binary(&bytes) {
+        return Ok(None);
+    }
+    String::from_utf8(bytes).map(Some).map_err(|_| not_found())
+}
+
+/// A file or string name with its content
+type Input = (String, String);
+
+/// Read all file and string inputs (files sequentially, for proper error
+/// reporting), returning them with the files skipped as binary.
+fn read_inputs(options: &LintOptions) -> Result<(Vec<Input>, Vec<String>)> {
+    let mut inputs: Vec<Input> = Vec::new();
+    let mut binary = Vec::new();
+    for file_path in &options.files {
+        match read_file(file_path, std::fs::read(file_path))? {
+            Some(content) => inputs.push((file_path.clone(), content)),
+            None => binary.push(file_path.clone()),
+        }
+    }
+    for (name, content) in &options.strings {
+        inputs.push((name.clone(), content.clone()));
+    }
+    Ok((inputs, binary))
+}
+
+/// Build (or reuse) the workspace heading index for cross-file MD051 validation.
+///
+/// Uses the cached version if provided (avoids rebuilds in multi-pass fix loops).
+fn workspace_headings_for(
+    options: &LintOptions,
+    inputs: &[(String, String)],
+    prepared: &PreparedRules<'_>,
+) -> Option<HashMap<String, Vec<String>>> {
+    if let Some(ref cached) = options.cached_workspace_headings {
+        Some(cached.clone())
+    } else if inputs.len() > 1 && prepared.enabled.iter().any(|r| r.names()[0] == "MD051") {
+        // Keyed by the paths rules see, so links resolve between virtual paths
+        let inputs: Vec<(String, String)> = inputs
+            .iter()
+            .map(|(name, content)| (options.rule_path(name), content.clone()))
+            .collect();
+        Some(build_workspace_headings(&inputs))
+    } else {
+        None
+    }
+}
+
 /// Lint markdown content synchronously
 ///
 /// Files are read sequentially (for proper error reporting) then linted
@@ -99,34 +346,41 @@ pub fn lint_sync(options: &LintOptions) -> Result<LintResults> {
     let config = load_config(options)?;
 
     // Read all files first (sequential for proper error reporting)
-    let mut inputs: Vec<(String, String)> = Vec::new();
-    for file_path in &options.files {
-        let content = std::fs::read_to_string(file_path)
-            .map_err(|_| MarkdownlintError::FileNotFound(file_path.clone()))?;
-        inputs.push((file_path.clone(), content));
-    }
-    for (name, content) in &options.strings {
-        inputs.push((name.clone(), content.clone()));
+    let (inputs, binary) = read_inputs(options)?;
+    for name in binary {
+        results.add_skipped_binary(name);
     }
 
     // Precompute enabled rules once (avoids per-file HashMap lookups)
-    let prepared = prepare_rules(&config, &options.custom_rules, options.front_matter.clone());
+    let prepared = prepare_rules(
+        &config,
+        &options.custom_rules,
+        &options.front_matter_pattern_list(),
+        options.rule_timeout_ms,
+        options.capture_line_text,
+    )?
+    .with_line_ranges(options.line_ranges.as_ref())
+    .with_code_block_languages(&options.code_block_languages)
+    .with_virtual_paths(&options.virtual_paths)
+    .with_unused_disables(options.report_unused_disables);
+
+    results.rules_run = prepared.rule_ids();
+
+    // Nothing to run: skip splitting, parsing and the thread pool. Inputs
+    // were still read, so missing files fail and generated ones are recorded
+    // as usual.
+    if prepared.enabled.is_empty() {
+        for (name, content) in inputs {
+            let errors = (!is_generated(&config, &content)).then(Vec::new);
+            record(&mut results, name, errors);
+        }
+        return Ok(results);
+    }
 
-    // Build workspace heading index for cross-file MD051 validation.
-    // Use cached version if provided (avoids rebuilds in multi-pass fix loops).
-    let workspace_headings = if let Some(ref cached) = options.cached_workspace_headings {
-        Some(cached.clone())
-    } else if inputs.len() > 1 && prepared.enabled.iter().any(|r| r.names()[0] == "MD051") {
-        Some(build_workspace_headings(&inputs))
-    } else {
-        None
-    };
+    let workspace_headings = workspace_headings_for(options, &inputs, &prepared);
 
     // Lint all inputs in parallel
-    let file_results: Vec<(
-        String,
-        std::result::Result<Vec<LintError>, MarkdownlintError>,
-    )> = inputs
+    let file_results: Vec<_> = inputs
         .par_iter()
         .map(|(name, content)| {
             let errors = lint_content(
@@ -141,12 +395,153 @@ pub fn lint_sync(options: &LintOptions) -> Result<LintResults> {
         .collect();
 
     for (name, result) in file_results {
-        results.add(name, result?);
+        record(&mut results, name, result?);
     }
 
     Ok(results)
 }
 
+/// Name under which [`lint_string`] and [`fix_content`] lint their input
+const STRING_INPUT_NAME: &str = "<string>";
+
+/// Lint a single in-memory document.
+///
+/// A shorthand for [`lint_sync`] with one string input; the errors for that
+/// document are returned directly.
+///
+/// # Examples
+/// ```
+/// let errors = mkdlint::lint_string("#Title\n", &mkdlint::Config::default())?;
+/// assert!(errors.iter().any(|e| e.rule_names[0] == "MD018"));
+/// # Ok::<(), mkdlint::types::MarkdownlintError>(())
+/// ```
+pub fn lint_string(content: &str, config: &Config) -> Result<Vec<LintError>> {
+    let options = LintOptions::new()
+        .with_string(STRING_INPUT_NAME, content)
+        .with_config(config.clone());
+    let mut results = lint_sync(&options)?;
+    Ok(results
+        .results
+        .remove(STRING_INPUT_NAME)
+        .unwrap_or_default())
+}
+
+/// Lint and fix a single in-memory document.
+///
+/// Fixes are applied repeatedly, up to [`DEFAULT_FIX_PASSES`] times, until
+/// the content stops changing, since one fix can expose another.
+///
+/// # Examples
+/// ```
+/// let fixed = mkdlint::fix_content("# Title  \n", &mkdlint::Config::default())?;
+/// assert_eq!(fixed, "# Title\n");
+/// # Ok::<(), mkdlint::types::MarkdownlintError>(())
+/// ```
+pub fn fix_content(content: &str, config: &Config) -> Result<String> {
+    converge_fixes(content, |current| lint_string(current, config)).map(|outcome| outcome.content)
+}
+
+/// Fix a document until it stops changing, recording what each pass fixed.
+///
+/// `lint` produces the errors for the current content on each pass; at most
+/// [`DEFAULT_FIX_PASSES`] passes run. [`fix_content`] is this with
+/// [`lint_string`].
+///
+/// # Examples
+/// ```
+/// let config = mkdlint::Config::default();
+/// let outcome = mkdlint::lint::converge_fixes("# Title  \n", |content| {
+///     mkdlint::lint_string(content, &config)
+/// })?;
+/// assert_eq!(outcome.content, "# Title\n");
+/// assert_eq!(outcome.fixes_by_rule().get("MD009"), Some(&1));
+/// # Ok::<(), mkdlint::types::MarkdownlintError>(())
+/// ```
+pub fn converge_fixes<F>(content: &str, mut lint: F) -> Result<FixOutcome>
+where
+    F: FnMut(&str) -> Result<Vec<LintError>>,
+{
+    let mut outcome = FixOutcome {
+        content: content.to_string(),
+        passes: Vec::new(),
+    };
+    for _ in 0..DEFAULT_FIX_PASSES {
+        let errors = lint(&outcome.content)?;
+        let (next, counts) = apply_fixes_counted(&outcome.content, &errors);
+        if next == outcome.content {
+            break;
+        }
+        outcome.content = next;
+        outcome.passes.push(counts);
+    }
+    Ok(outcome)
+}
+
+/// Lint markdown content synchronously, reporting each file as soon as it is done.
+///
+/// Behaves like [`lint_sync`], but `on_file` is invoked on the calling thread
+/// with each file's errors as soon as that file finishes linting, so callers
+/// can stream output instead of waiting for the whole run. Files complete in
+/// no particular order. The full results are still returned at the end.
+pub fn lint_sync_streaming<F>(options: &LintOptions, mut on_file: F) -> Result<LintResults>
+where
+    F: FnMut(&str, &[LintError]),
+{
+    let mut results = LintResults::new();
+
+    let config = load_config(options)?;
+    let (inputs, binary) = read_inputs(options)?;
+    for name in binary {
+        on_file(&name, &[]);
+        results.add_skipped_binary(name);
+    }
+    let prepared = prepare_rules(
+        &config,
+        &options.custom_rules,
+        &options.front_matter_pattern_list(),
+        options.rule_timeout_ms,
+        options.capture_line_text,
+    )?
+    .with_line_ranges(options.line_ranges.as_ref())
+    .with_code_block_languages(&options.code_block_languages)
+    .with_virtual_paths(&options.virtual_paths)
+    .with_unused_disables(options.report_unused_disables);
+    let workspace_headings = workspace_headings_for(options, &inputs, &prepared);
+    results.rules_run = prepared.rule_ids();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut first_error = None;
+
+    std::thread::scope(|scope| {
+        let (config, prepared, inputs) = (&config, &prepared, &inputs);
+        let workspace_headings = workspace_headings.as_ref();
+        scope.spawn(move || {
+            inputs.par_iter().for_each_with(tx, |tx, (name, content)| {
+                let errors = lint_content(content, config, name, prepared, workspace_headings);
+                // The receiver only goes away once every sender is dropped
+                let _ = tx.send((name.clone(), errors));
+            });
+        });
+
+        for (name, result) in rx {
+            match result {
+                Ok(errors) => {
+                    on_file(&name, errors.as_deref().unwrap_or_default());
+                    record(&mut results, name, errors);
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+    });
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(results),
+    }
+}
+
 /// Lint markdown content asynchronously
 ///
 /// Files are read concurrently with tokio, then linted in parallel
@@ -168,9 +563,7 @@ pub async fn lint_async(options: &LintOptions) -> Result<LintResults> {
         .map(|file_path| {
             let path = file_path.clone();
             tokio::spawn(async move {
-                let content = fs::read_to_string(&path)
-                    .await
-                    .map_err(|_| MarkdownlintError::FileNotFound(path.clone()));
+                let content = read_file(&path, fs::read(&path).await);
                 (path, content)
             })
         })
@@ -181,7 +574,10 @@ pub async fn lint_async(options: &LintOptions) -> Result<LintResults> {
         let (path, content_result) = handle
             .await
             .map_err(|e| MarkdownlintError::AsyncRuntime(format!("Task join error: {}", e)))?;
-        inputs.push((path, content_result?));
+        match content_result? {
+            Some(content) => inputs.push((path, content)),
+            None => results.add_skipped_binary(path),
+        }
     }
 
     // Add string inputs
@@ -192,7 +588,20 @@ pub async fn lint_async(options: &LintOptions) -> Result<LintResults> {
     // Handle custom rules: they require sequential processing due to lifetime constraints
     if options.custom_rules.is_empty() {
         // Fast path: static rules only, can use spawn_blocking in parallel
-        let prepared = Arc::new(prepare_rules(&config, &[], options.front_matter.clone()));
+        let prepared = Arc::new(
+            prepare_rules(
+                &config,
+                &[],
+                &options.front_matter_pattern_list(),
+                options.rule_timeout_ms,
+                options.capture_line_text,
+            )?
+            .with_line_ranges(options.line_ranges.as_ref())
+            .with_code_block_languages(&options.code_block_languages)
+            .with_virtual_paths(&options.virtual_paths)
+            .with_unused_disables(options.report_unused_disables),
+        );
+        results.rules_run = prepared.rule_ids();
 
         // Lint all inputs concurrently using spawn_blocking (CPU-bound)
         let lint_handles: Vec<_> = inputs
@@ -211,20 +620,64 @@ pub async fn lint_async(options: &LintOptions) -> Result<LintResults> {
             let (name, error_result) = handle
                 .await
                 .map_err(|e| MarkdownlintError::AsyncRuntime(format!("Task join error: {}", e)))?;
-            results.add(name, error_result?);
+            record(&mut results, name, error_result?);
         }
     } else {
         // Sequential path for custom rules (non-'static lifetime)
-        let prepared = prepare_rules(&config, &options.custom_rules, options.front_matter.clone());
+        let prepared = prepare_rules(
+            &config,
+            &options.custom_rules,
+            &options.front_matter_pattern_list(),
+            options.rule_timeout_ms,
+            options.capture_line_text,
+        )?
+        .with_line_ranges(options.line_ranges.as_ref())
+        .with_code_block_languages(&options.code_block_languages)
+        .with_virtual_paths(&options.virtual_paths)
+        .with_unused_disables(options.report_unused_disables);
+        results.rules_run = prepared.rule_ids();
         for (name, content) in &inputs {
             let errors = lint_content(content, &config, name, &prepared, None)?;
-            results.add(name.clone(), errors);
+            record(&mut results, name.clone(), errors);
         }
     }
 
     Ok(results)
 }
 
+/// The error reported in place of a rule's results when the rule panics
+fn internal_error(
+    rule: &dyn crate::types::Rule,
+    payload: &(dyn std::any::Any + Send),
+) -> LintError {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic");
+    LintError {
+        line_number: 1,
+        rule_names: rule.names(),
+        rule_description: rule.description(),
+        error_detail: Some(format!(
+            "{}{} (please report): {}",
+            crate::types::INTERNAL_ERROR_PREFIX,
+            rule.names()[0],
+            message
+        )),
+        severity: crate::types::Severity::Error,
+        ..Default::default()
+    }
+}
+
+/// Add one file's [`lint_content`] outcome to `results`
+fn record(results: &mut LintResults, name: String, errors: Option<Vec<LintError>>) {
+    match errors {
+        Some(errors) => results.add(name, errors),
+        None => results.add_skipped_generated(name),
+    }
+}
+
 /// Load configuration from options
 fn load_config(options: &LintOptions) -> Result<Config> {
     let config = if let Some(config) = &options.config {
@@ -232,90 +685,169 @@ fn load_config(options: &LintOptions) -> Result<Config> {
     } else if let Some(config_file) = &options.config_file {
         Config::from_file(config_file)?
     } else {
-        // Auto-discover from first file's parent directory or CWD
+        // Auto-discover from the first file's parent directory, else the
+        // first virtual path's (by string name), else the CWD
+        let first_virtual_path = options
+            .virtual_paths
+            .iter()
+            .filter(|(name, _)| options.strings.contains_key(*name))
+            .min_by_key(|(name, _)| name.as_str())
+            .map(|(_, path)| path.as_path());
         let start = options
             .files
             .first()
-            .and_then(|f| std::path::Path::new(f).parent().map(|p| p.to_path_buf()))
-            .unwrap_or_else(|| std::path::PathBuf::from("."));
+            .map(std::path::Path::new)
+            .or(first_virtual_path)
+            .and_then(|path| path.parent())
+            .map(|dir| dir.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
         Config::discover(&start).unwrap_or_default()
     };
 
-    // Resolve extends chain
-    config.resolve_extends()
+    // Resolve extends chain, over the user's global config if asked to
+    let global = if options.use_global_config {
+        Config::global()?
+    } else {
+        None
+    };
+    match global {
+        Some((_, global)) => config.resolve_over(&global),
+        None => config.resolve_extends(),
+    }
 }
 
 /// Extract front matter line count from document.
 ///
-/// Supports custom regex pattern. When pattern is None, no front matter is extracted
-/// (for backwards compatibility - user must opt-in via --front-matter flag).
-/// Returns the number of lines in the front matter block (including delimiters),
-/// or 0 if no front matter is detected.
-fn extract_front_matter_line_count(lines: &[&str], pattern: Option<&str>) -> usize {
-    if lines.is_empty() {
+/// Front matter is only extracted when delimiters are configured (opt-in via
+/// `LintOptions::front_matter`). Each (opening, closing) pair is tried in
+/// order; returns the number of lines in the first block that opens on line
+/// 1 and is closed (including delimiters), or 0 if no front matter is
+/// detected.
+fn extract_front_matter_line_count(
+    lines: &[&str],
+    delimiters: &[(regex::Regex, regex::Regex)],
+) -> usize {
+    let Some(first_line) = lines.first() else {
         return 0;
-    }
-
-    let first_line = lines[0].trim_end_matches(['\n', '\r']);
-
-    // Only extract front matter when explicitly requested via pattern
-    let pattern_str = match pattern {
-        Some(p) => p,
-        None => return 0, // No pattern = no front matter extraction (opt-in only)
     };
+    let first_line = first_line.trim_end_matches(['\n', '\r']);
 
-    let Ok(regex) = regex::Regex::new(pattern_str) else {
-        return 0;
-    };
-    if !regex.is_match(first_line) {
-        return 0;
-    }
-    // Scan for closing delimiter (second pattern match)
-    for i in 1..lines.len() {
-        let line = lines[i].trim_end_matches(['\n', '\r']);
-        if regex.is_match(line) {
-            return i + 1;
+    for (open, close) in delimiters {
+        if !open.is_match(first_line) {
+            continue;
+        }
+        // Scan for the closing delimiter
+        let closing = lines
+            .iter()
+            .skip(1)
+            .position(|line| close.is_match(line.trim_end_matches(['\n', '\r'])));
+        if let Some(i) = closing {
+            return i + 2;
         }
     }
-    0 // No closing = no front matter
+    0 // No closed block = no front matter
+}
+
+/// Whether `content` carries one of the config's `skip_generated` markers
+fn is_generated(config: &Config, content: &str) -> bool {
+    let (_, content) = crate::helpers::strip_bom(content);
+    config
+        .skip_generated
+        .as_ref()
+        .is_some_and(|skip| skip.matches(content))
 }
 
 /// Lint a single piece of content using pre-computed rule state.
+///
+/// Returns `None` when the content carries a `skip_generated` marker.
 fn lint_content(
     content: &str,
     config: &Config,
     name: &str,
     prepared: &PreparedRules<'_>,
     workspace_headings: Option<&HashMap<String, Vec<String>>>,
-) -> Result<Vec<LintError>> {
+) -> Result<Option<Vec<LintError>>> {
     use crate::config::RuleConfig;
     use std::sync::LazyLock;
 
     static EMPTY_CONFIG: LazyLock<HashMap<String, serde_json::Value>> = LazyLock::new(HashMap::new);
 
+    if is_generated(config, content) {
+        return Ok(None);
+    }
+
+    // Strip a UTF-8 byte order mark so line 1 matches heading/front-matter
+    // patterns; columns are relative to the content after it.
+    let (has_bom, content) = crate::helpers::strip_bom(content);
+
     // Split into lines (zero-copy, preserving line endings)
     let lines: Vec<&str> = content.split_inclusive('\n').collect();
 
+    // Parse inline configuration directives (<!-- markdownlint-disable/enable -->)
+    let inline_config = InlineConfig::from_lines_with_config(&lines, config);
+
+    // With `code_block_languages`, rules see only those code blocks
+    let code_view = (!prepared.code_block_languages.is_empty())
+        .then(|| code_block_view(content, &prepared.code_block_languages));
+    let (content, lines) = match &code_view {
+        Some((view, _)) => (view.as_str(), view.split_inclusive('\n').collect()),
+        None => (content, lines),
+    };
+
     // Extract front matter if present
-    let fm_count =
-        extract_front_matter_line_count(&lines, prepared.front_matter_pattern.as_deref());
+    let fm_count = extract_front_matter_line_count(&lines, &prepared.front_matter);
     let front_matter_lines: &[&str] = &lines[..fm_count];
 
-    // Parse inline configuration directives (<!-- markdownlint-disable/enable -->)
-    let inline_config = InlineConfig::parse(&lines);
-
     let mut all_errors = Vec::new();
+    // Uppercased IDs of the rules run on this file
+    let mut ran = HashSet::new();
 
     // Only parse if at least one enabled rule needs tokens
-    let tokens = if prepared.needs_parser {
-        parser::parse(content)
+    let tokens = if prepared.needs_parser && fm_count > 0 {
+        // Parse the body only (front matter such as `key: x` over `---` would
+        // read as a setext heading), keeping absolute line numbers by standing
+        // in a blank line for each front matter line
+        let fm_len: usize = front_matter_lines.iter().map(|line| line.len()).sum();
+        parser::parse_with_math(
+            &format!("{}{}", "\n".repeat(fm_count), &content[fm_len..]),
+            config.math_enabled(),
+        )
+    } else if prepared.needs_parser {
+        parser::parse_with_math(content, config.math_enabled())
     } else {
         vec![]
     };
 
-    for rule in &prepared.enabled {
+    let analysis = crate::helpers::AnalysisCache::new(&lines, &tokens);
+
+    // Rules see a string input under its virtual path, if it has one
+    let path = prepared
+        .virtual_paths
+        .get(name)
+        .map_or(name, String::as_str);
+
+    for (rule_idx, rule) in prepared.enabled.iter().enumerate() {
         let rule_name = rule.names()[0];
 
+        // Skip rules that blew their time budget on an earlier file
+        if prepared.timed_out[rule_idx].load(Ordering::Relaxed) {
+            continue;
+        }
+
+        // Skip rules scoped away from this file
+        if !rule.applies_to_file(path)
+            || prepared.file_globs[rule_idx]
+                .as_ref()
+                .is_some_and(|globs| !file_globs_match(globs, path))
+            || prepared.exclude_globs[rule_idx]
+                .as_ref()
+                .is_some_and(|globs| file_globs_match(globs, path))
+        {
+            continue;
+        }
+
+        ran.insert(rule_name.to_uppercase());
+
         // Extract per-rule config options (avoid clone when no config)
         let rule_config = match config.get_rule_config(rule_name) {
             Some(RuleConfig::Options(opts)) => opts,
@@ -323,17 +855,32 @@ fn lint_content(
         };
 
         let params = crate::types::RuleParams {
-            name,
+            name: path,
             version: crate::VERSION,
             lines: &lines,
             front_matter_lines,
             tokens: &tokens,
             config: rule_config,
             workspace_headings,
+            global_config: Some(config),
+            has_bom,
+            patterns: Some(&prepared.patterns),
+            analysis: Some(&analysis),
+        };
+
+        // Run the rule; a panic is reported as an error instead of taking
+        // down the run (or the language server)
+        let started = Instant::now();
+        let mut errors = match std::panic::catch_unwind(AssertUnwindSafe(|| rule.lint(&params))) {
+            Ok(errors) => errors,
+            Err(payload) => vec![internal_error(*rule, payload.as_ref())],
         };
+        let elapsed = started.elapsed();
 
-        // Run the rule
-        let mut errors = rule.lint(&params);
+        // Front matter is not Markdown; rules see it only for line numbering
+        if fm_count > 0 && !rule.lints_front_matter() {
+            errors.retain(|error| error.line_number > fm_count || error.is_internal_error());
+        }
 
         // Apply per-rule severity override from config (if set)
         if let Some(severity) = config.get_rule_severity(rule_name) {
@@ -342,280 +889,259 @@ fn lint_content(
             }
         }
 
-        all_errors.extend(errors);
-    }
-
-    // Filter out errors suppressed by inline configuration
-    if inline_config.has_directives {
-        all_errors.retain(|error| !inline_config.is_disabled(error.line_number, error.rule_names));
-    }
-
-    // Sort errors by line number
-    all_errors.sort_by_key(|e| e.line_number);
-
-    Ok(all_errors)
-}
-
-// ---------------------------------------------------------------------------
-// Inline configuration directives
-// ---------------------------------------------------------------------------
-
-/// Parsed inline configuration state.
-///
-/// Uses a snapshot-based approach: instead of cloning rule ID strings into
-/// per-line HashSets (O(lines × rules) allocations), we store directive
-/// events and evaluate `is_disabled()` lazily by scanning events.
-///
-/// Supports the following HTML comment directives:
-/// - `<!-- markdownlint-disable MD001 MD002 -->` — disable specific rules
-/// - `<!-- markdownlint-disable -->` — disable all rules
-/// - `<!-- markdownlint-enable MD001 -->` — re-enable specific rules
-/// - `<!-- markdownlint-enable -->` — re-enable all rules
-/// - `<!-- markdownlint-disable-next-line MD001 -->` — disable for next line only
-/// - `<!-- markdownlint-disable-file MD001 -->` — disable for entire file
-/// - `<!-- markdownlint-enable-file MD001 -->` — re-enable for rest of file
-struct InlineConfig {
-    /// Whether any directives were found (fast path for skipping filter).
-    has_directives: bool,
-    /// Sorted directive events (line_number, event). Always sorted by line_number.
-    events: Vec<(usize, DirectiveEvent)>,
-}
-
-use std::collections::HashSet;
-
-/// A single inline directive event, stored once during parse.
-enum DirectiveEvent {
-    Disable(Vec<String>),
-    Enable(Vec<String>),
-    DisableNextLine(Vec<String>),
-    DisableFile(Vec<String>),
-    EnableFile(Vec<String>),
-}
-
-impl InlineConfig {
-    /// Parse inline directives from document lines.
-    fn parse(lines: &[&str]) -> Self {
-        let mut has_directives = false;
-        let mut events = Vec::new();
-
-        for (idx, line) in lines.iter().enumerate() {
-            let line_number = idx + 1;
-            let trimmed = line.trim();
-
-            if let Some(directive) = Self::parse_directive(trimmed) {
-                has_directives = true;
-                let event = match directive {
-                    Directive::Disable(rules) => DirectiveEvent::Disable(rules),
-                    Directive::Enable(rules) => DirectiveEvent::Enable(rules),
-                    Directive::DisableNextLine(rules) => DirectiveEvent::DisableNextLine(rules),
-                    Directive::DisableFile(rules) => DirectiveEvent::DisableFile(rules),
-                    Directive::EnableFile(rules) => DirectiveEvent::EnableFile(rules),
-                };
-                events.push((line_number, event));
+        // `no_fix` rules are reported, never fixed
+        if config.is_fix_disabled(rule.names()) {
+            errors.retain(|error| !error.fix_only);
+            for error in &mut errors {
+                error.fix_info = None;
             }
         }
 
-        InlineConfig {
-            has_directives,
-            events,
+        // Over budget: keep this file's results, skip the rule from now on,
+        // and report it once per run
+        if let Some(budget) = prepared.rule_timeout
+            && elapsed > budget
+            && !prepared.timed_out[rule_idx].swap(true, Ordering::Relaxed)
+        {
+            errors.push(LintError {
+                line_number: 1,
+                rule_names: rule.names(),
+                rule_description: rule.description(),
+                error_detail: Some(format!(
+                    "{} timed out, skipped ({}ms exceeds the {}ms rule budget; \
+                     disabled for the remaining files)",
+                    rule_name,
+                    elapsed.as_millis(),
+                    budget.as_millis()
+                )),
+                severity: crate::types::Severity::Warning,
+                ..Default::default()
+            });
         }
-    }
 
-    /// Check if a rule is disabled at a given line.
-    ///
-    /// Replays directive events up to `line_number` to compute the disabled
-    /// state. This avoids the O(lines × rules) String cloning of the
-    /// previous per-line HashSet approach.
-    fn is_disabled(&self, line_number: usize, rule_names: &[&str]) -> bool {
-        let mut active_disabled: HashSet<&str> = HashSet::new();
-        let mut file_disabled: HashSet<&str> = HashSet::new();
-        // Track the line number of the last disable-next-line directive
-        let mut disable_next_line: Option<(usize, &[String])> = None;
+        all_errors.extend(errors);
+    }
 
-        for (event_line, event) in &self.events {
-            if *event_line >= line_number {
-                break;
+    // Filter out errors suppressed by inline configuration, noting which
+    // directive suppressed each for MKD006
+    if inline_config.has_directives() {
+        let mut hits = HashSet::new();
+        all_errors.retain(|error| {
+            if error.is_internal_error() {
+                return true;
             }
-            match event {
-                DirectiveEvent::Disable(rules) => {
-                    if rules.is_empty() {
-                        active_disabled.insert("");
-                    } else {
-                        for r in rules {
-                            active_disabled.insert(r);
-                        }
-                    }
-                }
-                DirectiveEvent::Enable(rules) => {
-                    if rules.is_empty() {
-                        active_disabled.clear();
-                    } else {
-                        for r in rules {
-                            active_disabled.remove(r.as_str());
-                        }
-                    }
-                }
-                DirectiveEvent::DisableNextLine(rules) => {
-                    disable_next_line = Some((*event_line, rules));
-                }
-                DirectiveEvent::DisableFile(rules) => {
-                    if rules.is_empty() {
-                        file_disabled.insert("");
-                    } else {
-                        for r in rules {
-                            file_disabled.insert(r);
-                        }
-                    }
+            match inline_config.disabled_by(error.line_number, error.rule_names) {
+                Some((line, rule)) => {
+                    hits.insert((line, rule.to_string()));
+                    false
                 }
-                DirectiveEvent::EnableFile(rules) => {
-                    if rules.is_empty() {
-                        file_disabled.clear();
-                    } else {
-                        for r in rules {
-                            file_disabled.remove(r.as_str());
-                        }
-                    }
-                }
-            }
-        }
-
-        // Check file-level disables
-        if file_disabled.contains("") {
-            return true;
-        }
-        for name in rule_names {
-            if file_disabled.contains(name) {
-                return true;
+                None => true,
             }
-        }
+        });
 
-        // Check sticky disable/enable
-        if active_disabled.contains("") {
-            return true;
+        let mut errors = Vec::new();
+        if ran.contains("MKD006") {
+            let options = match config.get_rule_config("MKD006") {
+                Some(RuleConfig::Options(opts)) => opts,
+                _ => &EMPTY_CONFIG,
+            };
+            errors = crate::rules::inline_config_errors(
+                &inline_config,
+                options,
+                &prepared.custom_names,
+                &ran,
+                &hits,
+                !prepared.report_unused_disables,
+            );
         }
-        for name in rule_names {
-            if active_disabled.contains(name) {
-                return true;
-            }
+        if prepared.report_unused_disables {
+            errors.extend(crate::rules::unused_disable_errors(
+                &inline_config,
+                &lines,
+                &hits,
+            ));
         }
-
-        // Check disable-next-line: applies to the first non-directive line
-        // after the directive. We need to find if line_number is the target.
-        if let Some((dnl_line, rules)) = disable_next_line {
-            // The disable-next-line applies to the next non-directive line
-            // after dnl_line. Find it by checking if any events exist between
-            // dnl_line and line_number.
-            let next_non_directive = self.find_next_non_directive_line(dnl_line);
-            if next_non_directive == Some(line_number) {
-                if rules.is_empty() {
-                    return true;
-                }
-                for name in rule_names {
-                    if rules.iter().any(|r| r == name) {
-                        return true;
-                    }
+        if !errors.is_empty() {
+            if let Some(severity) = config.get_rule_severity("MKD006") {
+                for error in &mut errors {
+                    error.severity = severity;
                 }
             }
+            errors.retain(|error| !inline_config.is_disabled(error.line_number, error.rule_names));
+            all_errors.extend(errors);
         }
+    }
 
-        false
-    }
-
-    /// Find the first non-directive line after `after_line`.
-    fn find_next_non_directive_line(&self, after_line: usize) -> Option<usize> {
-        // Collect all directive line numbers
-        let directive_lines: HashSet<usize> = self.events.iter().map(|(l, _)| *l).collect();
-        let mut line = after_line + 1;
-        // Skip consecutive directive lines
-        while directive_lines.contains(&line) {
-            line += 1;
-        }
-        Some(line)
-    }
-
-    /// Parse a single directive from a trimmed line.
-    fn parse_directive(line: &str) -> Option<Directive> {
-        // Must be an HTML comment: <!-- markdownlint-xxx ... -->
-        let inner = line.strip_prefix("<!--")?.strip_suffix("-->")?.trim();
-
-        if let Some(rest) = inner.strip_prefix("markdownlint-disable-next-line") {
-            let rules = Self::parse_rule_list(rest);
-            Some(Directive::DisableNextLine(rules))
-        } else if let Some(rest) = inner.strip_prefix("markdownlint-disable-file") {
-            let rules = Self::parse_rule_list(rest);
-            Some(Directive::DisableFile(rules))
-        } else if let Some(rest) = inner.strip_prefix("markdownlint-enable-file") {
-            let rules = Self::parse_rule_list(rest);
-            Some(Directive::EnableFile(rules))
-        } else if let Some(rest) = inner.strip_prefix("markdownlint-disable") {
-            let rules = Self::parse_rule_list(rest);
-            Some(Directive::Disable(rules))
-        } else if let Some(rest) = inner.strip_prefix("markdownlint-enable") {
-            let rules = Self::parse_rule_list(rest);
-            Some(Directive::Enable(rules))
-        } else {
-            None
-        }
+    // Keep only errors inside the selected code blocks
+    if let Some((_, code_lines)) = &code_view {
+        all_errors.retain(|error| {
+            error.is_internal_error()
+                || code_lines
+                    .get(error.line_number.wrapping_sub(1))
+                    .is_some_and(|&in_block| in_block)
+        });
+    }
+
+    // Keep only errors anchored in the requested line ranges
+    if let Some(ranges) = prepared.line_ranges.get(name) {
+        all_errors.retain(|error| {
+            error.is_internal_error() || ranges.iter().any(|r| r.contains(&error.line_number))
+        });
     }
 
-    /// Parse a space-separated list of rule IDs from directive content.
-    fn parse_rule_list(s: &str) -> Vec<String> {
-        s.split_whitespace().map(|r| r.to_uppercase()).collect()
+    // Sort errors by line number
+    all_errors.sort_by_key(|e| e.line_number);
+
+    if prepared.capture_line_text {
+        for error in &mut all_errors {
+            if let Some(line) = error.line_number.checked_sub(1).and_then(|i| lines.get(i)) {
+                error.line_text = Some(line.trim_end_matches(['\n', '\r']).to_string());
+            }
+        }
     }
+
+    Ok(Some(all_errors))
 }
 
-enum Directive {
-    Disable(Vec<String>),
-    Enable(Vec<String>),
-    DisableNextLine(Vec<String>),
-    DisableFile(Vec<String>),
-    EnableFile(Vec<String>),
+/// `content` with every line blanked except fenced code blocks whose
+/// language is in `languages`, and flags marking those blocks' contents
+/// (index `line_number - 1`).
+///
+/// The blocks' fence lines are kept so the contents still parse as code.
+fn code_block_view(content: &str, languages: &[String]) -> (String, Vec<bool>) {
+    let mut fences = crate::helpers::CodeFenceTracker::new();
+    let mut selected = false;
+    let mut view = String::with_capacity(content.len());
+    let mut code_lines = Vec::new();
+
+    for line in content.split_inclusive('\n') {
+        let was_open = fences.in_code_block();
+        let in_block = fences.update(line);
+        if !was_open && in_block {
+            selected = crate::helpers::fence_line(line)
+                .and_then(|fence| fence.info.split_whitespace().next())
+                .is_some_and(|lang| languages.iter().any(|l| l.eq_ignore_ascii_case(lang)));
+        }
+        let contents = was_open && fences.in_code_block() && selected;
+        if in_block && selected {
+            view.push_str(line);
+        } else {
+            view.push_str(&line[line.trim_end_matches(['\n', '\r']).len()..]);
+        }
+        code_lines.push(contents);
+    }
+    (view, code_lines)
 }
 
 /// Apply fixes to markdown content
+///
+/// Columns are relative to the content after any UTF-8 byte order mark; a
+/// fix at column 0 of line 1 addresses the BOM itself (a positive
+/// `delete_count` removes it).
+///
+/// Fixes are applied bottom-up and right-to-left in a fixed order, so the
+/// output doesn't depend on the order of `errors`: line descending, then
+/// column descending, then fixes that delete text before pure insertions
+/// (so an insertion isn't deleted by a replacement at the same column),
+/// then [`Rule::fix_priority`](crate::types::Rule::fix_priority) ascending,
+/// then rule ID. Rules outside the built-in set sort with priority `0`.
 pub fn apply_fixes(content: &str, errors: &[LintError]) -> String {
+    apply_fixes_counted(content, errors).0
+}
+
+/// Apply fixes like [`apply_fixes`], also counting the fixes applied, by
+/// rule ID.
+///
+/// Fixes dropped as conflicting or skipped because their line was deleted
+/// or restructured are not counted, nor are `fix_only` helper edits, which
+/// belong to another error's fix.
+///
+/// # Examples
+/// ```
+/// let content = "# Title  \n\nText  \n";
+/// let errors = mkdlint::lint_string(content, &mkdlint::Config::default())?;
+/// let (fixed, counts) = mkdlint::lint::apply_fixes_counted(content, &errors);
+/// assert_eq!(fixed, "# Title\n\nText\n");
+/// assert_eq!(counts.get("MD009"), Some(&2));
+/// # Ok::<(), mkdlint::types::MarkdownlintError>(())
+/// ```
+pub fn apply_fixes_counted(
+    content: &str,
+    errors: &[LintError],
+) -> (String, BTreeMap<String, usize>) {
     use crate::types::FixInfo;
 
     // Collect only errors that have fix_info
-    let mut fixable: Vec<(usize, &FixInfo)> = errors
+    let mut fixable: Vec<(usize, &FixInfo, &LintError)> = errors
         .iter()
         .filter_map(|e| {
             e.fix_info.as_ref().map(|fi| {
                 let line = fi.line_number.unwrap_or(e.line_number);
-                (line, fi)
+                (line, fi, e)
             })
         })
         .collect();
 
     if fixable.is_empty() {
-        return content.to_string();
+        return (content.to_string(), BTreeMap::new());
     }
 
-    // Split content into lines, preserving line endings
-    let line_ending = if content.contains("\r\n") {
-        "\r\n"
-    } else {
-        "\n"
-    };
-    let mut lines: Vec<String> = if line_ending == "\r\n" {
-        content.split("\r\n").map(|s| s.to_string()).collect()
-    } else {
-        content.split('\n').map(|s| s.to_string()).collect()
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut count = |error: &LintError| {
+        if !error.fix_only {
+            *counts.entry(fix_rule_id(error).to_string()).or_default() += 1;
+        }
     };
 
-    // Remove trailing empty element from split (if content ends with newline)
-    if lines.last().is_some_and(|l| l.is_empty()) && content.ends_with(line_ending) {
-        lines.pop();
+    // Edits are relative to the content after the BOM, which is re-attached
+    // at the end unless a fix deletes it
+    let (mut keep_bom, content) = crate::helpers::strip_bom(content);
+
+    // Inserted newlines follow the document's style
+    let line_ending = crate::helpers::detect_line_ending(content);
+
+    // Split content into lines, keeping each line's own ending separately
+    let mut lines: Vec<String> = Vec::new();
+    let mut endings: Vec<&str> = Vec::new();
+    for line in content.split_inclusive('\n') {
+        let body = line
+            .strip_suffix("\r\n")
+            .or_else(|| line.strip_suffix('\n'))
+            .unwrap_or(line);
+        lines.push(body.to_string());
+        endings.push(&line[body.len()..]);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+        endings.push("");
     }
+    // Text lengths as the rules saw them, to recognize line ending edits
+    let text_lens: Vec<usize> = lines.iter().map(String::len).collect();
 
-    // Sort fixes: line DESC, then column DESC (apply bottom-up, right-to-left)
+    // Sort fixes: line DESC, then column DESC (apply bottom-up, right-to-left).
+    // At the same column, replacements go before pure insertions so an
+    // insertion isn't deleted by a replacement applied after it; remaining
+    // ties go by rule priority and ID so rule order never matters.
     fixable.sort_by(|a, b| {
-        b.0.cmp(&a.0).then_with(|| {
-            let col_b = b.1.edit_column.unwrap_or(1);
-            let col_a = a.1.edit_column.unwrap_or(1);
-            col_b.cmp(&col_a)
-        })
+        b.0.cmp(&a.0)
+            .then_with(|| {
+                let col_b = b.1.edit_column.unwrap_or(1);
+                let col_a = a.1.edit_column.unwrap_or(1);
+                col_b.cmp(&col_a)
+            })
+            .then_with(|| {
+                let del_b = b.1.delete_count.unwrap_or(0).max(0);
+                let del_a = a.1.delete_count.unwrap_or(0).max(0);
+                del_b.cmp(&del_a)
+            })
+            .then_with(|| {
+                let (rule_a, rule_b) = (fix_rule_id(a.2), fix_rule_id(b.2));
+                crate::rules::fix_priority(rule_a)
+                    .cmp(&crate::rules::fix_priority(rule_b))
+                    .then_with(|| rule_a.cmp(rule_b))
+            })
     });
+    let fixable = drop_overlapping_fixes(fixable);
 
     // Track which lines have been deleted or structurally modified
     let mut deleted_lines: std::collections::HashSet<usize> = std::collections::HashSet::new();
@@ -623,13 +1149,22 @@ pub fn apply_fixes(content: &str, errors: &[LintError]) -> String {
     // shifted content, so we skip them (they'll be caught on the next lint pass).
     let mut restructured_lines: std::collections::HashSet<usize> = std::collections::HashSet::new();
 
-    for (line_num, fix) in &fixable {
+    for (line_num, fix, error) in &fixable {
         let line_idx = line_num.saturating_sub(1);
 
+        // Column 0 of line 1 is the byte order mark
+        if line_idx == 0 && fix.edit_column == Some(0) {
+            if fix.delete_count.unwrap_or(0) > 0 && keep_bom {
+                keep_bom = false;
+                count(error);
+            }
+            continue;
+        }
+
         // Delete entire line
         if fix.delete_count == Some(-1) {
-            if line_idx < lines.len() && !deleted_lines.contains(&line_idx) {
-                deleted_lines.insert(line_idx);
+            if line_idx < lines.len() && deleted_lines.insert(line_idx) {
+                count(error);
             }
             continue;
         }
@@ -640,6 +1175,7 @@ pub fn apply_fixes(content: &str, errors: &[LintError]) -> String {
         {
             continue;
         }
+        count(error);
 
         let line = &mut lines[line_idx];
         let col = fix.edit_column.unwrap_or(1);
@@ -647,6 +1183,22 @@ pub fn apply_fixes(content: &str, errors: &[LintError]) -> String {
 
         // Delete characters if specified
         let del = fix.delete_count.unwrap_or(0).max(0) as usize;
+
+        // A deletion starting at the end of the original text replaces the
+        // line ending (only while no other fix has changed the line)
+        if del > 0
+            && col_idx == text_lens[line_idx]
+            && line.len() == text_lens[line_idx]
+            && !endings[line_idx].is_empty()
+        {
+            let ending = endings[line_idx];
+            endings[line_idx] = &ending[del.min(ending.len())..];
+            if let Some(ref text) = fix.insert_text {
+                line.push_str(text);
+            }
+            continue;
+        }
+
         if del > 0 && col_idx < line.len() {
             let end = (col_idx + del).min(line.len());
             line.replace_range(col_idx..end, "");
@@ -671,28 +1223,94 @@ pub fn apply_fixes(content: &str, errors: &[LintError]) -> String {
         }
     }
 
-    // Remove deleted lines in a single pass
-    if !deleted_lines.is_empty() {
-        let mut idx = 0;
-        lines.retain(|_| {
-            let keep = !deleted_lines.contains(&idx);
-            idx += 1;
-            keep
-        });
+    // Rejoin, dropping deleted lines; deleting an unterminated last line
+    // also drops the ending before it
+    let mut result = String::with_capacity(content.len());
+    let mut pending_ending = "";
+    for (idx, (line, ending)) in lines.iter().zip(&endings).enumerate() {
+        if deleted_lines.contains(&idx) {
+            continue;
+        }
+        result.push_str(pending_ending);
+        result.push_str(line);
+        pending_ending = ending;
     }
-
-    // Rejoin with line endings
-    let mut result = lines.join(line_ending);
-    if content.ends_with(line_ending) {
-        result.push_str(line_ending);
+    let last = lines.len() - 1;
+    if !deleted_lines.contains(&last) || !endings[last].is_empty() {
+        result.push_str(pending_ending);
     }
-    result
+    if keep_bom {
+        result.insert_str(0, crate::helpers::BOM);
+    }
+    (result, counts)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::types::{FixInfo, Severity};
+/// Rule ID a fix is attributed to and ordered by
+fn fix_rule_id(error: &LintError) -> &'static str {
+    error.rule_names.first().copied().unwrap_or("")
+}
+
+/// Drop fixes that edit the same text as a fix sorted before them on the
+/// same line; they are reconsidered on the next lint pass.
+///
+/// A fix whose deletion strictly contains every fix it overlaps replaces
+/// them instead, since the larger rewrite (e.g. MD011 rebuilding a link)
+/// already covers their text. Whole-line deletions and byte order mark
+/// fixes never conflict.
+fn drop_overlapping_fixes<'e>(
+    fixable: Vec<(usize, &'e crate::types::FixInfo, &'e LintError)>,
+) -> Vec<(usize, &'e crate::types::FixInfo, &'e LintError)> {
+    let is_column_edit = |(line, fix, _): &(usize, &crate::types::FixInfo, &LintError)| {
+        fix.delete_count != Some(-1) && !(*line <= 1 && fix.edit_column == Some(0))
+    };
+    // 0-based byte range the fix deletes; empty for pure insertions
+    let span = |fix: &crate::types::FixInfo| {
+        let start = fix.edit_column.unwrap_or(1).saturating_sub(1);
+        (start, start + fix.delete_count.unwrap_or(0).max(0) as usize)
+    };
+    // An insertion only conflicts with a deletion it falls strictly inside
+    let overlaps = |(a1, a2): (usize, usize), (b1, b2): (usize, usize)| {
+        if a1 == a2 {
+            b1 < a1 && a1 < b2
+        } else if b1 == b2 {
+            a1 < b1 && b1 < a2
+        } else {
+            a1 < b2 && b1 < a2
+        }
+    };
+
+    let mut kept: Vec<(usize, &crate::types::FixInfo, &LintError)> =
+        Vec::with_capacity(fixable.len());
+    // Fixes are sorted by line, so only the tail of `kept` can share a line
+    let mut line_start = 0;
+    for fix in fixable {
+        if kept.last().is_some_and(|last| last.0 != fix.0) {
+            line_start = kept.len();
+        }
+        if !is_column_edit(&fix) {
+            kept.push(fix);
+            continue;
+        }
+        let new = span(fix.1);
+        let conflicts: Vec<usize> = (line_start..kept.len())
+            .filter(|&i| is_column_edit(&kept[i]) && overlaps(span(kept[i].1), new))
+            .collect();
+        let contains =
+            |(a1, a2): (usize, usize)| new.0 <= a1 && a2 <= new.1 && a2 - a1 < new.1 - new.0;
+        if conflicts.iter().all(|&i| contains(span(kept[i].1))) {
+            for &i in conflicts.iter().rev() {
+                kept.remove(i);
+            }
+            kept.push(fix);
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FixInfo, Severity};
 
     #[test]
     fn test_lint_string() {
@@ -708,6 +1326,692 @@ mod tests {
         assert!(results.get("test.md").is_some());
     }
 
+    #[test]
+    fn test_lint_sync_streaming_reports_every_file() {
+        let options = LintOptions::new()
+            .with_string("a.md", "# A\n")
+            .with_string("b.md", "# B\ntrailing   \n")
+            .with_string("c.md", "# C\n");
+
+        let mut seen = Vec::new();
+        let results = lint_sync_streaming(&options, |name, errors| {
+            seen.push((name.to_string(), errors.len()));
+        })
+        .unwrap();
+        seen.sort();
+
+        assert_eq!(seen.len(), 3);
+        for (name, count) in &seen {
+            assert_eq!(results.get(name).unwrap().len(), *count);
+        }
+        assert_eq!(results.results, lint_sync(&options).unwrap().results);
+    }
+
+    #[test]
+    fn test_lint_sync_records_stats() {
+        let config: Config =
+            serde_json::from_str(r#"{"default": false, "MD009": true, "MD018": true}"#).unwrap();
+        let options = LintOptions::new()
+            .with_string("a.md", "#Title\n\ntrailing   \nmore  x \n")
+            .with_string("b.md", "# B\n")
+            .with_config(config);
+
+        let stats = lint_sync(&options).unwrap().stats();
+        assert_eq!(stats.errors, 3);
+        assert_eq!(stats.fixable, 3);
+        assert_eq!(stats.by_rule.get("MD009"), Some(&2));
+        assert_eq!(stats.by_rule.get("MD018"), Some(&1));
+        assert_eq!(stats.by_file.get("a.md"), Some(&3));
+        assert_eq!(stats.by_file.get("b.md"), Some(&0));
+        assert_eq!(
+            stats.rules_run.iter().collect::<Vec<_>>(),
+            ["MD009", "MD018"]
+        );
+    }
+
+    #[test]
+    fn test_skip_generated_marker_near_top() {
+        let config: Config = serde_json::from_str(r#"{"skip_generated": {}}"#).unwrap();
+        let generated = "# API\n\n<!-- AUTO-GENERATED, do not edit -->\ntrailing   \n";
+        let options = LintOptions::new()
+            .with_string("api.md", generated)
+            .with_config(config.clone());
+
+        let results = lint_sync(&options).unwrap();
+        assert!(results.get("api.md").unwrap().is_empty());
+        assert_eq!(results.skipped_generated, vec!["api.md".to_string()]);
+        assert_eq!(fix_content(generated, &config).unwrap(), generated);
+    }
+
+    #[test]
+    fn test_foreign_directive_line_is_not_linted() {
+        let content = "<!-- prettier-ignore -->  \n| a | b |\n|---|---|\n";
+        let options = LintOptions::new().with_string("table.md", content);
+        let results = lint_sync(&options).unwrap();
+        let errors = results.get("table.md").unwrap();
+        assert!(
+            errors.iter().all(|e| e.line_number != 1),
+            "directive line reported: {errors:?}"
+        );
+
+        let config: Config = serde_json::from_str(
+            r#"{"foreign_directives": {"prettier-ignore": "disable-next-line"}}"#,
+        )
+        .unwrap();
+        let options = LintOptions::new()
+            .with_string("table.md", content)
+            .with_config(config);
+        let results = lint_sync(&options).unwrap();
+        let errors = results.get("table.md").unwrap();
+        assert!(
+            errors.iter().all(|e| e.line_number > 2),
+            "next line reported: {errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_math_is_literal_unless_disabled() {
+        let content = "# Math\n\nSum $a * bc * d$ and $2 *a* b$ or _emphasis_.\n\n\
+                       $$\nx * yy * z  \n$$\n";
+        let rules = |config: &str| {
+            let config: Config = serde_json::from_str(config).unwrap();
+            let options = LintOptions::new()
+                .with_string("math.md", content)
+                .with_config(config);
+            let results = lint_sync(&options).unwrap();
+            let mut rules: Vec<(usize, &str)> = results
+                .get("math.md")
+                .unwrap()
+                .iter()
+                .map(|e| (e.line_number, e.rule_names[0]))
+                .collect();
+            rules.sort();
+            rules.dedup();
+            rules
+        };
+
+        assert_eq!(rules("{}"), []);
+        assert_eq!(
+            rules(r#"{"math": false}"#),
+            [(3, "MD037"), (3, "MD049"), (6, "MD009"), (6, "MD037"),]
+        );
+    }
+
+    #[test]
+    fn test_no_fix_rules_are_reported_but_not_fixed() {
+        let content = "Title\n=====\n\n## Section  \n";
+        let config: Config = serde_json::from_str(
+            r#"{"default": false, "MD003": {"style": "atx"}, "MD009": true, "no_fix": ["md003"]}"#,
+        )
+        .unwrap();
+        let errors = lint_string(content, &config).unwrap();
+        let md003: Vec<&LintError> = errors
+            .iter()
+            .filter(|e| e.rule_names[0] == "MD003")
+            .collect();
+        assert_eq!(md003.len(), 1);
+        assert!(md003.iter().all(|e| e.fix_info.is_none()));
+
+        // Other rules are still fixed
+        let fixed = fix_content(content, &config).unwrap();
+        assert_eq!(fixed, "Title\n=====\n\n## Section\n");
+    }
+
+    #[test]
+    fn test_no_enabled_rules_returns_empty_results() {
+        let config: Config =
+            serde_json::from_str(r#"{"default": false, "skip_generated": {}}"#).unwrap();
+        let mut options = LintOptions::new().with_config(config);
+        for i in 0..500 {
+            options = options.with_string(format!("doc{i}.md"), "#Title  \n\n* a\n- b\n");
+        }
+        options = options.with_string("gen.md", "<!-- @generated -->\n#Title  \n");
+
+        let results = lint_sync(&options).unwrap();
+        assert_eq!(results.results.len(), 501);
+        assert!(results.results.values().all(Vec::is_empty));
+        assert!(results.rules_run.is_empty());
+        assert_eq!(results.skipped_generated, ["gen.md"]);
+
+        // Files are still read, so a missing one is an error
+        let options = LintOptions::new()
+            .with_file("does-not-exist.md")
+            .with_config(serde_json::from_str(r#"{"default": false}"#).unwrap());
+        assert!(lint_sync(&options).is_err());
+    }
+
+    #[test]
+    fn test_apply_fixes_counted_skips_conflicts_and_helpers() {
+        let fix = |edit_column, delete_count, insert_text: &str| FixInfo {
+            line_number: Some(1),
+            edit_column: Some(edit_column),
+            delete_count: Some(delete_count),
+            insert_text: Some(insert_text.to_string()),
+        };
+        let errors = vec![
+            LintError {
+                line_number: 1,
+                rule_names: &["MD009"],
+                fix_info: Some(fix(3, 4, "")),
+                ..Default::default()
+            },
+            // Overlaps the MD009 fix, so it is dropped
+            LintError {
+                line_number: 1,
+                rule_names: &["MD010"],
+                fix_info: Some(fix(2, 2, "x")),
+                ..Default::default()
+            },
+            LintError {
+                line_number: 2,
+                rule_names: &["MD012"],
+                fix_info: Some(FixInfo {
+                    line_number: Some(2),
+                    ..fix(1, -1, "")
+                }),
+                ..Default::default()
+            },
+            LintError {
+                line_number: 3,
+                rule_names: &["MD012"],
+                fix_info: Some(FixInfo {
+                    line_number: Some(3),
+                    ..fix(1, -1, "")
+                }),
+                fix_only: true,
+                ..Default::default()
+            },
+        ];
+        let (fixed, counts) = apply_fixes_counted("abcdefg\n\n\nend\n", &errors);
+        assert_eq!(fixed, "abg\nend\n");
+        assert_eq!(
+            counts,
+            BTreeMap::from([("MD009".to_string(), 1), ("MD012".to_string(), 1)])
+        );
+    }
+
+    #[test]
+    fn test_converge_fixes_records_passes() {
+        let config = Config::default();
+        let outcome = converge_fixes("# Title  \n\nText  \n", |content| {
+            lint_string(content, &config)
+        })
+        .unwrap();
+        assert_eq!(outcome.content, "# Title\n\nText\n");
+        assert_eq!(outcome.passes.len(), 1);
+        assert_eq!(outcome.total_fixes(), 2);
+    }
+
+    #[test]
+    fn test_code_block_languages_lints_only_those_blocks() {
+        let long = "x".repeat(100);
+        let content = format!(
+            "# Title\n\n{long}\n\n```sql\nSELECT '{long}';\nSELECT 1;\n```\n\n```python\nprint('{long}')\n```\n\n```SQL title=q\nSELECT '{long}';\n```\n"
+        );
+        let config: Config = serde_json::from_str(
+            r#"{"default": false, "MD013": {"line_length": 80, "code_blocks": true}}"#,
+        )
+        .unwrap();
+        let options = LintOptions::new()
+            .with_string("a.md", content.clone())
+            .with_config(config.clone())
+            .with_code_block_language("sql");
+        let results = lint_sync(&options).unwrap();
+        let lines: Vec<(usize, &str)> = results
+            .get("a.md")
+            .unwrap()
+            .iter()
+            .map(|e| (e.line_number, e.rule_names[0]))
+            .collect();
+        assert_eq!(lines, [(6, "MD013"), (15, "MD013")]);
+
+        // Without the option, the prose and every block are checked
+        let options = LintOptions::new()
+            .with_string("a.md", content)
+            .with_config(config);
+        let results = lint_sync(&options).unwrap();
+        assert_eq!(results.get("a.md").unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_line_ranges_filter_keeps_absolute_lines() {
+        // MD041 points at line 1, MD047 at the last line
+        let content = "Intro\n\n# Title\n\nOne  \nTwo  \nThree  ";
+        let lines_for = |options: LintOptions| -> Vec<(usize, &'static str)> {
+            let results = lint_sync(&options.with_string("a.md", content)).unwrap();
+            results
+                .get("a.md")
+                .unwrap()
+                .iter()
+                .map(|e| (e.line_number, e.rule_names[0]))
+                .collect()
+        };
+
+        assert_eq!(
+            lines_for(LintOptions::new().with_line_range("a.md", 5..7)),
+            [(5, "MD009"), (6, "MD009")]
+        );
+        assert_eq!(
+            lines_for(
+                LintOptions::new()
+                    .with_line_range("a.md", 1..2)
+                    .with_line_range("a.md", 7..8)
+            ),
+            [(1, "MD041"), (7, "MD009"), (7, "MD047")]
+        );
+        // Other inputs are reported in full
+        assert_eq!(
+            lines_for(LintOptions::new().with_line_range("b.md", 1..2)).len(),
+            5
+        );
+    }
+
+    #[test]
+    fn test_skip_generated_marker_past_search_lines() {
+        let config: Config =
+            serde_json::from_str(r#"{"skip_generated": {"markers": ["@generated"]}}"#).unwrap();
+        let content = "# Notes\n\na\n\nb\n\nMentions @generated  \n";
+        let options = LintOptions::new()
+            .with_string("notes.md", content)
+            .with_config(config);
+
+        let results = lint_sync(&options).unwrap();
+        assert!(!results.get("notes.md").unwrap().is_empty());
+        assert!(!results.is_skipped_generated("notes.md"));
+    }
+
+    #[test]
+    fn test_capture_line_text_matches_source_line() {
+        let content = "# Title\r\n\r\nSome text   \r\n";
+        let options = LintOptions::new()
+            .with_string("a.md", content)
+            .with_line_text();
+        let results = lint_sync(&options).unwrap();
+        let errors = results.get("a.md").unwrap();
+
+        let md009 = errors
+            .iter()
+            .find(|e| e.rule_names[0] == "MD009")
+            .expect("trailing spaces reported");
+        assert_eq!(md009.line_text.as_deref(), Some("Some text   "));
+        assert!(errors.iter().all(|e| e.line_text.is_some()));
+
+        // Off by default
+        let options = LintOptions::new().with_string("a.md", content);
+        let results = lint_sync(&options).unwrap();
+        assert!(
+            results
+                .get("a.md")
+                .unwrap()
+                .iter()
+                .all(|e| e.line_text.is_none())
+        );
+    }
+
+    /// Custom rule that sleeps on every run and counts its invocations.
+    struct SlowRule(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+    impl crate::types::Rule for SlowRule {
+        fn names(&self) -> &'static [&'static str] {
+            &["TEST-SLOW"]
+        }
+
+        fn description(&self) -> &'static str {
+            "Takes longer than the rule budget"
+        }
+
+        fn tags(&self) -> &[&'static str] {
+            &["test"]
+        }
+
+        fn parser_type(&self) -> ParserType {
+            ParserType::None
+        }
+
+        fn lint(&self, _params: &crate::types::RuleParams) -> Vec<LintError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(30));
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_rule_timeout_disables_slow_rule() {
+        let config: Config =
+            serde_json::from_str(r#"{"default": false, "TEST-SLOW": true}"#).unwrap();
+        let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let custom: Vec<BoxedRule> = vec![Box::new(SlowRule(calls.clone()))];
+        let prepared = prepare_rules(&config, &custom, &[], Some(5), false).unwrap();
+
+        let first = lint_content("# A\n", &config, "a.md", &prepared, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].severity, Severity::Warning);
+        assert!(
+            first[0]
+                .error_detail
+                .as_deref()
+                .unwrap()
+                .starts_with("TEST-SLOW timed out, skipped")
+        );
+
+        // Later files skip the rule entirely and report nothing more
+        let second = lint_content("# B\n", &config, "b.md", &prepared, None)
+            .unwrap()
+            .unwrap();
+        assert!(second.is_empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    /// Custom rule that panics on documents containing "boom".
+    struct PanickingRule;
+
+    impl crate::types::Rule for PanickingRule {
+        fn names(&self) -> &'static [&'static str] {
+            &["TEST-PANIC"]
+        }
+
+        fn description(&self) -> &'static str {
+            "Panics on some documents"
+        }
+
+        fn tags(&self) -> &[&'static str] {
+            &["test"]
+        }
+
+        fn parser_type(&self) -> ParserType {
+            ParserType::None
+        }
+
+        fn lint(&self, params: &crate::types::RuleParams) -> Vec<LintError> {
+            if params.lines.iter().any(|line| line.contains("boom")) {
+                let empty: Vec<usize> = Vec::new();
+                let _ = empty[params.lines.len()];
+            }
+            Vec::new()
+        }
+    }
+
+    #[test]
+    fn test_rule_panic_is_reported_and_run_continues() {
+        let options = LintOptions::new()
+            .with_string("a.md", "# A\n\nboom  \n")
+            .with_string("b.md", "# B\n\nfine  \n")
+            .with_custom_rule(Box::new(PanickingRule));
+        let results = lint_sync(&options).unwrap();
+
+        let a = results.get("a.md").unwrap();
+        let internal: Vec<&LintError> = a.iter().filter(|e| e.is_internal_error()).collect();
+        assert_eq!(internal.len(), 1);
+        assert_eq!(internal[0].line_number, 1);
+        assert_eq!(internal[0].severity, Severity::Error);
+        let detail = internal[0].error_detail.as_deref().unwrap();
+        assert!(
+            detail.starts_with("internal error in TEST-PANIC (please report): index out of bounds"),
+            "{}",
+            detail
+        );
+        // Other rules still ran on the same file, and other files are unaffected
+        assert!(a.iter().any(|e| e.rule_names[0] == "MD009"));
+        let b = results.get("b.md").unwrap();
+        assert!(b.iter().any(|e| e.rule_names[0] == "MD009"));
+        assert!(!b.iter().any(|e| e.is_internal_error()));
+        assert_eq!(results.internal_errors().count(), 1);
+    }
+
+    /// Custom rule that only applies to changelogs and flags every file.
+    struct ChangelogRule;
+
+    impl crate::types::Rule for ChangelogRule {
+        fn names(&self) -> &'static [&'static str] {
+            &["TEST-CHANGELOG"]
+        }
+
+        fn description(&self) -> &'static str {
+            "Changelog format"
+        }
+
+        fn tags(&self) -> &[&'static str] {
+            &["test"]
+        }
+
+        fn parser_type(&self) -> ParserType {
+            ParserType::None
+        }
+
+        fn applies_to_file(&self, name: &str) -> bool {
+            name.ends_with("CHANGELOG.md")
+        }
+
+        fn lint(&self, params: &crate::types::RuleParams) -> Vec<LintError> {
+            vec![LintError {
+                line_number: 1,
+                rule_names: self.names(),
+                rule_description: self.description(),
+                error_detail: Some(params.name.to_string()),
+                ..Default::default()
+            }]
+        }
+    }
+
+    #[test]
+    fn test_rule_applies_to_file() {
+        let config: Config =
+            serde_json::from_str(r#"{"default": false, "TEST-CHANGELOG": true}"#).unwrap();
+        let options = LintOptions {
+            strings: [
+                ("CHANGELOG.md".to_string(), "# Changelog\n".to_string()),
+                ("README.md".to_string(), "# Readme\n".to_string()),
+            ]
+            .into(),
+            config: Some(config),
+            custom_rules: vec![Box::new(ChangelogRule)],
+            ..Default::default()
+        };
+        let results = lint_sync(&options).unwrap();
+        assert_eq!(results.get("CHANGELOG.md").unwrap().len(), 1);
+        assert!(results.get("README.md").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rule_files_globs() {
+        let config: Config =
+            serde_json::from_str(r#"{"MD009": {"files": ["docs/**", "NOTES.md"]}}"#).unwrap();
+        let prepared = prepare_rules(&config, &[], &[], None, false).unwrap();
+        let md009 = |name: &str| {
+            lint_content("# A\n\nText  \n", &config, name, &prepared, None)
+                .unwrap()
+                .unwrap()
+                .iter()
+                .filter(|e| e.rule_names[0] == "MD009")
+                .count()
+        };
+        assert_eq!(md009("./docs/guide/a.md"), 1);
+        assert_eq!(md009("/repo/NOTES.md"), 1);
+        assert_eq!(md009("README.md"), 0);
+    }
+
+    #[test]
+    fn test_rule_include_exclude_globs() {
+        let config: Config = serde_json::from_str(
+            r#"{"MD013": {"line_length": 80, "exclude": ["**/CHANGELOG.md"]}}"#,
+        )
+        .unwrap();
+        let prepared = prepare_rules(&config, &[], &[], None, false).unwrap();
+        let content = format!("# A\n\n{}\n", "word ".repeat(20));
+        let md013 = |prepared: &PreparedRules, name: &str| {
+            lint_content(&content, &config, name, prepared, None)
+                .unwrap()
+                .unwrap()
+                .iter()
+                .filter(|e| e.rule_names[0] == "MD013")
+                .count()
+        };
+        assert_eq!(md013(&prepared, "CHANGELOG.md"), 0);
+        assert_eq!(md013(&prepared, "./docs/CHANGELOG.md"), 0);
+        assert_eq!(md013(&prepared, "README.md"), 1);
+        assert_eq!(md013(&prepared, "docs/guide.md"), 1);
+
+        // `exclude` wins over `include`
+        let config: Config = serde_json::from_str(
+            r#"{"MD013": {"include": ["docs/**"], "exclude": ["**/CHANGELOG.md"]}}"#,
+        )
+        .unwrap();
+        let prepared = prepare_rules(&config, &[], &[], None, false).unwrap();
+        assert_eq!(md013(&prepared, "docs/guide.md"), 1);
+        assert_eq!(md013(&prepared, "docs/CHANGELOG.md"), 0);
+        assert_eq!(md013(&prepared, "README.md"), 0);
+    }
+
+    #[test]
+    fn test_virtual_path_discovers_config_and_scopes_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("crates/foo")).unwrap();
+        std::fs::write(
+            dir.path().join(".markdownlint.json"),
+            r#"{"default": false, "MD009": {"files": ["**/crates/*/README.md"]}}"#,
+        )
+        .unwrap();
+        let options = || {
+            LintOptions::new()
+                .with_string("readme", "Text  \n")
+                .with_virtual_path("readme", dir.path().join("crates/foo/README.md"))
+        };
+        let rules = |options: &LintOptions| -> Vec<&'static str> {
+            let results = lint_sync(options).unwrap();
+            let errors = results.get("readme").unwrap();
+            errors.iter().map(|e| e.rule_names[0]).collect()
+        };
+
+        // The config is discovered from the virtual directory, and its glob
+        // matches the virtual path rather than the string's name
+        assert_eq!(rules(&options()), ["MD009"]);
+
+        // Explicit config beats discovery
+        assert_eq!(
+            rules(&options().with_config(Config::default())),
+            ["MD009", "MD041"]
+        );
+    }
+
+    #[test]
+    fn test_virtual_paths_resolve_cross_file_links() {
+        let config: Config = serde_json::from_str(r#"{"default": false, "MD051": true}"#).unwrap();
+        let options = LintOptions::new()
+            .with_string("readme", "# Foo\n\n[Usage](docs/usage.md#missing)\n")
+            .with_virtual_path("readme", "pkg/README.md")
+            .with_string("usage", "# Usage\n")
+            .with_virtual_path("usage", "pkg/docs/usage.md")
+            .with_config(config);
+        let results = lint_sync(&options).unwrap();
+        let errors = results.get("readme").unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0]
+                .error_detail
+                .as_deref()
+                .unwrap()
+                .contains("'docs/usage.md'")
+        );
+    }
+
+    #[test]
+    fn test_rule_files_invalid_glob_is_config_error() {
+        let config: Config = serde_json::from_str(r#"{"MD009": {"files": ["a[.md"]}}"#).unwrap();
+        let err = prepare_rules(&config, &[], &[], None, false).err().unwrap();
+        assert!(matches!(err, MarkdownlintError::InvalidConfig(_)), "{err}");
+    }
+
+    #[test]
+    fn test_rule_regex_option_invalid_is_config_error() {
+        for json in [
+            r#"{"MD041": {"allowed_preamble_patterns": ["(unclosed"]}}"#,
+            r#"{"MD025": {"front_matter_title": "[title"}}"#,
+        ] {
+            let config: Config = serde_json::from_str(json).unwrap();
+            let options = LintOptions::new()
+                .with_string("doc.md", "# Title\n")
+                .with_config(config);
+            let err = lint_sync(&options).err().unwrap();
+            assert!(matches!(err, MarkdownlintError::InvalidConfig(_)), "{err}");
+            assert!(err.to_string().contains("invalid pattern"), "{err}");
+        }
+
+        // A disabled rule's options are not compiled
+        let config: Config = serde_json::from_str(
+            r#"{"MD041": {"enabled": false, "allowed_preamble_patterns": ["(unclosed"]}}"#,
+        )
+        .unwrap();
+        assert!(prepare_rules(&config, &[], &[], None, false).is_ok());
+    }
+
+    #[test]
+    fn test_rule_timeout_unlimited_by_default() {
+        let config: Config =
+            serde_json::from_str(r#"{"default": false, "TEST-SLOW": true}"#).unwrap();
+        let options = LintOptions::new()
+            .with_string("a.md", "# A\n")
+            .with_config(config)
+            .with_custom_rule(Box::new(SlowRule(Default::default())));
+        let results = lint_sync(&options).unwrap();
+        assert!(results.get("a.md").unwrap().is_empty());
+    }
+
+    /// Custom rule that reports MD009's configured `br_spaces` on line 1.
+    struct SiblingConfigRule;
+
+    impl crate::types::Rule for SiblingConfigRule {
+        fn names(&self) -> &'static [&'static str] {
+            &["TEST-SIBLING"]
+        }
+
+        fn description(&self) -> &'static str {
+            "Reads a sibling rule's option"
+        }
+
+        fn tags(&self) -> &[&'static str] {
+            &["test"]
+        }
+
+        fn parser_type(&self) -> ParserType {
+            ParserType::None
+        }
+
+        fn lint(&self, params: &crate::types::RuleParams) -> Vec<LintError> {
+            let br_spaces = params
+                .sibling_option("MD009", "br_spaces")
+                .and_then(|v| v.as_u64());
+            vec![LintError {
+                line_number: 1,
+                rule_names: self.names(),
+                rule_description: self.description(),
+                error_detail: Some(format!("br_spaces: {:?}", br_spaces)),
+                ..Default::default()
+            }]
+        }
+    }
+
+    #[test]
+    fn test_rule_reads_sibling_rule_option() {
+        let config: Config = serde_json::from_str(
+            r#"{"default": false, "TEST-SIBLING": true, "MD009": {"br_spaces": 4}}"#,
+        )
+        .unwrap();
+        let options = LintOptions::new()
+            .with_string("test.md", "# Title\n")
+            .with_config(config)
+            .with_custom_rule(Box::new(SiblingConfigRule));
+
+        let results = lint_sync(&options).unwrap();
+        let errors = results.get("test.md").unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("br_spaces: Some(4)")
+        );
+    }
+
     fn make_error(line: usize, fix: FixInfo) -> LintError {
         LintError {
             line_number: line,
@@ -805,6 +2109,92 @@ mod tests {
         assert_eq!(result, "# heading\n");
     }
 
+    #[test]
+    fn test_apply_fixes_replacement_before_insertion_at_same_column() {
+        // A prefix insertion and a fence replacement on the same line
+        let content = "~~~\n";
+        let errors = vec![
+            make_error(
+                1,
+                FixInfo {
+                    line_number: None,
+                    edit_column: Some(1),
+                    delete_count: None,
+                    insert_text: Some("x ".to_string()),
+                },
+            ),
+            make_error(
+                1,
+                FixInfo {
+                    line_number: None,
+                    edit_column: Some(1),
+                    delete_count: Some(3),
+                    insert_text: Some("```".to_string()),
+                },
+            ),
+        ];
+        let result = apply_fixes(content, &errors);
+        assert_eq!(result, "x ```\n");
+    }
+
+    #[test]
+    fn test_apply_fixes_same_column_order_is_deterministic() {
+        // Three rules fixing the same spot; the result must not depend on
+        // the order the errors arrive in
+        let content = "a\tb  \n";
+        let fix = |rule: &'static [&'static str], delete: Option<i32>, text: &str| LintError {
+            rule_names: rule,
+            ..make_error(
+                1,
+                FixInfo {
+                    line_number: None,
+                    edit_column: Some(2),
+                    delete_count: delete,
+                    insert_text: Some(text.to_string()),
+                },
+            )
+        };
+        let mut errors = vec![
+            fix(&["MD010"], None, "<tab>"),
+            fix(&["MD031"], None, "<blank>"),
+            fix(&["ZZZ001"], None, "<custom>"),
+        ];
+        let expected = apply_fixes(content, &errors);
+        // MD031 (structural) applies first, MD010 (whitespace) last
+        assert_eq!(expected, "a<tab><custom><blank>\tb  \n");
+        for i in 0..50 {
+            errors.rotate_left(1);
+            if i % 2 == 0 {
+                errors.swap(0, 1);
+            }
+            assert_eq!(apply_fixes(content, &errors), expected, "iteration {i}");
+        }
+    }
+
+    #[test]
+    fn test_apply_fixes_overlapping_edits() {
+        let fix = |column: usize, delete: i32, text: &str| {
+            make_error(
+                1,
+                FixInfo {
+                    line_number: None,
+                    edit_column: Some(column),
+                    delete_count: Some(delete),
+                    insert_text: Some(text.to_string()),
+                },
+            )
+        };
+        // Two edits of the same byte: only the first one sorted applies
+        let errors = vec![fix(4, 1, "text"), fix(4, 1, "")];
+        assert_eq!(apply_fixes("~~~ \n", &errors), "~~~text\n");
+        // A rewrite containing another edit replaces it
+        let errors = vec![fix(5, 3, "<xyz>"), fix(1, 8, "[t](xyz)")];
+        assert_eq!(apply_fixes("(t)[xyz]\n", &errors), "[t](xyz)\n");
+        // Partly overlapping edits: the leftmost waits for the next pass
+        let errors = vec![fix(3, 3, "C"), fix(1, 3, "A")];
+        assert_eq!(apply_fixes("abcdef\n", &errors), "abCf\n");
+    }
+
     #[test]
     fn test_apply_fixes_multiple_lines() {
         let content = "hello   \n#heading\nworld  \n";
@@ -918,23 +2308,110 @@ mod tests {
         assert_eq!(result, "# Title\r\n\r\nSome text\r\n");
     }
 
+    #[test]
+    fn test_apply_fixes_keeps_bom_for_other_fixes() {
+        let content = "\u{FEFF}hello   \n";
+        let errors = vec![make_error(
+            1,
+            FixInfo {
+                line_number: None,
+                edit_column: Some(6),
+                delete_count: Some(3),
+                insert_text: None,
+            },
+        )];
+        assert_eq!(apply_fixes(content, &errors), "\u{FEFF}hello\n");
+    }
+
+    #[test]
+    fn test_apply_fixes_removes_bom_at_column_zero() {
+        let content = "\u{FEFF}hello\n";
+        let errors = vec![make_error(
+            1,
+            FixInfo {
+                line_number: Some(1),
+                edit_column: Some(0),
+                delete_count: Some(3),
+                insert_text: None,
+            },
+        )];
+        assert_eq!(apply_fixes(content, &errors), "hello\n");
+    }
+
+    #[test]
+    fn test_apply_fixes_replaces_line_ending() {
+        // A deletion at the end of the text edits the line ending
+        let content = "one\r\ntwo\r\n";
+        let errors = vec![make_error(
+            2,
+            FixInfo {
+                line_number: None,
+                edit_column: Some(4),
+                delete_count: Some(2),
+                insert_text: Some("\n".to_string()),
+            },
+        )];
+        assert_eq!(apply_fixes(content, &errors), "one\r\ntwo\n");
+    }
+
+    #[test]
+    fn test_apply_fixes_mixed_line_endings_line_numbers() {
+        // Line numbers count every `\n`, as the rules see them
+        let content = "one\ntwo   \r\nthree\n";
+        let errors = vec![make_error(
+            2,
+            FixInfo {
+                line_number: None,
+                edit_column: Some(4),
+                delete_count: Some(3),
+                insert_text: None,
+            },
+        )];
+        assert_eq!(apply_fixes(content, &errors), "one\ntwo\r\nthree\n");
+    }
+
+    #[test]
+    fn test_apply_fixes_delete_unterminated_last_line() {
+        let content = "one\ntwo";
+        let errors = vec![make_error(
+            2,
+            FixInfo {
+                line_number: None,
+                edit_column: Some(1),
+                delete_count: Some(-1),
+                insert_text: None,
+            },
+        )];
+        assert_eq!(apply_fixes(content, &errors), "one");
+    }
+
+    /// Front matter delimiters as `prepare_rules` builds them
+    fn front_matter_delimiters(patterns: &[&str]) -> Vec<(regex::Regex, regex::Regex)> {
+        prepare_rules(&Config::default(), &[], patterns, None, false)
+            .unwrap()
+            .front_matter
+    }
+
     #[test]
     fn test_extract_front_matter_no_pattern() {
         let lines = vec!["---", "title: Test", "---", "# Content"];
-        assert_eq!(extract_front_matter_line_count(&lines, None), 0);
+        assert_eq!(extract_front_matter_line_count(&lines, &[]), 0);
     }
 
     #[test]
     fn test_extract_front_matter_yaml() {
         let lines = vec!["---\n", "title: Test\n", "---\n", "# Content\n"];
-        assert_eq!(extract_front_matter_line_count(&lines, Some("^---$")), 3);
+        assert_eq!(
+            extract_front_matter_line_count(&lines, &front_matter_delimiters(&["^---$"])),
+            3
+        );
     }
 
     #[test]
     fn test_extract_front_matter_toml() {
         let lines = vec!["+++\n", "title = \"Test\"\n", "+++\n", "# Content\n"];
         assert_eq!(
-            extract_front_matter_line_count(&lines, Some("^\\+\\+\\+$")),
+            extract_front_matter_line_count(&lines, &front_matter_delimiters(&["^\\+\\+\\+$"])),
             3
         );
     }
@@ -942,18 +2419,133 @@ mod tests {
     #[test]
     fn test_extract_front_matter_unclosed() {
         let lines = vec!["---\n", "title: Test\n", "# Content\n"];
-        assert_eq!(extract_front_matter_line_count(&lines, Some("^---$")), 0);
+        assert_eq!(
+            extract_front_matter_line_count(&lines, &front_matter_delimiters(&["^---$"])),
+            0
+        );
     }
 
     #[test]
     fn test_extract_front_matter_empty_doc() {
         let lines: Vec<&str> = vec![];
-        assert_eq!(extract_front_matter_line_count(&lines, Some("^---$")), 0);
+        assert_eq!(
+            extract_front_matter_line_count(&lines, &front_matter_delimiters(&["^---$"])),
+            0
+        );
+    }
+
+    #[test]
+    fn test_extract_front_matter_auto() {
+        let auto = front_matter_delimiters(&["auto"]);
+        let yaml = vec![
+            "---\n",
+            "title: Test\n",
+            "tags: [a]\n",
+            "---\n",
+            "# Content\n",
+        ];
+        assert_eq!(extract_front_matter_line_count(&yaml, &auto), 4);
+
+        let toml = vec![
+            "+++\r\n",
+            "title = \"Test\"\r\n",
+            "+++\r\n",
+            "# Content\r\n",
+        ];
+        assert_eq!(extract_front_matter_line_count(&toml, &auto), 3);
+
+        // Indented closing braces belong to nested objects
+        let json = vec![
+            "{\n",
+            "  \"title\": \"Test\",\n",
+            "  \"params\": {\n",
+            "    \"draft\": true\n",
+            "  }\n",
+            "}\n",
+            "# Content\n",
+        ];
+        assert_eq!(extract_front_matter_line_count(&json, &auto), 6);
+
+        let none = vec!["# Content\n", "---\n"];
+        assert_eq!(extract_front_matter_line_count(&none, &auto), 0);
+    }
+
+    #[test]
+    fn test_extract_front_matter_tries_each_pattern() {
+        // An unclosed `---` falls through to the next pattern
+        let delimiters = front_matter_delimiters(&["^-{3}$", "^[-+]{3}$"]);
+        let lines = vec!["---\n", "a\n", "+++\n", "# Content\n"];
+        assert_eq!(extract_front_matter_line_count(&lines, &delimiters), 3);
+    }
+
+    #[test]
+    fn test_front_matter_auto_skips_json_block() {
+        // Without front matter, MD041 would report the `{` line (a `title`
+        // key would make MD025 report `# Content` as a second title)
+        let content = "{\n  \"author\": \"Test\"\n}\n\n# Content\n";
+        let options = LintOptions::new()
+            .with_string("test.md", content)
+            .with_front_matter("auto");
+        let results = lint_sync(&options).unwrap();
+        assert!(results.get("test.md").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_front_matter_lines_are_not_linted() {
+        let long = "x".repeat(100);
+        let content = format!("---\ndescription: {long}\ntags: [a]   \n---\n# Title\n\nText   \n");
+        let options = LintOptions::new()
+            .with_string("test.md", content)
+            .with_front_matter("auto");
+        let results = lint_sync(&options).unwrap();
+        let errors = results.get("test.md").unwrap();
+        // Only the trailing spaces after the front matter, at their absolute line
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].rule_names[0], "MD009");
+        assert_eq!(errors[0].line_number, 7);
     }
 
     #[test]
-    fn test_extract_front_matter_invalid_regex() {
-        let lines = vec!["---\n", "title: Test\n", "---\n"];
-        assert_eq!(extract_front_matter_line_count(&lines, Some("[")), 0);
+    fn test_front_matter_whole_file_rules_still_apply() {
+        // MD047 opts in, so front matter without a final newline is reported
+        let options = LintOptions::new()
+            .with_string("test.md", "---\ntitle: Test\n---")
+            .with_front_matter("auto");
+        let results = lint_sync(&options).unwrap();
+        let errors = results.get("test.md").unwrap();
+        assert!(
+            errors.iter().any(|e| e.rule_names[0] == "MD047"),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn test_front_matter_invalid_regex_is_config_error() {
+        let mut options = LintOptions::new();
+        options
+            .strings
+            .insert("test.md".to_string(), "---\ntitle: Test\n---\n".to_string());
+        options.front_matter = Some("[".to_string());
+
+        let err = lint_sync(&options).unwrap_err();
+        assert!(matches!(err, MarkdownlintError::InvalidConfig(_)), "{err}");
+    }
+
+    #[test]
+    fn test_front_matter_regex_compiled_once() {
+        let config = Config::default();
+        let prepared = prepare_rules(&config, &[], &["^---$"], None, false).unwrap();
+        assert_eq!(prepared.patterns.compilations(), 1);
+
+        // Rule option patterns (MD001's front_matter_title) are compiled on
+        // the first file; no file after that compiles anything
+        let lint = |i: usize| {
+            let content = format!("---\ntitle: Doc {i}\n---\n# Doc {i}\n");
+            lint_content(&content, &config, "test.md", &prepared, None).unwrap();
+        };
+        lint(0);
+        let after_first = prepared.patterns.compilations();
+        (1..100).for_each(lint);
+        assert_eq!(prepared.patterns.compilations(), after_first);
     }
 }
@@ -3,7 +3,12 @@
 //! This module provides the main Language Server implementation.
 
 use super::{
-    code_actions, config::ConfigManager, diagnostics, document::DocumentManager, utils::Debouncer,
+    code_actions,
+    config::{ConfigManager, format_rule_options},
+    config_file, diagnostics,
+    document::DocumentManager,
+    scan,
+    utils::Debouncer,
 };
 use crate::{LintOptions, apply_fixes, lint_sync};
 use dashmap::DashMap;
@@ -11,6 +16,7 @@ use regex::Regex;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tower_lsp::jsonrpc::Result;
@@ -22,6 +28,10 @@ use tower_lsp::{Client, LanguageServer};
 static ANCHOR_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r#"\(#([^)"'\s]+)"#).expect("valid regex"));
 
+/// Per-rule time budget for LSP lints, so a pathological document cannot
+/// stall the request path (the CLI runs rules without a limit)
+const LSP_RULE_TIMEOUT_MS: u64 = 200;
+
 /// Walk a directory recursively and collect `.md`/`.markdown` files.
 ///
 /// Skips hidden directories (starting with `.`) and common build directories
@@ -70,6 +80,10 @@ pub struct MkdlintLanguageServer {
     /// Workspace-wide heading index: maps file system paths to heading anchor IDs.
     /// Updated during scan_workspace and on did_open/did_change/did_close.
     heading_index: Arc<DashMap<String, Vec<String>>>,
+    /// `mkdlint.fixOnSave`: return fix edits from `willSaveWaitUntil`
+    fix_on_save: Arc<AtomicBool>,
+    /// `scanConcurrency`: files linted at once by the workspace scan
+    scan_concurrency: Arc<AtomicUsize>,
 }
 
 impl MkdlintLanguageServer {
@@ -82,6 +96,8 @@ impl MkdlintLanguageServer {
             config_manager: Arc::new(RwLock::new(ConfigManager::new(vec![]))),
             debouncer: Arc::new(Debouncer::new(Duration::from_millis(300))),
             heading_index: Arc::new(DashMap::new()),
+            fix_on_save: Arc::new(AtomicBool::new(false)),
+            scan_concurrency: Arc::new(AtomicUsize::new(scan::default_concurrency())),
         }
     }
 
@@ -170,7 +186,7 @@ impl MkdlintLanguageServer {
             .await;
 
         // Phase 1: Sequential I/O — read files and discover configs
-        let mut scan_inputs: Vec<(Url, String, String, Option<crate::config::Config>)> = Vec::new();
+        let mut scan_inputs: Vec<scan::ScanInput> = Vec::new();
         for path in md_files {
             let content = match std::fs::read_to_string(&path) {
                 Ok(c) => c,
@@ -193,45 +209,99 @@ impl MkdlintLanguageServer {
             // Update heading index for cross-file MD051 validation
             self.update_heading_index(&file_name, &content);
 
-            scan_inputs.push((uri, file_name, content, config));
+            scan_inputs.push(scan::ScanInput {
+                uri,
+                file_name,
+                content,
+                config,
+            });
         }
 
-        // Take heading snapshot for cross-file validation (before parallel phase)
-        let heading_snapshot = self.snapshot_heading_index();
-
-        // Phase 2: Parallel lint using rayon
-        use rayon::prelude::*;
-        let lint_results: Vec<(Url, Vec<Diagnostic>)> = scan_inputs
-            .par_iter()
-            .filter_map(|(uri, file_name, content, config)| {
-                let mut options = LintOptions::default();
-                options.strings.insert(file_name.clone(), content.clone());
-                options.cached_workspace_headings = Some(heading_snapshot.clone());
-                if let Some(config) = config {
-                    options.config = Some(config.clone());
-                }
+        // Phase 2: Lint on blocking threads, a bounded number at a time,
+        // publishing each file's diagnostics as soon as it is done. Files
+        // opened meanwhile are left to their own lint.
+        scan::lint_files(
+            scan_inputs,
+            self.snapshot_heading_index(),
+            LSP_RULE_TIMEOUT_MS,
+            self.scan_concurrency.load(Ordering::Relaxed),
+            |uri| self.document_manager.contains(uri),
+            |uri, diags| self.client.publish_diagnostics(uri, diags, None),
+        )
+        .await;
+    }
 
-                let results = lint_sync(&options).ok()?;
-                let errors = results.get(file_name).unwrap_or(&[]).to_vec();
-                let lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
-                let diags: Vec<Diagnostic> = errors
+    /// Apply fixes to in-memory content until it stops changing (never writes)
+    ///
+    /// Returns the fixed content and the number of fixable issues found in
+    /// the original content.
+    fn converge_fixes(&self, uri: &Url, content: &str) -> (String, usize) {
+        let file_name = uri
+            .to_file_path()
+            .ok()
+            .and_then(|p| p.to_str().map(String::from))
+            .unwrap_or_else(|| uri.to_string());
+        let (config, virtual_path) = {
+            let manager = self.config_manager.read().unwrap();
+            (manager.discover_config(uri), manager.virtual_path(uri))
+        };
+        let headings = self.snapshot_heading_index();
+
+        let mut current = content.to_string();
+        let mut fixable = 0;
+        for pass in 0..crate::lint::DEFAULT_FIX_PASSES {
+            let options = LintOptions {
+                strings: [(file_name.clone(), current.clone())].into(),
+                virtual_paths: virtual_path
                     .iter()
-                    .filter(|err| !err.fix_only)
-                    .map(|err| diagnostics::lint_error_to_diagnostic(err, &lines))
-                    .collect();
-
-                if diags.is_empty() {
-                    None
-                } else {
-                    Some((uri.clone(), diags))
-                }
-            })
-            .collect();
+                    .map(|path| (file_name.clone(), path.clone()))
+                    .collect(),
+                config: config.clone(),
+                cached_workspace_headings: Some(headings.clone()),
+                rule_timeout_ms: Some(LSP_RULE_TIMEOUT_MS),
+                ..Default::default()
+            };
+            let Ok(results) = lint_sync(&options) else {
+                break;
+            };
+            let errors = results.get(&file_name).unwrap_or(&[]);
+            if pass == 0 {
+                fixable = errors
+                    .iter()
+                    .filter(|e| e.fix_info.is_some() && !e.fix_only)
+                    .count();
+            }
 
-        // Phase 3: Sequential publish (async — can't run inside rayon)
-        for (uri, diags) in lint_results {
-            self.client.publish_diagnostics(uri, diags, None).await;
+            let next = apply_fixes(&current, errors);
+            if next == current {
+                break; // Converged
+            }
+            current = next;
         }
+        (current, fixable)
+    }
+
+    /// Result of `mkdlint.previewFixes`: a unified diff of what the fixes
+    /// would change, without modifying the document
+    ///
+    /// `{"uri", "fixable", "diff", "message"}`; `diff` is empty when there is
+    /// nothing to fix.
+    fn preview_fixes(&self, uri: &Url, content: &str) -> serde_json::Value {
+        let (fixed, fixable) = self.converge_fixes(uri, content);
+        let path = uri.path().trim_start_matches('/');
+        let diff = crate::formatters::unified_diff(path, content, &fixed);
+        let fixable = if diff.is_empty() { 0 } else { fixable };
+        let message = if diff.is_empty() {
+            "No fixable issues".to_string()
+        } else {
+            format!("{} fixable issue(s)", fixable)
+        };
+        serde_json::json!({
+            "uri": uri,
+            "fixable": fixable,
+            "diff": diff,
+            "message": message,
+        })
     }
 
     /// Lint a document and publish diagnostics
@@ -249,12 +319,19 @@ impl MkdlintLanguageServer {
             .and_then(|p| p.to_str().map(String::from))
             .unwrap_or_else(|| uri.to_string());
 
-        // Discover config for this file
-        let config = self.config_manager.read().unwrap().discover_config(&uri);
+        // Discover config for this file; an unsaved document is linted as
+        // if it were in the workspace root
+        let (config, virtual_path) = {
+            let manager = self.config_manager.read().unwrap();
+            (manager.discover_config(&uri), manager.virtual_path(&uri))
+        };
 
         // Lint the document using string content
-        let mut options = LintOptions::default();
+        let mut options = LintOptions::default().with_rule_timeout_ms(LSP_RULE_TIMEOUT_MS);
         options.strings.insert(file_name.clone(), content.clone());
+        if let Some(path) = virtual_path {
+            options.virtual_paths.insert(file_name.clone(), path);
+        }
 
         // Provide workspace heading index for cross-file MD051 validation
         options.cached_workspace_headings = Some(self.snapshot_heading_index());
@@ -274,6 +351,21 @@ impl MkdlintLanguageServer {
             }
         };
 
+        // A rule panicked: it is also shown as a diagnostic, but log it so
+        // it can be reported
+        for (_, error) in results.internal_errors() {
+            self.client
+                .log_message(
+                    MessageType::ERROR,
+                    format!(
+                        "{}: {}",
+                        uri,
+                        error.error_detail.as_deref().unwrap_or_default()
+                    ),
+                )
+                .await;
+        }
+
         // Get errors for this file
         let errors = results.get(&file_name).unwrap_or(&[]).to_vec();
 
@@ -282,7 +374,7 @@ impl MkdlintLanguageServer {
         let diagnostics: Vec<Diagnostic> = errors
             .iter()
             .filter(|err| !err.fix_only)
-            .map(|err| diagnostics::lint_error_to_diagnostic(err, &lines))
+            .map(|err| diagnostics::lint_error_to_diagnostic(err, &lines, &uri))
             .collect();
 
         // Update cached errors
@@ -293,6 +385,17 @@ impl MkdlintLanguageServer {
             .publish_diagnostics(uri, diagnostics, None)
             .await;
     }
+
+    /// Validate an open mkdlint config file and publish its diagnostics
+    async fn validate_config_file(&self, uri: Url) {
+        let diagnostics = match self.document_manager.get(&uri) {
+            Some(doc) => config_file::diagnostics(&uri, &doc.content),
+            None => return,
+        };
+        self.client
+            .publish_diagnostics(uri, diagnostics, None)
+            .await;
+    }
 }
 
 #[tower_lsp::async_trait]
@@ -329,9 +432,45 @@ impl LanguageServer for MkdlintLanguageServer {
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
 
+        // Fix-on-save via willSaveWaitUntil (VS Code setting `mkdlint.fixOnSave`)
+        let fix_on_save = params
+            .initialization_options
+            .as_ref()
+            .and_then(|o| o.get("fixOnSave"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        self.fix_on_save.store(fix_on_save, Ordering::Relaxed);
+
+        // Files the initial workspace scan lints at once
+        if let Some(concurrency) = params
+            .initialization_options
+            .as_ref()
+            .and_then(|o| o.get("scanConcurrency"))
+            .and_then(|v| v.as_u64())
+            .filter(|&n| n > 0)
+        {
+            self.scan_concurrency
+                .store(concurrency as usize, Ordering::Relaxed);
+        }
+
+        // The user's global config, layered under workspace configs
+        let global_config = match crate::Config::global() {
+            Ok(global) => global.map(|(_, config)| config),
+            Err(e) => {
+                self.client
+                    .log_message(
+                        MessageType::WARNING,
+                        format!("Ignoring global mkdlint config: {e}"),
+                    )
+                    .await;
+                None
+            }
+        };
+
         // Update config manager with workspace roots and optional preset override
         *self.config_manager.write().unwrap() =
-            ConfigManager::with_preset(workspace_roots, preset_override);
+            ConfigManager::with_preset(workspace_roots, preset_override)
+                .with_global_config(global_config);
 
         self.client
             .log_message(
@@ -345,12 +484,21 @@ impl LanguageServer for MkdlintLanguageServer {
 
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
-                text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        change: Some(TextDocumentSyncKind::FULL),
+                        will_save_wait_until: Some(true),
+                        save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                        ..Default::default()
+                    },
                 )),
                 code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
                 execute_command_provider: Some(ExecuteCommandOptions {
-                    commands: vec!["mkdlint.fixAll".to_string()],
+                    commands: vec![
+                        "mkdlint.fixAll".to_string(),
+                        "mkdlint.previewFixes".to_string(),
+                    ],
                     work_done_progress_options: WorkDoneProgressOptions::default(),
                 }),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
@@ -459,6 +607,13 @@ impl LanguageServer for MkdlintLanguageServer {
         let content = params.text_document.text;
         let version = params.text_document.version;
 
+        // Config files are validated, not linted as Markdown
+        if config_file::is_config_file(&uri) {
+            self.document_manager.insert(uri.clone(), content, version);
+            self.validate_config_file(uri).await;
+            return;
+        }
+
         // Update heading index for cross-file validation
         let file_path = uri
             .to_file_path()
@@ -482,6 +637,12 @@ impl LanguageServer for MkdlintLanguageServer {
         if let Some(change) = params.content_changes.first() {
             let content = change.text.clone();
 
+            if config_file::is_config_file(&uri) {
+                self.document_manager.update(&uri, content, version);
+                self.validate_config_file(uri).await;
+                return;
+            }
+
             // Update heading index for cross-file validation
             let file_path = uri
                 .to_file_path()
@@ -513,8 +674,35 @@ impl LanguageServer for MkdlintLanguageServer {
         }
     }
 
+    async fn will_save_wait_until(
+        &self,
+        params: WillSaveTextDocumentParams,
+    ) -> Result<Option<Vec<TextEdit>>> {
+        if !self.fix_on_save.load(Ordering::Relaxed) {
+            return Ok(Some(vec![]));
+        }
+        let uri = params.text_document.uri;
+
+        // Cached errors are stale while a debounced lint is pending
+        if self.debouncer.is_pending(&uri) {
+            self.debouncer.cancel(&uri);
+            self.lint_and_publish(uri.clone()).await;
+        }
+
+        // Single fix pass over the cached errors; did_save re-lints afterwards
+        let edits = match self.document_manager.get(&uri) {
+            Some(doc) => code_actions::fix_edits(&doc.content, &doc.cached_errors),
+            None => vec![],
+        };
+        Ok(Some(edits))
+    }
+
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
         let uri = params.text_document.uri;
+        if config_file::is_config_file(&uri) {
+            self.validate_config_file(uri).await;
+            return;
+        }
 
         // Snapshot old heading IDs for cross-file re-lint
         let file_path = uri
@@ -590,22 +778,32 @@ impl LanguageServer for MkdlintLanguageServer {
     }
 
     async fn did_change_configuration(&self, _params: DidChangeConfigurationParams) {
-        // Fetch the current mkdlint.preset value from the client
-        let config_items = vec![ConfigurationItem {
-            scope_uri: None,
-            section: Some("mkdlint.preset".to_string()),
-        }];
+        // Fetch the current mkdlint.preset and mkdlint.fixOnSave values from the client
+        let config_items = vec![
+            ConfigurationItem {
+                scope_uri: None,
+                section: Some("mkdlint.preset".to_string()),
+            },
+            ConfigurationItem {
+                scope_uri: None,
+                section: Some("mkdlint.fixOnSave".to_string()),
+            },
+        ];
 
         let new_preset: Option<String> = match self.client.configuration(config_items).await {
-            Ok(values) => values
-                .into_iter()
-                .next()
-                .and_then(|v| v.as_str().map(|s| s.to_string())),
+            Ok(values) => {
+                let fix_on_save = values.get(1).and_then(|v| v.as_bool()).unwrap_or(false);
+                self.fix_on_save.store(fix_on_save, Ordering::Relaxed);
+                values
+                    .into_iter()
+                    .next()
+                    .and_then(|v| v.as_str().map(|s| s.to_string()))
+            }
             Err(e) => {
                 self.client
                     .log_message(
                         MessageType::WARNING,
-                        format!("Failed to fetch mkdlint.preset config: {e}"),
+                        format!("Failed to fetch mkdlint configuration: {e}"),
                     )
                     .await;
                 return;
@@ -645,6 +843,24 @@ impl LanguageServer for MkdlintLanguageServer {
             None => return Ok(None),
         };
 
+        // Rule IDs and top-level keys in a config file
+        if config_file::is_config_file(&uri) {
+            let hover = doc
+                .content
+                .lines()
+                .nth(position.line as usize)
+                .and_then(|line| extract_word(line, position.character as usize))
+                .and_then(config_file::hover)
+                .map(|value| Hover {
+                    contents: HoverContents::Markup(MarkupContent {
+                        kind: MarkupKind::Markdown,
+                        value,
+                    }),
+                    range: None,
+                });
+            return Ok(hover);
+        }
+
         // Find errors at the hover position
         let hover_line = position.line as usize + 1; // Convert 0-based to 1-based
         let matching_errors: Vec<_> = doc
@@ -653,6 +869,18 @@ impl LanguageServer for MkdlintLanguageServer {
             .filter(|e| e.line_number == hover_line)
             .collect();
 
+        // Resolved config, for showing each rule's effective options
+        let (config, source) = {
+            let manager = self.config_manager.read().unwrap();
+            (manager.discover_config(&uri), manager.config_source(&uri))
+        };
+        let options_line = |rule_id: &str| {
+            format!(
+                "\n**Configuration:** {}\n",
+                format_rule_options(config.as_ref(), rule_id, source.as_deref())
+            )
+        };
+
         let mut sections = Vec::new();
         for error in &matching_errors {
             let rule_id = error.rule_names.first().unwrap_or(&"unknown");
@@ -670,6 +898,8 @@ impl LanguageServer for MkdlintLanguageServer {
                 md.push_str(&format!("\n**Suggestion:** {}\n", suggestion));
             }
 
+            md.push_str(&options_line(rule_id));
+
             if error.fix_info.is_some() {
                 md.push_str("\n*Auto-fixable* \u{1f527}\n");
             }
@@ -702,6 +932,7 @@ impl LanguageServer for MkdlintLanguageServer {
                         let mut md = format!("### {} / {}\n\n", rule_id, rule_alias);
                         md.push_str(rule.description());
                         md.push('\n');
+                        md.push_str(&options_line(rule_id));
                         sections.push(md);
                     }
                 }
@@ -732,6 +963,12 @@ impl LanguageServer for MkdlintLanguageServer {
             None => return Ok(None),
         };
 
+        // Rule IDs and options at key positions in a config file
+        if config_file::is_config_file(&uri) {
+            let items = config_file::completions(&uri, &doc.content, position);
+            return Ok(Some(CompletionResponse::Array(items)));
+        }
+
         let lines: Vec<&str> = doc.content.lines().collect();
         let line = match lines.get(position.line as usize) {
             Some(l) => *l,
@@ -938,6 +1175,9 @@ impl LanguageServer for MkdlintLanguageServer {
         params: DocumentSymbolParams,
     ) -> Result<Option<DocumentSymbolResponse>> {
         let uri = params.text_document.uri;
+        if config_file::is_config_file(&uri) {
+            return Ok(None);
+        }
 
         let doc = match self.document_manager.get(&uri) {
             Some(doc) => doc,
@@ -952,10 +1192,6 @@ impl LanguageServer for MkdlintLanguageServer {
             .map(|h| (h.level, h.line as u32, h.text))
             .collect();
 
-        if headings.is_empty() {
-            return Ok(Some(DocumentSymbolResponse::Nested(vec![])));
-        }
-
         // Build nested DocumentSymbol tree using a stack-based approach
         fn build_tree(headings: &[(usize, u32, String)], total_lines: u32) -> Vec<DocumentSymbol> {
             if headings.is_empty() {
@@ -1052,7 +1288,12 @@ impl LanguageServer for MkdlintLanguageServer {
             build_level(headings, &end_lines, 0, headings.len(), top_level)
         }
 
-        let symbols = build_tree(&headings, total_lines);
+        let mut symbols = build_tree(&headings, total_lines);
+
+        // Link and footnote definitions, in a trailing container or under
+        // the headings whose sections hold them
+        crate::lsp::symbols::add_definitions(&mut symbols, &doc.content);
+
         Ok(Some(DocumentSymbolResponse::Nested(symbols)))
     }
 
@@ -1098,6 +1339,9 @@ impl LanguageServer for MkdlintLanguageServer {
 
     async fn folding_range(&self, params: FoldingRangeParams) -> Result<Option<Vec<FoldingRange>>> {
         let uri = params.text_document.uri;
+        if config_file::is_config_file(&uri) {
+            return Ok(None);
+        }
 
         let doc = match self.document_manager.get(&uri) {
             Some(doc) => doc,
@@ -1553,6 +1797,20 @@ impl LanguageServer for MkdlintLanguageServer {
                 continue;
             }
 
+            // ── MD040 fence language choices ───────────────────────────
+            if error.rule_names.first() == Some(&"MD040") {
+                let matched_diag = context_diagnostics.iter().find(|d| {
+                    d.range.start.line == error_line
+                        && d.code == Some(NumberOrString::String("MD040".to_string()))
+                });
+                actions.extend(code_actions::md040_code_actions(
+                    &uri,
+                    error,
+                    matched_diag.cloned(),
+                ));
+                continue;
+            }
+
             // Skip non-fixable errors (except MD051 handled above)
             if error.fix_info.is_none() {
                 continue;
@@ -1700,6 +1958,35 @@ impl LanguageServer for MkdlintLanguageServer {
 
                 Ok(None)
             }
+            "mkdlint.previewFixes" => {
+                let uri = match params
+                    .arguments
+                    .first()
+                    .and_then(|arg| serde_json::from_value::<Url>(arg.clone()).ok())
+                {
+                    Some(uri) => uri,
+                    None => {
+                        self.client
+                            .log_message(MessageType::ERROR, "No URI provided for previewFixes")
+                            .await;
+                        return Ok(None);
+                    }
+                };
+
+                // Ref guard drops at the semicolon, before any .await
+                let content = self
+                    .document_manager
+                    .get(&uri)
+                    .map(|doc| doc.content.clone());
+                let Some(content) = content else {
+                    self.client
+                        .log_message(MessageType::ERROR, format!("Document not found: {}", uri))
+                        .await;
+                    return Ok(None);
+                };
+
+                Ok(Some(self.preview_fixes(&uri, &content)))
+            }
             _ => {
                 self.client
                     .log_message(
@@ -1871,6 +2158,8 @@ impl Clone for MkdlintLanguageServer {
             config_manager: Arc::clone(&self.config_manager),
             debouncer: Arc::clone(&self.debouncer),
             heading_index: Arc::clone(&self.heading_index),
+            fix_on_save: Arc::clone(&self.fix_on_save),
+            scan_concurrency: Arc::clone(&self.scan_concurrency),
         }
     }
 }
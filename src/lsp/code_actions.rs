@@ -125,6 +125,47 @@ fn create_delete_line_edit(line_number: usize, total_lines: usize) -> TextEdit {
     }
 }
 
+/// Minimal edits that apply one pass of the fixes in `errors` to `content`.
+///
+/// The fixed text is diffed against the original line by line, so only
+/// changed lines are replaced and the editor keeps cursors and folds on the
+/// rest. Returns no edits when nothing changes.
+pub fn fix_edits(content: &str, errors: &[LintError]) -> Vec<TextEdit> {
+    let fixed = crate::apply_fixes(content, errors);
+    if fixed == content {
+        return Vec::new();
+    }
+
+    let old_lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let diff = similar::TextDiff::from_lines(content, fixed.as_str());
+    diff.ops()
+        .iter()
+        .filter(|op| op.tag() != similar::DiffTag::Equal)
+        .map(|op| TextEdit {
+            range: Range {
+                start: line_start(&old_lines, op.old_range().start),
+                end: line_start(&old_lines, op.old_range().end),
+            },
+            new_text: diff.new_slices()[op.new_range()].concat(),
+        })
+        .collect()
+}
+
+/// Position of the start of line `idx`, or the end of the document when
+/// `idx` is past an unterminated last line
+fn line_start(lines: &[&str], idx: usize) -> Position {
+    match lines.last() {
+        Some(last) if idx >= lines.len() && !last.ends_with('\n') => Position {
+            line: (lines.len() - 1) as u32,
+            character: last.encode_utf16().count() as u32,
+        },
+        _ => Position {
+            line: idx as u32,
+            character: 0,
+        },
+    }
+}
+
 /// Compute the Levenshtein edit distance between two strings.
 fn edit_distance(a: &str, b: &str) -> usize {
     let b_len = b.len();
@@ -231,6 +272,61 @@ pub fn md051_code_actions(
     actions
 }
 
+/// Languages offered by the MD040 quick fix after the configured default
+const FENCE_LANGUAGES: [&str; 6] = ["text", "bash", "json", "yaml", "python", "rust"];
+
+/// Build code actions for MD040 fences without a language.
+///
+/// The first action inserts the configured `default_language` (carried in
+/// the error's fix) and is marked preferred; the rest offer common
+/// languages, each inserted right after the opening fence.
+pub fn md040_code_actions(
+    uri: &Url,
+    error: &LintError,
+    diagnostic: Option<Diagnostic>,
+) -> Vec<CodeActionOrCommand> {
+    let Some(fix_info) = error.fix_info.as_ref() else {
+        return vec![];
+    };
+    let Some(edit_col) = fix_info.edit_column else {
+        return vec![];
+    };
+    let position = to_position(fix_info.line_number.unwrap_or(error.line_number), edit_col);
+    let default_language = fix_info.insert_text.as_deref().unwrap_or("text");
+
+    let mut languages = vec![default_language];
+    languages.extend(FENCE_LANGUAGES.iter().filter(|l| **l != default_language));
+
+    languages
+        .into_iter()
+        .enumerate()
+        .map(|(i, language)| {
+            let mut changes = HashMap::new();
+            changes.insert(
+                uri.clone(),
+                vec![TextEdit {
+                    range: Range {
+                        start: position,
+                        end: position,
+                    },
+                    new_text: language.to_string(),
+                }],
+            );
+            CodeActionOrCommand::CodeAction(CodeAction {
+                title: format!("MD040: Set code fence language to {}", language),
+                kind: Some(CodeActionKind::QUICKFIX),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }),
+                diagnostics: diagnostic.as_ref().map(|d| vec![d.clone()]),
+                is_preferred: Some(i == 0),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -249,6 +345,8 @@ mod tests {
             suggestion: Some("Apply fix".to_string()),
             severity: Severity::Error,
             fix_only: false,
+            line_text: None,
+            related: None,
         }
     }
 
@@ -411,6 +509,8 @@ mod tests {
             suggestion: None,
             severity: Severity::Error,
             fix_only: false,
+            line_text: None,
+            related: None,
         };
         let content = "# Introduction\n\n[link](#introductoin)\n";
         let headings = vec![
@@ -451,11 +551,68 @@ mod tests {
             suggestion: None,
             severity: Severity::Error,
             fix_only: false,
+            line_text: None,
+            related: None,
         };
         let actions = md051_code_actions(&uri, &error, "# Test\n", &["test".to_string()], None, 3);
         assert!(actions.is_empty(), "No context should produce no actions");
     }
 
+    /// Apply LSP edits (line-start positions only) to `content`
+    fn apply_edits(content: &str, edits: &[TextEdit]) -> String {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let offset = |pos: Position| -> usize {
+            let line_start: usize = lines[..pos.line as usize].iter().map(|l| l.len()).sum();
+            line_start + pos.character as usize
+        };
+        let mut out = content.to_string();
+        for edit in edits.iter().rev() {
+            let range = offset(edit.range.start)..offset(edit.range.end);
+            out.replace_range(range, &edit.new_text);
+        }
+        out
+    }
+
+    #[test]
+    fn test_fix_edits_only_touch_changed_lines() {
+        let content = "# Title\n\nSome text   \nMore\n\nEnd  \n";
+        let errors = crate::lint_sync(&crate::LintOptions::new().with_string("a.md", content))
+            .unwrap()
+            .get("a.md")
+            .unwrap()
+            .to_vec();
+
+        let edits = fix_edits(content, &errors);
+        assert_eq!(edits.len(), 2);
+        assert_eq!(edits[0].range.start, Position::new(2, 0));
+        assert_eq!(edits[0].range.end, Position::new(3, 0));
+        assert_eq!(edits[0].new_text, "Some text\n");
+        assert_eq!(
+            apply_edits(content, &edits),
+            crate::apply_fixes(content, &errors)
+        );
+    }
+
+    #[test]
+    fn test_fix_edits_unterminated_last_line() {
+        let content = "# Title\n\nText";
+        let errors = crate::lint_sync(&crate::LintOptions::new().with_string("a.md", content))
+            .unwrap()
+            .get("a.md")
+            .unwrap()
+            .to_vec();
+
+        let edits = fix_edits(content, &errors);
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].range.end, Position::new(2, 4));
+        assert_eq!(apply_edits(content, &edits), "# Title\n\nText\n");
+    }
+
+    #[test]
+    fn test_fix_edits_nothing_to_fix() {
+        assert!(fix_edits("# Title\n", &[]).is_empty());
+    }
+
     #[test]
     fn test_md051_code_actions_empty_headings() {
         let uri = Url::parse("file:///tmp/test.md").unwrap();
@@ -471,6 +628,8 @@ mod tests {
             suggestion: None,
             severity: Severity::Error,
             fix_only: false,
+            line_text: None,
+            related: None,
         };
         let actions = md051_code_actions(&uri, &error, "[link](#broken)\n", &[], None, 3);
         assert!(
@@ -478,4 +637,36 @@ mod tests {
             "Empty headings should produce no actions"
         );
     }
+
+    #[test]
+    fn test_md040_code_actions_bare_fence() {
+        use crate::types::RuleParams;
+
+        let content = "```\ncode\n```\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let mut config = HashMap::new();
+        config.insert("default_language".to_string(), serde_json::json!("rust"));
+        let rule = crate::rules::find_rule("MD040").unwrap();
+        let errors = rule.lint(&RuleParams::test(&lines, &config));
+        let uri = Url::parse("file:///test.md").unwrap();
+
+        let actions = md040_code_actions(&uri, &errors[0], None);
+        let titles: Vec<String> = actions
+            .iter()
+            .map(|a| match a {
+                CodeActionOrCommand::CodeAction(a) => a.title.clone(),
+                _ => panic!("Expected CodeAction"),
+            })
+            .collect();
+        assert_eq!(titles.len(), 6);
+        assert_eq!(titles[0], "MD040: Set code fence language to rust");
+        assert_eq!(titles[1], "MD040: Set code fence language to text");
+
+        let CodeActionOrCommand::CodeAction(preferred) = &actions[0] else {
+            panic!("Expected CodeAction");
+        };
+        assert_eq!(preferred.is_preferred, Some(true));
+        let edits = &preferred.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(apply_edits(content, edits), "```rust\ncode\n```\n");
+    }
 }
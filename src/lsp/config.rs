@@ -18,6 +18,8 @@ pub struct ConfigManager {
     pub(crate) workspace_roots: Vec<PathBuf>,
     /// Optional preset override from workspace settings (e.g. `mkdlint.preset`)
     pub(crate) preset_override: Option<String>,
+    /// Global user config that discovered configs are layered over
+    pub(crate) global_config: Option<Config>,
 }
 
 impl ConfigManager {
@@ -27,6 +29,7 @@ impl ConfigManager {
             cache: Arc::new(DashMap::new()),
             workspace_roots,
             preset_override: None,
+            global_config: None,
         }
     }
 
@@ -36,18 +39,50 @@ impl ConfigManager {
             cache: Arc::new(DashMap::new()),
             workspace_roots,
             preset_override: preset,
+            global_config: None,
         }
     }
 
+    /// Layer discovered configs over `global` (see [`Config::global`]),
+    /// which also applies on its own where no config is found
+    pub fn with_global_config(mut self, global: Option<Config>) -> Self {
+        self.global_config = global;
+        self
+    }
+
+    /// Pretend location of a document that is not a file (e.g. an unsaved
+    /// `untitled:Untitled-1`): its last path segment in the first workspace
+    /// root, so it still picks up the workspace config
+    ///
+    /// None for `file:` URIs and when there is no workspace root.
+    pub fn virtual_path(&self, uri: &Url) -> Option<PathBuf> {
+        if uri.scheme() == "file" {
+            return None;
+        }
+        let name = uri
+            .path()
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())?;
+        Some(self.workspace_roots.first()?.join(name))
+    }
+
+    /// On-disk path of a document, or its [`virtual_path`](Self::virtual_path)
+    fn document_path(&self, uri: &Url) -> Option<PathBuf> {
+        uri.to_file_path().ok().or_else(|| self.virtual_path(uri))
+    }
+
     /// Discover config for a file URI
     ///
     /// Walks up the directory tree from the file's directory to the workspace root,
+    /// starting from the [`virtual_path`](Self::virtual_path) for non-file URIs,
     /// looking for known config file names. Results are cached by directory.
     ///
-    /// If `preset_override` is set and the discovered config has no preset,
-    /// the override preset is applied.
+    /// The discovered config is layered over the global config, if any. If
+    /// `preset_override` is set and the result has no preset, the override
+    /// preset is applied.
     pub fn discover_config(&self, uri: &Url) -> Option<Config> {
-        let file_path = uri.to_file_path().ok()?;
+        let file_path = self.document_path(uri)?;
         let dir = file_path.parent()?;
 
         // Check cache first
@@ -58,6 +93,15 @@ impl ConfigManager {
         // Walk up directory tree to workspace root
         let mut config = self.find_config(dir);
 
+        // A config whose `extends` fails to load is kept as is, so linting
+        // reports the error
+        if let Some(global) = &self.global_config {
+            config = Some(match config {
+                Some(config) => config.resolve_over(global).unwrap_or(config),
+                None => global.clone(),
+            });
+        }
+
         // Apply preset override if no file-based preset is set
         if let Some(ref preset) = self.preset_override {
             let cfg = config.get_or_insert_with(Config::default);
@@ -73,8 +117,22 @@ impl ConfigManager {
         config
     }
 
+    /// Path of the config file that applies to a file URI, if any
+    ///
+    /// Uncached; intended for on-demand lookups such as hover.
+    pub fn config_source(&self, uri: &Url) -> Option<PathBuf> {
+        let file_path = self.document_path(uri)?;
+        self.find_config_file(file_path.parent()?)
+            .map(|(path, _)| path)
+    }
+
     /// Walk up directory tree looking for config files
     fn find_config(&self, start_dir: &Path) -> Option<Config> {
+        self.find_config_file(start_dir).map(|(_, config)| config)
+    }
+
+    /// Walk up directory tree looking for config files, returning the path too
+    fn find_config_file(&self, start_dir: &Path) -> Option<(PathBuf, Config)> {
         let mut current = start_dir;
 
         loop {
@@ -85,12 +143,13 @@ impl ConfigManager {
                 ".markdownlint.yaml",
                 ".markdownlint.yml",
                 ".markdownlintrc",
+                "package.json",
             ] {
                 let config_path = current.join(name);
                 if config_path.exists() {
                     // Try to parse the config
                     if let Ok(config) = Config::from_file(&config_path) {
-                        return Some(config);
+                        return Some((config_path, config));
                     }
                     // If parsing failed, continue looking for other config files
                 }
@@ -140,6 +199,44 @@ impl ConfigManager {
     }
 }
 
+/// One-line summary of a rule's resolved options for hover text
+///
+/// Options are listed in key order, e.g.
+/// `line_length: 120, code_blocks: false, source: .markdownlint.json`.
+/// Collapses to `default options` when the rule is not configured.
+pub(crate) fn format_rule_options(
+    config: Option<&Config>,
+    rule_id: &str,
+    source: Option<&Path>,
+) -> String {
+    use crate::config::RuleConfig;
+
+    let mut parts = match config.and_then(|c| c.get_rule_config(rule_id)) {
+        None => return "default options".to_string(),
+        Some(RuleConfig::Enabled(true)) => vec!["enabled".to_string()],
+        Some(RuleConfig::Enabled(false)) => vec!["disabled".to_string()],
+        Some(RuleConfig::Severity(severity)) if severity.eq_ignore_ascii_case("off") => {
+            vec!["disabled".to_string()]
+        }
+        Some(RuleConfig::Severity(severity)) => vec![format!("severity: {}", severity)],
+        Some(RuleConfig::Options(opts)) => {
+            let mut keys: Vec<_> = opts.keys().collect();
+            keys.sort();
+            keys.into_iter()
+                .map(|key| match &opts[key] {
+                    serde_json::Value::String(s) => format!("{}: {}", key, s),
+                    value => format!("{}: {}", key, value),
+                })
+                .collect()
+        }
+    };
+
+    if let Some(name) = source.and_then(|p| p.file_name()) {
+        parts.push(format!("source: {}", name.to_string_lossy()));
+    }
+    parts.join(", ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -245,6 +342,31 @@ mod tests {
         assert_eq!(manager.cache_size(), 1);
     }
 
+    #[test]
+    fn test_discovered_config_layers_over_global_config() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let project = root.join("project");
+        let plain = root.join("plain");
+        fs::create_dir_all(&project).unwrap();
+        fs::create_dir_all(&plain).unwrap();
+        fs::write(project.join(".markdownlint.json"), r#"{"MD013": true}"#).unwrap();
+
+        let global: Config = serde_json::from_str(r#"{"MD013": false, "MD033": false}"#).unwrap();
+        let manager = ConfigManager::new(vec![root.to_path_buf()]).with_global_config(Some(global));
+
+        // The project config wins where both set a rule
+        let url = Url::from_file_path(project.join("doc.md")).unwrap();
+        let config = manager.discover_config(&url).unwrap();
+        assert!(config.is_rule_enabled("MD013"));
+        assert!(!config.is_rule_enabled("MD033"));
+
+        // Without a project config the global config applies on its own
+        let url = Url::from_file_path(plain.join("doc.md")).unwrap();
+        let config = manager.discover_config(&url).unwrap();
+        assert!(!config.is_rule_enabled("MD013"));
+    }
+
     #[test]
     fn test_cache_invalidation() {
         let temp = TempDir::new().unwrap();
@@ -382,4 +504,65 @@ mod tests {
 
         assert!(config.is_some(), "Should discover config from URL");
     }
+
+    #[test]
+    fn test_untitled_document_uses_workspace_config() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(root.join(".markdownlint.json"), r#"{"MD013": false}"#).unwrap();
+
+        let manager = ConfigManager::new(vec![root.to_path_buf()]);
+        let uri = Url::parse("untitled:Untitled-1").unwrap();
+        assert_eq!(manager.virtual_path(&uri), Some(root.join("Untitled-1")));
+        assert!(
+            !manager
+                .discover_config(&uri)
+                .unwrap()
+                .is_rule_enabled("MD013")
+        );
+
+        // Files have a real path; without a workspace there is no fallback
+        let file = Url::from_file_path(root.join("a.md")).unwrap();
+        assert_eq!(manager.virtual_path(&file), None);
+        assert_eq!(ConfigManager::new(vec![]).virtual_path(&uri), None);
+    }
+
+    #[test]
+    fn test_format_rule_options() {
+        let config: Config = serde_json::from_str(
+            r#"{"MD013": {"line_length": 120, "code_blocks": false}, "MD009": "warning", "MD033": false}"#,
+        )
+        .unwrap();
+        let source = Path::new("/repo/.markdownlint.json");
+
+        assert_eq!(
+            format_rule_options(Some(&config), "MD013", Some(source)),
+            "code_blocks: false, line_length: 120, source: .markdownlint.json"
+        );
+        assert_eq!(
+            format_rule_options(Some(&config), "MD009", None),
+            "severity: warning"
+        );
+        assert_eq!(
+            format_rule_options(Some(&config), "MD033", None),
+            "disabled"
+        );
+        assert_eq!(
+            format_rule_options(Some(&config), "MD001", Some(source)),
+            "default options"
+        );
+        assert_eq!(format_rule_options(None, "MD013", None), "default options");
+    }
+
+    #[test]
+    fn test_config_source() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let config_path = root.join(".markdownlint.json");
+        fs::write(&config_path, r#"{"MD013": false}"#).unwrap();
+
+        let manager = ConfigManager::new(vec![root.to_path_buf()]);
+        let uri = Url::from_file_path(root.join("README.md")).unwrap();
+        assert_eq!(manager.config_source(&uri), Some(config_path));
+    }
 }
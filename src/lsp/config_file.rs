@@ -0,0 +1,734 @@
+//! Editor support for mkdlint config files themselves
+//!
+//! When a `.markdownlint.*` file is open, the server validates it with
+//! [`Config::validate`] instead of linting it as Markdown, completes rule IDs
+//! and options, and describes keys on hover. serde doesn't report where keys
+//! are, so a light per-format scan locates top-level keys and the entries
+//! directly under them.
+
+use crate::config::{COMMON_RULE_OPTIONS, Config};
+use tower_lsp::lsp_types::{
+    CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, Position, Range, Url,
+};
+
+/// File names the server treats as mkdlint config files
+const CONFIG_FILE_NAMES: [&str; 6] = [
+    ".markdownlint.json",
+    ".markdownlint.jsonc",
+    ".markdownlint.yaml",
+    ".markdownlint.yml",
+    ".markdownlint.toml",
+    ".markdownlintrc",
+];
+
+/// Top-level keys other than rule IDs, with hover descriptions
+const TOP_LEVEL_KEYS: [(&str, &str); 8] = [
+    (
+        "default",
+        "Default enabled/disabled state for all rules not explicitly configured",
+    ),
+    ("extends", "Path to another config file to extend"),
+    (
+        "preset",
+        "Named preset to apply (e.g. `kramdown`, `github`)",
+    ),
+    (
+        "budgets",
+        "Most violations allowed per rule, enforced with `--enforce-budgets`",
+    ),
+    (
+        "skip_generated",
+        "Skip files with a generated-file marker (`markers`) in their first `search_lines` lines",
+    ),
+    (
+        "foreign_directives",
+        "Other tools' comment directives, such as `prettier-ignore`, mapped to `ignore`, `disable-next-line`, `disable` or `enable`",
+    ),
+    (
+        "no_fix",
+        "Rules whose violations are reported but never auto-fixed",
+    ),
+    (
+        "math",
+        "Recognize `$...$` and `$$...$$` as math, which emphasis and whitespace rules skip (default `true`)",
+    ),
+];
+
+/// Whether `uri` names an mkdlint config file
+pub(crate) fn is_config_file(uri: &Url) -> bool {
+    uri.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .is_some_and(|name| CONFIG_FILE_NAMES.contains(&name))
+}
+
+/// Syntax of a config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl Format {
+    /// Format from the file name; `.markdownlintrc` may hold JSON or YAML
+    fn detect(uri: &Url, content: &str) -> Self {
+        let path = uri.path();
+        if path.ends_with(".json") || path.ends_with(".jsonc") {
+            Format::Json
+        } else if path.ends_with(".toml") {
+            Format::Toml
+        } else if path.ends_with(".markdownlintrc") && content.trim_start().starts_with('{') {
+            Format::Json
+        } else {
+            Format::Yaml
+        }
+    }
+}
+
+/// A key and where it is written (0-based line, byte columns)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct KeySpan {
+    name: String,
+    line: u32,
+    start: u32,
+    end: u32,
+    /// Index of the enclosing top-level key, for entries one level down
+    parent: Option<usize>,
+}
+
+/// Top-level keys and the entries directly under them, in document order
+fn scan_keys(format: Format, content: &str) -> Vec<KeySpan> {
+    match format {
+        Format::Json => scan_json(content, None).keys,
+        Format::Yaml => scan_yaml(content),
+        Format::Toml => scan_toml(content),
+    }
+}
+
+/// Result of scanning JSON up to an optional limit
+struct JsonScan {
+    keys: Vec<KeySpan>,
+    /// Open `{`/`[` at the limit
+    stack: Vec<u8>,
+    /// Last significant byte before the limit, outside strings and comments
+    last: Option<u8>,
+    /// Whether the limit falls inside a string
+    in_string: bool,
+}
+
+/// Scan JSON (with `//` and `/* */` comments allowed) for object keys,
+/// stopping at `limit` when given
+fn scan_json(content: &str, limit: Option<Position>) -> JsonScan {
+    let mut scan = JsonScan {
+        keys: Vec::new(),
+        stack: Vec::new(),
+        last: None,
+        in_string: false,
+    };
+    let bytes = content.as_bytes();
+    let (mut line, mut col) = (0u32, 0u32);
+    // Most recent top-level key, and a string that may turn out to be a key
+    let mut top: Option<usize> = None;
+    let mut pending: Option<KeySpan> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if limit.is_some_and(|p| (line, col) >= (p.line, p.character)) {
+            return scan;
+        }
+        let b = bytes[i];
+        match b {
+            b'"' => {
+                // Read the whole string, tracking its end for a possible key
+                let (start_line, start_col) = (line, col);
+                let mut j = i + 1;
+                let mut closed = false;
+                while j <= bytes.len() {
+                    if limit.is_some_and(|p| (line, col + (j - i) as u32) >= (p.line, p.character))
+                    {
+                        scan.in_string = true;
+                        return scan;
+                    }
+                    if j == bytes.len() || bytes[j] == b'\n' {
+                        break;
+                    }
+                    match bytes[j] {
+                        b'\\' => j += 1,
+                        b'"' => {
+                            closed = true;
+                            break;
+                        }
+                        _ => {}
+                    }
+                    j += 1;
+                }
+                let end = j.min(bytes.len());
+                col += (end - i) as u32 + u32::from(closed);
+                if closed && scan.stack.last() == Some(&b'{') {
+                    pending = Some(KeySpan {
+                        name: content[i + 1..end].to_string(),
+                        line: start_line,
+                        start: start_col + 1,
+                        end: start_col + 1 + (end - i - 1) as u32,
+                        parent: None,
+                    });
+                }
+                scan.last = Some(b'"');
+                i = end + usize::from(closed);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                    col += 1;
+                }
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                col += 2;
+                while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    if bytes[i] == b'\n' {
+                        line += 1;
+                        col = 0;
+                    } else {
+                        col += 1;
+                    }
+                    i += 1;
+                }
+                i += 2;
+                col += 2;
+                continue;
+            }
+            b'\n' => {
+                line += 1;
+                col = 0;
+                i += 1;
+                continue;
+            }
+            b':' => {
+                if let Some(mut key) = pending.take() {
+                    let depth = scan.stack.iter().filter(|&&c| c == b'{').count();
+                    if depth == 1 {
+                        top = Some(scan.keys.len());
+                        scan.keys.push(key);
+                    } else if depth == 2 && scan.stack.len() == 2 {
+                        key.parent = top;
+                        scan.keys.push(key);
+                    }
+                }
+            }
+            b'{' | b'[' => scan.stack.push(b),
+            b'}' | b']' => {
+                scan.stack.pop();
+            }
+            _ => {}
+        }
+        if !b.is_ascii_whitespace() {
+            pending = pending.filter(|_| b == b':');
+            scan.last = Some(b);
+        }
+        i += 1;
+        col += 1;
+    }
+    scan
+}
+
+/// The key on a YAML line, with its indentation and byte range
+fn yaml_key(line: &str) -> Option<(usize, usize, usize)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let rest = &line[indent..];
+    if rest.is_empty() || rest.starts_with(['#', '-']) {
+        return None;
+    }
+    let (start, end, after) = match rest.as_bytes()[0] {
+        quote @ (b'"' | b'\'') => {
+            let close = rest[1..].find(quote as char)? + 1;
+            (indent + 1, indent + close, &rest[close + 1..])
+        }
+        _ => {
+            let colon = rest.find(':')?;
+            let key = rest[..colon].trim_end();
+            (indent, indent + key.len(), &rest[colon..])
+        }
+    };
+    let after = after.trim_start_matches(' ');
+    let value = after.strip_prefix(':')?;
+    (value.is_empty() || value.starts_with([' ', '\t', '\r'])).then_some((indent, start, end))
+}
+
+/// Scan YAML block mappings for keys at the top level and one level down
+fn scan_yaml(content: &str) -> Vec<KeySpan> {
+    let mut keys = Vec::new();
+    let mut top: Option<usize> = None;
+    // Indentation of the first entry under the current top-level key
+    let mut child_indent: Option<usize> = None;
+
+    for (idx, line) in content.lines().enumerate() {
+        let Some((indent, start, end)) = yaml_key(line) else {
+            continue;
+        };
+        let parent = if indent == 0 {
+            top = Some(keys.len());
+            child_indent = None;
+            None
+        } else if top.is_some() && *child_indent.get_or_insert(indent) == indent {
+            top
+        } else {
+            continue;
+        };
+        keys.push(KeySpan {
+            name: line[start..end].to_string(),
+            line: idx as u32,
+            start: start as u32,
+            end: end as u32,
+            parent,
+        });
+    }
+    keys
+}
+
+/// The bare or quoted key before `=` in a TOML key/value, with its byte range
+fn toml_key(text: &str, offset: usize) -> Option<(String, usize, usize)> {
+    let (key, _) = text.split_once('=')?;
+    let trimmed = key.trim();
+    let lead = key.len() - key.trim_start().len();
+    let name = trimmed.trim_matches('"');
+    let quote = usize::from(trimmed.starts_with('"'));
+    let start = offset + lead + quote;
+    (!name.is_empty()).then(|| (name.to_string(), start, start + name.len()))
+}
+
+/// Scan TOML for top-level keys, `[table]` headers, and keys within tables
+/// or inline tables
+fn scan_toml(content: &str) -> Vec<KeySpan> {
+    let mut keys = Vec::new();
+    let mut table: Option<usize> = None;
+
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let lead = line.len() - trimmed.len();
+        let span = |(name, start, end): (String, usize, usize), parent| KeySpan {
+            name,
+            line: idx as u32,
+            start: start as u32,
+            end: end as u32,
+            parent,
+        };
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let Some(close) = header.find(']') else {
+                continue;
+            };
+            let inner = &header[..close];
+            let name = inner.trim().trim_matches('"');
+            let start = lead + 1 + inner.find(name).unwrap_or(0);
+            table = Some(keys.len());
+            keys.push(span((name.to_string(), start, start + name.len()), None));
+            continue;
+        }
+        let Some(key) = toml_key(trimmed, lead) else {
+            continue;
+        };
+        let parent = table;
+        let index = keys.len();
+        keys.push(span(key, parent));
+        // `MD013 = { line_length = 100 }` holds options on the same line
+        if parent.is_none()
+            && let Some(open) = line.find('{')
+        {
+            let close = line.rfind('}').unwrap_or(line.len());
+            let mut offset = open + 1;
+            for entry in line[open + 1..close.max(open + 1)].split(',') {
+                if let Some(option) = toml_key(entry, offset) {
+                    keys.push(span(option, Some(index)));
+                }
+                offset += entry.len() + 1;
+            }
+        }
+    }
+    keys
+}
+
+/// Line (0-based) of a parse error, where the parser reports one
+fn parse(format: Format, content: &str) -> Result<Config, (u32, String)> {
+    match format {
+        Format::Json => serde_json::from_str(content)
+            .map_err(|e| (e.line().saturating_sub(1) as u32, e.to_string())),
+        Format::Yaml => serde_yaml_ng::from_str(content).map_err(|e| {
+            let line = e.location().map_or(0, |l| l.line().saturating_sub(1));
+            (line as u32, e.to_string())
+        }),
+        Format::Toml => toml::from_str(content).map_err(|e| {
+            let offset = e.span().map_or(0, |span| span.start);
+            let line = content[..offset.min(content.len())].matches('\n').count();
+            (line as u32, e.message().to_string())
+        }),
+    }
+}
+
+/// Range covering a whole line, for problems without a key to point at
+fn line_range(content: &str, line: u32) -> Range {
+    let len = content.lines().nth(line as usize).map_or(0, str::len);
+    Range {
+        start: Position { line, character: 0 },
+        end: Position {
+            line,
+            character: len as u32,
+        },
+    }
+}
+
+/// Diagnostics for a config file: a parse error, or [`Config::validate`]
+/// issues placed on the offending key
+pub(crate) fn diagnostics(uri: &Url, content: &str) -> Vec<Diagnostic> {
+    let format = Format::detect(uri, content);
+    let diagnostic = |range, severity, message| Diagnostic {
+        range,
+        severity: Some(severity),
+        source: Some("mkdlint".to_string()),
+        message,
+        ..Default::default()
+    };
+
+    let config = match parse(format, content) {
+        Ok(config) => config,
+        Err((line, message)) => {
+            return vec![diagnostic(
+                line_range(content, line),
+                DiagnosticSeverity::ERROR,
+                format!("Invalid config: {}", message),
+            )];
+        }
+    };
+
+    let keys = scan_keys(format, content);
+    config
+        .validate()
+        .into_iter()
+        .map(|issue| {
+            let top = keys
+                .iter()
+                .position(|k| k.parent.is_none() && k.name == issue.key);
+            let span = issue
+                .option
+                .as_ref()
+                .and_then(|option| {
+                    keys.iter()
+                        .find(|k| k.parent.is_some() && k.parent == top && &k.name == option)
+                })
+                .or_else(|| top.map(|i| &keys[i]));
+            let range = match span {
+                Some(span) => Range {
+                    start: Position {
+                        line: span.line,
+                        character: span.start,
+                    },
+                    end: Position {
+                        line: span.line,
+                        character: span.end,
+                    },
+                },
+                None => line_range(content, 0),
+            };
+            diagnostic(range, DiagnosticSeverity::WARNING, issue.message)
+        })
+        .collect()
+}
+
+/// Where the cursor is, for completion
+#[derive(Debug, PartialEq, Eq)]
+enum KeyContext {
+    /// Typing a top-level key
+    TopLevel,
+    /// Typing an entry under the named top-level key
+    Under(String),
+}
+
+/// The key the cursor is typing, if any
+fn key_context(format: Format, content: &str, position: Position) -> Option<KeyContext> {
+    let line = content.lines().nth(position.line as usize).unwrap_or("");
+    let prefix = &line[..(position.character as usize).min(line.len())];
+    let keys = scan_keys(format, content);
+    // Closest top-level key above the cursor
+    let enclosing = || {
+        keys.iter()
+            .rev()
+            .find(|k| k.parent.is_none() && k.line < position.line)
+            .map(|k| KeyContext::Under(k.name.clone()))
+    };
+
+    match format {
+        Format::Json => {
+            let scan = scan_json(content, Some(position));
+            // A typed-but-unclosed key ends the scan inside its string
+            let after_separator = matches!(scan.last, Some(b'{' | b','))
+                || (scan.in_string && prefix.trim_end().ends_with(['{', ',', '"']));
+            if scan.stack.last() != Some(&b'{') || !after_separator {
+                return None;
+            }
+            match scan.stack.len() {
+                1 => Some(KeyContext::TopLevel),
+                2 => scan
+                    .keys
+                    .iter()
+                    .rev()
+                    .find(|k| k.parent.is_none())
+                    .map(|k| KeyContext::Under(k.name.clone())),
+                _ => None,
+            }
+        }
+        Format::Yaml => {
+            if prefix.contains(':') || prefix.trim_start().starts_with(['#', '-']) {
+                return None;
+            }
+            if prefix.starts_with(' ') {
+                enclosing()
+            } else {
+                Some(KeyContext::TopLevel)
+            }
+        }
+        Format::Toml => {
+            let trimmed = prefix.trim_start();
+            if trimmed.starts_with('[') {
+                return Some(KeyContext::TopLevel);
+            }
+            if trimmed.contains('=') || trimmed.starts_with('#') {
+                return None;
+            }
+            // Keys after a `[table]` header belong to that table
+            let in_table = content
+                .lines()
+                .take(position.line as usize)
+                .any(|l| l.trim_start().starts_with('['));
+            if in_table {
+                enclosing()
+            } else {
+                Some(KeyContext::TopLevel)
+            }
+        }
+    }
+}
+
+/// Completion items for rule IDs, matched by alias as well
+fn rule_items() -> impl Iterator<Item = CompletionItem> {
+    crate::rules::get_rules().iter().map(|rule| {
+        let names = rule.names();
+        CompletionItem {
+            label: names[0].to_string(),
+            kind: Some(CompletionItemKind::PROPERTY),
+            detail: Some(format!(
+                "{} — {}",
+                names[1..].join(", "),
+                rule.description()
+            )),
+            filter_text: Some(names.join(" ")),
+            ..Default::default()
+        }
+    })
+}
+
+/// Completion items for the key the cursor is typing in a config file
+pub(crate) fn completions(uri: &Url, content: &str, position: Position) -> Vec<CompletionItem> {
+    let format = Format::detect(uri, content);
+    let property = |label: &str, detail: String| CompletionItem {
+        label: label.to_string(),
+        kind: Some(CompletionItemKind::PROPERTY),
+        detail: Some(detail),
+        ..Default::default()
+    };
+
+    match key_context(format, content, position) {
+        None => Vec::new(),
+        Some(KeyContext::TopLevel) => TOP_LEVEL_KEYS
+            .iter()
+            .map(|(key, description)| property(key, description.to_string()))
+            .chain(rule_items())
+            .collect(),
+        Some(KeyContext::Under(key)) if key == "budgets" => rule_items().collect(),
+        Some(KeyContext::Under(key)) if key == "skip_generated" => vec![
+            property("markers", "Text that marks a file as generated".to_string()),
+            property("search_lines", "Lines from the top to search".to_string()),
+        ],
+        Some(KeyContext::Under(key)) => {
+            let Some(rule) = crate::rules::find_rule(&key) else {
+                return Vec::new();
+            };
+            let id = rule.names()[0];
+            crate::rules::rule_options(id)
+                .into_iter()
+                .map(|option| property(option, format!("{} option", id)))
+                .chain(
+                    COMMON_RULE_OPTIONS
+                        .iter()
+                        .map(|option| property(option, "Option for every rule".to_string())),
+                )
+                .collect()
+        }
+    }
+}
+
+/// Hover text for a rule name or top-level key in a config file
+pub(crate) fn hover(word: &str) -> Option<String> {
+    if let Some((key, description)) = TOP_LEVEL_KEYS.iter().find(|(key, _)| *key == word) {
+        return Some(format!("### {}\n\n{}\n", key, description));
+    }
+    let rule = crate::rules::find_rule(word)?;
+    let names = rule.names();
+    let mut md = format!(
+        "### {} / {}\n\n{}\n",
+        names[0],
+        names.get(1).unwrap_or(&names[0]),
+        rule.description()
+    );
+    let options = crate::rules::rule_options(names[0]);
+    if !options.is_empty() {
+        md.push_str(&format!("\n**Options:** {}\n", options.join(", ")));
+    }
+    if let Some(url) = rule.information() {
+        md.push_str(&format!("\n[Documentation]({})\n", url));
+    }
+    Some(md)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn uri(name: &str) -> Url {
+        Url::parse(&format!("file:///project/{}", name)).unwrap()
+    }
+
+    fn names(keys: &[KeySpan]) -> Vec<(&str, Option<usize>)> {
+        keys.iter().map(|k| (k.name.as_str(), k.parent)).collect()
+    }
+
+    #[test]
+    fn test_is_config_file() {
+        assert!(is_config_file(&uri(".markdownlint.yaml")));
+        assert!(is_config_file(&uri("docs/.markdownlint.json")));
+        assert!(!is_config_file(&uri("README.md")));
+        assert!(!is_config_file(&uri("markdownlint.yaml")));
+    }
+
+    #[test]
+    fn test_scan_keys_per_format() {
+        let json = "{\n  // comment\n  \"MD013\": {\"line_length\": 100, \"x\": [1, {\"deep\": 2}]},\n  \"MD009\": false\n}\n";
+        assert_eq!(
+            names(&scan_keys(Format::Json, json)),
+            [
+                ("MD013", None),
+                ("line_length", Some(0)),
+                ("x", Some(0)),
+                ("MD009", None)
+            ]
+        );
+
+        let yaml = "default: true\nMD013:\n  line_length: 100\n  code_blocks:\n    - nested\n\"MD009\": false\n";
+        let keys = scan_keys(Format::Yaml, yaml);
+        assert_eq!(
+            names(&keys),
+            [
+                ("default", None),
+                ("MD013", None),
+                ("line_length", Some(1)),
+                ("code_blocks", Some(1)),
+                ("MD009", None)
+            ]
+        );
+        assert_eq!((keys[4].line, keys[4].start, keys[4].end), (5, 1, 6));
+
+        let toml = "default = true\nMD009 = { br_spaces = 2 }\n\n[MD013]\nline_length = 100\n";
+        assert_eq!(
+            names(&scan_keys(Format::Toml, toml)),
+            [
+                ("default", None),
+                ("MD009", None),
+                ("br_spaces", Some(1)),
+                ("MD013", None),
+                ("line_length", Some(3))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_point_at_keys() {
+        let yaml = "MD013:\n  line_lenght: 100\nMD999: false\n";
+        let found = diagnostics(&uri(".markdownlint.yaml"), yaml);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].range.start, Position::new(1, 2));
+        assert_eq!(found[0].range.end, Position::new(1, 13));
+        assert!(found[0].message.contains("Unknown option 'line_lenght'"));
+        assert_eq!(found[1].range.start, Position::new(2, 0));
+        assert_eq!(found[1].message, "Unknown rule 'MD999'");
+
+        let json = "{\n  \"budgets\": {\"MD013\": 1, \"MD000\": 2}\n}\n";
+        let found = diagnostics(&uri(".markdownlint.json"), json);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].range.start, Position::new(1, 27));
+    }
+
+    #[test]
+    fn test_diagnostics_parse_error() {
+        let found = diagnostics(&uri(".markdownlint.json"), "{\n  \"MD013\": ,\n}\n");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(found[0].range.start.line, 1);
+    }
+
+    #[test]
+    fn test_completions() {
+        let labels = |items: Vec<CompletionItem>| -> Vec<String> {
+            items.into_iter().map(|item| item.label).collect()
+        };
+
+        let yaml = "MD013:\n  \nM";
+        let top = labels(completions(
+            &uri(".markdownlint.yaml"),
+            yaml,
+            Position::new(2, 1),
+        ));
+        assert!(top.contains(&"MD001".to_string()));
+        assert!(top.contains(&"default".to_string()));
+        let nested = labels(completions(
+            &uri(".markdownlint.yaml"),
+            yaml,
+            Position::new(1, 2),
+        ));
+        assert_eq!(nested[0], "line_length");
+        assert!(nested.contains(&"severity".to_string()));
+
+        let json = "{\n  \"MD013\": {\"\"},\n  \"\n}";
+        let nested = labels(completions(
+            &uri(".markdownlint.json"),
+            json,
+            Position::new(1, 13),
+        ));
+        assert_eq!(nested[0], "line_length");
+        let top = labels(completions(
+            &uri(".markdownlint.json"),
+            json,
+            Position::new(2, 3),
+        ));
+        assert!(top.contains(&"MD013".to_string()));
+        // Values are not keys
+        assert!(completions(&uri(".markdownlint.json"), json, Position::new(1, 11)).is_empty());
+
+        let item = completions(&uri(".markdownlint.yaml"), "", Position::new(0, 0))
+            .into_iter()
+            .find(|item| item.label == "MD013")
+            .unwrap();
+        assert_eq!(item.filter_text.as_deref(), Some("MD013 line-length"));
+    }
+
+    #[test]
+    fn test_hover() {
+        let md = hover("line-length").unwrap();
+        assert!(md.starts_with("### MD013 / line-length"));
+        assert!(md.contains("**Options:** line_length"));
+        assert!(hover("preset").unwrap().contains("Named preset"));
+        assert!(hover("nonsense").is_none());
+    }
+}
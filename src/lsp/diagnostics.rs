@@ -1,12 +1,15 @@
 //! Convert mkdlint errors to LSP diagnostics
 
 use crate::types::{LintError, Severity};
-use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString,
+    Position, Range, Url,
+};
 
 use super::utils::{to_position, to_range};
 
-/// Convert a LintError to an LSP Diagnostic
-pub fn lint_error_to_diagnostic(error: &LintError, lines: &[String]) -> Diagnostic {
+/// Convert a LintError to an LSP Diagnostic for the document at `uri`
+pub fn lint_error_to_diagnostic(error: &LintError, lines: &[String], uri: &Url) -> Diagnostic {
     let range = calculate_range(error, lines);
     let severity = severity_to_lsp(error.severity);
     let message = format_message(error);
@@ -15,6 +18,15 @@ pub fn lint_error_to_diagnostic(error: &LintError, lines: &[String]) -> Diagnost
         .rule_names
         .first()
         .map(|name| NumberOrString::String(name.to_string()));
+    let related_information = error.related.as_ref().map(|related| {
+        vec![DiagnosticRelatedInformation {
+            location: Location {
+                uri: uri.clone(),
+                range: line_range(related.line_number, lines),
+            },
+            message: related.message.clone(),
+        }]
+    });
 
     Diagnostic {
         range,
@@ -22,6 +34,7 @@ pub fn lint_error_to_diagnostic(error: &LintError, lines: &[String]) -> Diagnost
         code,
         source,
         message,
+        related_information,
         ..Default::default()
     }
 }
@@ -33,21 +46,28 @@ fn calculate_range(error: &LintError, lines: &[String]) -> Range {
         to_range(error.line_number, start_col, length)
     } else {
         // Fall back to highlighting the entire line
-        let line_idx = error.line_number.saturating_sub(1);
-        let line_content = lines.get(line_idx).map(|s| s.as_str()).unwrap_or("");
-
-        // Trim trailing newline/whitespace for better UX
-        let trimmed_len = line_content.trim_end().len();
-        let start = to_position(error.line_number, 1);
-        let end = Position {
-            line: start.line,
-            character: trimmed_len as u32,
-        };
-
-        Range { start, end }
+        line_range(error.line_number, lines)
     }
 }
 
+/// Range covering a whole line (1-based) up to its trailing whitespace
+fn line_range(line_number: usize, lines: &[String]) -> Range {
+    let line_content = lines
+        .get(line_number.saturating_sub(1))
+        .map(|s| s.as_str())
+        .unwrap_or("");
+
+    // Trim trailing newline/whitespace for better UX
+    let trimmed_len = line_content.trim_end().len();
+    let start = to_position(line_number, 1);
+    let end = Position {
+        line: start.line,
+        character: trimmed_len as u32,
+    };
+
+    Range { start, end }
+}
+
 /// Convert mkdlint Severity to LSP DiagnosticSeverity
 fn severity_to_lsp(severity: Severity) -> DiagnosticSeverity {
     match severity {
@@ -92,9 +112,15 @@ mod tests {
             suggestion: Some("Fix this issue".to_string()),
             severity,
             fix_only: false,
+            line_text: None,
+            related: None,
         }
     }
 
+    fn test_uri() -> Url {
+        Url::parse("file:///test.md").unwrap()
+    }
+
     #[test]
     fn test_severity_conversion() {
         assert_eq!(severity_to_lsp(Severity::Error), DiagnosticSeverity::ERROR);
@@ -108,7 +134,7 @@ mod tests {
     fn test_diagnostic_with_error_range() {
         let error = create_test_error(1, Some((5, 10)), Severity::Error);
         let lines = vec!["# Test heading\n".to_string()];
-        let diagnostic = lint_error_to_diagnostic(&error, &lines);
+        let diagnostic = lint_error_to_diagnostic(&error, &lines, &test_uri());
 
         assert_eq!(diagnostic.range.start, Position::new(0, 4));
         assert_eq!(diagnostic.range.end, Position::new(0, 14));
@@ -120,7 +146,7 @@ mod tests {
     fn test_diagnostic_without_error_range() {
         let error = create_test_error(1, None, Severity::Warning);
         let lines = vec!["# Test heading\n".to_string()];
-        let diagnostic = lint_error_to_diagnostic(&error, &lines);
+        let diagnostic = lint_error_to_diagnostic(&error, &lines, &test_uri());
 
         assert_eq!(diagnostic.range.start, Position::new(0, 0));
         // Should use trimmed line length
@@ -147,11 +173,29 @@ mod tests {
     fn test_diagnostic_code() {
         let error = create_test_error(1, None, Severity::Error);
         let lines = vec!["# Test\n".to_string()];
-        let diagnostic = lint_error_to_diagnostic(&error, &lines);
+        let diagnostic = lint_error_to_diagnostic(&error, &lines, &test_uri());
 
         assert_eq!(
             diagnostic.code,
             Some(lsp_types::NumberOrString::String("MD001".to_string()))
         );
     }
+
+    #[test]
+    fn test_diagnostic_related_line() {
+        let mut error = create_test_error(3, None, Severity::Error);
+        error.related = Some(crate::types::RelatedLine {
+            line_number: 1,
+            message: "First top-level heading".to_string(),
+        });
+        let lines = vec!["# Title".to_string(), String::new(), "# Again".to_string()];
+        let diagnostic = lint_error_to_diagnostic(&error, &lines, &test_uri());
+
+        let related = diagnostic.related_information.expect("related information");
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].location.uri, test_uri());
+        assert_eq!(related[0].location.range.start, Position::new(0, 0));
+        assert_eq!(related[0].location.range.end, Position::new(0, 7));
+        assert_eq!(related[0].message, "First top-level heading");
+    }
 }
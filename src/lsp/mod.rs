@@ -30,9 +30,12 @@
 mod backend;
 mod code_actions;
 mod config;
+mod config_file;
 mod diagnostics;
 mod document;
 mod heading;
+mod scan;
+mod symbols;
 mod utils;
 
 pub use backend::MkdlintLanguageServer;
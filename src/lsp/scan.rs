@@ -0,0 +1,137 @@
+//! Workspace scan: lint files the user hasn't opened, a bounded number at a
+//! time, off the async runtime
+
+use super::diagnostics;
+use crate::config::Config;
+use crate::{LintOptions, lint_sync};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tower_lsp::lsp_types::{Diagnostic, Url};
+
+/// One workspace file to lint
+pub(crate) struct ScanInput {
+    pub(crate) uri: Url,
+    /// Path the file is linted under (and keyed by in the heading index)
+    pub(crate) file_name: String,
+    pub(crate) content: String,
+    /// Discovered config; None lints with the defaults
+    pub(crate) config: Option<Config>,
+}
+
+/// Files linted at once when the client sets no `scanConcurrency`
+pub(crate) fn default_concurrency() -> usize {
+    std::thread::available_parallelism().map_or(4, |n| n.get())
+}
+
+/// Lint `inputs` on blocking threads, at most `concurrency` at once, and
+/// hand each file's diagnostics to `publish` as soon as it is done.
+///
+/// Files for which `is_open` holds are skipped, both before linting and
+/// again before publishing, since an open document has fresher diagnostics
+/// of its own. Files without diagnostics are not published.
+pub(crate) async fn lint_files<F, Fut>(
+    inputs: Vec<ScanInput>,
+    headings: HashMap<String, Vec<String>>,
+    rule_timeout_ms: u64,
+    concurrency: usize,
+    is_open: impl Fn(&Url) -> bool,
+    mut publish: F,
+) where
+    F: FnMut(Url, Vec<Diagnostic>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let headings = Arc::new(headings);
+    let mut pending = inputs.into_iter().filter(|input| !is_open(&input.uri));
+    let mut running = JoinSet::new();
+
+    loop {
+        while running.len() < concurrency.max(1) {
+            let Some(input) = pending.next() else {
+                break;
+            };
+            let headings = Arc::clone(&headings);
+            running.spawn_blocking(move || lint_file(input, &headings, rule_timeout_ms));
+        }
+        let Some(done) = running.join_next().await else {
+            break;
+        };
+        // A panicking lint only loses that file's diagnostics
+        if let Ok(Some((uri, diags))) = done
+            && !is_open(&uri)
+        {
+            publish(uri, diags).await;
+        }
+    }
+}
+
+/// Diagnostics for one file, or None when it has none (or fails to lint)
+fn lint_file(
+    input: ScanInput,
+    headings: &HashMap<String, Vec<String>>,
+    rule_timeout_ms: u64,
+) -> Option<(Url, Vec<Diagnostic>)> {
+    let mut options = LintOptions::default().with_rule_timeout_ms(rule_timeout_ms);
+    options
+        .strings
+        .insert(input.file_name.clone(), input.content.clone());
+    options.cached_workspace_headings = Some(headings.clone());
+    options.config = input.config;
+
+    let results = lint_sync(&options).ok()?;
+    let errors = results.get(&input.file_name).unwrap_or(&[]);
+    let lines: Vec<String> = input.content.lines().map(|s| s.to_string()).collect();
+    let diags: Vec<Diagnostic> = errors
+        .iter()
+        .filter(|err| !err.fix_only)
+        .map(|err| diagnostics::lint_error_to_diagnostic(err, &lines, &input.uri))
+        .collect();
+
+    (!diags.is_empty()).then_some((input.uri, diags))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str, content: &str) -> ScanInput {
+        let file_name = format!("/workspace/{}", name);
+        ScanInput {
+            uri: Url::from_file_path(&file_name).unwrap(),
+            file_name,
+            content: content.to_string(),
+            config: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lint_files_publishes_all_but_open_and_clean_files() {
+        let mut inputs: Vec<ScanInput> = (0..6)
+            .map(|i| input(&format!("doc{}.md", i), "#Title\n"))
+            .collect();
+        inputs.push(input("clean.md", "# Title\n"));
+        let open = inputs[2].uri.clone();
+
+        let mut published = Vec::new();
+        lint_files(
+            inputs,
+            HashMap::new(),
+            1000,
+            2,
+            |uri| *uri == open,
+            |uri, diags| {
+                assert!(diags.iter().any(|d| d.message.contains("space")));
+                published.push(uri.path().to_string());
+                std::future::ready(())
+            },
+        )
+        .await;
+
+        published.sort();
+        assert_eq!(
+            published,
+            [0, 1, 3, 4, 5].map(|i| format!("/workspace/doc{}.md", i))
+        );
+    }
+}
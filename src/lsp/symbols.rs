@@ -0,0 +1,254 @@
+//! Document symbols besides the heading outline
+
+use tower_lsp::lsp_types::{DocumentSymbol, Position, Range, SymbolKind};
+
+/// Name of the container symbol holding link and footnote definitions
+pub const DEFINITIONS_SYMBOL: &str = "Definitions";
+
+/// Add the document's link reference definitions (`[label]: url`) and
+/// footnote definitions (`[^1]: text`) to its heading outline `symbols`.
+///
+/// Definitions forming one block at the end of the document go in a
+/// "Definitions" container after the headings, whose ranges then end where
+/// it starts. Definitions anywhere else go in the innermost heading whose
+/// range holds them, so every symbol stays inside its parent and sibling
+/// ranges never overlap.
+pub fn add_definitions(symbols: &mut Vec<DocumentSymbol>, content: &str) {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    match definitions_symbol(&lines) {
+        Some(container) if is_trailing_block(&lines, &container) => {
+            clamp_ranges(symbols, container.range.start);
+            symbols.push(container);
+        }
+        Some(container) => {
+            for definition in container.children.into_iter().flatten() {
+                insert_nested(symbols, definition);
+            }
+        }
+        None => {}
+    }
+}
+
+/// A "Definitions" container listing the document's definitions, or `None`
+/// if there are none.
+///
+/// Each definition is a `CONSTANT` child named by its label, with the
+/// destination (or the footnote text) as detail. The container's range
+/// runs from the first definition to the end of the last one.
+fn definitions_symbol(lines: &[&str]) -> Option<DocumentSymbol> {
+    let mask = crate::helpers::code_block_lines(lines, &[]);
+
+    let children: Vec<DocumentSymbol> = crate::helpers::reference_definitions(lines, &mask)
+        .into_iter()
+        .map(|def| {
+            let first = def.line_number - 1;
+            let last = first + def.line_count - 1;
+            let detail = if def.label.starts_with('^') {
+                footnote_text(lines[first])
+            } else {
+                def.destination
+            };
+            let range = Range {
+                start: Position::new(first as u32, 0),
+                end: Position::new(last as u32, line_len(lines[last])),
+            };
+            #[allow(deprecated)]
+            DocumentSymbol {
+                name: def.label,
+                detail: (!detail.is_empty()).then_some(detail),
+                kind: SymbolKind::CONSTANT,
+                tags: None,
+                deprecated: None,
+                range,
+                selection_range: Range {
+                    start: range.start,
+                    end: Position::new(first as u32, line_len(lines[first])),
+                },
+                children: None,
+            }
+        })
+        .collect();
+
+    let range = Range {
+        start: children.first()?.range.start,
+        end: children.last()?.range.end,
+    };
+    #[allow(deprecated)]
+    Some(DocumentSymbol {
+        name: DEFINITIONS_SYMBOL.to_string(),
+        detail: None,
+        kind: SymbolKind::NAMESPACE,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range: children[0].selection_range,
+        children: Some(children),
+    })
+}
+
+/// Whether the definitions in `container` are the last thing in the
+/// document, with only blank lines between and after them
+fn is_trailing_block(lines: &[&str], container: &DocumentSymbol) -> bool {
+    let Some(definitions) = &container.children else {
+        return false;
+    };
+    let mut covered = vec![false; lines.len()];
+    for definition in definitions {
+        let (first, last) = (definition.range.start.line, definition.range.end.line);
+        for slot in &mut covered[first as usize..=last as usize] {
+            *slot = true;
+        }
+    }
+    let start = container.range.start.line as usize;
+    lines[start..]
+        .iter()
+        .zip(&covered[start..])
+        .all(|(line, &covered)| covered || line.trim().is_empty())
+}
+
+/// Put `symbol` into the innermost of `symbols` (or their children) whose
+/// range holds it, or else among `symbols` in document order
+fn insert_nested(symbols: &mut Vec<DocumentSymbol>, symbol: DocumentSymbol) {
+    let holds = |parent: &DocumentSymbol| {
+        parent.range.start <= symbol.range.start && symbol.range.end <= parent.range.end
+    };
+    if let Some(parent) = symbols.iter_mut().find(|parent| holds(parent)) {
+        insert_nested(parent.children.get_or_insert_with(Vec::new), symbol);
+        return;
+    }
+    let at = symbols.partition_point(|sibling| sibling.range.start < symbol.range.start);
+    symbols.insert(at, symbol);
+}
+
+/// End the ranges of `symbols` (and their children) no later than `limit`,
+/// so a container placed after them doesn't overlap its siblings.
+///
+/// Symbols starting at or after `limit` are left as they are at the top
+/// level; children starting there no longer fit in their parent and are
+/// dropped.
+fn clamp_ranges(symbols: &mut [DocumentSymbol], limit: Position) {
+    for symbol in symbols {
+        if symbol.range.start >= limit {
+            continue;
+        }
+        symbol.range.end = symbol.range.end.min(limit);
+        symbol.selection_range.end = symbol.selection_range.end.min(symbol.range.end);
+        if let Some(children) = &mut symbol.children {
+            children.retain(|child| child.range.start < limit);
+            clamp_ranges(children, limit);
+        }
+    }
+}
+
+/// Text of a footnote definition line after `[^label]:`
+fn footnote_text(line: &str) -> String {
+    line.split_once("]:")
+        .map_or("", |(_, text)| text.trim())
+        .to_string()
+}
+
+/// Length of a line without its ending, in UTF-16 code units
+fn line_len(line: &str) -> u32 {
+    line.trim_end_matches(['\n', '\r']).encode_utf16().count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(deprecated)]
+    fn heading(name: &str, lines: (u32, u32), children: Vec<DocumentSymbol>) -> DocumentSymbol {
+        DocumentSymbol {
+            name: name.to_string(),
+            detail: None,
+            kind: SymbolKind::STRING,
+            tags: None,
+            deprecated: None,
+            range: Range::new(Position::new(lines.0, 0), Position::new(lines.1, 0)),
+            selection_range: Range::new(Position::new(lines.0, 0), Position::new(lines.0, 5)),
+            children: (!children.is_empty()).then_some(children),
+        }
+    }
+
+    /// Names of `symbols` and their children, as `name(children...)`
+    fn outline(symbols: &[DocumentSymbol]) -> String {
+        symbols
+            .iter()
+            .map(|symbol| match &symbol.children {
+                Some(children) => format!("{}({})", symbol.name, outline(children)),
+                None => symbol.name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Whether every child lies in its parent and siblings don't overlap
+    fn well_nested(symbols: &[DocumentSymbol]) -> bool {
+        symbols
+            .windows(2)
+            .all(|pair| pair[0].range.end <= pair[1].range.start)
+            && symbols.iter().all(|symbol| {
+                let children = symbol.children.as_deref().unwrap_or_default();
+                children.iter().all(|child| {
+                    symbol.range.start <= child.range.start && child.range.end <= symbol.range.end
+                }) && well_nested(children)
+            })
+    }
+
+    #[test]
+    fn test_definitions_symbol_links_and_footnotes() {
+        let content = "# Title\n\nSee [docs] and note[^1].\n\n```\n[x]: skipped\n```\n\n[docs]: https://example.com \"Docs\"\n[^1]: The note.\n";
+        let mut symbols = vec![heading("Title", (0, 10), vec![])];
+        add_definitions(&mut symbols, content);
+        assert_eq!(outline(&symbols), "Title Definitions(docs ^1)");
+        assert!(well_nested(&symbols));
+        assert_eq!(symbols[0].range.end, Position::new(8, 0));
+
+        let symbol = &symbols[1];
+        assert_eq!(symbol.range.start, Position::new(8, 0));
+        assert_eq!(symbol.range.end, Position::new(9, 15));
+        let children = symbol.children.as_ref().unwrap();
+        assert_eq!(children[0].kind, SymbolKind::CONSTANT);
+        assert_eq!(children[0].detail.as_deref(), Some("https://example.com"));
+        assert_eq!(children[1].detail.as_deref(), Some("The note."));
+    }
+
+    #[test]
+    fn test_definitions_scattered_through_sections() {
+        let content = "[top]: /top\n\n# One\n\n[a]: /a\n\n## Sub\n\n[b]: /b\n\n# Two\n\ntext\n\n[c]: /c\n\nmore text\n";
+        let mut symbols = vec![
+            heading("One", (2, 10), vec![heading("Sub", (6, 10), vec![])]),
+            heading("Two", (10, 17), vec![]),
+        ];
+        add_definitions(&mut symbols, content);
+        assert_eq!(outline(&symbols), "top One(a Sub(b)) Two(c)");
+        assert!(well_nested(&symbols));
+        // Heading ranges are left alone
+        assert_eq!(symbols[1].range.end, Position::new(10, 0));
+    }
+
+    #[test]
+    fn test_definitions_none_without_definitions() {
+        let mut symbols = vec![heading("Title", (0, 3), vec![])];
+        add_definitions(&mut symbols, "# Title\n\n[not](a-definition.md)\n");
+        assert_eq!(outline(&symbols), "Title");
+    }
+
+    #[test]
+    fn test_clamp_ranges_stops_before_limit() {
+        let mut symbols = vec![heading(
+            "Title",
+            (0, 9),
+            vec![
+                heading("Early", (1, 9), vec![]),
+                heading("Late", (5, 9), vec![]),
+            ],
+        )];
+        clamp_ranges(&mut symbols, Position::new(4, 0));
+        assert_eq!(symbols[0].range.end, Position::new(4, 0));
+        assert_eq!(symbols[0].selection_range.end, Position::new(0, 5));
+        // A child starting past the limit can't stay inside its parent
+        assert_eq!(outline(&symbols), "Title(Early)");
+        assert!(well_nested(&symbols));
+    }
+}
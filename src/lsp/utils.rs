@@ -75,6 +75,11 @@ impl Debouncer {
         self.pending_tasks.insert(uri, handle);
     }
 
+    /// Whether a task is scheduled but has not finished for the given URI
+    pub fn is_pending(&self, uri: &Url) -> bool {
+        self.pending_tasks.contains_key(uri)
+    }
+
     /// Cancel any pending task for the given URI
     pub fn cancel(&self, uri: &Url) {
         if let Some((_, handle)) = self.pending_tasks.remove(uri) {
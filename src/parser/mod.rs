@@ -13,6 +13,12 @@ use comrak::{
 
 /// Parse markdown content into tokens
 pub fn parse(content: &str) -> Vec<Token> {
+    parse_with_math(content, true)
+}
+
+/// Parse Markdown content into tokens, with `$` math recognized only when
+/// `math` is set; otherwise dollars are plain text.
+pub fn parse_with_math(content: &str, math: bool) -> Vec<Token> {
     let arena = Arena::new();
     let mut options = Options::default();
 
@@ -24,7 +30,7 @@ pub fn parse(content: &str) -> Vec<Token> {
     options.extension.tasklist = true;
     options.extension.footnotes = true;
     options.extension.description_lists = true;
-    options.extension.math_dollars = true;
+    options.extension.math_dollars = math;
 
     let root = comrak::parse_document(&arena, content, &options);
 
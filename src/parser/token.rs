@@ -1,6 +1,7 @@
 //! Token types for parsed markdown
 
 use std::collections::HashMap;
+use std::str::FromStr;
 
 /// A token representing a parsed element of markdown
 #[derive(Debug, Clone, PartialEq)]
@@ -67,6 +68,100 @@ impl Token {
             0
         }
     }
+
+    /// Parse a metadata value, returning `None` if it is missing or malformed
+    fn metadata_as<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.metadata.get(key).and_then(|v| v.parse().ok())
+    }
+
+    /// Heading level (1-6) of a `heading` token
+    ///
+    /// ```
+    /// let tokens = mkdlint::parser::parse("## Install\n");
+    /// let heading = tokens.iter().find(|t| t.is_type("heading")).unwrap();
+    /// assert_eq!(heading.heading_level(), Some(2));
+    /// ```
+    pub fn heading_level(&self) -> Option<u8> {
+        self.metadata_as("level")
+    }
+
+    /// Whether a `heading` token uses setext (underline) style
+    ///
+    /// ```
+    /// let tokens = mkdlint::parser::parse("Title\n=====\n");
+    /// let heading = tokens.iter().find(|t| t.is_type("heading")).unwrap();
+    /// assert_eq!(heading.is_setext(), Some(true));
+    /// assert_eq!(heading.heading_level(), Some(1));
+    /// ```
+    pub fn is_setext(&self) -> Option<bool> {
+        self.metadata_as("setext")
+    }
+
+    /// Whether a `list` token is ordered (`1.`) rather than bulleted (`-`)
+    ///
+    /// ```
+    /// let tokens = mkdlint::parser::parse("1. one\n2. two\n");
+    /// let list = tokens.iter().find(|t| t.is_type("list")).unwrap();
+    /// assert_eq!(list.is_ordered_list(), Some(true));
+    /// ```
+    pub fn is_ordered_list(&self) -> Option<bool> {
+        self.metadata_as("ordered")
+    }
+
+    /// Info string of a fenced `codeBlock` token (empty if none was given)
+    ///
+    /// Returns `None` for indented code blocks and other token types.
+    ///
+    /// ```
+    /// let tokens = mkdlint::parser::parse("```rust\nfn main() {}\n```\n");
+    /// let block = tokens.iter().find(|t| t.is_type("codeBlock")).unwrap();
+    /// assert_eq!(block.fence_info(), Some("rust"));
+    /// ```
+    pub fn fence_info(&self) -> Option<&str> {
+        if self.metadata_as::<bool>("fenced")? {
+            self.metadata.get("info").map(String::as_str)
+        } else {
+            None
+        }
+    }
+
+    /// Destination URL of a `link` or `image` token
+    ///
+    /// ```
+    /// let tokens = mkdlint::parser::parse("[docs](https://example.com)\n");
+    /// let link = tokens.iter().find(|t| t.is_type("link")).unwrap();
+    /// assert_eq!(link.link_url(), Some("https://example.com"));
+    /// ```
+    pub fn link_url(&self) -> Option<&str> {
+        self.metadata.get("url").map(String::as_str)
+    }
+
+    /// Iterate over this token's direct children in `all`
+    ///
+    /// `all` must be the token list this token came from.
+    ///
+    /// ```
+    /// let tokens = mkdlint::parser::parse("- a\n- b\n");
+    /// let list = tokens.iter().find(|t| t.is_type("list")).unwrap();
+    /// assert_eq!(list.children(&tokens).filter(|t| t.is_type("listItem")).count(), 2);
+    /// ```
+    pub fn children<'t>(&self, all: &'t [Token]) -> impl Iterator<Item = &'t Token> + use<'t, '_> {
+        self.children.iter().filter_map(move |&idx| all.get(idx))
+    }
+
+    /// Iterate over this token's ancestors in `all`, nearest first
+    ///
+    /// `all` must be the token list this token came from.
+    ///
+    /// ```
+    /// let tokens = mkdlint::parser::parse("- outer\n  - inner\n");
+    /// let inner = tokens.iter().rev().find(|t| t.is_type("listItem")).unwrap();
+    /// assert_eq!(inner.ancestors(&tokens).filter(|t| t.is_type("list")).count(), 2);
+    /// ```
+    pub fn ancestors<'t>(&self, all: &'t [Token]) -> impl Iterator<Item = &'t Token> + use<'t> {
+        let first = self.parent.and_then(|idx| all.get(idx));
+        std::iter::successors(first, move |t| t.parent.and_then(|idx| all.get(idx)))
+    }
 }
 
 /// Helper functions for working with token collections
@@ -142,4 +237,34 @@ mod tests {
         let headings = tokens.filter_by_type("heading");
         assert_eq!(headings.len(), 2);
     }
+
+    #[test]
+    fn test_typed_getters_missing_metadata() {
+        let token = Token::new("heading");
+        assert_eq!(token.heading_level(), None);
+        assert_eq!(token.is_setext(), None);
+        assert_eq!(token.fence_info(), None);
+        assert_eq!(token.link_url(), None);
+    }
+
+    #[test]
+    fn test_fence_info_indented_code() {
+        let tokens = crate::parser::parse("Text\n\n    indented\n");
+        let block = tokens.iter().find(|t| t.is_type("codeBlock")).unwrap();
+        assert_eq!(block.fence_info(), None);
+    }
+
+    #[test]
+    fn test_children_and_ancestors() {
+        let tokens = crate::parser::parse("> # Quoted\n");
+        let heading = tokens.iter().find(|t| t.is_type("heading")).unwrap();
+        let ancestors: Vec<_> = heading
+            .ancestors(&tokens)
+            .map(|t| t.token_type.as_str())
+            .collect();
+        assert_eq!(ancestors, vec!["blockQuote"]);
+
+        let quote = &tokens[heading.parent.unwrap()];
+        assert_eq!(quote.children(&tokens).next(), Some(heading));
+    }
 }
@@ -0,0 +1,25 @@
+//! The supported public API in one import
+//!
+//! ```
+//! use mkdlint::prelude::*;
+//!
+//! let errors = lint_string("# Title\n", &Config::default())?;
+//! assert!(errors.is_empty());
+//! # Ok::<(), MarkdownlintError>(())
+//! ```
+//!
+//! Everything re-exported here follows semantic versioning; see the crate
+//! documentation for the policy on the rest of the public modules.
+
+pub use crate::config::{Config, RuleConfig};
+pub use crate::formatters::{
+    format_github, format_json, format_ndjson, format_sarif, format_text, format_text_with_context,
+};
+pub use crate::lint::{apply_fixes, fix_content, lint_string, lint_sync, lint_sync_streaming};
+pub use crate::types::{
+    FixInfo, LintError, LintOptions, LintResults, MarkdownlintError, ParserType, Rule, RuleParams,
+    Severity,
+};
+
+#[cfg(feature = "async")]
+pub use crate::lint::lint_async;
@@ -0,0 +1,201 @@
+//! Per-rule documentation embedded from `docs/rules/`
+
+/// Markdown documentation for a rule, by canonical ID (e.g. `"MD013"`).
+///
+/// Docs are embedded at compile time from `docs/rules/`.
+pub fn rule_doc(canonical: &str) -> Option<&'static str> {
+    match canonical {
+        "MD001" => Some(include_str!("../../docs/rules/md001.md")),
+        "MD003" => Some(include_str!("../../docs/rules/md003.md")),
+        "MD004" => Some(include_str!("../../docs/rules/md004.md")),
+        "MD005" => Some(include_str!("../../docs/rules/md005.md")),
+        "MD007" => Some(include_str!("../../docs/rules/md007.md")),
+        "MD009" => Some(include_str!("../../docs/rules/md009.md")),
+        "MD010" => Some(include_str!("../../docs/rules/md010.md")),
+        "MD011" => Some(include_str!("../../docs/rules/md011.md")),
+        "MD012" => Some(include_str!("../../docs/rules/md012.md")),
+        "MD013" => Some(include_str!("../../docs/rules/md013.md")),
+        "MD014" => Some(include_str!("../../docs/rules/md014.md")),
+        "MD018" => Some(include_str!("../../docs/rules/md018.md")),
+        "MD019" => Some(include_str!("../../docs/rules/md019.md")),
+        "MD020" => Some(include_str!("../../docs/rules/md020.md")),
+        "MD021" => Some(include_str!("../../docs/rules/md021.md")),
+        "MD022" => Some(include_str!("../../docs/rules/md022.md")),
+        "MD023" => Some(include_str!("../../docs/rules/md023.md")),
+        "MD024" => Some(include_str!("../../docs/rules/md024.md")),
+        "MD025" => Some(include_str!("../../docs/rules/md025.md")),
+        "MD026" => Some(include_str!("../../docs/rules/md026.md")),
+        "MD027" => Some(include_str!("../../docs/rules/md027.md")),
+        "MD028" => Some(include_str!("../../docs/rules/md028.md")),
+        "MD029" => Some(include_str!("../../docs/rules/md029.md")),
+        "MD030" => Some(include_str!("../../docs/rules/md030.md")),
+        "MD031" => Some(include_str!("../../docs/rules/md031.md")),
+        "MD032" => Some(include_str!("../../docs/rules/md032.md")),
+        "MD033" => Some(include_str!("../../docs/rules/md033.md")),
+        "MD034" => Some(include_str!("../../docs/rules/md034.md")),
+        "MD035" => Some(include_str!("../../docs/rules/md035.md")),
+        "MD036" => Some(include_str!("../../docs/rules/md036.md")),
+        "MD037" => Some(include_str!("../../docs/rules/md037.md")),
+        "MD038" => Some(include_str!("../../docs/rules/md038.md")),
+        "MD039" => Some(include_str!("../../docs/rules/md039.md")),
+        "MD040" => Some(include_str!("../../docs/rules/md040.md")),
+        "MD041" => Some(include_str!("../../docs/rules/md041.md")),
+        "MD042" => Some(include_str!("../../docs/rules/md042.md")),
+        "MD043" => Some(include_str!("../../docs/rules/md043.md")),
+        "MD044" => Some(include_str!("../../docs/rules/md044.md")),
+        "MD045" => Some(include_str!("../../docs/rules/md045.md")),
+        "MD046" => Some(include_str!("../../docs/rules/md046.md")),
+        "MD047" => Some(include_str!("../../docs/rules/md047.md")),
+        "MD048" => Some(include_str!("../../docs/rules/md048.md")),
+        "MD049" => Some(include_str!("../../docs/rules/md049.md")),
+        "MD050" => Some(include_str!("../../docs/rules/md050.md")),
+        "MD051" => Some(include_str!("../../docs/rules/md051.md")),
+        "MD052" => Some(include_str!("../../docs/rules/md052.md")),
+        "MD053" => Some(include_str!("../../docs/rules/md053.md")),
+        "MD054" => Some(include_str!("../../docs/rules/md054.md")),
+        "MD055" => Some(include_str!("../../docs/rules/md055.md")),
+        "MD056" => Some(include_str!("../../docs/rules/md056.md")),
+        "MD058" => Some(include_str!("../../docs/rules/md058.md")),
+        "MD059" => Some(include_str!("../../docs/rules/md059.md")),
+        "MD060" => Some(include_str!("../../docs/rules/md060.md")),
+        "KMD001" => Some(include_str!("../../docs/rules/kmd001.md")),
+        "KMD002" => Some(include_str!("../../docs/rules/kmd002.md")),
+        "KMD003" => Some(include_str!("../../docs/rules/kmd003.md")),
+        "KMD004" => Some(include_str!("../../docs/rules/kmd004.md")),
+        "KMD005" => Some(include_str!("../../docs/rules/kmd005.md")),
+        "KMD006" => Some(include_str!("../../docs/rules/kmd006.md")),
+        "KMD007" => Some(include_str!("../../docs/rules/kmd007.md")),
+        "KMD008" => Some(include_str!("../../docs/rules/kmd008.md")),
+        "KMD009" => Some(include_str!("../../docs/rules/kmd009.md")),
+        "KMD010" => Some(include_str!("../../docs/rules/kmd010.md")),
+        "KMD011" => Some(include_str!("../../docs/rules/kmd011.md")),
+        "MKD001" => Some(include_str!("../../docs/rules/mkd001.md")),
+        "MKD002" => Some(include_str!("../../docs/rules/mkd002.md")),
+        "MKD003" => Some(include_str!("../../docs/rules/mkd003.md")),
+        "MKD004" => Some(include_str!("../../docs/rules/mkd004.md")),
+        "MKD005" => Some(include_str!("../../docs/rules/mkd005.md")),
+        "MKD006" => Some(include_str!("../../docs/rules/mkd006.md")),
+        "MKD007" => Some(include_str!("../../docs/rules/mkd007.md")),
+        _ => None,
+    }
+}
+
+/// One row of the option table in a rule's doc
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleOptionDoc {
+    /// Option name
+    pub name: &'static str,
+    /// The Type column, such as `integer`, `string array` or
+    /// ``string or `false` ``
+    pub kind: &'static str,
+    /// The Default column as written, such as `` `80` `` or `unset`
+    pub default: &'static str,
+    /// The Description column, in Markdown
+    pub description: &'static str,
+}
+
+/// Options a rule accepts, from the table in its doc's `## Configuration`
+/// section, in table order.
+///
+/// # Examples
+/// ```
+/// let options = mkdlint::rules::rule_options("MD013");
+/// assert!(options.contains(&"line_length"));
+/// assert!(mkdlint::rules::rule_options("MD011").is_empty());
+/// ```
+pub fn rule_options(canonical: &str) -> Vec<&'static str> {
+    rule_option_docs(canonical)
+        .into_iter()
+        .map(|option| option.name)
+        .collect()
+}
+
+/// The rows of the option table in a rule's doc, in table order; see
+/// [`rule_options`].
+///
+/// # Examples
+/// ```
+/// let options = mkdlint::rules::rule_option_docs("MD047");
+/// assert_eq!((options[0].name, options[0].kind), ("newlines", "integer"));
+/// ```
+pub fn rule_option_docs(canonical: &str) -> Vec<RuleOptionDoc> {
+    let Some(doc) = rule_doc(canonical) else {
+        return Vec::new();
+    };
+    let Some((_, section)) = doc.split_once("\n## Configuration\n") else {
+        return Vec::new();
+    };
+    let section = section.split("\n## ").next().unwrap_or(section);
+    section
+        .lines()
+        .filter_map(|line| {
+            // The description is last, so pipes in it stay with it
+            let mut cells = line.strip_prefix("| `")?.splitn(4, " | ");
+            let name = cells.next()?.strip_suffix('`')?;
+            Some(RuleOptionDoc {
+                name,
+                kind: cells.next()?.trim(),
+                default: cells.next()?.trim(),
+                description: cells.next()?.trim().trim_end_matches('|').trim_end(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_rules_have_docs() {
+        for rule in crate::rules::get_rules().iter() {
+            let canonical = rule.names()[0];
+            assert!(
+                rule_doc(canonical).is_some(),
+                "Missing documentation for rule {}",
+                canonical
+            );
+        }
+    }
+
+    #[test]
+    fn test_doc_content_not_empty() {
+        for rule in crate::rules::get_rules().iter() {
+            let canonical = rule.names()[0];
+            let doc = rule_doc(canonical).unwrap();
+            assert!(
+                !doc.is_empty(),
+                "Empty documentation for rule {}",
+                canonical
+            );
+            assert!(
+                doc.contains(&format!("# {}", canonical)),
+                "Documentation for {} should contain the rule name in the title",
+                canonical
+            );
+        }
+    }
+
+    #[test]
+    fn test_alias_lookup_resolves_to_doc() {
+        // "heading-increment" is an alias for MD001
+        let rule = crate::rules::find_rule("heading-increment").unwrap();
+        assert_eq!(rule.names()[0], "MD001");
+        assert!(rule_doc("MD001").is_some());
+    }
+
+    #[test]
+    fn test_unknown_rule_returns_none() {
+        assert!(rule_doc("NONEXISTENT").is_none());
+    }
+
+    #[test]
+    fn test_rule_options_from_configuration_table() {
+        assert_eq!(
+            rule_options("MD013")[..3],
+            ["line_length", "code_blocks", "ignore_html_comments"]
+        );
+        assert!(rule_options("MD001").contains(&"front_matter_title"));
+        assert!(rule_options("NONEXISTENT").is_empty());
+    }
+}
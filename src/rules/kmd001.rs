@@ -153,15 +153,7 @@ mod tests {
     fn lint(content: &str) -> Vec<LintError> {
         let lines: Vec<&str> = content.split_inclusive('\n').collect();
         let rule = KMD001;
-        rule.lint(&RuleParams {
-            name: "test.md",
-            version: "0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        })
+        rule.lint(&RuleParams::test(&lines, &HashMap::new()))
     }
 
     #[test]
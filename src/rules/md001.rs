@@ -3,43 +3,11 @@
 //! This rule checks that heading levels only increment by one at a time.
 //! For example, an h3 heading should not appear directly after an h1 heading.
 
-use crate::parser::{Token, TokenExt};
+use crate::parser::TokenExt;
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
-use regex::Regex;
 
 pub struct MD001;
 
-impl MD001 {
-    /// Get the heading level from a heading token's metadata
-    fn get_heading_level(heading: &Token) -> usize {
-        heading
-            .metadata
-            .get("level")
-            .and_then(|l| l.parse::<usize>().ok())
-            .unwrap_or(1)
-    }
-
-    /// Check if front matter has a title field
-    fn front_matter_has_title(
-        front_matter_lines: &[&str],
-        config: &std::collections::HashMap<String, serde_json::Value>,
-    ) -> bool {
-        // Get front_matter_title pattern from config, default to checking for "title:"
-        let pattern = match config.get("front_matter_title") {
-            Some(serde_json::Value::String(s)) if !s.is_empty() => s.clone(),
-            Some(serde_json::Value::Bool(false)) => return false, // Ignore front matter
-            _ => r#"^\s*"?title"?\s*[:=]"#.to_string(),
-        };
-
-        let re = match Regex::new(&format!("(?i){}", pattern)) {
-            Ok(r) => r,
-            Err(_) => return false,
-        };
-
-        front_matter_lines.iter().any(|line| re.is_match(line))
-    }
-}
-
 impl Rule for MD001 {
     fn names(&self) -> &'static [&'static str] {
         &["MD001", "heading-increment"]
@@ -65,7 +33,7 @@ impl Rule for MD001 {
         let mut errors = Vec::new();
 
         // Check if front matter has a title (acts as implicit h1)
-        let has_title = Self::front_matter_has_title(params.front_matter_lines, params.config);
+        let has_title = params.front_matter_title_line().is_some();
         let mut prev_level = if has_title {
             1
         } else {
@@ -76,16 +44,12 @@ impl Rule for MD001 {
         let headings = params.tokens.filter_by_type("heading");
 
         for heading in headings {
-            let level = Self::get_heading_level(heading);
+            let level = heading.heading_level().map_or(1, usize::from);
 
             // Only report error if level increases by more than 1
             if level > prev_level.saturating_add(1) {
                 let expected_level = prev_level + 1;
-                let is_setext = heading
-                    .metadata
-                    .get("setext")
-                    .and_then(|v| v.parse::<bool>().ok())
-                    .unwrap_or(false);
+                let is_setext = heading.is_setext().unwrap_or(false);
 
                 // Generate fix_info to adjust the heading level
                 let fix_info = if !is_setext {
@@ -143,6 +107,8 @@ impl Rule for MD001 {
                     ),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
 
@@ -156,6 +122,7 @@ impl Rule for MD001 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parser::Token;
     use std::collections::HashMap;
 
     /// Helper to create a heading token with metadata
@@ -187,15 +154,8 @@ mod tests {
 
         let lines = vec!["# Heading 1\n", "## Heading 2\n", "### Heading 3\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD001;
         let errors = rule.lint(&params);
@@ -211,15 +171,8 @@ mod tests {
 
         let lines = vec!["# Heading 1\n", "### Heading 3\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD001;
         let errors = rule.lint(&params);
@@ -248,15 +201,8 @@ mod tests {
             "# Heading 1 again\n",
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD001;
         let errors = rule.lint(&params);
@@ -278,14 +224,10 @@ mod tests {
 
         let front_matter = vec!["title: Document Title\n"];
 
+        let config = HashMap::new();
         let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
             front_matter_lines: &front_matter,
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
+            ..RuleParams::test_with_tokens(&lines, &tokens, &config)
         };
 
         let rule = MD001;
@@ -305,15 +247,8 @@ mod tests {
             "---------\n",
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD001;
         let errors = rule.lint(&params);
@@ -330,15 +265,8 @@ mod tests {
 
         let lines = vec!["# Heading 1\n", "#### Heading 4\n", "###### Heading 6\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD001;
         let errors = rule.lint(&params);
@@ -357,15 +285,8 @@ mod tests {
 
         let lines = vec!["# Heading 1\n", "### Heading 3\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD001;
         let errors = rule.lint(&params);
@@ -387,15 +308,8 @@ mod tests {
 
         let lines = vec!["# Heading 1\n", "Heading 2\n", "---------\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD001;
         let errors = rule.lint(&params);
@@ -9,6 +9,7 @@
 //! - `setext_with_atx_closed`: Setext for h1 and h2, ATX closed for h3-h6
 //! - `consistent`: First heading determines the style
 
+use crate::helpers::strip_closing_sequence;
 use crate::parser::TokenExt;
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
@@ -46,16 +47,11 @@ fn get_heading_style(lines: &[&str], start_line: usize, end_line: usize) -> Head
 
     // Check if it's an ATX-style heading (starts with #)
     if trimmed.starts_with('#') {
-        // Check if it's closed (ends with #)
-        let text_without_leading_hashes = trimmed.trim_start_matches('#').trim();
-        if text_without_leading_hashes.ends_with('#') {
-            // Make sure it's not just the content containing #
-            let without_trailing = text_without_leading_hashes.trim_end_matches('#').trim_end();
-            if without_trailing.len() < text_without_leading_hashes.len() - 1 {
-                return HeadingStyle::AtxClosed;
-            }
-        }
-        return HeadingStyle::Atx;
+        let text = trimmed.trim_start_matches('#').trim();
+        return match strip_closing_sequence(text) {
+            Some(_) => HeadingStyle::AtxClosed,
+            None => HeadingStyle::Atx,
+        };
     }
 
     // Check if it's a Setext-style heading (underlined)
@@ -99,10 +95,9 @@ fn generate_heading_fix(
             trimmed.trim_start_matches('#').trim().to_string()
         }
         HeadingStyle::AtxClosed => {
-            // Remove leading and trailing # symbols
-            let trimmed = line.trim();
-            let without_leading = trimmed.trim_start_matches('#').trim();
-            without_leading.trim_end_matches('#').trim().to_string()
+            // Remove leading # symbols and the closing sequence
+            let text = line.trim().trim_start_matches('#').trim();
+            strip_closing_sequence(text).unwrap_or(text).to_string()
         }
         HeadingStyle::Setext => {
             // Text is on the first line, underline is on the next
@@ -236,7 +231,10 @@ impl Rule for MD003 {
 
         for heading in headings {
             let style = get_heading_style(params.lines, heading.start_line, heading.end_line);
-            let level = get_heading_level(params.lines, heading.start_line, heading.end_line);
+            let level = heading.heading_level().map_or_else(
+                || get_heading_level(params.lines, heading.start_line, heading.end_line),
+                usize::from,
+            );
 
             // For consistent mode, use the first heading's style
             if configured_style == "consistent" {
@@ -271,6 +269,8 @@ impl Rule for MD003 {
                             )),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
 
                         // If converting FROM setext, also delete the underline
@@ -298,6 +298,8 @@ impl Rule for MD003 {
                                 ),
                                 severity: Severity::Error,
                                 fix_only: false,
+                                line_text: None,
+                                related: None,
                             });
                         }
                     }
@@ -386,6 +388,8 @@ impl Rule for MD003 {
                         suggestion: Some(format!("Convert heading to {} style", expected)),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
 
                     // If converting FROM setext, also delete the underline.
@@ -408,6 +412,8 @@ impl Rule for MD003 {
                             suggestion: None,
                             severity: Severity::Error,
                             fix_only: true,
+                            line_text: None,
+                            related: None,
                         });
                     }
                 }
@@ -457,15 +463,7 @@ mod tests {
         let mut config = HashMap::new();
         config.insert("style".to_string(), Value::String("consistent".to_string()));
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD003;
         let errors = rule.lint(&params);
@@ -481,15 +479,7 @@ mod tests {
         let mut config = HashMap::new();
         config.insert("style".to_string(), Value::String("consistent".to_string()));
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD003;
         let all_errors = rule.lint(&params);
@@ -525,15 +515,7 @@ mod tests {
         let mut config = HashMap::new();
         config.insert("style".to_string(), Value::String("atx".to_string()));
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD003;
         let all_errors = rule.lint(&params);
@@ -552,15 +534,7 @@ mod tests {
         let mut config = HashMap::new();
         config.insert("style".to_string(), Value::String("setext".to_string()));
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD003;
         let errors = rule.lint(&params);
@@ -577,15 +551,7 @@ mod tests {
         let mut config = HashMap::new();
         config.insert("style".to_string(), Value::String("atx_closed".to_string()));
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD003;
         let all_errors = rule.lint(&params);
@@ -619,15 +585,7 @@ mod tests {
             Value::String("setext_with_atx".to_string()),
         );
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD003;
         let errors = rule.lint(&params);
@@ -646,15 +604,7 @@ mod tests {
             Value::String("setext_with_atx_closed".to_string()),
         );
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD003;
         let errors = rule.lint(&params);
@@ -679,6 +629,39 @@ mod tests {
         assert_eq!(get_heading_style(&lines, 1, 2), HeadingStyle::Setext);
     }
 
+    #[test]
+    fn test_md003_atx_strips_only_closing_sequences() {
+        let config: crate::Config =
+            serde_json::from_str(r#"{"default": false, "MD003": {"style": "atx"}}"#).unwrap();
+        let content = "# Title #\n\n## Sub ##  \n\n## C#\n\n## Use \\#\n\n\
+                       ## Run `x #`\n\n### Foo \\##\n\n### Bar \\# ###\n";
+        let lines: Vec<usize> = crate::lint_string(content, &config)
+            .unwrap()
+            .iter()
+            .map(|e| e.line_number)
+            .collect();
+        assert_eq!(lines, [1, 3, 13]);
+
+        let fixed = crate::fix_content(content, &config).unwrap();
+        assert_eq!(
+            fixed,
+            "# Title\n\n## Sub\n\n## C#\n\n## Use \\#\n\n\
+             ## Run `x #`\n\n### Foo \\##\n\n### Bar \\#\n"
+        );
+        assert!(crate::lint_string(&fixed, &config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_md003_atx_fix_with_default_rules_keeps_hash_text() {
+        // MD020 and MD021 must not read `C#` left behind by the fix as a
+        // closed heading and take another `#` off it
+        let config: crate::Config = serde_json::from_str(r#"{"MD003": {"style": "atx"}}"#).unwrap();
+        let content = "# Title #\n\n## C# ##\n\n## Esc \\# ##\n\n## Tab\t#\n";
+        let fixed = crate::fix_content(content, &config).unwrap();
+        assert_eq!(fixed, "# Title\n\n## C#\n\n## Esc \\#\n\n## Tab\n");
+        assert!(crate::lint_string(&fixed, &config).unwrap().is_empty());
+    }
+
     #[test]
     fn test_get_heading_level_atx() {
         let lines = vec!["# H1\n", "## H2\n", "### H3\n"];
@@ -94,22 +94,10 @@ fn get_nesting_level(
     tokens: &[crate::parser::Token],
     current_token: &crate::parser::Token,
 ) -> usize {
-    let mut level = 0;
-    let mut current = current_token;
-
-    while let Some(parent_idx) = current.parent {
-        if let Some(parent) = tokens.get(parent_idx) {
-            // Check if parent is a list (ordered or unordered)
-            if parent.token_type == "list" {
-                level += 1;
-            }
-            current = parent;
-        } else {
-            break;
-        }
-    }
-
-    level
+    current_token
+        .ancestors(tokens)
+        .filter(|t| t.is_type("list"))
+        .count()
 }
 
 impl Rule for MD004 {
@@ -226,6 +214,8 @@ impl Rule for MD004 {
                         suggestion: Some("Use consistent list marker style".to_string()),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
@@ -264,15 +254,8 @@ mod tests {
 
         let lines = vec!["* Item 1\n", "* Item 2\n", "* Item 3\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD004;
         let errors = rule.lint(&params);
@@ -289,15 +272,8 @@ mod tests {
 
         let lines = vec!["- Item 1\n", "- Item 2\n", "- Item 3\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD004;
         let errors = rule.lint(&params);
@@ -314,15 +290,8 @@ mod tests {
 
         let lines = vec!["* Item 1\n", "- Item 2\n", "+ Item 3\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD004;
         let errors = rule.lint(&params);
@@ -344,15 +313,7 @@ mod tests {
 
         let lines = vec!["* Item 1\n", "- Item 2\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD004;
         let errors = rule.lint(&params);
@@ -374,15 +335,7 @@ mod tests {
 
         let lines = vec!["- Item 1\n", "- Item 2\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD004;
         let errors = rule.lint(&params);
@@ -402,15 +355,7 @@ mod tests {
 
         let lines = vec!["+ Item 1\n", "+ Item 2\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD004;
         let errors = rule.lint(&params);
@@ -2,14 +2,15 @@
 //!
 //! This rule checks that unordered list items have consistent indentation.
 //! Each nested level should be indented by a consistent number of spaces
-//! (default: 2).
+//! (default: 2). A list nested in an ordered item lines up with the item's
+//! text instead, since CommonMark nests it only that far in.
+//!
+//! With `check_continuations`, wrapped text lines of an item must also line
+//! up under the item's text.
 
+use crate::helpers::ListItemGeometry;
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
-use regex::Regex;
-use std::sync::LazyLock;
-
-static UL_MARKER_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^(\s*)[*+\-]\s").expect("valid regex"));
+use std::collections::{BTreeMap, HashMap};
 
 pub struct MD007;
 
@@ -41,58 +42,181 @@ impl Rule for MD007 {
             .get("indent")
             .and_then(|v| v.as_u64())
             .unwrap_or(2) as usize;
+        let check_continuations = params
+            .config
+            .get("check_continuations")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
 
-        let mut in_code_block = false;
+        let code_block_lines = params.code_block_lines();
+        let list_context = params.list_context();
+        // How far the fixes move each item, by its marker line, so nested
+        // items line up with where their parent ends up
+        let mut moved: HashMap<usize, isize> = HashMap::new();
+        // Lines that move along with their item
+        let mut shifts = BTreeMap::new();
+        // Content column of the item whose text the next line may wrap
+        let mut item_content: Option<usize> = None;
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
             let trimmed = line.trim_end_matches('\n').trim_end_matches('\r');
-
-            // Track code blocks
-            if crate::helpers::is_code_fence(trimmed.trim_start()) {
-                in_code_block = !in_code_block;
+            let in_code = code_block_lines.get(idx).copied().unwrap_or(false);
+            let Some(info) = list_context.get(idx).copied().flatten() else {
+                item_content = None;
                 continue;
-            }
-            if in_code_block {
+            };
+            if in_code {
+                // Code blocks in an item move along with it
+                let delta = moved.get(&info.item.line_number).copied().unwrap_or(0);
+                shifts.insert(line_number, delta);
+                item_content = None;
                 continue;
             }
+            // Indentation is measured inside any blockquote
+            let quote = crate::helpers::blockquote_prefix_len(trimmed);
 
-            // Check for unordered list markers
-            if let Some(caps) = UL_MARKER_RE.captures(trimmed) {
-                let leading_spaces = caps.get(1).unwrap().as_str().len();
+            if info.is_marker_line(line_number) {
+                // A list in a blockquote inside an item starts a new margin
+                let parent = info.parent.filter(|parent| parent.marker_start >= quote);
+                let parent_moved = parent
+                    .and_then(|parent| moved.get(&parent.line_number))
+                    .copied()
+                    .unwrap_or(0);
+                let leading_spaces = info.item.marker_start - quote;
+                let delta = if info.ordered {
+                    // Ordered items are MD005's; they only follow their parent
+                    *shifts.entry(line_number).or_default() += parent_moved;
+                    parent_moved
+                } else {
+                    let expected = expected_indent(&info.item, parent, parent_moved, quote, indent);
+                    if expected != leading_spaces {
+                        errors.push(LintError {
+                            line_number,
+                            rule_names: self.names(),
+                            rule_description: self.description(),
+                            error_detail: Some(format!(
+                                "Expected: {}; Actual: {}",
+                                expected, leading_spaces
+                            )),
+                            error_context: Some(trimmed.to_string()),
+                            rule_information: self.information(),
+                            error_range: Some((quote + 1, leading_spaces)),
+                            fix_info: Some(FixInfo {
+                                line_number: None,
+                                edit_column: Some(quote + 1),
+                                delete_count: Some(leading_spaces as i32),
+                                insert_text: Some(" ".repeat(expected)),
+                            }),
+                            suggestion: Some(
+                                "Use consistent indentation for nested lists".to_string(),
+                            ),
+                            severity: Severity::Error,
+                            fix_only: false,
+                            line_text: None,
+                            related: None,
+                        });
+                    }
+                    expected as isize - leading_spaces as isize
+                };
+                moved.insert(line_number, delta);
+                item_content = (!info.ordered)
+                    .then(|| (info.item.content_column() - quote).saturating_add_signed(delta));
+                continue;
+            }
 
-                // If there's indentation, check it's a multiple of `indent`
-                if leading_spaces > 0 && leading_spaces % indent != 0 {
-                    let expected = (leading_spaces / indent) * indent;
+            let delta = moved.get(&info.item.line_number).copied().unwrap_or(0);
+            if let Some(content_col) = item_content {
+                let body = &trimmed[quote..];
+                let actual = crate::helpers::leading_whitespace_len(body);
+                if !is_wrapped_text(body.trim_start()) {
+                    item_content = None;
+                } else if check_continuations && actual != content_col {
                     errors.push(LintError {
                         line_number,
                         rule_names: self.names(),
                         rule_description: self.description(),
                         error_detail: Some(format!(
-                            "Expected: {}; Actual: {}",
-                            expected, leading_spaces
+                            "Continuation line; Expected: {}; Actual: {}",
+                            content_col, actual
                         )),
                         error_context: Some(trimmed.to_string()),
                         rule_information: self.information(),
-                        error_range: Some((1, leading_spaces)),
+                        error_range: (actual > 0).then_some((quote + 1, actual)),
                         fix_info: Some(FixInfo {
                             line_number: None,
-                            edit_column: Some(1),
-                            delete_count: Some(leading_spaces as i32),
-                            insert_text: Some(" ".repeat(expected)),
+                            edit_column: Some(quote + 1),
+                            delete_count: Some(actual as i32),
+                            insert_text: Some(" ".repeat(content_col)),
                         }),
-                        suggestion: Some("Use consistent indentation for nested lists".to_string()),
+                        suggestion: Some(
+                            "Align wrapped lines with the list item's text".to_string(),
+                        ),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
+                    continue;
                 }
             }
+            shifts.insert(line_number, delta);
+        }
+
+        for fix_info in crate::helpers::shift_lines_fixes(params.lines, &shifts) {
+            errors.push(LintError {
+                line_number: fix_info.line_number.unwrap_or(1),
+                rule_names: self.names(),
+                rule_description: self.description(),
+                rule_information: self.information(),
+                fix_info: Some(fix_info),
+                severity: Severity::Error,
+                fix_only: true,
+                ..Default::default()
+            });
         }
 
         errors
     }
 }
 
+/// Where an unordered item's marker belongs, in columns after any
+/// blockquote markers: under the text of an ordered parent, a multiple of
+/// `indent` past an unordered parent's marker, or a multiple of `indent` at
+/// the top level. `parent_moved` is how far the fixes move the parent.
+fn expected_indent(
+    item: &ListItemGeometry,
+    parent: Option<ListItemGeometry>,
+    parent_moved: isize,
+    quote: usize,
+    indent: usize,
+) -> usize {
+    let indent = indent.max(1);
+    let leading_spaces = item.marker_start - quote;
+    match parent {
+        Some(parent) if matches!(parent.marker, '.' | ')') => {
+            (parent.content_column() - quote).saturating_add_signed(parent_moved)
+        }
+        Some(parent) => {
+            let base = parent.marker_start - quote;
+            // Rounding down to the parent's own column would un-nest the item
+            let steps = (leading_spaces.saturating_sub(base) / indent).max(1);
+            base.saturating_add_signed(parent_moved) + steps * indent
+        }
+        None => leading_spaces / indent * indent,
+    }
+}
+
+/// Whether a line directly after a list item continues its paragraph text,
+/// rather than being blank or starting another block
+fn is_wrapped_text(line: &str) -> bool {
+    !line.is_empty()
+        && !line.starts_with(['#', '>', '<', '|'])
+        && crate::helpers::list_marker_len(line).is_none()
+        // Setext underlines and thematic breaks
+        && !line.chars().all(|c| matches!(c, '-' | '=' | '*' | '_' | ' ' | '\t'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +278,53 @@ mod tests {
         assert_eq!(errors.len(), 0);
     }
 
+    fn fix(content: &str, config: &str) -> (Vec<LintError>, String) {
+        let config: crate::config::Config = serde_json::from_str(config).unwrap();
+        let errors: Vec<LintError> = crate::lint_string(content, &config)
+            .unwrap()
+            .into_iter()
+            .filter(|e| !e.fix_only)
+            .collect();
+        (errors, crate::fix_content(content, &config).unwrap())
+    }
+
+    #[test]
+    fn test_md007_nested_in_ordered_item() {
+        // The item's text starts at column 3, where the nested list belongs
+        let (errors, _) = fix(
+            "1. one\n   - x\n     - y\n",
+            r#"{"default": false, "MD007": true}"#,
+        );
+        assert!(errors.is_empty());
+
+        let (errors, fixed) = fix(
+            "1. one\n    - x\n      - y\n",
+            r#"{"default": false, "MD007": true}"#,
+        );
+        // `y` follows `x` to its new place
+        let details: Vec<&str> = errors
+            .iter()
+            .filter_map(|e| e.error_detail.as_deref())
+            .collect();
+        assert_eq!(
+            details,
+            ["Expected: 3; Actual: 4", "Expected: 5; Actual: 6"]
+        );
+        assert_eq!(fixed, "1. one\n   - x\n     - y\n");
+    }
+
+    #[test]
+    fn test_md007_fix_moves_nested_blocks_along() {
+        let content = "* a\n  * x\n    * y\n\n    ```\n    code\n    ```\n\n    1. n\n";
+        let (errors, fixed) = fix(content, r#"{"default": false, "MD007": {"indent": 4}}"#);
+        let lines: Vec<usize> = errors.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, [2, 3]);
+        assert_eq!(
+            fixed,
+            "* a\n    * x\n        * y\n\n      ```\n      code\n      ```\n\n      1. n\n"
+        );
+    }
+
     #[test]
     fn test_md007_in_code_block_ignored() {
         let lines: Vec<&str> = vec!["```\n", "   * not a list\n", "```\n"];
@@ -164,4 +335,85 @@ mod tests {
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 0);
     }
+
+    fn continuation_config() -> HashMap<String, serde_json::Value> {
+        let mut config = HashMap::new();
+        config.insert("check_continuations".to_string(), serde_json::json!(true));
+        config
+    }
+
+    #[test]
+    fn test_md007_continuations_off_by_default() {
+        let lines: Vec<&str> = vec!["* Item with text\n", " that wraps\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD007.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_md007_continuation_under_indented() {
+        let lines: Vec<&str> = vec![
+            "* Item with text\n",
+            " that wraps\n",
+            "  * Nested item\n",
+            "    aligned wrap\n",
+        ];
+        let config = continuation_config();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD007.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 2);
+        assert_eq!(
+            errors[0].error_detail,
+            Some("Continuation line; Expected: 2; Actual: 1".to_string())
+        );
+
+        let content = lines.concat();
+        let fixed = crate::lint::apply_fixes(&content, &errors);
+        assert_eq!(
+            fixed,
+            "* Item with text\n  that wraps\n  * Nested item\n    aligned wrap\n"
+        );
+    }
+
+    #[test]
+    fn test_md007_continuation_lazy_line_flagged() {
+        let lines: Vec<&str> = vec!["-   Wide marker spacing\n", "lazy line\n"];
+        let config = continuation_config();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD007.lint(&params);
+        assert_eq!(errors.len(), 1);
+        let fix = errors[0].fix_info.as_ref().unwrap();
+        assert_eq!(fix.delete_count, Some(0));
+        assert_eq!(fix.insert_text, Some("    ".to_string()));
+    }
+
+    #[test]
+    fn test_md007_continuation_stops_at_blocks() {
+        let lines: Vec<&str> = vec![
+            "* Item\n",
+            "\n",
+            "Paragraph after the list\n",
+            "* Item\n",
+            "# Heading\n",
+            "* Item\n",
+            "1. Ordered\n",
+            "   ordered wrap\n",
+        ];
+        let config = continuation_config();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD007.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_md007_lists_in_blockquotes() {
+        let content = "> * Item\n>    * Nested\n";
+        let config: crate::config::Config =
+            serde_json::from_str(r#"{"default": false, "MD007": true}"#).unwrap();
+        let errors = crate::lint_string(content, &config).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error_range, Some((3, 3)));
+        let fixed = crate::fix_content(content, &config).unwrap();
+        assert_eq!(fixed, "> * Item\n>   * Nested\n");
+    }
 }
@@ -1,8 +1,14 @@
 //! MD009 - Trailing spaces
 //!
 //! This rule checks for lines that end with trailing whitespace.
+//!
+//! Inside a list item, `br_spaces` trailing spaces before a line that
+//! continues the item are a hard line break and are allowed. Trailing
+//! whitespace inside multi-line math or code spans is left alone.
 
+use crate::parser::TokenExt;
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+use std::collections::HashSet;
 
 pub struct MD009;
 
@@ -19,8 +25,12 @@ impl Rule for MD009 {
         &["whitespace", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        1
+    }
+
     fn parser_type(&self) -> ParserType {
-        ParserType::None
+        ParserType::Micromark
     }
 
     fn information(&self) -> Option<&'static str> {
@@ -30,6 +40,33 @@ impl Rule for MD009 {
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
 
+        // Values below 2 cannot form a hard break and disable the allowance
+        let br_spaces = params
+            .config
+            .get("br_spaces")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(2) as usize;
+        let items = params.tokens.filter_by_type("listItem");
+        let item_starts: HashSet<usize> = items.iter().map(|item| item.start_line).collect();
+        let code_lines = params.code_block_lines();
+        let literal_spans = params.literal_spans();
+        // Whether the line break after `line_number` is a hard break inside
+        // a list item: the next line continues the same item's text
+        let breaks_list_item = |line_number: usize| {
+            let next = line_number + 1;
+            br_spaces >= 2
+                && !code_lines.get(line_number - 1).copied().unwrap_or(false)
+                && !code_lines.get(next - 1).copied().unwrap_or(true)
+                && params
+                    .lines
+                    .get(next - 1)
+                    .is_some_and(|line| !line.trim().is_empty())
+                && !item_starts.contains(&next)
+                && items
+                    .iter()
+                    .any(|item| item.start_line <= line_number && next <= item.end_line)
+        };
+
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
 
@@ -40,24 +77,55 @@ impl Rule for MD009 {
             if trimmed_end.ends_with(' ') || trimmed_end.ends_with('\t') {
                 let trailing_start = trimmed_end.trim_end().len();
                 let trailing_count = trimmed_end.len() - trailing_start;
+                let trailing = &trimmed_end[trailing_start..];
+
+                // Whitespace inside multi-line math or a code span is content
+                if literal_spans
+                    .get(idx)
+                    .is_some_and(|spans| spans.iter().any(|span| span.contains(&trailing_start)))
+                {
+                    continue;
+                }
+
+                // A list item's hard break keeps `br_spaces` spaces; extra
+                // spaces are trimmed back to that rather than removed, which
+                // would join the lines
+                let hard_break = !trailing.contains('\t') && breaks_list_item(line_number);
+                if hard_break && trailing_count == br_spaces {
+                    continue;
+                }
+                let (expected, kept, suggestion) = if hard_break && trailing_count > br_spaces {
+                    (
+                        format!("0 or {}", br_spaces),
+                        br_spaces,
+                        format!("Keep {} trailing spaces for the line break", br_spaces),
+                    )
+                } else {
+                    ("0".to_string(), 0, "Remove trailing spaces".to_string())
+                };
 
                 errors.push(LintError {
                     line_number,
                     rule_names: self.names(),
                     rule_description: self.description(),
-                    error_detail: Some(format!("Expected: 0; Actual: {}", trailing_count)),
-                    error_context: Some(trimmed_end[trailing_start..].to_string()),
+                    error_detail: Some(format!(
+                        "Expected: {}; Actual: {}",
+                        expected, trailing_count
+                    )),
+                    error_context: Some(trailing.to_string()),
                     rule_information: self.information(),
                     error_range: Some((trailing_start + 1, trailing_count)),
                     fix_info: Some(FixInfo {
                         line_number: None,
-                        edit_column: Some(trailing_start + 1),
-                        delete_count: Some(trailing_count as i32),
+                        edit_column: Some(trailing_start + kept + 1),
+                        delete_count: Some((trailing_count - kept) as i32),
                         insert_text: None,
                     }),
-                    suggestion: Some("Remove trailing spaces".to_string()),
+                    suggestion: Some(suggestion),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
@@ -145,6 +213,55 @@ mod tests {
         assert_eq!(MD009.lint(&params).len(), 1);
     }
 
+    fn lint_markdown(content: &str, config: &HashMap<String, serde_json::Value>) -> Vec<LintError> {
+        let tokens = crate::parser::parse(content);
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, config);
+        MD009.lint(&params)
+    }
+
+    #[test]
+    fn test_md009_list_item_hard_break_kept() {
+        let content = "- first line  \n  continued\n- second item\n";
+        assert!(lint_markdown(content, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_md009_list_item_extra_spaces_trimmed_to_break() {
+        let content = "- first line   \n  continued\n";
+        let errors = lint_markdown(content, &HashMap::new());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Expected: 0 or 2; Actual: 3")
+        );
+        assert_eq!(
+            crate::lint::apply_fixes(content, &errors),
+            "- first line  \n  continued\n"
+        );
+    }
+
+    #[test]
+    fn test_md009_list_item_break_without_continuation_removed() {
+        // At the end of an item, or before a nested item, nothing is broken
+        let content = "- one  \n- two  \n  - nested  \n\ntext  \nmore\n";
+        let errors = lint_markdown(content, &HashMap::new());
+        let lines: Vec<usize> = errors.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, [1, 2, 3, 5]);
+        assert_eq!(
+            crate::lint::apply_fixes(content, &errors),
+            "- one\n- two\n  - nested\n\ntext\nmore\n"
+        );
+    }
+
+    #[test]
+    fn test_md009_br_spaces_below_two_disables_breaks() {
+        let config = HashMap::from([("br_spaces".to_string(), serde_json::json!(0))]);
+        let errors = lint_markdown("- first line  \n  continued\n", &config);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].fix_info.as_ref().unwrap().delete_count, Some(2));
+    }
+
     #[test]
     fn test_md009_empty_lines_no_error() {
         let lines = vec!["\n", "\n"];
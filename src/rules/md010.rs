@@ -19,6 +19,10 @@ impl Rule for MD010 {
         &["whitespace", "hard_tab", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::None
     }
@@ -29,13 +33,23 @@ impl Rule for MD010 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
+        let check_code_blocks = params
+            .config
+            .get("code_blocks")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let code_block_lines = params.code_block_lines();
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
 
+            if code_block_lines[idx] && !check_code_blocks {
+                continue;
+            }
+
             // Find all tab characters in the line
-            let mut column = 1;
-            for ch in line.chars() {
+            for (char_idx, ch) in line.chars().enumerate() {
+                let column = char_idx + 1;
                 if ch == '\t' {
                     errors.push(LintError {
                         line_number,
@@ -54,9 +68,10 @@ impl Rule for MD010 {
                         suggestion: Some("Replace hard tabs with spaces".to_string()),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
-                column += 1;
 
                 // Stop at newline
                 if ch == '\n' || ch == '\r' {
@@ -78,15 +93,8 @@ mod tests {
     fn test_md010_no_tabs() {
         let lines = vec!["# Heading\n", "    Indented with spaces\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD010;
         let errors = rule.lint(&params);
@@ -97,15 +105,8 @@ mod tests {
     fn test_md010_with_tabs() {
         let lines = vec!["\tTabbed line\n", "Normal\tline with tab\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD010;
         let errors = rule.lint(&params);
@@ -119,15 +120,8 @@ mod tests {
     #[test]
     fn test_md010_fix_info_correct_column() {
         let lines = vec!["abc\tdef\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
         let rule = MD010;
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 1);
@@ -142,15 +136,8 @@ mod tests {
     #[test]
     fn test_md010_multiple_tabs_same_line() {
         let lines = vec!["\t\ttwo tabs\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
         let rule = MD010;
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 2);
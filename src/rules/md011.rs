@@ -63,6 +63,8 @@ impl Rule for MD011 {
                     ),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
@@ -80,15 +82,8 @@ mod tests {
     fn test_md011_correct_syntax() {
         let lines = vec!["[text](link)\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD011;
         let errors = rule.lint(&params);
@@ -99,15 +94,8 @@ mod tests {
     fn test_md011_reversed_syntax() {
         let lines = vec!["(text)[link]\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD011;
         let errors = rule.lint(&params);
@@ -118,15 +106,8 @@ mod tests {
     fn test_md011_fix_info() {
         let lines = vec!["(text)[link]\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD011;
         let errors = rule.lint(&params);
@@ -146,15 +127,8 @@ mod tests {
     fn test_md011_fix_info_with_offset() {
         let lines = vec!["See (hello)[world] for details\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD011;
         let errors = rule.lint(&params);
@@ -1,8 +1,24 @@
 //! MD012 - Multiple consecutive blank lines
 //!
-//! This rule checks for multiple consecutive blank lines.
-
-use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+//! This rule checks for multiple consecutive blank lines. Blank lines that
+//! are part of an HTML block or an HTML comment are structural and are not
+//! counted.
+
+use crate::types::{FixInfo, LintError, Rule, RuleParams, Severity};
+use std::collections::HashSet;
+
+/// Collect 1-based line numbers covered by HTML blocks and HTML comments.
+fn html_block_lines(params: &RuleParams) -> HashSet<usize> {
+    let mut lines = HashSet::new();
+    for token in params.tokens {
+        let is_html = token.token_type == "htmlBlock"
+            || (token.token_type == "htmlInline" && token.text.starts_with("<!--"));
+        if is_html && token.end_line > token.start_line {
+            lines.extend(token.start_line..=token.end_line);
+        }
+    }
+    lines
+}
 
 pub struct MD012;
 
@@ -19,8 +35,8 @@ impl Rule for MD012 {
         &["whitespace", "blank_lines", "fixable"]
     }
 
-    fn parser_type(&self) -> ParserType {
-        ParserType::None
+    fn fix_priority(&self) -> i32 {
+        -1
     }
 
     fn information(&self) -> Option<&'static str> {
@@ -31,12 +47,13 @@ impl Rule for MD012 {
         let mut errors = Vec::new();
         let mut blank_count = 0;
         let mut first_blank_line = 0;
+        let html_lines = html_block_lines(params);
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
             let trimmed = line.trim();
 
-            if trimmed.is_empty() {
+            if trimmed.is_empty() && !html_lines.contains(&line_number) {
                 if blank_count == 0 {
                     first_blank_line = line_number;
                 }
@@ -62,24 +79,34 @@ impl Rule for MD012 {
                         suggestion: Some("Remove consecutive blank lines".to_string()),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
                 blank_count = 0;
             }
         }
 
-        // Check if file ends with multiple blanks
-        if blank_count > 1 {
+        // Check if file ends with multiple blanks, allowing as many as
+        // MD047's `newlines` requires so the two fixes don't fight
+        let trailing_allowed = params
+            .sibling_option("MD047", "newlines")
+            .and_then(|v| v.as_u64())
+            .map_or(1, |n| (n as usize).saturating_sub(1).max(1));
+        if blank_count > trailing_allowed {
             errors.push(LintError {
-                line_number: first_blank_line + 1,
+                line_number: first_blank_line + trailing_allowed,
                 rule_names: self.names(),
                 rule_description: self.description(),
-                error_detail: Some(format!("Expected: 1; Actual: {}", blank_count)),
+                error_detail: Some(format!(
+                    "Expected: {}; Actual: {}",
+                    trailing_allowed, blank_count
+                )),
                 error_context: None,
                 rule_information: self.information(),
                 error_range: None,
                 fix_info: Some(FixInfo {
-                    line_number: Some(first_blank_line + 1),
+                    line_number: Some(first_blank_line + trailing_allowed),
                     edit_column: Some(1),
                     delete_count: Some(-1),
                     insert_text: None,
@@ -87,6 +114,8 @@ impl Rule for MD012 {
                 suggestion: Some("Remove consecutive blank lines".to_string()),
                 severity: Severity::Error,
                 fix_only: false,
+                line_text: None,
+                related: None,
             });
         }
 
@@ -180,6 +209,28 @@ mod tests {
         assert_eq!(fix.delete_count, Some(-1)); // whole-line delete
     }
 
+    #[test]
+    fn test_md012_blank_lines_inside_html_comment() {
+        let content = "# Heading\n\n<!--\nnote\n\n\nmore\n-->\n\nContent\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
+        assert_eq!(MD012.lint(&params).len(), 0);
+    }
+
+    #[test]
+    fn test_md012_blank_lines_in_prose_with_tokens() {
+        let content = "# Heading\n\nText\n\n\nContent\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
+        let errors = MD012.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 5);
+    }
+
     #[test]
     fn test_md012_only_blank_lines() {
         let lines = vec!["\n", "\n", "\n"];
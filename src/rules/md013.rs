@@ -6,6 +6,44 @@ use crate::types::{LintError, ParserType, Rule, RuleParams, Severity};
 
 pub struct MD013;
 
+/// Whether a line holds nothing but HTML comment text, given whether a
+/// comment is open at its start; updates `in_comment` for the next line.
+fn is_comment_line(line: &str, in_comment: &mut bool) -> bool {
+    let mut rest = line.trim();
+    let mut only_comment = *in_comment || rest.starts_with("<!--");
+    loop {
+        if *in_comment {
+            let Some(end) = rest.find("-->") else {
+                return only_comment;
+            };
+            *in_comment = false;
+            rest = &rest[end + 3..];
+        } else {
+            let Some(start) = rest.find("<!--") else {
+                return only_comment && rest.trim().is_empty();
+            };
+            only_comment &= rest[..start].trim().is_empty();
+            *in_comment = true;
+            rest = &rest[start + 4..];
+        }
+    }
+}
+
+/// Display width of each character of `line`, in order, with tabs
+/// expanded to the next multiple of `tab_size` columns
+fn char_widths(line: &str, tab_size: usize) -> impl Iterator<Item = usize> + '_ {
+    let mut column = 0;
+    line.chars().map(move |c| {
+        let width = if c == '\t' {
+            tab_size - column % tab_size
+        } else {
+            1
+        };
+        column += width;
+        width
+    })
+}
+
 impl Rule for MD013 {
     fn names(&self) -> &'static [&'static str] {
         &["MD013", "line-length"]
@@ -34,25 +72,56 @@ impl Rule for MD013 {
             .get("line_length")
             .and_then(|v| v.as_u64())
             .unwrap_or(80) as usize;
-        let mut in_code_block = false;
+        let check_code_blocks = params
+            .config
+            .get("code_blocks")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let tab_size = params
+            .config
+            .get("tab_size")
+            .and_then(|v| v.as_u64())
+            .filter(|&n| n > 0)
+            .unwrap_or(4) as usize;
+        let ignore_html_comments = params
+            .config
+            .get("ignore_html_comments")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let code_block_lines = params.code_block_lines();
+        let mut in_comment = false;
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
             let trimmed = line.trim_end_matches('\n').trim_end_matches('\r');
 
-            // Check for code block fences
-            if crate::helpers::is_code_fence(trimmed) {
-                in_code_block = !in_code_block;
-                continue;
-            }
+            // Track comments on every non-code line so `-->` is never missed
+            let comment_line = !code_block_lines[idx] && is_comment_line(trimmed, &mut in_comment);
 
-            // Skip code blocks, tables, and headings
-            if in_code_block || trimmed.starts_with('|') || trimmed.starts_with('#') {
+            // Skip code blocks (unless configured), tables, headings, and
+            // HTML comments such as inline configuration directives
+            if code_block_lines[idx] {
+                if !check_code_blocks {
+                    continue;
+                }
+            } else if trimmed.starts_with('|')
+                || trimmed.starts_with('#')
+                || (comment_line && ignore_html_comments)
+            {
                 continue;
             }
 
-            let actual_length = trimmed.chars().count();
+            let actual_length: usize = char_widths(trimmed, tab_size).sum();
             if actual_length > line_length {
+                // The range starts at the first character past the limit
+                let mut width = 0;
+                let over = char_widths(trimmed, tab_size)
+                    .take_while(|w| {
+                        width += w;
+                        width <= line_length
+                    })
+                    .count();
+                let char_count = trimmed.chars().count();
                 errors.push(LintError {
                     line_number,
                     rule_names: self.names(),
@@ -68,13 +137,15 @@ impl Rule for MD013 {
                         trimmed.to_string()
                     }),
                     rule_information: self.information(),
-                    error_range: Some((line_length + 1, actual_length - line_length)),
+                    error_range: Some((over + 1, char_count - over)),
                     fix_info: None,
                     suggestion: Some(
                         "Consider breaking long lines for better readability".to_string(),
                     ),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
@@ -92,15 +163,8 @@ mod tests {
     fn test_md013_short_line() {
         let lines = vec!["Short line\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD013;
         let errors = rule.lint(&params);
@@ -112,34 +176,50 @@ mod tests {
         let long_line = "a".repeat(100) + "\n";
         let lines = vec![long_line.as_str()];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD013;
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 1);
     }
 
+    #[test]
+    fn test_md013_expands_tabs() {
+        // 1 tab + 77 characters: 81 columns with 4-column tabs (a leading
+        // tab makes the line an indented code block)
+        let line = format!("\t{}\n", "a".repeat(77));
+        let lines = vec![line.as_str()];
+        let config = HashMap::from([("code_blocks".to_string(), serde_json::json!(true))]);
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD013.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Expected: 80; Actual: 81")
+        );
+        // Columns 1-80 are the tab and 76 characters
+        assert_eq!(errors[0].error_range, Some((78, 1)));
+
+        // Tabs advance to the next tab stop
+        let config = HashMap::from([
+            ("code_blocks".to_string(), serde_json::json!(true)),
+            ("tab_size".to_string(), serde_json::json!(2)),
+        ]);
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD013.lint(&params).is_empty());
+        let line = format!("-\t{}\n", "b".repeat(77));
+        let lines = vec![line.as_str()];
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD013.lint(&params).is_empty());
+    }
+
     #[test]
     fn test_md013_code_block_excluded() {
         let long_code = "a".repeat(120) + "\n";
         let lines = vec!["```\n", long_code.as_str(), "```\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
         let rule = MD013;
         let errors = rule.lint(&params);
         assert_eq!(
@@ -153,17 +233,55 @@ mod tests {
     fn test_md013_heading_excluded() {
         let long_heading = format!("# {}\n", "a".repeat(120));
         let lines = vec![long_heading.as_str()];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
         let rule = MD013;
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 0, "Long headings should be excluded");
     }
+
+    #[test]
+    fn test_md013_html_comments_excluded() {
+        let directive = format!(
+            "<!-- markdownlint-disable-next-line MD033 {} -->\n",
+            "x".repeat(80)
+        );
+        let open = format!("<!-- {}\n", "a".repeat(90));
+        let inside = format!("{}\n", "b".repeat(90));
+        let close = format!("{} -->\n", "c".repeat(90));
+        let prose = format!("{}\n", "d".repeat(90));
+        let lines = vec![
+            directive.as_str(),
+            open.as_str(),
+            inside.as_str(),
+            close.as_str(),
+            prose.as_str(),
+        ];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD013.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 5);
+    }
+
+    #[test]
+    fn test_md013_text_after_comment_is_checked() {
+        let line = format!("<!-- note --> {}\n", "a".repeat(90));
+        let lines = vec![line.as_str()];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert_eq!(MD013.lint(&params).len(), 1);
+    }
+
+    #[test]
+    fn test_md013_html_comments_option_off() {
+        let comment = format!("<!-- {} -->\n", "a".repeat(90));
+        let lines = vec![comment.as_str()];
+        let config = HashMap::from([(
+            "ignore_html_comments".to_string(),
+            serde_json::Value::Bool(false),
+        )]);
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert_eq!(MD013.lint(&params).len(), 1);
+    }
 }
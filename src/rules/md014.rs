@@ -1,11 +1,62 @@
 //! MD014 - Dollar signs used before commands without showing output
 //!
-//! This rule checks for code blocks that show commands with $ prefix
+//! This rule checks for code blocks in which every line is a `$`-prefixed
+//! command. Blocks that mix commands with their output keep their prompts.
 
+use crate::helpers::{CodeFenceTracker, fence_line};
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
 pub struct MD014;
 
+/// A fenced or indented code block's content lines
+struct CodeBlock {
+    /// First word of the fence info string, if any
+    language: Option<String>,
+    /// 0-based indices of the content lines, fences excluded
+    lines: Vec<usize>,
+}
+
+/// Split the document into code blocks
+fn code_blocks(params: &RuleParams) -> Vec<CodeBlock> {
+    let flags = params.code_block_lines();
+    let mut blocks = Vec::new();
+    let mut fences = CodeFenceTracker::new();
+    let mut current: Option<CodeBlock> = None;
+
+    for (idx, line) in params.lines.iter().enumerate() {
+        let was_fenced = fences.in_code_block();
+        if fences.update(line) {
+            if !was_fenced {
+                blocks.extend(current.take());
+                let language = fence_line(line)
+                    .and_then(|fence| fence.info.split_whitespace().next())
+                    .map(str::to_string);
+                current = Some(CodeBlock {
+                    language,
+                    lines: Vec::new(),
+                });
+            } else if !fences.in_code_block() {
+                blocks.extend(current.take());
+            } else if let Some(block) = current.as_mut() {
+                block.lines.push(idx);
+            }
+        } else if flags.get(idx).copied().unwrap_or(false) {
+            current
+                .get_or_insert_with(|| CodeBlock {
+                    language: None,
+                    lines: Vec::new(),
+                })
+                .lines
+                .push(idx);
+        } else if !line.trim().is_empty() {
+            // Blank lines may sit inside an indented block
+            blocks.extend(current.take());
+        }
+    }
+    blocks.extend(current);
+    blocks
+}
+
 impl Rule for MD014 {
     fn names(&self) -> &'static [&'static str] {
         &["MD014", "commands-show-output"]
@@ -28,28 +79,53 @@ impl Rule for MD014 {
     }
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        let ignore_languages: Vec<String> = params
+            .config
+            .get("ignore_languages")
+            .and_then(|v| v.as_array())
+            .map(|langs| {
+                langs
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .map(str::to_lowercase)
+                    .collect()
+            })
+            .unwrap_or_default();
+
         let mut errors = Vec::new();
-        let mut in_code_block = false;
-        for (idx, line) in params.lines.iter().enumerate() {
-            let line_number = idx + 1;
-            let trimmed = line.trim();
-
-            if crate::helpers::is_code_fence(trimmed) {
-                in_code_block = !in_code_block;
-            } else if in_code_block && trimmed.starts_with('$') {
-                // Calculate position and deletion count
-                let leading_ws = line.len() - line.trim_start().len();
-                let dollar_pos = leading_ws + 1; // 1-based column
+        for block in code_blocks(params) {
+            if block
+                .language
+                .as_ref()
+                .is_some_and(|lang| ignore_languages.contains(&lang.to_lowercase()))
+            {
+                continue;
+            }
+
+            let commands: Vec<usize> = block
+                .lines
+                .iter()
+                .copied()
+                .filter(|&idx| !params.lines[idx].trim().is_empty())
+                .collect();
+            // A block showing any output keeps its prompts
+            if commands.is_empty()
+                || !commands
+                    .iter()
+                    .all(|&idx| params.lines[idx].trim_start().starts_with('$'))
+            {
+                continue;
+            }
 
-                // Check if there's a space after $
-                let delete_count = if trimmed.len() > 1 && trimmed.chars().nth(1) == Some(' ') {
-                    2 // Delete "$ "
-                } else {
-                    1 // Delete "$"
-                };
+            for idx in commands {
+                let line = params.lines[idx];
+                let trimmed = line.trim();
+                let leading_ws = line.len() - line.trim_start().len();
+                // Strip "$ " or a bare "$"
+                let delete_count = if trimmed[1..].starts_with(' ') { 2 } else { 1 };
 
                 errors.push(LintError {
-                    line_number,
+                    line_number: idx + 1,
                     rule_names: self.names(),
                     rule_description: self.description(),
                     error_detail: None,
@@ -58,13 +134,15 @@ impl Rule for MD014 {
                     error_range: Some((1, line.len())),
                     fix_info: Some(FixInfo {
                         line_number: None,
-                        edit_column: Some(dollar_pos),
+                        edit_column: Some(leading_ws + 1),
                         delete_count: Some(delete_count),
                         insert_text: None,
                     }),
                     suggestion: Some("Remove the $ prefix from this command".to_string()),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
@@ -82,15 +160,8 @@ mod tests {
     fn test_md014_no_dollar_signs() {
         let lines = vec!["```bash\n", "echo hello\n", "```\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD014;
         let errors = rule.lint(&params);
@@ -101,15 +172,8 @@ mod tests {
     fn test_md014_with_dollar_signs() {
         let lines = vec!["```bash\n", "$ echo hello\n", "```\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD014;
         let errors = rule.lint(&params);
@@ -120,15 +184,8 @@ mod tests {
     fn test_md014_fix_dollar_with_space() {
         let lines = vec!["```bash\n", "$ echo hello\n", "```\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD014;
         let errors = rule.lint(&params);
@@ -143,15 +200,8 @@ mod tests {
     fn test_md014_fix_dollar_without_space() {
         let lines = vec!["```bash\n", "$echo hello\n", "```\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD014;
         let errors = rule.lint(&params);
@@ -166,15 +216,8 @@ mod tests {
     fn test_md014_fix_indented_dollar() {
         let lines = vec!["```bash\n", "  $ echo hello\n", "```\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD014;
         let errors = rule.lint(&params);
@@ -184,4 +227,58 @@ mod tests {
         assert_eq!(fix.delete_count, Some(2)); // "$ "
         assert_eq!(fix.insert_text, None);
     }
+
+    fn lint_md014(content: &str, config: HashMap<String, serde_json::Value>) -> Vec<LintError> {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let params = RuleParams::test(&lines, &config);
+        MD014.lint(&params)
+    }
+
+    #[test]
+    fn test_md014_all_commands_fixed_together() {
+        let content = "```sh\n$ npm install\n\n  $ npm test\n```\n";
+        let errors = lint_md014(content, HashMap::new());
+        assert_eq!(
+            errors.iter().map(|e| e.line_number).collect::<Vec<_>>(),
+            [2, 4]
+        );
+        assert_eq!(
+            crate::apply_fixes(content, &errors),
+            "```sh\nnpm install\n\n  npm test\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_md014_commands_with_output() {
+        let content = "```bash\n$ npm test\nAll tests passed\n$ ls\nREADME.md\n```\n";
+        assert!(lint_md014(content, HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_md014_blocks_judged_separately() {
+        let content = "```\n$ ls\nREADME.md\n```\n\n```\n$ make\n```\n";
+        let errors = lint_md014(content, HashMap::new());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 7);
+    }
+
+    #[test]
+    fn test_md014_indented_code_block() {
+        let content = "Run:\n\n    $ make\n    $ make install\n\nText\n";
+        let errors = lint_md014(content, HashMap::new());
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].fix_info.as_ref().unwrap().edit_column, Some(5));
+    }
+
+    #[test]
+    fn test_md014_ignore_languages() {
+        let content = "```Console\n$ make\n```\n";
+        assert_eq!(lint_md014(content, HashMap::new()).len(), 1);
+
+        let config = HashMap::from([(
+            "ignore_languages".to_string(),
+            serde_json::json!(["console"]),
+        )]);
+        assert!(lint_md014(content, config).is_empty());
+    }
 }
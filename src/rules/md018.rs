@@ -2,6 +2,7 @@
 //!
 //! This rule checks that ATX headings have a space after the hash
 
+use crate::helpers::atx_opening;
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
 pub struct MD018;
@@ -29,53 +30,51 @@ impl Rule for MD018 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
+        let code_block_lines = params.code_block_lines();
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
-            let trimmed = line.trim_start();
-
-            // Check for ATX heading without space
-            if trimmed.starts_with('#') {
-                let hash_count = trimmed.chars().take_while(|&c| c == '#').count();
-                if hash_count > 0 && hash_count <= 6 {
-                    let after_hash = &trimmed[hash_count..];
-                    // Skip if nothing follows the hashes (empty heading),
-                    // if content is only whitespace/newlines (avoids MD009 oscillation),
-                    // or if a space/tab already exists.
-                    if !after_hash.is_empty()
-                        && !after_hash.trim().is_empty()
-                        && !after_hash.starts_with(' ')
-                        && !after_hash.starts_with('\t')
-                    {
-                        errors.push(LintError {
-                            line_number,
-                            rule_names: self.names(),
-                            rule_description: self.description(),
-                            error_detail: None,
-                            error_context: Some(
-                                trimmed
-                                    .chars()
-                                    .take(hash_count + 10.min(after_hash.chars().count()))
-                                    .collect(),
-                            ),
-                            rule_information: self.information(),
-                            error_range: Some((hash_count + 1, 1)),
-                            fix_info: Some(FixInfo {
-                                line_number: None,
-                                edit_column: Some(hash_count + 1),
-                                delete_count: None,
-                                insert_text: Some(" ".to_string()),
-                            }),
-                            suggestion: Some(format!(
-                                "Add a space after the # symbol: '{} {}'",
-                                "#".repeat(hash_count),
-                                after_hash.trim()
-                            )),
-                            severity: Severity::Error,
-                            fix_only: false,
-                        });
-                    }
-                }
+            if code_block_lines[idx] {
+                continue;
+            }
+            let Some(opening) = atx_opening(line) else {
+                continue;
+            };
+            let (indent, hash_count, after_hash) = (opening.indent, opening.level, opening.rest);
+
+            // Skip if nothing but a line ending follows the hashes (empty
+            // heading; avoids MD009 oscillation) or if any whitespace, tabs
+            // included, already exists (MD019 normalizes it).
+            if opening.whitespace.is_empty() && !after_hash.trim().is_empty() {
+                errors.push(LintError {
+                    line_number,
+                    rule_names: self.names(),
+                    rule_description: self.description(),
+                    error_detail: None,
+                    error_context: Some(
+                        line[indent..]
+                            .chars()
+                            .take(hash_count + 10.min(after_hash.chars().count()))
+                            .collect(),
+                    ),
+                    rule_information: self.information(),
+                    error_range: Some((indent + hash_count + 1, 1)),
+                    fix_info: Some(FixInfo {
+                        line_number: None,
+                        edit_column: Some(indent + hash_count + 1),
+                        delete_count: None,
+                        insert_text: Some(" ".to_string()),
+                    }),
+                    suggestion: Some(format!(
+                        "Add a space after the # symbol: '{} {}'",
+                        "#".repeat(hash_count),
+                        after_hash.trim()
+                    )),
+                    severity: Severity::Error,
+                    fix_only: false,
+                    line_text: None,
+                    related: None,
+                });
             }
         }
 
@@ -92,15 +91,8 @@ mod tests {
     fn test_md018_with_space() {
         let lines = vec!["# Heading\n", "## Heading 2\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD018;
         let errors = rule.lint(&params);
@@ -111,15 +103,8 @@ mod tests {
     fn test_md018_no_space() {
         let lines = vec!["#Heading\n", "##Heading 2\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD018;
         let errors = rule.lint(&params);
@@ -130,15 +115,8 @@ mod tests {
     #[test]
     fn test_md018_fix_info_inserts_space() {
         let lines = vec!["#Title\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
         let rule = MD018;
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 1);
@@ -152,15 +130,8 @@ mod tests {
     fn test_md018_empty_hash_no_error() {
         // A lone "#" with nothing meaningful after it should not trigger
         let lines = vec!["#\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
         let rule = MD018;
         let errors = rule.lint(&params);
         assert_eq!(
@@ -169,4 +140,41 @@ mod tests {
             "Lone # with no content should not trigger MD018"
         );
     }
+
+    #[test]
+    fn test_md018_tab_or_ideographic_space_is_whitespace() {
+        let lines = vec!["#\tHeading\n", "#\u{3000}見出し\n", "# 🎉 Party\n"];
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
+        assert!(MD018.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_md018_skips_code_blocks() {
+        let lines = vec!["```sh\n", "#comment\n", "```\n", "\n", "    #comment\n"];
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
+        assert!(MD018.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_md018_emoji_after_hash() {
+        let content = "#🎉 Party\n";
+        let lines = vec![content];
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
+        let errors = MD018.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(crate::lint::apply_fixes(content, &errors), "# 🎉 Party\n");
+    }
+
+    #[test]
+    fn test_md018_indented_fix_column() {
+        let content = "  ##Heading\n";
+        let lines = vec![content];
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
+        let errors = MD018.lint(&params);
+        assert_eq!(crate::lint::apply_fixes(content, &errors), "  ## Heading\n");
+    }
 }
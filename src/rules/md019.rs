@@ -1,6 +1,7 @@
 //! MD019 - Multiple spaces after hash on atx style heading
 
-use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+use crate::helpers::{atx_opening, describe_whitespace, normalize_whitespace_fix};
+use crate::types::{LintError, ParserType, Rule, RuleParams, Severity};
 
 pub struct MD019;
 
@@ -27,40 +28,47 @@ impl Rule for MD019 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
+        let code_block_lines = params.code_block_lines();
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
-            let trimmed = line.trim_start();
-
-            if trimmed.starts_with('#') {
-                let hash_count = trimmed.chars().take_while(|&c| c == '#').count();
-                if hash_count > 0 && hash_count <= 6 {
-                    let after_hash = &trimmed[hash_count..];
-                    let space_count = after_hash.chars().take_while(|&c| c == ' ').count();
-
-                    if space_count > 1 {
-                        errors.push(LintError {
-                            line_number,
-                            rule_names: self.names(),
-                            rule_description: self.description(),
-                            error_detail: Some(format!("Expected: 1; Actual: {}", space_count)),
-                            error_context: None,
-                            rule_information: self.information(),
-                            error_range: Some((hash_count + 2, space_count - 1)),
-                            fix_info: Some(FixInfo {
-                                line_number: None,
-                                edit_column: Some(hash_count + 2),
-                                delete_count: Some((space_count - 1) as i32),
-                                insert_text: None,
-                            }),
-                            suggestion: Some(
-                                "Remove multiple spaces after hash on ATX heading".to_string(),
-                            ),
-                            severity: Severity::Error,
-                            fix_only: false,
-                        });
-                    }
-                }
+            if code_block_lines[idx] {
+                continue;
+            }
+            let Some(opening) = atx_opening(line) else {
+                continue;
+            };
+            let whitespace = opening.whitespace;
+
+            // Anything other than exactly one ASCII space (tabs, ideographic
+            // spaces, runs) is normalized to one space
+            if !whitespace.is_empty() && whitespace != " " {
+                let fix = normalize_whitespace_fix(whitespace, opening.indent + opening.level + 1);
+                // Highlight the whitespace the fix removes or replaces
+                let error_range = fix
+                    .edit_column
+                    .zip(fix.delete_count)
+                    .map(|(col, len)| (col, len as usize));
+                errors.push(LintError {
+                    line_number,
+                    rule_names: self.names(),
+                    rule_description: self.description(),
+                    error_detail: Some(format!(
+                        "Expected: 1; Actual: {}",
+                        describe_whitespace(whitespace)
+                    )),
+                    error_context: None,
+                    rule_information: self.information(),
+                    error_range,
+                    fix_info: Some(fix),
+                    suggestion: Some(
+                        "Remove multiple spaces after hash on ATX heading".to_string(),
+                    ),
+                    severity: Severity::Error,
+                    fix_only: false,
+                    line_text: None,
+                    related: None,
+                });
             }
         }
 
@@ -154,4 +162,68 @@ mod tests {
             Some("Expected: 1; Actual: 4")
         );
     }
+
+    fn fix(content: &str) -> String {
+        let lines = vec![content];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        crate::lint::apply_fixes(content, &MD019.lint(&params))
+    }
+
+    #[test]
+    fn test_md019_tab_after_hash() {
+        for (line, actual) in [("#\tHeading\n", "tab"), ("## \tHeading\n", "space, tab")] {
+            let lines = vec![line];
+            let config = HashMap::new();
+            let params = crate::types::RuleParams::test(&lines, &config);
+            let errors = MD019.lint(&params);
+            assert_eq!(
+                errors[0].error_detail,
+                Some(format!("Expected: 1; Actual: {}", actual))
+            );
+        }
+        assert_eq!(fix("#\tHeading\n"), "# Heading\n");
+        assert_eq!(fix("## \tHeading\n"), "## Heading\n");
+    }
+
+    #[test]
+    fn test_md019_ideographic_space() {
+        let lines = vec!["#\u{3000}見出し\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD019.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Expected: 1; Actual: ideographic space")
+        );
+        assert_eq!(fix("#\u{3000}見出し\n"), "# 見出し\n");
+        assert_eq!(fix("#\u{3000}\u{3000}見出し\n"), "# 見出し\n");
+    }
+
+    #[test]
+    fn test_md019_skips_code_blocks() {
+        let content = "# Title\n\n```sh\n#\tcomment\n#\u{3000}x\n#  two\n```\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD019.lint(&params).is_empty());
+        // MD010 would still replace the tab
+        let config: crate::Config = serde_json::from_str(r#"{"MD010": false}"#).unwrap();
+        assert_eq!(crate::fix_content(content, &config).unwrap(), content);
+    }
+
+    #[test]
+    fn test_md019_emoji_heading() {
+        let lines = vec!["# 🎉 Party\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD019.lint(&params).is_empty());
+        assert_eq!(fix("#   🎉 Party\n"), "# 🎉 Party\n");
+    }
+
+    #[test]
+    fn test_md019_indented_heading() {
+        assert_eq!(fix("  #   Heading\n"), "  # Heading\n");
+    }
 }
@@ -1,5 +1,6 @@
 //! MD020 - No space inside hashes on closed atx style heading
 
+use crate::helpers::strip_closing_sequence;
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
 pub struct MD020;
@@ -27,16 +28,31 @@ impl Rule for MD020 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
+        let code_block_lines = params.code_block_lines();
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
+            if code_block_lines[idx] {
+                continue;
+            }
             let trimmed = line.trim();
 
-            if trimmed.starts_with('#') && trimmed.ends_with('#') {
-                let content = trimmed.trim_start_matches('#').trim_end_matches('#');
+            let text = trimmed.trim_start_matches('#');
+            // A heading is closed by a run of `#` after whitespace, so `C#`
+            // is text. Without a space after the opening hashes either, the
+            // line is no heading at all and glued hashes on both sides
+            // (`#Heading#`) read as a closed heading missing both spaces.
+            let closed = strip_closing_sequence(text).is_some()
+                || (!text.starts_with(char::is_whitespace)
+                    && text.ends_with('#')
+                    && !text.trim_end_matches('#').ends_with('\\'));
+
+            if trimmed.starts_with('#') && closed {
+                let content = text.trim_end_matches('#');
                 if !content.is_empty() {
-                    let has_start_space = content.starts_with(' ');
-                    let has_end_space = content.ends_with(' ');
+                    // Any whitespace counts here; MD021 normalizes it to one space
+                    let has_start_space = content.starts_with(char::is_whitespace);
+                    let has_end_space = content.ends_with(char::is_whitespace);
 
                     // Calculate positions for fix_info
                     let leading_hashes = trimmed.chars().take_while(|&c| c == '#').count();
@@ -61,6 +77,8 @@ impl Rule for MD020 {
                             suggestion: Some("Add space after opening #".to_string()),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
                     }
 
@@ -83,6 +101,8 @@ impl Rule for MD020 {
                             suggestion: Some("Add space before closing #".to_string()),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
                     }
                 }
@@ -147,18 +167,21 @@ mod tests {
     }
 
     #[test]
-    fn test_md020_fix_missing_end_space() {
-        let lines: Vec<&str> = "# Heading#\n".lines().collect();
+    fn test_md020_skips_code_blocks() {
+        let lines = vec!["```sh\n", "#comment#\n", "```\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD020.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_md020_hash_ending_text_is_not_closing() {
+        // After a spaced opening, `#` glued to the text is part of it
+        let lines: Vec<&str> = "# Heading#\n\n## C#\n\n## Esc \\#\n".lines().collect();
         let tokens = vec![];
         let config = HashMap::new();
         let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let rule = MD020;
-        let errors = rule.lint(&params);
-        assert_eq!(errors.len(), 1);
-        let fix = errors[0].fix_info.as_ref().unwrap();
-        assert_eq!(fix.edit_column, Some(10)); // Before closing #
-        assert_eq!(fix.delete_count, None);
-        assert_eq!(fix.insert_text, Some(" ".to_string()));
+        assert!(MD020.lint(&params).is_empty());
     }
 
     #[test]
@@ -179,4 +202,26 @@ mod tests {
         assert_eq!(fix2.edit_column, Some(9));
         assert_eq!(fix2.insert_text, Some(" ".to_string()));
     }
+
+    #[test]
+    fn test_md020_unicode_whitespace_counts_as_space() {
+        let lines = vec!["#\tHeading\t#\n", "#\u{3000}見出し\u{3000}#\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD020.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_md020_emoji_next_to_hash() {
+        let content = "#🎉 Party🎉#\n";
+        let lines = vec![content];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD020.lint(&params);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            crate::lint::apply_fixes(content, &errors),
+            "# 🎉 Party🎉 #\n"
+        );
+    }
 }
@@ -1,6 +1,10 @@
 //! MD021 - Multiple spaces inside hashes on closed atx style heading
 
-use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+use crate::helpers::{
+    describe_whitespace, leading_whitespace_len, normalize_whitespace_fix, strip_closing_sequence,
+    trailing_whitespace_len,
+};
+use crate::types::{LintError, ParserType, Rule, RuleParams, Severity};
 
 pub struct MD021;
 
@@ -27,62 +31,76 @@ impl Rule for MD021 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
+        let code_block_lines = params.code_block_lines();
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
+            if code_block_lines[idx] {
+                continue;
+            }
             let trimmed = line.trim();
 
-            if trimmed.starts_with('#') && trimmed.ends_with('#') {
-                let content = trimmed.trim_start_matches('#').trim_end_matches('#');
+            let text = trimmed.trim_start_matches('#');
+            if trimmed.starts_with('#') && strip_closing_sequence(text).is_some() {
+                let content = text.trim_end_matches('#');
                 if !content.is_empty() {
-                    let start_spaces = content.chars().take_while(|&c| c == ' ').count();
-                    let end_spaces = content.chars().rev().take_while(|&c| c == ' ').count();
+                    let start_ws = &content[..leading_whitespace_len(content)];
+                    let end_ws = &content[content.len() - trailing_whitespace_len(content)..];
 
                     // Calculate positions for fix_info
                     let leading_hashes = trimmed.chars().take_while(|&c| c == '#').count();
                     let trailing_hashes = trimmed.chars().rev().take_while(|&c| c == '#').count();
                     let leading_ws = line.len() - line.trim_start().len();
 
-                    if start_spaces > 1 {
+                    // Anything other than exactly one ASCII space (tabs,
+                    // ideographic spaces, runs) is normalized to one space
+                    if !start_ws.is_empty() && start_ws != " " {
                         errors.push(LintError {
                             line_number,
                             rule_names: self.names(),
                             rule_description: self.description(),
-                            error_detail: Some(format!("Expected: 1; Actual: {}", start_spaces)),
+                            error_detail: Some(format!(
+                                "Expected: 1; Actual: {}",
+                                describe_whitespace(start_ws)
+                            )),
                             error_context: Some(trimmed.to_string()),
                             rule_information: self.information(),
                             error_range: None,
-                            fix_info: Some(FixInfo {
-                                line_number: None,
-                                edit_column: Some(leading_ws + leading_hashes + 2), // After first space
-                                delete_count: Some((start_spaces - 1) as i32),
-                                insert_text: None,
-                            }),
+                            fix_info: Some(normalize_whitespace_fix(
+                                start_ws,
+                                leading_ws + leading_hashes + 1,
+                            )),
                             suggestion: Some("Remove extra spaces after opening #".to_string()),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
                     }
 
-                    if end_spaces > 1 {
+                    // Whitespace-only content is one run, reported once above
+                    if !end_ws.is_empty() && end_ws != " " && end_ws.len() < content.len() {
                         let content_end = trimmed.len() - trailing_hashes;
                         errors.push(LintError {
                             line_number,
                             rule_names: self.names(),
                             rule_description: self.description(),
-                            error_detail: Some(format!("Expected: 1; Actual: {}", end_spaces)),
+                            error_detail: Some(format!(
+                                "Expected: 1; Actual: {}",
+                                describe_whitespace(end_ws)
+                            )),
                             error_context: Some(trimmed.to_string()),
                             rule_information: self.information(),
                             error_range: None,
-                            fix_info: Some(FixInfo {
-                                line_number: None,
-                                edit_column: Some(leading_ws + content_end - end_spaces + 2), // After first space
-                                delete_count: Some((end_spaces - 1) as i32),
-                                insert_text: None,
-                            }),
+                            fix_info: Some(normalize_whitespace_fix(
+                                end_ws,
+                                leading_ws + content_end - end_ws.len() + 1,
+                            )),
                             suggestion: Some("Remove extra spaces before closing #".to_string()),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
                     }
                 }
@@ -174,4 +192,58 @@ mod tests {
         assert_eq!(fix.delete_count, Some(4)); // Delete 4 extra spaces
         assert_eq!(fix.insert_text, None);
     }
+
+    fn fix(content: &str) -> String {
+        let lines = vec![content];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        crate::lint::apply_fixes(content, &MD021.lint(&params))
+    }
+
+    #[test]
+    fn test_md021_tabs_inside_hashes() {
+        assert_eq!(fix("#\tHeading\t#\n"), "# Heading #\n");
+        assert_eq!(fix("## \tHeading\t ##\n"), "## Heading ##\n");
+    }
+
+    #[test]
+    fn test_md021_ideographic_space() {
+        assert_eq!(fix("#\u{3000}見出し\u{3000}#\n"), "# 見出し #\n");
+    }
+
+    #[test]
+    fn test_md021_skips_code_blocks() {
+        let lines = vec!["```sh\n", "#\u{3000}x\u{3000}#\n", "#  y  #\n", "```\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD021.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_md021_whitespace_detail() {
+        let lines = vec!["#\tHeading\u{3000}\u{3000}#\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let details: Vec<Option<String>> = MD021
+            .lint(&params)
+            .into_iter()
+            .map(|e| e.error_detail)
+            .collect();
+        assert_eq!(
+            details,
+            [
+                Some("Expected: 1; Actual: tab".to_string()),
+                Some("Expected: 1; Actual: 2 ideographic spaces".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_md021_emoji_heading() {
+        let lines = vec!["# 🎉 Party 🎉 #\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD021.lint(&params).is_empty());
+        assert_eq!(fix("#  🎉 Party 🎉  #\n"), "# 🎉 Party 🎉 #\n");
+    }
 }
@@ -18,6 +18,10 @@ impl Rule for MD022 {
         &["headings", "headers", "blank_lines", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        -1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::Micromark
     }
@@ -29,12 +33,14 @@ impl Rule for MD022 {
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
         let headings = params.tokens.filter_by_type("heading");
+        // The closing front matter delimiter counts as a blank line above
+        let first_content_line = params.content_start_line();
 
         for heading in headings {
             let line_num = heading.start_line;
 
             // Check line before heading
-            if line_num > 1 && (line_num - 2) < params.lines.len() {
+            if line_num > first_content_line && (line_num - 2) < params.lines.len() {
                 let prev_line = &params.lines[line_num - 2];
                 if !prev_line.trim().is_empty() {
                     errors.push(LintError {
@@ -56,6 +62,8 @@ impl Rule for MD022 {
                         ),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
@@ -83,6 +91,8 @@ impl Rule for MD022 {
                         ),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
@@ -119,15 +129,8 @@ mod tests {
             "More text\n",
         ];
         let tokens = vec![make_heading(1, 1), make_heading(5, 2)];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD022.lint(&params);
         assert_eq!(
@@ -141,15 +144,8 @@ mod tests {
     fn test_md022_missing_blank_before_heading() {
         let lines = vec!["# Title\n", "Some text\n", "## Section\n"];
         let tokens = vec![make_heading(1, 1), make_heading(3, 2)];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD022.lint(&params);
         let before_errors: Vec<_> = errors
@@ -164,15 +160,8 @@ mod tests {
     fn test_md022_missing_blank_after_heading() {
         let lines = vec!["# Title\n", "Some text\n"];
         let tokens = vec![make_heading(1, 1)];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD022.lint(&params);
         let after_errors: Vec<_> = errors
@@ -187,15 +176,8 @@ mod tests {
     fn test_md022_fix_info_inserts_blank_before() {
         let lines = vec!["# Title\n", "Some text\n", "## Section\n"];
         let tokens = vec![make_heading(1, 1), make_heading(3, 2)];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD022.lint(&params);
         let before_error = errors
@@ -216,15 +198,8 @@ mod tests {
         // First heading at line 1 should not complain about missing blank before
         let lines = vec!["# Title\n", "\n", "Content\n"];
         let tokens = vec![make_heading(1, 1)];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD022.lint(&params);
         assert_eq!(
@@ -238,15 +213,8 @@ mod tests {
     fn test_md022_fix_info_inserts_blank_after() {
         let lines = vec!["# Title\n", "Some text\n"];
         let tokens = vec![make_heading(1, 1)];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD022.lint(&params);
         let after_error = errors
@@ -259,4 +227,41 @@ mod tests {
         assert_eq!(fix.edit_column, Some(1));
         assert_eq!(fix.insert_text, Some("\n".to_string()));
     }
+
+    /// Lint through the full pipeline so front matter and tokens are real
+    fn lint_md022(content: &str, front_matter: Option<&str>) -> Vec<LintError> {
+        let config: crate::Config =
+            serde_json::from_str(r#"{"default": false, "MD022": true}"#).unwrap();
+        let mut options = crate::LintOptions::new()
+            .with_string("test.md", content)
+            .with_config(config);
+        options.front_matter = front_matter.map(String::from);
+        let results = crate::lint_sync(&options).unwrap();
+        results.get("test.md").unwrap().to_vec()
+    }
+
+    #[test]
+    fn test_md022_heading_right_after_front_matter() {
+        let content = "---\ntitle: Test\n---\n# Title\n\nText\n";
+        assert_eq!(lint_md022(content, Some("^---$")), vec![]);
+        // Without front matter detection the `---` is content
+        let errors = lint_md022(content, None);
+        assert!(errors.iter().any(|e| e.line_number == 4));
+    }
+
+    #[test]
+    fn test_md022_heading_on_line_one() {
+        assert!(lint_md022("# Title\n\nText\n", None).is_empty());
+    }
+
+    #[test]
+    fn test_md022_adjacent_headings_fixed_once() {
+        let content = "# One\n## Two\n\nText\n";
+        let errors = lint_md022(content, None);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            crate::apply_fixes(content, &errors),
+            "# One\n\n## Two\n\nText\n"
+        );
+    }
 }
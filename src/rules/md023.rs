@@ -64,6 +64,8 @@ impl Rule for MD023 {
                         ),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
@@ -74,6 +74,8 @@ impl Rule for MD024 {
                             )),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
                     }
                 }
@@ -113,15 +115,8 @@ mod tests {
             "\n",
             "## Conclusion\n",
         ];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD024.lint(&params);
         assert_eq!(errors.len(), 0, "Unique headings should have no errors");
@@ -135,15 +130,8 @@ mod tests {
             make_heading(5, "Setup", 2),
         ];
         let lines = vec!["## Setup\n", "\n", "## Usage\n", "\n", "## Setup\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD024.lint(&params);
         assert_eq!(errors.len(), 1);
@@ -159,15 +147,8 @@ mod tests {
             make_heading(5, "FAQ", 2),
         ];
         let lines = vec!["## FAQ\n", "\n", "## FAQ\n", "\n", "## FAQ\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD024.lint(&params);
         assert_eq!(errors.len(), 2, "Second and third occurrence should error");
@@ -180,15 +161,8 @@ mod tests {
             make_heading(3, "Overview", 2),
         ];
         let lines = vec!["# Overview\n", "\n", "## Overview\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD024.lint(&params);
         assert_eq!(
@@ -202,15 +176,8 @@ mod tests {
     fn test_md024_fix_info() {
         let tokens = vec![make_heading(1, "Title", 1), make_heading(3, "Title", 2)];
         let lines = vec!["# Title\n", "\n", "## Title\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD024.lint(&params);
         assert_eq!(errors.len(), 1);
@@ -227,15 +194,8 @@ mod tests {
             make_heading(5, "FAQ", 2),
         ];
         let lines = vec!["## FAQ\n", "\n", "## FAQ\n", "\n", "## FAQ\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD024.lint(&params);
         assert_eq!(errors.len(), 2);
@@ -255,15 +215,8 @@ mod tests {
     fn test_md024_fix_column_calculation() {
         let tokens = vec![make_heading(1, "Setup", 2), make_heading(3, "Setup", 2)];
         let lines = vec!["## Setup\n", "\n", "## Setup\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD024.lint(&params);
         assert_eq!(errors.len(), 1);
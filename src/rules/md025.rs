@@ -1,7 +1,11 @@
 //! MD025 - Multiple top-level headings in the same document
+//!
+//! Works on heading tokens, so ATX (`# Title`) and setext (`Title` over
+//! `=====`) headings count alike. The top level is `level` (default 1), and
+//! a front matter title (`front_matter_title`) takes the first slot.
 
 use crate::parser::TokenExt;
-use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+use crate::types::{LintError, ParserType, RelatedLine, Rule, RuleParams, Severity};
 
 pub struct MD025;
 
@@ -15,7 +19,7 @@ impl Rule for MD025 {
     }
 
     fn tags(&self) -> &[&'static str] {
-        &["headings", "headers", "fixable"]
+        &["headings", "headers"]
     }
 
     fn parser_type(&self) -> ParserType {
@@ -27,65 +31,53 @@ impl Rule for MD025 {
     }
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        let top_level = params
+            .config
+            .get("level")
+            .and_then(|v| v.as_u64())
+            .filter(|level| (1..=6).contains(level))
+            .unwrap_or(1) as u8;
+
+        // The first top-level heading, or the front matter title standing in for it
+        let mut first = params.front_matter_title_line().map(|line| RelatedLine {
+            line_number: line,
+            message: "Title in front matter".to_string(),
+        });
+
         let mut errors = Vec::new();
-        let headings = params.tokens.filter_by_type("heading");
-        let mut found_h1 = false;
-
-        for heading in headings {
-            // Check if it's an H1 via metadata
-            let level = heading
-                .metadata
-                .get("level")
-                .and_then(|l| l.parse::<u8>().ok())
-                .unwrap_or(0);
-
-            if level == 1 {
-                if found_h1 {
-                    // Generate fix to convert H1 to H2
-                    let line = params.lines.get(heading.start_line - 1);
-                    let fix_info = if let Some(line_text) = line {
-                        let trimmed = line_text.trim_start();
-                        if trimmed.starts_with('#') {
-                            // ATX style heading - add one more #
-                            let hash_count = trimmed.chars().take_while(|&c| c == '#').count();
-                            Some(FixInfo {
-                                line_number: Some(heading.start_line),
-                                edit_column: Some(1),
-                                delete_count: Some(hash_count as i32),
-                                insert_text: Some("##".to_string()),
-                            })
-                        } else {
-                            // Setext style - convert to ATX H2
-                            let heading_text = trimmed.trim_end();
-                            Some(FixInfo {
-                                line_number: Some(heading.start_line),
-                                edit_column: Some(1),
-                                delete_count: Some(i32::MAX),
-                                insert_text: Some(format!("## {}", heading_text)),
-                            })
-                        }
-                    } else {
-                        None
-                    };
-
-                    errors.push(LintError {
-                        line_number: heading.start_line,
-                        rule_names: self.names(),
-                        rule_description: self.description(),
-                        error_detail: None,
-                        error_context: Some(heading.text.trim().to_string()),
-                        rule_information: self.information(),
-                        error_range: None,
-                        fix_info,
-                        suggestion: Some(
-                            "Convert this heading to H2 (##) or restructure your document to have only one H1".to_string(),
-                        ),
-                        severity: Severity::Error,
-                        fix_only: false,
-                    });
-                }
-                found_h1 = true;
+        for heading in params.tokens.filter_by_type("heading") {
+            if heading.heading_level() != Some(top_level) {
+                continue;
             }
+            let Some(related) = &first else {
+                first = Some(RelatedLine {
+                    line_number: heading.start_line,
+                    message: "First top-level heading".to_string(),
+                });
+                continue;
+            };
+
+            errors.push(LintError {
+                line_number: heading.start_line,
+                rule_names: self.names(),
+                rule_description: self.description(),
+                error_detail: Some(format!(
+                    "{} on line {}",
+                    related.message, related.line_number
+                )),
+                error_context: Some(heading.text.trim().to_string()),
+                rule_information: self.information(),
+                error_range: None,
+                fix_info: None,
+                suggestion: Some(format!(
+                    "Use a lower heading level or split the document; only one H{} is allowed",
+                    top_level
+                )),
+                severity: Severity::Error,
+                fix_only: false,
+                line_text: None,
+                related: Some(related.clone()),
+            });
         }
 
         errors
@@ -111,15 +103,8 @@ mod tests {
     fn test_md025_single_h1() {
         let tokens = vec![make_heading(1, "Title", 1), make_heading(3, "Section", 2)];
         let lines = vec!["# Title\n", "\n", "## Section\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD025.lint(&params);
         assert_eq!(errors.len(), 0, "Single H1 should not trigger MD025");
@@ -132,15 +117,8 @@ mod tests {
             make_heading(3, "Another Title", 1),
         ];
         let lines = vec!["# Title\n", "\n", "# Another Title\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD025.lint(&params);
         assert_eq!(errors.len(), 1);
@@ -156,15 +134,8 @@ mod tests {
             make_heading(5, "Third", 1),
         ];
         let lines = vec!["# First\n", "\n", "# Second\n", "\n", "# Third\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD025.lint(&params);
         assert_eq!(errors.len(), 2, "Second and third H1 should both error");
@@ -177,15 +148,8 @@ mod tests {
             make_heading(3, "Subsection", 3),
         ];
         let lines = vec!["## Section\n", "\n", "### Subsection\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD025.lint(&params);
         assert_eq!(errors.len(), 0, "No H1 headings should not trigger MD025");
@@ -195,20 +159,69 @@ mod tests {
     fn test_md025_no_fix_info() {
         let tokens = vec![make_heading(1, "Title", 1), make_heading(3, "Second", 1)];
         let lines = vec!["# Title\n", "\n", "# Second\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let errors = MD025.lint(&params);
         assert!(
-            errors[0].fix_info.is_some(),
-            "MD025 should have fix_info to convert H1 to H2"
+            errors[0].fix_info.is_none(),
+            "Demoting a heading is editorial, so MD025 has no fix"
+        );
+    }
+
+    fn lint(content: &str, config: serde_json::Value, front_matter: usize) -> Vec<LintError> {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        let config: HashMap<String, serde_json::Value> = serde_json::from_value(config).unwrap();
+        let params = RuleParams {
+            front_matter_lines: &lines[..front_matter],
+            ..RuleParams::test_with_tokens(&lines, &tokens, &config)
+        };
+        MD025.lint(&params)
+    }
+
+    #[test]
+    fn test_md025_mixed_atx_and_setext() {
+        let errors = lint("# Title\n\nAnother\n=======\n", serde_json::json!({}), 0);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 3);
+        assert_eq!(
+            errors[0].related,
+            Some(RelatedLine {
+                line_number: 1,
+                message: "First top-level heading".to_string(),
+            })
         );
+
+        let errors = lint("Title\n=====\n\n# Another\n", serde_json::json!({}), 0);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 4);
+        assert_eq!(errors[0].related.as_ref().unwrap().line_number, 1);
+    }
+
+    #[test]
+    fn test_md025_level_option() {
+        let content = "# Site\n\n## Page\n\nOther\n-----\n";
+        assert!(lint(content, serde_json::json!({}), 0).is_empty());
+
+        let errors = lint(content, serde_json::json!({"level": 2}), 0);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 5);
+        assert_eq!(errors[0].error_context.as_deref(), Some("Other"));
+    }
+
+    #[test]
+    fn test_md025_front_matter_title() {
+        let content = "---\ntitle: Doc\n---\n# Title\n";
+        let errors = lint(content, serde_json::json!({}), 3);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 4);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Title in front matter on line 2")
+        );
+
+        let ignored = serde_json::json!({"front_matter_title": false});
+        assert!(lint(content, ignored, 3).is_empty());
     }
 }
@@ -2,6 +2,9 @@
 
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
+/// Default set of trailing punctuation characters
+const DEFAULT_PUNCTUATION: &str = ".,;:!?";
+
 pub struct MD026;
 
 impl Rule for MD026 {
@@ -27,7 +30,25 @@ impl Rule for MD026 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
-        let punctuation = ".,;:!?";
+
+        // Characters are matched individually, so full-width punctuation
+        // such as `。！？` can be listed alongside ASCII
+        let punctuation = params
+            .config
+            .get("punctuation")
+            .and_then(|v| v.as_str())
+            .unwrap_or(DEFAULT_PUNCTUATION);
+
+        // Heading levels to check (default: all)
+        let levels: Option<Vec<usize>> = params
+            .config
+            .get("levels")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_u64().map(|n| n as usize))
+                    .collect()
+            });
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
@@ -35,7 +56,10 @@ impl Rule for MD026 {
 
             if trimmed.starts_with('#') {
                 let hash_count = trimmed.chars().take_while(|&c| c == '#').count();
-                if hash_count > 0 && hash_count <= 6 {
+                if hash_count > 0
+                    && hash_count <= 6
+                    && levels.as_ref().is_none_or(|l| l.contains(&hash_count))
+                {
                     let content = trimmed[hash_count..].trim();
                     // Remove trailing # for closed ATX
                     let content = content.trim_end_matches('#').trim_end();
@@ -73,6 +97,8 @@ impl Rule for MD026 {
                             ),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
                     }
                 }
@@ -92,15 +118,8 @@ mod tests {
     fn test_md026_no_punctuation() {
         let lines = vec!["# Heading\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD026;
         let errors = rule.lint(&params);
@@ -111,15 +130,8 @@ mod tests {
     fn test_md026_with_punctuation() {
         let lines = vec!["# Heading!\n", "## Question?\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD026;
         let errors = rule.lint(&params);
@@ -130,15 +142,8 @@ mod tests {
     fn test_md026_fix_info_exclamation() {
         let lines = vec!["# Heading!\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD026;
         let errors = rule.lint(&params);
@@ -159,15 +164,8 @@ mod tests {
     fn test_md026_fix_info_question() {
         let lines = vec!["## Question?\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD026;
         let errors = rule.lint(&params);
@@ -188,15 +186,8 @@ mod tests {
     fn test_md026_fix_info_closed_atx() {
         let lines = vec!["# Heading! ##\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD026;
         let errors = rule.lint(&params);
@@ -213,4 +204,43 @@ mod tests {
         assert_eq!(fix.delete_count, Some(1));
         assert_eq!(fix.insert_text, None);
     }
+
+    #[test]
+    fn test_md026_full_width_punctuation() {
+        let lines = vec!["# 見出し。\n", "## 質問？\n"];
+        let mut config = HashMap::new();
+        config.insert("punctuation".to_string(), serde_json::json!(".,;:!。！？"));
+        let params = RuleParams::test(&lines, &config);
+
+        let errors = MD026.lint(&params);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].error_detail.as_deref(), Some("Punctuation: '。'"));
+
+        let fixed = crate::lint::apply_fixes(&lines.concat(), &errors);
+        assert_eq!(fixed, "# 見出し\n## 質問\n");
+    }
+
+    #[test]
+    fn test_md026_custom_punctuation_replaces_default() {
+        let lines = vec!["# Heading!\n", "# Heading;\n"];
+        let mut config = HashMap::new();
+        config.insert("punctuation".to_string(), serde_json::json!(";"));
+        let params = RuleParams::test(&lines, &config);
+
+        let errors = MD026.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 2);
+    }
+
+    #[test]
+    fn test_md026_levels() {
+        let lines = vec!["# Title!\n", "## Section!\n", "### Detail!\n"];
+        let mut config = HashMap::new();
+        config.insert("levels".to_string(), serde_json::json!([2, 3]));
+        let params = RuleParams::test(&lines, &config);
+
+        let errors = MD026.lint(&params);
+        let flagged: Vec<_> = errors.iter().map(|e| e.line_number).collect();
+        assert_eq!(flagged, vec![2, 3]);
+    }
 }
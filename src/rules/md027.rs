@@ -17,6 +17,10 @@ impl Rule for MD027 {
         &["blockquote", "whitespace", "indentation", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::None
     }
@@ -55,6 +59,8 @@ impl Rule for MD027 {
                         ),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
@@ -73,15 +79,8 @@ mod tests {
     fn test_md027_single_space() {
         let lines = vec!["> Blockquote\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD027;
         let errors = rule.lint(&params);
@@ -92,15 +91,8 @@ mod tests {
     fn test_md027_multiple_spaces() {
         let lines = vec![">  Blockquote\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD027;
         let errors = rule.lint(&params);
@@ -111,15 +103,8 @@ mod tests {
     fn test_md027_no_space_no_error() {
         // ">text" (no space at all) should not trigger MD027
         let lines = vec![">text\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
         let rule = MD027;
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 0, "Zero spaces after > should not trigger");
@@ -128,15 +113,8 @@ mod tests {
     #[test]
     fn test_md027_three_spaces_fires() {
         let lines = vec![">   Three spaces\n"];
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
         let rule = MD027;
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 1);
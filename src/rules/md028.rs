@@ -1,9 +1,44 @@
 //! MD028 - Blank line inside blockquote
+//!
+//! Supports `fix_style` config: "join" (default) or "separate".
+//! - "join": blank lines between the quotes become a `>` line, making one
+//!   blockquote with a paragraph break
+//! - "separate": an HTML comment goes between the quotes, keeping two
+//!   blockquotes that no parser will merge
 
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
+/// Line put between two blockquotes by the `separate` fix
+const SEPARATOR: &str = "<!-- -->";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FixStyle {
+    Join,
+    Separate,
+}
+
 pub struct MD028;
 
+impl MD028 {
+    fn error(&self, line_number: usize, fix_info: FixInfo, fix_only: bool) -> LintError {
+        LintError {
+            line_number,
+            rule_names: self.names(),
+            rule_description: self.description(),
+            error_detail: None,
+            error_context: None,
+            rule_information: self.information(),
+            error_range: None,
+            fix_info: Some(fix_info),
+            suggestion: (!fix_only).then(|| "Remove blank lines inside blockquote".to_string()),
+            severity: Severity::Error,
+            fix_only,
+            line_text: None,
+            related: None,
+        }
+    }
+}
+
 impl Rule for MD028 {
     fn names(&self) -> &'static [&'static str] {
         &["MD028", "no-blanks-blockquote"]
@@ -17,6 +52,10 @@ impl Rule for MD028 {
         &["blockquote", "whitespace", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::None
     }
@@ -26,8 +65,16 @@ impl Rule for MD028 {
     }
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        let fix_style = match params.config.get("fix_style").and_then(|v| v.as_str()) {
+            Some("separate") => FixStyle::Separate,
+            _ => FixStyle::Join,
+        };
+
         let mut errors = Vec::new();
-        let mut in_blockquote = false;
+        // Last blockquote line, while the blockquote may continue
+        let mut last_quote: Option<&str> = None;
+        // First and last blank lines after it
+        let mut first_blank = 0;
         let mut blank_line = 0;
 
         for (idx, line) in params.lines.iter().enumerate() {
@@ -35,34 +82,53 @@ impl Rule for MD028 {
             let trimmed = line.trim();
 
             if trimmed.starts_with('>') {
-                if blank_line > 0 && in_blockquote {
-                    errors.push(LintError {
-                        line_number: blank_line,
-                        rule_names: self.names(),
-                        rule_description: self.description(),
-                        error_detail: None,
-                        error_context: None,
-                        rule_information: self.information(),
-                        error_range: None,
-                        fix_info: Some(FixInfo {
+                if blank_line > 0
+                    && let Some(previous) = last_quote
+                {
+                    let text_len = params.lines[blank_line - 1]
+                        .trim_end_matches(['\n', '\r'])
+                        .len() as i32;
+                    let fix = match fix_style {
+                        FixStyle::Join => FixInfo {
                             line_number: Some(blank_line),
                             edit_column: Some(1),
-                            delete_count: Some(-1), // Delete entire line
-                            insert_text: None,
-                        }),
-                        suggestion: Some("Remove blank lines inside blockquote".to_string()),
-                        severity: Severity::Error,
-                        fix_only: false,
-                    });
+                            delete_count: Some(text_len),
+                            insert_text: Some(common_marker(previous, line).to_string()),
+                        },
+                        FixStyle::Separate => FixInfo {
+                            line_number: Some(blank_line),
+                            edit_column: Some(1),
+                            delete_count: Some(text_len),
+                            insert_text: Some(format!("\n{}\n", SEPARATOR)),
+                        },
+                    };
+                    errors.push(self.error(blank_line, fix, false));
+                    // Joining leaves a single `>` line in place of all the blanks
+                    if fix_style == FixStyle::Join {
+                        for extra in first_blank..blank_line {
+                            let fix = FixInfo {
+                                line_number: Some(extra),
+                                edit_column: Some(1),
+                                delete_count: Some(-1),
+                                insert_text: None,
+                            };
+                            errors.push(self.error(extra, fix, true));
+                        }
+                    }
                 }
-                in_blockquote = true;
+                last_quote = Some(*line);
+                first_blank = 0;
                 blank_line = 0;
             } else if trimmed.is_empty() {
-                if in_blockquote {
+                if last_quote.is_some() {
+                    if first_blank == 0 {
+                        first_blank = line_number;
+                    }
                     blank_line = line_number;
                 }
             } else {
-                in_blockquote = false;
+                last_quote = None;
+                first_blank = 0;
                 blank_line = 0;
             }
         }
@@ -71,6 +137,31 @@ impl Rule for MD028 {
     }
 }
 
+/// The `>` markers the blockquote lines `a` and `b` share, as written in
+/// `a`: `>` for `> > one` and `> two`
+fn common_marker<'a>(a: &'a str, b: &str) -> &'a str {
+    let depth = quote_marker(a)
+        .matches('>')
+        .count()
+        .min(quote_marker(b).matches('>').count());
+    let marker = quote_marker(a);
+    let end = marker
+        .match_indices('>')
+        .nth(depth.saturating_sub(1))
+        .map_or(0, |(i, _)| i + 1);
+    &marker[..end]
+}
+
+/// The leading `>` markers of a blockquote line, with the whitespace
+/// between them
+fn quote_marker(line: &str) -> &str {
+    let line = line.trim_start();
+    let end = line
+        .find(|c: char| !c.is_whitespace() && c != '>')
+        .unwrap_or(line.len());
+    line[..end].trim_end()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,8 +213,8 @@ mod tests {
         let fix = errors[0].fix_info.as_ref().unwrap();
         assert_eq!(fix.line_number, Some(2));
         assert_eq!(fix.edit_column, Some(1));
-        assert_eq!(fix.delete_count, Some(-1)); // Delete entire line
-        assert_eq!(fix.insert_text, None);
+        assert_eq!(fix.delete_count, Some(0));
+        assert_eq!(fix.insert_text, Some(">".to_string()));
     }
 
     #[test]
@@ -134,9 +225,20 @@ mod tests {
         let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
         let rule = MD028;
         let errors = rule.lint(&params);
-        // Only the last blank line before the next blockquote is reported
-        assert_eq!(errors.len(), 1);
-        assert_eq!(errors[0].line_number, 3);
+        // Only the last blank line before the next blockquote is reported;
+        // the fix deletes the others
+        let reported: Vec<usize> = errors
+            .iter()
+            .filter(|e| !e.fix_only)
+            .map(|e| e.line_number)
+            .collect();
+        assert_eq!(reported, [3]);
+        let helpers: Vec<Option<usize>> = errors
+            .iter()
+            .filter(|e| e.fix_only)
+            .map(|e| e.fix_info.as_ref().unwrap().line_number)
+            .collect();
+        assert_eq!(helpers, [Some(2)]);
     }
 
     #[test]
@@ -149,6 +251,41 @@ mod tests {
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 1);
         let fix = errors[0].fix_info.as_ref().unwrap();
-        assert_eq!(fix.delete_count, Some(-1));
+        assert_eq!(fix.delete_count, Some(3));
+        assert_eq!(fix.insert_text, Some(">".to_string()));
+    }
+
+    fn fix_round_trip(content: &str, config: &str) -> String {
+        let config: crate::Config = serde_json::from_str(config).unwrap();
+        let errors = crate::lint_string(content, &config).unwrap();
+        let fixed = crate::apply_fixes(content, &errors);
+        let remaining = crate::lint_string(&fixed, &config).unwrap();
+        assert!(remaining.is_empty(), "{:?}", remaining);
+        fixed
+    }
+
+    #[test]
+    fn test_md028_fix_style_join_round_trip() {
+        let content = "# Title\n\n> First quote\n\n\n> Second quote\n\n> > Nested\n\n> Outer\n";
+        let config = r#"{"default": false, "MD028": true}"#;
+        assert_eq!(
+            fix_round_trip(content, config),
+            "# Title\n\n> First quote\n>\n> Second quote\n>\n> > Nested\n>\n> Outer\n"
+        );
+        let explicit = r#"{"default": false, "MD028": {"fix_style": "join"}}"#;
+        assert_eq!(
+            fix_round_trip(content, explicit),
+            fix_round_trip(content, config)
+        );
+    }
+
+    #[test]
+    fn test_md028_fix_style_separate_round_trip() {
+        let content = "# Title\n\n> First quote\n\n> Second quote\n   \n> Third quote\n";
+        let config = r#"{"default": false, "MD028": {"fix_style": "separate"}}"#;
+        assert_eq!(
+            fix_round_trip(content, config),
+            "# Title\n\n> First quote\n\n<!-- -->\n\n> Second quote\n\n<!-- -->\n\n> Third quote\n"
+        );
     }
 }
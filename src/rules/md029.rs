@@ -79,18 +79,16 @@ fn is_ordered_list(
     lines: &[&str],
     list_token: &crate::parser::Token,
 ) -> bool {
-    // Check if any child list items are ordered
-    for &child_idx in &list_token.children {
-        if let Some(child) = tokens.get(child_idx)
-            && child.token_type == "listItem"
-            && child.start_line > 0
-            && child.start_line <= lines.len()
-        {
-            let line = &lines[child.start_line - 1];
-            return get_ordered_list_value(line).is_some();
-        }
+    if list_token.is_ordered_list() == Some(false) {
+        return false;
     }
-    false
+
+    // Only `1.`-style markers are checked; confirm against the source line
+    list_token
+        .children(tokens)
+        .find(|child| child.is_type("listItem"))
+        .filter(|child| child.start_line > 0 && child.start_line <= lines.len())
+        .is_some_and(|child| get_ordered_list_value(lines[child.start_line - 1]).is_some())
 }
 
 impl Rule for MD029 {
@@ -135,14 +133,10 @@ impl Rule for MD029 {
             }
 
             // Get all list items for this ordered list
-            let mut list_items = Vec::new();
-            for &child_idx in &list.children {
-                if let Some(child) = params.tokens.get(child_idx)
-                    && child.token_type == "listItem"
-                {
-                    list_items.push(child);
-                }
-            }
+            let list_items: Vec<_> = list
+                .children(params.tokens)
+                .filter(|child| child.is_type("listItem"))
+                .collect();
 
             if list_items.is_empty() {
                 continue;
@@ -223,6 +217,8 @@ impl Rule for MD029 {
                             suggestion: Some("Use consistent list numbering style".to_string()),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
                     }
 
@@ -283,15 +279,8 @@ mod tests {
             create_list_item_token(3, Some(0)),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD029;
         let errors = rule.lint(&params);
@@ -309,15 +298,8 @@ mod tests {
             create_list_item_token(3, Some(0)),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD029;
         let errors = rule.lint(&params);
@@ -339,15 +321,8 @@ mod tests {
             create_list_item_token(3, Some(0)),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD029;
         let errors = rule.lint(&params);
@@ -380,15 +355,7 @@ mod tests {
             create_list_item_token(3, Some(0)),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD029;
         let errors = rule.lint(&params);
@@ -415,15 +382,7 @@ mod tests {
             create_list_item_token(3, Some(0)),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD029;
         let errors = rule.lint(&params);
@@ -446,15 +405,7 @@ mod tests {
             create_list_item_token(3, Some(0)),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD029;
         let errors = rule.lint(&params);
@@ -472,15 +423,8 @@ mod tests {
             create_list_item_token(3, Some(0)),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD029;
         let errors = rule.lint(&params);
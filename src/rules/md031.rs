@@ -77,6 +77,10 @@ impl Rule for MD031 {
         &["code", "blank_lines", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        -1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::None
     }
@@ -158,6 +162,8 @@ impl Rule for MD031 {
                             ),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
                     }
                 } else if current_fence_char == fence_char {
@@ -216,6 +222,8 @@ impl Rule for MD031 {
                                 ),
                                 severity: Severity::Error,
                                 fix_only: false,
+                                line_text: None,
+                                related: None,
                             });
                         }
                     }
@@ -244,15 +252,8 @@ mod tests {
             "More text\n",
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD031;
         let errors = rule.lint(&params);
@@ -263,15 +264,8 @@ mod tests {
     fn test_md031_missing_blank_before() {
         let lines = vec!["# Heading\n", "```rust\n", "let x = 5;\n", "```\n", "\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD031;
         let errors = rule.lint(&params);
@@ -290,15 +284,8 @@ mod tests {
             "More text\n",
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD031;
         let errors = rule.lint(&params);
@@ -316,15 +303,8 @@ mod tests {
             "More text\n",
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD031;
         let errors = rule.lint(&params);
@@ -337,15 +317,8 @@ mod tests {
     fn test_md031_tilde_fences() {
         let lines = vec!["Text\n", "~~~\n", "code\n", "~~~\n", "Text\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD031;
         let errors = rule.lint(&params);
@@ -356,15 +329,8 @@ mod tests {
     fn test_md031_start_of_file() {
         let lines = vec!["```rust\n", "let x = 5;\n", "```\n", "\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD031;
         let errors = rule.lint(&params);
@@ -376,15 +342,8 @@ mod tests {
     fn test_md031_end_of_file() {
         let lines = vec!["\n", "```rust\n", "let x = 5;\n", "```\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD031;
         let errors = rule.lint(&params);
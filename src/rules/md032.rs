@@ -131,6 +131,10 @@ impl Rule for MD032 {
         &["bullet", "ul", "ol", "blank_lines", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        -1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::Micromark
     }
@@ -198,6 +202,8 @@ impl Rule for MD032 {
                             ),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
                     }
                 }
@@ -261,6 +267,8 @@ impl Rule for MD032 {
                         suggestion: Some("Lists should be surrounded by blank lines".to_string()),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
@@ -341,15 +349,8 @@ mod tests {
             create_list_item_token(4, 4, vec![], 0),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD032;
         let errors = rule.lint(&params);
@@ -366,15 +367,8 @@ mod tests {
             create_list_item_token(3, 3, vec![], 0),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD032;
         let errors = rule.lint(&params);
@@ -392,15 +386,8 @@ mod tests {
             create_list_item_token(3, 3, vec![], 0),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD032;
         let errors = rule.lint(&params);
@@ -418,15 +405,8 @@ mod tests {
             create_list_item_token(3, 3, vec![], 0),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD032;
         let errors = rule.lint(&params);
@@ -444,15 +424,8 @@ mod tests {
             create_list_item_token(2, 2, vec![], 0),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD032;
         let errors = rule.lint(&params);
@@ -470,15 +443,8 @@ mod tests {
             create_list_item_token(3, 3, vec![], 0),
         ];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD032;
         let errors = rule.lint(&params);
@@ -1,52 +1,81 @@
 //! MD033 - Inline HTML
 //!
 //! This rule checks for inline HTML elements in the markdown content.
-//! It can be configured to allow specific HTML elements.
+//! It can be configured to allow specific HTML elements, and to require
+//! that some elements only appear inside others (e.g. `<summary>` inside
+//! `<details>`).
 
+use crate::parser::Token;
 use crate::types::{LintError, ParserType, Rule, RuleParams, Severity};
 use regex::Regex;
+use std::collections::HashMap;
 use std::sync::LazyLock;
 
-static HTML_TAG_NAME_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^<([^!>][^/\s>]*)").expect("valid regex"));
+/// An HTML comment, or an opening, closing or self-closing tag
+static HTML_TAG_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)<!--.*?-->|<(/?)([A-Za-z][A-Za-z0-9-]*)(?:\s[^>]*?)?(/?)>")
+        .expect("valid regex")
+});
+
+/// Elements that never have content, so they are never anyone's parent
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
 
 pub struct MD033;
 
-/// Extract HTML tag information from a token
-struct HtmlTagInfo {
-    name: String,
+/// An HTML tag found in a token's text
+#[derive(Debug)]
+struct HtmlTag<'a> {
+    /// Byte offset of the `<` in the token's text
+    offset: usize,
+    /// The tag as written, `<` to `>`
+    text: &'a str,
+    /// Element name as written
+    name: &'a str,
     close: bool,
+    self_closing: bool,
 }
 
-fn get_html_tag_info(text: &str) -> Option<HtmlTagInfo> {
-    if let Some(captures) = HTML_TAG_NAME_RE.captures(text)
-        && let Some(name_match) = captures.get(1)
-    {
-        let mut name = name_match.as_str();
-        let close = name.starts_with('/');
-
-        // Strip leading '/' for closing tags
-        if close {
-            name = &name[1..];
-        }
-
-        // Strip trailing '/' for self-closing tags like <br/>
-        let name = name.trim_end_matches('/');
+/// Every tag in `text`, comments skipped
+fn html_tags(text: &str) -> impl Iterator<Item = HtmlTag<'_>> {
+    HTML_TAG_RE.captures_iter(text).filter_map(|captures| {
+        let whole = captures.get(0)?;
+        let name = captures.get(2)?;
+        Some(HtmlTag {
+            offset: whole.start(),
+            text: whole.as_str(),
+            name: name.as_str(),
+            close: !captures[1].is_empty(),
+            self_closing: !captures[3].is_empty(),
+        })
+    })
+}
 
-        return Some(HtmlTagInfo {
-            name: name.to_string(),
-            close,
-        });
+/// 1-based line and column of the tag at `offset` in `token`'s text.
+///
+/// HTML blocks span several lines and their text omits any container
+/// prefixes, so later lines are located in the source line itself.
+fn tag_position(params: &RuleParams, token: &Token, offset: usize) -> (usize, usize) {
+    let before = &token.text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let in_line = offset - line_start;
+    let line_number = token.start_line + before.matches('\n').count();
+    if token.token_type == "htmlInline" {
+        return (line_number, token.start_column + in_line);
     }
-    None
+    let literal_line = token.text[line_start..].lines().next().unwrap_or_default();
+    let column = params
+        .lines
+        .get(line_number.wrapping_sub(1))
+        .and_then(|line| line.find(literal_line))
+        .map_or(token.start_column + in_line, |start| start + in_line + 1);
+    (line_number, column)
 }
 
 /// Check if a token has a parent of the specified type
-fn has_parent_of_type(
-    tokens: &[crate::parser::Token],
-    token_idx: usize,
-    parent_type: &str,
-) -> bool {
+fn has_parent_of_type(tokens: &[Token], token_idx: usize, parent_type: &str) -> bool {
     if let Some(token) = tokens.get(token_idx)
         && let Some(parent_idx) = token.parent
         && let Some(parent) = tokens.get(parent_idx)
@@ -74,6 +103,23 @@ fn to_lowercase_string_array(value: Option<&serde_json::Value>) -> Vec<String> {
     Vec::new()
 }
 
+/// `required_parents`: element name to the elements it must sit directly
+/// inside, each given as a name or a list of names
+fn required_parents(value: Option<&serde_json::Value>) -> HashMap<String, Vec<String>> {
+    let Some(map) = value.and_then(|v| v.as_object()) else {
+        return HashMap::new();
+    };
+    map.iter()
+        .map(|(child, parents)| {
+            let parents = match parents.as_str() {
+                Some(parent) => vec![parent.to_lowercase()],
+                None => to_lowercase_string_array(Some(parents)),
+            };
+            (child.to_lowercase(), parents)
+        })
+        .collect()
+}
+
 impl Rule for MD033 {
     fn names(&self) -> &'static [&'static str] {
         &["MD033", "no-inline-html"]
@@ -108,43 +154,71 @@ impl Rule for MD033 {
             allowed_elements.clone()
         };
 
+        let required_parents = required_parents(params.config.get("required_parents"));
+
+        // Elements opened so far and not yet closed, across all HTML tokens
+        let mut open_elements: Vec<String> = Vec::new();
+
         for (idx, token) in params.tokens.iter().enumerate() {
-            if token.token_type != "htmlText" {
+            if token.token_type != "htmlInline" && token.token_type != "htmlBlock" {
                 continue;
             }
-
-            // Get HTML tag info
-            if let Some(html_tag_info) = get_html_tag_info(&token.text) {
-                // Skip closing tags
-                if html_tag_info.close {
+            let in_table = has_parent_of_type(params.tokens, idx, "table");
+
+            for tag in html_tags(&token.text) {
+                let element_name = tag.name.to_lowercase();
+                if tag.close {
+                    if let Some(pos) = open_elements.iter().rposition(|n| *n == element_name) {
+                        open_elements.truncate(pos);
+                    }
                     continue;
                 }
 
-                let element_name = html_tag_info.name.to_lowercase();
-                let in_table = has_parent_of_type(params.tokens, idx, "table");
-
                 // Check if element should trigger an error
                 // Logic from JS: (inTable || !allowedElements.includes(elementName)) && (!inTable || !tableAllowedElements.includes(elementName))
-                let should_error = (in_table || !allowed_elements.contains(&element_name))
+                let disallowed = (in_table || !allowed_elements.contains(&element_name))
                     && (!in_table || !table_allowed_elements.contains(&element_name));
 
-                if should_error {
-                    // Calculate range - first line only
-                    let first_line_text = token.text.lines().next().unwrap_or(&token.text);
-                    let range = (token.start_column, first_line_text.len());
+                let error_detail = if disallowed {
+                    Some(format!("Element: {}", tag.name))
+                } else {
+                    required_parents
+                        .get(&element_name)
+                        .filter(|parents| {
+                            !open_elements
+                                .last()
+                                .is_some_and(|open| parents.contains(open))
+                        })
+                        .map(|parents| {
+                            let parents: Vec<String> =
+                                parents.iter().map(|p| format!("<{p}>")).collect();
+                            format!("Element: {} outside {}", tag.name, parents.join(" or "))
+                        })
+                };
+
+                if !tag.self_closing && !VOID_ELEMENTS.contains(&element_name.as_str()) {
+                    open_elements.push(element_name);
+                }
+
+                if let Some(error_detail) = error_detail {
+                    let (line_number, column) = tag_position(params, token, tag.offset);
+                    // Range covers the tag's first line only
+                    let tag_len = tag.text.lines().next().unwrap_or(tag.text).len();
 
                     errors.push(LintError {
-                        line_number: token.start_line,
+                        line_number,
                         rule_names: self.names(),
                         rule_description: self.description(),
-                        error_detail: Some(format!("Element: {}", html_tag_info.name)),
+                        error_detail: Some(error_detail),
                         error_context: None,
                         rule_information: self.information(),
-                        error_range: Some(range),
+                        error_range: Some((column, tag_len)),
                         fix_info: None,
                         suggestion: Some("Avoid using raw HTML in Markdown".to_string()),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
@@ -157,31 +231,28 @@ impl Rule for MD033 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parser::Token;
-    use std::collections::HashMap;
 
     #[test]
-    fn test_get_html_tag_info() {
-        let info = get_html_tag_info("<div>");
-        assert!(info.is_some());
-        let info = info.unwrap();
-        assert_eq!(info.name, "div");
-        assert!(!info.close);
-
-        let info = get_html_tag_info("</div>");
-        assert!(info.is_some());
-        let info = info.unwrap();
-        assert_eq!(info.name, "div");
-        assert!(info.close);
-
-        let info = get_html_tag_info("<br/>");
-        assert!(info.is_some());
-        let info = info.unwrap();
-        assert_eq!(info.name, "br"); // Self-closing tags should have the tag name without '/'
-        assert!(!info.close);
-
-        let info = get_html_tag_info("<!-- comment -->");
-        assert!(info.is_none());
+    fn test_html_tags() {
+        let tags: Vec<_> = html_tags("<div>").collect();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "div");
+        assert!(!tags[0].close);
+
+        let tags: Vec<_> = html_tags("</div>").collect();
+        assert_eq!(tags[0].name, "div");
+        assert!(tags[0].close);
+
+        let tags: Vec<_> = html_tags("<br/>").collect();
+        assert_eq!(tags[0].name, "br"); // Self-closing tags should have the tag name without '/'
+        assert!(!tags[0].close);
+        assert!(tags[0].self_closing);
+
+        assert_eq!(html_tags("<!-- <div> -->").count(), 0);
+
+        let tags: Vec<_> = html_tags("<details>\n<summary a=\"b\">x</summary>").collect();
+        assert_eq!(tags.len(), 3);
+        assert_eq!((tags[1].offset, tags[1].text), (10, "<summary a=\"b\">"));
     }
 
     #[test]
@@ -189,15 +260,8 @@ mod tests {
         let tokens = vec![];
         let lines = vec!["# Heading\n", "Some text\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD033;
         let errors = rule.lint(&params);
@@ -207,7 +271,7 @@ mod tests {
     #[test]
     fn test_md033_with_html() {
         let tokens = vec![Token {
-            token_type: "htmlText".to_string(),
+            token_type: "htmlInline".to_string(),
             start_line: 1,
             start_column: 1,
             end_line: 1,
@@ -220,15 +284,8 @@ mod tests {
 
         let lines = vec!["<div>\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD033;
         let errors = rule.lint(&params);
@@ -239,7 +296,7 @@ mod tests {
     #[test]
     fn test_md033_with_allowed_elements() {
         let tokens = vec![Token {
-            token_type: "htmlText".to_string(),
+            token_type: "htmlInline".to_string(),
             start_line: 1,
             start_column: 1,
             end_line: 1,
@@ -255,15 +312,7 @@ mod tests {
         let mut config = HashMap::new();
         config.insert("allowed_elements".to_string(), serde_json::json!(["div"]));
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD033;
         let errors = rule.lint(&params);
@@ -273,7 +322,7 @@ mod tests {
     #[test]
     fn test_md033_closing_tag_ignored() {
         let tokens = vec![Token {
-            token_type: "htmlText".to_string(),
+            token_type: "htmlInline".to_string(),
             start_line: 1,
             start_column: 1,
             end_line: 1,
@@ -286,18 +335,70 @@ mod tests {
 
         let lines = vec!["</div>\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD033;
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 0);
     }
+
+    fn lint_md033(content: &str, config: serde_json::Value) -> Vec<LintError> {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        let config: HashMap<String, serde_json::Value> = serde_json::from_value(config).unwrap();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
+        MD033.lint(&params)
+    }
+
+    #[test]
+    fn test_md033_html_block_tags() {
+        let errors = lint_md033(
+            "# Title\n\n<div>\n  <p align=\"center\">x</p>\n</div>\n\nText <b>bold</b>\n",
+            serde_json::json!({}),
+        );
+        let found: Vec<_> = errors
+            .iter()
+            .map(|e| (e.line_number, e.error_range.unwrap().0))
+            .collect();
+        assert_eq!(found, [(3, 1), (4, 3), (7, 6)]);
+    }
+
+    fn details_config() -> serde_json::Value {
+        serde_json::json!({
+            "allowed_elements": ["details", "summary"],
+            "required_parents": {"summary": "details"}
+        })
+    }
+
+    #[test]
+    fn test_md033_summary_inside_details() {
+        let content = "<details><summary>x</summary>\n\nBody\n\n</details>\n";
+        assert!(lint_md033(content, details_config()).is_empty());
+    }
+
+    #[test]
+    fn test_md033_orphan_summary() {
+        let content = "<details><summary>x</summary>\n\n</details>\n\nA <summary>bare</summary>\n";
+        let errors = lint_md033(content, details_config());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 5);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Element: summary outside <details>")
+        );
+        // Without the option a bare `<summary>` is just an allowed element
+        let allowed = serde_json::json!({"allowed_elements": ["details", "summary"]});
+        assert!(lint_md033(content, allowed).is_empty());
+    }
+
+    #[test]
+    fn test_md033_required_parent_must_be_direct() {
+        let content = "<details>\n<div><summary>x</summary></div>\n</details>\n";
+        let config = serde_json::json!({
+            "allowed_elements": ["details", "summary", "div"],
+            "required_parents": {"summary": ["details"]}
+        });
+        assert_eq!(lint_md033(content, config).len(), 1);
+    }
 }
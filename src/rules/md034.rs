@@ -1,6 +1,7 @@
 //! MD034 - Bare URL used
 
-use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+use crate::parser::Token;
+use crate::types::{FixInfo, LintError, Rule, RuleParams, Severity};
 use regex::Regex;
 use std::sync::LazyLock;
 
@@ -9,6 +10,21 @@ static URL_RE: LazyLock<Regex> =
 
 pub struct MD034;
 
+/// Whether the 1-based byte `column` on `line` falls inside `token`.
+///
+/// Block tokens cover their lines entirely; inline tokens are bounded by
+/// their start and (inclusive) end columns.
+fn token_covers(token: &Token, line: usize, column: usize) -> bool {
+    if line < token.start_line || line > token.end_line {
+        return false;
+    }
+    if matches!(token.token_type.as_str(), "codeBlock" | "htmlBlock") {
+        return true;
+    }
+    (line > token.start_line || column >= token.start_column)
+        && (line < token.end_line || column <= token.end_column)
+}
+
 impl Rule for MD034 {
     fn names(&self) -> &'static [&'static str] {
         &["MD034", "no-bare-urls"]
@@ -22,10 +38,6 @@ impl Rule for MD034 {
         &["links", "url", "fixable"]
     }
 
-    fn parser_type(&self) -> ParserType {
-        ParserType::None
-    }
-
     fn information(&self) -> Option<&'static str> {
         Some("https://github.com/DavidAnson/markdownlint/blob/main/doc/md034.md")
     }
@@ -33,6 +45,18 @@ impl Rule for MD034 {
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
 
+        // URLs in code or raw HTML (e.g. `href="..."`) are not bare URLs
+        let excluded: Vec<&Token> = params
+            .tokens
+            .iter()
+            .filter(|t| {
+                matches!(
+                    t.token_type.as_str(),
+                    "code" | "codeBlock" | "htmlInline" | "htmlBlock"
+                )
+            })
+            .collect();
+
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
 
@@ -42,6 +66,12 @@ impl Rule for MD034 {
             }
 
             for mat in URL_RE.find_iter(line) {
+                if excluded
+                    .iter()
+                    .any(|t| token_covers(t, line_number, mat.start() + 1))
+                {
+                    continue;
+                }
                 let url = mat.as_str();
                 errors.push(LintError {
                     line_number,
@@ -62,6 +92,8 @@ impl Rule for MD034 {
                     ),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
@@ -79,15 +111,8 @@ mod tests {
     fn test_md034_with_markdown_link() {
         let lines = vec!["[link](https://example.com)\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD034;
         let errors = rule.lint(&params);
@@ -98,15 +123,8 @@ mod tests {
     fn test_md034_bare_url() {
         let lines = vec!["Visit https://example.com for more\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD034;
         let errors = rule.lint(&params);
@@ -117,15 +135,8 @@ mod tests {
     fn test_md034_fix_info() {
         let lines = vec!["Visit https://example.com for more\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD034;
         let errors = rule.lint(&params);
@@ -147,15 +158,8 @@ mod tests {
     fn test_md034_fix_info_at_start() {
         let lines = vec!["http://test.org/path\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD034;
         let errors = rule.lint(&params);
@@ -170,4 +174,37 @@ mod tests {
         assert_eq!(fix.delete_count, Some(20)); // "http://test.org/path" is 20 chars
         assert_eq!(fix.insert_text, Some("<http://test.org/path>".to_string()));
     }
+
+    fn lint_markdown(content: &str) -> Vec<LintError> {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
+        MD034.lint(&params)
+    }
+
+    #[test]
+    fn test_md034_url_in_inline_code() {
+        assert!(lint_markdown("Run `curl https://example.com` first.\n").is_empty());
+    }
+
+    #[test]
+    fn test_md034_url_in_code_block() {
+        assert!(lint_markdown("```sh\ncurl https://example.com\n```\n").is_empty());
+    }
+
+    #[test]
+    fn test_md034_url_in_html_attribute() {
+        assert!(lint_markdown("Go <a href=\"https://example.com\">here</a>.\n").is_empty());
+        assert!(lint_markdown("<div data-src=\"https://example.com\">\n</div>\n").is_empty());
+    }
+
+    #[test]
+    fn test_md034_url_in_prose_next_to_code() {
+        let content = "See `x` at https://example.com today\n";
+        let errors = lint_markdown(content);
+        assert_eq!(errors.len(), 1);
+        let fixed = crate::lint::apply_fixes(content, &errors);
+        assert_eq!(fixed, "See `x` at <https://example.com> today\n");
+    }
 }
@@ -69,6 +69,8 @@ impl Rule for MD035 {
                     suggestion: Some("Use consistent horizontal rule style".to_string()),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
@@ -112,15 +114,8 @@ mod tests {
 
         let lines = vec!["---\n", "\n", "---\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD035;
         let errors = rule.lint(&params);
@@ -156,15 +151,8 @@ mod tests {
 
         let lines = vec!["---\n", "\n", "***\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD035;
         let errors = rule.lint(&params);
@@ -211,15 +199,7 @@ mod tests {
             serde_json::Value::String("***".to_string()),
         );
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD035;
         let errors = rule.lint(&params);
@@ -271,15 +251,8 @@ mod tests {
 
         let lines = vec!["---\n", "\n", "***\n", "\n", "* * *\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD035;
         let errors = rule.lint(&params);
@@ -293,15 +266,8 @@ mod tests {
         let tokens = vec![];
         let lines = vec!["# Heading\n", "Some text\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD035;
         let errors = rule.lint(&params);
@@ -337,15 +303,8 @@ mod tests {
 
         let lines = vec!["---\n", "\n", "***\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD035;
         let errors = rule.lint(&params);
@@ -56,9 +56,8 @@ impl Rule for MD036 {
 
         // Create regex pattern to match punctuation at end of string
         let punctuation_pattern = format!("[{}]$", regex::escape(punctuation));
-        let punctuation_re = match regex::Regex::new(&punctuation_pattern) {
-            Ok(re) => re,
-            Err(_) => return errors, // Return empty if regex fails
+        let Some(punctuation_re) = params.user_regex(&punctuation_pattern) else {
+            return errors;
         };
 
         // Find all paragraph tokens
@@ -166,6 +165,8 @@ impl Rule for MD036 {
                             ),
                             severity: Severity::Error,
                             fix_only: false,
+                            line_text: None,
+                            related: None,
                         });
                     }
                 }
@@ -242,15 +243,8 @@ mod tests {
 
         let lines = vec!["_Heading_\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD036;
         let errors = rule.lint(&params);
@@ -276,15 +270,8 @@ mod tests {
 
         let lines = vec!["**Heading**\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD036;
         let errors = rule.lint(&params);
@@ -305,15 +292,8 @@ mod tests {
 
         let lines = vec!["_Not a heading._\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD036;
         let errors = rule.lint(&params);
@@ -331,15 +311,8 @@ mod tests {
 
         let lines = vec!["Normal text\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD036;
         let errors = rule.lint(&params);
@@ -363,15 +336,8 @@ mod tests {
 
         let lines = vec!["_Heading_\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD036;
         let errors = rule.lint(&params);
@@ -398,15 +364,8 @@ mod tests {
 
         let lines = vec!["**Heading**\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD036;
         let errors = rule.lint(&params);
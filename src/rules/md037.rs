@@ -1,4 +1,7 @@
 //! MD037 - Spaces inside emphasis markers
+//!
+//! Markers inside code spans and math (`$a * b$`) are literal text and are
+//! skipped.
 
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 use regex::Regex;
@@ -22,8 +25,12 @@ impl Rule for MD037 {
         &["whitespace", "emphasis", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        1
+    }
+
     fn parser_type(&self) -> ParserType {
-        ParserType::None
+        ParserType::Micromark
     }
 
     fn information(&self) -> Option<&'static str> {
@@ -32,12 +39,27 @@ impl Rule for MD037 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
+        let code_block_lines = params.code_block_lines();
+        let literal_spans = params.literal_spans();
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
 
+            // Markers inside code blocks are literal text
+            if code_block_lines[idx] {
+                continue;
+            }
+
             for caps in EMPHASIS_SPACE_RE.captures_iter(line) {
                 let full_match = caps.get(0).unwrap();
+                let in_literal = |offset: usize| {
+                    literal_spans
+                        .get(idx)
+                        .is_some_and(|spans| spans.iter().any(|span| span.contains(&offset)))
+                };
+                if in_literal(full_match.start()) || in_literal(full_match.end() - 1) {
+                    continue;
+                }
                 let open_marker = caps.get(1).unwrap().as_str();
                 let inner_content = caps.get(2).unwrap().as_str();
                 let close_marker = caps.get(3).unwrap().as_str();
@@ -61,6 +83,8 @@ impl Rule for MD037 {
                     suggestion: Some("Remove spaces inside emphasis markers".to_string()),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
@@ -140,6 +164,22 @@ mod tests {
         assert_eq!(fix.insert_text, Some("_text_".to_string()));
     }
 
+    #[test]
+    fn test_md037_skips_code_spans_and_math() {
+        let content =
+            "`a * bb * c` and $a * bb * c$ and $x * y$ or $z * w$\n\n$$\nx * yy * z\n$$\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
+        assert!(MD037.lint(&params).is_empty());
+
+        // The same text outside math is still reported
+        let lines = ["a * bb * c\n"];
+        let params = crate::types::RuleParams::test_with_tokens(&lines, &[], &config);
+        assert_eq!(MD037.lint(&params).len(), 1);
+    }
+
     #[test]
     fn test_md037_fix_info_no_error_no_fix() {
         let lines: Vec<&str> = vec!["This is *emphasis* text"];
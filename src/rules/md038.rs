@@ -22,6 +22,10 @@ impl Rule for MD038 {
         &["whitespace", "code", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::None
     }
@@ -32,10 +36,16 @@ impl Rule for MD038 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
+        let code_block_lines = params.code_block_lines();
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
 
+            // Markers inside code blocks are literal text
+            if code_block_lines[idx] {
+                continue;
+            }
+
             for caps in CODE_SPACE_RE.captures_iter(line) {
                 let full_match = caps.get(0).unwrap();
                 let inner_content = caps.get(1).unwrap().as_str();
@@ -59,6 +69,8 @@ impl Rule for MD038 {
                     suggestion: Some("Remove spaces inside code span markers".to_string()),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
@@ -22,6 +22,10 @@ impl Rule for MD039 {
         &["whitespace", "links", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::None
     }
@@ -59,6 +63,8 @@ impl Rule for MD039 {
                     suggestion: Some("Remove spaces inside link text".to_string()),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
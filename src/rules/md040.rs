@@ -1,5 +1,6 @@
 //! MD040 - Fenced code blocks should have a language specified
 
+use crate::helpers::{CodeFenceTracker, fence_line};
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
 pub struct MD040;
@@ -27,60 +28,52 @@ impl Rule for MD040 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
-        let mut in_code_block = false;
+        let mut fences = CodeFenceTracker::new();
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
+            let was_in_block = fences.in_code_block();
+            if !fences.update(line) || was_in_block {
+                continue;
+            }
+            // Opening fence - check if it has a language
+            let Some(fence) = fence_line(line) else {
+                continue;
+            };
+            if !fence.info.trim().is_empty() {
+                continue;
+            }
+
+            // Get the configured default language (default: "text")
+            let default_lang = params
+                .config
+                .get("default_language")
+                .and_then(|v| v.as_str())
+                .unwrap_or("text");
             let trimmed = line.trim();
 
-            if crate::helpers::is_code_fence(trimmed) {
-                let fence_chars = if trimmed.starts_with("```") {
-                    "```"
-                } else {
-                    "~~~"
-                };
-                let after_fence = trimmed.trim_start_matches(fence_chars).trim();
-
-                if in_code_block {
-                    // This is a closing fence
-                    in_code_block = false;
-                } else {
-                    // This is an opening fence - check if it has a language
-                    in_code_block = true;
-                    if after_fence.is_empty() {
-                        // Get the configured default language (default: "text")
-                        let default_lang = params
-                            .config
-                            .get("default_language")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("text");
-
-                        let leading_spaces = line.len() - line.trim_start().len();
-                        let fence_len = fence_chars.len();
-
-                        errors.push(LintError {
-                            line_number,
-                            rule_names: self.names(),
-                            rule_description: self.description(),
-                            error_detail: Some("Missing language specification".to_string()),
-                            error_context: Some(trimmed.to_string()),
-                            rule_information: self.information(),
-                            error_range: Some((leading_spaces + 1, trimmed.len())),
-                            fix_info: Some(FixInfo {
-                                line_number: Some(line_number),
-                                edit_column: Some(leading_spaces + fence_len + 1),
-                                delete_count: None,
-                                insert_text: Some(default_lang.to_string()),
-                            }),
-                            suggestion: Some(
-                                "Specify a language for fenced code blocks".to_string(),
-                            ),
-                            severity: Severity::Error,
-                            fix_only: false,
-                        });
-                    }
-                }
-            }
+            // The language goes directly after the fence run, replacing any
+            // stray whitespace, so the result is always `` ```lang ``
+            errors.push(LintError {
+                line_number,
+                rule_names: self.names(),
+                rule_description: self.description(),
+                error_detail: Some("Missing language specification".to_string()),
+                error_context: Some(trimmed.to_string()),
+                rule_information: self.information(),
+                error_range: Some((fence.indent + 1, trimmed.len())),
+                fix_info: Some(FixInfo {
+                    line_number: Some(line_number),
+                    edit_column: Some(fence.indent + fence.length + 1),
+                    delete_count: (!fence.info.is_empty()).then_some(fence.info.len() as i32),
+                    insert_text: Some(default_lang.to_string()),
+                }),
+                suggestion: Some("Specify a language for fenced code blocks".to_string()),
+                severity: Severity::Error,
+                fix_only: false,
+                line_text: None,
+                related: None,
+            });
         }
 
         errors
@@ -96,15 +89,8 @@ mod tests {
     fn test_md040_with_language() {
         let lines = vec!["```rust\n", "let x = 5;\n", "```\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD040;
         let errors = rule.lint(&params);
@@ -115,15 +101,8 @@ mod tests {
     fn test_md040_no_language() {
         let lines = vec!["```\n", "code\n", "```\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD040;
         let errors = rule.lint(&params);
@@ -135,15 +114,8 @@ mod tests {
     fn test_md040_fix_info() {
         let lines = vec!["```\n", "code here\n", "```\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD040;
         let errors = rule.lint(&params);
@@ -166,15 +138,7 @@ mod tests {
             serde_json::Value::String("plaintext".to_string()),
         );
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &config,
-            workspace_headings: None,
-        };
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD040;
         let errors = rule.lint(&params);
@@ -183,4 +147,17 @@ mod tests {
         let fix = errors[0].fix_info.as_ref().expect("Should have fix_info");
         assert_eq!(fix.insert_text, Some("plaintext".to_string()));
     }
+
+    #[test]
+    fn test_md040_fix_replaces_stray_whitespace() {
+        let lines = vec!["````  \n", "```\n", "````\n"];
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
+        let errors = MD040.lint(&params);
+        // The inner ``` is content of the four-backtick block
+        assert_eq!(errors.len(), 1);
+        let fix = errors[0].fix_info.as_ref().unwrap();
+        assert_eq!(fix.edit_column, Some(5));
+        assert_eq!(fix.delete_count, Some(2));
+    }
 }
@@ -1,9 +1,18 @@
 //! MD041 - First line in a file should be a top-level heading
 //!
 //! This rule checks that the first line of the file is a top-level (h1) heading.
+//!
+//! Options:
+//! - `allow_preamble_html` (default `false`): allow leading HTML blocks (such
+//!   as a centered badges `<p>`) before the first heading
+//! - `allowed_preamble_patterns` (default `[]`): regexes; a leading block
+//!   whose first line matches one is allowed before the first heading
+//! - `front_matter_title` (default: a `title:` key): a front matter title
+//!   stands in for the heading, as in MD025
 
 use crate::parser::TokenExt;
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+use regex::Regex;
 
 pub struct MD041;
 
@@ -20,6 +29,10 @@ impl Rule for MD041 {
         &["headings", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        -1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::Micromark
     }
@@ -31,14 +44,18 @@ impl Rule for MD041 {
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
 
-        // Skip empty files
-        if params.lines.is_empty() {
+        // Skip empty files and files titled by their front matter
+        if params.lines.is_empty() || params.front_matter_title_line().is_some() {
             return errors;
         }
 
-        // Skip front matter
+        // Skip front matter and any blank lines separating it from content
         let first_content_line = if !params.front_matter_lines.is_empty() {
-            params.front_matter_lines.len() + 1
+            let fm_len = params.front_matter_lines.len();
+            params.lines[fm_len..]
+                .iter()
+                .position(|line| !line.trim().is_empty())
+                .map_or(fm_len + 1, |offset| fm_len + offset + 1)
         } else {
             1
         };
@@ -47,7 +64,10 @@ impl Rule for MD041 {
         let headings = params.tokens.filter_by_type("heading");
 
         if let Some(first_heading) = headings.first() {
-            // Check if first heading is on the first content line
+            // Check if first heading is on the first content line (after
+            // any allowed preamble)
+            let first_content_line =
+                skip_preamble(params, first_content_line, first_heading.start_line);
             if first_heading.start_line != first_content_line {
                 // Fix: insert a heading before the current content
                 errors.push(LintError {
@@ -69,6 +89,8 @@ impl Rule for MD041 {
                     ),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         } else {
@@ -90,6 +112,8 @@ impl Rule for MD041 {
                 suggestion: Some("Add a top-level heading as the first line".to_string()),
                 severity: Severity::Error,
                 fix_only: false,
+                line_text: None,
+                related: None,
             });
         }
 
@@ -97,6 +121,56 @@ impl Rule for MD041 {
     }
 }
 
+/// Skip leading blocks allowed by `allow_preamble_html` and
+/// `allowed_preamble_patterns`, stopping at the first heading.
+///
+/// Returns the 1-based line where the heading is expected.
+fn skip_preamble(params: &RuleParams, mut line_number: usize, heading_line: usize) -> usize {
+    let allow_html = params
+        .config
+        .get("allow_preamble_html")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let patterns: Vec<Regex> = params
+        .config
+        .get("allowed_preamble_patterns")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .filter_map(|p| params.user_regex(p))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if !allow_html && patterns.is_empty() {
+        return line_number;
+    }
+
+    let lines = params.lines;
+    while line_number < heading_line
+        && let Some(line) = lines.get(line_number - 1)
+    {
+        let trimmed = line.trim();
+        let allowed = (allow_html && trimmed.starts_with('<'))
+            || patterns.iter().any(|re| re.is_match(trimmed));
+        if !allowed {
+            break;
+        }
+
+        // Skip the rest of the block, then the blank lines after it
+        let mut next = line_number;
+        while lines.get(next).is_some_and(|l| !l.trim().is_empty()) {
+            next += 1;
+        }
+        while lines.get(next).is_some_and(|l| l.trim().is_empty()) {
+            next += 1;
+        }
+        line_number = next + 1;
+    }
+    line_number
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,15 +193,8 @@ mod tests {
 
         let lines = vec!["# Heading\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD041;
         let errors = rule.lint(&params);
@@ -139,15 +206,8 @@ mod tests {
         let tokens = vec![];
         let lines = vec!["Just some text\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD041;
         let errors = rule.lint(&params);
@@ -170,15 +230,8 @@ mod tests {
 
         let lines = vec!["Some text\n", "\n", "# Heading\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD041;
         let errors = rule.lint(&params);
@@ -190,15 +243,8 @@ mod tests {
         let tokens = vec![];
         let lines = vec!["Just some text\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &tokens,
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
 
         let rule = MD041;
         let errors = rule.lint(&params);
@@ -225,19 +271,105 @@ mod tests {
 
         let lines = vec!["Some text\n", "\n", "# Heading\n"];
 
+        let config = HashMap::new();
+        let params = RuleParams::test_with_tokens(&lines, &tokens, &config);
+
+        let rule = MD041;
+        let errors = rule.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].fix_info.is_some());
+    }
+
+    #[test]
+    fn test_md041_blank_line_after_front_matter() {
+        let tokens = vec![Token {
+            token_type: "heading".to_string(),
+            start_line: 5,
+            start_column: 1,
+            end_line: 5,
+            end_column: 8,
+            text: "# Title".to_string(),
+            children: vec![],
+            parent: None,
+            metadata: HashMap::new(),
+        }];
+
+        // No title key, so the heading is still checked
+        let lines = vec!["---\n", "author: Test\n", "---\n", "\n", "# Title\n"];
+
         let params = RuleParams {
             name: "test.md",
             version: "0.1.0",
             lines: &lines,
-            front_matter_lines: &[],
+            front_matter_lines: &lines[..3],
             tokens: &tokens,
             config: &HashMap::new(),
             workspace_headings: None,
+            global_config: None,
+            has_bom: false,
+            patterns: None,
+            analysis: None,
         };
 
-        let rule = MD041;
-        let errors = rule.lint(&params);
+        assert!(MD041.lint(&params).is_empty());
+    }
+
+    fn lint_content(content: &str, config: serde_json::Value) -> Vec<LintError> {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        let config: HashMap<String, serde_json::Value> = serde_json::from_value(config).unwrap();
+        MD041.lint(&RuleParams::test_with_tokens(&lines, &tokens, &config))
+    }
+
+    const BADGES: &str = "<p align=\"center\">\n  <img src=\"logo.png\">\n</p>\n\n# Title\n";
+
+    #[test]
+    fn test_md041_html_preamble_flagged_by_default() {
+        assert_eq!(lint_content(BADGES, serde_json::json!({})).len(), 1);
+    }
+
+    #[test]
+    fn test_md041_allow_preamble_html() {
+        let config = serde_json::json!({"allow_preamble_html": true});
+        assert!(lint_content(BADGES, config.clone()).is_empty());
+
+        // Text between the preamble and the heading is still flagged
+        let errors = lint_content("<p>badges</p>\n\nIntro\n\n# Title\n", config.clone());
         assert_eq!(errors.len(), 1);
-        assert!(errors[0].fix_info.is_some());
+        assert_eq!(errors[0].line_number, 3);
+
+        // No heading anywhere is still flagged
+        assert_eq!(lint_content("<p>badges</p>\n\nText\n", config).len(), 1);
+    }
+
+    #[test]
+    fn test_md041_allowed_preamble_patterns() {
+        let content = "[![CI](ci.svg)](ci) [![Docs](docs.svg)](docs)\n\n# Title\n";
+        assert_eq!(lint_content(content, serde_json::json!({})).len(), 1);
+
+        let config = serde_json::json!({"allowed_preamble_patterns": ["^\\[!\\["]});
+        assert!(lint_content(content, config.clone()).is_empty());
+        assert_eq!(lint_content("Intro\n\n# Title\n", config).len(), 1);
+    }
+
+    #[test]
+    fn test_md041_front_matter_title_stands_in_for_heading() {
+        let content = "---\ntitle: Post\n---\n\nIntro\n\n## Section\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let lint = |config: serde_json::Value| {
+            let config: HashMap<String, serde_json::Value> =
+                serde_json::from_value(config).unwrap();
+            let params = RuleParams {
+                front_matter_lines: &lines[..3],
+                ..RuleParams::test(&lines, &config)
+            };
+            MD041.lint(&params)
+        };
+
+        assert!(lint(serde_json::json!({})).is_empty());
+        assert_eq!(
+            lint(serde_json::json!({"front_matter_title": false})).len(),
+            1
+        );
     }
 }
@@ -1,10 +1,13 @@
 //! MD042 - No empty links
 //!
-//! This rule checks for links with no URL or only a fragment (#).
+//! This rule checks for links with no URL or only a fragment (#). Empty
+//! destinations include `()`, whitespace-only `( )` and `(<>)`; reference
+//! links are checked against their definitions. A fragment naming an anchor
+//! (`#section`) is a real destination, validated by MD051. Images are exempt.
 
+use crate::helpers::{destinations_by_label, link_destination, normalize_label};
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 use regex::Regex;
-use std::collections::HashMap;
 use std::sync::LazyLock;
 
 static INLINE_LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
@@ -22,36 +25,23 @@ static REFERENCE_LINK_RE: LazyLock<Regex> = LazyLock::new(|| {
 static LINK_DEFINITION_RE: LazyLock<Regex> = LazyLock::new(|| {
     // Match link definitions: [ref]: url
     // Note: no $ anchor because lines may have trailing \n
-    Regex::new(r"^\s*\[([^\]]+)\]:\s*(\S*)").expect("valid regex")
+    Regex::new(r"^\s*\[([^\]]+)\]:").expect("valid regex")
 });
 
 pub struct MD042;
 
 impl MD042 {
-    /// Check if a URL is empty or just a fragment
-    fn is_empty_or_fragment_only(url: &str) -> bool {
-        let trimmed = url.trim();
-        if trimmed.is_empty() {
-            return true;
-        }
-
-        // Check for empty angle brackets: <> or < >
-        let angle_stripped = trimmed.trim_start_matches('<').trim_end_matches('>').trim();
-        if angle_stripped.is_empty() {
-            return true;
-        }
-
-        // Check for just # or # with title
-        if let Some(after_hash) = trimmed.strip_prefix('#') {
-            let after_hash = after_hash.trim();
-            // If nothing after # or if it starts with a quote (title), it's empty
-            if after_hash.is_empty() || after_hash.starts_with('"') || after_hash.starts_with('\'')
-            {
-                return true;
-            }
-        }
+    /// Check if a link destination is empty or just a fragment
+    ///
+    /// `raw` is the text inside an inline link's parentheses (destination
+    /// plus optional title) or a definition's resolved destination.
+    fn is_empty_or_fragment_only(raw: &str) -> bool {
+        matches!(link_destination(raw), "" | "#")
+    }
 
-        false
+    /// Whether the match starting at `start` is an image (`![...]`)
+    fn is_image(line: &str, start: usize) -> bool {
+        start > 0 && line.as_bytes()[start - 1] == b'!'
     }
 }
 
@@ -79,22 +69,14 @@ impl Rule for MD042 {
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
 
-        // First pass: collect link definitions
-        let mut definitions: HashMap<String, String> = HashMap::new();
-        for line in params.lines.iter() {
-            if let Some(cap) = LINK_DEFINITION_RE.captures(line) {
-                let ref_name = cap.get(1).unwrap().as_str().trim().to_lowercase();
-                let url = cap.get(2).unwrap().as_str().trim().to_string();
-                definitions.insert(ref_name, url);
-            }
-        }
+        let code_blocks = params.code_block_lines();
+        let definitions = destinations_by_label(&params.reference_definitions());
 
-        // Second pass: check links
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
 
-            // Skip link definition lines
-            if LINK_DEFINITION_RE.is_match(line) {
+            // Skip code blocks and link definition lines
+            if code_blocks[idx] || LINK_DEFINITION_RE.is_match(line) {
                 continue;
             }
 
@@ -103,6 +85,10 @@ impl Rule for MD042 {
                 let full_match = cap.get(0).unwrap();
                 let url = cap.get(2).unwrap().as_str();
 
+                if Self::is_image(line, full_match.start()) {
+                    continue;
+                }
+
                 if Self::is_empty_or_fragment_only(url) {
                     // Calculate position for fix
                     let paren_content = cap.get(2).unwrap();
@@ -129,6 +115,8 @@ impl Rule for MD042 {
                         ),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
@@ -137,9 +125,11 @@ impl Rule for MD042 {
             for cap in REFERENCE_LINK_RE.captures_iter(line) {
                 let full_match = cap.get(0).unwrap();
 
-                // Skip if this is actually an inline link (followed by '(')
+                // Skip if this is actually an inline link (followed by '(') or an image
                 let end_pos = full_match.end();
-                if line.as_bytes().get(end_pos) == Some(&b'(') {
+                if line.as_bytes().get(end_pos) == Some(&b'(')
+                    || Self::is_image(line, full_match.start())
+                {
                     continue;
                 }
 
@@ -157,7 +147,7 @@ impl Rule for MD042 {
                     text
                 };
 
-                let ref_key = ref_name.trim().to_lowercase();
+                let ref_key = normalize_label(ref_name);
 
                 // Check if this reference exists and if it points to an empty URL
                 if let Some(url) = definitions.get(&ref_key)
@@ -175,6 +165,8 @@ impl Rule for MD042 {
                         suggestion: None,
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
@@ -193,15 +185,8 @@ mod tests {
     fn test_md042_empty_inline_link() {
         let lines = vec!["[text]()\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -213,15 +198,8 @@ mod tests {
     fn test_md042_empty_with_angle_brackets() {
         let lines = vec!["[text](<>)\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -232,15 +210,8 @@ mod tests {
     fn test_md042_fragment_only() {
         let lines = vec!["[text](#)\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -251,15 +222,8 @@ mod tests {
     fn test_md042_fragment_with_title() {
         let lines = vec!["[text](# \"title\")\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -270,15 +234,8 @@ mod tests {
     fn test_md042_valid_link() {
         let lines = vec!["[text](https://example.com)\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -289,15 +246,8 @@ mod tests {
     fn test_md042_valid_fragment() {
         let lines = vec!["[text](#section)\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -308,15 +258,8 @@ mod tests {
     fn test_md042_reference_link_with_empty_definition() {
         let lines = vec!["[text][frag]\n", "\n", "[frag]: #\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -328,15 +271,8 @@ mod tests {
     fn test_md042_reference_link_shorthand() {
         let lines = vec!["[frag][]\n", "\n", "[frag]: #\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -347,15 +283,8 @@ mod tests {
     fn test_md042_reference_link_implicit() {
         let lines = vec!["[frag]\n", "\n", "[frag]: #\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -366,15 +295,8 @@ mod tests {
     fn test_md042_reference_link_with_valid_definition() {
         let lines = vec!["[text][ref]\n", "\n", "[ref]: https://example.com\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -385,15 +307,8 @@ mod tests {
     fn test_md042_multiple_empty_links_on_same_line() {
         let lines = vec!["[text1](link-1) [text2]() [text3](link-3)\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -411,15 +326,8 @@ mod tests {
     fn test_md042_fix_empty_inline_link() {
         let lines = vec!["[text]()\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -434,15 +342,8 @@ mod tests {
     fn test_md042_fix_fragment_only() {
         let lines = vec!["[text](#)\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -457,15 +358,8 @@ mod tests {
     fn test_md042_no_fix_reference_link() {
         let lines = vec!["[text][frag]\n", "\n", "[frag]: #\n"];
 
-        let params = RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &HashMap::new(),
-            workspace_headings: None,
-        };
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
 
         let rule = MD042;
         let errors = rule.lint(&params);
@@ -473,4 +367,55 @@ mod tests {
         // Reference links should not have fix_info
         assert!(errors[0].fix_info.is_none());
     }
+
+    fn lint_lines(lines: &[&str]) -> Vec<LintError> {
+        let config = HashMap::new();
+        MD042.lint(&RuleParams::test(lines, &config))
+    }
+
+    #[test]
+    fn test_md042_whitespace_and_angle_destinations() {
+        assert_eq!(lint_lines(&["[text]( )\n"]).len(), 1);
+        assert_eq!(lint_lines(&["[text](< >)\n"]).len(), 1);
+        assert_eq!(lint_lines(&["[text](<> \"title\")\n"]).len(), 1);
+        assert_eq!(lint_lines(&["[text](<#>)\n"]).len(), 1);
+        assert!(lint_lines(&["[text](<page one.md>)\n"]).is_empty());
+    }
+
+    #[test]
+    fn test_md042_existing_fragment_is_valid() {
+        assert!(lint_lines(&["# Section\n", "\n", "[text](#section)\n"]).is_empty());
+    }
+
+    #[test]
+    fn test_md042_reference_definitions() {
+        // Empty angle-bracket destination
+        assert_eq!(lint_lines(&["[text][ref]\n", "\n", "[ref]: <>\n"]).len(), 1);
+        // Definition with nothing on its line or the next
+        assert_eq!(
+            lint_lines(&["[text][ref]\n", "\n", "[ref]:\n", "\n"]).len(),
+            1
+        );
+        // Destination on the following line
+        assert!(
+            lint_lines(&["[text][ref]\n", "\n", "[ref]:\n", "  https://example.com\n"]).is_empty()
+        );
+        // Labels match case-insensitively with collapsed whitespace
+        assert_eq!(
+            lint_lines(&["[the  Ref]\n", "\n", "[the ref]: #\n"]).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_md042_images_exempt() {
+        assert!(lint_lines(&["![alt]()\n"]).is_empty());
+        assert!(lint_lines(&["![alt][ref]\n", "\n", "[ref]: <>\n"]).is_empty());
+        assert_eq!(lint_lines(&["![alt]() and [text]()\n"]).len(), 1);
+    }
+
+    #[test]
+    fn test_md042_skips_code_blocks() {
+        assert!(lint_lines(&["```\n", "[text]()\n", "```\n"]).is_empty());
+    }
 }
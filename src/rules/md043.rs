@@ -121,6 +121,8 @@ impl Rule for MD043 {
                     suggestion: Some("Follow the required heading structure".to_string()),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
                 continue;
             }
@@ -144,6 +146,8 @@ impl Rule for MD043 {
                     suggestion: Some("Follow the required heading structure".to_string()),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
             actual_idx += 1;
@@ -164,6 +168,8 @@ impl Rule for MD043 {
                 suggestion: Some("Follow the required heading structure".to_string()),
                 severity: Severity::Error,
                 fix_only: false,
+                line_text: None,
+                related: None,
             });
             actual_idx += 1;
         }
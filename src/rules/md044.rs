@@ -1,4 +1,8 @@
 //! MD044 - Proper names should have the correct capitalization
+//!
+//! Code blocks and code spans are skipped by default, since code keeps its
+//! source spelling; `ignore_code_blocks` and `ignore_inline_code` control
+//! the two separately.
 
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
@@ -18,7 +22,7 @@ impl Rule for MD044 {
     }
 
     fn parser_type(&self) -> ParserType {
-        ParserType::None
+        ParserType::Micromark
     }
 
     fn information(&self) -> Option<&'static str> {
@@ -47,73 +51,71 @@ impl Rule for MD044 {
                 ]
             });
 
-        let check_code_blocks = params
-            .config
-            .get("code_blocks")
-            .and_then(|v| v.as_bool())
-            .unwrap_or(false);
+        let bool_option = |key: &str| params.config.get(key).and_then(|v| v.as_bool());
+        // `code_blocks: true` is the older spelling of `ignore_code_blocks: false`
+        let ignore_code_blocks = bool_option("ignore_code_blocks")
+            .unwrap_or_else(|| !bool_option("code_blocks").unwrap_or(false));
+        let ignore_inline_code = bool_option("ignore_inline_code").unwrap_or(true);
 
-        // Build lookup pairs: (lowercase, correct)
-        let proper_names: Vec<(String, String)> = names
+        // Case-insensitive matchers, run on the original line so columns
+        // stay right even where lowercasing changes a character's length
+        let proper_names: Vec<(regex::Regex, &str)> = names
             .iter()
-            .map(|name| (name.to_lowercase(), name.clone()))
+            .filter_map(|name| {
+                let re = params.user_regex(&format!("(?i){}", regex::escape(name)))?;
+                Some((re, name.as_str()))
+            })
             .collect();
 
-        let mut in_code_block = false;
+        let code_block_lines = params.code_block_lines();
+        let literal_spans = params.literal_spans();
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
             let trimmed = line.trim();
 
-            // Track code blocks
-            if crate::helpers::is_code_fence(trimmed) {
-                in_code_block = !in_code_block;
-                continue;
-            }
-
-            // Skip code block content unless configured to check
-            if in_code_block && !check_code_blocks {
+            // Skip code block content unless configured to check; fence
+            // lines (and their info strings) are never checked
+            if code_block_lines[idx]
+                && (ignore_code_blocks || crate::helpers::is_code_fence(trimmed))
+            {
                 continue;
             }
 
-            let lower_line = line.to_lowercase();
-
-            for (incorrect, correct) in &proper_names {
-                // Iterate over all occurrences of the lowercase name in the line
-                let mut search_start = 0;
-                while let Some(pos) = lower_line[search_start..].find(incorrect.as_str()) {
-                    let absolute_pos = search_start + pos;
-                    let end_pos = absolute_pos + correct.len();
-
-                    // Check if this particular occurrence is already correctly cased
-                    if end_pos <= line.len() && &line[absolute_pos..end_pos] != correct.as_str() {
-                        let actual = &line[absolute_pos..end_pos];
-                        errors.push(LintError {
-                            line_number,
-                            rule_names: self.names(),
-                            rule_description: self.description(),
-                            error_detail: Some(format!(
-                                "Expected: {}; Actual: {}",
-                                correct, actual
-                            )),
-                            error_context: None,
-                            rule_information: self.information(),
-                            error_range: Some((absolute_pos + 1, correct.len())),
-                            fix_info: Some(FixInfo {
-                                line_number: None,
-                                edit_column: Some(absolute_pos + 1), // 1-based
-                                delete_count: Some(correct.len() as i32),
-                                insert_text: Some(correct.clone()),
-                            }),
-                            suggestion: Some(
-                                "Use proper capitalization for this proper noun".to_string(),
-                            ),
-                            severity: Severity::Error,
-                            fix_only: false,
-                        });
+            // Every occurrence on the line is reported, each with its own fix
+            for (re, correct) in &proper_names {
+                for m in re.find_iter(line) {
+                    let actual = m.as_str();
+                    if actual == *correct
+                        || ignore_inline_code
+                            && literal_spans[idx]
+                                .iter()
+                                .any(|span| span.contains(&m.start()))
+                    {
+                        continue;
                     }
-
-                    search_start = absolute_pos + incorrect.len();
+                    errors.push(LintError {
+                        line_number,
+                        rule_names: self.names(),
+                        rule_description: self.description(),
+                        error_detail: Some(format!("Expected: {}; Actual: {}", correct, actual)),
+                        error_context: None,
+                        rule_information: self.information(),
+                        error_range: Some((m.start() + 1, actual.len())),
+                        fix_info: Some(FixInfo {
+                            line_number: None,
+                            edit_column: Some(m.start() + 1), // 1-based
+                            delete_count: Some(actual.len() as i32),
+                            insert_text: Some(correct.to_string()),
+                        }),
+                        suggestion: Some(
+                            "Use proper capitalization for this proper noun".to_string(),
+                        ),
+                        severity: Severity::Error,
+                        fix_only: false,
+                        line_text: None,
+                        related: None,
+                    });
                 }
             }
         }
@@ -287,4 +289,62 @@ mod tests {
         // "caf\u{e9}" should be detected as wrong case for "Caf\u{e9}"
         assert_eq!(errors.len(), 1);
     }
+
+    #[test]
+    fn test_md044_fixes_every_occurrence() {
+        let content = "Fork it on github, then open a github issue.\n";
+        let config: crate::Config =
+            serde_json::from_str(r#"{"default": false, "MD044": true}"#).unwrap();
+        let options = crate::LintOptions::new()
+            .with_string("a.md", content)
+            .with_config(config);
+        let results = crate::lint_sync(&options).unwrap();
+        let errors = results.get("a.md").unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].error_range, Some((12, 6)));
+        assert_eq!(errors[1].error_range, Some((32, 6)));
+        assert_eq!(
+            crate::apply_fixes(content, errors),
+            "Fork it on GitHub, then open a GitHub issue.\n"
+        );
+    }
+
+    #[test]
+    fn test_md044_columns_after_multibyte_lowercase() {
+        // 'İ' lowercases to two characters; columns must follow the original line
+        let rule = MD044;
+        let lines = vec!["İ github\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = rule.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error_range, Some(("İ ".len() + 1, 6)));
+    }
+
+    fn lint_md044(content: &str, options: &str) -> Vec<usize> {
+        let config: crate::config::Config =
+            serde_json::from_str(&format!(r#"{{"default": false, "MD044": {}}}"#, options))
+                .unwrap();
+        crate::lint_string(content, &config)
+            .unwrap()
+            .iter()
+            .map(|e| e.line_number)
+            .collect()
+    }
+
+    #[test]
+    fn test_md044_inline_code_ignored_by_default() {
+        let content = "Hosted on github.\n\nRun `github --version`.\n\n```\ngithub\n```\n";
+        assert_eq!(lint_md044(content, "true"), [1]);
+        assert_eq!(
+            lint_md044(content, r#"{"ignore_inline_code": false}"#),
+            [1, 3]
+        );
+        assert_eq!(
+            lint_md044(content, r#"{"ignore_code_blocks": false}"#),
+            [1, 6]
+        );
+        // The older `code_blocks` option still works
+        assert_eq!(lint_md044(content, r#"{"code_blocks": true}"#), [1, 6]);
+    }
 }
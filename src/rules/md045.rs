@@ -1,11 +1,118 @@
 //! MD045 - Images should have alternate text (alt text)
+//!
+//! Inline (`![alt](src)`) and reference (`![alt][ref]`, `![alt]`) images
+//! must have alt text. Opt-in quality options also reject alt text that is
+//! too short, is the image's filename, or is a placeholder such as "image";
+//! those checks extend to `<img alt="...">` tags when MD033 allows `<img>`.
 
+use crate::helpers::{destinations_by_label, link_destination, normalize_label};
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 use regex::Regex;
 use std::sync::LazyLock;
 
-static IMAGE_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"!\[([^\]]*)\]\([^)]+\)").expect("valid regex"));
+/// An image: alt text, then an inline destination, a reference label, or
+/// nothing (shortcut reference)
+static IMAGE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"!\[([^\]]*)\](?:\(([^)]+)\)|\[([^\]]*)\])?").expect("valid regex")
+});
+
+static IMG_TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)<img\b[^>]*>").expect("valid regex"));
+
+static ALT_ATTR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\salt\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).expect("valid regex")
+});
+
+static SRC_ATTR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\ssrc\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+))"#).expect("valid regex")
+});
+
+/// Alt text quality options; all off by default
+struct AltTextChecks {
+    min_length: usize,
+    forbid_filename: bool,
+    /// Lowercased
+    prohibited_texts: Vec<String>,
+}
+
+impl AltTextChecks {
+    fn from_params(params: &RuleParams) -> Self {
+        Self {
+            min_length: params
+                .config
+                .get("min_length")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0) as usize,
+            forbid_filename: params
+                .config
+                .get("forbid_filename")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            prohibited_texts: params
+                .config
+                .get("prohibited_texts")
+                .and_then(|v| v.as_array())
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str())
+                        .map(|s| s.trim().to_lowercase())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.min_length > 0 || self.forbid_filename || !self.prohibited_texts.is_empty()
+    }
+
+    /// Detail for the first failed check, if any
+    fn check(&self, alt: &str, src: &str) -> Option<String> {
+        let alt = alt.trim();
+        if self.prohibited_texts.contains(&alt.to_lowercase()) {
+            return Some(format!("Prohibited alt text: \"{}\"", alt));
+        }
+        if self.forbid_filename {
+            let path = src.split(['?', '#']).next().unwrap_or("");
+            let filename = path.rsplit('/').next().unwrap_or("");
+            if !filename.is_empty() && alt.eq_ignore_ascii_case(filename) {
+                return Some(format!("Alt text is the image filename: \"{}\"", alt));
+            }
+        }
+        let length = alt.chars().count();
+        if length < self.min_length {
+            return Some(format!(
+                "Alt text too short: Expected: >= {}; Actual: {}",
+                self.min_length, length
+            ));
+        }
+        None
+    }
+}
+
+/// Whether MD033 lets `<img>` tags through, so their alt text is checked here
+fn html_img_allowed(params: &RuleParams) -> bool {
+    let Some(config) = params.global_config else {
+        return false;
+    };
+    if !config.is_rule_enabled("MD033") {
+        return true;
+    }
+    params
+        .sibling_option("MD033", "allowed_elements")
+        .and_then(|v| v.as_array())
+        .is_some_and(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .any(|s| s.eq_ignore_ascii_case("img"))
+        })
+}
+
+/// Value of the first capture group that participated in the match
+fn attr_value<'h>(re: &Regex, tag: &'h str) -> Option<&'h str> {
+    let cap = re.captures(tag)?;
+    (1..cap.len()).find_map(|i| cap.get(i)).map(|m| m.as_str())
+}
 
 pub struct MD045;
 
@@ -32,18 +139,36 @@ impl Rule for MD045 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
+        let checks = AltTextChecks::from_params(params);
+        let check_html = checks.is_enabled() && html_img_allowed(params);
+        let code_block_lines = params.code_block_lines();
+        let definitions = destinations_by_label(&params.reference_definitions());
 
         for (idx, line) in params.lines.iter().enumerate() {
+            if code_block_lines.get(idx).copied().unwrap_or(false) {
+                continue;
+            }
             let line_number = idx + 1;
 
             for cap in IMAGE_RE.captures_iter(line) {
-                let alt_text = &cap[1];
-                if alt_text.trim().is_empty() {
-                    // Calculate column position for the alt text
-                    let full_match = cap.get(0).unwrap();
-                    let alt_match = cap.get(1).unwrap();
-                    let alt_col = alt_match.start() + 1; // 1-based column
+                let full_match = cap.get(0).unwrap();
+                let alt_match = cap.get(1).unwrap();
+                let alt_text = alt_match.as_str();
 
+                // Reference images only count when their label is defined
+                let src = match (cap.get(2), cap.get(3)) {
+                    (Some(dest), _) => link_destination(dest.as_str()),
+                    (None, label) => {
+                        let label = label.map_or("", |m| m.as_str());
+                        let label = if label.is_empty() { alt_text } else { label };
+                        match definitions.get(&normalize_label(label)) {
+                            Some(dest) => dest.as_str(),
+                            None => continue,
+                        }
+                    }
+                };
+
+                if alt_text.trim().is_empty() {
                     errors.push(LintError {
                         line_number,
                         rule_names: self.names(),
@@ -54,7 +179,7 @@ impl Rule for MD045 {
                         error_range: Some((full_match.start() + 1, full_match.len())),
                         fix_info: Some(FixInfo {
                             line_number: None,
-                            edit_column: Some(alt_col),
+                            edit_column: Some(alt_match.start() + 1), // 1-based
                             delete_count: Some(alt_text.len() as i32),
                             insert_text: Some("image".to_string()),
                         }),
@@ -63,7 +188,26 @@ impl Rule for MD045 {
                         ),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
+                } else if let Some(detail) = checks.check(alt_text, src) {
+                    errors.push(self.quality_error(line_number, full_match, detail));
+                }
+            }
+
+            if check_html {
+                for tag in IMG_TAG_RE.find_iter(line) {
+                    // An empty `alt` marks a decorative image in HTML
+                    let Some(alt) =
+                        attr_value(&ALT_ATTR_RE, tag.as_str()).filter(|alt| !alt.trim().is_empty())
+                    else {
+                        continue;
+                    };
+                    let src = attr_value(&SRC_ATTR_RE, tag.as_str()).unwrap_or("");
+                    if let Some(detail) = checks.check(alt, src) {
+                        errors.push(self.quality_error(line_number, tag, detail));
+                    }
                 }
             }
         }
@@ -72,6 +216,27 @@ impl Rule for MD045 {
     }
 }
 
+impl MD045 {
+    /// Error for alt text that fails a quality check (not auto-fixable)
+    fn quality_error(&self, line_number: usize, image: regex::Match, detail: String) -> LintError {
+        LintError {
+            line_number,
+            rule_names: self.names(),
+            rule_description: self.description(),
+            error_detail: Some(detail),
+            error_context: Some(image.as_str().to_string()),
+            rule_information: self.information(),
+            error_range: Some((image.start() + 1, image.len())),
+            fix_info: None,
+            suggestion: Some("Describe what the image shows".to_string()),
+            severity: Severity::Error,
+            fix_only: false,
+            line_text: None,
+            related: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,4 +318,99 @@ mod tests {
         let params = crate::types::RuleParams::test(&lines, &config);
         assert_eq!(MD045.lint(&params).len(), 0);
     }
+
+    fn lint_with(lines: &[&str], options: serde_json::Value) -> Vec<LintError> {
+        let config: HashMap<String, serde_json::Value> = serde_json::from_value(options).unwrap();
+        let params = crate::types::RuleParams::test(lines, &config);
+        MD045.lint(&params)
+    }
+
+    #[test]
+    fn test_md045_reference_images() {
+        let lines = vec![
+            "![][logo] and ![logo] and ![Logo][]\n",
+            "\n",
+            "[logo]: logo.png\n",
+        ];
+        let errors = lint_with(&lines, serde_json::json!({}));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error_range, Some((1, 9)));
+
+        // Undefined labels are not images
+        let lines = vec!["![][missing] and ![]\n"];
+        assert!(lint_with(&lines, serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_md045_skips_code_blocks() {
+        let lines = vec!["```\n", "![](image.png)\n", "```\n"];
+        assert!(lint_with(&lines, serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_md045_quality_checks_off_by_default() {
+        let lines = vec!["![image](screenshot.png) ![a](b.png)\n"];
+        assert!(lint_with(&lines, serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_md045_min_length() {
+        let lines = vec!["![ab](a.png) ![Chart](b.png)\n"];
+        let errors = lint_with(&lines, serde_json::json!({"min_length": 3}));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Alt text too short: Expected: >= 3; Actual: 2")
+        );
+        assert!(errors[0].fix_info.is_none());
+    }
+
+    #[test]
+    fn test_md045_forbid_filename() {
+        let lines = vec![
+            "![Screenshot.PNG](img/screenshot.png?v=2) ![screenshot](img/screenshot.png)\n",
+            "![diagram.svg][d]\n",
+            "\n",
+            "[d]: ./diagram.svg\n",
+        ];
+        let errors = lint_with(&lines, serde_json::json!({"forbid_filename": true}));
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Alt text is the image filename: \"Screenshot.PNG\"")
+        );
+        assert_eq!(errors[1].line_number, 2);
+    }
+
+    #[test]
+    fn test_md045_prohibited_texts() {
+        let lines = vec!["![ Image ](a.png) ![Picture of a cat](b.png)\n"];
+        let errors = lint_with(
+            &lines,
+            serde_json::json!({"prohibited_texts": ["image", "picture", "screenshot"]}),
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Prohibited alt text: \"Image\"")
+        );
+    }
+
+    #[test]
+    fn test_md045_html_img_checked_when_md033_allows_it() {
+        let lines = vec![r#"<img src="logo.png" alt="image"> <img src="x.png" alt="">"#];
+        let options: HashMap<String, serde_json::Value> =
+            serde_json::from_value(serde_json::json!({"prohibited_texts": ["image"]})).unwrap();
+        let lint = |global: &str| {
+            let global: crate::Config = serde_json::from_str(global).unwrap();
+            let mut params = crate::types::RuleParams::test(&lines, &options);
+            params.global_config = Some(&global);
+            MD045.lint(&params).len()
+        };
+
+        // MD033 enabled without `img` allowed reports the tag itself
+        assert_eq!(lint(r#"{}"#), 0);
+        assert_eq!(lint(r#"{"MD033": {"allowed_elements": ["IMG"]}}"#), 1);
+        assert_eq!(lint(r#"{"MD033": false}"#), 1);
+    }
 }
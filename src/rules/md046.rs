@@ -107,6 +107,8 @@ impl Rule for MD046 {
                     suggestion: Some(format!("Use {} code block style", expected_label)),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
 
                 // Emit helper delete-line errors for remaining lines of the block.
@@ -129,6 +131,8 @@ impl Rule for MD046 {
                         suggestion: None,
                         severity: Severity::Error,
                         fix_only: true,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
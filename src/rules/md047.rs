@@ -17,10 +17,18 @@ impl Rule for MD047 {
         &["blank_lines", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        -1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::None
     }
 
+    fn lints_front_matter(&self) -> bool {
+        true
+    }
+
     fn information(&self) -> Option<&'static str> {
         Some("https://github.com/DavidAnson/markdownlint/blob/main/doc/md047.md")
     }
@@ -32,34 +40,153 @@ impl Rule for MD047 {
             return errors;
         }
 
-        let last_line = &params.lines[params.lines.len() - 1];
+        // Without `newlines`, only a missing final newline is reported;
+        // trailing blank lines are left to MD012. Files matching
+        // `no_final_newline` must end without one.
+        let configured = if no_final_newline(params) {
+            Some(0)
+        } else {
+            params
+                .config
+                .get("newlines")
+                .and_then(|v| v.as_u64())
+                .map(|n| n as usize)
+        };
+        let expected = configured.unwrap_or(1);
+        let (actual, blank_lines) = trailing_newlines(params.lines);
+        let last = params.lines.len();
 
-        // Check if file ends with newline
-        if !last_line.ends_with('\n') && !last_line.ends_with("\r\n") {
-            errors.push(LintError {
-                line_number: params.lines.len(),
-                rule_names: self.names(),
-                rule_description: self.description(),
-                error_detail: None,
-                error_context: None,
-                rule_information: self.information(),
-                error_range: None,
-                fix_info: Some(FixInfo {
-                    line_number: Some(params.lines.len()),
-                    edit_column: Some(last_line.len() + 1),
+        if actual < expected {
+            // Append the missing newlines after the last line's text
+            let text = params.lines[last - 1].trim_end_matches(['\n', '\r']);
+            errors.push(self.error(
+                last,
+                configured.map(|_| (expected, actual)),
+                FixInfo {
+                    line_number: Some(last),
+                    edit_column: Some(text.len() + 1),
                     delete_count: None,
-                    insert_text: Some("\n".to_string()),
-                }),
-                suggestion: Some("Files should end with a single newline character".to_string()),
-                severity: Severity::Error,
-                fix_only: false,
-            });
+                    insert_text: Some("\n".repeat(expected - actual)),
+                },
+            ));
+        } else if configured.is_some() && actual > expected {
+            // Delete surplus blank lines from the end; with `newlines: 0`
+            // the last text line's ending goes too
+            let surplus_lines = (actual - expected).min(blank_lines);
+            let first_deleted = last - surplus_lines + 1;
+            let mut fixes: Vec<(usize, FixInfo)> = (first_deleted..=last)
+                .map(|line_number| {
+                    let fix = FixInfo {
+                        line_number: Some(line_number),
+                        edit_column: Some(1),
+                        delete_count: Some(-1),
+                        insert_text: None,
+                    };
+                    (line_number, fix)
+                })
+                .collect();
+            if expected == 0 && blank_lines < last {
+                let text_line = last - blank_lines;
+                let line = params.lines[text_line - 1];
+                let text = line.trim_end_matches(['\n', '\r']);
+                let fix = FixInfo {
+                    line_number: Some(text_line),
+                    edit_column: Some(text.len() + 1),
+                    delete_count: Some((line.len() - text.len()) as i32),
+                    insert_text: None,
+                };
+                fixes.insert(0, (text_line, fix));
+            }
+
+            let mut fixes = fixes.into_iter();
+            if let Some((line_number, fix)) = fixes.next() {
+                errors.push(self.error(line_number, Some((expected, actual)), fix));
+            }
+            errors.extend(fixes.map(|(line_number, fix)| LintError {
+                fix_only: true,
+                error_detail: None,
+                suggestion: None,
+                ..self.error(line_number, None, fix)
+            }));
         }
 
         errors
     }
 }
 
+impl MD047 {
+    fn error(
+        &self,
+        line_number: usize,
+        counts: Option<(usize, usize)>,
+        fix_info: FixInfo,
+    ) -> LintError {
+        LintError {
+            line_number,
+            rule_names: self.names(),
+            // The rule's description holds for the default single newline
+            rule_description: match counts {
+                Some((0, _)) => "Files should end without a newline character",
+                Some((expected, _)) if expected > 1 => {
+                    "Files should end with the configured number of newline characters"
+                }
+                _ => self.description(),
+            },
+            error_detail: counts
+                .map(|(expected, actual)| format!("Expected: {}; Actual: {}", expected, actual)),
+            error_context: None,
+            rule_information: self.information(),
+            error_range: None,
+            fix_info: Some(fix_info),
+            suggestion: Some(match counts {
+                Some((0, _)) => "Remove the newline(s) at the end of the file".to_string(),
+                Some((expected, _)) => format!("End the file with exactly {} newline(s)", expected),
+                None => "Files should end with a single newline character".to_string(),
+            }),
+            severity: Severity::Error,
+            fix_only: false,
+            line_text: None,
+            related: None,
+        }
+    }
+}
+
+/// Whether the file matches a `no_final_newline` glob; invalid patterns
+/// are ignored
+fn no_final_newline(params: &RuleParams) -> bool {
+    let Some(patterns) = params
+        .config
+        .get("no_final_newline")
+        .and_then(|v| v.as_array())
+    else {
+        return false;
+    };
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in patterns.iter().filter_map(|p| p.as_str()) {
+        if let Ok(glob) = globset::Glob::new(pattern) {
+            builder.add(glob);
+        }
+    }
+    builder
+        .build()
+        .is_ok_and(|globs| crate::lint::file_globs_match(&globs, params.name))
+}
+
+/// Line endings at the end of the document, counting `\r\n` as one, and
+/// how many of the last lines are empty
+fn trailing_newlines(lines: &[&str]) -> (usize, usize) {
+    let blank_lines = lines
+        .iter()
+        .rev()
+        .take_while(|line| **line == "\n" || **line == "\r\n")
+        .count();
+    let text_ends = lines
+        .len()
+        .checked_sub(blank_lines + 1)
+        .is_some_and(|idx| lines[idx].ends_with('\n'));
+    (blank_lines + usize::from(text_ends), blank_lines)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,4 +256,154 @@ mod tests {
         let params = crate::types::RuleParams::test(&lines, &config);
         assert_eq!(MD047.lint(&params).len(), 0);
     }
+
+    fn newlines_config(newlines: u64) -> HashMap<String, serde_json::Value> {
+        HashMap::from([("newlines".to_string(), serde_json::json!(newlines))])
+    }
+
+    #[test]
+    fn test_md047_trailing_blank_lines_unconfigured() {
+        let lines = vec!["Content\n", "\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert_eq!(MD047.lint(&params).len(), 0);
+    }
+
+    #[test]
+    fn test_md047_newlines_counts() {
+        for (content, newlines, expected_errors) in [
+            ("Content\n", 1, 0),
+            ("Content\n\n", 1, 1),
+            ("Content\n\n", 2, 0),
+            ("Content\n", 2, 1),
+            ("Content", 0, 0),
+            ("Content\n", 0, 1),
+            ("Content\r\n\r\n", 2, 0),
+            ("Content\r\n\r\n", 1, 1),
+        ] {
+            let lines: Vec<&str> = content.split_inclusive('\n').collect();
+            let config = newlines_config(newlines);
+            let params = crate::types::RuleParams::test(&lines, &config);
+            let errors: Vec<_> = MD047
+                .lint(&params)
+                .into_iter()
+                .filter(|e| !e.fix_only)
+                .collect();
+            assert_eq!(
+                errors.len(),
+                expected_errors,
+                "{content:?} newlines={newlines}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_md047_newlines_detail() {
+        let lines = vec!["Content\n", "\n", "\n"];
+        let config = newlines_config(1);
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD047.lint(&params);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].line_number, 2);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Expected: 1; Actual: 3")
+        );
+        assert!(errors[1].fix_only);
+        assert_eq!(errors[0].rule_description, MD047.description());
+    }
+
+    #[test]
+    fn test_md047_description_follows_newlines() {
+        for (lines, newlines, description) in [
+            (
+                vec!["Content\n"],
+                0,
+                "Files should end without a newline character",
+            ),
+            (
+                vec!["Content\n"],
+                2,
+                "Files should end with the configured number of newline characters",
+            ),
+        ] {
+            let config = newlines_config(newlines);
+            let params = crate::types::RuleParams::test(&lines, &config);
+            let errors = MD047.lint(&params);
+            assert_eq!(errors[0].rule_description, description);
+        }
+    }
+
+    #[test]
+    fn test_md047_newlines_fix() {
+        for (content, newlines, fixed) in [
+            ("Content", 2, "Content\n\n"),
+            ("Content\n", 3, "Content\n\n\n"),
+            ("Content\n\n\n", 1, "Content\n"),
+            ("Content\n\n", 0, "Content"),
+            ("Content\r\n", 2, "Content\r\n\r\n"),
+            ("Content\r\n\r\n\r\n", 0, "Content"),
+        ] {
+            let lines: Vec<&str> = content.split_inclusive('\n').collect();
+            let config = newlines_config(newlines);
+            let params = crate::types::RuleParams::test(&lines, &config);
+            let errors = MD047.lint(&params);
+            assert_eq!(
+                crate::apply_fixes(content, &errors),
+                fixed,
+                "{content:?} newlines={newlines}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_md047_no_final_newline_files() {
+        let config: crate::Config = serde_json::from_str(
+            r#"{"default": false, "MD047": {"no_final_newline": ["snippets/**"]}}"#,
+        )
+        .unwrap();
+        let options = crate::LintOptions::new()
+            .with_string("snippets/greeting.md", "Hello\n")
+            .with_string("README.md", "# Readme\n")
+            .with_config(config);
+        let results = crate::lint_sync(&options).unwrap();
+
+        let errors = results.get("snippets/greeting.md").unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Expected: 0; Actual: 1")
+        );
+        assert_eq!(crate::apply_fixes("Hello\n", errors), "Hello");
+        assert!(results.get("README.md").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_md047_newlines_converges_with_md012() {
+        for newlines in 0..=3 {
+            let config: crate::Config = serde_json::from_value(serde_json::json!({
+                "MD047": { "newlines": newlines }
+            }))
+            .unwrap();
+            for content in [
+                "# Title\n",
+                "# Title",
+                "# Title\n\n\n\n\n",
+                "# Title\r\n\r\n\r\n\r\n",
+            ] {
+                let fixed = crate::fix_content(content, &config).unwrap();
+                let ending = if content.contains('\r') { "\r\n" } else { "\n" };
+                assert_eq!(
+                    fixed,
+                    format!("# Title{}", ending.repeat(newlines)),
+                    "{content:?} newlines={newlines}"
+                );
+                let errors = crate::lint::lint_string(&fixed, &config).unwrap();
+                assert!(
+                    errors.is_empty(),
+                    "{content:?} newlines={newlines}: {errors:?}"
+                );
+            }
+        }
+    }
 }
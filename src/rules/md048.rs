@@ -1,5 +1,6 @@
 //! MD048 - Code fence style
 
+use crate::helpers::{CodeFenceTracker, fence_line};
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
 pub struct MD048;
@@ -26,73 +27,62 @@ impl Rule for MD048 {
     }
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        let blocks = find_fenced_blocks(params.lines);
+        let Some(first) = blocks.first() else {
+            return Vec::new();
+        };
+
+        let expected = match params.config.get("style").and_then(|v| v.as_str()) {
+            Some("backtick") => '`',
+            Some("tilde") => '~',
+            _ => first.marker, // "consistent" — determined by the first fence
+        };
+        let expected_label = fence_label(expected);
+
         let mut errors = Vec::new();
-        let mut backtick_lines = Vec::new();
-        let mut tilde_lines = Vec::new();
-        let mut first_style_line = 0;
-        let mut first_style = "";
-
-        for (idx, line) in params.lines.iter().enumerate() {
-            let line_number = idx + 1;
-            let trimmed = line.trim();
-
-            if trimmed.starts_with("```") {
-                if first_style_line == 0 {
-                    first_style_line = line_number;
-                    first_style = "```";
-                }
-                backtick_lines.push(line_number);
-            } else if trimmed.starts_with("~~~") {
-                if first_style_line == 0 {
-                    first_style_line = line_number;
-                    first_style = "~~~";
-                }
-                tilde_lines.push(line_number);
-            }
-        }
+        for block in blocks.iter().filter(|b| b.marker != expected) {
+            // Lengthen the new fence if the body holds a run that would
+            // otherwise close it early
+            let body_end = block.close.unwrap_or(params.lines.len());
+            let length = params.lines[block.open + 1..body_end]
+                .iter()
+                .filter_map(|l| fence_line(l))
+                .filter(|f| f.marker == expected)
+                .map(|f| f.length + 1)
+                .fold(block.length, usize::max);
+            let replacement = expected.to_string().repeat(length);
 
-        // If both styles are used, convert all to the first style encountered
-        if !backtick_lines.is_empty() && !tilde_lines.is_empty() {
-            // Report errors for each line that needs to be converted
-            let lines_to_fix = if first_style == "```" {
-                &tilde_lines
-            } else {
-                &backtick_lines
-            };
-
-            for &line_num in lines_to_fix {
-                let line_idx = line_num - 1;
-                let line = &params.lines[line_idx];
-                let trimmed = line.trim();
-
-                // Determine what to replace
-                let (old_fence, new_fence) = if trimmed.starts_with("~~~") {
-                    ("~~~", "```")
-                } else {
-                    ("```", "~~~")
+            for idx in std::iter::once(block.open).chain(block.close) {
+                let line = params.lines[idx];
+                let Some(fence) = fence_line(line) else {
+                    continue;
                 };
-
-                // Find the fence prefix (could be `~~~rust` or `~~~`)
-                let fence_len = old_fence.len();
-                let leading_spaces = line.len() - line.trim_start().len();
+                // Backtick fences can't carry an info string with backticks
+                let fixable = !(expected == '`' && fence.info.contains('`'));
 
                 errors.push(LintError {
-                    line_number: line_num,
+                    line_number: idx + 1,
                     rule_names: self.names(),
                     rule_description: self.description(),
-                    error_detail: Some(format!("Expected: {}; Actual: {}", first_style, old_fence)),
-                    error_context: Some(trimmed.to_string()),
+                    error_detail: Some(format!(
+                        "Expected: {}; Actual: {}",
+                        expected_label,
+                        fence_label(block.marker)
+                    )),
+                    error_context: Some(line.trim().to_string()),
                     rule_information: self.information(),
-                    error_range: Some((leading_spaces + 1, fence_len)),
-                    fix_info: Some(FixInfo {
-                        line_number: Some(line_num),
-                        edit_column: Some(leading_spaces + 1),
-                        delete_count: Some(fence_len as i32),
-                        insert_text: Some(new_fence.to_string()),
+                    error_range: Some((fence.indent + 1, fence.length)),
+                    fix_info: fixable.then(|| FixInfo {
+                        line_number: Some(idx + 1),
+                        edit_column: Some(fence.indent + 1),
+                        delete_count: Some(fence.length as i32),
+                        insert_text: Some(replacement.clone()),
                     }),
                     suggestion: Some("Use consistent code fence style".to_string()),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
@@ -101,6 +91,52 @@ impl Rule for MD048 {
     }
 }
 
+/// A fenced code block found by line scanning
+struct FencedBlock {
+    /// Fence character, `` ` `` or `~`
+    marker: char,
+    /// Length of the opening fence run
+    length: usize,
+    /// 0-based index of the opening fence line
+    open: usize,
+    /// 0-based index of the closing fence line, if the block is closed
+    close: Option<usize>,
+}
+
+/// Find fenced code blocks, matching each opening fence with its closing fence
+fn find_fenced_blocks(lines: &[&str]) -> Vec<FencedBlock> {
+    let mut blocks: Vec<FencedBlock> = Vec::new();
+    let mut fences = CodeFenceTracker::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        let was_in_block = fences.in_code_block();
+        if !fences.update(line) {
+            continue;
+        }
+        if !was_in_block {
+            if let Some(fence) = fence_line(line) {
+                blocks.push(FencedBlock {
+                    marker: fence.marker,
+                    length: fence.length,
+                    open: idx,
+                    close: None,
+                });
+            }
+        } else if !fences.in_code_block()
+            && let Some(block) = blocks.last_mut()
+        {
+            block.close = Some(idx);
+        }
+    }
+
+    blocks
+}
+
+/// Short form of a fence character used in error details
+fn fence_label(marker: char) -> &'static str {
+    if marker == '`' { "```" } else { "~~~" }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +234,44 @@ mod tests {
         assert_eq!(fix2.delete_count, Some(3));
         assert_eq!(fix2.insert_text, Some("```".to_string()));
     }
+
+    #[test]
+    fn test_md048_style_backtick_with_only_tildes() {
+        let lines: Vec<&str> = vec!["~~~\n", "code\n", "~~~\n"];
+        let mut config = HashMap::new();
+        config.insert("style".to_string(), serde_json::json!("backtick"));
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD048.lint(&params);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Expected: ```; Actual: ~~~")
+        );
+    }
+
+    #[test]
+    fn test_md048_fix_replaces_whole_fence_and_lengthens() {
+        let lines: Vec<&str> = vec!["```\n", "x\n", "```\n", "~~~~md\n", "```\n", "~~~~\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD048.lint(&params);
+        // The ``` inside the tilde block is content, not a fence
+        assert_eq!(errors.len(), 2);
+        let fix = errors[0].fix_info.as_ref().unwrap();
+        assert_eq!(fix.delete_count, Some(4));
+        assert_eq!(fix.insert_text.as_deref(), Some("````"));
+        assert_eq!(errors[1].line_number, 6);
+    }
+
+    #[test]
+    fn test_md048_backtick_info_string_not_fixable() {
+        let lines: Vec<&str> = vec!["~~~ a`b\n", "code\n", "~~~\n"];
+        let mut config = HashMap::new();
+        config.insert("style".to_string(), serde_json::json!("backtick"));
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD048.lint(&params);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].fix_info.is_none());
+        assert!(errors[1].fix_info.is_some());
+    }
 }
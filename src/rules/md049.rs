@@ -1,101 +1,40 @@
 //! MD049 - Emphasis style should be consistent
+//!
+//! Only emphasis the parser recognizes is checked, so underscores inside
+//! words (`snake_case`) and math such as `$x_i$` are left alone.
 
+use crate::helpers::{EmphasisSpan, emphasis_spans};
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
 pub struct MD049;
 
-/// Represents a single emphasis match in a line
-#[derive(Debug)]
-struct EmphasisMatch {
-    /// The full matched text including markers (e.g., "*text*" or "_text_")
-    full_match: String,
-    /// The style of emphasis: "asterisk" or "underscore"
-    style: String,
-    /// 0-based start position in the line
-    start: usize,
-}
-
-/// Find all single-emphasis patterns in a line.
-/// Matches *text* and _text_ but NOT **text** or __text__.
-fn find_emphasis_matches(line: &str) -> Vec<EmphasisMatch> {
-    let mut matches = Vec::new();
-    let bytes = line.as_bytes();
-    let len = bytes.len();
-
-    let mut i = 0;
-    while i < len {
-        let ch = bytes[i];
-
-        if ch == b'*' || ch == b'_' {
-            // Skip if this is a doubled marker (strong emphasis)
-            if i + 1 < len && bytes[i + 1] == ch {
-                // This is ** or __, skip the strong emphasis block entirely
-                // Find the closing ** or __
-                let marker = ch;
-                let mut j = i + 2;
-                while j + 1 < len {
-                    if bytes[j] == marker && bytes[j + 1] == marker {
-                        // Check it's not tripled (or more) at the start
-                        j += 2;
-                        break;
-                    }
-                    j += 1;
-                }
-                i = j;
-                continue;
-            }
-
-            // Single marker -- look for closing single marker
-            let marker = ch;
-            let start = i;
-            let mut j = i + 1;
-
-            // Content must be non-empty and not start with a space
-            if j >= len || bytes[j] == b' ' || bytes[j] == b'\n' || bytes[j] == marker {
-                i += 1;
-                continue;
-            }
-
-            // Find closing single marker (not doubled)
-            let mut found_close = false;
-            while j < len {
-                if bytes[j] == marker {
-                    // Check it's not preceded or followed by the same marker (doubled)
-                    let preceded_by_marker = j > 0 && bytes[j - 1] == marker;
-                    let followed_by_marker = j + 1 < len && bytes[j + 1] == marker;
-
-                    if !preceded_by_marker && !followed_by_marker {
-                        // Found a valid closing marker
-                        let full = &line[start..=j];
-                        matches.push(EmphasisMatch {
-                            full_match: full.to_string(),
-                            style: if marker == b'*' {
-                                "asterisk".to_string()
-                            } else {
-                                "underscore".to_string()
-                            },
-                            start,
-                        });
-                        i = j + 1;
-                        found_close = true;
-                        break;
-                    }
-                }
-                if bytes[j] == b'\n' {
-                    break;
-                }
-                j += 1;
-            }
-
-            if !found_close {
-                i += 1;
-            }
-        } else {
-            i += 1;
+/// Length of the emphasis marker on each side of the span
+const MARKER_LEN: usize = 1;
+
+impl MD049 {
+    /// Replace the marker at `line_number`/`column` with the preferred one
+    fn marker_fix(line_number: usize, column: usize, preferred: &str) -> FixInfo {
+        let marker = if preferred == "asterisk" { "*" } else { "_" };
+        FixInfo {
+            line_number: Some(line_number),
+            edit_column: Some(column),
+            delete_count: Some(MARKER_LEN as i32),
+            insert_text: Some(marker.repeat(MARKER_LEN)),
         }
     }
 
-    matches
+    /// The span's source text, or the rest of its first line when it wraps
+    fn span_text(params: &RuleParams, span: &EmphasisSpan) -> String {
+        let line = params.lines[span.start_line - 1].trim_end_matches(['\n', '\r']);
+        let end = if span.end_line == span.start_line {
+            span.end_column
+        } else {
+            line.len()
+        };
+        line.get(span.start_column - 1..end)
+            .unwrap_or_default()
+            .to_string()
+    }
 }
 
 impl Rule for MD049 {
@@ -112,7 +51,7 @@ impl Rule for MD049 {
     }
 
     fn parser_type(&self) -> ParserType {
-        ParserType::None
+        ParserType::Micromark
     }
 
     fn information(&self) -> Option<&'static str> {
@@ -127,66 +66,68 @@ impl Rule for MD049 {
             .config
             .get("style")
             .and_then(|v| v.as_str())
-            .unwrap_or("consistent")
-            .to_string();
+            .unwrap_or("consistent");
 
-        // First pass: collect all emphasis occurrences to determine preferred style
-        let mut all_matches: Vec<(usize, EmphasisMatch)> = Vec::new(); // (line_number, match)
-
-        for (idx, line) in params.lines.iter().enumerate() {
-            let line_number = idx + 1;
-            for em in find_emphasis_matches(line) {
-                all_matches.push((line_number, em));
-            }
-        }
-
-        if all_matches.is_empty() {
+        let spans = emphasis_spans(params.lines, params.tokens, "emphasis");
+        let Some(first) = spans.first() else {
             return errors;
-        }
+        };
 
-        // Determine the preferred style
+        // "consistent" follows the first occurrence
         let preferred_style = if configured_style == "consistent" {
-            // Use the style of the first occurrence
-            all_matches[0].1.style.clone()
+            first.style()
         } else {
-            configured_style.clone()
+            configured_style
         };
 
-        // Second pass: report errors for wrong-style emphasis with fix_info
-        for (line_number, em) in &all_matches {
-            if em.style != preferred_style {
-                let corrected = if preferred_style == "asterisk" {
-                    // Replace _text_ with *text*
-                    let inner = &em.full_match[1..em.full_match.len() - 1];
-                    format!("*{}*", inner)
-                } else {
-                    // Replace *text* with _text_
-                    let inner = &em.full_match[1..em.full_match.len() - 1];
-                    format!("_{}_", inner)
-                };
-
-                errors.push(LintError {
-                    line_number: *line_number,
-                    rule_names: self.names(),
-                    rule_description: self.description(),
-                    error_detail: Some(format!(
-                        "Expected: {}; Actual: {}",
-                        preferred_style, em.style
-                    )),
-                    error_context: Some(em.full_match.clone()),
-                    rule_information: self.information(),
-                    error_range: Some((em.start + 1, em.full_match.len())),
-                    fix_info: Some(FixInfo {
-                        line_number: None,
-                        edit_column: Some(em.start + 1), // 1-based
-                        delete_count: Some(em.full_match.len() as i32),
-                        insert_text: Some(corrected),
-                    }),
-                    suggestion: Some("Use consistent emphasis style".to_string()),
-                    severity: Severity::Error,
-                    fix_only: false,
-                });
+        for span in &spans {
+            if span.style() == preferred_style {
+                continue;
+            }
+            // Underscores don't emphasize inside a word, so `foo*bar*baz` stays
+            if preferred_style == "underscore" && span.intraword {
+                continue;
             }
+
+            let text = Self::span_text(params, span);
+            errors.push(LintError {
+                line_number: span.start_line,
+                rule_names: self.names(),
+                rule_description: self.description(),
+                error_detail: Some(format!(
+                    "Expected: {}; Actual: {}",
+                    preferred_style,
+                    span.style()
+                )),
+                error_range: Some((span.start_column, text.len())),
+                error_context: Some(text),
+                rule_information: self.information(),
+                fix_info: Some(Self::marker_fix(
+                    span.start_line,
+                    span.start_column,
+                    preferred_style,
+                )),
+                suggestion: Some("Use consistent emphasis style".to_string()),
+                severity: Severity::Error,
+                fix_only: false,
+                line_text: None,
+                related: None,
+            });
+
+            // The closing marker is a separate edit, which may be on a later line
+            errors.push(LintError {
+                line_number: span.end_line,
+                rule_names: self.names(),
+                rule_description: self.description(),
+                rule_information: self.information(),
+                fix_info: Some(Self::marker_fix(
+                    span.end_line,
+                    span.end_column + 1 - MARKER_LEN,
+                    preferred_style,
+                )),
+                fix_only: true,
+                ..Default::default()
+            });
         }
 
         errors
@@ -198,37 +139,32 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    /// Reported errors and the content after applying every fix
+    fn lint(content: &str, config: serde_json::Value) -> (Vec<LintError>, String) {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let tokens = crate::parser::parse(content);
+        let config: HashMap<String, serde_json::Value> = serde_json::from_value(config).unwrap();
+        let errors = MD049.lint(&RuleParams::test_with_tokens(&lines, &tokens, &config));
+        let fixed = crate::lint::apply_fixes(content, &errors);
+        (errors.into_iter().filter(|e| !e.fix_only).collect(), fixed)
+    }
+
     #[test]
     fn test_md049_consistent_asterisks() {
-        let rule = MD049;
-        let lines: Vec<&str> = vec!["*one* and *two* and *three*\n"];
-        let tokens = vec![];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let errors = rule.lint(&params);
+        let (errors, _) = lint("*one* and *two* and *three*\n", serde_json::json!({}));
         assert_eq!(errors.len(), 0);
     }
 
     #[test]
     fn test_md049_consistent_underscores() {
-        let rule = MD049;
-        let lines: Vec<&str> = vec!["_one_ and _two_ and _three_\n"];
-        let tokens = vec![];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let errors = rule.lint(&params);
+        let (errors, _) = lint("_one_ and _two_ and _three_\n", serde_json::json!({}));
         assert_eq!(errors.len(), 0);
     }
 
     #[test]
     fn test_md049_mixed_styles_consistent_mode() {
-        let rule = MD049;
         // First emphasis is asterisk, so underscore ones should be flagged
-        let lines: Vec<&str> = vec!["*one* and _two_\n"];
-        let tokens = vec![];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let errors = rule.lint(&params);
+        let (errors, _) = lint("*one* and _two_\n", serde_json::json!({}));
         assert_eq!(errors.len(), 1);
         assert_eq!(
             errors[0].error_detail.as_deref(),
@@ -238,102 +174,103 @@ mod tests {
 
     #[test]
     fn test_md049_configured_asterisk_style() {
-        let rule = MD049;
-        let lines: Vec<&str> = vec!["_one_ and _two_\n"];
-        let tokens = vec![];
-        let mut config = HashMap::new();
-        config.insert("style".to_string(), serde_json::json!("asterisk"));
-        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let errors = rule.lint(&params);
+        let (errors, _) = lint(
+            "_one_ and _two_\n",
+            serde_json::json!({"style": "asterisk"}),
+        );
         assert_eq!(errors.len(), 2);
     }
 
     #[test]
     fn test_md049_configured_underscore_style() {
-        let rule = MD049;
-        let lines: Vec<&str> = vec!["*one* and *two*\n"];
-        let tokens = vec![];
-        let mut config = HashMap::new();
-        config.insert("style".to_string(), serde_json::json!("underscore"));
-        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let errors = rule.lint(&params);
+        let (errors, _) = lint(
+            "*one* and *two*\n",
+            serde_json::json!({"style": "underscore"}),
+        );
         assert_eq!(errors.len(), 2);
     }
 
     #[test]
     fn test_md049_fix_info_underscore_to_asterisk() {
-        let rule = MD049;
         // First is asterisk, so underscore should get fix_info to convert to asterisk
-        let lines: Vec<&str> = vec!["*one* and _two_\n"];
-        let tokens = vec![];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let errors = rule.lint(&params);
+        let (errors, fixed) = lint("*one* and _two_\n", serde_json::json!({}));
         assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error_context.as_deref(), Some("_two_"));
+        assert_eq!(errors[0].error_range, Some((11, 5)));
 
+        // The fix replaces the opening marker; the closing one is a fix-only edit
         let fix = errors[0].fix_info.as_ref().expect("should have fix_info");
-        assert_eq!(fix.line_number, None);
-        // "_two_" starts at index 10, 1-based = 11
         assert_eq!(fix.edit_column, Some(11));
-        assert_eq!(fix.delete_count, Some(5)); // "_two_".len() == 5
-        assert_eq!(fix.insert_text, Some("*two*".to_string()));
+        assert_eq!(fix.delete_count, Some(1));
+        assert_eq!(fix.insert_text, Some("*".to_string()));
+        assert_eq!(fixed, "*one* and *two*\n");
     }
 
     #[test]
     fn test_md049_fix_info_asterisk_to_underscore() {
-        let rule = MD049;
         // First is underscore, so asterisk should get fix_info to convert to underscore
-        let lines: Vec<&str> = vec!["_one_ and *two*\n"];
-        let tokens = vec![];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let errors = rule.lint(&params);
+        let (errors, fixed) = lint("_one_ and *two*\n", serde_json::json!({}));
         assert_eq!(errors.len(), 1);
-
-        let fix = errors[0].fix_info.as_ref().expect("should have fix_info");
-        // "*two*" starts at index 10, 1-based = 11
-        assert_eq!(fix.edit_column, Some(11));
-        assert_eq!(fix.delete_count, Some(5));
-        assert_eq!(fix.insert_text, Some("_two_".to_string()));
+        assert_eq!(fixed, "_one_ and _two_\n");
     }
 
     #[test]
     fn test_md049_fix_info_multiple_errors() {
-        let rule = MD049;
-        let lines: Vec<&str> = vec!["*ok* and _bad1_ and _bad2_\n"];
-        let tokens = vec![];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let errors = rule.lint(&params);
+        let (errors, fixed) = lint("*ok* and _bad1_ and _bad2_\n", serde_json::json!({}));
         assert_eq!(errors.len(), 2);
-
-        let fix0 = errors[0].fix_info.as_ref().expect("first fix_info");
-        assert_eq!(fix0.insert_text, Some("*bad1*".to_string()));
-
-        let fix1 = errors[1].fix_info.as_ref().expect("second fix_info");
-        assert_eq!(fix1.insert_text, Some("*bad2*".to_string()));
+        assert_eq!(fixed, "*ok* and *bad1* and *bad2*\n");
     }
 
     #[test]
     fn test_md049_no_emphasis() {
-        let rule = MD049;
-        let lines: Vec<&str> = vec!["Just plain text.\n"];
-        let tokens = vec![];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let errors = rule.lint(&params);
+        let (errors, _) = lint("Just plain text.\n", serde_json::json!({}));
         assert_eq!(errors.len(), 0);
     }
 
     #[test]
     fn test_md049_does_not_match_strong() {
-        let rule = MD049;
         // **bold** should NOT be treated as emphasis
-        let lines: Vec<&str> = vec!["**bold** and __also bold__\n"];
-        let tokens = vec![];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test_with_tokens(&lines, &tokens, &config);
-        let errors = rule.lint(&params);
+        let (errors, _) = lint("**bold** and __also bold__\n", serde_json::json!({}));
         assert_eq!(errors.len(), 0);
     }
+
+    #[test]
+    fn test_md049_ignores_intraword_underscores_and_math() {
+        let content = "Set my_var_name to $x_i$ for _emphasis_.\n";
+        let (errors, fixed) = lint(content, serde_json::json!({"style": "asterisk"}));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error_context.as_deref(), Some("_emphasis_"));
+        assert_eq!(fixed, "Set my_var_name to $x_i$ for *emphasis*.\n");
+    }
+
+    #[test]
+    fn test_md049_ignores_code_spans() {
+        let (errors, _) = lint("Call `_private_` or *this*\n", serde_json::json!({}));
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_md049_intraword_asterisk_kept_for_underscore_style() {
+        // `foo_bar_baz` would not be emphasis, so there is nothing to convert to
+        let content = "foo*bar*baz and *word*\n";
+        let (errors, fixed) = lint(content, serde_json::json!({"style": "underscore"}));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(fixed, "foo*bar*baz and _word_\n");
+    }
+
+    #[test]
+    fn test_md049_fix_spans_lines() {
+        let content = "A _wrapped\nphrase_ here\n";
+        let (errors, fixed) = lint(content, serde_json::json!({"style": "asterisk"}));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 1);
+        assert_eq!(fixed, "A *wrapped\nphrase* here\n");
+    }
+
+    #[test]
+    fn test_md049_nested_in_strong() {
+        let (errors, fixed) = lint("___both___\n", serde_json::json!({"style": "asterisk"}));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(fixed, "*__both__*\n");
+    }
 }
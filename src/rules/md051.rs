@@ -12,6 +12,41 @@ static FRAGMENT_LINK_RE: LazyLock<Regex> =
 static CROSS_FILE_LINK_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\(([^#)]+)#([^)]+)\)").expect("valid regex"));
 
+/// `fragment` with `%XX` escapes decoded, or `None` when it has none or
+/// they do not decode to UTF-8
+fn percent_decode(fragment: &str) -> Option<String> {
+    if !fragment.contains('%') {
+        return None;
+    }
+    let bytes = fragment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| fragment.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Whether `fragment`, as written or percent-decoded, is one of `ids`
+fn fragment_matches(ids: &[String], fragment: &str) -> bool {
+    let decoded = percent_decode(fragment);
+    ids.iter()
+        .any(|id| id == fragment || decoded.as_deref() == Some(id.as_str()))
+}
+
 pub struct MD051;
 
 impl Rule for MD051 {
@@ -39,100 +74,59 @@ impl Rule for MD051 {
         let mut errors = Vec::new();
 
         // Collect all valid heading IDs for same-file validation
-        let heading_ids = crate::helpers::collect_heading_ids(params.lines);
+        let heading_ids = params.heading_ids();
+        let code_block_lines = params.code_block_lines();
 
         // Find all fragment links and check them
-        let mut in_code_block = false;
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
-            let trimmed = line.trim();
 
-            if crate::helpers::is_code_fence(trimmed) {
-                in_code_block = !in_code_block;
-                continue;
-            }
-            if in_code_block {
+            if code_block_lines[idx] {
                 continue;
             }
 
             // Same-file fragment links: [text](#fragment)
             for cap in FRAGMENT_LINK_RE.captures_iter(line) {
                 let fragment = &cap[2];
-                if !heading_ids.contains(&fragment.to_string()) {
-                    errors.push(LintError {
-                        line_number,
-                        rule_names: self.names(),
-                        rule_description: self.description(),
-                        error_detail: Some(format!(
-                            "No matching heading for fragment: #{}",
-                            fragment
-                        )),
-                        error_context: Some(cap[0].to_string()),
-                        rule_information: self.information(),
-                        error_range: None,
-                        fix_info: None,
-                        suggestion: Some(
-                            "Ensure link fragments point to valid headings".to_string(),
-                        ),
-                        severity: Severity::Error,
-                        fix_only: false,
-                    });
+                if !fragment_matches(&heading_ids, fragment) {
+                    errors.push(self.missing_heading(line_number, fragment, &cap[0], &heading_ids));
                 }
             }
 
             // Cross-file fragment links: [text](file.md#fragment)
-            if let Some(workspace_headings) = params.workspace_headings {
-                for cap in CROSS_FILE_LINK_RE.captures_iter(line) {
-                    let file_ref = &cap[2];
-                    let fragment = &cap[3];
-
-                    // Skip external URLs
-                    if file_ref.starts_with("http://") || file_ref.starts_with("https://") {
-                        continue;
-                    }
+            for cap in CROSS_FILE_LINK_RE.captures_iter(line) {
+                errors.extend(self.check_cross_file(
+                    params,
+                    line_number,
+                    &cap[2],
+                    &cap[3],
+                    &cap[0],
+                ));
+            }
+        }
 
-                    // Resolve relative path from current file's directory
-                    let current_dir = std::path::Path::new(params.name)
-                        .parent()
-                        .unwrap_or(std::path::Path::new(""));
-                    let resolved = current_dir.join(file_ref);
-
-                    // Try to find the target file in the workspace heading index
-                    let resolved_str = resolved.to_string_lossy();
-                    let target_headings =
-                        workspace_headings.get(resolved_str.as_ref()).or_else(|| {
-                            // Try canonical path for ../relative resolution
-                            resolved.canonicalize().ok().and_then(|p| {
-                                workspace_headings.get(&p.to_string_lossy().into_owned())
-                            })
-                        });
-
-                    if let Some(headings) = target_headings
-                        && !headings.contains(&fragment.to_string())
-                    {
-                        errors.push(LintError {
-                            line_number,
-                            rule_names: self.names(),
-                            rule_description: self.description(),
-                            error_detail: Some(format!(
-                                "No matching heading '{}' in '{}'",
-                                fragment, file_ref
-                            )),
-                            error_context: Some(cap[0].to_string()),
-                            rule_information: self.information(),
-                            error_range: None,
-                            fix_info: None,
-                            suggestion: Some(format!(
-                                "Check that '{}' contains a heading that produces anchor '#{}'",
-                                file_ref, fragment
-                            )),
-                            severity: Severity::Error,
-                            fix_only: false,
-                        });
+        // Reference definitions carry fragments too: [label]: #fragment
+        for definition in params.reference_definitions().iter() {
+            let context = format!("[{}]: {}", definition.label, definition.destination);
+            match definition.destination.split_once('#') {
+                None | Some((_, "")) => {}
+                Some(("", fragment)) => {
+                    if !fragment_matches(&heading_ids, fragment) {
+                        errors.push(self.missing_heading(
+                            definition.line_number,
+                            fragment,
+                            &context,
+                            &heading_ids,
+                        ));
                     }
-                    // If the target file isn't in workspace_headings, skip silently
-                    // (file might not be a .md file or not in workspace)
                 }
+                Some((file_ref, fragment)) => errors.extend(self.check_cross_file(
+                    params,
+                    definition.line_number,
+                    file_ref,
+                    fragment,
+                    &context,
+                )),
             }
         }
 
@@ -140,6 +134,105 @@ impl Rule for MD051 {
     }
 }
 
+impl MD051 {
+    /// Error for a same-file fragment with no matching heading.
+    ///
+    /// A decoded fragment that is heading text rather than its anchor
+    /// (`#section%20one` for "Section one") gets the anchor as suggestion,
+    /// since browsers do not resolve it.
+    fn missing_heading(
+        &self,
+        line_number: usize,
+        fragment: &str,
+        context: &str,
+        heading_ids: &[String],
+    ) -> LintError {
+        let anchor = percent_decode(fragment)
+            .map(|decoded| crate::helpers::heading_to_anchor_id(&decoded))
+            .filter(|anchor| heading_ids.contains(anchor));
+        let suggestion = match anchor {
+            Some(anchor) => format!("Link to the heading's anchor: #{}", anchor),
+            None => "Ensure link fragments point to valid headings".to_string(),
+        };
+        LintError {
+            line_number,
+            rule_names: self.names(),
+            rule_description: self.description(),
+            error_detail: Some(format!("No matching heading for fragment: #{}", fragment)),
+            error_context: Some(context.to_string()),
+            rule_information: self.information(),
+            error_range: None,
+            fix_info: None,
+            suggestion: Some(suggestion),
+            severity: Severity::Error,
+            fix_only: false,
+            line_text: None,
+            related: None,
+        }
+    }
+
+    /// Check a `file.md#fragment` link against the workspace heading index.
+    ///
+    /// Skipped without workspace context, for external URLs, and for targets
+    /// not in the index (not Markdown, or outside the workspace).
+    fn check_cross_file(
+        &self,
+        params: &RuleParams,
+        line_number: usize,
+        file_ref: &str,
+        fragment: &str,
+        context: &str,
+    ) -> Option<LintError> {
+        let workspace_headings = params.workspace_headings?;
+
+        // Skip external URLs
+        if file_ref.starts_with("http://") || file_ref.starts_with("https://") {
+            return None;
+        }
+
+        // Resolve relative path from current file's directory
+        let current_dir = std::path::Path::new(params.name)
+            .parent()
+            .unwrap_or(std::path::Path::new(""));
+        let resolved = current_dir.join(file_ref);
+
+        // Try to find the target file in the workspace heading index
+        let resolved_str = resolved.to_string_lossy();
+        let headings = workspace_headings.get(resolved_str.as_ref()).or_else(|| {
+            // Try canonical path for ../relative resolution
+            resolved
+                .canonicalize()
+                .ok()
+                .and_then(|p| workspace_headings.get(&p.to_string_lossy().into_owned()))
+        })?;
+
+        if fragment_matches(headings, fragment) {
+            return None;
+        }
+        Some(LintError {
+            line_number,
+            rule_names: self.names(),
+            rule_description: self.description(),
+            error_detail: Some(format!(
+                "No matching heading '{}' in '{}'",
+                fragment, file_ref
+            )),
+            error_context: Some(context.to_string()),
+            rule_information: self.information(),
+            error_range: None,
+            fix_info: None,
+            suggestion: Some(format!(
+                "Check that '{}' contains a heading that produces anchor '#{}'",
+                file_ref, fragment
+            )),
+            severity: Severity::Error,
+            fix_only: false,
+            line_text: None,
+            related: None,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,6 +344,51 @@ mod tests {
         assert_eq!(errors.len(), 0, "Unicode heading IDs should match");
     }
 
+    #[test]
+    fn test_md051_percent_encoded_fragment() {
+        let rule = MD051;
+        let config = HashMap::new();
+        let lines = vec![
+            "# Caf\u{00e9} Guide\n",
+            "\n",
+            "## Section one\n",
+            "\n",
+            "[a](#caf%C3%A9-guide) [b](#section%2Done) [c](#100%-sure)\n",
+        ];
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = rule.lint(&params);
+        // `%-s` is not an escape, so the fragment is taken as written
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("No matching heading for fragment: #100%-sure")
+        );
+    }
+
+    #[test]
+    fn test_md051_percent_encoded_fragment_missing() {
+        let rule = MD051;
+        let config = HashMap::new();
+        let lines = vec![
+            "## Section one\n",
+            "\n",
+            "[a](#section%20one) [b](#section%20two)\n",
+        ];
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = rule.lint(&params);
+        // Decoded, these are heading text, not anchors: browsers resolve
+        // neither, but the first names an existing heading
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].suggestion.as_deref(),
+            Some("Link to the heading's anchor: #section-one")
+        );
+        assert_eq!(
+            errors[1].suggestion.as_deref(),
+            Some("Ensure link fragments point to valid headings")
+        );
+    }
+
     #[test]
     fn test_md051_cross_file_valid_fragment() {
         let rule = MD051;
@@ -261,13 +399,8 @@ mod tests {
         workspace.insert("other.md".to_string(), vec!["intro".to_string()]);
 
         let params = crate::types::RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &config,
             workspace_headings: Some(&workspace),
+            ..crate::types::RuleParams::test(&lines, &config)
         };
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 0);
@@ -283,13 +416,8 @@ mod tests {
         workspace.insert("other.md".to_string(), vec!["intro".to_string()]);
 
         let params = crate::types::RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &config,
             workspace_headings: Some(&workspace),
+            ..crate::types::RuleParams::test(&lines, &config)
         };
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 1);
@@ -318,13 +446,8 @@ mod tests {
         let workspace = HashMap::new(); // empty workspace
 
         let params = crate::types::RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &config,
             workspace_headings: Some(&workspace),
+            ..crate::types::RuleParams::test(&lines, &config)
         };
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 0, "Unknown files should be skipped silently");
@@ -339,13 +462,8 @@ mod tests {
         let workspace = HashMap::new();
 
         let params = crate::types::RuleParams {
-            name: "test.md",
-            version: "0.1.0",
-            lines: &lines,
-            front_matter_lines: &[],
-            tokens: &[],
-            config: &config,
             workspace_headings: Some(&workspace),
+            ..crate::types::RuleParams::test(&lines, &config)
         };
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 0, "URL links should be skipped");
@@ -366,4 +484,53 @@ mod tests {
             "Cross-file links should be skipped without workspace context"
         );
     }
+
+    #[test]
+    fn test_md051_reference_definition_fragment() {
+        let rule = MD051;
+        let config = HashMap::new();
+
+        let lines = vec![
+            "# Existing\n",
+            "\n",
+            "See [x][r].\n",
+            "\n",
+            "[r]: #existing\n",
+        ];
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(rule.lint(&params).is_empty());
+
+        let lines = vec![
+            "# Existing\n",
+            "\n",
+            "See [x][r].\n",
+            "\n",
+            "[r]: #missing\n",
+        ];
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = rule.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 5);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("No matching heading for fragment: #missing")
+        );
+        assert_eq!(errors[0].error_context.as_deref(), Some("[r]: #missing"));
+    }
+
+    #[test]
+    fn test_md051_reference_definition_cross_file_fragment() {
+        let rule = MD051;
+        let lines = vec!["# Local\n", "\n", "[r]: other.md#nonexistent\n"];
+        let config = HashMap::new();
+
+        let mut workspace = HashMap::new();
+        workspace.insert("other.md".to_string(), vec!["intro".to_string()]);
+        let mut params = crate::types::RuleParams::test(&lines, &config);
+        params.workspace_headings = Some(&workspace);
+
+        let errors = rule.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 3);
+    }
 }
@@ -1,21 +1,8 @@
 //! MD052 - Reference links and images should use a label that is defined
 
+use crate::helpers::ReferenceKind;
 use crate::types::{LintError, ParserType, Rule, RuleParams, Severity};
-use regex::Regex;
 use std::collections::HashSet;
-use std::sync::LazyLock;
-
-/// Regex for reference link definitions: `[label]: url`
-static DEF_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^\s*\[([^\]]+)\]:\s+").expect("valid regex"));
-
-/// Regex for full reference links: `[text][label]`
-static FULL_REF_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\[([^\]]+)\]").expect("valid regex"));
-
-/// Regex for collapsed reference links: `[label][]`
-static COLLAPSED_REF_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\[\]").expect("valid regex"));
 
 pub struct MD052;
 
@@ -42,133 +29,65 @@ impl Rule for MD052 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
-        let mut defined_labels: HashSet<String> = HashSet::new();
-
-        // Pass 1: Collect all reference definitions (skipping code blocks)
-        let mut in_code_block = false;
-        for line in params.lines.iter() {
-            if crate::helpers::is_code_fence(line.trim_start()) {
-                in_code_block = !in_code_block;
-                continue;
-            }
-            if in_code_block {
-                continue;
-            }
-
-            if let Some(caps) = DEF_RE.captures(line) {
-                let label = caps[1].to_lowercase();
-                defined_labels.insert(label);
-            }
+        if params.lines.is_empty() {
+            return errors;
         }
 
-        // Pass 2: Find all reference usages and check if they are defined
-        in_code_block = false;
-        for (idx, line) in params.lines.iter().enumerate() {
-            let line_number = idx + 1;
-
-            if crate::helpers::is_code_fence(line.trim_start()) {
-                in_code_block = !in_code_block;
-                continue;
-            }
-            if in_code_block {
+        // Collect all reference definitions (outside code blocks)
+        let defined_labels: HashSet<String> = params
+            .reference_definitions()
+            .iter()
+            .map(|definition| definition.label.to_lowercase())
+            .collect();
+
+        // Missing definitions are appended to the last non-empty line
+        // Note: apply_fixes pops trailing empty lines (lines that are just "\n" or "\r\n")
+        // so we need to target the line before it if it exists
+        let last_line_idx = params.lines.len().saturating_sub(1);
+        let is_trailing_empty = params
+            .lines
+            .get(last_line_idx)
+            .map(|l| *l == "\n" || *l == "\r\n")
+            .unwrap_or(false);
+        let insert_line = if is_trailing_empty {
+            last_line_idx.max(1) // Target line before trailing empty
+        } else {
+            params.lines.len() // Target the actual last line
+        };
+        let target_line = params.lines.get(insert_line - 1).copied().unwrap_or("");
+        let target_stripped = target_line.trim_end_matches('\n').trim_end_matches('\r');
+        let insert_col = target_stripped.len() + 1;
+
+        // Check full (`[text][label]`) and collapsed (`[label][]`) references;
+        // shortcut `[label]` references are not checked
+        for link in params
+            .reference_links()
+            .iter()
+            .filter(|link| link.kind != ReferenceKind::Shortcut)
+        {
+            if defined_labels.contains(&link.label.to_lowercase()) {
                 continue;
             }
-
-            // Check full reference links: [text][label]
-            for caps in FULL_REF_RE.captures_iter(line) {
-                let label = caps[2].to_lowercase();
-                if !defined_labels.contains(&label) {
-                    // Append to the last non-empty line
-                    // Note: apply_fixes pops trailing empty lines (lines that are just "\n" or "\r\n")
-                    // so we need to target the line before it if it exists
-                    let last_line_idx = params.lines.len().saturating_sub(1);
-                    let is_trailing_empty = params
-                        .lines
-                        .get(last_line_idx)
-                        .map(|l| *l == "\n" || *l == "\r\n")
-                        .unwrap_or(false);
-                    let insert_line = if is_trailing_empty {
-                        last_line_idx.max(1) // Target line before trailing empty
-                    } else {
-                        params.lines.len() // Target the actual last line
-                    };
-                    let target_line = params.lines.get(insert_line - 1).copied().unwrap_or("");
-                    let target_stripped = target_line.trim_end_matches('\n').trim_end_matches('\r');
-                    let insert_col = target_stripped.len() + 1;
-
-                    errors.push(LintError {
-                        line_number,
-                        rule_names: self.names(),
-                        rule_description: self.description(),
-                        error_detail: Some(format!(
-                            "Reference label \"{}\" is not defined",
-                            &caps[2]
-                        )),
-                        error_context: Some(caps[0].to_string()),
-                        rule_information: self.information(),
-                        error_range: None,
-                        fix_info: Some(crate::types::FixInfo {
-                            line_number: Some(insert_line),
-                            edit_column: Some(insert_col),
-                            delete_count: None,
-                            insert_text: Some(format!("\n[{}]: #link\n", &caps[2])),
-                        }),
-                        suggestion: Some(
-                            "Define all link reference labels that are used".to_string(),
-                        ),
-                        severity: Severity::Error,
-                        fix_only: false,
-                    });
-                }
-            }
-
-            // Check collapsed reference links: [label][]
-            for caps in COLLAPSED_REF_RE.captures_iter(line) {
-                let label = caps[1].to_lowercase();
-                if !defined_labels.contains(&label) {
-                    // Append to the last non-empty line
-                    // Note: apply_fixes pops trailing empty lines (lines that are just "\n" or "\r\n")
-                    // so we need to target the line before it if it exists
-                    let last_line_idx = params.lines.len().saturating_sub(1);
-                    let is_trailing_empty = params
-                        .lines
-                        .get(last_line_idx)
-                        .map(|l| *l == "\n" || *l == "\r\n")
-                        .unwrap_or(false);
-                    let insert_line = if is_trailing_empty {
-                        last_line_idx.max(1) // Target line before trailing empty
-                    } else {
-                        params.lines.len() // Target the actual last line
-                    };
-                    let target_line = params.lines.get(insert_line - 1).copied().unwrap_or("");
-                    let target_stripped = target_line.trim_end_matches('\n').trim_end_matches('\r');
-                    let insert_col = target_stripped.len() + 1;
-
-                    errors.push(LintError {
-                        line_number,
-                        rule_names: self.names(),
-                        rule_description: self.description(),
-                        error_detail: Some(format!(
-                            "Reference label \"{}\" is not defined",
-                            &caps[1]
-                        )),
-                        error_context: Some(caps[0].to_string()),
-                        rule_information: self.information(),
-                        error_range: None,
-                        fix_info: Some(crate::types::FixInfo {
-                            line_number: Some(insert_line),
-                            edit_column: Some(insert_col),
-                            delete_count: None,
-                            insert_text: Some(format!("\n[{}]: #link\n", &caps[1])),
-                        }),
-                        suggestion: Some(
-                            "Define all link reference labels that are used".to_string(),
-                        ),
-                        severity: Severity::Error,
-                        fix_only: false,
-                    });
-                }
-            }
+            errors.push(LintError {
+                line_number: link.line_number,
+                rule_names: self.names(),
+                rule_description: self.description(),
+                error_detail: Some(format!("Reference label \"{}\" is not defined", link.label)),
+                error_context: Some(link.text.clone()),
+                rule_information: self.information(),
+                error_range: None,
+                fix_info: Some(crate::types::FixInfo {
+                    line_number: Some(insert_line),
+                    edit_column: Some(insert_col),
+                    delete_count: None,
+                    insert_text: Some(format!("\n[{}]: #link\n", link.label)),
+                }),
+                suggestion: Some("Define all link reference labels that are used".to_string()),
+                severity: Severity::Error,
+                fix_only: false,
+                line_text: None,
+                related: None,
+            });
         }
 
         errors
@@ -1,25 +1,7 @@
 //! MD053 - Link and image reference definitions should be needed
 
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
-use regex::Regex;
 use std::collections::HashSet;
-use std::sync::LazyLock;
-
-/// Regex for reference link definitions: `[label]: url`
-static DEF_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"^\s*\[([^\]]+)\]:\s+").expect("valid regex"));
-
-/// Regex for full reference links: `[text][label]`
-static FULL_REF_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\[([^\]]+)\]").expect("valid regex"));
-
-/// Regex for collapsed reference links: `[label][]`
-static COLLAPSED_REF_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\[\]").expect("valid regex"));
-
-/// Regex for shortcut reference links: `[label]` (not followed by `[` or `(` or `:`)
-static SHORTCUT_REF_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\[([^\]]+)\](?:[^(\[:]|$)").expect("valid regex"));
 
 pub struct MD053;
 
@@ -57,68 +39,28 @@ impl Rule for MD053 {
             .config
             .get("ignored_definitions")
             .and_then(|v| serde_json::from_value::<Vec<String>>(v.clone()).ok())
-            .unwrap_or_else(|| vec!["//".to_string()]);
-
-        // Pass 1: Collect all reference definitions with line numbers (skipping code blocks)
-        let mut definitions: Vec<(String, usize)> = Vec::new(); // (label_lowercase, line_number)
-        let mut in_code_block = false;
-        for (idx, line) in params.lines.iter().enumerate() {
-            let line_number = idx + 1;
-
-            if crate::helpers::is_code_fence(line.trim_start()) {
-                in_code_block = !in_code_block;
-                continue;
-            }
-            if in_code_block {
-                continue;
-            }
-
-            if let Some(caps) = DEF_RE.captures(line) {
-                let label = caps[1].to_string();
-                let label_lower = label.to_lowercase();
-
-                // Skip ignored definitions
-                if is_ignored(
-                    &label_lower,
-                    &ignored_definitions
-                        .iter()
-                        .map(|s| s.to_lowercase())
-                        .collect::<Vec<_>>(),
-                ) {
-                    continue;
-                }
-
-                definitions.push((label_lower, line_number));
-            }
-        }
-
-        // Pass 2: Collect all reference usages (skipping code blocks)
-        let mut used_labels: HashSet<String> = HashSet::new();
-        in_code_block = false;
-        for line in params.lines.iter() {
-            if crate::helpers::is_code_fence(line.trim_start()) {
-                in_code_block = !in_code_block;
-                continue;
-            }
-            if in_code_block {
-                continue;
-            }
-
-            // Full reference links: [text][label]
-            for caps in FULL_REF_RE.captures_iter(line) {
-                used_labels.insert(caps[2].to_lowercase());
-            }
-
-            // Collapsed reference links: [label][]
-            for caps in COLLAPSED_REF_RE.captures_iter(line) {
-                used_labels.insert(caps[1].to_lowercase());
-            }
-
-            // Shortcut reference links: [label]
-            for caps in SHORTCUT_REF_RE.captures_iter(line) {
-                used_labels.insert(caps[1].to_lowercase());
-            }
-        }
+            .unwrap_or_else(|| vec!["//".to_string()])
+            .iter()
+            .map(|s| s.to_lowercase())
+            .collect();
+
+        // Collect all reference definitions with line numbers (outside code
+        // blocks). Footnotes (`[^1]: ...`) share the syntax but are a separate
+        // namespace, checked by KMD003.
+        let definitions: Vec<(String, usize)> = params
+            .reference_definitions()
+            .iter()
+            .filter(|definition| !definition.label.starts_with('^'))
+            .map(|definition| (definition.label.to_lowercase(), definition.line_number))
+            .filter(|(label, _)| !is_ignored(label, &ignored_definitions))
+            .collect();
+
+        // Collect all reference usages: full, collapsed, and shortcut
+        let used_labels: HashSet<String> = params
+            .reference_links()
+            .iter()
+            .map(|link| link.label.to_lowercase())
+            .collect();
 
         // Report definitions that are never used
         for (label, line_number) in &definitions {
@@ -140,6 +82,8 @@ impl Rule for MD053 {
                     suggestion: Some("Remove this unused link definition".to_string()),
                     severity: Severity::Error,
                     fix_only: false,
+                    line_text: None,
+                    related: None,
                 });
             }
         }
@@ -247,4 +191,25 @@ mod tests {
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 0); // No errors, all definitions used
     }
+
+    #[test]
+    fn test_md053_footnote_definitions_are_not_link_definitions() {
+        let lines: Vec<&str> = vec![
+            "A claim[^1] with an unused link.\n",
+            "\n",
+            "[^1]: The footnote.\n",
+            "[^2]: Unreferenced, but that is KMD003's job.\n",
+            "[unused]: https://example.com\n",
+        ];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+
+        let errors = MD053.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 5);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Unused reference definition \"unused\"")
+        );
+    }
 }
@@ -1,9 +1,127 @@
 //! MD055 - Table pipe style
+//!
+//! Supports `style` config: "consistent" (default), "leading_and_trailing",
+//! "leading_only", "trailing_only" or "no_leading_or_trailing".
+//! - "consistent": each row has pipes at both ends or at neither
+//! - the others: every row of every table has exactly the pipes named
 
+use crate::helpers::table_row_content;
+use crate::parser::TokenExt;
 use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PipeStyle {
+    LeadingAndTrailing,
+    LeadingOnly,
+    TrailingOnly,
+    NoLeadingOrTrailing,
+}
+
+impl PipeStyle {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "leading_and_trailing" => Some(PipeStyle::LeadingAndTrailing),
+            "leading_only" => Some(PipeStyle::LeadingOnly),
+            "trailing_only" => Some(PipeStyle::TrailingOnly),
+            "no_leading_or_trailing" => Some(PipeStyle::NoLeadingOrTrailing),
+            _ => None,
+        }
+    }
+
+    /// Whether rows have a (leading, trailing) pipe
+    fn pipes(self) -> (bool, bool) {
+        match self {
+            PipeStyle::LeadingAndTrailing => (true, true),
+            PipeStyle::LeadingOnly => (true, false),
+            PipeStyle::TrailingOnly => (false, true),
+            PipeStyle::NoLeadingOrTrailing => (false, false),
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            PipeStyle::LeadingAndTrailing => "leading and trailing pipes",
+            PipeStyle::LeadingOnly => "a leading pipe only",
+            PipeStyle::TrailingOnly => "a trailing pipe only",
+            PipeStyle::NoLeadingOrTrailing => "no leading or trailing pipes",
+        }
+    }
+}
+
+/// Whether a trimmed table row has a (leading, trailing) pipe
+fn outer_pipes(row: &str) -> (bool, bool) {
+    (
+        row.starts_with('|'),
+        row.len() > 1 && row.ends_with('|') && !row.ends_with("\\|"),
+    )
+}
+
+/// A trimmed table row rewritten with the outer pipes of `style`. The
+/// result holds no line ending, so a fix inserting it keeps the row's own.
+fn restyle_row(row: &str, style: PipeStyle) -> String {
+    let (starts, ends) = outer_pipes(row);
+    let mut inner = row;
+    if starts {
+        inner = inner[1..].trim_start();
+    }
+    if ends {
+        inner = inner[..inner.len() - 1].trim_end();
+    }
+    let (leading, trailing) = style.pipes();
+    format!(
+        "{}{}{}",
+        if leading { "| " } else { "" },
+        inner,
+        if trailing { " |" } else { "" }
+    )
+}
+
 pub struct MD055;
 
+impl MD055 {
+    /// Errors for the table rows whose outer pipes don't match `style`
+    fn lint_style(&self, params: &RuleParams, style: PipeStyle) -> Vec<LintError> {
+        let mut errors = Vec::new();
+        for table in params.tokens.filter_by_type("table") {
+            for line_number in table.start_line..=table.end_line {
+                let Some(line) = params.lines.get(line_number - 1) else {
+                    continue;
+                };
+                let content = table_row_content(line, line_number > table.start_line);
+                let row = content.trim();
+                if row.is_empty() || outer_pipes(row) == style.pipes() {
+                    continue;
+                }
+                let column = line.len() - content.trim_start().len() + 1;
+                let fixed = restyle_row(row, style);
+                // Without any pipe left the row would no longer be a table row
+                let fix_info = fixed.contains('|').then_some(FixInfo {
+                    line_number: None,
+                    edit_column: Some(column),
+                    delete_count: Some(row.len() as i32),
+                    insert_text: Some(fixed),
+                });
+                errors.push(LintError {
+                    line_number,
+                    rule_names: self.names(),
+                    rule_description: self.description(),
+                    error_detail: Some(format!("Expected {}", style.describe())),
+                    error_context: Some(row.to_string()),
+                    rule_information: self.information(),
+                    error_range: Some((column, row.len())),
+                    fix_info,
+                    suggestion: Some(format!("Write table rows with {}", style.describe())),
+                    severity: Severity::Error,
+                    fix_only: false,
+                    line_text: None,
+                    related: None,
+                });
+            }
+        }
+        errors
+    }
+}
+
 impl Rule for MD055 {
     fn names(&self) -> &'static [&'static str] {
         &["MD055", "table-pipe-style"]
@@ -18,7 +136,7 @@ impl Rule for MD055 {
     }
 
     fn parser_type(&self) -> ParserType {
-        ParserType::None
+        ParserType::Micromark
     }
 
     fn information(&self) -> Option<&'static str> {
@@ -26,11 +144,24 @@ impl Rule for MD055 {
     }
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        if let Some(style) = params
+            .config
+            .get("style")
+            .and_then(|v| v.as_str())
+            .and_then(PipeStyle::from_name)
+        {
+            return self.lint_style(params, style);
+        }
+
         let mut errors = Vec::new();
+        let mut in_table = false;
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
-            let trimmed = line.trim();
+            // Tables may sit inside blockquotes and list items
+            let content = table_row_content(line, in_table);
+            let trimmed = content.trim();
+            in_table = trimmed.contains('|');
 
             // Check for inconsistent table pipe usage
             if trimmed.contains('|') && trimmed.matches('|').count() > 1 {
@@ -38,8 +169,8 @@ impl Rule for MD055 {
                 let ends_with_pipe = trimmed.trim_end().ends_with('|');
 
                 if starts_with_pipe != ends_with_pipe {
-                    // Calculate leading whitespace
-                    let leading_ws = line.len() - line.trim_start().len();
+                    // Byte offset of the row's first character
+                    let leading_ws = line.len() - content.trim_start().len();
 
                     // Calculate trailing whitespace (including newline)
                     let line_without_newline = line.trim_end_matches('\n');
@@ -59,7 +190,7 @@ impl Rule for MD055 {
                             insert_text: Some(" |".to_string()),
                         })
                     } else if !starts_with_pipe && ends_with_pipe {
-                        // Add leading pipe: insert "| " at the start (after leading whitespace)
+                        // Add leading pipe: insert "| " at the start of the row
                         Some(FixInfo {
                             line_number: None,
                             edit_column: Some(leading_ws + 1),
@@ -85,6 +216,8 @@ impl Rule for MD055 {
                         ),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             }
@@ -205,4 +338,105 @@ mod tests {
         assert_eq!(fix.edit_column, Some(24)); // After "  | Header 1 | Header 2" (23 chars + 1)
         assert_eq!(fix.insert_text, Some(" |".to_string()));
     }
+
+    #[test]
+    fn test_md055_blockquote_table() {
+        let lines: Vec<&str> = vec![
+            "> | Header 1 | Header 2 |\n",
+            "> | -------- | -------- |\n",
+            "> | Cell 1   | Cell 2   |\n",
+        ];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD055.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_md055_blockquote_table_inconsistent() {
+        let lines: Vec<&str> = vec![
+            "> | Header 1 | Header 2 |\n",
+            "> | -------- | -------- |\n",
+            "> Cell 1   | Cell 2   |\n",
+        ];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD055.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 3);
+        let fix = errors[0].fix_info.as_ref().unwrap();
+        assert_eq!(fix.edit_column, Some(3)); // After "> "
+        assert_eq!(fix.insert_text, Some("| ".to_string()));
+    }
+
+    fn fix_round_trip(content: &str, style: &str) -> (Vec<LintError>, String) {
+        let config: crate::Config = serde_json::from_str(&format!(
+            r#"{{"default": false, "MD055": {{"style": "{}"}}}}"#,
+            style
+        ))
+        .unwrap();
+        let errors = crate::lint_string(content, &config).unwrap();
+        let fixed = crate::fix_content(content, &config).unwrap();
+        assert!(crate::lint_string(&fixed, &config).unwrap().is_empty());
+        (errors, fixed)
+    }
+
+    #[test]
+    fn test_md055_style_fix_keeps_crlf() {
+        let content = "Intro\r\n\r\nHeader 1 | Header 2\r\n-------- | --------\r\nCell 1   | Cell 2\r\n\r\n> a | b\r\n> --|--\r\n";
+        let (errors, fixed) = fix_round_trip(content, "leading_and_trailing");
+        let lines: Vec<usize> = errors.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, [3, 4, 5, 7, 8]);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Expected leading and trailing pipes")
+        );
+        assert_eq!(
+            fixed,
+            "Intro\r\n\r\n| Header 1 | Header 2 |\r\n| -------- | -------- |\r\n| Cell 1   | Cell 2 |\r\n\r\n> | a | b |\r\n> | --|-- |\r\n"
+        );
+        assert_eq!(fixed.matches('\n').count(), fixed.matches("\r\n").count());
+
+        // And back again
+        let (_, restored) = fix_round_trip(&fixed, "no_leading_or_trailing");
+        assert_eq!(
+            restored,
+            "Intro\r\n\r\nHeader 1 | Header 2\r\n-------- | --------\r\nCell 1   | Cell 2\r\n\r\n> a | b\r\n> --|--\r\n"
+        );
+    }
+
+    #[test]
+    fn test_md055_style_one_sided() {
+        let content = "| a | b |\n|---|---|\n| c | d |\n";
+        let (_, fixed) = fix_round_trip(content, "leading_only");
+        assert_eq!(fixed, "| a | b\n| ---|---\n| c | d\n");
+        let (_, fixed) = fix_round_trip(content, "trailing_only");
+        assert_eq!(fixed, "a | b |\n---|--- |\nc | d |\n");
+    }
+
+    #[test]
+    fn test_md055_style_single_column_is_not_unpiped() {
+        // Without its pipes a one-column row would not be a table row
+        let content = "| a |\n| - |\n";
+        let config: crate::Config = serde_json::from_str(
+            r#"{"default": false, "MD055": {"style": "no_leading_or_trailing"}}"#,
+        )
+        .unwrap();
+        let errors = crate::lint_string(content, &config).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.fix_info.is_none()));
+    }
+
+    #[test]
+    fn test_md055_list_item_table() {
+        let lines: Vec<&str> = vec![
+            "- | Header 1 | Header 2 |\n",
+            "  | -------- | -------- |\n",
+            "\n",
+            "Header | Other\n",
+            "- | -\n",
+        ];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD055.lint(&params).is_empty());
+    }
 }
@@ -32,7 +32,8 @@ impl Rule for MD056 {
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
-            let trimmed = line.trim();
+            // Tables may sit inside blockquotes and list items
+            let trimmed = crate::helpers::table_row_content(line, in_table).trim();
 
             if trimmed.contains('|') {
                 let col_count = trimmed.matches('|').count() - 1;
@@ -58,6 +59,8 @@ impl Rule for MD056 {
                         ),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
             } else if !trimmed.is_empty() {
@@ -133,4 +136,14 @@ mod tests {
         let errors = rule.lint(&params);
         assert_eq!(errors.len(), 0);
     }
+
+    #[test]
+    fn test_md056_blockquote_table() {
+        let lines: Vec<&str> = vec!["> | A | B |\n", "> | - | - |\n", "> | 1 | 2 | 3 |\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD056.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 3);
+    }
 }
@@ -17,6 +17,10 @@ impl Rule for MD058 {
         &["table", "blank_lines", "fixable"]
     }
 
+    fn fix_priority(&self) -> i32 {
+        -1
+    }
+
     fn parser_type(&self) -> ParserType {
         ParserType::None
     }
@@ -28,39 +32,41 @@ impl Rule for MD058 {
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
         let mut table_start = 0;
+        // Blank line to insert, keeping the table's blockquote markers
+        let mut blank_line = String::new();
 
         for (idx, line) in params.lines.iter().enumerate() {
             let line_number = idx + 1;
-            let trimmed = line.trim();
+            // Tables may sit inside blockquotes and list items
+            let trimmed = crate::helpers::table_row_content(line, table_start > 0).trim();
 
             if trimmed.contains('|') && table_start == 0 {
                 table_start = line_number;
+                let quote_len = crate::helpers::blockquote_prefix_len(line);
+                blank_line = format!("{}\n", line[..quote_len].trim_end());
 
                 // Check for blank line before
-                if line_number > 1 {
-                    let prev_line = &params.lines[line_number - 2];
-                    if !prev_line.trim().is_empty() {
-                        errors.push(LintError {
-                            line_number,
-                            rule_names: self.names(),
-                            rule_description: self.description(),
-                            error_detail: Some("Expected blank line before table".to_string()),
-                            error_context: None,
-                            rule_information: self.information(),
-                            error_range: None,
-                            fix_info: Some(FixInfo {
-                                line_number: Some(line_number),
-                                edit_column: Some(1),
-                                delete_count: None,
-                                insert_text: Some("\n".to_string()),
-                            }),
-                            suggestion: Some(
-                                "Tables should be surrounded by blank lines".to_string(),
-                            ),
-                            severity: Severity::Error,
-                            fix_only: false,
-                        });
-                    }
+                if line_number > 1 && !is_blank(params.lines[line_number - 2]) {
+                    errors.push(LintError {
+                        line_number,
+                        rule_names: self.names(),
+                        rule_description: self.description(),
+                        error_detail: Some("Expected blank line before table".to_string()),
+                        error_context: None,
+                        rule_information: self.information(),
+                        error_range: None,
+                        fix_info: Some(FixInfo {
+                            line_number: Some(line_number),
+                            edit_column: Some(1),
+                            delete_count: None,
+                            insert_text: Some(blank_line.clone()),
+                        }),
+                        suggestion: Some("Tables should be surrounded by blank lines".to_string()),
+                        severity: Severity::Error,
+                        fix_only: false,
+                        line_text: None,
+                        related: None,
+                    });
                 }
             } else if !trimmed.contains('|') && table_start > 0 {
                 // End of table
@@ -78,11 +84,13 @@ impl Rule for MD058 {
                             line_number: Some(line_number),
                             edit_column: Some(1),
                             delete_count: None,
-                            insert_text: Some("\n".to_string()),
+                            insert_text: Some(blank_line.clone()),
                         }),
                         suggestion: Some("Tables should be surrounded by blank lines".to_string()),
                         severity: Severity::Error,
                         fix_only: false,
+                        line_text: None,
+                        related: None,
                     });
                 }
                 table_start = 0;
@@ -93,6 +101,13 @@ impl Rule for MD058 {
     }
 }
 
+/// Whether a line is blank once blockquote markers are removed
+fn is_blank(line: &str) -> bool {
+    line[crate::helpers::blockquote_prefix_len(line)..]
+        .trim()
+        .is_empty()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,4 +222,37 @@ mod tests {
         assert_eq!(fix.delete_count, None);
         assert_eq!(fix.insert_text, Some("\n".to_string()));
     }
+
+    #[test]
+    fn test_md058_blockquote_table_with_blank_lines() {
+        let lines: Vec<&str> = vec![
+            "> Some text\n",
+            ">\n",
+            "> | Header |\n",
+            "> | ------ |\n",
+            ">\n",
+            "> More text\n",
+        ];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MD058.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_md058_blockquote_table_without_blank_line() {
+        let lines: Vec<&str> = vec![
+            "> Some text\n",
+            "> | Header |\n",
+            "> | ------ |\n",
+            "> More text\n",
+        ];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MD058.lint(&params);
+        assert_eq!(errors.len(), 2);
+        for error in &errors {
+            let fix = error.fix_info.as_ref().unwrap();
+            assert_eq!(fix.insert_text, Some(">\n".to_string()));
+        }
+    }
 }
@@ -26,7 +26,7 @@ impl Rule for MD059 {
     }
 
     fn parser_type(&self) -> ParserType {
-        ParserType::None
+        ParserType::Micromark
     }
 
     fn information(&self) -> Option<&'static str> {
@@ -35,30 +35,43 @@ impl Rule for MD059 {
 
     fn lint(&self, params: &RuleParams) -> Vec<LintError> {
         let mut errors = Vec::new();
-        let mut in_display_math = false;
 
-        for (idx, line) in params.lines.iter().enumerate() {
-            let line_number = idx + 1;
-            let trimmed = line.trim_end_matches('\n').trim_end_matches('\r');
-
-            // Handle display math blocks ($$...$$)
-            // Check for $$ delimiters on a line
-            if trimmed.trim() == "$$" {
-                in_display_math = !in_display_math;
-                continue;
-            }
+        // Only spans the parser recognizes as math are checked, so a literal
+        // `$` (a price, a shell variable) never opens a math context
+        for token in params.tokens.iter().filter(|t| t.is_type("math")) {
+            let display = token.metadata.get("display").is_some_and(|d| d == "true");
+            let (math_type, delimiter) = if display {
+                ("display math", 2)
+            } else {
+                ("math", 1)
+            };
 
-            if in_display_math {
-                // Check this content line for emphasis underscores
-                self.check_line_for_emphasis(trimmed, line_number, 0, "display math", &mut errors);
-                continue;
+            for line_number in token.start_line..=token.end_line {
+                let Some(line) = params.lines.get(line_number - 1) else {
+                    break;
+                };
+                let line = line.trim_end_matches(['\n', '\r']);
+                // Byte range of the math content on this line, delimiters excluded
+                let start = if line_number == token.start_line {
+                    token.start_column - 1 + delimiter
+                } else {
+                    0
+                };
+                let end = if line_number == token.end_line {
+                    token.end_column.saturating_sub(delimiter)
+                } else {
+                    line.len()
+                };
+                if let Some(content) = line.get(start..end) {
+                    self.check_line_for_emphasis(
+                        content,
+                        line_number,
+                        start,
+                        math_type,
+                        &mut errors,
+                    );
+                }
             }
-
-            // Handle inline display math ($$...$$) on a single line
-            self.check_inline_display_math(trimmed, line_number, &mut errors);
-
-            // Handle inline math ($...$) on a single line
-            self.check_inline_math(trimmed, line_number, &mut errors);
         }
 
         errors
@@ -101,86 +114,11 @@ impl MD059 {
                 suggestion: Some("Escape underscores with backslash in math context".to_string()),
                 severity: Severity::Warning,
                 fix_only: false,
+                line_text: None,
+                related: None,
             });
         }
     }
-
-    /// Check for emphasis underscores in single-line $$...$$ math
-    fn check_inline_display_math(
-        &self,
-        line: &str,
-        line_number: usize,
-        errors: &mut Vec<LintError>,
-    ) {
-        // Find $$...$$ spans that are on a single line (not standalone $$)
-        let mut search_start = 0;
-        while let Some(start) = line[search_start..].find("$$") {
-            let abs_start = search_start + start;
-            let after_open = abs_start + 2;
-            if after_open >= line.len() {
-                break;
-            }
-            if let Some(end) = line[after_open..].find("$$") {
-                let abs_end = after_open + end;
-                let math_content = &line[after_open..abs_end];
-                self.check_line_for_emphasis(
-                    math_content,
-                    line_number,
-                    after_open,
-                    "display math",
-                    errors,
-                );
-                search_start = abs_end + 2;
-            } else {
-                break;
-            }
-        }
-    }
-
-    /// Check for emphasis underscores in single-line $...$ math
-    fn check_inline_math(&self, line: &str, line_number: usize, errors: &mut Vec<LintError>) {
-        let bytes = line.as_bytes();
-        let mut i = 0;
-        while i < bytes.len() {
-            // Skip $$ (handled by display math)
-            if i + 1 < bytes.len() && bytes[i] == b'$' && bytes[i + 1] == b'$' {
-                i += 2;
-                // Skip past the closing $$
-                while i + 1 < bytes.len() {
-                    if bytes[i] == b'$' && bytes[i + 1] == b'$' {
-                        i += 2;
-                        break;
-                    }
-                    i += 1;
-                }
-                continue;
-            }
-
-            if bytes[i] == b'$' {
-                let start = i;
-                i += 1;
-                // Find the closing $
-                while i < bytes.len() && bytes[i] != b'$' {
-                    i += 1;
-                }
-                if i < bytes.len() && bytes[i] == b'$' {
-                    let math_content = &line[start + 1..i];
-                    if !math_content.is_empty() {
-                        self.check_line_for_emphasis(
-                            math_content,
-                            line_number,
-                            start + 1,
-                            "math",
-                            errors,
-                        );
-                    }
-                    i += 1;
-                }
-            } else {
-                i += 1;
-            }
-        }
-    }
 }
 
 #[cfg(test)]
@@ -188,23 +126,25 @@ mod tests {
     use super::*;
     use std::collections::HashMap;
 
+    /// Errors for `lines`, with math spans taken from the parser
+    fn lint(lines: &[&str]) -> Vec<LintError> {
+        let content = lines.concat();
+        let tokens = crate::parser::parse(&content);
+        let config = HashMap::new();
+        MD059.lint(&RuleParams::test_with_tokens(lines, &tokens, &config))
+    }
+
     #[test]
     fn test_md059_no_emphasis_in_math() {
         let lines = vec!["$x^2$\n"];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test(&lines, &config);
-        let rule = MD059;
-        let errors = rule.lint(&params);
+        let errors = lint(&lines);
         assert_eq!(errors.len(), 0);
     }
 
     #[test]
     fn test_md059_emphasis_in_math() {
         let lines = vec!["$_text_$\n"];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test(&lines, &config);
-        let rule = MD059;
-        let errors = rule.lint(&params);
+        let errors = lint(&lines);
         assert_eq!(errors.len(), 1);
         assert_eq!(errors[0].line_number, 1);
         assert_eq!(errors[0].severity, Severity::Warning);
@@ -221,9 +161,7 @@ mod tests {
     fn test_md059_fix_info_inline_math() {
         // "$_text_$" — underscore match at column 2 (1-based), length 6
         let lines = vec!["$_text_$"];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test(&lines, &config);
-        let errors = MD059.lint(&params);
+        let errors = lint(&lines);
         assert_eq!(errors.len(), 1);
         let fix = errors[0].fix_info.as_ref().expect("Should have fix_info");
         assert_eq!(fix.edit_column, Some(2)); // 1-based: after the $
@@ -235,9 +173,7 @@ mod tests {
     fn test_md059_fix_info_display_math_block() {
         // The fix should target the content line (line 2), not the $$ start line
         let lines = vec!["$$\n", "_text_\n", "$$\n"];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test(&lines, &config);
-        let errors = MD059.lint(&params);
+        let errors = lint(&lines);
         assert_eq!(errors.len(), 1);
         assert_eq!(errors[0].line_number, 2, "Error should be on content line");
         let fix = errors[0].fix_info.as_ref().expect("Should have fix_info");
@@ -250,9 +186,7 @@ mod tests {
     fn test_md059_fix_info_inline_display_math() {
         // "$$_x_$$" — underscore match inside $$...$$
         let lines = vec!["$$_x_$$"];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test(&lines, &config);
-        let errors = MD059.lint(&params);
+        let errors = lint(&lines);
         assert_eq!(errors.len(), 1);
         let fix = errors[0].fix_info.as_ref().expect("Should have fix_info");
         assert_eq!(fix.edit_column, Some(3)); // after $$
@@ -264,9 +198,7 @@ mod tests {
     fn test_md059_multiple_underscores() {
         // Two emphasis patterns in one math span
         let lines = vec!["$_a_ + _b_$"];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test(&lines, &config);
-        let errors = MD059.lint(&params);
+        let errors = lint(&lines);
         assert_eq!(errors.len(), 2);
         assert_eq!(
             errors[0].fix_info.as_ref().unwrap().insert_text,
@@ -282,9 +214,7 @@ mod tests {
     fn test_md059_subscript_no_trigger() {
         // Single underscore (subscript like x_1) should not trigger — needs _text_ pattern
         let lines = vec!["$x_1$"];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test(&lines, &config);
-        let errors = MD059.lint(&params);
+        let errors = lint(&lines);
         assert_eq!(errors.len(), 0);
     }
 
@@ -292,14 +222,44 @@ mod tests {
     fn test_md059_indented_dollar_dollar() {
         // Indented $$ should still toggle display math
         let lines = vec!["  $$\n", "  _text_\n", "  $$\n"];
-        let config = HashMap::new();
-        let params = crate::types::RuleParams::test(&lines, &config);
-        let errors = MD059.lint(&params);
-        // The $$ lines have extra whitespace so `trimmed.trim() == "$$"` should match
+        let errors = lint(&lines);
         assert_eq!(
             errors.len(),
             1,
             "Indented $$ should toggle display math block"
         );
     }
+
+    #[test]
+    fn test_md059_fixes_inline_and_display_math() {
+        let content = "Inline $_x_$ here.\n\n$$\n\\frac{_a_}{b}\n$$\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let errors = lint(&lines);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Emphasis-style underscore found in math")
+        );
+        assert_eq!(
+            errors[1].error_detail.as_deref(),
+            Some("Emphasis-style underscore found in display math")
+        );
+        assert_eq!(
+            crate::lint::apply_fixes(content, &errors),
+            "Inline $\\_x\\_$ here.\n\n$$\n\\frac{\\_a\\_}{b}\n$$\n"
+        );
+    }
+
+    #[test]
+    fn test_md059_literal_dollars_not_math() {
+        // Prices aren't math, so the emphasis between them is left alone
+        let lines = vec!["Costs $5.00 and _really_ $10 now.\n"];
+        assert!(lint(&lines).is_empty());
+    }
+
+    #[test]
+    fn test_md059_math_in_code_span_ignored() {
+        let lines = vec!["Use `$_a_$` literally.\n"];
+        assert!(lint(&lines).is_empty());
+    }
 }
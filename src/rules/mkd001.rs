@@ -0,0 +1,86 @@
+//! MKD001 - Files should not start with a byte order mark
+//!
+//! Some Windows editors save UTF-8 files with a leading byte order mark
+//! (`U+FEFF`). The lint pipeline strips it before rules run so it never
+//! confuses line-1 checks, and this rule reports it with a fix that deletes it.
+
+use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+
+pub struct MKD001;
+
+impl Rule for MKD001 {
+    fn names(&self) -> &'static [&'static str] {
+        &["MKD001", "no-bom"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Files should not start with a byte order mark"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["encoding", "fixable"]
+    }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::None
+    }
+
+    fn lints_front_matter(&self) -> bool {
+        true
+    }
+
+    fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        if !params.has_bom {
+            return Vec::new();
+        }
+
+        vec![LintError {
+            line_number: 1,
+            rule_names: self.names(),
+            rule_description: self.description(),
+            error_detail: Some("UTF-8 byte order mark (U+FEFF)".to_string()),
+            error_context: None,
+            rule_information: self.information(),
+            error_range: None,
+            // Column 0 addresses the byte order mark, which precedes column 1
+            fix_info: Some(FixInfo {
+                line_number: Some(1),
+                edit_column: Some(0),
+                delete_count: Some(crate::helpers::BOM.len() as i32),
+                insert_text: None,
+            }),
+            suggestion: Some("Save the file as UTF-8 without a byte order mark".to_string()),
+            severity: Severity::Error,
+            fix_only: false,
+            line_text: None,
+            related: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_mkd001_no_bom() {
+        let lines = vec!["# Heading\n"];
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MKD001.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_mkd001_bom() {
+        let lines = vec!["# Heading\n"];
+        let config = HashMap::new();
+        let mut params = crate::types::RuleParams::test(&lines, &config);
+        params.has_bom = true;
+        let errors = MKD001.lint(&params);
+        assert_eq!(errors.len(), 1);
+        let fix = errors[0].fix_info.as_ref().unwrap();
+        assert_eq!(fix.edit_column, Some(0));
+        assert_eq!(fix.delete_count, Some(3));
+    }
+}
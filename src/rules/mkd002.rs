@@ -0,0 +1,187 @@
+//! MKD002 - Line endings should match the configured style
+//!
+//! Opt-in rule that keeps line endings uniform across files, avoiding churn
+//! when editors on different platforms touch the same repository. The
+//! `line_ending` option is `lf`, `crlf`, or `auto` (the default), which
+//! expects every line to end like the file's first line.
+//!
+//! Only the first mismatched line is reported; the fixes for the remaining
+//! lines ride along as `fix_only` errors so `--fix` normalizes the whole file.
+
+use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+
+pub struct MKD002;
+
+/// Ending of a raw line (`"\r\n"`, `"\n"`, or `""` for an unterminated line)
+fn line_ending_of(line: &str) -> &str {
+    if line.ends_with("\r\n") {
+        "\r\n"
+    } else if line.ends_with('\n') {
+        "\n"
+    } else {
+        ""
+    }
+}
+
+fn ending_name(ending: &str) -> &'static str {
+    if ending == "\r\n" { "crlf" } else { "lf" }
+}
+
+impl Rule for MKD002 {
+    fn names(&self) -> &'static [&'static str] {
+        &["MKD002", "line-ending"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Line endings should match the configured style"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["whitespace", "line_ending", "fixable"]
+    }
+
+    fn fix_priority(&self) -> i32 {
+        1
+    }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::None
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn lints_front_matter(&self) -> bool {
+        true
+    }
+
+    fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        let style = params
+            .config
+            .get("line_ending")
+            .and_then(|v| v.as_str())
+            .unwrap_or("auto");
+        let expected = match style.to_ascii_lowercase().as_str() {
+            "lf" => "\n",
+            "crlf" => "\r\n",
+            _ => match params.lines.first().map(|l| line_ending_of(l)) {
+                Some(ending) if !ending.is_empty() => ending,
+                _ => return Vec::new(),
+            },
+        };
+
+        let mismatched: Vec<(usize, &str, &str)> = params
+            .lines
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, line)| {
+                let actual = line_ending_of(line);
+                (!actual.is_empty() && actual != expected).then(|| (idx + 1, *line, actual))
+            })
+            .collect();
+
+        let total = mismatched.len();
+        mismatched
+            .into_iter()
+            .enumerate()
+            .map(|(i, (line_number, line, actual))| LintError {
+                line_number,
+                rule_names: self.names(),
+                rule_description: self.description(),
+                error_detail: Some(format!(
+                    "Expected: {}; Actual: {} ({} line(s))",
+                    ending_name(expected),
+                    ending_name(actual),
+                    total
+                )),
+                error_context: None,
+                rule_information: self.information(),
+                error_range: None,
+                // Replace the line ending, which starts right after the text
+                fix_info: Some(FixInfo {
+                    line_number: None,
+                    edit_column: Some(line.len() - actual.len() + 1),
+                    delete_count: Some(actual.len() as i32),
+                    insert_text: Some(expected.to_string()),
+                }),
+                suggestion: Some(format!(
+                    "Convert all line endings to {}",
+                    ending_name(expected).to_uppercase()
+                )),
+                severity: Severity::Error,
+                fix_only: i > 0,
+                line_text: None,
+                related: None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(style: &str) -> HashMap<String, serde_json::Value> {
+        let mut config = HashMap::new();
+        config.insert("line_ending".to_string(), serde_json::json!(style));
+        config
+    }
+
+    #[test]
+    fn test_mkd002_crlf_file_under_lf() {
+        let content = "# Title\r\n\r\nText\r\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let config = config("lf");
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MKD002.lint(&params);
+
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors.iter().filter(|e| !e.fix_only).count(), 1);
+        assert_eq!(
+            errors[0].error_detail,
+            Some("Expected: lf; Actual: crlf (3 line(s))".to_string())
+        );
+        assert_eq!(
+            crate::lint::apply_fixes(content, &errors),
+            "# Title\n\nText\n"
+        );
+    }
+
+    #[test]
+    fn test_mkd002_lf_file_under_crlf() {
+        let content = "# Title\n\nText";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let config = config("crlf");
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MKD002.lint(&params);
+        assert_eq!(
+            crate::lint::apply_fixes(content, &errors),
+            "# Title\r\n\r\nText"
+        );
+    }
+
+    #[test]
+    fn test_mkd002_auto_follows_first_line() {
+        let content = "# Title\r\n\nText\r\n";
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let config = HashMap::new();
+        let params = crate::types::RuleParams::test(&lines, &config);
+        let errors = MKD002.lint(&params);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 2);
+        assert_eq!(
+            crate::lint::apply_fixes(content, &errors),
+            "# Title\r\n\r\nText\r\n"
+        );
+    }
+
+    #[test]
+    fn test_mkd002_consistent_file() {
+        let lines: Vec<&str> = vec!["# Title\n", "\n", "Text\n"];
+        let config = config("lf");
+        let params = crate::types::RuleParams::test(&lines, &config);
+        assert!(MKD002.lint(&params).is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+//! MKD003 - Inline configuration should name known rules
+//!
+//! Opt-in rule that catches typos in `<!-- markdownlint-disable ... -->` and
+//! related directives. A misspelled rule name silently disables nothing, so
+//! the violation it was meant to suppress keeps firing (or, after a rename,
+//! the suppression quietly stops applying).
+//!
+//! Custom rules are not known to built-in rules; list their names in the
+//! `known_rules` option so directives naming them are not reported.
+
+use crate::lint::inline::InlineConfig;
+use crate::types::{LintError, ParserType, Rule, RuleParams, Severity};
+
+pub struct MKD003;
+
+impl Rule for MKD003 {
+    fn names(&self) -> &'static [&'static str] {
+        &["MKD003", "inline-config-unknown-rule"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Inline configuration should name known rules"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["inline_config"]
+    }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::None
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        let known: Vec<String> = params
+            .config
+            .get("known_rules")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str())
+                    .map(|s| s.to_uppercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let inline = InlineConfig::from_lines(params.lines);
+        inline
+            .unknown_rules()
+            .iter()
+            .filter(|(_, name)| !known.contains(name))
+            .map(|(line_number, name)| LintError {
+                line_number: *line_number,
+                rule_names: self.names(),
+                rule_description: self.description(),
+                error_detail: Some(format!("Unknown rule: {}", name)),
+                error_context: Some(name.clone()),
+                rule_information: self.information(),
+                error_range: None,
+                fix_info: None,
+                suggestion: Some(
+                    "Use a rule ID (e.g. MD009) or alias (e.g. no-trailing-spaces)".to_string(),
+                ),
+                severity: Severity::Error,
+                fix_only: false,
+                line_text: None,
+                related: None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_mkd003_known_names() {
+        let lines = vec![
+            "<!-- markdownlint-disable MD009, no-hard-tabs -->\n",
+            "text\n",
+        ];
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
+        assert!(MKD003.lint(&params).is_empty());
+    }
+
+    #[test]
+    fn test_mkd003_unknown_name() {
+        let lines = vec![
+            "# Title\n",
+            "<!-- markdownlint-disable-next-line MD099 no-trailing-space -->\n",
+            "text  \n",
+        ];
+        let config = HashMap::new();
+        let params = RuleParams::test(&lines, &config);
+        let errors = MKD003.lint(&params);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].line_number, 2);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Unknown rule: MD099")
+        );
+        assert_eq!(
+            errors[1].error_detail.as_deref(),
+            Some("Unknown rule: NO-TRAILING-SPACE")
+        );
+    }
+
+    #[test]
+    fn test_mkd003_known_rules_option() {
+        let lines = vec!["<!-- markdownlint-disable custom-rule -->\n"];
+        let mut config = HashMap::new();
+        config.insert(
+            "known_rules".to_string(),
+            serde_json::json!(["Custom-Rule"]),
+        );
+        let params = RuleParams::test(&lines, &config);
+        assert!(MKD003.lint(&params).is_empty());
+    }
+}
@@ -0,0 +1,441 @@
+//! MKD004 - Link reference definitions should be grouped at the end
+//!
+//! Opt-in house-style rule: every `[label]: url` definition belongs in one
+//! block at the end of the document (or, with `position: "section"`, at the
+//! end of its heading's section), sorted by label unless `sorted` is false.
+//! The fix moves the definitions into that block and sorts it.
+//!
+//! Definitions in code blocks and block quotes are not definitions here, and
+//! ones disabled by inline configuration stay where they are. Duplicate labels
+//! are reported, never merged.
+
+use crate::helpers::{ReferenceDefinition, normalize_label};
+use crate::lint::inline::InlineConfig;
+use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+use std::collections::{BTreeSet, HashMap};
+
+pub struct MKD004;
+
+/// Whether a line is an ATX heading (`#` markers followed by a space)
+fn is_atx_heading(line: &str) -> bool {
+    let trimmed = line.trim();
+    let indent = line.len() - line.trim_start().len();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    indent <= 3
+        && (1..=6).contains(&level)
+        && trimmed[level..].starts_with([' ', '\t'])
+        && crate::helpers::parse_heading_line(trimmed).is_some()
+}
+
+/// Line text without its line ending
+fn text_of(line: &str) -> &str {
+    line.trim_end_matches(['\n', '\r'])
+}
+
+impl MKD004 {
+    fn error(&self, line_number: usize, detail: String, fix_info: Option<FixInfo>) -> LintError {
+        LintError {
+            line_number,
+            rule_names: self.names(),
+            rule_description: self.description(),
+            error_detail: Some(detail),
+            error_context: None,
+            rule_information: self.information(),
+            error_range: None,
+            fix_info,
+            suggestion: Some(
+                "Group link reference definitions in one sorted block at the end".to_string(),
+            ),
+            severity: Severity::Error,
+            fix_only: false,
+            line_text: None,
+            related: None,
+        }
+    }
+
+    /// Fix-only line deletion, tied to `line_number` so inline configuration
+    /// keeps or drops it together with the reported error
+    fn delete_line(&self, line_number: usize, target: usize) -> LintError {
+        LintError {
+            fix_only: true,
+            ..self.error(
+                line_number,
+                String::new(),
+                Some(FixInfo {
+                    line_number: Some(target),
+                    edit_column: Some(1),
+                    delete_count: Some(-1),
+                    insert_text: None,
+                }),
+            )
+        }
+    }
+
+    /// Check the definitions of one region (`lines[start..end]`), which
+    /// should end with all of them, and build the fix that moves them there
+    #[allow(clippy::too_many_arguments)]
+    fn check_region(
+        &self,
+        lines: &[&str],
+        code_block_lines: &[bool],
+        definitions: &[&ReferenceDefinition],
+        (start, end): (usize, usize),
+        last_region: bool,
+        sorted: bool,
+        place: &str,
+        errors: &mut Vec<LintError>,
+    ) {
+        let def_lines: BTreeSet<usize> = definitions
+            .iter()
+            .flat_map(|d| (d.line_number - 1)..(d.line_number - 1 + d.line_count))
+            .collect();
+
+        // The trailing run of definitions and blank lines
+        let mut tail = end;
+        while tail > start
+            && !code_block_lines[tail - 1]
+            && (def_lines.contains(&(tail - 1)) || lines[tail - 1].trim().is_empty())
+        {
+            tail -= 1;
+        }
+
+        let mut reported: Vec<(&ReferenceDefinition, String)> = Vec::new();
+        let mut previous: Option<&ReferenceDefinition> = None;
+        for &definition in definitions {
+            if definition.line_number - 1 < tail {
+                reported.push((
+                    definition,
+                    format!(
+                        "Definition [{}] should be at the end of {}",
+                        definition.label, place
+                    ),
+                ));
+                continue;
+            }
+            if sorted
+                && let Some(previous) = previous
+                && normalize_label(&definition.label) < normalize_label(&previous.label)
+            {
+                reported.push((
+                    definition,
+                    format!(
+                        "Definition [{}] should be sorted before [{}]",
+                        definition.label, previous.label
+                    ),
+                ));
+                continue;
+            }
+            previous = Some(definition);
+        }
+        let Some(&(first, _)) = reported.first() else {
+            return;
+        };
+
+        // The replacement block, in document order unless sorted
+        let mut block: Vec<&ReferenceDefinition> = definitions.to_vec();
+        if sorted {
+            block.sort_by_cached_key(|d| normalize_label(&d.label));
+        }
+        let mut block_text = block
+            .iter()
+            .flat_map(|d| lines[d.line_number - 1..d.line_number - 1 + d.line_count].iter())
+            .map(|line| text_of(line))
+            .collect::<Vec<_>>()
+            .join("\n");
+        // Keep a blank line before the next section's heading
+        if !last_region {
+            block_text.push('\n');
+        }
+
+        let is_blank = |idx: usize| lines[idx].trim().is_empty();
+        let mut delete: BTreeSet<usize> = def_lines.clone();
+        delete.extend((tail..end).filter(|&idx| is_blank(idx)));
+        // A definition moved out from between blank lines takes one with it
+        for &idx in def_lines.range(..tail) {
+            let run_end = !def_lines.contains(&(idx + 1));
+            if run_end && idx + 1 < tail && is_blank(idx + 1) {
+                let mut first = idx;
+                while first > 0 && def_lines.contains(&(first - 1)) {
+                    first -= 1;
+                }
+                if first == 0 || is_blank(first - 1) {
+                    delete.insert(idx + 1);
+                }
+            }
+        }
+
+        let insertion = if tail > start {
+            // Append after the region's last line of content
+            let anchor = tail - 1;
+            FixInfo {
+                line_number: Some(anchor + 1),
+                edit_column: Some(text_of(lines[anchor]).len() + 1),
+                delete_count: None,
+                insert_text: Some(format!("\n\n{}", block_text)),
+            }
+        } else {
+            // The region holds only definitions: rewrite the first in place
+            let target = definitions[0].line_number - 1;
+            delete.remove(&target);
+            FixInfo {
+                line_number: Some(target + 1),
+                edit_column: Some(1),
+                delete_count: Some(text_of(lines[target]).len() as i32),
+                insert_text: Some(block_text),
+            }
+        };
+
+        for (i, (definition, detail)) in reported.into_iter().enumerate() {
+            let fix_info = if i == 0 {
+                Some(insertion.clone())
+            } else {
+                let own_line = definition.line_number - 1;
+                delete.remove(&own_line).then_some(FixInfo {
+                    line_number: None,
+                    edit_column: Some(1),
+                    delete_count: Some(-1),
+                    insert_text: None,
+                })
+            };
+            errors.push(self.error(definition.line_number, detail, fix_info));
+        }
+        for idx in delete {
+            errors.push(self.delete_line(first.line_number, idx + 1));
+        }
+    }
+}
+
+impl Rule for MKD004 {
+    fn names(&self) -> &'static [&'static str] {
+        &["MKD004", "link-ref-style"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Link reference definitions should be grouped at the end"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["links", "fixable"]
+    }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::None
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        let by_section = params.config.get("position").and_then(|v| v.as_str()) == Some("section");
+        let sorted = params
+            .config
+            .get("sorted")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+        let lines = params.lines;
+        let code_block_lines = params.code_block_lines();
+        let inline = InlineConfig::from_lines(lines);
+        let front_matter = params.front_matter_lines.len();
+
+        // Footnotes (`[^1]: ...`) share the syntax but are not links
+        let definitions: Vec<ReferenceDefinition> = params
+            .reference_definitions()
+            .iter()
+            .filter(|d| {
+                d.line_number > front_matter
+                    && !d.label.starts_with('^')
+                    && !inline.is_disabled(d.line_number, self.names())
+            })
+            .cloned()
+            .collect();
+        if definitions.is_empty() {
+            return Vec::new();
+        }
+
+        let mut errors = Vec::new();
+        let mut first_seen: HashMap<String, usize> = HashMap::new();
+        for definition in &definitions {
+            let first = *first_seen
+                .entry(normalize_label(&definition.label))
+                .or_insert(definition.line_number);
+            if first != definition.line_number {
+                errors.push(self.error(
+                    definition.line_number,
+                    format!(
+                        "Duplicate definition [{}] (first defined on line {})",
+                        definition.label, first
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        let mut starts = vec![0];
+        if by_section {
+            starts.extend(
+                (1..lines.len())
+                    .filter(|&idx| !code_block_lines[idx] && is_atx_heading(lines[idx])),
+            );
+        }
+        let place = if by_section {
+            "its section"
+        } else {
+            "the document"
+        };
+
+        for (i, &start) in starts.iter().enumerate() {
+            let end = starts.get(i + 1).copied().unwrap_or(lines.len());
+            let in_region: Vec<&ReferenceDefinition> = definitions
+                .iter()
+                .filter(|d| (start..end).contains(&(d.line_number - 1)))
+                .collect();
+            if in_region.is_empty() {
+                continue;
+            }
+            self.check_region(
+                lines,
+                &code_block_lines,
+                &in_region,
+                (start, end),
+                i + 1 == starts.len(),
+                sorted,
+                place,
+                &mut errors,
+            );
+        }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Config;
+
+    fn lint(content: &str, options: &str) -> (Vec<LintError>, String) {
+        let config: Config =
+            serde_json::from_str(&format!(r#"{{"default": false, "MKD004": {}}}"#, options))
+                .unwrap();
+        let errors = crate::lint_string(content, &config).unwrap();
+        let fixed = crate::fix_content(content, &config).unwrap();
+        assert!(
+            crate::lint_string(&fixed, &config)
+                .unwrap()
+                .iter()
+                .all(|e| e.fix_info.is_none()),
+            "fix should converge:\n{}",
+            fixed
+        );
+        (errors, fixed)
+    }
+
+    fn reported(errors: &[LintError]) -> Vec<(usize, &str)> {
+        errors
+            .iter()
+            .filter(|e| !e.fix_only)
+            .map(|e| (e.line_number, e.error_detail.as_deref().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_mkd004_sorted_bottom_block_is_clean() {
+        let content = "# Title\n\nSee [a] and [b].\n\n[a]: a.md\n[b]: b.md\n";
+        let (errors, fixed) = lint(content, "true");
+        assert!(errors.is_empty());
+        assert_eq!(fixed, content);
+    }
+
+    #[test]
+    fn test_mkd004_consolidates_scattered_definitions() {
+        let content = "# Title\n\n[zeta]: z.md\n\nSee [zeta], [Alpha] and [mid].\n\n\
+                       [mid]:\n  m.md \"Mid\"\n\nMore text.\n\n[Alpha]: a.md\n";
+        let (errors, fixed) = lint(content, "true");
+        assert_eq!(
+            reported(&errors),
+            [
+                (3, "Definition [zeta] should be at the end of the document"),
+                (7, "Definition [mid] should be at the end of the document"),
+            ]
+        );
+        assert!(errors[0].fix_info.is_some());
+        assert_eq!(
+            fixed,
+            "# Title\n\nSee [zeta], [Alpha] and [mid].\n\nMore text.\n\n\
+             [Alpha]: a.md\n[mid]:\n  m.md \"Mid\"\n[zeta]: z.md\n"
+        );
+    }
+
+    #[test]
+    fn test_mkd004_sorts_bottom_block() {
+        let content = "# Title\n\nText\n\n[b]: b.md\n[c]: c.md\n[A]: a.md\n";
+        let (errors, fixed) = lint(content, "true");
+        assert_eq!(
+            reported(&errors),
+            [(7, "Definition [A] should be sorted before [c]")]
+        );
+        assert_eq!(
+            fixed,
+            "# Title\n\nText\n\n[A]: a.md\n[b]: b.md\n[c]: c.md\n"
+        );
+    }
+
+    #[test]
+    fn test_mkd004_unsorted_allowed() {
+        let content = "# Title\n\n[b]: b.md\n\nText\n\n[c]: c.md\n[a]: a.md\n";
+        let (errors, fixed) = lint(content, r#"{"sorted": false}"#);
+        assert_eq!(reported(&errors).len(), 1);
+        assert_eq!(
+            fixed,
+            "# Title\n\nText\n\n[b]: b.md\n[c]: c.md\n[a]: a.md\n"
+        );
+    }
+
+    #[test]
+    fn test_mkd004_section_position() {
+        let content = "# One\n\n[b]: b.md\nText [b] [a].\n\n[a]: a.md\n\n\
+                       ## Two\n\n[c]: c.md\n\nText [c].\n";
+        let (errors, fixed) = lint(content, r#"{"position": "section"}"#);
+        assert_eq!(
+            reported(&errors),
+            [
+                (3, "Definition [b] should be at the end of its section"),
+                (10, "Definition [c] should be at the end of its section"),
+            ]
+        );
+        assert_eq!(
+            fixed,
+            "# One\n\nText [b] [a].\n\n[a]: a.md\n[b]: b.md\n\n## Two\n\nText [c].\n\n[c]: c.md\n"
+        );
+    }
+
+    #[test]
+    fn test_mkd004_ignores_code_and_block_quotes() {
+        let content = "# Title\n\n```md\n[x]: x.md\n```\n\n> [q]: q.md\n\nText\n\n[a]: a.md\n";
+        let (errors, _) = lint(content, "true");
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_mkd004_reports_duplicates() {
+        let content = "# Title\n\nText\n\n[a]: one.md\n[A]: two.md\n";
+        let (errors, fixed) = lint(content, r#"{"sorted": false}"#);
+        assert_eq!(
+            reported(&errors),
+            [(6, "Duplicate definition [A] (first defined on line 5)")]
+        );
+        assert!(errors[0].fix_info.is_none());
+        assert_eq!(fixed, content);
+    }
+
+    #[test]
+    fn test_mkd004_crlf_round_trip() {
+        let content = "# Title\r\n\r\n[b]: b.md\r\nText\r\n\r\n[a]: a.md\r\n";
+        let (_, fixed) = lint(content, "true");
+        assert_eq!(
+            fixed,
+            "# Title\r\n\r\nText\r\n\r\n[a]: a.md\r\n[b]: b.md\r\n"
+        );
+    }
+}
@@ -0,0 +1,301 @@
+//! MKD005 - Heading capitalization should match the configured style
+//!
+//! Opt-in house-style rule for ATX headings: `case: "sentence"` (the
+//! default) capitalizes only the first word, `case: "title"` every word but
+//! short articles, conjunctions and prepositions. Words listed in
+//! `proper_nouns` or MD044's `names` keep their spelling, and so do words
+//! with a capital letter after the first character ("API", "GitHub").
+//!
+//! Code spans, link destinations and HTML are not checked.
+
+use crate::helpers::parse_headings;
+use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+use std::collections::HashMap;
+
+pub struct MKD005;
+
+/// Words left lowercase inside a title-case heading
+const MINOR_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "in", "into", "nor", "of", "on",
+    "or", "per", "so", "the", "to", "via", "vs", "with", "yet",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Case {
+    Sentence,
+    Title,
+}
+
+impl Case {
+    fn name(self) -> &'static str {
+        match self {
+            Case::Sentence => "sentence",
+            Case::Title => "title",
+        }
+    }
+}
+
+/// Byte ranges of the words to check in heading text: runs of letters,
+/// digits and apostrophes, outside code spans, link destinations, autolinks
+/// and HTML tags
+fn word_ranges(text: &str) -> Vec<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut ranges = Vec::new();
+    let mut word_start = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = text[i..].chars().next().unwrap_or_default();
+        if c.is_alphanumeric() || c == '\'' || c == '\u{2019}' {
+            word_start.get_or_insert(i);
+            i += c.len_utf8();
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            ranges.push((start, i));
+        }
+        // Skip over spans whose text is not prose
+        let skip_to = match c {
+            '`' => {
+                let run = bytes[i..].iter().take_while(|&&b| b == b'`').count();
+                let fence = &text[i..i + run];
+                text[i + run..]
+                    .find(fence)
+                    .map(|end| i + run + end + run)
+                    .unwrap_or(i + run)
+            }
+            ']' if text[i..].starts_with("](") => {
+                text[i..].find(')').map_or(bytes.len(), |end| i + end + 1)
+            }
+            '<' => text[i..].find('>').map_or(i + 1, |end| i + end + 1),
+            _ => i + c.len_utf8(),
+        };
+        i = skip_to;
+    }
+    if let Some(start) = word_start {
+        ranges.push((start, bytes.len()));
+    }
+    ranges
+}
+
+/// `word` with its first letter in uppercase
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// `text` rewritten in `case`; `proper_nouns` maps lowercase words to their
+/// required spelling
+fn apply_case(text: &str, case: Case, proper_nouns: &HashMap<String, String>) -> String {
+    let ranges = word_ranges(text);
+    // The first word is the first one starting with a letter, so the
+    // number in `2. Introduction` or `2024 Roadmap` doesn't take its place
+    let first = ranges
+        .iter()
+        .position(|&(start, end)| text[start..end].starts_with(char::is_alphabetic));
+    let mut fixed = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (index, &(start, end)) in ranges.iter().enumerate() {
+        let word = &text[start..end];
+        let lower = word.to_lowercase();
+        let expected = if let Some(noun) = proper_nouns.get(&lower) {
+            noun.clone()
+        } else if word.chars().skip(1).any(char::is_uppercase)
+            || !word.starts_with(char::is_alphabetic)
+            || word == "I"
+            || word.starts_with("I'")
+            || word.starts_with("I\u{2019}")
+        {
+            word.to_string()
+        } else if Some(index) == first {
+            capitalize(word)
+        } else {
+            match case {
+                Case::Sentence => lower,
+                Case::Title
+                    if index + 1 < ranges.len() && MINOR_WORDS.contains(&lower.as_str()) =>
+                {
+                    lower
+                }
+                Case::Title => capitalize(word),
+            }
+        };
+        fixed.push_str(&text[copied..start]);
+        fixed.push_str(&expected);
+        copied = end;
+    }
+    fixed.push_str(&text[copied..]);
+    fixed
+}
+
+/// String entries of a JSON array option
+fn string_list(value: Option<&serde_json::Value>) -> impl Iterator<Item = &str> {
+    value
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+}
+
+impl Rule for MKD005 {
+    fn names(&self) -> &'static [&'static str] {
+        &["MKD005", "heading-capitalization"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Heading capitalization should match the configured style"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["headings", "fixable"]
+    }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::None
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        let case = match params.config.get("case").and_then(|v| v.as_str()) {
+            Some("title") => Case::Title,
+            _ => Case::Sentence,
+        };
+        // Multi-word names count word by word
+        let proper_nouns: HashMap<String, String> = string_list(params.config.get("proper_nouns"))
+            .chain(string_list(params.sibling_option("MD044", "names")))
+            .flat_map(str::split_whitespace)
+            .map(|word| (word.to_lowercase(), word.to_string()))
+            .collect();
+        let code_block_lines = params.code_block_lines();
+
+        let mut errors = Vec::new();
+        for heading in parse_headings(params.lines) {
+            if code_block_lines
+                .get(heading.line_index)
+                .copied()
+                .unwrap_or(false)
+            {
+                continue;
+            }
+            let fixed = apply_case(&heading.text, case, &proper_nouns);
+            if fixed == heading.text {
+                continue;
+            }
+            let line = params.lines[heading.line_index];
+            let hashes = line.find('#').unwrap_or(0) + heading.level;
+            let Some(offset) = line[hashes..].find(&heading.text) else {
+                continue;
+            };
+            let column = hashes + offset + 1;
+            errors.push(LintError {
+                line_number: heading.line_index + 1,
+                rule_names: self.names(),
+                rule_description: self.description(),
+                error_detail: Some(format!("Expected {} case: \"{}\"", case.name(), fixed)),
+                error_context: Some(heading.text.clone()),
+                rule_information: self.information(),
+                error_range: Some((column, heading.text.len())),
+                fix_info: Some(FixInfo {
+                    line_number: None,
+                    edit_column: Some(column),
+                    delete_count: Some(heading.text.len() as i32),
+                    insert_text: Some(fixed),
+                }),
+                suggestion: Some(format!("Write the heading in {} case", case.name())),
+                severity: Severity::Error,
+                fix_only: false,
+                line_text: None,
+                related: None,
+            });
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn lint(content: &str, options: &str) -> (Vec<LintError>, String) {
+        let config: Config =
+            serde_json::from_str(&format!(r#"{{"default": false, "MKD005": {}}}"#, options))
+                .unwrap();
+        let errors = crate::lint_string(content, &config).unwrap();
+        let fixed = crate::fix_content(content, &config).unwrap();
+        (errors, fixed)
+    }
+
+    #[test]
+    fn test_mkd005_sentence_case_fix_keeps_acronyms() {
+        let content = "# This Is A Heading\n\n## Using The API\n\n## Already fine\n";
+        let (errors, fixed) = lint(content, "true");
+        let lines: Vec<usize> = errors.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, [1, 3]);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Expected sentence case: \"This is a heading\"")
+        );
+        assert_eq!(
+            fixed,
+            "# This is a heading\n\n## Using the API\n\n## Already fine\n"
+        );
+    }
+
+    #[test]
+    fn test_mkd005_proper_nouns() {
+        let content = "# Getting Started With Rust And Github\n";
+        let (_, fixed) = lint(content, r#"{"proper_nouns": ["Rust", "GitHub"]}"#);
+        assert_eq!(fixed, "# Getting started with Rust and GitHub\n");
+
+        // MD044's names count as proper nouns too
+        let config: Config = serde_json::from_str(
+            r#"{"default": false, "MKD005": true, "MD044": {"names": ["Rust"]}}"#,
+        )
+        .unwrap();
+        let fixed = crate::fix_content("# Learning Rust\n", &config).unwrap();
+        assert_eq!(fixed, "# Learning Rust\n");
+    }
+
+    #[test]
+    fn test_mkd005_numbered_headings_capitalize_first_word() {
+        let content = "# 2. Introduction\n\n## 1. First Step\n\n## 2024 Roadmap\n\n## 3. the end\n";
+        let (errors, fixed) = lint(content, "true");
+        let lines: Vec<usize> = errors.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, [3, 7]);
+        assert_eq!(
+            fixed,
+            "# 2. Introduction\n\n## 1. First step\n\n## 2024 Roadmap\n\n## 3. The end\n"
+        );
+
+        let (errors, _) = lint("# 1. A Guide to Testing\n", r#"{"case": "title"}"#);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_mkd005_title_case() {
+        let content = "# a guide to the command line\n\n## What Is It For\n";
+        let (errors, fixed) = lint(content, r#"{"case": "title"}"#);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            fixed,
+            "# A Guide to the Command Line\n\n## What Is It For\n"
+        );
+    }
+
+    #[test]
+    fn test_mkd005_skips_code_links_and_code_blocks() {
+        let content =
+            "# Run `Cargo Build` With [The Docs](Docs/Page.md)\n\n```md\n# Not A Heading\n```\n";
+        let (_, fixed) = lint(content, "true");
+        assert_eq!(
+            fixed,
+            "# Run `Cargo Build` with [the docs](Docs/Page.md)\n\n```md\n# Not A Heading\n```\n"
+        );
+    }
+}
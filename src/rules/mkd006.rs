@@ -0,0 +1,341 @@
+//! MKD006 - Inline configuration directives should be valid
+//!
+//! Reports `<!-- markdownlint-... -->` directives that do nothing:
+//!
+//! - rule names matching no rule, built-in or custom (a typo, or a rule
+//!   that has been removed),
+//! - `enable` and `enable-file` directives for rules that are not disabled
+//!   at that point,
+//! - `disable` directives that suppressed no errors during the run (a
+//!   suppression left behind after the violation was fixed).
+//!
+//! Only the enable check can be made from the document alone; the linter
+//! reports the other two through [`inline_config_errors`], since they depend on which
+//! rules are registered and which errors the directives suppressed.
+//!
+//! Warnings by default, so `--severity error` runs can leave them out.
+//! With `--report-unused-disable`, unused disables are reported for every
+//! rule and can be fixed; see [`unused_disable_errors`].
+
+use crate::lint::inline::InlineConfig;
+use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+use std::collections::{HashMap, HashSet};
+
+pub struct MKD006;
+
+impl MKD006 {
+    fn error(&self, line_number: usize, detail: String, context: Option<String>) -> LintError {
+        LintError {
+            line_number,
+            rule_names: self.names(),
+            rule_description: self.description(),
+            error_detail: Some(detail),
+            error_context: context,
+            rule_information: self.information(),
+            error_range: None,
+            fix_info: None,
+            suggestion: Some("Remove the directive or fix its rule names".to_string()),
+            severity: Severity::Warning,
+            fix_only: false,
+            line_text: None,
+            related: None,
+        }
+    }
+}
+
+impl Rule for MKD006 {
+    fn names(&self) -> &'static [&'static str] {
+        &["MKD006", "inline-config-valid"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Inline configuration directives should be valid"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["inline_config"]
+    }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::None
+    }
+
+    fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        let inline = match params.global_config {
+            Some(config) => InlineConfig::from_lines_with_config(params.lines, config),
+            None => InlineConfig::from_lines(params.lines),
+        };
+        inline
+            .unmatched_enables()
+            .into_iter()
+            .map(|(line_number, rule)| match rule {
+                Some(rule) => self.error(
+                    line_number,
+                    format!("Enables {}, which is not disabled here", rule),
+                    Some(rule),
+                ),
+                None => self.error(
+                    line_number,
+                    "Enables all rules, but none are disabled here".to_string(),
+                    None,
+                ),
+            })
+            .collect()
+    }
+}
+
+/// Errors for the directives in `inline` that name unknown rules or, when
+/// `report_unused`, suppressed nothing.
+///
+/// `options` are MKD006's options (`known_rules`); `custom_names` the
+/// uppercased names of the run's custom rules; `ran` the uppercased IDs of
+/// the rules run on the file; `hits` the `(directive line, rule name)`
+/// pairs that suppressed an error, as returned by
+/// [`InlineConfig::disabled_by`]. A disable is only reported for rule names
+/// that ran, since one for a rule turned off in the config is expected to
+/// suppress nothing.
+pub(crate) fn inline_config_errors(
+    inline: &InlineConfig,
+    options: &HashMap<String, serde_json::Value>,
+    custom_names: &HashSet<String>,
+    ran: &HashSet<String>,
+    hits: &HashSet<(usize, String)>,
+    report_unused: bool,
+) -> Vec<LintError> {
+    let known: HashSet<String> = options
+        .get("known_rules")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+        .map(str::to_uppercase)
+        .collect();
+
+    let mut errors: Vec<LintError> = inline
+        .unknown_rules()
+        .iter()
+        .filter(|(_, name)| !known.contains(name) && !custom_names.contains(name))
+        .map(|(line_number, name)| {
+            MKD006.error(
+                *line_number,
+                format!("Unknown rule: {}", name),
+                Some(name.clone()),
+            )
+        })
+        .collect();
+
+    let unused = if report_unused {
+        unused_disables(inline, hits, |rule| ran.contains(rule))
+    } else {
+        Vec::new()
+    };
+    for (line_number, _, unused) in unused {
+        for rule in unused {
+            errors.push(if rule.is_empty() {
+                MKD006.error(
+                    line_number,
+                    "Disables all rules, but suppressed no errors".to_string(),
+                    None,
+                )
+            } else {
+                MKD006.error(
+                    line_number,
+                    format!("Disables {}, but suppressed no {} errors", rule, rule),
+                    Some(rule.to_string()),
+                )
+            });
+        }
+    }
+
+    errors.sort_by_key(|e| e.line_number);
+    errors
+}
+
+/// Fixable errors for every disable directive in `inline` that suppressed
+/// no errors for some of its rules (`--report-unused-disable`), whether or
+/// not those rules ran; `lines` are the document's lines and `hits` as for
+/// [`inline_config_errors`].
+///
+/// The fix removes the directive's line, or only the unused rule names
+/// when some of its rules did suppress errors.
+pub(crate) fn unused_disable_errors(
+    inline: &InlineConfig,
+    lines: &[&str],
+    hits: &HashSet<(usize, String)>,
+) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    for (line_number, rules, unused) in unused_disables(inline, hits, |_| true) {
+        let Some(line) = lines.get(line_number - 1) else {
+            continue;
+        };
+        let error = if rules.iter().all(|rule| unused.contains(&rule.as_str())) {
+            let mut error = MKD006.error(
+                line_number,
+                "Unused disable directive: suppressed no errors".to_string(),
+                None,
+            );
+            error.suggestion = Some("Remove the directive".to_string());
+            error.fix_info = Some(FixInfo {
+                line_number: None,
+                edit_column: Some(1),
+                delete_count: Some(-1),
+                insert_text: None,
+            });
+            error
+        } else {
+            let mut error = MKD006.error(
+                line_number,
+                format!("Unused rules in disable directive: {}", unused.join(", ")),
+                Some(unused.join(", ")),
+            );
+            error.suggestion = Some("Remove the unused rules from the directive".to_string());
+            error.fix_info = InlineConfig::without_rules(line, &unused).map(|fixed| FixInfo {
+                line_number: None,
+                edit_column: Some(1),
+                delete_count: Some(line.trim_end_matches(['\n', '\r']).len() as i32),
+                insert_text: Some(fixed),
+            });
+            error
+        };
+        errors.push(error);
+    }
+    errors
+}
+
+/// Disable directives with rule names that suppressed no errors, as
+/// `(line_number, rules, unused rules)`: only names `counts` accepts are
+/// considered, and a directive naming no rules is unused as a whole (its
+/// unused rules are then `[""]`)
+fn unused_disables<'a>(
+    inline: &'a InlineConfig,
+    hits: &HashSet<(usize, String)>,
+    counts: impl Fn(&str) -> bool,
+) -> Vec<(usize, &'a [String], Vec<&'a str>)> {
+    inline
+        .disables()
+        .filter_map(|(line_number, rules)| {
+            let unused: Vec<&str> = if rules.is_empty() {
+                std::iter::once("")
+                    .filter(|_| !hits.contains(&(line_number, String::new())))
+                    .collect()
+            } else {
+                let mut unused: Vec<&str> = rules
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|rule| counts(rule) && !hits.contains(&(line_number, rule.to_string())))
+                    .collect();
+                unused.dedup();
+                unused
+            };
+            (!unused.is_empty()).then_some((line_number, rules, unused))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn details(content: &str, config: &str) -> Vec<(usize, String)> {
+        let config: Config = serde_json::from_str(config).unwrap();
+        crate::lint_string(content, &config)
+            .unwrap()
+            .into_iter()
+            .filter(|e| e.rule_names[0] == "MKD006")
+            .map(|e| {
+                assert_eq!(e.severity, Severity::Warning);
+                (e.line_number, e.error_detail.unwrap_or_default())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mkd006_unknown_rules() {
+        let content = "# Title\n\n<!-- markdownlint-disable-next-line MD999 no-trailing-space custom-rule -->\ntext\n";
+        assert_eq!(
+            details(content, r#"{"MKD006": {"known_rules": ["Custom-Rule"]}}"#),
+            [
+                (3, "Unknown rule: MD999".to_string()),
+                (3, "Unknown rule: NO-TRAILING-SPACE".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mkd006_unmatched_enables() {
+        let content = "# Title\n\n<!-- markdownlint-disable MD009 -->\ntext  \n<!-- markdownlint-enable MD009 MD010 -->\n\n<!-- markdownlint-enable -->\n";
+        assert_eq!(
+            details(content, "{}"),
+            [
+                (5, "Enables MD010, which is not disabled here".to_string()),
+                (
+                    7,
+                    "Enables all rules, but none are disabled here".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mkd006_unused_disables() {
+        let content = "# Title\n\n<!-- markdownlint-disable-next-line MD009 MD010 -->\ntext  \n\n<!-- markdownlint-disable MD034 -->\nclean text\n<!-- markdownlint-disable-file -->\n";
+        assert_eq!(
+            details(content, "{}"),
+            [
+                (
+                    3,
+                    "Disables MD010, but suppressed no MD010 errors".to_string()
+                ),
+                (
+                    6,
+                    "Disables MD034, but suppressed no MD034 errors".to_string()
+                ),
+                (
+                    8,
+                    "Disables all rules, but suppressed no errors".to_string()
+                ),
+            ]
+        );
+        // A rule turned off in the config is expected to suppress nothing
+        assert_eq!(
+            details(content, r#"{"MD010": false, "MD034": false}"#),
+            [(
+                8,
+                "Disables all rules, but suppressed no errors".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_mkd006_report_unused_disables_fixes() {
+        let content = "# Title\n\n<!-- markdownlint-disable-next-line MD009, MD010 -->\ntext  \n\n<!-- markdownlint-disable MD034 MD999 -->\nclean text\n";
+        let config: Config = serde_json::from_str(r#"{"MKD006": false, "MD034": false}"#).unwrap();
+        let options = crate::LintOptions::new()
+            .with_string("doc.md", content)
+            .report_unused_disables();
+        let options = crate::LintOptions {
+            config: Some(config),
+            ..options
+        };
+        let results = crate::lint_sync(&options).unwrap();
+        let errors = results.get("doc.md").unwrap();
+        let details: Vec<(usize, &str)> = errors
+            .iter()
+            .map(|e| (e.line_number, e.error_detail.as_deref().unwrap_or_default()))
+            .collect();
+        // MD034 is off, and unknown names are MKD006's own check
+        assert_eq!(
+            details,
+            [
+                (3, "Unused rules in disable directive: MD010"),
+                (6, "Unused disable directive: suppressed no errors"),
+            ]
+        );
+        assert!(errors.iter().all(|e| e.severity == Severity::Warning));
+        assert_eq!(
+            crate::apply_fixes(content, errors),
+            "# Title\n\n<!-- markdownlint-disable-next-line MD009 -->\ntext  \n\nclean text\n"
+        );
+    }
+}
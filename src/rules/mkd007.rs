@@ -0,0 +1,454 @@
+//! MKD007 - Link titles should match the configured style
+//!
+//! Opt-in house-style rule for the titles of inline links and link
+//! reference definitions: `style: "none"` forbids titles, `style:
+//! "required-external"` requires one on every `http://` or `https://`
+//! link, and `style: "any"` (the default) leaves their presence alone.
+//! `quote` requires titles to be delimited by `"double"` quotes, `'single'`
+//! quotes or `(parentheses)`, and the fix re-quotes them.
+//!
+//! Reference links are checked through their definitions. Links and
+//! definitions spanning several lines are not checked, nor are images.
+
+use crate::helpers::is_url;
+use crate::parser::TokenExt;
+use crate::types::{FixInfo, LintError, ParserType, Rule, RuleParams, Severity};
+
+pub struct MKD007;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Any,
+    None,
+    RequiredExternal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quote {
+    Double,
+    Single,
+    Paren,
+}
+
+impl Quote {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "double" => Some(Quote::Double),
+            "single" => Some(Quote::Single),
+            "paren" => Some(Quote::Paren),
+            _ => None,
+        }
+    }
+
+    fn from_closer(c: u8) -> Option<Self> {
+        match c {
+            b'"' => Some(Quote::Double),
+            b'\'' => Some(Quote::Single),
+            b')' => Some(Quote::Paren),
+            _ => None,
+        }
+    }
+
+    fn delimiters(self) -> (char, char) {
+        match self {
+            Quote::Double => ('"', '"'),
+            Quote::Single => ('\'', '\''),
+            Quote::Paren => ('(', ')'),
+        }
+    }
+
+    /// Whether `c` has to be escaped in a title delimited this way
+    fn delimits(self, c: char) -> bool {
+        let (open, close) = self.delimiters();
+        c == open || c == close
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Quote::Double => "double quotes",
+            Quote::Single => "single quotes",
+            Quote::Paren => "parentheses",
+        }
+    }
+}
+
+/// Where a title is written on its line, as byte offsets
+struct Title {
+    /// Start of the whitespace separating it from the destination
+    gap: usize,
+    /// Opening delimiter
+    start: usize,
+    /// Just after the closing delimiter
+    end: usize,
+    /// End of the whitespace after it: the link's `)`, or the end of a
+    /// definition's line
+    tail: usize,
+    quote: Quote,
+}
+
+/// The title of the inline link spanning bytes `start..end` of `line`,
+/// which ends with `)`. Only call this for links that have a title: the
+/// text before the `)` is then the title's closing delimiter.
+fn inline_link_title(line: &str, start: usize, end: usize) -> Option<Title> {
+    let bytes = line.as_bytes();
+    let tail = end - 1;
+    let mut close = tail;
+    while close > start && matches!(bytes[close - 1], b' ' | b'\t') {
+        close -= 1;
+    }
+    let quote = Quote::from_closer(*bytes.get(close.checked_sub(1)?)?)?;
+    let (open, _) = quote.delimiters();
+    // A title can't hold its unescaped opening delimiter
+    let mut open_at = close - 1;
+    loop {
+        open_at = line[start..open_at].rfind(open)? + start;
+        let backslashes = line[start..open_at]
+            .bytes()
+            .rev()
+            .take_while(|&b| b == b'\\')
+            .count();
+        if backslashes.is_multiple_of(2) {
+            break;
+        }
+    }
+    let gap = line[start..open_at].trim_end_matches([' ', '\t']).len() + start;
+    (gap < open_at).then_some(Title {
+        gap,
+        start: open_at,
+        end: close,
+        tail,
+        quote,
+    })
+}
+
+/// The title of a reference definition whose destination starts at byte
+/// `from` of `line`
+fn definition_title(line: &str, from: usize) -> Option<Title> {
+    let text = line.trim_end_matches(['\n', '\r']);
+    let rest = text.get(from..)?;
+    let dest_start = from + (rest.len() - rest.trim_start().len());
+    let dest = &text[dest_start..];
+    let dest_len = match dest.strip_prefix('<') {
+        Some(inner) => inner.find('>')? + 2,
+        None => dest.find([' ', '\t']).unwrap_or(dest.len()),
+    };
+    let gap = dest_start + dest_len;
+    let after = &text[gap..];
+    let title = after.trim();
+    let start = gap + (after.len() - after.trim_start().len());
+    if title.len() < 2 || start == gap {
+        return None;
+    }
+    let quote = Quote::from_closer(title.as_bytes()[title.len() - 1])?;
+    (title.starts_with(quote.delimiters().0)).then_some(Title {
+        gap,
+        start,
+        end: start + title.len(),
+        tail: text.len(),
+        quote,
+    })
+}
+
+/// `title`, written with `from` delimiters, rewritten with `to` ones: the
+/// escapes the old delimiters needed are dropped, and the new ones escaped
+fn requote(title: &str, from: Quote, to: Quote) -> String {
+    let (open, close) = to.delimiters();
+    let inner = &title[1..title.len() - 1];
+    let mut out = String::with_capacity(title.len() + 2);
+    out.push(open);
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some(next) if from.delimits(next) && !to.delimits(next) => out.push(next),
+                Some(next) => {
+                    out.push('\\');
+                    out.push(next);
+                }
+                None => out.push('\\'),
+            }
+        } else {
+            if to.delimits(c) {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+    }
+    out.push(close);
+    out
+}
+
+impl MKD007 {
+    fn error(
+        &self,
+        line_number: usize,
+        detail: String,
+        range: (usize, usize),
+        fix_info: Option<FixInfo>,
+        suggestion: &str,
+    ) -> LintError {
+        LintError {
+            line_number,
+            rule_names: self.names(),
+            rule_description: self.description(),
+            error_detail: Some(detail),
+            error_context: None,
+            rule_information: self.information(),
+            error_range: Some(range),
+            fix_info,
+            suggestion: Some(suggestion.to_string()),
+            severity: Severity::Error,
+            fix_only: false,
+            line_text: None,
+            related: None,
+        }
+    }
+
+    /// Check one link or definition: `span` is the `(column, length)` of
+    /// the whole link, `title` its title if it has one
+    #[allow(clippy::too_many_arguments)]
+    fn check(
+        &self,
+        errors: &mut Vec<LintError>,
+        line_number: usize,
+        line: &str,
+        span: (usize, usize),
+        url: &str,
+        title: Option<Title>,
+        style: Style,
+        quote: Option<Quote>,
+    ) {
+        let Some(title) = title else {
+            if style == Style::RequiredExternal && is_url(url) {
+                errors.push(self.error(
+                    line_number,
+                    format!("External link has no title: {}", url),
+                    span,
+                    None,
+                    "Add a title after the destination: [text](url \"Title\")",
+                ));
+            }
+            return;
+        };
+        let written = &line[title.start..title.end];
+        let range = (title.start + 1, written.len());
+        if style == Style::None {
+            errors.push(self.error(
+                line_number,
+                format!("Link has a title: {}", written),
+                range,
+                Some(FixInfo {
+                    line_number: None,
+                    edit_column: Some(title.gap + 1),
+                    delete_count: Some((title.tail - title.gap) as i32),
+                    insert_text: None,
+                }),
+                "Remove the link title",
+            ));
+        } else if let Some(quote) = quote
+            && quote != title.quote
+        {
+            errors.push(self.error(
+                line_number,
+                format!("Expected a title in {}: {}", quote.describe(), written),
+                range,
+                Some(FixInfo {
+                    line_number: None,
+                    edit_column: Some(title.start + 1),
+                    delete_count: Some(written.len() as i32),
+                    insert_text: Some(requote(written, title.quote, quote)),
+                }),
+                &format!("Delimit the title with {}", quote.describe()),
+            ));
+        }
+    }
+}
+
+impl Rule for MKD007 {
+    fn names(&self) -> &'static [&'static str] {
+        &["MKD007", "link-title-style"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Link titles should match the configured style"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["links", "fixable"]
+    }
+
+    fn parser_type(&self) -> ParserType {
+        ParserType::Micromark
+    }
+
+    fn is_enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+        let style = match params.config.get("style").and_then(|v| v.as_str()) {
+            Some("none") => Style::None,
+            Some("required-external") => Style::RequiredExternal,
+            _ => Style::Any,
+        };
+        let quote = params
+            .config
+            .get("quote")
+            .and_then(|v| v.as_str())
+            .and_then(Quote::from_name);
+
+        let mut errors = Vec::new();
+        for link in params.tokens.filter_by_type("link") {
+            if link.start_line != link.end_line {
+                continue;
+            }
+            let Some(line) = params.lines.get(link.start_line - 1) else {
+                continue;
+            };
+            let (start, end) = (link.start_column - 1, link.end_column);
+            // Reference links end with `]`, autolinks with `>` or the URL
+            if line.as_bytes().get(end - 1) != Some(&b')') {
+                continue;
+            }
+            let has_title = link.metadata.get("title").is_some_and(|t| !t.is_empty());
+            let title = has_title
+                .then(|| inline_link_title(line, start, end))
+                .flatten();
+            if has_title && title.is_none() {
+                continue;
+            }
+            self.check(
+                &mut errors,
+                link.start_line,
+                line,
+                (start + 1, end - start),
+                link.link_url().unwrap_or_default(),
+                title,
+                style,
+                quote,
+            );
+        }
+
+        for definition in params.reference_definitions().iter() {
+            let (index, from) = if definition.line_count == 1 {
+                let line = params.lines[definition.line_number - 1];
+                let Some(colon) = line.find("]:") else {
+                    continue;
+                };
+                (definition.line_number - 1, colon + 2)
+            } else {
+                (definition.line_number, 0)
+            };
+            let line = params.lines[index];
+            let title = definition_title(line, from).filter(|t| t.end - t.start > 2);
+            let length = line.trim_end_matches(['\n', '\r']).len();
+            self.check(
+                &mut errors,
+                index + 1,
+                line,
+                (1, length),
+                &definition.destination,
+                title,
+                style,
+                quote,
+            );
+        }
+
+        errors.sort_by_key(|e| e.line_number);
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn lint(content: &str, options: &str) -> (Vec<LintError>, String) {
+        let config: Config =
+            serde_json::from_str(&format!(r#"{{"default": false, "MKD007": {}}}"#, options))
+                .unwrap();
+        let errors = crate::lint_string(content, &config).unwrap();
+        let fixed = crate::fix_content(content, &config).unwrap();
+        // Fixing leaves only the errors without a fix
+        let unfixable = errors.iter().filter(|e| e.fix_info.is_none()).count();
+        assert_eq!(
+            crate::lint_string(&fixed, &config).unwrap().len(),
+            unfixable
+        );
+        (errors, fixed)
+    }
+
+    #[test]
+    fn test_mkd007_none_removes_titles() {
+        let content = "See [a](https://a.com \"A\") and [b](b.md 'B' ), [c](c.md (C)).\n\nA [ref][r] and [plain](p.md).\n\n[r]: https://r.com \"R\"\n";
+        let (errors, fixed) = lint(content, r#"{"style": "none"}"#);
+        let lines: Vec<usize> = errors.iter().map(|e| e.line_number).collect();
+        assert_eq!(lines, [1, 1, 1, 5]);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Link has a title: \"A\"")
+        );
+        assert_eq!(errors[0].error_range, Some((23, 3)));
+        assert_eq!(
+            fixed,
+            "See [a](https://a.com) and [b](b.md), [c](c.md).\n\nA [ref][r] and [plain](p.md).\n\n[r]: https://r.com\n"
+        );
+    }
+
+    #[test]
+    fn test_mkd007_required_external() {
+        let content = "[Site](https://example.com) [Doc](doc.md) [Titled](https://t.com \"T\") <https://auto.com>\n\n[r]: https://r.com\n[s]: https://s.com 'S'\n";
+        let (errors, _) = lint(content, r#"{"style": "required-external"}"#);
+        let details: Vec<(usize, &str)> = errors
+            .iter()
+            .map(|e| (e.line_number, e.error_detail.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            details,
+            [
+                (1, "External link has no title: https://example.com"),
+                (3, "External link has no title: https://r.com"),
+            ]
+        );
+        assert!(errors.iter().all(|e| e.fix_info.is_none()));
+        assert_eq!(errors[0].error_range, Some((1, 27)));
+    }
+
+    #[test]
+    fn test_mkd007_quote_requotes_titles() {
+        let content = "[a](a.md 'It\\'s \"here\"') [b](b.md (x \\(y\\))) [c](c.md \"ok\")\n\n[r]: <r.md> 'Don\\'t'\n";
+        let (errors, fixed) = lint(content, r#"{"quote": "double"}"#);
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Expected a title in double quotes: 'It\\'s \"here\"'")
+        );
+        assert_eq!(
+            fixed,
+            "[a](a.md \"It's \\\"here\\\"\") [b](b.md \"x (y)\") [c](c.md \"ok\")\n\n[r]: <r.md> \"Don't\"\n"
+        );
+
+        let (_, fixed) = lint(
+            "[a](a.md \"Say \\\"hi\\\" (now)\")\n",
+            r#"{"quote": "paren"}"#,
+        );
+        assert_eq!(fixed, "[a](a.md (Say \"hi\" \\(now\\)))\n");
+        let (_, fixed) = lint("[a](a.md \"It's\")\n", r#"{"quote": "single"}"#);
+        assert_eq!(fixed, "[a](a.md 'It\\'s')\n");
+    }
+
+    #[test]
+    fn test_mkd007_titles_parse_as_written() {
+        // The rewritten titles must mean what the originals did
+        let content = "[a](a.md 'It\\'s \"here\"')\n";
+        let (_, fixed) = lint(content, r#"{"quote": "double"}"#);
+        let title = |text: &str| {
+            let tokens = crate::parser::parse(text);
+            let link = tokens.iter().find(|t| t.is_type("link")).unwrap();
+            link.metadata.get("title").cloned().unwrap()
+        };
+        assert_eq!(title(&fixed), title(content));
+        assert_eq!(title(&fixed), "It's \"here\"");
+    }
+}
@@ -1,144 +1,307 @@
 //! Built-in and custom rules
 
 use crate::types::{BoxedRule, Rule};
+use std::collections::HashMap;
 use std::sync::LazyLock;
 
-// ALL 64 RULES IMPLEMENTED!
-// (53 standard MD rules + 11 Kramdown extension KMD rules)
+mod docs;
+
+pub use docs::{RuleOptionDoc, rule_doc, rule_option_docs, rule_options};
+pub(crate) use mkd006::{inline_config_errors, unused_disable_errors};
+
+// ALL 71 RULES IMPLEMENTED!
+// (53 standard MD rules + 11 Kramdown extension KMD rules + 7 mkdlint MKD rules)
+// Each group is built only with its `rules-*` cargo feature (see
+// RULE_FEATURES); `rules-all`, on by default, builds every group.
+#[cfg(feature = "rules-kramdown")]
 mod kmd001;
+#[cfg(feature = "rules-kramdown")]
 mod kmd002;
+#[cfg(feature = "rules-kramdown")]
 mod kmd003;
+#[cfg(feature = "rules-kramdown")]
 mod kmd004;
+#[cfg(feature = "rules-kramdown")]
 mod kmd005;
+#[cfg(feature = "rules-kramdown")]
 mod kmd006;
+#[cfg(feature = "rules-kramdown")]
 mod kmd007;
+#[cfg(feature = "rules-kramdown")]
 mod kmd008;
+#[cfg(feature = "rules-kramdown")]
 mod kmd009;
+#[cfg(feature = "rules-kramdown")]
 mod kmd010;
+#[cfg(feature = "rules-kramdown")]
 mod kmd011;
 
+mod mkd001;
+#[cfg(feature = "rules-whitespace")]
+mod mkd002;
+mod mkd003;
+#[cfg(feature = "rules-links")]
+mod mkd004;
+#[cfg(feature = "rules-headings")]
+mod mkd005;
+mod mkd006;
+#[cfg(feature = "rules-links")]
+mod mkd007;
+
+#[cfg(feature = "rules-headings")]
 mod md001;
+#[cfg(feature = "rules-headings")]
 mod md003;
+#[cfg(feature = "rules-lists")]
 mod md004;
+#[cfg(feature = "rules-lists")]
 mod md005;
+#[cfg(feature = "rules-lists")]
 mod md007;
+#[cfg(feature = "rules-whitespace")]
 mod md009;
+#[cfg(feature = "rules-whitespace")]
 mod md010;
+#[cfg(feature = "rules-links")]
 mod md011;
+#[cfg(feature = "rules-whitespace")]
 mod md012;
+#[cfg(feature = "rules-whitespace")]
 mod md013;
+#[cfg(feature = "rules-code")]
 mod md014;
+#[cfg(feature = "rules-headings")]
 mod md018;
+#[cfg(feature = "rules-headings")]
 mod md019;
+#[cfg(feature = "rules-headings")]
 mod md020;
+#[cfg(feature = "rules-headings")]
 mod md021;
+#[cfg(feature = "rules-headings")]
 mod md022;
+#[cfg(feature = "rules-headings")]
 mod md023;
+#[cfg(feature = "rules-headings")]
 mod md024;
+#[cfg(feature = "rules-headings")]
 mod md025;
+#[cfg(feature = "rules-headings")]
 mod md026;
+#[cfg(feature = "rules-whitespace")]
 mod md027;
+#[cfg(feature = "rules-whitespace")]
 mod md028;
+#[cfg(feature = "rules-lists")]
 mod md029;
+#[cfg(feature = "rules-lists")]
 mod md030;
+#[cfg(feature = "rules-code")]
 mod md031;
+#[cfg(feature = "rules-lists")]
 mod md032;
+#[cfg(feature = "rules-style")]
 mod md033;
+#[cfg(feature = "rules-links")]
 mod md034;
+#[cfg(feature = "rules-style")]
 mod md035;
+#[cfg(feature = "rules-headings")]
 mod md036;
+#[cfg(feature = "rules-whitespace")]
 mod md037;
+#[cfg(feature = "rules-whitespace")]
 mod md038;
+#[cfg(feature = "rules-links")]
 mod md039;
+#[cfg(feature = "rules-code")]
 mod md040;
+#[cfg(feature = "rules-headings")]
 mod md041;
+#[cfg(feature = "rules-links")]
 mod md042;
+#[cfg(feature = "rules-headings")]
 mod md043;
+#[cfg(feature = "rules-style")]
 mod md044;
+#[cfg(feature = "rules-links")]
 mod md045;
+#[cfg(feature = "rules-code")]
 mod md046;
+#[cfg(feature = "rules-whitespace")]
 mod md047;
+#[cfg(feature = "rules-code")]
 mod md048;
+#[cfg(feature = "rules-style")]
 mod md049;
+#[cfg(feature = "rules-style")]
 mod md050;
+#[cfg(feature = "rules-links")]
 mod md051;
+#[cfg(feature = "rules-links")]
 mod md052;
+#[cfg(feature = "rules-links")]
 mod md053;
+#[cfg(feature = "rules-links")]
 mod md054;
+#[cfg(feature = "rules-tables")]
 mod md055;
+#[cfg(feature = "rules-tables")]
 mod md056;
+#[cfg(feature = "rules-tables")]
 mod md058;
+#[cfg(feature = "rules-style")]
 mod md059;
+#[cfg(feature = "rules-code")]
 mod md060;
 
-/// Global rule registry - standard + Kramdown extension rules
+/// Global rule registry - standard + Kramdown and mkdlint extension rules
+///
+/// Internal; use [`get_rules`] instead.
+#[doc(hidden)]
 pub static RULES: LazyLock<Vec<BoxedRule>> = LazyLock::new(|| {
     vec![
         // Kramdown extension rules (disabled by default; enabled by kramdown preset)
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd001::KMD001),
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd002::KMD002),
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd003::KMD003),
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd004::KMD004),
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd005::KMD005),
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd006::KMD006),
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd007::KMD007),
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd008::KMD008),
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd009::KMD009),
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd010::KMD010),
+        #[cfg(feature = "rules-kramdown")]
         Box::new(kmd011::KMD011),
+        // mkdlint extension rules
+        Box::new(mkd001::MKD001),
+        #[cfg(feature = "rules-whitespace")]
+        Box::new(mkd002::MKD002),
+        Box::new(mkd003::MKD003),
+        #[cfg(feature = "rules-links")]
+        Box::new(mkd004::MKD004),
+        #[cfg(feature = "rules-headings")]
+        Box::new(mkd005::MKD005),
+        Box::new(mkd006::MKD006),
+        #[cfg(feature = "rules-links")]
+        Box::new(mkd007::MKD007),
         // Standard markdownlint rules
+        #[cfg(feature = "rules-headings")]
         Box::new(md001::MD001),
+        #[cfg(feature = "rules-headings")]
         Box::new(md003::MD003),
+        #[cfg(feature = "rules-lists")]
         Box::new(md004::MD004),
+        #[cfg(feature = "rules-lists")]
         Box::new(md005::MD005),
+        #[cfg(feature = "rules-lists")]
         Box::new(md007::MD007),
+        #[cfg(feature = "rules-whitespace")]
         Box::new(md009::MD009),
+        #[cfg(feature = "rules-whitespace")]
         Box::new(md010::MD010),
+        #[cfg(feature = "rules-links")]
         Box::new(md011::MD011),
+        #[cfg(feature = "rules-whitespace")]
         Box::new(md012::MD012),
+        #[cfg(feature = "rules-whitespace")]
         Box::new(md013::MD013),
+        #[cfg(feature = "rules-code")]
         Box::new(md014::MD014),
+        #[cfg(feature = "rules-headings")]
         Box::new(md018::MD018),
+        #[cfg(feature = "rules-headings")]
         Box::new(md019::MD019),
+        #[cfg(feature = "rules-headings")]
         Box::new(md020::MD020),
+        #[cfg(feature = "rules-headings")]
         Box::new(md021::MD021),
+        #[cfg(feature = "rules-headings")]
         Box::new(md022::MD022),
+        #[cfg(feature = "rules-headings")]
         Box::new(md023::MD023),
+        #[cfg(feature = "rules-headings")]
         Box::new(md024::MD024),
+        #[cfg(feature = "rules-headings")]
         Box::new(md025::MD025),
+        #[cfg(feature = "rules-headings")]
         Box::new(md026::MD026),
+        #[cfg(feature = "rules-whitespace")]
         Box::new(md027::MD027),
+        #[cfg(feature = "rules-whitespace")]
         Box::new(md028::MD028),
+        #[cfg(feature = "rules-lists")]
         Box::new(md029::MD029),
+        #[cfg(feature = "rules-lists")]
         Box::new(md030::MD030),
+        #[cfg(feature = "rules-code")]
         Box::new(md031::MD031),
+        #[cfg(feature = "rules-lists")]
         Box::new(md032::MD032),
+        #[cfg(feature = "rules-style")]
         Box::new(md033::MD033),
+        #[cfg(feature = "rules-links")]
         Box::new(md034::MD034),
+        #[cfg(feature = "rules-style")]
         Box::new(md035::MD035),
+        #[cfg(feature = "rules-headings")]
         Box::new(md036::MD036),
+        #[cfg(feature = "rules-whitespace")]
         Box::new(md037::MD037),
+        #[cfg(feature = "rules-whitespace")]
         Box::new(md038::MD038),
+        #[cfg(feature = "rules-links")]
         Box::new(md039::MD039),
+        #[cfg(feature = "rules-code")]
         Box::new(md040::MD040),
+        #[cfg(feature = "rules-headings")]
         Box::new(md041::MD041),
+        #[cfg(feature = "rules-links")]
         Box::new(md042::MD042),
+        #[cfg(feature = "rules-headings")]
         Box::new(md043::MD043),
+        #[cfg(feature = "rules-style")]
         Box::new(md044::MD044),
+        #[cfg(feature = "rules-links")]
         Box::new(md045::MD045),
+        #[cfg(feature = "rules-code")]
         Box::new(md046::MD046),
+        #[cfg(feature = "rules-whitespace")]
         Box::new(md047::MD047),
+        #[cfg(feature = "rules-code")]
         Box::new(md048::MD048),
+        #[cfg(feature = "rules-style")]
         Box::new(md049::MD049),
+        #[cfg(feature = "rules-style")]
         Box::new(md050::MD050),
+        #[cfg(feature = "rules-links")]
         Box::new(md051::MD051),
+        #[cfg(feature = "rules-links")]
         Box::new(md052::MD052),
+        #[cfg(feature = "rules-links")]
         Box::new(md053::MD053),
+        #[cfg(feature = "rules-links")]
         Box::new(md054::MD054),
+        #[cfg(feature = "rules-tables")]
         Box::new(md055::MD055),
+        #[cfg(feature = "rules-tables")]
         Box::new(md056::MD056),
+        #[cfg(feature = "rules-tables")]
         Box::new(md058::MD058),
+        #[cfg(feature = "rules-style")]
         Box::new(md059::MD059),
+        #[cfg(feature = "rules-code")]
         Box::new(md060::MD060),
     ]
 });
@@ -148,6 +311,132 @@ pub fn get_rules() -> &'static [BoxedRule] {
     &RULES
 }
 
+/// Built-in rules' [`Rule::fix_priority`] by rule ID
+static FIX_PRIORITIES: LazyLock<HashMap<&'static str, i32>> = LazyLock::new(|| {
+    RULES
+        .iter()
+        .map(|rule| (rule.names()[0], rule.fix_priority()))
+        .collect()
+});
+
+/// Fix priority of the built-in rule with this ID; `0` for other rules
+pub(crate) fn fix_priority(rule_id: &str) -> i32 {
+    FIX_PRIORITIES.get(rule_id).copied().unwrap_or(0)
+}
+
+/// Every rule that a cargo feature can leave out of the build, as `(names,
+/// feature)`; MKD001, MKD003 and MKD006 are always built
+const RULE_FEATURES: &[(&[&str], &str)] = &[
+    (
+        &["KMD001", "definition-list-term-has-definition"],
+        "rules-kramdown",
+    ),
+    (&["KMD002", "footnote-refs-defined"], "rules-kramdown"),
+    (&["KMD003", "footnote-defs-used"], "rules-kramdown"),
+    (&["KMD004", "abbreviation-defs-used"], "rules-kramdown"),
+    (&["KMD005", "no-duplicate-heading-ids"], "rules-kramdown"),
+    (&["KMD006", "valid-ial-syntax"], "rules-kramdown"),
+    (&["KMD007", "math-block-delimiters"], "rules-kramdown"),
+    (&["KMD008", "block-extension-syntax"], "rules-kramdown"),
+    (&["KMD009", "ald-defs-used"], "rules-kramdown"),
+    (&["KMD010", "inline-ial-syntax"], "rules-kramdown"),
+    (&["KMD011", "inline-math-balanced"], "rules-kramdown"),
+    (&["MKD002", "line-ending"], "rules-whitespace"),
+    (&["MKD004", "link-ref-style"], "rules-links"),
+    (&["MKD005", "heading-capitalization"], "rules-headings"),
+    (&["MKD007", "link-title-style"], "rules-links"),
+    (&["MD001", "heading-increment"], "rules-headings"),
+    (&["MD003", "heading-style"], "rules-headings"),
+    (&["MD004", "ul-style"], "rules-lists"),
+    (&["MD005", "list-indent"], "rules-lists"),
+    (&["MD007", "ul-indent"], "rules-lists"),
+    (&["MD009", "no-trailing-spaces"], "rules-whitespace"),
+    (&["MD010", "no-hard-tabs"], "rules-whitespace"),
+    (&["MD011", "no-reversed-links"], "rules-links"),
+    (&["MD012", "no-multiple-blanks"], "rules-whitespace"),
+    (&["MD013", "line-length"], "rules-whitespace"),
+    (&["MD014", "commands-show-output"], "rules-code"),
+    (&["MD018", "no-missing-space-atx"], "rules-headings"),
+    (&["MD019", "no-multiple-space-atx"], "rules-headings"),
+    (&["MD020", "no-missing-space-closed-atx"], "rules-headings"),
+    (&["MD021", "no-multiple-space-closed-atx"], "rules-headings"),
+    (
+        &["MD022", "blanks-around-headings", "blanks-around-headers"],
+        "rules-headings",
+    ),
+    (&["MD023", "heading-start-left"], "rules-headings"),
+    (
+        &["MD024", "no-duplicate-heading", "no-duplicate-header"],
+        "rules-headings",
+    ),
+    (&["MD025", "single-title", "single-h1"], "rules-headings"),
+    (&["MD026", "no-trailing-punctuation"], "rules-headings"),
+    (
+        &["MD027", "no-multiple-space-blockquote"],
+        "rules-whitespace",
+    ),
+    (&["MD028", "no-blanks-blockquote"], "rules-whitespace"),
+    (&["MD029", "ol-prefix"], "rules-lists"),
+    (&["MD030", "list-marker-space"], "rules-lists"),
+    (&["MD031", "blanks-around-fences"], "rules-code"),
+    (&["MD032", "blanks-around-lists"], "rules-lists"),
+    (&["MD033", "no-inline-html"], "rules-style"),
+    (&["MD034", "no-bare-urls"], "rules-links"),
+    (&["MD035", "hr-style"], "rules-style"),
+    (&["MD036", "no-emphasis-as-heading"], "rules-headings"),
+    (&["MD037", "no-space-in-emphasis"], "rules-whitespace"),
+    (&["MD038", "no-space-in-code"], "rules-whitespace"),
+    (&["MD039", "no-space-in-links"], "rules-links"),
+    (&["MD040", "fenced-code-language"], "rules-code"),
+    (
+        &["MD041", "first-line-heading", "first-line-h1"],
+        "rules-headings",
+    ),
+    (&["MD042", "no-empty-links"], "rules-links"),
+    (
+        &["MD043", "required-headings", "required-headers"],
+        "rules-headings",
+    ),
+    (&["MD044", "proper-names"], "rules-style"),
+    (&["MD045", "no-alt-text"], "rules-links"),
+    (&["MD046", "code-block-style"], "rules-code"),
+    (&["MD047", "single-trailing-newline"], "rules-whitespace"),
+    (&["MD048", "code-fence-style"], "rules-code"),
+    (&["MD049", "emphasis-style"], "rules-style"),
+    (&["MD050", "strong-style"], "rules-style"),
+    (&["MD051", "link-fragments"], "rules-links"),
+    (&["MD052", "reference-links-images"], "rules-links"),
+    (
+        &["MD053", "link-image-reference-definitions"],
+        "rules-links",
+    ),
+    (&["MD054", "link-image-style"], "rules-links"),
+    (&["MD055", "table-pipe-style"], "rules-tables"),
+    (&["MD056", "table-column-count"], "rules-tables"),
+    (&["MD058", "blanks-around-tables"], "rules-tables"),
+    (&["MD059", "emphasis-marker-style-math"], "rules-style"),
+    (&["MD060", "dollar-in-code-fence"], "rules-code"),
+];
+
+/// The cargo feature that builds the rule with this ID or alias, when the
+/// rule is not in this build; None for rules that are, or that don't exist
+///
+/// # Examples
+/// ```
+/// // With the default `rules-all` feature every rule is built
+/// assert_eq!(mkdlint::rules::missing_rule_feature("MD013"), None);
+/// assert_eq!(mkdlint::rules::missing_rule_feature("MD999"), None);
+/// ```
+pub fn missing_rule_feature(name: &str) -> Option<&'static str> {
+    if find_rule(name).is_some() {
+        return None;
+    }
+    RULE_FEATURES
+        .iter()
+        .find(|(names, _)| names.iter().any(|n| n.eq_ignore_ascii_case(name)))
+        .map(|(_, feature)| *feature)
+}
+
 /// Find a rule by name
 pub fn find_rule(name: &str) -> Option<&'static dyn Rule> {
     let name_upper = name.to_uppercase();
@@ -165,18 +454,21 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg(feature = "rules-all")]
     fn test_rule_counts() {
         let rules = get_rules();
         // 53 standard rules (MD001-MD060 minus 7 deprecated: MD002, MD006, MD008, MD015, MD016, MD017, MD057)
         // + 11 Kramdown extension rules (KMD001-KMD011)
+        // + 7 mkdlint extension rules (MKD001-MKD007)
         assert_eq!(
             rules.len(),
-            64,
-            "Should have 53 standard + 11 KMD extension rules"
+            71,
+            "Should have 53 standard + 11 KMD + 7 MKD extension rules"
         );
     }
 
     #[test]
+    #[cfg(feature = "rules-all")]
     fn test_find_rule_by_id() {
         assert!(find_rule("MD001").is_some());
         assert!(find_rule("MD007").is_some());
@@ -185,9 +477,58 @@ mod tests {
         assert!(find_rule("MD047").is_some());
         assert!(find_rule("KMD001").is_some());
         assert!(find_rule("KMD006").is_some());
+        assert!(find_rule("MKD001").is_some());
+        assert!(find_rule("MKD002").is_some());
+        assert!(find_rule("MKD003").is_some());
+        assert!(find_rule("MKD004").is_some());
+        assert!(find_rule("MKD005").is_some());
+        assert!(find_rule("MKD006").is_some());
+        assert!(find_rule("MKD007").is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "rules-all")]
+    fn test_rule_features_cover_every_gated_rule() {
+        let always = ["MKD001", "MKD003", "MKD006"];
+        for rule in get_rules() {
+            let names = rule.names();
+            if always.contains(&names[0]) {
+                continue;
+            }
+            assert!(
+                RULE_FEATURES.iter().any(|(listed, _)| *listed == names),
+                "{} is missing from RULE_FEATURES",
+                names[0]
+            );
+        }
+        assert_eq!(RULE_FEATURES.len() + always.len(), get_rules().len());
+    }
+
+    #[test]
+    #[cfg(not(feature = "rules-kramdown"))]
+    fn test_compiled_out_rule() {
+        assert!(find_rule("KMD001").is_none());
+        assert!(
+            get_rules()
+                .iter()
+                .all(|rule| !rule.names()[0].starts_with("KMD"))
+        );
+        assert_eq!(missing_rule_feature("kmd001"), Some("rules-kramdown"));
+        assert_eq!(
+            missing_rule_feature("definition-list-term-has-definition"),
+            Some("rules-kramdown")
+        );
+        assert_eq!(missing_rule_feature("MD999"), None);
+
+        let config: crate::Config = serde_json::from_str(r#"{"KMD001": false}"#).unwrap();
+        assert_eq!(
+            config.validate()[0].message,
+            "Rule 'KMD001' is not in this build of mkdlint (cargo feature `rules-kramdown`)"
+        );
     }
 
     #[test]
+    #[cfg(feature = "rules-all")]
     fn test_find_rule_by_alias() {
         assert!(find_rule("ul-indent").is_some());
         assert!(find_rule("no-trailing-spaces").is_some());
@@ -1,6 +1,6 @@
 //! Error types for markdownlint
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Main error type for markdownlint operations
@@ -88,10 +88,30 @@ pub struct LintError {
     /// If true, this error is only used internally for auto-fix (not shown to users)
     #[serde(skip)]
     pub fix_only: bool,
+
+    /// Source text of the offending line, without its line ending
+    ///
+    /// Only populated when `LintOptions::capture_line_text` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_text: Option<String>,
+
+    /// Another line the error refers to, such as the first of two duplicates
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub related: Option<RelatedLine>,
+}
+
+/// A line related to a lint error, shown by editors alongside the error
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelatedLine {
+    /// Line number (1-based)
+    pub line_number: usize,
+
+    /// What is on that line, e.g. "First top-level heading"
+    pub message: String,
 }
 
 /// Severity level for lint errors
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Severity {
     /// Error level
     Error,
@@ -109,7 +129,7 @@ impl fmt::Display for Severity {
 }
 
 /// Information for automatically fixing a lint error
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FixInfo {
     /// Line number to apply the fix (defaults to error line if None)
     pub line_number: Option<usize>,
@@ -124,6 +144,19 @@ pub struct FixInfo {
     pub insert_text: Option<String>,
 }
 
+/// `error_detail` prefix of the error reported when a rule panics
+pub(crate) const INTERNAL_ERROR_PREFIX: &str = "internal error in ";
+
+impl LintError {
+    /// Whether this error reports a rule that panicked rather than a
+    /// violation in the document
+    pub fn is_internal_error(&self) -> bool {
+        self.error_detail
+            .as_deref()
+            .is_some_and(|detail| detail.starts_with(INTERNAL_ERROR_PREFIX))
+    }
+}
+
 impl Default for LintError {
     fn default() -> Self {
         Self {
@@ -138,6 +171,8 @@ impl Default for LintError {
             suggestion: None,
             severity: Severity::Error,
             fix_only: false,
+            line_text: None,
+            related: None,
         }
     }
 }
@@ -2,10 +2,12 @@
 
 mod error;
 mod options;
+mod owned;
 mod results;
 mod rule;
 
 pub use error::*;
 pub use options::*;
+pub use owned::*;
 pub use results::*;
 pub use rule::*;
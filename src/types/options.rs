@@ -3,6 +3,8 @@
 use crate::config::Config;
 use crate::types::BoxedRule;
 use std::collections::HashMap;
+use std::ops::Range;
+use std::path::PathBuf;
 
 /// Options for linting markdown content
 #[derive(Default)]
@@ -13,21 +15,65 @@ pub struct LintOptions {
     /// Strings to lint (keyed by identifier)
     pub strings: HashMap<String, String>,
 
+    /// Pretend on-disk locations for string inputs, keyed by string name
+    ///
+    /// Rules see a mapped string under this path instead of its name, so
+    /// per-rule `files` globs match it and relative links resolve from its
+    /// directory. When neither `config` nor `config_file` is set and there
+    /// are no file inputs, the configuration is discovered from the
+    /// directory of the first mapped string (by name) rather than the
+    /// current directory. Results stay keyed by the string's name.
+    pub virtual_paths: HashMap<String, PathBuf>,
+
     /// Configuration object
+    ///
+    /// Takes precedence over `config_file` and over discovery.
     pub config: Option<Config>,
 
     /// Path to configuration file
+    ///
+    /// Takes precedence over discovery.
     pub config_file: Option<String>,
 
+    /// Layer the configuration over the global user config
+    /// ([`Config::global`]), as the CLI does unless `--no-global-config`
+    ///
+    /// Off by default so library use does not depend on the user's home
+    /// directory. Applies to whichever config is used (`config`,
+    /// `config_file` or a discovered file), and on its own when there is
+    /// none.
+    pub use_global_config: bool,
+
     /// Custom rules to use
     pub custom_rules: Vec<BoxedRule>,
 
-    /// Front matter pattern (regex)
+    /// Front matter pattern (regex), or `"auto"`
+    ///
+    /// The pattern must match both the opening and the closing delimiter
+    /// line. `"auto"` recognizes YAML (`---`), TOML (`+++`), and JSON (`{` to
+    /// `}`) front matter. An invalid pattern makes linting fail with an
+    /// invalid-configuration error.
     pub front_matter: Option<String>,
 
+    /// Additional front matter patterns, tried in order after `front_matter`
+    ///
+    /// Each entry is a regex or `"auto"`, with the same meaning as
+    /// `front_matter`; the first whose opening delimiter matches line 1 and
+    /// which has a closing delimiter wins.
+    pub front_matter_patterns: Vec<String>,
+
     /// Whether to ignore inline configuration
     pub no_inline_config: bool,
 
+    /// Report every inline disable directive that suppressed no errors
+    ///
+    /// Each is reported as a fixable MKD006 warning (whether or not MKD006
+    /// is enabled) whose fix removes the directive line, or only the unused
+    /// rule names when others in the same directive suppressed errors.
+    /// Unlike MKD006 on its own, this covers disables for rules that did
+    /// not run.
+    pub report_unused_disables: bool,
+
     /// Result version for backward compatibility
     pub result_version: u32,
 
@@ -39,6 +85,37 @@ pub struct LintOptions {
     /// When provided, `lint_sync()` uses this instead of rebuilding the index
     /// from inputs. Useful for multi-pass fix convergence and watch mode.
     pub cached_workspace_headings: Option<HashMap<String, Vec<String>>>,
+
+    /// Per-rule time budget in milliseconds (`None` = unlimited).
+    ///
+    /// A rule whose run on one file takes longer is reported once with a
+    /// warning and skipped for the remaining files of the run.
+    pub rule_timeout_ms: Option<u64>,
+
+    /// Attach the offending line's text to each error (`LintError::line_text`)
+    ///
+    /// Off by default to avoid copying source lines; lets formatters show
+    /// context without keeping a separate map of file contents.
+    pub capture_line_text: bool,
+
+    /// Report only errors on these 1-based line ranges, keyed by file or
+    /// string name
+    ///
+    /// The whole document is still linted, so rules that depend on context
+    /// see all of it; errors outside the ranges are dropped afterwards.
+    /// Document-level errors (MD041, MD047) are kept only when the line they
+    /// point at is in range. Inputs without an entry are reported in full.
+    pub line_ranges: Option<HashMap<String, Vec<Range<usize>>>>,
+
+    /// Lint only the contents of fenced code blocks in these languages
+    ///
+    /// When non-empty, every other line is blanked before the rules run,
+    /// except the blocks' fence lines, so rules still see the blocks as
+    /// code: options such as MD013's `code_blocks` apply as usual. Errors
+    /// are reported only on lines inside the blocks. Languages match the
+    /// first word of the info string, case-insensitively. Pick the rules to
+    /// run with the config, e.g. `{"default": false, "MD013": {...}}`.
+    pub code_block_languages: Vec<String>,
 }
 
 impl LintOptions {
@@ -65,6 +142,30 @@ impl LintOptions {
         self
     }
 
+    /// Lint string `name` as if it were the file at `path`; see
+    /// [`LintOptions::virtual_paths`]
+    ///
+    /// ```
+    /// let options = mkdlint::LintOptions::new()
+    ///     .with_string("README", "# Crate\n")
+    ///     .with_virtual_path("README", "crates/foo/README.md");
+    /// let results = mkdlint::lint_sync(&options)?;
+    /// assert!(results.get("README").is_some());
+    /// # Ok::<(), mkdlint::types::MarkdownlintError>(())
+    /// ```
+    pub fn with_virtual_path(mut self, name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        self.virtual_paths.insert(name.into(), path.into());
+        self
+    }
+
+    /// The path rules see for input `name`: its virtual path, if mapped
+    pub(crate) fn rule_path(&self, name: &str) -> String {
+        self.virtual_paths.get(name).map_or_else(
+            || name.to_string(),
+            |path| path.to_string_lossy().into_owned(),
+        )
+    }
+
     /// Set the configuration
     pub fn with_config(mut self, config: Config) -> Self {
         self.config = Some(config);
@@ -89,9 +190,78 @@ impl LintOptions {
         self
     }
 
+    /// Add a front matter pattern tried after the ones already set
+    pub fn with_front_matter_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.front_matter_patterns.push(pattern.into());
+        self
+    }
+
+    /// All configured front matter patterns, in the order they are tried
+    pub(crate) fn front_matter_pattern_list(&self) -> Vec<&str> {
+        self.front_matter
+            .iter()
+            .chain(&self.front_matter_patterns)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Set the per-rule time budget in milliseconds
+    pub fn with_rule_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.rule_timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    /// Attach the offending line's text to each error
+    pub fn with_line_text(mut self) -> Self {
+        self.capture_line_text = true;
+        self
+    }
+
+    /// Report only `name`'s errors on `lines` (1-based, end exclusive); see
+    /// [`LintOptions::line_ranges`]
+    ///
+    /// ```
+    /// let options = mkdlint::LintOptions::new()
+    ///     .with_string("a.md", "# A\n\nOne  \nTwo  \n")
+    ///     .with_line_range("a.md", 4..5);
+    /// let results = mkdlint::lint_sync(&options)?;
+    /// let lines: Vec<usize> = results.get("a.md").unwrap().iter().map(|e| e.line_number).collect();
+    /// assert_eq!(lines, [4]);
+    /// # Ok::<(), mkdlint::types::MarkdownlintError>(())
+    /// ```
+    pub fn with_line_range(mut self, name: impl Into<String>, lines: Range<usize>) -> Self {
+        self.line_ranges
+            .get_or_insert_with(HashMap::new)
+            .entry(name.into())
+            .or_default()
+            .push(lines);
+        self
+    }
+
+    /// Lint only fenced code blocks in `language`; see
+    /// [`LintOptions::code_block_languages`]
+    pub fn with_code_block_language(mut self, language: impl Into<String>) -> Self {
+        self.code_block_languages.push(language.into());
+        self
+    }
+
     /// Disable inline configuration
     pub fn no_inline_config(mut self) -> Self {
         self.no_inline_config = true;
         self
     }
+
+    /// Layer the configuration over the global user config; see
+    /// [`LintOptions::use_global_config`]
+    pub fn use_global_config(mut self) -> Self {
+        self.use_global_config = true;
+        self
+    }
+
+    /// Report unused disable directives; see
+    /// [`LintOptions::report_unused_disables`]
+    pub fn report_unused_disables(mut self) -> Self {
+        self.report_unused_disables = true;
+        self
+    }
 }
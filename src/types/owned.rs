@@ -0,0 +1,259 @@
+//! Owned lint results that can be deserialized, for persisting a run
+//!
+//! [`LintError`] borrows its rule names and description from the rule
+//! registry, so it can be serialized but not read back. The owned types here
+//! serialize to the same JSON and also implement `Deserialize`:
+//!
+//! ```
+//! use mkdlint::types::OwnedLintResults;
+//!
+//! let options = mkdlint::LintOptions::new().with_string("a.md", "# Title  \n");
+//! let results = mkdlint::lint_sync(&options)?;
+//!
+//! let json = serde_json::to_string(&OwnedLintResults::from(&results)).unwrap();
+//! let reloaded: OwnedLintResults = serde_json::from_str(&json).unwrap();
+//! assert_eq!(reloaded.results["a.md"][0].rule_names[0], "MD009");
+//!
+//! let restored = mkdlint::LintResults::try_from(reloaded).unwrap();
+//! assert_eq!(restored.results["a.md"], results.results["a.md"]);
+//! # Ok::<(), mkdlint::types::MarkdownlintError>(())
+//! ```
+
+use crate::types::{FixInfo, LintError, LintResults, RelatedLine, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// A [`LintError`] that owns its strings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnedLintError {
+    /// Line number (1-based) where the error occurs
+    pub line_number: usize,
+
+    /// Rule names (e.g., ["MD001", "heading-increment"])
+    pub rule_names: Vec<String>,
+
+    /// Rule description
+    pub rule_description: String,
+
+    /// Additional detail about the error
+    pub error_detail: Option<String>,
+
+    /// Context information (excerpt from the line)
+    pub error_context: Option<String>,
+
+    /// URL with more information about the rule
+    pub rule_information: Option<String>,
+
+    /// Column range for the error [start, length]
+    pub error_range: Option<(usize, usize)>,
+
+    /// Fix information for automatic correction
+    pub fix_info: Option<FixInfo>,
+
+    /// Helpful suggestion for how to fix the issue
+    pub suggestion: Option<String>,
+
+    /// Severity level
+    pub severity: Severity,
+
+    /// Source text of the offending line, if it was captured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_text: Option<String>,
+
+    /// Another line the error refers to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub related: Option<RelatedLine>,
+}
+
+impl From<&LintError> for OwnedLintError {
+    fn from(error: &LintError) -> Self {
+        Self {
+            line_number: error.line_number,
+            rule_names: error.rule_names.iter().map(|n| n.to_string()).collect(),
+            rule_description: error.rule_description.to_string(),
+            error_detail: error.error_detail.clone(),
+            error_context: error.error_context.clone(),
+            rule_information: error.rule_information.map(str::to_string),
+            error_range: error.error_range,
+            fix_info: error.fix_info.clone(),
+            suggestion: error.suggestion.clone(),
+            severity: error.severity,
+            line_text: error.line_text.clone(),
+            related: error.related.clone(),
+        }
+    }
+}
+
+impl From<LintError> for OwnedLintError {
+    fn from(error: LintError) -> Self {
+        Self::from(&error)
+    }
+}
+
+/// Converts back for errors of built-in rules, looked up by their first
+/// rule name; the rule's names, description and information URL come from
+/// the registry. Errors of custom rules are returned unchanged as `Err`.
+impl TryFrom<OwnedLintError> for LintError {
+    type Error = OwnedLintError;
+
+    fn try_from(error: OwnedLintError) -> Result<Self, Self::Error> {
+        let Some(rule) = error
+            .rule_names
+            .first()
+            .and_then(|name| crate::rules::find_rule(name))
+        else {
+            return Err(error);
+        };
+        Ok(LintError {
+            line_number: error.line_number,
+            rule_names: rule.names(),
+            rule_description: rule.description(),
+            error_detail: error.error_detail,
+            error_context: error.error_context,
+            rule_information: rule.information(),
+            error_range: error.error_range,
+            fix_info: error.fix_info,
+            suggestion: error.suggestion,
+            severity: error.severity,
+            fix_only: false,
+            line_text: error.line_text,
+            related: error.related,
+        })
+    }
+}
+
+/// A [`LintResults`] that owns its errors' strings
+///
+/// `fix_only` errors are internal to auto-fix and are left out, since the
+/// flag itself is not serialized.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnedLintResults {
+    /// Map of file/string name to lint errors
+    pub results: HashMap<String, Vec<OwnedLintError>>,
+
+    /// Files skipped as generated (`skip_generated`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_generated: Vec<String>,
+
+    /// Files skipped because their content looks binary
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_binary: Vec<String>,
+
+    /// Line offsets of documents cut from a larger input
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub line_offsets: BTreeMap<String, usize>,
+}
+
+impl From<&LintResults> for OwnedLintResults {
+    fn from(results: &LintResults) -> Self {
+        Self {
+            results: results
+                .results
+                .iter()
+                .map(|(name, errors)| {
+                    let errors = errors
+                        .iter()
+                        .filter(|e| !e.fix_only)
+                        .map(OwnedLintError::from)
+                        .collect();
+                    (name.clone(), errors)
+                })
+                .collect(),
+            skipped_generated: results.skipped_generated.clone(),
+            skipped_binary: results.skipped_binary.clone(),
+            line_offsets: results.line_offsets.clone(),
+        }
+    }
+}
+
+impl From<LintResults> for OwnedLintResults {
+    fn from(results: LintResults) -> Self {
+        Self::from(&results)
+    }
+}
+
+/// Converts back when every error belongs to a built-in rule; otherwise
+/// the first error that does not is returned.
+impl TryFrom<OwnedLintResults> for LintResults {
+    type Error = OwnedLintError;
+
+    fn try_from(owned: OwnedLintResults) -> Result<Self, Self::Error> {
+        let mut results = LintResults::new();
+        for (name, errors) in owned.results {
+            let errors = errors
+                .into_iter()
+                .map(LintError::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            results.add(name, errors);
+        }
+        results.skipped_generated = owned.skipped_generated;
+        results.skipped_binary = owned.skipped_binary;
+        results.line_offsets = owned.line_offsets;
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owned_results_round_trip() {
+        let mut results = LintResults::new();
+        results.add(
+            "docs/a.md".to_string(),
+            vec![
+                LintError {
+                    line_number: 3,
+                    rule_names: &["MD013", "line-length"],
+                    rule_description: "Line length",
+                    error_detail: Some("Expected: 80; Actual: 95".to_string()),
+                    error_range: Some((81, 15)),
+                    severity: Severity::Warning,
+                    ..Default::default()
+                },
+                LintError {
+                    line_number: 4,
+                    rule_names: &["MD009"],
+                    fix_only: true,
+                    ..Default::default()
+                },
+            ],
+        );
+
+        let json = serde_json::to_string(&OwnedLintResults::from(&results)).unwrap();
+        let reloaded: OwnedLintResults = serde_json::from_str(&json).unwrap();
+        // The owned form also reads what `LintError` itself serializes to
+        let error_json = serde_json::to_string(&results.results["docs/a.md"][0]).unwrap();
+        let direct: OwnedLintError = serde_json::from_str(&error_json).unwrap();
+        assert_eq!(reloaded.results["docs/a.md"], [direct]);
+
+        let errors = &reloaded.results["docs/a.md"];
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 3);
+        assert_eq!(errors[0].rule_names, ["MD013", "line-length"]);
+        assert_eq!(errors[0].severity, Severity::Warning);
+        assert_eq!(
+            errors[0].error_detail.as_deref(),
+            Some("Expected: 80; Actual: 95")
+        );
+
+        let restored = LintResults::try_from(reloaded).unwrap();
+        let error = &restored.results["docs/a.md"][0];
+        assert_eq!(
+            error.rule_names,
+            crate::rules::find_rule("MD013").unwrap().names()
+        );
+        assert_eq!(error.error_range, Some((81, 15)));
+    }
+
+    #[test]
+    fn test_custom_rule_error_does_not_convert_back() {
+        let owned = OwnedLintError::from(LintError {
+            line_number: 1,
+            rule_names: &["CUSTOM001"],
+            ..Default::default()
+        });
+        assert_eq!(LintError::try_from(owned.clone()), Err(owned));
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::types::LintError;
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt;
 
 /// Results from linting operations
@@ -10,6 +10,70 @@ use std::fmt;
 pub struct LintResults {
     /// Map of file/string name to lint errors
     pub results: HashMap<String, Vec<LintError>>,
+    /// Files skipped as generated (`skip_generated`), in the order recorded
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_generated: Vec<String>,
+    /// Files skipped because their content looks binary, in the order
+    /// recorded
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_binary: Vec<String>,
+    /// For documents cut from a larger input (e.g. `--stdin-multi`), the
+    /// number of input lines before each one, by name; error line numbers
+    /// stay relative to the document
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub line_offsets: BTreeMap<String, usize>,
+    /// IDs of the rules enabled for the run, recorded by the lint functions
+    #[serde(skip)]
+    pub rules_run: Vec<String>,
+}
+
+/// Aggregate counts over a [`LintResults`], from [`LintResults::stats`]
+///
+/// Fix-only entries are not counted. Maps are sorted by key so the
+/// serialized form is stable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LintStats {
+    /// Violations with error severity
+    pub errors: usize,
+    /// Violations with warning severity
+    pub warnings: usize,
+    /// Violations that carry a fix
+    pub fixable: usize,
+    /// Violations per rule ID
+    pub by_rule: BTreeMap<String, usize>,
+    /// Violations per file, including files without any
+    pub by_file: BTreeMap<String, usize>,
+    /// IDs of the rules that ran
+    pub rules_run: BTreeSet<String>,
+}
+
+/// What a convergent fix run did to one document, from
+/// [`converge_fixes`](crate::lint::converge_fixes)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FixOutcome {
+    /// Content after the last pass
+    pub content: String,
+    /// Fixes applied by each pass that changed the content, by rule ID
+    ///
+    /// Counted as by [`apply_fixes_counted`](crate::lint::apply_fixes_counted):
+    /// conflicting or skipped fixes and `fix_only` helper edits are left out.
+    pub passes: Vec<BTreeMap<String, usize>>,
+}
+
+impl FixOutcome {
+    /// Fixes applied across all passes, by rule ID
+    pub fn fixes_by_rule(&self) -> BTreeMap<String, usize> {
+        let mut totals = BTreeMap::new();
+        for (rule, count) in self.passes.iter().flatten() {
+            *totals.entry(rule.clone()).or_default() += count;
+        }
+        totals
+    }
+
+    /// Fixes applied across all passes
+    pub fn total_fixes(&self) -> usize {
+        self.passes.iter().flat_map(|pass| pass.values()).sum()
+    }
 }
 
 impl LintResults {
@@ -23,6 +87,35 @@ impl LintResults {
         self.results.insert(name, errors);
     }
 
+    /// Record a file skipped as generated; it is added with no errors
+    pub fn add_skipped_generated(&mut self, name: String) {
+        self.results.insert(name.clone(), Vec::new());
+        self.skipped_generated.push(name);
+    }
+
+    /// Whether a file was skipped as generated
+    pub fn is_skipped_generated(&self, name: &str) -> bool {
+        self.skipped_generated.iter().any(|n| n == name)
+    }
+
+    /// Record a file skipped because it looks binary; it is added with no
+    /// errors
+    pub fn add_skipped_binary(&mut self, name: String) {
+        self.results.insert(name.clone(), Vec::new());
+        self.skipped_binary.push(name);
+    }
+
+    /// Whether a file was skipped because it looks binary
+    pub fn is_skipped_binary(&self, name: &str) -> bool {
+        self.skipped_binary.iter().any(|n| n == name)
+    }
+
+    /// The line in the original input of `line` in document `name`, if the
+    /// document was cut from a larger input
+    pub fn absolute_line(&self, name: &str, line: usize) -> Option<usize> {
+        self.line_offsets.get(name).map(|offset| offset + line)
+    }
+
     /// Get errors for a specific file or string
     pub fn get(&self, name: &str) -> Option<&[LintError]> {
         self.results.get(name).map(|v| v.as_slice())
@@ -54,6 +147,65 @@ impl LintResults {
             .sum()
     }
 
+    /// A copy holding only the errors `keep` accepts, e.g. errors only:
+    /// `results.filtered(|e| e.severity == Severity::Error)`
+    ///
+    /// Every file stays listed, with an empty list if nothing matched, and
+    /// the run's other records are carried over. `fix_only` entries are
+    /// passed to `keep` like any other.
+    pub fn filtered(&self, keep: impl Fn(&LintError) -> bool) -> LintResults {
+        LintResults {
+            results: self
+                .results
+                .iter()
+                .map(|(name, errors)| {
+                    let errors = errors.iter().filter(|e| keep(e)).cloned().collect();
+                    (name.clone(), errors)
+                })
+                .collect(),
+            skipped_generated: self.skipped_generated.clone(),
+            skipped_binary: self.skipped_binary.clone(),
+            line_offsets: self.line_offsets.clone(),
+            rules_run: self.rules_run.clone(),
+        }
+    }
+
+    /// Errors reporting rules that panicked, with the file they occurred in
+    pub fn internal_errors(&self) -> impl Iterator<Item = (&str, &LintError)> {
+        self.results.iter().flat_map(|(name, errors)| {
+            errors
+                .iter()
+                .filter(|e| e.is_internal_error())
+                .map(move |e| (name.as_str(), e))
+        })
+    }
+
+    /// Per-rule and per-file counts for the whole run
+    pub fn stats(&self) -> LintStats {
+        let mut stats = LintStats {
+            rules_run: self.rules_run.iter().cloned().collect(),
+            ..Default::default()
+        };
+        for (name, errors) in &self.results {
+            let reported: Vec<&LintError> = errors.iter().filter(|e| !e.fix_only).collect();
+            stats.by_file.insert(name.clone(), reported.len());
+            for error in reported {
+                match error.severity {
+                    crate::types::Severity::Error => stats.errors += 1,
+                    crate::types::Severity::Warning => stats.warnings += 1,
+                }
+                if error.fix_info.is_some() {
+                    stats.fixable += 1;
+                }
+                *stats
+                    .by_rule
+                    .entry(error.rule_names[0].to_string())
+                    .or_default() += 1;
+            }
+        }
+        stats
+    }
+
     /// Check if there are any errors (not warnings)
     pub fn has_errors(&self) -> bool {
         self.error_count() > 0
@@ -176,4 +328,92 @@ mod tests {
         assert!(!results.is_empty());
         assert_eq!(results.files_with_errors().len(), 2);
     }
+
+    #[test]
+    fn test_lint_results_filtered() {
+        let mut results = LintResults::new();
+        results.add(
+            "a.md".to_string(),
+            vec![
+                LintError {
+                    line_number: 1,
+                    rule_names: &["MD001"],
+                    severity: Severity::Error,
+                    ..Default::default()
+                },
+                LintError {
+                    line_number: 2,
+                    rule_names: &["MD013"],
+                    severity: Severity::Warning,
+                    ..Default::default()
+                },
+            ],
+        );
+        results.add(
+            "b.md".to_string(),
+            vec![LintError {
+                line_number: 3,
+                rule_names: &["MD013"],
+                severity: Severity::Warning,
+                ..Default::default()
+            }],
+        );
+        results.rules_run = vec!["MD001".to_string(), "MD013".to_string()];
+
+        let errors = results.filtered(|e| e.severity == Severity::Error);
+        assert_eq!(errors.error_count(), 1);
+        assert_eq!(errors.warning_count(), 0);
+        assert_eq!(errors.files_with_errors(), ["a.md"]);
+        assert_eq!(errors.get("b.md"), Some(&[][..]));
+        assert_eq!(errors.rules_run, results.rules_run);
+        assert_eq!(results.warning_count(), 2);
+    }
+
+    #[test]
+    fn test_lint_results_stats() {
+        let error = |rule: &'static [&'static str], severity, fixable: bool| LintError {
+            line_number: 1,
+            rule_names: rule,
+            severity,
+            fix_info: fixable.then_some(crate::types::FixInfo {
+                line_number: None,
+                edit_column: Some(1),
+                delete_count: Some(1),
+                insert_text: None,
+            }),
+            ..Default::default()
+        };
+        let mut results = LintResults::new();
+        results.add(
+            "a.md".to_string(),
+            vec![
+                error(&["MD009", "no-trailing-spaces"], Severity::Error, true),
+                error(&["MD009", "no-trailing-spaces"], Severity::Error, true),
+                error(&["MD013", "line-length"], Severity::Warning, false),
+                LintError {
+                    fix_only: true,
+                    ..error(&["MD047"], Severity::Error, true)
+                },
+            ],
+        );
+        results.add("b.md".to_string(), Vec::new());
+        results.rules_run = vec!["MD013".to_string(), "MD009".to_string()];
+
+        let stats = results.stats();
+        assert_eq!(stats.errors, 2);
+        assert_eq!(stats.warnings, 1);
+        assert_eq!(stats.fixable, 2);
+        assert_eq!(
+            stats.by_rule,
+            BTreeMap::from([("MD009".to_string(), 2), ("MD013".to_string(), 1)])
+        );
+        assert_eq!(
+            stats.by_file,
+            BTreeMap::from([("a.md".to_string(), 3), ("b.md".to_string(), 0)])
+        );
+        assert_eq!(
+            stats.rules_run.iter().collect::<Vec<_>>(),
+            ["MD009", "MD013"]
+        );
+    }
 }
@@ -1,8 +1,15 @@
 //! Rule trait and related types
 
+use crate::config::{Config, RuleConfig};
+use crate::helpers::{
+    AnalysisCache, ListGeometry, ListLineInfo, PatternCache, ReferenceDefinition, ReferenceLink,
+};
 use crate::parser::Token;
 use crate::types::LintError;
+use regex::Regex;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::ops::Range;
 
 #[cfg(feature = "async")]
 use std::future::Future;
@@ -30,6 +37,11 @@ pub struct RuleParams<'a> {
     pub lines: &'a [&'a str],
 
     /// Front matter lines (if present)
+    ///
+    /// `lines` still includes them so line numbers stay absolute. Errors a
+    /// rule reports inside the front matter are dropped unless the rule
+    /// opts in with [`Rule::lints_front_matter`]; see
+    /// [`RuleParams::content_start_line`].
     pub front_matter_lines: &'a [&'a str],
 
     /// Parsed tokens from the markdown content
@@ -42,6 +54,154 @@ pub struct RuleParams<'a> {
     /// Maps normalized file paths to their heading anchor IDs.
     /// None when workspace context is unavailable (e.g., stdin, single-file lint).
     pub workspace_headings: Option<&'a HashMap<String, Vec<String>>>,
+
+    /// The fully resolved configuration for this lint run (read-only).
+    ///
+    /// Intended narrowly for rules that must agree with a sibling rule's
+    /// settings (e.g. a rule honoring MD009's `br_spaces`). A rule's own
+    /// options should still be read from `config`. Prefer
+    /// [`RuleParams::sibling_option`] over inspecting this directly.
+    /// None when the rule is invoked outside a lint run (e.g., unit tests).
+    pub global_config: Option<&'a Config>,
+
+    /// Whether the content started with a UTF-8 byte order mark.
+    ///
+    /// The BOM is stripped before `lines` and `tokens` are built, so column 1
+    /// of line 1 is always the first character after it.
+    pub has_bom: bool,
+
+    /// Shared cache for compiling user-supplied patterns from rule options
+    ///
+    /// None in unit tests; [`RuleParams::user_regex`] then compiles directly.
+    pub patterns: Option<&'a PatternCache>,
+
+    /// Document structure shared between rules, computed once per file
+    ///
+    /// None in unit tests; use the accessors such as
+    /// [`RuleParams::code_block_lines`], which then compute on demand.
+    pub analysis: Option<&'a AnalysisCache<'a>>,
+}
+
+impl<'a> RuleParams<'a> {
+    /// Look up an option configured for another rule, by rule ID.
+    ///
+    /// Returns None when no global config is available, the rule has no
+    /// options object, or the key is absent — callers fall back to the
+    /// sibling rule's documented default.
+    pub fn sibling_option(&self, rule_id: &str, key: &str) -> Option<&'a serde_json::Value> {
+        match self.global_config?.get_rule_config(rule_id)? {
+            RuleConfig::Options(opts) => opts.get(key),
+            _ => None,
+        }
+    }
+
+    /// Line number (1-based) of the first line after any front matter.
+    ///
+    /// Line-based rules start scanning here; `lines[content_start_line() - 1..]`
+    /// is the Markdown body.
+    pub fn content_start_line(&self) -> usize {
+        self.front_matter_lines.len() + 1
+    }
+
+    /// Flags marking lines inside fenced or indented code blocks, fence
+    /// lines included (index `line_number - 1`).
+    pub fn code_block_lines(&self) -> Cow<'a, [bool]> {
+        match self.analysis {
+            Some(analysis) => Cow::Borrowed(analysis.code_block_lines()),
+            None => Cow::Owned(crate::helpers::code_block_lines(self.lines, self.tokens)),
+        }
+    }
+
+    /// Byte ranges of each line covered by code spans and math, whose text
+    /// is literal (index `line_number - 1`).
+    pub fn literal_spans(&self) -> Cow<'a, [Vec<Range<usize>>]> {
+        match self.analysis {
+            Some(analysis) => Cow::Borrowed(analysis.literal_spans()),
+            None => Cow::Owned(crate::helpers::literal_spans(self.lines, self.tokens)),
+        }
+    }
+
+    /// Anchor IDs of the document's headings, in order, with `-1`, `-2`, ...
+    /// suffixes on duplicates.
+    pub fn heading_ids(&self) -> Cow<'a, [String]> {
+        match self.analysis {
+            Some(analysis) => Cow::Borrowed(analysis.heading_ids()),
+            None => Cow::Owned(crate::helpers::collect_heading_ids(self.lines)),
+        }
+    }
+
+    /// Link reference definitions outside code blocks, in order.
+    pub fn reference_definitions(&self) -> Cow<'a, [ReferenceDefinition]> {
+        match self.analysis {
+            Some(analysis) => Cow::Borrowed(analysis.reference_definitions()),
+            None => Cow::Owned(crate::helpers::reference_definitions(
+                self.lines,
+                &self.code_block_lines(),
+            )),
+        }
+    }
+
+    /// Geometry of every list, outer lists before the lists nested in them.
+    pub fn list_geometry(&self) -> Cow<'a, [ListGeometry]> {
+        match self.analysis {
+            Some(analysis) => Cow::Borrowed(analysis.list_geometry()),
+            None => Cow::Owned(crate::helpers::list_geometry(self.tokens, self.lines)),
+        }
+    }
+
+    /// The innermost list item containing each line, or `None` outside
+    /// lists (index `line_number - 1`).
+    pub fn list_context(&self) -> Cow<'a, [Option<ListLineInfo>]> {
+        match self.analysis {
+            Some(analysis) => Cow::Borrowed(analysis.list_context()),
+            None => Cow::Owned(crate::helpers::list_context(self.lines, self.tokens)),
+        }
+    }
+
+    /// Reference links and images outside code blocks, in order.
+    pub fn reference_links(&self) -> Cow<'a, [ReferenceLink]> {
+        match self.analysis {
+            Some(analysis) => Cow::Borrowed(analysis.reference_links()),
+            None => Cow::Owned(crate::helpers::reference_links(
+                self.lines,
+                &self.code_block_lines(),
+            )),
+        }
+    }
+
+    /// Line number (1-based) of the front matter title, which stands in for
+    /// a top-level heading.
+    ///
+    /// The rule's `front_matter_title` option is a regex matched
+    /// case-insensitively against each front matter line (default: a
+    /// `title:` or `title =` key); `false` ignores front matter.
+    pub fn front_matter_title_line(&self) -> Option<usize> {
+        let pattern = match self.config.get("front_matter_title") {
+            Some(serde_json::Value::String(s)) if !s.is_empty() => s.as_str(),
+            Some(serde_json::Value::Bool(false)) => return None,
+            _ => r#"^\s*"?title"?\s*[:=]"#,
+        };
+        let re = self.user_regex(&format!("(?i){}", pattern))?;
+        self.front_matter_lines
+            .iter()
+            .position(|line| re.is_match(line))
+            .map(|idx| idx + 1)
+    }
+
+    /// Compile a user-supplied pattern from rule options, once per lint run.
+    ///
+    /// Returns None if the pattern is invalid or exceeds the size limits;
+    /// rules treat that like an unset option. Regexes in rule options are
+    /// checked before linting (see [`Config::validate`]), so that only
+    /// happens for patterns the rules build themselves.
+    ///
+    /// [`Config::validate`]: crate::Config::validate
+    pub fn user_regex(&self, pattern: &str) -> Option<Regex> {
+        match self.patterns {
+            Some(cache) => cache.get(pattern).ok(),
+            None => PatternCache::new().get(pattern).ok(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -60,6 +220,10 @@ impl<'a> RuleParams<'a> {
             tokens,
             config,
             workspace_headings: None,
+            global_config: None,
+            has_bom: false,
+            patterns: None,
+            analysis: None,
         }
     }
 
@@ -70,6 +234,7 @@ impl<'a> RuleParams<'a> {
 }
 
 /// Callback type for reporting errors
+#[doc(hidden)]
 pub type OnErrorFn<'a> = &'a mut dyn FnMut(LintError);
 
 /// Trait that all rules must implement
@@ -107,6 +272,35 @@ pub trait Rule: Send + Sync {
         true
     }
 
+    /// Whether this rule should run on the file with this name (path as
+    /// given to the linter).
+    ///
+    /// Consulted before the rule runs; a rule scoped to particular files
+    /// (e.g. a changelog format check) returns `false` for the rest. Users
+    /// can narrow any rule further with the `files` glob list in its options.
+    fn applies_to_file(&self, _name: &str) -> bool {
+        true
+    }
+
+    /// Whether this rule checks front matter lines too.
+    ///
+    /// By default errors reported on a front matter line are dropped, so
+    /// rules never flag YAML or TOML as Markdown. Rules about the file as a
+    /// whole (byte order mark, line endings, final newline) return `true`.
+    fn lints_front_matter(&self) -> bool {
+        false
+    }
+
+    /// Order of this rule's fixes among fixes at the same line and column.
+    ///
+    /// [`apply_fixes`](crate::apply_fixes) applies lower values first, so
+    /// the fixed output never depends on the order rules ran in. Rules that
+    /// add or remove lines return `-1`, whitespace-only fixes return `1`;
+    /// everything else keeps the default `0`.
+    fn fix_priority(&self) -> i32 {
+        0
+    }
+
     /// Whether this rule is asynchronous
     fn is_async(&self) -> bool {
         false
@@ -129,6 +323,7 @@ pub trait Rule: Send + Sync {
 pub type BoxedRule = Box<dyn Rule>;
 
 /// Helper trait for creating rule registries
+#[doc(hidden)]
 pub trait RuleRegistry {
     /// Get all rules in the registry
     fn rules(&self) -> &[BoxedRule];
@@ -180,6 +375,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sibling_option() {
+        let config: Config =
+            serde_json::from_str(r#"{"MD009": {"br_spaces": 3}, "MD013": false}"#).unwrap();
+        let rule_config = HashMap::new();
+        let mut params = RuleParams::test(&[], &rule_config);
+        assert_eq!(params.sibling_option("MD009", "br_spaces"), None);
+
+        params.global_config = Some(&config);
+        assert_eq!(
+            params.sibling_option("MD009", "br_spaces"),
+            Some(&serde_json::json!(3))
+        );
+        assert_eq!(params.sibling_option("MD009", "strict"), None);
+        assert_eq!(params.sibling_option("MD013", "line_length"), None);
+    }
+
     #[test]
     fn test_rule_names() {
         let rule = TestRule;
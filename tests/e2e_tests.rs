@@ -48,6 +48,14 @@ fn test_cli_help() {
     );
 }
 
+#[test]
+fn test_cli_completions_bash() {
+    let (code, stdout, _stderr) = run_mkdlint(&["completions", "bash"]);
+    assert_eq!(code, 0, "completions should exit 0");
+    assert!(stdout.contains("--output-format"));
+    assert!(stdout.contains("MD013"), "rule IDs should be completed");
+}
+
 #[test]
 fn test_cli_clean_file() {
     let dir = tempfile::tempdir().unwrap();
@@ -109,6 +117,127 @@ fn test_cli_with_config() {
     );
 }
 
+#[test]
+fn test_cli_lines_reports_only_range() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("doc.md");
+    std::fs::write(&file, "# Doc\n\nOne  \nTwo  \nThree  \n").unwrap();
+    let path = file.to_str().unwrap();
+
+    let (code, stdout, _) = run_mkdlint(&["--no-color", "--lines", "4:5", path]);
+    assert_eq!(code, 1);
+    let lines: Vec<&str> = stdout.lines().filter(|l| l.contains("MD009")).collect();
+    assert_eq!(lines.len(), 2, "{stdout}");
+    assert!(
+        lines[0].contains(": 4: ") && lines[1].contains(": 5: "),
+        "{stdout}"
+    );
+
+    let other = dir.path().join("other.md");
+    std::fs::write(&other, "# Other\n").unwrap();
+    let (code, _, stderr) = run_mkdlint(&["--lines", "4:5", path, other.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    assert!(
+        stderr.contains("--lines needs exactly one input file"),
+        "{stderr}"
+    );
+
+    let (code, _, stderr) = run_mkdlint(&["--lines", "5:4", path]);
+    assert_eq!(code, 2);
+    assert!(stderr.contains("START <= END"), "{stderr}");
+}
+
+#[test]
+fn test_cli_list_files_verbose() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("docs/drafts")).unwrap();
+    std::fs::write(dir.path().join("README.md"), "# Readme\n").unwrap();
+    std::fs::write(dir.path().join("docs/guide.md"), "# Guide  \n").unwrap();
+    std::fs::write(dir.path().join("docs/drafts/wip.md"), "# WIP\n").unwrap();
+    std::fs::write(dir.path().join("docs/logo.svg"), "<svg/>\n").unwrap();
+
+    let output = Command::new(binary_path())
+        .args(["--list-files", "--verbose", "--ignore", "docs/drafts/**"])
+        .args(["README.md", "docs"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    // Listing never lints, so the trailing spaces don't fail the run
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(stdout, "README.md\ndocs/guide.md\n");
+    assert!(
+        stderr.contains("excluded: docs/drafts/wip.md (ignored by --ignore 'docs/drafts/**')"),
+        "stderr: {stderr}"
+    );
+    assert!(
+        stderr.contains("excluded: docs/logo.svg (not a markdown extension)"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn test_cli_explain_config_sources() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("base.json"),
+        r#"{"MD013": false, "MD033": false}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join(".markdownlint.json"),
+        r#"{"extends": "base.json", "preset": "github", "MD013": true}"#,
+    )
+    .unwrap();
+
+    let output = Command::new(binary_path())
+        .args(["--explain-config", "--no-color", "--disable", "MD009"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = |rule: &str| {
+        stdout
+            .lines()
+            .find(|line| line.split_whitespace().next() == Some(rule))
+            .unwrap_or_else(|| panic!("no {rule} line in: {stdout}"))
+            .split_whitespace()
+            .skip(1)
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    assert_eq!(line("MD013"), "enabled ./.markdownlint.json");
+    assert_eq!(line("MD033"), "disabled base.json");
+    assert_eq!(line("MD034"), "disabled preset github");
+    assert_eq!(line("MD009"), "disabled command line");
+    assert_eq!(line("MD001"), "enabled built-in default");
+}
+
+#[test]
+fn test_cli_discovers_package_json_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("doc.md");
+    std::fs::write(&file, "# Title\n\nText   \n").unwrap();
+    let file = file.to_str().unwrap();
+
+    let (code, stdout, _stderr) = run_mkdlint(&[file]);
+    assert_eq!(code, 1);
+    assert!(stdout.contains("MD009"));
+
+    std::fs::write(
+        dir.path().join("package.json"),
+        r#"{"name": "docs", "private": true, "markdownlint": {"MD009": false}}"#,
+    )
+    .unwrap();
+    let (code, stdout, _stderr) = run_mkdlint(&[file]);
+    assert_eq!(
+        code, 0,
+        "package.json config should disable MD009: {stdout}"
+    );
+}
+
 #[test]
 fn test_cli_multiple_files() {
     let dir = tempfile::tempdir().unwrap();
@@ -133,6 +262,59 @@ fn test_cli_nonexistent_file() {
     );
 }
 
+#[test]
+fn test_cli_binary_file_skipped_with_warning() {
+    let png = fixture_path("pixel.png");
+    let (code, stdout, stderr) = run_mkdlint(&[&png, &fixture_path("notes.txt")]);
+    assert_eq!(code, 1, "notes.txt has violations. stderr: {}", stderr);
+    assert!(stderr.contains(&format!("warning: {}: skipped: binary file", png)));
+    assert!(stdout.contains("notes.txt") && !stdout.contains("pixel.png"));
+
+    let (code, _, stderr) = run_mkdlint(&["--fix-dry-run", &png]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+}
+
+#[test]
+fn test_cli_config_migrate_normalizes_aliases() {
+    let dir = tempfile::tempdir().unwrap();
+    let from = dir.path().join(".markdownlint-cli2.jsonc");
+    std::fs::write(
+        &from,
+        r#"{
+  // Shared settings
+  "config": {"line-length": {"line_length": 100}, "no-bare-urls": false, "default": true}
+}"#,
+    )
+    .unwrap();
+    let to = dir.path().join(".markdownlint.yaml");
+
+    let (code, stdout, stderr) = run_mkdlint(&[
+        "config",
+        "migrate",
+        "--from",
+        from.to_str().unwrap(),
+        "--to",
+        to.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 0, "stderr: {}", stderr);
+    assert!(stdout.contains("line-length → MD013"), "stdout: {}", stdout);
+    assert_eq!(
+        std::fs::read_to_string(&to).unwrap(),
+        "default: true\n# Was 'line-length'\nMD013:\n  line_length: 100\n# Was 'no-bare-urls'\nMD034: false\n"
+    );
+
+    // The target is never overwritten
+    let (code, _, _) = run_mkdlint(&[
+        "config",
+        "migrate",
+        "--from",
+        from.to_str().unwrap(),
+        "--to",
+        to.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1);
+}
+
 #[test]
 fn test_cli_output_format() {
     let dir = tempfile::tempdir().unwrap();
@@ -243,6 +425,57 @@ fn test_fixture_json_output_format() {
         )
     });
     assert!(parsed.is_object(), "JSON root should be an object");
+    assert!(parsed.get("summary").is_none());
+}
+
+#[test]
+fn test_json_output_statistics_summary() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("doc.md");
+    std::fs::write(&file, "# Title\n\nTrailing   \n").unwrap();
+
+    let (code, stdout, _) = run_mkdlint(&["-o", "json", "--statistics", file.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let summary = &parsed["summary"];
+    assert_eq!(summary["errors"], 1);
+    assert_eq!(summary["fixable"], 1);
+    assert_eq!(summary["by_rule"]["MD009"], 1);
+    assert_eq!(summary["by_file"][file.to_str().unwrap()], 1);
+    assert!(
+        summary["rules_run"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r == "MD009")
+    );
+}
+
+#[test]
+fn test_github_output_paths_relative_to_root() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("docs")).unwrap();
+    let file = dir.path().join("docs").join("a.md");
+    std::fs::write(&file, "# Title\n\nTrailing   \n").unwrap();
+
+    // Relative to the current directory by default
+    let output = Command::new(binary_path())
+        .args(["-o", "github", "./docs/a.md"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("file=docs/a.md,"), "stdout: {}", stdout);
+
+    // An absolute path under --root
+    let (_, stdout, _) = run_mkdlint(&[
+        "-o",
+        "github",
+        "--root",
+        dir.path().to_str().unwrap(),
+        file.to_str().unwrap(),
+    ]);
+    assert!(stdout.contains("file=docs/a.md,"), "stdout: {}", stdout);
 }
 
 #[test]
@@ -292,6 +525,64 @@ fn test_fixture_fix_roundtrip() {
     );
 }
 
+#[test]
+fn test_report_unused_disable_fix_removes_only_useless_directive() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("doc.md");
+    std::fs::write(
+        &file,
+        "# Title\n\n<!-- markdownlint-disable-next-line MD009 -->\ntext  \n\n<!-- markdownlint-disable-next-line MD009 -->\nclean text\n",
+    )
+    .unwrap();
+
+    let (code, stdout, _) = run_mkdlint(&[
+        "--no-color",
+        "--report-unused-disable",
+        file.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1);
+    assert!(
+        stdout.contains("6: MKD006/inline-config-valid")
+            && stdout.contains("Unused disable directive"),
+        "{}",
+        stdout
+    );
+
+    let (code, _, _) = run_mkdlint(&["--fix", "--report-unused-disable", file.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert_eq!(
+        std::fs::read_to_string(&file).unwrap(),
+        "# Title\n\n<!-- markdownlint-disable-next-line MD009 -->\ntext  \n\nclean text\n"
+    );
+}
+
+#[test]
+fn test_directive_warnings_do_not_fail_the_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("doc.md");
+    std::fs::write(
+        &file,
+        "# Title\n\n<!-- markdownlint-disable MD013 MD999 -->\nShort line.\n",
+    )
+    .unwrap();
+
+    let (code, stdout, _) = run_mkdlint(&["--no-color", file.to_str().unwrap()]);
+    assert_eq!(code, 0, "{}", stdout);
+    assert!(stdout.contains("MKD006/inline-config-valid"), "{}", stdout);
+
+    // Configured as an error, MKD006 fails the run again
+    let config = dir.path().join("strict.json");
+    std::fs::write(&config, r#"{"MKD006": "error"}"#).unwrap();
+    let (code, stdout, _) = run_mkdlint(&[
+        "--no-color",
+        "--config",
+        config.to_str().unwrap(),
+        file.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1, "{}", stdout);
+    assert!(stdout.contains("MKD006/inline-config-valid"), "{}", stdout);
+}
+
 #[test]
 fn test_fixture_directory_recursion() {
     let dir = tempfile::tempdir().unwrap();
@@ -385,6 +676,36 @@ fn test_fix_dry_run_exits_zero_when_clean() {
     );
 }
 
+#[test]
+fn test_fix_dry_run_json_proposals() {
+    let (code, stdout, _) = run_mkdlint(&[
+        "--fix-dry-run",
+        "--output-format",
+        "json",
+        &fixture_path("fixable_errors.md"),
+        &fixture_path("clean.md"),
+    ]);
+    assert_eq!(code, 1, "Output: {}", stdout);
+
+    let proposals: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    let proposals = proposals.as_array().unwrap();
+    assert_eq!(
+        proposals.len(),
+        1,
+        "one proposal per fixable file: {}",
+        stdout
+    );
+    assert!(
+        proposals[0]["file"]
+            .as_str()
+            .unwrap()
+            .ends_with("fixable_errors.md")
+    );
+    assert!(!proposals[0]["fixes"].as_array().unwrap().is_empty());
+    let diff = proposals[0]["diff"].as_str().unwrap();
+    assert!(diff.contains("@@"), "diff should have hunks: {}", diff);
+}
+
 #[test]
 fn test_fix_dry_run_does_not_modify_files() {
     let dir = tempfile::tempdir().unwrap();
@@ -402,3 +723,657 @@ fn test_fix_dry_run_does_not_modify_files() {
         "--fix-dry-run must not modify files"
     );
 }
+
+#[test]
+fn test_fix_patch_output_writes_applicable_diff() {
+    let dir = tempfile::tempdir().unwrap();
+    let original = "# Title\n\nTrailing   \nAlso trailing \n";
+    std::fs::write(dir.path().join("doc.md"), original).unwrap();
+    std::fs::write(dir.path().join("clean.md"), "# Clean\n").unwrap();
+
+    let output = Command::new(binary_path())
+        .args(["--fix", "--patch-output", "out.patch", "doc.md", "clean.md"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Sources stay untouched; the patch holds one hunk for the fixed file
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("doc.md")).unwrap(),
+        original
+    );
+    let patch = std::fs::read_to_string(dir.path().join("out.patch")).unwrap();
+    assert!(patch.contains("--- a/doc.md\n+++ b/doc.md\n"), "{}", patch);
+    assert!(patch.contains("@@"), "{}", patch);
+    assert!(patch.contains("\n-Trailing   \n"), "{}", patch);
+    assert!(patch.contains("\n+Trailing\n"), "{}", patch);
+    assert!(!patch.contains("clean.md"), "{}", patch);
+
+    let applied = Command::new("patch")
+        .args(["-p1", "-i", "out.patch"])
+        .current_dir(dir.path())
+        .output();
+    let Ok(applied) = applied else {
+        eprintln!("patch not available; skipping");
+        return;
+    };
+    assert!(applied.status.success());
+
+    let output = Command::new(binary_path())
+        .arg("doc.md")
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "patched file should lint clean: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+#[test]
+fn test_ndjson_output_stream() {
+    use mkdlint::formatters::{NDJSON_SCHEMA_VERSION, NdjsonEvent};
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("clean.md"), "# Title\n\nText.\n").unwrap();
+    std::fs::write(dir.path().join("bad.md"), "# Title\n\nTrailing   \n").unwrap();
+
+    let (code, stdout, _) = run_mkdlint(&["-o", "ndjson", dir.path().to_str().unwrap()]);
+    assert_eq!(code, 1, "violations should exit 1. Output: {}", stdout);
+
+    let events: Vec<NdjsonEvent> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("every line is a valid event"))
+        .collect();
+
+    assert!(matches!(
+        events.first(),
+        Some(NdjsonEvent::Start { version, .. }) if *version == NDJSON_SCHEMA_VERSION
+    ));
+    assert!(matches!(
+        events.last(),
+        Some(NdjsonEvent::Summary {
+            files: 2,
+            errors: 1,
+            ..
+        })
+    ));
+
+    let file_starts = events
+        .iter()
+        .filter(|e| matches!(e, NdjsonEvent::FileStart { .. }))
+        .count();
+    assert_eq!(file_starts, 2);
+    assert!(events.iter().any(|e| matches!(
+        e,
+        NdjsonEvent::Violation { rule, line: 3, fixable: true, .. } if rule == "MD009"
+    )));
+    assert!(events.iter().any(|e| matches!(
+        e,
+        NdjsonEvent::FileEnd { path, errors: 1, .. } if path.ends_with("bad.md")
+    )));
+}
+
+#[test]
+fn test_ndjson_fix_emits_fixed_events() {
+    use mkdlint::formatters::NdjsonEvent;
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("bad.md");
+    std::fs::write(&file, "# Title\n\nTrailing   \n").unwrap();
+
+    let (_, stdout, _) = run_mkdlint(&["--fix", "-o", "ndjson", file.to_str().unwrap()]);
+    let events: Vec<NdjsonEvent> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("every line is a valid event"))
+        .collect();
+
+    assert!(matches!(events.first(), Some(NdjsonEvent::Start { .. })));
+    assert!(events.iter().any(|e| matches!(
+        e,
+        NdjsonEvent::Fixed { rules, fixes, .. }
+            if rules == &vec!["MD009".to_string()] && fixes.get("MD009") == Some(&1)
+    )));
+    assert_eq!(
+        std::fs::read_to_string(&file).unwrap(),
+        "# Title\n\nTrailing\n"
+    );
+}
+
+#[test]
+fn test_fix_reports_fixes_per_rule() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.md"), "# A\n\nOne  \nTwo  \nThree").unwrap();
+    std::fs::write(dir.path().join("b.md"), "# B\n\nText  \n").unwrap();
+    let run = |flag: &str| {
+        let output = Command::new(binary_path())
+            .args(["--no-color", flag, "a.md", "b.md"])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    let stdout = run("--fix-dry-run");
+    assert!(
+        stdout.contains("MD009 would fix 3 occurrence(s) across 2 file(s)"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("MD047 would fix 1 occurrence(s) across 1 file(s)"),
+        "{stdout}"
+    );
+
+    let stdout = run("--fix");
+    assert!(stdout.contains("2 file(s) fixed."), "{stdout}");
+    assert!(
+        stdout.contains("MD009 fixed 3 occurrence(s) across 2 file(s)"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("MD047 fixed 1 occurrence(s) across 1 file(s)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_jobs_one_matches_default() {
+    let fixtures = format!("{}/tests/fixtures", env!("CARGO_MANIFEST_DIR"));
+
+    let (default_code, default_out, _) = run_mkdlint(&["-o", "json", &fixtures]);
+    let (jobs_code, jobs_out, stderr) = run_mkdlint(&["--jobs", "1", "-o", "json", &fixtures]);
+
+    assert_eq!(jobs_code, default_code, "stderr: {}", stderr);
+    let default_json: serde_json::Value = serde_json::from_str(&default_out).unwrap();
+    let jobs_json: serde_json::Value = serde_json::from_str(&jobs_out).unwrap();
+    assert_eq!(jobs_json, default_json);
+}
+
+#[test]
+fn test_jobs_zero_rejected() {
+    let (code, _, stderr) = run_mkdlint(&["--jobs", "0", &fixture_path("clean.md")]);
+    assert_eq!(code, 2, "clap usage errors exit 2. stderr: {}", stderr);
+}
+
+/// Run git in `dir`, returning false if git is unavailable or fails
+fn git(dir: &std::path::Path, args: &[&str]) -> bool {
+    Command::new("git")
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(["-c", "commit.gpgsign=false"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
+#[test]
+fn test_changed_only_lints_changed_files() {
+    let dir = tempfile::tempdir().unwrap();
+    if !git(dir.path(), &["init", "-q"]) {
+        eprintln!("git not available; skipping");
+        return;
+    }
+    std::fs::write(dir.path().join("changed.md"), "# Changed\n").unwrap();
+    std::fs::write(
+        dir.path().join("untouched.md"),
+        "# Untouched\n\nTrailing   \n",
+    )
+    .unwrap();
+    assert!(git(dir.path(), &["add", "."]));
+    assert!(git(dir.path(), &["commit", "-q", "-m", "init"]));
+    std::fs::write(dir.path().join("changed.md"), "# Changed\n\nTrailing   \n").unwrap();
+
+    let output = Command::new(binary_path())
+        .args(["--changed-only", "-o", "json", "."])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let files: Vec<&str> = json["results"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(|k| k.as_str())
+        .collect();
+    assert_eq!(files, ["./changed.md"], "stdout: {}", stdout);
+
+    // Nothing differs from HEAD once the change is committed
+    assert!(git(dir.path(), &["commit", "-q", "-am", "change"]));
+    let output = Command::new(binary_path())
+        .args(["--changed-only=HEAD", "."])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No files to lint."));
+}
+
+#[test]
+fn test_changed_only_outside_git_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.md"), "# A\n").unwrap();
+
+    let output = Command::new(binary_path())
+        .args(["--changed-only", "a.md"])
+        .current_dir(dir.path())
+        .env("GIT_CEILING_DIRECTORIES", dir.path().parent().unwrap())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("not inside a git repository"),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_global_config_layered_under_project_config() {
+    let xdg = tempfile::tempdir().unwrap();
+    let global_dir = xdg.path().join("mkdlint");
+    std::fs::create_dir_all(&global_dir).unwrap();
+    std::fs::write(global_dir.join("config.yaml"), "MD013: false\n").unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let long_line = format!("# Title\n\n{}\n", "word ".repeat(40).trim_end());
+    std::fs::write(dir.path().join("doc.md"), long_line).unwrap();
+
+    let run = |args: &[&str]| {
+        let output = Command::new(binary_path())
+            .args(args)
+            .current_dir(dir.path())
+            .env("XDG_CONFIG_HOME", xdg.path())
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        (output.status.code(), stdout)
+    };
+
+    // The global config applies when there is no project config
+    let (code, stdout) = run(&["doc.md"]);
+    assert_eq!(code, Some(0), "stdout: {}", stdout);
+
+    // and is ignored with --no-global-config
+    let (code, stdout) = run(&["--no-global-config", "doc.md"]);
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("MD013"), "stdout: {}", stdout);
+
+    // A project config overrides it
+    std::fs::write(dir.path().join(".markdownlint.json"), r#"{"MD013": true}"#).unwrap();
+    let (code, stdout) = run(&["doc.md"]);
+    assert_eq!(code, Some(1));
+    assert!(stdout.contains("MD013"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_changed_ref_selects_added_and_modified_files() {
+    let dir = tempfile::tempdir().unwrap();
+    if !git(dir.path(), &["init", "-q"]) {
+        eprintln!("git not available; skipping");
+        return;
+    }
+    std::fs::create_dir(dir.path().join("docs")).unwrap();
+    for name in ["a.md", "gone.md", "keep.md"] {
+        std::fs::write(dir.path().join(name), "# Title\n").unwrap();
+    }
+    assert!(git(dir.path(), &["add", "."]));
+    assert!(git(dir.path(), &["commit", "-q", "-m", "init"]));
+    assert!(git(dir.path(), &["tag", "base"]));
+
+    std::fs::write(dir.path().join("a.md"), "# Title\n\nTrailing   \n").unwrap();
+    std::fs::write(dir.path().join("docs/new.md"), "# New\n\nTrailing   \n").unwrap();
+    std::fs::remove_file(dir.path().join("gone.md")).unwrap();
+    assert!(git(dir.path(), &["add", "-A"]));
+    assert!(git(dir.path(), &["commit", "-q", "-m", "change"]));
+
+    let changed_files_in = |cwd: &std::path::Path, args: &[&str]| -> Vec<String> {
+        let output = Command::new(binary_path())
+            .args(args)
+            .args(["-o", "json"])
+            .current_dir(cwd)
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+        let mut files: Vec<String> = json["results"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        files.sort();
+        files
+    };
+    let changed_files = |args: &[&str]| changed_files_in(dir.path(), args);
+
+    // Without paths the whole working directory is considered
+    assert_eq!(
+        changed_files(&["--changed", "base"]),
+        ["./a.md", "./docs/new.md"]
+    );
+    // Positional paths narrow the selection
+    assert_eq!(
+        changed_files(&["--changed", "base", "docs"]),
+        ["docs/new.md"]
+    );
+    // The diff covers the whole repository wherever mkdlint runs from
+    for flag in ["--changed=base", "--changed-only=base"] {
+        assert_eq!(
+            changed_files_in(&dir.path().join("docs"), &[flag, ".."]),
+            ["../a.md", "../docs/new.md"]
+        );
+    }
+
+    let output = Command::new(binary_path())
+        .args(["--changed", "no-such-ref"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("unknown git ref 'no-such-ref'"),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_front_matter_auto() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("hugo.md");
+    std::fs::write(
+        &file,
+        "+++\ntitle = \"Post\"\n+++\n\nIntro.\n\n## Section\n",
+    )
+    .unwrap();
+    let file = file.to_str().unwrap();
+
+    // Without front matter detection, line 1 is not a heading (MD041)
+    let (code, stdout, _) = run_mkdlint(&[file]);
+    assert_eq!(code, 1);
+    assert!(stdout.contains("MD041"), "stdout: {}", stdout);
+
+    // With it, the front matter title stands in for the H1 (MD025, MD041)
+    let (code, stdout, _) = run_mkdlint(&["--front-matter", "auto", file]);
+    assert_eq!(code, 0, "stdout: {}", stdout);
+}
+
+/// Run mkdlint in `dir` with `input` piped to stdin; returns (exit_code, stdout)
+fn run_mkdlint_stdin(dir: &std::path::Path, args: &[&str], input: &str) -> (i32, String) {
+    use std::io::Write;
+
+    let mut child = Command::new(binary_path())
+        .args(args)
+        .current_dir(dir)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .expect("Failed to execute mkdlint binary");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn test_stdin_filename_resolves_config_like_a_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("docs")).unwrap();
+    std::fs::write(
+        dir.path().join("docs/.markdownlint.json"),
+        r#"{"MD013": false}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join(".markdownlint.json"),
+        r#"{"MD009": {"files": ["guides/**"]}}"#,
+    )
+    .unwrap();
+    let input = format!("# Title\n\n{}  \n", "word ".repeat(20));
+    let lint = |args: &[&str]| run_mkdlint_stdin(dir.path(), args, &input);
+
+    // The docs/ config disables MD013 for content placed under docs/
+    let (code, stdout) = lint(&["--stdin", "--stdin-filename", "docs/x.md"]);
+    assert_eq!(code, 1);
+    assert!(!stdout.contains("MD013"), "stdout: {}", stdout);
+    assert!(stdout.contains("docs/x.md: 3: MD009"), "stdout: {}", stdout);
+
+    // Elsewhere the root config applies, which scopes MD009 to guides/
+    let (_, stdout) = lint(&["--stdin", "--stdin-filename", "x.md"]);
+    assert!(stdout.contains("MD013"), "stdout: {}", stdout);
+    assert!(!stdout.contains("MD009"), "stdout: {}", stdout);
+    let (_, stdout) = lint(&["--stdin", "--stdin-filename", "guides/x.md"]);
+    assert!(stdout.contains("MD009"), "stdout: {}", stdout);
+
+    // --ignore patterns match the stdin path too
+    let (code, stdout) = lint(&["--stdin", "--stdin-filename", "x.md", "--ignore", "*.md"]);
+    assert_eq!(code, 0, "stdout: {}", stdout);
+    assert!(!stdout.contains("MD013"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_stdin_filename_fix_and_fix_dry_run() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("docs")).unwrap();
+    std::fs::write(
+        dir.path().join("docs/.markdownlint.json"),
+        r#"{"MD009": false}"#,
+    )
+    .unwrap();
+    let input = "# Title\n\ntext  \n\n*  item\n";
+    let lint = |args: &[&str]| run_mkdlint_stdin(dir.path(), args, input);
+
+    // --fix prints the fixed content, with the docs/ config applied
+    let (code, stdout) = lint(&["--stdin", "--stdin-filename", "docs/x.md", "--fix"]);
+    assert_eq!(code, 0, "stdout: {}", stdout);
+    assert_eq!(stdout, "# Title\n\ntext  \n\n* item\n");
+
+    let (code, stdout) = lint(&["--stdin", "--stdin-filename", "docs/x.md", "--fix-dry-run"]);
+    assert_eq!(code, 1, "stdout: {}", stdout);
+    assert!(
+        stdout.contains("Would fix: docs/x.md"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("MD030"), "stdout: {}", stdout);
+    assert!(!stdout.contains("MD009"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_stdin_multi_lints_each_document() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = "# One\n\x0c\n# Two\n\n# Again\n<!-- mkdlint:file docs/b.md -->\nNo heading\n";
+    let lint = |args: &[&str]| run_mkdlint_stdin(dir.path(), args, input);
+
+    // Each document is checked on its own: one H1 each is fine, the second
+    // document's extra H1 and the third's missing one are reported
+    let (code, stdout) = lint(&["--stdin", "--stdin-multi", "--no-color"]);
+    assert_eq!(code, 1);
+    assert!(!stdout.contains("stdin:1"), "stdout: {}", stdout);
+    assert!(
+        stdout.contains("stdin:2: 3: MD025") && stdout.contains("[input line 5]"),
+        "stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("docs/b.md: 1: MD041"), "stdout: {}", stdout);
+
+    let (_, stdout) = lint(&["--stdin", "--stdin-multi", "--output-format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["line_offsets"]["stdin:2"], 2);
+    assert_eq!(json["line_offsets"]["docs/b.md"], 6);
+
+    // A custom marker; its capture group names the documents
+    let input = "=== a.md\n# A\n=== b.md\n# B\n# B again\n";
+    let (code, stdout) = run_mkdlint_stdin(
+        dir.path(),
+        &[
+            "--stdin",
+            "--stdin-multi",
+            "--stdin-separator",
+            r"^=== (\S+)$",
+        ],
+        input,
+    );
+    assert_eq!(code, 1);
+    assert!(stdout.contains("b.md: 2: MD025"), "stdout: {}", stdout);
+    assert!(!stdout.contains("a.md"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_severity_and_fixability_filters() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join(".markdownlint.json"),
+        r#"{"MD013": "warn"}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("doc.md"),
+        format!("# Title\n\n{}\n\ntext  \n", "word ".repeat(20).trim_end()),
+    )
+    .unwrap();
+    let lint = |args: &[&str]| {
+        let output = Command::new(binary_path())
+            .current_dir(dir.path())
+            .args(args)
+            .args(["--no-color", "doc.md"])
+            .output()
+            .unwrap();
+        (
+            output.status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&output.stdout).to_string(),
+        )
+    };
+
+    // MD013 is a warning: --severity error drops it from output and exit code
+    let (code, stdout) = lint(&["--severity", "error", "--output-format", "json"]);
+    assert_eq!(code, 1);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let rules: Vec<&str> = json["results"]["doc.md"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["rule_names"][0].as_str().unwrap())
+        .collect();
+    assert_eq!(rules, ["MD009"]);
+
+    std::fs::write(
+        dir.path().join("doc.md"),
+        format!("# Title\n\n{}\n", "word ".repeat(20).trim_end()),
+    )
+    .unwrap();
+    let (code, stdout) = lint(&["--severity", "error"]);
+    assert_eq!(code, 0, "stdout: {}", stdout);
+    assert!(!stdout.contains("MD013"), "stdout: {}", stdout);
+
+    // --only-fixable / --only-unfixable narrow the output but not the exit code
+    std::fs::write(
+        dir.path().join("doc.md"),
+        format!("# Title\n\n{}\n\ntext  \n", "word ".repeat(20).trim_end()),
+    )
+    .unwrap();
+    let (code, stdout) = lint(&["--only-fixable"]);
+    assert_eq!(code, 1);
+    assert!(stdout.contains("MD009"), "stdout: {}", stdout);
+    assert!(!stdout.contains("MD013"), "stdout: {}", stdout);
+    let (code, stdout) = lint(&["--only-unfixable", "--output-format", "sarif"]);
+    assert_eq!(code, 1);
+    assert!(stdout.contains("MD013"), "stdout: {}", stdout);
+    assert!(!stdout.contains("MD009"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_output_template() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("doc.md");
+    std::fs::write(&file, "# Title\n\ntext  \n").unwrap();
+    let file = file.to_str().unwrap();
+
+    // Vim errorformat: %f:%l:%c: %t%*[^:]: %m
+    let (code, stdout, _) = run_mkdlint(&[
+        "--output-template",
+        "{file}:{line}:{column}: {severity}: {rule} {message} [{detail}]",
+        file,
+    ]);
+    assert_eq!(code, 1);
+    assert_eq!(
+        stdout,
+        format!("{file}:3:5: error: MD009 Trailing spaces [Expected: 0; Actual: 2]\n")
+    );
+
+    // Unknown placeholders are rejected before linting
+    let (code, _, stderr) = run_mkdlint(&["--output-template", "{file}:{lnum}", file]);
+    assert_eq!(code, 2);
+    assert!(
+        stderr.contains("unknown placeholder '{lnum}'"),
+        "stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_enforce_budgets() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join(".markdownlint.json");
+    std::fs::write(&config, r#"{"budgets": {"MD009": 1, "MD012": 0}}"#).unwrap();
+    let file = dir.path().join("doc.md");
+    std::fs::write(&file, "# Title\n\none  \ntwo  \n").unwrap();
+    let config = config.to_str().unwrap();
+    let file = file.to_str().unwrap();
+
+    // MD009 reports two violations against a budget of one; MD012 has none
+    let (code, _, stderr) = run_mkdlint(&["--enforce-budgets", "--config", config, file]);
+    assert_eq!(code, 1);
+    assert!(
+        stderr.contains("MD009: 2 violation(s) exceed budget of 1"),
+        "stderr: {}",
+        stderr
+    );
+    assert!(!stderr.contains("MD012"), "stderr: {}", stderr);
+
+    // Within budget, remaining violations no longer fail the run
+    std::fs::write(file, "# Title\n\none  \ntwo\n").unwrap();
+    let (code, stdout, _) = run_mkdlint(&["--enforce-budgets", "--config", config, file]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("MD009"), "stdout: {}", stdout);
+}
+
+#[test]
+fn test_skip_generated_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join(".markdownlint.json");
+    std::fs::write(&config, r#"{"skip_generated": {}}"#).unwrap();
+    let generated = "# API\n\n<!-- AUTO-GENERATED, do not edit -->\ntrailing   \n";
+    let file = dir.path().join("api.md");
+    std::fs::write(&file, generated).unwrap();
+    let config = config.to_str().unwrap();
+    let file = file.to_str().unwrap();
+
+    let (code, _, stderr) = run_mkdlint(&["--verbose", "--config", config, file]);
+    assert_eq!(code, 0);
+    assert!(
+        stderr.contains(&format!("skipped (generated): {}", file)),
+        "stderr: {}",
+        stderr
+    );
+
+    let (code, _, _) = run_mkdlint(&["--fix", "--config", config, file]);
+    assert_eq!(code, 0);
+    assert_eq!(std::fs::read_to_string(file).unwrap(), generated);
+}
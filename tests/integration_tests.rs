@@ -149,6 +149,55 @@ fn test_multiple_rules_fire() {
     assert!(has_rule(&errors, "MD010"), "Expected MD010");
 }
 
+// ---- Code block awareness shared across rules ----
+
+/// An indented code block with trailing spaces, a tab, a long line, fake
+/// emphasis and code spans, and a lowercase proper name
+fn indented_code_block_doc() -> String {
+    format!(
+        "# Title\n\nSome text.\n\n    trailing   \n    a\tb\n    {}\n    * fake * and ` code ` in javascript\n\nDone.\n",
+        "x".repeat(100)
+    )
+}
+
+fn rule_lines(errors: &[mkdlint::LintError], rule_id: &str) -> Vec<usize> {
+    errors
+        .iter()
+        .filter(|e| e.rule_names[0] == rule_id)
+        .map(|e| e.line_number)
+        .collect()
+}
+
+#[test]
+fn test_indented_code_block_default_options() {
+    let errors = lint_string(&indented_code_block_doc());
+
+    // MD009 has no code block option; MD010 checks code blocks by default
+    assert_eq!(rule_lines(&errors, "MD009"), vec![5]);
+    assert_eq!(rule_lines(&errors, "MD010"), vec![6]);
+    // MD013 and MD044 skip code blocks by default; MD037/MD038 always do
+    for rule in ["MD013", "MD044", "MD037", "MD038"] {
+        assert!(rule_lines(&errors, rule).is_empty(), "{rule}: {errors:?}");
+    }
+}
+
+#[test]
+fn test_indented_code_block_options_flip() {
+    let json = r#"{
+        "MD010": { "code_blocks": false },
+        "MD013": { "code_blocks": true },
+        "MD044": { "code_blocks": true }
+    }"#;
+    let config: Config = serde_json::from_str(json).unwrap();
+    let errors = lint_string_with_config(&indented_code_block_doc(), config);
+
+    assert!(rule_lines(&errors, "MD010").is_empty(), "{errors:?}");
+    assert_eq!(rule_lines(&errors, "MD013"), vec![7]);
+    assert_eq!(rule_lines(&errors, "MD044"), vec![8]);
+    assert!(rule_lines(&errors, "MD037").is_empty());
+    assert!(rule_lines(&errors, "MD038").is_empty());
+}
+
 #[test]
 fn test_rule_disable_via_config() {
     // Disable MD009 (trailing whitespace) and verify it's not reported
@@ -314,6 +363,27 @@ fn test_lint_nonexistent_file() {
     );
 }
 
+#[test]
+fn test_lint_skips_binary_files_and_lints_text_with_any_extension() {
+    let fixture = |name: &str| format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let (png, txt) = (fixture("pixel.png"), fixture("notes.txt"));
+    let options = LintOptions {
+        files: vec![png.clone(), txt.clone()],
+        ..Default::default()
+    };
+
+    let results = lint_sync(&options).unwrap();
+    assert!(results.is_skipped_binary(&png) && results.skipped_binary.len() == 1);
+    assert_eq!(results.get(&png), Some(&[][..]));
+    let rules: Vec<&str> = results
+        .get(&txt)
+        .unwrap()
+        .iter()
+        .map(|e| e.rule_names[0])
+        .collect();
+    assert!(rules.contains(&"MD018") && rules.contains(&"MD009"));
+}
+
 #[test]
 fn test_error_has_line_number() {
     // MD009 should report a specific line number
@@ -598,6 +668,18 @@ fn test_inline_disable_file() {
     );
 }
 
+#[test]
+fn test_inline_directives_in_code_fence_are_examples() {
+    let markdown = "# Title\n\n```markdown\n<!-- markdownlint-disable MD009 -->\n<!-- markdownlint-enable MD009 -->\n<!-- markdownlint-disable -->\n```\n\nText   \n";
+    let errors = lint_string(markdown);
+    assert!(
+        errors
+            .iter()
+            .any(|e| e.rule_names.contains(&"MD009") && e.line_number == 9),
+        "MD009 should still fire after a fenced directive example: {errors:?}"
+    );
+}
+
 #[test]
 fn test_inline_disable_does_not_affect_other_rules() {
     let markdown = "# Title\n\n<!-- markdownlint-disable MD009 -->\nText   \n\ttab\n<!-- markdownlint-enable -->\n";
@@ -887,6 +969,25 @@ fn test_md019_fix_round_trip() {
     assert!(!has_rule(&errors_after, "MD019"), "Fixed: {:?}", fixed);
 }
 
+#[test]
+fn test_md019_tab_after_hash_fixed_to_space() {
+    // MD010 also replaces the tab; the fix passes still settle on one space
+    let content = "#\tTitle\n";
+    let errors = lint_string(content);
+    assert!(has_rule(&errors, "MD019"));
+    assert!(!has_rule(&errors, "MD018"));
+    let fixed = mkdlint::fix_content(content, &Config::default()).unwrap();
+    assert_eq!(fixed, "# Title\n");
+}
+
+#[test]
+fn test_md019_multi_space_fixed_to_space() {
+    let content = "#  Title\n";
+    assert!(!has_rule(&lint_string(content), "MD018"));
+    let fixed = mkdlint::fix_content(content, &Config::default()).unwrap();
+    assert_eq!(fixed, "# Title\n");
+}
+
 #[test]
 fn test_md020_no_space_closed() {
     let content = "#Title#\n";
@@ -1069,12 +1170,9 @@ fn test_md004_fix_round_trip() {
 
 #[test]
 fn test_md005_inconsistent_indent() {
-    // MD005 requires Micromark tokens with specific listUnordered structure.
-    // Verify no panic through lint_sync pipeline.
     let content = "# Title\n\n- Item a\n - Item b\n- Item c\n";
     let errors = lint_string(content);
-    // Token structure may vary; at minimum this is a no-panic smoke test.
-    let _ = errors;
+    assert!(has_rule(&errors, "MD005"));
 }
 
 #[test]
@@ -1120,14 +1218,49 @@ fn test_md029_fix_round_trip() {
     assert!(!has_rule(&errors_after, "MD029"), "Fixed: {:?}", fixed);
 }
 
+#[test]
+fn test_md030_md005_md007_fixes_keep_nested_unordered_list() {
+    // MD030 narrows item 2's spacing, moving its text to column 3; the
+    // nested bullets follow it and stay one list inside item 2
+    let content = "# Title\n\n1. one\n2.  two\n    - x\n     - y\n";
+    let errors = lint_string(content);
+    assert!(has_rule(&errors, "MD005"));
+    assert!(has_rule(&errors, "MD007"));
+    assert!(has_rule(&errors, "MD030"));
+
+    let fixed = mkdlint::fix_content(content, &Config::default()).unwrap();
+    assert_eq!(fixed, "# Title\n\n1. one\n2. two\n   - x\n   - y\n");
+    assert!(lint_string(&fixed).is_empty(), "Fixed: {:?}", fixed);
+
+    // Ordered and unordered lists nested in turn all move together
+    let content = "# Title\n\n1.  a\n    - b\n      1. c\n         - d\n";
+    let fixed = mkdlint::fix_content(content, &Config::default()).unwrap();
+    assert_eq!(fixed, "# Title\n\n1. a\n   - b\n     1. c\n        - d\n");
+    assert!(lint_string(&fixed).is_empty(), "Fixed: {:?}", fixed);
+}
+
 #[test]
 fn test_md030_extra_space() {
-    // MD030 requires Micromark tokens; use ordered list variant
     let content = "# Title\n\n1.  Two-space item\n";
     let errors = lint_string(content);
-    // MD030 may not fire through lint_sync if Micromark token structure differs
-    // from what the rule expects; this is a detection-only test
-    let _ = errors;
+    assert!(has_rule(&errors, "MD030"));
+}
+
+#[test]
+fn test_md005_md030_fixes_align_nested_list() {
+    // MD030 narrows the outer item's spacing and MD005 realigns the second
+    // nested item; the nested list follows the outer item's text
+    let content = "# Title\n\n1.   First\n     1. Sub one\n      2. Sub two\n2. Second\n";
+    let errors = lint_string(content);
+    assert!(has_rule(&errors, "MD005"));
+    assert!(has_rule(&errors, "MD030"));
+
+    let fixed = mkdlint::fix_content(content, &Config::default()).unwrap();
+    assert_eq!(
+        fixed,
+        "# Title\n\n1. First\n   1. Sub one\n   2. Sub two\n2. Second\n"
+    );
+    assert!(lint_string(&fixed).is_empty(), "Fixed: {:?}", fixed);
 }
 
 #[test]
@@ -1180,15 +1313,46 @@ fn test_md048_fix_round_trip() {
     assert!(!has_rule(&errors_after, "MD048"), "Fixed: {:?}", fixed);
 }
 
+#[test]
+fn test_fix_output_is_stable_across_runs() {
+    // MD009, MD010, and MD018 all fix line 1; MD031 and MD040 share line 3
+    let content = "#Heading\t with trailing  \n* item\t one  \n```\ncode\n```\nText\n";
+    let expected = mkdlint::fix_content(content, &Config::default()).unwrap();
+    for _ in 0..50 {
+        assert_eq!(
+            mkdlint::fix_content(content, &Config::default()).unwrap(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_md040_md048_fixes_converge_on_one_fence() {
+    let config: Config = serde_json::from_str(
+        r#"{"MD048": {"style": "backtick"}, "MD040": {"default_language": "text"}}"#,
+    )
+    .unwrap();
+    let content = "# Title\n\n~~~ \ncode\n~~~\n";
+    let fixed = mkdlint::fix_content(content, &config).unwrap();
+    assert_eq!(fixed, "# Title\n\n```text\ncode\n```\n");
+    // MD031 inserts a blank line at the same fence line
+    let content = "# Title\n\nPara\n~~~\ncode\n~~~\n";
+    let fixed = mkdlint::fix_content(content, &config).unwrap();
+    assert_eq!(fixed, "# Title\n\nPara\n\n```text\ncode\n```\n");
+}
+
 // ---- Link/reference rules (MD033, MD034, MD039, MD043, MD044, MD045, MD047, MD051, MD052, MD053) ----
 
 #[test]
 fn test_md033_inline_html() {
-    // MD033 requires Micromark htmlText tokens (inline HTML, not block htmlFlow).
-    // Verify no panic; detection depends on Micromark token structure.
-    let content = "# Title\n\nSome text with <b>bold</b> inline.\n";
+    let content = "# Title\n\nSome text with <b>bold</b> inline.\n\n<div>\nblock\n</div>\n";
     let errors = lint_string(content);
-    let _ = errors;
+    let lines: Vec<usize> = errors
+        .iter()
+        .filter(|e| e.rule_names[0] == "MD033")
+        .map(|e| e.line_number)
+        .collect();
+    assert_eq!(lines, [3, 5]);
 }
 
 #[test]
@@ -2229,6 +2393,66 @@ fn test_front_matter_extraction_with_pattern() {
     );
 }
 
+#[test]
+fn test_bom_with_front_matter_reports_only_bom() {
+    // TOML-style delimiters, since `---` around a single line parses as a
+    // setext heading
+    let markdown = "\u{FEFF}+++\r\nauthor = \"Test\"\r\n+++\r\n\r\n# Title\r\n\r\nText.\r\n";
+
+    let mut options = LintOptions::new();
+    options
+        .strings
+        .insert("test.md".to_string(), markdown.to_string());
+    options.front_matter = Some(r"^\+\+\+$".to_string());
+
+    let results = lint_sync(&options).unwrap();
+    let errors = results.get("test.md").unwrap();
+    let rules: Vec<_> = errors.iter().map(|e| e.rule_names[0]).collect();
+    assert_eq!(rules, vec!["MKD001"], "Errors: {:?}", errors);
+
+    // Fixing removes the BOM and nothing else
+    let fixed = apply_fixes(markdown, errors);
+    assert_eq!(fixed, &markdown[3..]);
+}
+
+#[test]
+fn test_line_ending_rule_normalizes_crlf_file() {
+    let markdown = "# Title\r\n\r\nText with a list:\r\n\r\n- item\r\n";
+    let config: Config = serde_json::from_str(r#"{"MKD002": {"line_ending": "lf"}}"#).unwrap();
+    let errors = lint_string_with_config(markdown, config);
+
+    let reported: Vec<_> = errors.iter().filter(|e| !e.fix_only).collect();
+    assert_eq!(reported.len(), 1, "Errors: {:?}", errors);
+    assert_eq!(reported[0].rule_names[0], "MKD002");
+    assert_eq!(reported[0].line_number, 1);
+
+    let fixed = apply_fixes(markdown, &errors);
+    assert_eq!(fixed, "# Title\n\nText with a list:\n\n- item\n");
+    assert!(lint_string(&fixed).is_empty());
+}
+
+#[test]
+fn test_inline_directive_aliases_and_unknown_names() {
+    let markdown = "# Title\n\n<!-- markdownlint-disable no-trailing-spaces,no-hard-tabs -->\ntrailing  \n\ttab\n<!-- markdownlint-enable MD009 md010 -->\n\n<!-- markdownlint-disable no-such-rule -->\ntrailing  \n";
+    let config: Config = serde_json::from_str(r#"{"MKD003": true}"#).unwrap();
+    let errors = lint_string_with_config(markdown, config);
+
+    let md009: Vec<usize> = errors
+        .iter()
+        .filter(|e| e.rule_names[0] == "MD009")
+        .map(|e| e.line_number)
+        .collect();
+    assert_eq!(md009, [9], "Errors: {:?}", errors);
+    assert!(!has_rule(&errors, "MD010"));
+
+    let unknown: Vec<_> = errors
+        .iter()
+        .filter(|e| has_rule(std::slice::from_ref(e), "MKD003"))
+        .collect();
+    assert_eq!(unknown.len(), 1);
+    assert_eq!(unknown[0].line_number, 8);
+}
+
 #[test]
 fn test_front_matter_no_extraction_by_default() {
     // When no pattern is provided, front matter is NOT extracted (opt-in only)
@@ -2274,6 +2498,8 @@ fn test_custom_rule_fires() {
                 error_range: None,
                 fix_info: None,
                 fix_only: false,
+                line_text: None,
+                related: None,
                 suggestion: None,
             }]
         }
@@ -2328,6 +2554,8 @@ fn test_custom_rule_respects_config() {
                 error_range: None,
                 fix_info: None,
                 fix_only: false,
+                line_text: None,
+                related: None,
                 suggestion: None,
             }]
         }
@@ -2351,3 +2579,55 @@ fn test_custom_rule_respects_config() {
         "Disabled custom rule should not fire"
     );
 }
+
+#[test]
+fn test_custom_rule_timeout_reported_once() {
+    use mkdlint::types::{LintError, ParserType, Rule, RuleParams, Severity};
+
+    struct SlowRule;
+    impl Rule for SlowRule {
+        fn names(&self) -> &'static [&'static str] {
+            &["CUSTOM003"]
+        }
+        fn description(&self) -> &'static str {
+            "Slow rule"
+        }
+        fn tags(&self) -> &[&'static str] {
+            &[]
+        }
+        fn parser_type(&self) -> ParserType {
+            ParserType::None
+        }
+        fn lint(&self, _params: &RuleParams) -> Vec<LintError> {
+            std::thread::sleep(std::time::Duration::from_millis(30));
+            Vec::new()
+        }
+    }
+
+    let mut options = LintOptions::new()
+        .with_custom_rule(Box::new(SlowRule))
+        .with_rule_timeout_ms(5);
+    for name in ["a.md", "b.md", "c.md"] {
+        options
+            .strings
+            .insert(name.to_string(), "# Test\n".to_string());
+    }
+
+    let results = lint_sync(&options).unwrap();
+    let timeouts: Vec<&LintError> = results
+        .results
+        .values()
+        .flatten()
+        .filter(|e| e.rule_names.contains(&"CUSTOM003"))
+        .collect();
+
+    assert_eq!(timeouts.len(), 1, "Timeout is reported once per run");
+    assert_eq!(timeouts[0].severity, Severity::Warning);
+    assert!(
+        timeouts[0]
+            .error_detail
+            .as_deref()
+            .unwrap()
+            .contains("timed out")
+    );
+}
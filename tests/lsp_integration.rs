@@ -256,6 +256,64 @@ async fn test_execute_fix_all_command() {
     assert!(result.is_ok());
 }
 
+async fn preview_fixes(server: &MkdlintLanguageServer, uri: &Url) -> serde_json::Value {
+    server
+        .execute_command(ExecuteCommandParams {
+            command: "mkdlint.previewFixes".to_string(),
+            arguments: vec![serde_json::to_value(uri).unwrap()],
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        })
+        .await
+        .unwrap()
+        .expect("previewFixes returns a result")
+}
+
+#[tokio::test]
+async fn test_execute_preview_fixes_command() {
+    let server = create_test_server().await;
+    server
+        .initialize(InitializeParams::default())
+        .await
+        .unwrap();
+    server.initialized(InitializedParams {}).await;
+
+    // MD009 (trailing spaces) and MD047 (no final newline)
+    let uri = Url::parse("file:///preview.md").unwrap();
+    open_doc(&server, &uri, "# Title\n\nSome text   \nLast line").await;
+
+    let preview = preview_fixes(&server, &uri).await;
+    assert_eq!(preview["fixable"], 2);
+    let diff = preview["diff"].as_str().unwrap();
+    assert!(
+        diff.starts_with("--- a/preview.md\n+++ b/preview.md\n"),
+        "{diff}"
+    );
+    assert!(diff.contains("-Some text   \n"), "{diff}");
+    assert!(diff.contains("+Some text\n"), "{diff}");
+    assert!(diff.contains("+Last line\n"), "{diff}");
+
+    // The document is left untouched, so a second preview is identical
+    assert_eq!(preview_fixes(&server, &uri).await, preview);
+}
+
+#[tokio::test]
+async fn test_execute_preview_fixes_clean_document() {
+    let server = create_test_server().await;
+    server
+        .initialize(InitializeParams::default())
+        .await
+        .unwrap();
+    server.initialized(InitializedParams {}).await;
+
+    let uri = Url::parse("file:///clean.md").unwrap();
+    open_doc(&server, &uri, "# Title\n\nSome text\n").await;
+
+    let preview = preview_fixes(&server, &uri).await;
+    assert_eq!(preview["fixable"], 0);
+    assert_eq!(preview["diff"], "");
+    assert_eq!(preview["message"], "No fixable issues");
+}
+
 #[tokio::test]
 async fn test_workspace_roots_from_initialize() {
     let server = create_test_server().await;
@@ -820,6 +878,66 @@ async fn test_document_symbol_skips_code_blocks() {
     }
 }
 
+#[tokio::test]
+async fn test_document_symbol_definitions_container() {
+    let server = create_test_server().await;
+
+    server
+        .initialize(InitializeParams::default())
+        .await
+        .unwrap();
+    server.initialized(InitializedParams {}).await;
+
+    let uri = Url::parse("file:///test.md").unwrap();
+
+    server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.clone(),
+                language_id: "markdown".to_string(),
+                version: 1,
+                text: "# Title\n\nSee [docs][d].[^1]\n\n## Links\n\n[d]: https://example.com\n[^1]: A note.\n"
+                    .to_string(),
+            },
+        })
+        .await;
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let result = server
+        .document_symbol(DocumentSymbolParams {
+            text_document: TextDocumentIdentifier { uri },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        })
+        .await
+        .unwrap();
+
+    match result.unwrap() {
+        DocumentSymbolResponse::Nested(symbols) => {
+            assert_eq!(symbols.len(), 2);
+            assert_eq!(symbols[0].name, "Title");
+            assert_eq!(symbols[0].children.as_ref().unwrap()[0].name, "Links");
+
+            let definitions = &symbols[1];
+            assert_eq!(definitions.name, "Definitions");
+            let children = definitions.children.as_ref().unwrap();
+            let names: Vec<&str> = children.iter().map(|c| c.name.as_str()).collect();
+            assert_eq!(names, ["d", "^1"]);
+            assert_eq!(children[0].kind, SymbolKind::CONSTANT);
+            assert_eq!(children[0].detail.as_deref(), Some("https://example.com"));
+
+            // Headings end where the container starts, children stay inside it
+            assert!(symbols[0].range.end <= definitions.range.start);
+            for child in children {
+                assert!(child.range.start >= definitions.range.start);
+                assert!(child.range.end <= definitions.range.end);
+            }
+        }
+        _ => panic!("Expected nested document symbols"),
+    }
+}
+
 #[tokio::test]
 async fn test_document_symbol_detail_shows_level() {
     let server = create_test_server().await;
@@ -1516,6 +1634,76 @@ async fn test_formatting_returns_none_for_clean_document() {
     );
 }
 
+// ---------------------------------------------------------------------------
+// Fix-on-save (willSaveWaitUntil) tests
+// ---------------------------------------------------------------------------
+
+fn will_save_params(uri: &Url) -> WillSaveTextDocumentParams {
+    WillSaveTextDocumentParams {
+        text_document: TextDocumentIdentifier { uri: uri.clone() },
+        reason: TextDocumentSaveReason::MANUAL,
+    }
+}
+
+#[tokio::test]
+async fn test_capabilities_include_will_save_wait_until() {
+    let server = create_test_server().await;
+    let result = server
+        .initialize(InitializeParams::default())
+        .await
+        .unwrap();
+    match result.capabilities.text_document_sync {
+        Some(TextDocumentSyncCapability::Options(options)) => {
+            assert_eq!(options.will_save_wait_until, Some(true));
+            assert_eq!(options.change, Some(TextDocumentSyncKind::FULL));
+        }
+        other => panic!("Expected sync options, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_will_save_wait_until_disabled_by_default() {
+    let server = create_test_server().await;
+    server
+        .initialize(InitializeParams::default())
+        .await
+        .unwrap();
+
+    let uri = Url::parse("file:///test.md").unwrap();
+    open_doc(&server, &uri, "# Title\n\nTrailing spaces   \n").await;
+
+    let edits = server
+        .will_save_wait_until(will_save_params(&uri))
+        .await
+        .unwrap();
+    assert_eq!(edits, Some(vec![]), "fixOnSave is off by default");
+}
+
+#[tokio::test]
+async fn test_will_save_wait_until_returns_fix_edits() {
+    let server = create_test_server().await;
+    server
+        .initialize(InitializeParams {
+            initialization_options: Some(serde_json::json!({ "fixOnSave": true })),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let uri = Url::parse("file:///test.md").unwrap();
+    open_doc(&server, &uri, "# Title\n\nTrailing spaces   \n").await;
+
+    let edits = server
+        .will_save_wait_until(will_save_params(&uri))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(edits.len(), 1, "Only the changed line is edited");
+    assert_eq!(edits[0].range.start, Position::new(2, 0));
+    assert_eq!(edits[0].range.end, Position::new(3, 0));
+    assert_eq!(edits[0].new_text, "Trailing spaces\n");
+}
+
 // ---------------------------------------------------------------------------
 // Folding range tests
 // ---------------------------------------------------------------------------
@@ -368,6 +368,11 @@ proptest! {
             default: default_val,
             extends: None,
             preset: None,
+            budgets: HashMap::new(),
+            skip_generated: None,
+            foreign_directives: HashMap::new(),
+            math: None,
+            no_fix: Vec::new(),
             rules,
         };
 
@@ -0,0 +1,156 @@
+//! Public API surface tests
+//!
+//! The prelude and the crate root are the supported API. These tests pin
+//! both, so adding or removing an item is a deliberate change that updates
+//! the lists below (and the CHANGELOG) rather than an accident.
+
+use mkdlint::prelude::*;
+use std::collections::HashMap;
+
+/// `pub` items declared at the top level of a source file, in order
+fn top_level_pub_items(source: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut pending: Option<String> = None;
+    for line in source.lines() {
+        if let Some(item) = pending.as_mut() {
+            item.push(' ');
+            item.push_str(line.trim());
+        } else if line.starts_with("pub ") {
+            pending = Some(line.to_string());
+        }
+        // `use` lists may span lines; other items end at their opening brace
+        let complete = |item: &mut String| {
+            item.ends_with(';') || (!item.starts_with("pub use") && item.ends_with('{'))
+        };
+        if let Some(item) = pending.take_if(complete) {
+            let item = item.trim_end_matches(['{', ' ']).to_string();
+            items.push(item.replace("{ ", "{").replace(", }", "}"));
+        }
+    }
+    items
+}
+
+#[test]
+fn test_crate_root_surface() {
+    assert_eq!(
+        top_level_pub_items(include_str!("../src/lib.rs")),
+        [
+            "pub mod config;",
+            "pub mod formatters;",
+            "pub mod helpers;",
+            "pub mod lint;",
+            "pub mod parser;",
+            "pub mod prelude;",
+            "pub mod rules;",
+            "pub mod types;",
+            "pub mod lsp;",
+            "pub use config::{Config, ConfigParser, RuleConfig};",
+            "pub use lint::{apply_fixes, build_workspace_headings, fix_content, lint_string, lint_sync, lint_sync_streaming};",
+            "pub use types::{LintError, LintOptions, LintResults, Rule, RuleParams};",
+            "pub use lint::lint_async;",
+            "pub const VERSION: &str = env!(\"CARGO_PKG_VERSION\");",
+            "pub fn version() -> &'static str",
+        ]
+    );
+}
+
+#[test]
+fn test_prelude_surface() {
+    assert_eq!(
+        top_level_pub_items(include_str!("../src/prelude.rs")),
+        [
+            "pub use crate::config::{Config, RuleConfig};",
+            "pub use crate::formatters::{format_github, format_json, format_ndjson, format_sarif, format_text, format_text_with_context};",
+            "pub use crate::lint::{apply_fixes, fix_content, lint_string, lint_sync, lint_sync_streaming};",
+            "pub use crate::types::{FixInfo, LintError, LintOptions, LintResults, MarkdownlintError, ParserType, Rule, RuleParams, Severity};",
+            "pub use crate::lint::lint_async;",
+        ]
+    );
+}
+
+/// Signatures of the prelude functions; a change here is a breaking change
+#[test]
+fn test_prelude_signatures() {
+    type LintResult<T> = Result<T, MarkdownlintError>;
+
+    let _: fn(&str, &Config) -> LintResult<Vec<LintError>> = lint_string;
+    let _: fn(&str, &Config) -> LintResult<String> = fix_content;
+    let _: fn(&LintOptions) -> LintResult<LintResults> = lint_sync;
+    let _: fn(&str, &[LintError]) -> String = apply_fixes;
+    let _: fn(&LintResults) -> String = format_text;
+    let _: fn(&LintResults, &HashMap<String, String>) -> String = format_text_with_context;
+    let _: fn(&LintResults) -> String = format_json;
+    let _: fn(&LintResults) -> String = format_sarif;
+    let _: fn(&LintResults) -> String = format_github;
+    let _: fn(&LintResults) -> String = format_ndjson;
+}
+
+#[test]
+fn test_prelude_lint_and_fix() {
+    let config = Config::default();
+    let errors = lint_string("# Title\n\nTrailing  \n", &config).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].rule_names[0], "MD009");
+    assert_eq!(errors[0].severity, Severity::Error);
+
+    let fixed = fix_content("# Title\n\nTrailing  \n", &config).unwrap();
+    assert_eq!(fixed, "# Title\n\nTrailing\n");
+    assert!(lint_string(&fixed, &config).unwrap().is_empty());
+}
+
+/// A custom rule can be written against the prelude alone
+#[test]
+fn test_prelude_custom_rule() {
+    struct NoTodo;
+
+    impl Rule for NoTodo {
+        fn names(&self) -> &'static [&'static str] {
+            &["CUSTOM001", "no-todo"]
+        }
+
+        fn description(&self) -> &'static str {
+            "No TODO markers"
+        }
+
+        fn tags(&self) -> &[&'static str] {
+            &["custom"]
+        }
+
+        fn parser_type(&self) -> ParserType {
+            ParserType::None
+        }
+
+        fn lint(&self, params: &RuleParams) -> Vec<LintError> {
+            params
+                .lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| line.contains("TODO"))
+                .map(|(i, _)| LintError {
+                    line_number: i + 1,
+                    rule_names: self.names(),
+                    rule_description: self.description(),
+                    fix_info: Some(FixInfo {
+                        line_number: None,
+                        edit_column: Some(1),
+                        delete_count: Some(4),
+                        insert_text: Some("Done".to_string()),
+                    }),
+                    ..Default::default()
+                })
+                .collect()
+        }
+    }
+
+    let options = LintOptions::new()
+        .with_string("a.md", "# Title\n\nTODO\n")
+        .with_custom_rule(Box::new(NoTodo));
+    let results = lint_sync(&options).unwrap();
+    let errors = results.get("a.md").unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line_number, 3);
+    assert_eq!(
+        apply_fixes("# Title\n\nTODO\n", errors),
+        "# Title\n\nDone\n"
+    );
+}
@@ -17,8 +17,9 @@ fn lint_snapshot(markdown: &str) -> String {
     let results = lint_sync(&options).unwrap();
     let errors = results.get("test.md").unwrap_or(&[]);
 
+    // Fix-only entries are internal edits, never shown to users
     let mut lines = Vec::new();
-    for e in errors {
+    for e in errors.iter().filter(|e| !e.fix_only) {
         let mut line = format!(
             "test.md:{}: {} {}",
             e.line_number,